@@ -0,0 +1,67 @@
+// ============================================================
+//  Pro-ration rounding
+//
+//  Splitting a premium by time or by share leaves a fraction of a
+//  base unit that somebody has to keep. The rule here is that it
+//  is always the customer:
+//    • a charge is rounded down — what a customer owes for part
+//      of a term, or for one installment of it
+//    • a refund is rounded up — what a customer gets back
+//  Each is within one base unit of the exact amount.
+//
+//  Installments are cut from the cumulative pro-rata amount, not
+//  one by one: after k of n installments the customer has paid
+//  exactly ⌊total·k/n⌋, so rounding never builds up across them
+//  and the last one brings the total to the premium exactly.
+//  Cancellation refunds take what was paid less what was earned,
+//  both cumulative, for the same reason.
+// ============================================================
+
+use crate::money::Ralo;
+
+pub const FULL_BPS: u64 = 10_000;
+
+// `part / whole` of `amount`, rounded down; all of it when `whole` is zero
+pub fn charge_share(amount: Ralo, part: u64, whole: u64) -> Ralo {
+    if whole == 0 {
+        return amount;
+    }
+    let part = part.min(whole) as u128;
+    Ralo((amount.0 as u128 * part / whole as u128) as u64)
+}
+
+// `part / whole` of `amount`, rounded up; all of it when `whole` is zero
+pub fn refund_share(amount: Ralo, part: u64, whole: u64) -> Ralo {
+    if whole == 0 {
+        return amount;
+    }
+    let part = part.min(whole) as u128;
+    Ralo((amount.0 as u128 * part).div_ceil(whole as u128) as u64)
+}
+
+// Refund of `bps` of `amount`, rounded up
+pub fn refund_bps(amount: Ralo, bps: u64) -> Ralo {
+    refund_share(amount, bps, FULL_BPS)
+}
+
+// Installment `index` (0-based) of `total` split `count` ways
+pub fn installment(total: Ralo, index: u32, count: u32) -> Ralo {
+    let count = count.max(1);
+    let index = index.min(count - 1);
+    charge_share(total, index as u64 + 1, count as u64) - charge_share(total, index as u64, count as u64)
+}
+
+// Every installment of `total` split `count` ways, in order
+pub fn installments(total: Ralo, count: u32) -> Vec<Ralo> {
+    (0..count.max(1)).map(|index| installment(total, index, count)).collect()
+}
+
+// Premium earned `elapsed_secs` into a `term_secs` term, rounded down
+pub fn earned_premium(premium: Ralo, elapsed_secs: i64, term_secs: i64) -> Ralo {
+    charge_share(premium, elapsed_secs.max(0) as u64, term_secs.max(0) as u64)
+}
+
+// Refund on cancelling `elapsed_secs` into the term: what was paid beyond what was earned
+pub fn cancellation_refund(premium: Ralo, paid: Ralo, elapsed_secs: i64, term_secs: i64) -> Ralo {
+    paid.saturating_sub(earned_premium(premium, elapsed_secs, term_secs))
+}
@@ -0,0 +1,54 @@
+// ============================================================
+//  Weather cache
+//
+//  Policies in the same city read the same weather. The first
+//  check for a (provider, canonical location) in an hourly
+//  measurement window pays for the HTTP call; every other policy
+//  checked in that window reuses the stored observation. Entries
+//  from earlier windows are pruned on insert, so the cache never
+//  holds more than one window's worth of cities.
+// ============================================================
+
+use serde::{Deserialize, Serialize};
+
+use crate::normalization::Observation;
+
+const WINDOW_SECS: i64 = 60 * 60;   // matches the provider's `rain.1h` reading
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CachedObservation {
+    pub window:      i64,      // measurement window (hour since epoch)
+    pub source:      String,   // provider base URL the reading came from
+    pub location:    String,   // canonical location
+    pub observation: Observation,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct WeatherCache {
+    pub entries: Vec<CachedObservation>,
+}
+
+impl WeatherCache {
+    pub fn window(now: i64) -> i64 {
+        now.div_euclid(WINDOW_SECS)
+    }
+
+    pub fn get(&self, source: &str, location: &str, now: i64) -> Option<Observation> {
+        let window = Self::window(now);
+        self.entries
+            .iter()
+            .find(|e| e.window == window && e.source == source && e.location == location)
+            .map(|e| e.observation.clone())
+    }
+
+    pub fn insert(&mut self, source: &str, location: &str, now: i64, observation: Observation) {
+        let window = Self::window(now);
+        self.entries.retain(|e| e.window == window && !(e.source == source && e.location == location));
+        self.entries.push(CachedObservation {
+            window,
+            source:   source.to_string(),
+            location: location.to_string(),
+            observation,
+        });
+    }
+}
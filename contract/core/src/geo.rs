@@ -0,0 +1,192 @@
+// ============================================================
+//  Great-circle distance
+//
+//  Radius-based perils ("storm passed within X km of the insured
+//  point") need the same distance on every validator, so this uses
+//  integer fixed-point only — no `f64` trig whose last bits can
+//  differ between platforms:
+//    • coordinates are micro-degrees
+//    • angles are radians scaled by 10¹⁸, so even metre-scale
+//      distances keep plenty of significant digits
+//    • sin is a Taylor series after range reduction; asin is found
+//      by bisection on sin, so there is only one primitive to vet
+//  Agrees with double-precision haversine to well under a metre.
+//  Also encodes points as geohashes, the coarse public buckets
+//  exposure is reported by.
+// ============================================================
+
+use serde::{Deserialize, Serialize};
+
+// Fixed-point unit: 1.0 == SCALE
+const SCALE: i128 = 1_000_000_000_000_000_000;
+
+const PI:      i128 = 3_141_592_653_589_793_238;   // π × 10¹⁸
+const HALF_PI: i128 = PI / 2;
+const TWO_PI:  i128 = PI * 2;
+
+// Mean Earth radius (IUGG), metres
+const EARTH_RADIUS_M: i128 = 6_371_009;
+
+// Distances come back in thousandths of a km
+pub const KM_SCALE: u64 = 1_000;
+
+const GEOHASH_ALPHABET: &[u8; 32] = b"0123456789bcdefghjkmnpqrstuvwxyz";
+pub const MAX_GEOHASH_LEN: usize = 12;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GeoPoint {
+    pub lat_e6: i32,   // latitude, micro-degrees (+ north)
+    pub lon_e6: i32,   // longitude, micro-degrees (+ east)
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CoordinateError {
+    LatitudeOutOfRange,
+    LongitudeOutOfRange,
+}
+
+impl CoordinateError {
+    pub fn message(&self) -> &'static str {
+        match self {
+            CoordinateError::LatitudeOutOfRange  => "Latitude must be within ±90°.",
+            CoordinateError::LongitudeOutOfRange => "Longitude must be within ±180°.",
+        }
+    }
+}
+
+impl GeoPoint {
+    pub fn new(lat_e6: i32, lon_e6: i32) -> Result<Self, CoordinateError> {
+        if !(-90_000_000..=90_000_000).contains(&lat_e6) {
+            return Err(CoordinateError::LatitudeOutOfRange);
+        }
+        if !(-180_000_000..=180_000_000).contains(&lon_e6) {
+            return Err(CoordinateError::LongitudeOutOfRange);
+        }
+        Ok(GeoPoint { lat_e6, lon_e6 })
+    }
+
+    // Nearest micro-degree point to decimal degrees, if they are on the globe
+    pub fn from_degrees(lat: f64, lon: f64) -> Option<Self> {
+        GeoPoint::new((lat * 1e6).round() as i32, (lon * 1e6).round() as i32).ok()
+    }
+}
+
+fn mul(a: i128, b: i128) -> i128 {
+    a * b / SCALE
+}
+
+fn radians(micro_degrees: i32) -> i128 {
+    micro_degrees as i128 * PI / 180_000_000
+}
+
+// sin of a fixed-point angle, any range
+fn sin(x: i128) -> i128 {
+    // Reduce to [-π, π], then fold onto [-π/2, π/2] where the series converges fast
+    let mut x = x % TWO_PI;
+    if x > PI {
+        x -= TWO_PI;
+    } else if x < -PI {
+        x += TWO_PI;
+    }
+    if x > HALF_PI {
+        x = PI - x;
+    } else if x < -HALF_PI {
+        x = -PI - x;
+    }
+
+    // Terms through x¹⁹/19!: truncation error below 10⁻¹⁵ on this range
+    let x2 = mul(x, x);
+    let mut term = x;
+    let mut sum = x;
+    for n in 1..=9 {
+        term = -mul(term, x2) / ((2 * n) * (2 * n + 1));
+        sum += term;
+    }
+    sum
+}
+
+fn cos(x: i128) -> i128 {
+    sin(x + HALF_PI)
+}
+
+// asin on [0, 1], by bisection over [0, π/2]
+fn asin(y: i128) -> i128 {
+    let (mut lo, mut hi) = (0, HALF_PI);
+    while hi - lo > 1 {
+        let mid = (lo + hi) / 2;
+        if sin(mid) < y {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    lo
+}
+
+fn isqrt(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+    let mut x = n;
+    let mut y = x.div_ceil(2);
+    while y < x {
+        x = y;
+        y = (x + n / x) / 2;
+    }
+    x
+}
+
+// Great-circle distance in thousandths of a km (KM_SCALE)
+pub fn haversine_km(a: &GeoPoint, b: &GeoPoint) -> u64 {
+    let (lat1, lat2) = (radians(a.lat_e6), radians(b.lat_e6));
+    let d_lat = lat2 - lat1;
+    let d_lon = radians(b.lon_e6) - radians(a.lon_e6);
+
+    let s_lat = sin(d_lat / 2);
+    let s_lon = sin(d_lon / 2);
+    let h = mul(s_lat, s_lat) + mul(mul(cos(lat1), cos(lat2)), mul(s_lon, s_lon));
+    let h = h.clamp(0, SCALE);
+
+    let root = isqrt((h * SCALE) as u128) as i128;
+    let central_angle = 2 * asin(root);
+
+    (central_angle * EARTH_RADIUS_M / SCALE) as u64
+}
+
+// Whether `b` lies within `radius_km` whole km of `a`
+pub fn within_km(a: &GeoPoint, b: &GeoPoint, radius_km: u64) -> bool {
+    haversine_km(a, b) <= radius_km.saturating_mul(KM_SCALE)
+}
+
+// Standard base-32 geohash of a point, `precision` characters long.
+// Bisection runs on micro-degrees scaled by 2³⁰ so every midpoint is exact.
+pub fn geohash(point: &GeoPoint, precision: usize) -> String {
+    const SHIFT: u32 = 30;
+    let mut lat = ((-90_000_000_i64) << SHIFT, 90_000_000_i64 << SHIFT);
+    let mut lon = ((-180_000_000_i64) << SHIFT, 180_000_000_i64 << SHIFT);
+    let (lat_v, lon_v) = ((point.lat_e6 as i64) << SHIFT, (point.lon_e6 as i64) << SHIFT);
+
+    let mut hash = String::with_capacity(precision);
+    let mut even = true;   // bits alternate, longitude first
+    for _ in 0..precision.min(MAX_GEOHASH_LEN) {
+        let mut index = 0;
+        for _ in 0..5 {
+            let (range, value) = if even { (&mut lon, lon_v) } else { (&mut lat, lat_v) };
+            let mid = (range.0 + range.1) / 2;
+            index <<= 1;
+            if value >= mid {
+                index |= 1;
+                range.0 = mid;
+            } else {
+                range.1 = mid;
+            }
+            even = !even;
+        }
+        hash.push(GEOHASH_ALPHABET[index] as char);
+    }
+    hash
+}
+
+pub fn is_geohash(hash: &str) -> bool {
+    !hash.is_empty() && hash.len() <= MAX_GEOHASH_LEN && hash.bytes().all(|b| GEOHASH_ALPHABET.contains(&b))
+}
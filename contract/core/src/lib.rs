@@ -0,0 +1,20 @@
+// ============================================================
+//  Weather insurance core
+//
+//  Everything about a policy that can be worked out without a
+//  chain: token amounts and their rounding, rainfall units,
+//  locations and observations, great-circle distance and
+//  geohashes, the lifecycle states a policy moves through and the
+//  streaks rain-day cover counts. Nothing here touches the SDK,
+//  state or the network, so the same arithmetic the contract
+//  settles on is available to anything that links this crate.
+// ============================================================
+
+pub mod actuarial;
+pub mod cache;
+pub mod geo;
+pub mod millimeters;
+pub mod money;
+pub mod normalization;
+pub mod status;
+pub mod streak;
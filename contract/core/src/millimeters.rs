@@ -0,0 +1,166 @@
+// ============================================================
+//  Fixed-point rainfall
+//
+//  Every rain amount the contract stores, adds up or triggers on
+//  is `Millimeters`: a count of hundredths of a mm, the finest
+//  resolution any provider reports. Floating point stays outside
+//  the settlement logic — a provider's f64 is converted once, where
+//  its response is parsed, and from there totals and comparisons
+//  are integer arithmetic every validator agrees on.
+//
+//  Conversion rounds the decimal the provider wrote, to the
+//  nearest hundredth with halves rounded up, not the binary
+//  approximation serde handed over: "1.005" is 1.01 mm, where
+//  `(1.005 * 100.0).round()` would give 1.00. Negative and NaN
+//  readings are no rain.
+//
+//  Thresholds are written in the peril's own unit, which for
+//  temperatures can be negative, so they are `Hundredths`: the
+//  same scale, signed. A reading leaves its `Observation` as one
+//  and is carried that way through settlement: the receipt and its
+//  hash, the outbox, check records, and payouts held for an
+//  attestation, dispute or FX retry. Everything it is compared
+//  with — policy, template and network thresholds, curve tiers,
+//  bundled perils and conditions — is held on the same scale.
+//
+//  Still f64: the products' own internals (degree-day and exposure
+//  totals, the smoothed statistic, storm distances, air quality),
+//  arbiter, manual and setup inputs until they are converted, and
+//  configured tolerances, which are converted where they're applied.
+// ============================================================
+
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign};
+
+use serde::{Deserialize, Serialize};
+
+pub const HUNDREDTHS_PER_MM: u64 = 100;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(transparent)]
+pub struct Millimeters(pub u64);   // hundredths of a mm
+
+impl Millimeters {
+    pub const ZERO: Millimeters = Millimeters(0);
+
+    // Nearest hundredth of a provider's reading in mm
+    pub fn from_mm(mm: f64) -> Self {
+        if mm.is_nan() || mm <= 0.0 {
+            return Millimeters::ZERO;
+        }
+        Millimeters(magnitude_hundredths(mm).min(u64::MAX as u128) as u64)
+    }
+
+    // For display and for the f64 paths shared with other perils
+    pub fn to_mm(self) -> f64 {
+        self.0 as f64 / HUNDREDTHS_PER_MM as f64
+    }
+
+    pub fn saturating_sub(self, rhs: Millimeters) -> Millimeters {
+        Millimeters(self.0.saturating_sub(rhs.0))
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(transparent)]
+pub struct Hundredths(pub i64);   // hundredths of the peril's unit: mm, °C, km/h, …
+
+impl Hundredths {
+    pub const ZERO: Hundredths = Hundredths(0);
+
+    // Nearest hundredth of a value written in the peril's unit
+    pub fn from_f64(value: f64) -> Self {
+        Hundredths(to_hundredths(value))
+    }
+
+    // For display and for the f64 paths products still compute on
+    pub fn to_f64(self) -> f64 {
+        self.0 as f64 / HUNDREDTHS_PER_MM as f64
+    }
+
+    pub fn is_positive(self) -> bool {
+        self.0 > 0
+    }
+}
+
+impl From<Millimeters> for Hundredths {
+    fn from(mm: Millimeters) -> Self {
+        Hundredths(mm.0.min(i64::MAX as u64) as i64)
+    }
+}
+
+// A rain reading put back on the rainfall scale; below zero is no rain
+impl From<Hundredths> for Millimeters {
+    fn from(reading: Hundredths) -> Self {
+        Millimeters(reading.0.max(0) as u64)
+    }
+}
+
+// Trailing zeros dropped and no unit, e.g. "-2.5"
+impl fmt::Display for Hundredths {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.0 < 0 { "-" } else { "" };
+        let (whole, frac) = (self.0.unsigned_abs() / HUNDREDTHS_PER_MM, self.0.unsigned_abs() % HUNDREDTHS_PER_MM);
+        match frac {
+            0                      => write!(f, "{sign}{whole}"),
+            frac if frac % 10 == 0 => write!(f, "{sign}{whole}.{}", frac / 10),
+            frac                   => write!(f, "{sign}{whole}.{frac:02}"),
+        }
+    }
+}
+
+// Nearest hundredth of `value`, halves away from zero. NaN is zero.
+pub fn to_hundredths(value: f64) -> i64 {
+    if value.is_nan() {
+        return 0;
+    }
+    let magnitude = magnitude_hundredths(value.abs()).min(i64::MAX as u128) as i64;
+    if value < 0.0 { -magnitude } else { magnitude }
+}
+
+// |value| in hundredths, rounded on the shortest decimal that reads back as it
+fn magnitude_hundredths(value: f64) -> u128 {
+    if value >= 1e30 {
+        return u128::MAX;
+    }
+    // Display never uses an exponent and prints the shortest round-trip digits
+    let text = value.to_string();
+    let (whole, frac) = text.split_once('.').unwrap_or((&text, ""));
+    let digit = |i: usize| frac.as_bytes().get(i).map_or(0, |d| (d - b'0') as u128);
+
+    let whole: u128 = whole.parse().unwrap_or(0);
+    whole * 100 + digit(0) * 10 + digit(1) + u128::from(digit(2) >= 5)
+}
+
+// Trailing zeros dropped, e.g. "12.5 mm"
+impl fmt::Display for Millimeters {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let whole = self.0 / HUNDREDTHS_PER_MM;
+        match self.0 % HUNDREDTHS_PER_MM {
+            0                      => write!(f, "{whole} mm"),
+            frac if frac % 10 == 0 => write!(f, "{whole}.{} mm", frac / 10),
+            frac                   => write!(f, "{whole}.{frac:02} mm"),
+        }
+    }
+}
+
+// Rain totals saturate rather than wrap
+impl Add for Millimeters {
+    type Output = Millimeters;
+    fn add(self, rhs: Millimeters) -> Millimeters {
+        Millimeters(self.0.saturating_add(rhs.0))
+    }
+}
+
+impl AddAssign for Millimeters {
+    fn add_assign(&mut self, rhs: Millimeters) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sum for Millimeters {
+    fn sum<I: Iterator<Item = Millimeters>>(iter: I) -> Millimeters {
+        iter.fold(Millimeters::ZERO, Add::add)
+    }
+}
@@ -0,0 +1,112 @@
+// ============================================================
+//  Money
+//
+//  Every token amount in the contract is a `Ralo`: a count of
+//  base units, lamport-style, with 9 decimals. `Ralo::whole(200)`
+//  is 200 tokens; `Ralo(200)` is 200 base units. The token API
+//  deals in raw base units, so `.base_units()` is only needed at
+//  the transfer boundary. Anything shown to people goes through
+//  `format_ralo`. The operators panic on overflow (release builds
+//  keep overflow checks); ledger balances move through the
+//  `checked_` methods instead, so a bad sum is an error the
+//  caller returns rather than an abort.
+// ============================================================
+
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Sub, SubAssign};
+
+use serde::{Deserialize, Serialize};
+
+pub const NATIVE_MINT: &str = "RALO";
+pub const RALO_DECIMALS: u32 = 9;
+pub const BASE_UNITS_PER_RALO: u64 = 10u64.pow(RALO_DECIMALS);
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(transparent)]
+pub struct Ralo(pub u64);   // base units
+
+impl Ralo {
+    pub const ZERO: Ralo = Ralo(0);
+
+    // `tokens` whole RALO
+    pub const fn whole(tokens: u64) -> Self {
+        Ralo(tokens * BASE_UNITS_PER_RALO)
+    }
+
+    pub const fn base_units(self) -> u64 {
+        self.0
+    }
+
+    pub fn is_zero(self) -> bool {
+        self.0 == 0
+    }
+
+    // Share of this amount in basis points, rounded down
+    pub fn bps(self, bps: u64) -> Ralo {
+        Ralo((self.0 as u128 * bps as u128 / 10_000) as u64)
+    }
+
+    pub fn saturating_sub(self, rhs: Ralo) -> Ralo {
+        Ralo(self.0.saturating_sub(rhs.0))
+    }
+
+    pub fn checked_add(self, rhs: Ralo) -> Option<Ralo> {
+        self.0.checked_add(rhs.0).map(Ralo)
+    }
+
+    pub fn checked_sub(self, rhs: Ralo) -> Option<Ralo> {
+        self.0.checked_sub(rhs.0).map(Ralo)
+    }
+
+    // `n` of this amount, e.g. a per-call cost over several calls
+    pub fn times(self, n: u64) -> Ralo {
+        Ralo(self.0 * n)
+    }
+
+    pub fn checked_times(self, n: u64) -> Option<Ralo> {
+        self.0.checked_mul(n).map(Ralo)
+    }
+}
+
+// Human-readable amount: whole tokens, trailing zeros dropped, e.g. "12.5 RALO"
+pub fn format_ralo(amount: Ralo) -> String {
+    let whole = amount.0 / BASE_UNITS_PER_RALO;
+    let frac  = amount.0 % BASE_UNITS_PER_RALO;
+    if frac == 0 {
+        return format!("{whole} {NATIVE_MINT}");
+    }
+    let digits = format!("{frac:0width$}", width = RALO_DECIMALS as usize);
+    format!("{whole}.{} {NATIVE_MINT}", digits.trim_end_matches('0'))
+}
+
+impl Add for Ralo {
+    type Output = Ralo;
+    fn add(self, rhs: Ralo) -> Ralo {
+        Ralo(self.0 + rhs.0)
+    }
+}
+
+impl Sub for Ralo {
+    type Output = Ralo;
+    fn sub(self, rhs: Ralo) -> Ralo {
+        Ralo(self.0 - rhs.0)
+    }
+}
+
+impl AddAssign for Ralo {
+    fn add_assign(&mut self, rhs: Ralo) {
+        self.0 += rhs.0;
+    }
+}
+
+impl SubAssign for Ralo {
+    fn sub_assign(&mut self, rhs: Ralo) {
+        self.0 -= rhs.0;
+    }
+}
+
+impl Sum for Ralo {
+    fn sum<I: Iterator<Item = Ralo>>(iter: I) -> Ralo {
+        iter.fold(Ralo::ZERO, Add::add)
+    }
+}
@@ -0,0 +1,426 @@
+// ============================================================
+//  Observation normalization
+//
+//  Turns a provider response into one `Observation` in fixed
+//  units (mm, °C, %, km/h) and derives the "feels like" metrics
+//  outdoor-labour cover is written against:
+//    • heat index — NOAA Rothfusz regression, from temp + humidity
+//    • wind chill — Environment Canada formula, from temp + wind
+//  Both fall back to the air temperature outside the range the
+//  formula is defined for, matching how weather services report them.
+//  Rainfall is also banded into WMO intensity classes for display.
+//
+//  A threshold can trigger from either side (`Comparison`): at or
+//  above it for floods, heat, wind and snow; at or below it for
+//  frost and drought.
+//
+//  Each observation also keeps what the provider said about where
+//  it came from — station or grid cell, the coordinates it was
+//  actually read at, the data source it was drawn from — so basis
+//  risk and disputes can be argued on the reading, not the city name.
+//  It keeps a digest of the raw response it was parsed from, too, so
+//  an archived copy of the provider's answer can be shown to be the
+//  one the contract read.
+//
+//  Location names are kept under an ASCII slug (`location_slug`),
+//  so "Nairobi", " nairobi " and "Nair%C3%B3bi" share one weather
+//  cache entry, one set of pricing tables and one line in every
+//  ledger keyed by location. With geohash keys on (geocoding.rs)
+//  a named place's key also carries where its provider places it,
+//  in place of a country qualifier: "Nairobi, KE" and "Nairobi"
+//  then share a key, and two Springfields never do.
+//
+//  Non-rain metrics are product features (see Cargo.toml); a build
+//  without them still parses the raw readings but never derives or
+//  exposes the metric.
+// ============================================================
+
+use serde::{Deserialize, Serialize};
+
+use crate::geo::{geohash, GeoPoint};
+use crate::millimeters::{Hundredths, Millimeters};
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Metric {
+    Rainfall,        // mm over the last hour
+    #[cfg(feature = "cold-chain")]
+    Temperature,     // °C
+    #[cfg(feature = "heat")]
+    HeatIndex,       // °C, "feels like" in heat
+    #[cfg(feature = "heat")]
+    HeatHours,       // hours at or above a °C threshold — read from One Call snapshots, not an Observation
+    #[cfg(feature = "heat")]
+    UvHours,         // hours at or above a UV-index threshold — read from One Call snapshots, not an Observation
+    #[cfg(feature = "wind")]
+    WindChill,       // °C, "feels like" in cold wind
+    #[cfg(feature = "wind")]
+    WindSpeed,       // km/h, sustained
+    #[cfg(feature = "snow")]
+    Snowfall,        // mm water-equivalent over the last hour
+    #[cfg(feature = "storm")]
+    StormTrack,      // cyclone proximity and category — read from the storm feed, not an Observation
+    #[cfg(feature = "flood")]
+    RiverLevel,      // river gauge height in mm — read from the gauge feed, not an Observation
+    #[cfg(feature = "air-quality")]
+    AirQuality,      // PM2.5 in µg/m³ — read from the air-pollution feed, not an Observation
+    #[cfg(feature = "degree-days")]
+    DegreeDays,      // heating or cooling degree-days — accumulated from temperature checks, not one Observation
+}
+
+impl Metric {
+    // Read straight off the provider's current or hourly weather
+    pub fn is_observed(self) -> bool {
+        match self {
+            Metric::Rainfall    => true,
+            #[cfg(feature = "cold-chain")]
+            Metric::Temperature => true,
+            #[cfg(feature = "heat")]
+            Metric::HeatIndex   => true,
+            #[cfg(feature = "heat")]
+            Metric::HeatHours | Metric::UvHours => false,
+            #[cfg(feature = "wind")]
+            Metric::WindChill | Metric::WindSpeed => true,
+            #[cfg(feature = "snow")]
+            Metric::Snowfall    => true,
+            #[cfg(feature = "storm")]
+            Metric::StormTrack  => false,
+            #[cfg(feature = "flood")]
+            Metric::RiverLevel  => false,
+            #[cfg(feature = "air-quality")]
+            Metric::AirQuality  => false,
+            #[cfg(feature = "degree-days")]
+            Metric::DegreeDays  => false,
+        }
+    }
+}
+
+// Side of the threshold a reading must land on to count
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Comparison {
+    #[default]
+    AtOrAbove,   // >= — floods, heat, wind, snow
+    AtOrBelow,   // <= — frost, drought
+}
+
+impl Comparison {
+    // Compared in whole hundredths of the unit (see millimeters.rs)
+    pub fn is_met(self, reading: Hundredths, threshold: Hundredths) -> bool {
+        match self {
+            Comparison::AtOrAbove => reading >= threshold,
+            Comparison::AtOrBelow => reading <= threshold,
+        }
+    }
+}
+
+// Where a provider says a reading came from; whatever it doesn't report is None
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Station {
+    pub station_id:  Option<String>,     // provider's station or grid-cell id
+    pub coord:       Option<GeoPoint>,   // point the provider resolved the query to
+    pub data_source: Option<String>,     // provider's name for the feed, e.g. OpenWeatherMap's "stations"
+}
+
+// The raw provider response a reading was parsed from
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ResponseDigest {
+    pub sha256: [u8; 32],   // of the body, byte for byte as received
+    pub len:    u32,        // body length in bytes
+    pub status: u16,        // HTTP status
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct Observation {
+    pub rainfall_mm:    Millimeters,
+    pub temperature_c:  Option<f64>,
+    pub humidity_pct:   Option<f64>,
+    pub wind_speed_kmh: Option<f64>,
+    pub heat_index_c:   Option<f64>,
+    pub wind_chill_c:   Option<f64>,
+    pub snowfall_mm:    Option<f64>,   // None when the provider doesn't report snow
+    pub station:        Station,
+    pub response:       Option<ResponseDigest>,   // None for a reading not parsed from a provider response
+}
+
+impl Observation {
+    // Build from raw readings, deriving the feels-like metrics
+    pub fn new(
+        rainfall_mm:    Millimeters,
+        temperature_c:  Option<f64>,
+        humidity_pct:   Option<f64>,
+        wind_speed_kmh: Option<f64>,
+    ) -> Self {
+        #[cfg(feature = "heat")]
+        let heat_index_c = match (temperature_c, humidity_pct) {
+            (Some(t), Some(rh)) => Some(heat_index_c(t, rh)),
+            _ => None,
+        };
+        #[cfg(not(feature = "heat"))]
+        let heat_index_c = None;
+
+        #[cfg(feature = "wind")]
+        let wind_chill_c = match (temperature_c, wind_speed_kmh) {
+            (Some(t), Some(v)) => Some(wind_chill_c(t, v)),
+            _ => None,
+        };
+        #[cfg(not(feature = "wind"))]
+        let wind_chill_c = None;
+
+        Observation { rainfall_mm, temperature_c, humidity_pct, wind_speed_kmh, heat_index_c, wind_chill_c, snowfall_mm: None, station: Station::default(), response: None }
+    }
+
+    pub fn with_station(mut self, station: Station) -> Self {
+        self.station = station;
+        self
+    }
+
+    pub fn with_response(mut self, response: ResponseDigest) -> Self {
+        self.response = Some(response);
+        self
+    }
+
+    pub fn with_snowfall(mut self, snowfall_mm: Option<f64>) -> Self {
+        self.snowfall_mm = snowfall_mm;
+        self
+    }
+
+    // The reading settlement takes from this observation, on the fixed-point
+    // scale: rain is already there, and every other provider f64 is rounded
+    // to hundredths here, once
+    pub fn metric(&self, metric: Metric) -> Option<Hundredths> {
+        match metric {
+            Metric::Rainfall    => Some(Hundredths::from(self.rainfall_mm)),
+            #[cfg(feature = "cold-chain")]
+            Metric::Temperature => self.temperature_c.map(Hundredths::from_f64),
+            #[cfg(feature = "heat")]
+            Metric::HeatIndex   => self.heat_index_c.map(Hundredths::from_f64),
+            #[cfg(feature = "heat")]
+            Metric::HeatHours | Metric::UvHours => None,
+            #[cfg(feature = "wind")]
+            Metric::WindChill   => self.wind_chill_c.map(Hundredths::from_f64),
+            #[cfg(feature = "wind")]
+            Metric::WindSpeed   => self.wind_speed_kmh.map(Hundredths::from_f64),
+            #[cfg(feature = "snow")]
+            Metric::Snowfall    => self.snowfall_mm.map(Hundredths::from_f64),
+            #[cfg(feature = "storm")]
+            Metric::StormTrack  => None,
+            #[cfg(feature = "flood")]
+            Metric::RiverLevel  => None,
+            #[cfg(feature = "air-quality")]
+            Metric::AirQuality  => None,
+            #[cfg(feature = "degree-days")]
+            Metric::DegreeDays  => None,
+        }
+    }
+}
+
+// Canonical form of a location name: trimmed, single-spaced, lowercase.
+// What a provider is asked about by name.
+pub fn canonical_location(location: &str) -> String {
+    location
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+// Internal key of a location name: percent-decoded, lowercase, accents
+// folded to ASCII, and every run of anything else a single '-', e.g.
+// "São Paulo, BR" → "sao-paulo-br"
+pub fn location_slug(location: &str) -> String {
+    let (mut words, mut word) = (Vec::new(), String::new());
+    for c in percent_decode(location).to_lowercase().chars() {
+        match fold_ascii(c) {
+            Some(folded)                      => word.push_str(folded),
+            None if c.is_ascii_alphanumeric() => word.push(c),
+            None if !word.is_empty()          => words.push(std::mem::take(&mut word)),
+            None                              => {}
+        }
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words.join("-")
+}
+
+// The slug of a named place at `point`, its geohash standing in for a
+// trailing two-letter country qualifier, e.g. "Nairobi, KE" → "nairobi@kzf0t"
+pub fn geohashed_slug(location: &str, point: &GeoPoint, precision: usize) -> String {
+    let decoded = percent_decode(location);
+    let name = match decoded.rsplit_once(',') {
+        Some((name, qualifier)) if qualifier.trim().len() == 2 && qualifier.trim().bytes().all(|b| b.is_ascii_alphabetic()) => name,
+        _ => decoded.as_str(),
+    };
+    format!("{}@{}", location_slug(name), geohash(point, precision))
+}
+
+// %XX escapes decoded; a malformed escape is kept as written
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escape = (bytes[i] == b'%')
+            .then(|| text.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escape {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+// ASCII spelling of an accented lowercase letter
+fn fold_ascii(c: char) -> Option<&'static str> {
+    Some(match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => "a",
+        'ç' | 'ć' | 'č' => "c",
+        'ď' | 'đ' => "d",
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ė' | 'ę' | 'ě' => "e",
+        'ğ' => "g",
+        'ì' | 'í' | 'î' | 'ï' | 'ī' | 'ı' => "i",
+        'ł' => "l",
+        'ñ' | 'ń' | 'ň' => "n",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ő' => "o",
+        'ř' => "r",
+        'ś' | 'š' | 'ş' | 'ș' => "s",
+        'ť' | 'ţ' | 'ț' => "t",
+        'ù' | 'ú' | 'û' | 'ü' | 'ū' | 'ů' | 'ű' => "u",
+        'ý' | 'ÿ' => "y",
+        'ź' | 'ż' | 'ž' => "z",
+        'ß' => "ss",
+        'æ' => "ae",
+        'œ' => "oe",
+        'þ' => "th",
+        _ => return None,
+    })
+}
+
+// What a provider is asked about. A city name is ambiguous — there are
+// dozens of Springfields and the provider picks one without saying — so
+// a policy can be pinned to coordinates or to the provider's city id. Where
+// the provider can query one, it can be pinned to a single weather station.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum Location {
+    City(String),
+    Point(GeoPoint),
+    CityId(u64),       // the provider's own id, e.g. OpenWeatherMap's 184745
+    Station(String),   // a station id, e.g. Weatherbit's "KJFK" (providers.rs)
+}
+
+impl Location {
+    pub fn canonical(self) -> Location {
+        match self {
+            Location::City(name) => Location::City(canonical_location(&name)),
+            pinned               => pinned,
+        }
+    }
+
+    // Free-text form, for providers that take any location in one query
+    // parameter: the name, "lat,lon" in degrees, "id:<city id>" or
+    // "station:<station id>"
+    pub fn text(&self) -> String {
+        match self {
+            Location::City(name)  => canonical_location(name),
+            Location::Point(p)    => format!("{},{}", p.lat_e6 as f64 / 1e6, p.lon_e6 as f64 / 1e6),
+            Location::CityId(id)  => format!("id:{id}"),
+            Location::Station(id) => format!("station:{id}"),
+        }
+    }
+
+    // Internal key, kept as the policy's `location`: the text form, with
+    // a name reduced to its slug
+    pub fn key(&self) -> String {
+        match self {
+            Location::City(name) => location_slug(name),
+            pinned               => pinned.text(),
+        }
+    }
+}
+
+// Human-readable rainfall, to the hundredth of a mm, e.g. "12.5 mm"
+pub fn format_mm(mm: f64) -> String {
+    let fixed = format!("{mm:.2}");
+    let trimmed = fixed.trim_end_matches('0').trim_end_matches('.');
+    format!("{trimmed} mm")
+}
+
+// WMO rain intensity bands, by hourly rate
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RainIntensity {
+    None,        // no measurable rain
+    Light,       // under 2.5 mm/h
+    Moderate,    // 2.5 – 10 mm/h
+    Heavy,       // 10 – 50 mm/h
+    Violent,     // 50 mm/h and above
+}
+
+pub fn rain_intensity(rainfall_per_hour: Millimeters) -> RainIntensity {
+    match rainfall_per_hour.0 {
+        0              => RainIntensity::None,
+        r if r < 250   => RainIntensity::Light,
+        r if r < 1_000 => RainIntensity::Moderate,
+        r if r < 5_000 => RainIntensity::Heavy,
+        _              => RainIntensity::Violent,
+    }
+}
+
+// m/s as reported with units=metric → km/h
+pub fn ms_to_kmh(speed_ms: f64) -> f64 {
+    speed_ms * 3.6
+}
+
+// Heat index in °C from air temperature (°C) and relative humidity (%)
+#[cfg(feature = "heat")]
+pub fn heat_index_c(temp_c: f64, humidity_pct: f64) -> f64 {
+    let t  = temp_c * 9.0 / 5.0 + 32.0;
+    let rh = humidity_pct.clamp(0.0, 100.0);
+
+    // Steadman's simple form is used whenever it stays below 80 °F
+    let simple = 0.5 * (t + 61.0 + (t - 68.0) * 1.2 + rh * 0.094);
+    if (simple + t) / 2.0 < 80.0 {
+        return f_to_c(simple);
+    }
+
+    let mut hi = -42.379
+        + 2.049_015_23 * t
+        + 10.143_331_27 * rh
+        - 0.224_755_41 * t * rh
+        - 0.006_837_83 * t * t
+        - 0.054_817_17 * rh * rh
+        + 0.001_228_74 * t * t * rh
+        + 0.000_852_82 * t * rh * rh
+        - 0.000_001_99 * t * t * rh * rh;
+
+    // NOAA adjustments at the edges of the regression
+    if rh < 13.0 && (80.0..=112.0).contains(&t) {
+        hi -= ((13.0 - rh) / 4.0) * ((17.0 - (t - 95.0).abs()) / 17.0).sqrt();
+    } else if rh > 85.0 && (80.0..=87.0).contains(&t) {
+        hi += ((rh - 85.0) / 10.0) * ((87.0 - t) / 5.0);
+    }
+
+    f_to_c(hi)
+}
+
+// Wind chill in °C from air temperature (°C) and wind speed (km/h).
+// Only defined at or below 10 °C with wind above 4.8 km/h.
+#[cfg(feature = "wind")]
+pub fn wind_chill_c(temp_c: f64, wind_kmh: f64) -> f64 {
+    if temp_c > 10.0 || wind_kmh <= 4.8 {
+        return temp_c;
+    }
+    let v = wind_kmh.powf(0.16);
+    13.12 + 0.6215 * temp_c - 11.37 * v + 0.3965 * temp_c * v
+}
+
+#[cfg(feature = "heat")]
+fn f_to_c(f: f64) -> f64 {
+    (f - 32.0) * 5.0 / 9.0
+}
@@ -0,0 +1,25 @@
+// ============================================================
+//  Policy lifecycle
+//
+//  Where a policy is between being written and being done with.
+//  Every policy starts out PendingPayment; the contract moves it
+//  on as its premium clears, its cover runs and its trigger
+//  settles, and an off-chain reader can tell from the state alone
+//  whether the policy can still pay.
+// ============================================================
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PolicyStatus {
+    PendingPayment,     // created, premium not yet fully in escrow
+    Active,             // premium cleared, coverage live
+    PaidOut,            // triggered and settled
+    Withdrawn,          // owner pulled the escrowed premium before activation
+    Lapsed,             // voided after the payment grace period ran out
+    Expired,            // coverage ended without a trigger and was finalized
+    PayoutDeferred,     // triggered; USD conversion refused the price, waiting on retry_payout
+    Cancelled,          // owner ended live cover early for a pro-rata refund
+    PendingAttestation, // triggered; waiting on the beneficiary to attest the loss (attestation.rs)
+    PendingPayout,      // triggered; waiting out the dispute window before it pays (disputes.rs)
+}
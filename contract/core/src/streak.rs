@@ -0,0 +1,70 @@
+// ============================================================
+//  Continuous-rain streaks
+//
+//  "Rained continuously for ≥ N hours" products are checked once
+//  an hour; each policy keeps a streak of consecutive wet hours
+//  (rain at or above its threshold). A dry reading ends the
+//  streak. One missed check is tolerated per streak, because a
+//  keeper skipping an hour is no evidence the rain stopped.
+// ============================================================
+
+use serde::{Deserialize, Serialize};
+
+const SECS_PER_HOUR:     i64 = 60 * 60;
+const MAX_MISSED_CHECKS: u32 = 1;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RainStreak {
+    pub required_hours: u32,           // streak length that triggers the payout
+    pub start_hour:     Option<i64>,   // first wet hour of the current streak
+    pub last_hour:      i64,           // most recent wet hour
+    pub misses:         u32,           // hours skipped inside the current streak
+}
+
+impl RainStreak {
+    pub fn new(required_hours: u32) -> Self {
+        RainStreak { required_hours, start_hour: None, last_hour: 0, misses: 0 }
+    }
+
+    // Wet hours in the current streak, missed checks included
+    pub fn hours(&self) -> u32 {
+        self.start_hour.map_or(0, |start| (self.last_hour - start + 1) as u32)
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.hours() >= self.required_hours
+    }
+
+    // Fold one hourly check into the streak; returns its new length
+    pub fn record(&mut self, wet: bool, now: i64) -> u32 {
+        let hour = now.div_euclid(SECS_PER_HOUR);
+
+        if !wet {
+            *self = RainStreak::new(self.required_hours);
+            return 0;
+        }
+
+        match self.start_hour {
+            // A second check inside an hour already counted
+            Some(_) if hour <= self.last_hour => {}
+            Some(_) => {
+                let missed = (hour - self.last_hour - 1) as u32;
+                if self.misses + missed <= MAX_MISSED_CHECKS {
+                    self.misses   += missed;
+                    self.last_hour = hour;
+                } else {
+                    self.restart(hour);
+                }
+            }
+            None => self.restart(hour),
+        }
+
+        self.hours()
+    }
+
+    fn restart(&mut self, hour: i64) {
+        self.start_hour = Some(hour);
+        self.last_hour  = hour;
+        self.misses     = 0;
+    }
+}
@@ -0,0 +1,132 @@
+// ============================================================
+//  Rolling rainfall accumulation
+//
+//  One hour of rain is a poor proxy for a flood; what floods a
+//  road is the rain of the last day or three. A template can make
+//  its policies accumulate: each check files its hourly reading on
+//  the policy, one per hour, and the payout triggers once the rain
+//  over any `window_hours` in a row reaches the threshold.
+//
+//  Hours are placed by the provider's timestamp, not the order
+//  they were filed in. Readings older than the window behind the
+//  newest one can't be in any window still to be checked, so they
+//  are pruned as new ones arrive — a policy never stores more than
+//  a window of hours. Historical checks can fill a missed hour in
+//  any order while it is still inside the template's maximum
+//  observation age behind the newest reading: the whole window by
+//  default, or less, so a keeper's late backfill can't reopen a
+//  stretch of the window that has long since been read.
+// ============================================================
+
+use rialo_sdk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::InsuranceError;
+use crate::millimeters::{Hundredths, Millimeters};
+use crate::normalization::{Comparison, Metric};
+use crate::underwriter::{tenant_mut, TemplateId, UnderwriterId};
+use crate::InsuranceState;
+
+const SECS_PER_HOUR: i64 = 60 * 60;
+
+pub const MAX_WINDOW_HOURS: u32 = 7 * 24;
+
+// Accumulation state carried by a policy sold under an accumulating template
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RollingRain {
+    pub window_hours:  u32,
+    pub max_age_hours: u32,                       // furthest behind the newest reading a late one is still filed
+    pub readings:      Vec<(i64, Millimeters)>,   // (hour since epoch, rain), oldest first
+}
+
+impl RollingRain {
+    pub fn new(window_hours: u32) -> Self {
+        RollingRain { window_hours, max_age_hours: window_hours, readings: Vec::new() }
+    }
+
+    // Refuse late readings from further back than `max_age_hours`, up to the window
+    pub fn with_max_age(self, max_age_hours: Option<u32>) -> Self {
+        let max_age_hours = max_age_hours.map_or(self.window_hours, |h| h.min(self.window_hours));
+        RollingRain { max_age_hours, ..self }
+    }
+
+    // Rain over the window ending with the newest reading
+    pub fn latest_total_mm(&self) -> Millimeters {
+        let Some(&(newest, _)) = self.readings.last() else {
+            return Millimeters::ZERO;
+        };
+        self.total_mm_ending(newest)
+    }
+
+    fn total_mm_ending(&self, hour: i64) -> Millimeters {
+        let from = hour - self.window_hours as i64;
+        self.readings
+            .iter()
+            .filter(|&&(h, _)| h > from && h <= hour)
+            .map(|&(_, mm)| mm)
+            .sum()
+    }
+
+    // Wettest window among the stored readings
+    pub fn max_total_mm(&self) -> Millimeters {
+        self.readings
+            .iter()
+            .map(|&(hour, _)| self.total_mm_ending(hour))
+            .max()
+            .unwrap_or(Millimeters::ZERO)
+    }
+
+    // File one hourly reading observed at `observed_at`; returns true once some window
+    // reaches `threshold`. Hours already filed and hours past the maximum age add nothing.
+    pub fn record(&mut self, rainfall: Millimeters, observed_at: i64, threshold: Hundredths) -> bool {
+        let hour = observed_at.div_euclid(SECS_PER_HOUR);
+        let window = self.window_hours as i64;
+
+        if let Some(&(newest, _)) = self.readings.last() {
+            if hour <= newest - self.max_age_hours as i64 {
+                return false;
+            }
+        }
+        let at = self.readings.partition_point(|&(h, _)| h < hour);
+        if self.readings.get(at).is_some_and(|&(h, _)| h == hour) {
+            return false;
+        }
+        self.readings.insert(at, (hour, rainfall));
+
+        let newest = self.readings.last().map_or(hour, |&(h, _)| h);
+        self.readings.retain(|&(h, _)| h > newest - window);
+
+        Hundredths::from(self.max_total_mm()) >= threshold
+    }
+}
+
+// ── Entry point: make (or unmake) a template an accumulating product
+#[rialo::instruction]
+pub async fn set_template_accumulation(
+    ctx:            Context<InsuranceState>,
+    underwriter_id: UnderwriterId,
+    template_id:    TemplateId,
+    window_hours:   Option<u32>,
+    max_age_hours:  Option<u32>,   // latest a backfill may land behind the newest reading; None: the window
+) -> RialoResult<()> {
+
+    require!(window_hours.is_none_or(|h| (1..=MAX_WINDOW_HOURS).contains(&h)), InsuranceError::InvalidArgument("Accumulation window must be between 1 and 168 hours.".into()));
+    require!(max_age_hours.is_none_or(|age| window_hours.is_some_and(|h| (1..=h).contains(&age))), InsuranceError::InvalidArgument("Maximum observation age must be between 1 hour and the window.".into()));
+
+    let underwriter = tenant_mut(&mut ctx.state, underwriter_id, &ctx.signer)?;
+    let template = underwriter.templates.get_mut(&template_id).ok_or(InsuranceError::UnknownTemplate)?;
+    if window_hours.is_some() {
+        let plain_rain = template.peril() == Metric::Rainfall && template.comparison == Comparison::AtOrAbove
+            && template.continuous_hours.is_none() && template.evaluator.is_none() && !template.rain_normal;
+        require!(plain_rain, InsuranceError::InvalidState("Template already has its own trigger.".into()));
+    }
+    template.rolling_hours = window_hours;
+    template.rolling_max_age = max_age_hours;
+
+    emit!(TemplateAccumulationSet { underwriter_id, template_id, window_hours, max_age_hours });
+
+    Ok(())
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct TemplateAccumulationSet { pub underwriter_id: UnderwriterId, pub template_id: TemplateId, pub window_hours: Option<u32>, pub max_age_hours: Option<u32> }
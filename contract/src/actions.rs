@@ -0,0 +1,113 @@
+// ============================================================
+//  Pending actions
+//
+//  One view answering "what can this wallet do right now?", so
+//  the dApp renders a task inbox straight from chain state instead
+//  of re-implementing the policy and vault state machines in JS.
+// ============================================================
+
+use rialo_sdk::prelude::*;
+use serde::Serialize;
+
+use crate::money::Ralo;
+use crate::policy::{PolicyId, PolicyStatus};
+use crate::subscriptions::SubscriptionId;
+use crate::underwriter::UnderwriterId;
+use crate::InsuranceState;
+
+#[derive(Serialize, Clone, Debug)]
+pub enum PendingAction {
+    // Premium still owed; the policy can be voided once `lapses_at` passes
+    PremiumDue           { policy_id: PolicyId, outstanding: Ralo, lapses_at: i64 },
+    // Coverage is live — a weather check may trigger the payout
+    PolicyClaimable      { policy_id: PolicyId, payout: Ralo },
+    // Partial payment in escrow the owner can still take back
+    PremiumRefundable    { policy_id: PolicyId, amount: Ralo },
+    // Triggered USD payout waiting on a fair price; anyone can call retry_payout
+    PayoutDeferred       { policy_id: PolicyId, usd_cents: u64 },
+    // Triggered payout waiting on the owner's loss attestation; call attest_loss before `lapses_at`
+    AttestationDue       { policy_id: PolicyId, payout: Ralo, lapses_at: Option<i64> },
+    // Triggered payout in its dispute window; dispute_claim until `payable_at`, finalize_payout after
+    PayoutPending        { policy_id: PolicyId, payable_at: i64 },
+    // Expired under a provider outage; the owner can call claim_outage_refund
+    OutageRefundDue      { policy_id: PolicyId, amount: Ralo },
+    // Expired without paying under a no-claim bonus; the owner can call claim_no_loss_refund
+    NoClaimRefundDue     { policy_id: PolicyId, amount: Ralo },
+    // Subscription's next period can be written and paid for from `renews_at`; anyone can call renew_subscription
+    SubscriptionRenewal  { subscription_id: SubscriptionId, renews_at: i64 },
+    // A vault withdrawal the underwriter requested (executable once `executable_at` passes)
+    WithdrawalExecutable { underwriter_id: UnderwriterId, amount: Ralo, executable_at: i64 },
+}
+
+#[rialo::view]
+pub fn get_pending_actions(
+    ctx:   Context<InsuranceState>,
+    owner: Pubkey,
+) -> RialoResult<Vec<PendingAction>> {
+
+    let state = &ctx.state;
+    let grace = state.config.payment_grace_secs;
+    let mut actions = Vec::new();
+
+    for (id, policy) in state.policies.iter().filter(|(_, p)| p.owner == owner) {
+        match policy.status {
+            PolicyStatus::PendingPayment => {
+                actions.push(PendingAction::PremiumDue {
+                    policy_id:   *id,
+                    outstanding: policy.premium_outstanding(),
+                    lapses_at:   policy.created_at + grace,
+                });
+                if !policy.refundable_premium().is_zero() {
+                    actions.push(PendingAction::PremiumRefundable {
+                        policy_id: *id,
+                        amount:    policy.refundable_premium(),
+                    });
+                }
+            }
+            PolicyStatus::Active => {
+                actions.push(PendingAction::PolicyClaimable { policy_id: *id, payout: policy.payout_amount });
+            }
+            PolicyStatus::Expired => {
+                if let Some(impairment) = policy.impairment.filter(|i| !i.refunded) {
+                    actions.push(PendingAction::OutageRefundDue { policy_id: *id, amount: impairment.refund });
+                }
+                if let Some(amount) = policy.no_claim.filter(|b| !b.refunded).and_then(|b| b.due) {
+                    actions.push(PendingAction::NoClaimRefundDue { policy_id: *id, amount });
+                }
+            }
+            PolicyStatus::PayoutDeferred => {
+                let usd_cents = policy.usd_payout.map_or(0, |usd| usd.usd_cents);
+                actions.push(PendingAction::PayoutDeferred { policy_id: *id, usd_cents });
+            }
+            PolicyStatus::PendingAttestation => {
+                let lapses_at = policy.attestation.as_ref().and_then(|c| c.lapses_at());
+                actions.push(PendingAction::AttestationDue { policy_id: *id, payout: policy.coverage_remaining(), lapses_at });
+            }
+            PolicyStatus::PendingPayout => {
+                if let Some(claim) = policy.dispute.as_ref().and_then(|c| c.pending) {
+                    actions.push(PendingAction::PayoutPending { policy_id: *id, payable_at: claim.payable_at });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    for (id, subscription) in state.subscriptions.iter().filter(|(_, s)| s.owner == owner && s.ended.is_none()) {
+        let renews_at = state.policies.get(&subscription.current).and_then(|p| p.coverage_end());
+        if let Some(renews_at) = renews_at {
+            actions.push(PendingAction::SubscriptionRenewal { subscription_id: *id, renews_at });
+        }
+    }
+
+    for (id, underwriter) in state.underwriters.iter().filter(|(_, u)| u.authority == owner) {
+        if let Some(request) = underwriter.withdrawal {
+            actions.push(PendingAction::WithdrawalExecutable {
+                underwriter_id: *id,
+                amount:         request.amount,
+                executable_at:  request.executable_at,
+            });
+        }
+    }
+
+    Ok(actions)
+}
@@ -0,0 +1,236 @@
+// ============================================================
+//  Forecast advances (early-warning mode)
+//
+//  A delivery company that knows a downpour is coming tomorrow
+//  can use the money today — to hire vans, move stock, reroute.
+//  A template can offer forecast mode: a policy that opts in is
+//  advanced part of its payout as soon as OpenWeatherMap's 5-day
+//  forecast predicts rain past its threshold within the next 24
+//  hours of cover, before a drop has fallen.
+//
+//    check_forecast_and_advance → forecast step at or above the
+//                                  threshold (predicted mm per
+//                                  hour) → the advance share, once
+//    observed exceedance         → the rest of the payout, as a
+//                                  policy in observed mode would
+//
+//  The advance is part of the payout, not on top of it: the
+//  observed trigger tops the policy up to its full payout net of
+//  what was advanced (settlement::top_up). An advance on rain that
+//  never comes is the underwriter's cost, so forecast mode is the
+//  tenant's to offer, on plain flat rainfall cover, with a cap on
+//  the share; the owner picks it before paying the premium. A
+//  forecast check shares the policy's check cooldown (throttle.rs)
+//  with its live checks.
+//
+//  An advance settles on a provider reading like any other: it's
+//  logged as a check (observations.rs), with the predicted hourly
+//  rate as the reading, and booked under that check's round.
+//  `ForecastTriggered` marks it; observed triggers are announced
+//  as before. Both book their payment in `PolicyTriggered`.
+// ============================================================
+
+use rialo_sdk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::claims::{record_lae, LaeKind};
+use crate::config::require_unpaused;
+use crate::consensus::single_source_allowed;
+use crate::errors::InsuranceError;
+use crate::evaluation::Evaluation;
+use crate::incidents::under_incident;
+use crate::keys::{lease_key, report_key};
+use crate::millimeters::Hundredths;
+use crate::money::Ralo;
+use crate::normalization::{Comparison, Metric, Station};
+use crate::observations::{record_check, upcoming_check_id, CheckId};
+use crate::oracle::{forecast_url, parse_forecast, CallBudget, ForecastStep, FORECAST_STEP_SECS};
+use crate::policy::{Policy, PolicyId, PolicyStatus};
+use crate::providers::ProviderKind;
+use crate::resilience::{self, FetchError};
+use crate::{settlement, throttle};
+use crate::underwriter::{tenant_mut, Template, TemplateId, UnderwriterId};
+use crate::{provider_headers, InsuranceState};
+
+pub const MAX_ADVANCE_BPS:     u64 = 5_000;
+pub const FORECAST_AHEAD_SECS: i64 = 24 * 60 * 60;
+
+// How a policy's payout is reached
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TriggerMode {
+    #[default]
+    Observed,                        // on readings alone
+    Forecast { advance_bps: u64 },   // share of the payout advanced on a forecast, the rest on readings
+}
+
+impl TriggerMode {
+    pub fn advance_bps(self) -> Option<u64> {
+        match self {
+            TriggerMode::Observed                 => None,
+            TriggerMode::Forecast { advance_bps } => Some(advance_bps),
+        }
+    }
+}
+
+// Cover forecast mode can be offered on: flat cover paying above an hourly rainfall threshold
+fn offers_forecast_mode(template: &Template) -> bool {
+    template.is_plain() && template.peril() == Metric::Rainfall && template.comparison == Comparison::AtOrAbove
+        && template.bundle.is_none()
+}
+
+// Wettest step overlapping the next 24 hours of `policy`'s cover, if it is
+// forecast at or above the threshold
+pub fn advance_step(policy: &Policy, steps: &[ForecastStep], now: i64) -> Option<ForecastStep> {
+    let until = policy.coverage_end().map_or(now + FORECAST_AHEAD_SECS, |end| end.min(now + FORECAST_AHEAD_SECS));
+    steps
+        .iter()
+        .filter(|s| s.start + FORECAST_STEP_SECS > now && s.start < until)
+        .filter(|s| Hundredths::from_f64(s.hourly_mm()) >= policy.threshold)
+        .max_by(|a, b| a.hourly_mm().total_cmp(&b.hourly_mm()))
+        .copied()
+}
+
+// ── Entry point: offer (or withdraw) forecast mode on a template
+#[rialo::instruction]
+pub async fn set_template_advance(
+    ctx:            Context<InsuranceState>,
+    underwriter_id: UnderwriterId,
+    template_id:    TemplateId,
+    advance_bps:    Option<u64>,
+) -> RialoResult<()> {
+
+    require!(advance_bps.is_none_or(|bps| (1..=MAX_ADVANCE_BPS).contains(&bps)), InsuranceError::InvalidArgument("Advances must be between 0.01% and 50% of the payout.".into()));
+
+    let underwriter = tenant_mut(&mut ctx.state, underwriter_id, &ctx.signer)?;
+    if advance_bps.is_some() {
+        require!(underwriter.provider.kind == ProviderKind::OpenWeatherMap, InsuranceError::InvalidState("Forecast mode reads OpenWeatherMap's forecast.".into()));
+    }
+    let template = underwriter.templates.get_mut(&template_id).ok_or(InsuranceError::UnknownTemplate)?;
+    if advance_bps.is_some() {
+        require!(offers_forecast_mode(template), InsuranceError::InvalidState("Forecast mode is only offered on plain rainfall products paying above the threshold.".into()));
+    }
+    template.advance_bps = advance_bps;
+
+    emit!(TemplateAdvanceSet { underwriter_id, template_id, advance_bps });
+
+    Ok(())
+}
+
+// ── Entry point: owner picks how an unpaid policy triggers ───
+#[rialo::instruction]
+pub async fn set_trigger_mode(
+    ctx:       Context<InsuranceState>,
+    policy_id: PolicyId,
+    mode:      TriggerMode,
+) -> RialoResult<()> {
+
+    let policy = ctx.state.policies.get(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+
+    require!(policy.owner == *ctx.signer, InsuranceError::Unauthorized("Only the policy owner can choose the trigger mode.".into()));
+    require!(policy.status == PolicyStatus::PendingPayment, InsuranceError::InvalidState("Trigger mode is fixed once coverage starts.".into()));
+
+    if let Some(advance_bps) = mode.advance_bps() {
+        let template = ctx.state.underwriters
+            .get(&policy.underwriter_id)
+            .and_then(|u| u.templates.get(&policy.template_id))
+            .ok_or(InsuranceError::UnknownTemplate)?;
+        let offered = template.advance_bps.filter(|_| offers_forecast_mode(template)).ok_or_else(|| InsuranceError::InvalidState("Template does not offer forecast mode.".into()))?;
+        require!((1..=offered).contains(&advance_bps), InsuranceError::InvalidState("Advance exceeds what the template offers.".into()));
+        require!(policy.graded.is_none() && policy.bundle.is_none(), InsuranceError::InvalidState("Forecast mode is only offered on flat cover.".into()));
+    }
+
+    let policy = ctx.state.policies.get_mut(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+    policy.trigger_mode = mode;
+
+    emit!(TriggerModeSet { policy_id, mode });
+
+    Ok(())
+}
+
+// ── Entry point: anyone reads the forecast for a forecast-mode policy
+#[rialo::instruction]
+pub async fn check_forecast_and_advance(
+    ctx:       Context<InsuranceState>,
+    policy_id: PolicyId,
+) -> RialoResult<()> {
+
+    require_unpaused(&ctx.state.config)?;
+    let now = ctx.clock.unix_timestamp;
+    let state = &mut ctx.state;
+    let policy = state.policies.get(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+
+    require!(policy.status == PolicyStatus::Active, InsuranceError::PolicyNotActive);
+    require!(policy.is_covered_at(now), InsuranceError::PolicyExpired);
+    let advance_bps = policy.trigger_mode.advance_bps().ok_or_else(|| InsuranceError::InvalidState("Policy is not in forecast mode.".into()))?;
+    require!(policy.paid_out.is_zero(), InsuranceError::InvalidState("Policy has already been paid.".into()));
+    require!(single_source_allowed(&state.config, policy), InsuranceError::InvalidState("Policy settles on live readings from two providers only.".into()));
+    policy.check_throttle(now)?;
+
+    let underwriter_id = policy.underwriter_id;
+    let underwriter = state.underwriters.get(&underwriter_id).ok_or(InsuranceError::UnknownUnderwriter)?;
+    let provider = &underwriter.provider;
+    require!(provider.kind == ProviderKind::OpenWeatherMap, InsuranceError::InvalidState("Forecast mode reads OpenWeatherMap's forecast.".into()));
+
+    let mode = state.config.network_mode;
+    let source = provider.base_url_for(mode).to_string();
+    require!(!under_incident(state, &source, now), InsuranceError::DataIncident);
+    let headers = provider_headers(underwriter, policy.template_id)?;
+    let (endpoints, cost_per_call, place) = (
+        provider.endpoints_for(mode).into_iter().map(String::from).collect::<Vec<_>>(),
+        provider.cost_per_call,
+        policy.place.clone(),
+    );
+
+    let lease = lease_key(state, underwriter_id, now)?;
+    let urls: Vec<String> = endpoints.iter().map(|base| forecast_url(base, &place, &lease.key)).collect();
+
+    let fetched = resilience::fetch_with_retry(&urls, &headers, &mut CallBudget::per_instruction(), 0).await;
+    if let Some(status) = fetched.status() {
+        if !report_key(state, underwriter_id, &lease, status, now) {
+            return Ok(());
+        }
+    }
+    record_lae(state, policy_id, LaeKind::ProviderCost, cost_per_call.times(fetched.attempts as u64));
+
+    let parsed = fetched.result.and_then(|response| {
+        parse_forecast(response.body()).map(|steps| (steps, response.digest())).map_err(|_| FetchError::ParseError)
+    });
+    let (steps, response) = match parsed {
+        Ok(parsed) => parsed,
+        Err(reason) => {
+            resilience::report_failure(state, policy_id, &source, fetched.attempts, reason);
+            return Ok(());
+        }
+    };
+    throttle::stamp(state, policy_id, now);
+
+    let policy = state.policies.get(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+    let threshold = policy.threshold;
+    let Some(step) = advance_step(policy, &steps, now) else {
+        emit!(ForecastBelowThreshold { policy_id, threshold });
+        return Ok(());
+    };
+
+    let reading = Hundredths::from_f64(step.hourly_mm());
+    let round_id = upcoming_check_id(state);
+    let paid = settlement::pay_share(state, &ctx.vault, policy_id, Some(round_id), advance_bps, reading, now, now)?;
+    record_check(state, policy_id, &source, Station::default(), Some(response), reading, now, Evaluation::Triggered { paid });
+
+    emit!(ForecastTriggered {
+        policy_id,
+        round_id,
+        forecast_for: step.start,
+        forecast_mm:  reading,
+        threshold,
+        advance_bps,
+        paid,
+    });
+
+    Ok(())
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct TemplateAdvanceSet     { pub underwriter_id: UnderwriterId, pub template_id: TemplateId, pub advance_bps: Option<u64> }
+#[rialo::event] pub struct TriggerModeSet         { pub policy_id: PolicyId, pub mode: TriggerMode }
+#[rialo::event] pub struct ForecastTriggered      { pub policy_id: PolicyId, pub round_id: CheckId, pub forecast_for: i64, pub forecast_mm: Hundredths, pub threshold: Hundredths, pub advance_bps: u64, pub paid: Ralo }
+#[rialo::event] pub struct ForecastBelowThreshold { pub policy_id: PolicyId, pub threshold: Hundredths }
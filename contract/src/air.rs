@@ -0,0 +1,200 @@
+// ============================================================
+//  Air-quality cover
+//
+//  For outdoor-workforce employers, who lose shifts when the air
+//  turns hazardous. Checks read OpenWeatherMap's air-pollution
+//  endpoint at the insured point, through the underwriter's own
+//  provider account, and take:
+//    • the 1–5 air-quality index (`list[0].main.aqi`)
+//    • the PM2.5 concentration (`list[0].components.pm2_5`)
+//  A check is hazardous when the index reaches the product's level
+//  or PM2.5 reaches the policy's threshold (µg/m³). The payout
+//  goes out after N hazardous checks in a row; a clean check
+//  resets the run. Counted checks must be an hour apart, so a run
+//  can't be completed with N calls in one minute.
+// ============================================================
+
+use rialo_sdk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::claims::{record_lae, LaeKind};
+use crate::config::require_unpaused;
+use crate::errors::InsuranceError;
+use crate::geo::GeoPoint;
+use crate::incidents::under_incident;
+use crate::keys::{lease_key, report_key};
+use crate::millimeters::Hundredths;
+use crate::policy::{PolicyId, PolicyStatus};
+use crate::underwriter::{tenant_mut, TemplateId, UnderwriterId};
+use crate::{fetch, settlement, InsuranceState};
+
+// OpenWeatherMap scale: 1 Good, 2 Fair, 3 Moderate, 4 Poor, 5 Very Poor
+pub const MAX_AQI: u8 = 5;
+
+const MIN_CHECK_SPACING_SECS: i64 = 60 * 60;
+const MAX_REQUIRED_CHECKS:    u32 = 72;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AqiTrigger {
+    pub min_aqi: u8,    // index at or above which a check is hazardous
+    pub checks:  u32,   // hazardous checks in a row needed to pay
+}
+
+// Air-quality state carried by a policy sold under an air-quality template
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AirCover {
+    pub trigger:      AqiTrigger,
+    pub run:          u32,           // hazardous checks in a row so far
+    pub last_checked: Option<i64>,   // when the last counted check ran
+}
+
+impl AirCover {
+    pub fn new(trigger: AqiTrigger) -> Self {
+        AirCover { trigger, run: 0, last_checked: None }
+    }
+
+    pub fn is_hazardous(&self, reading: &AirReading, pm2_5_threshold: f64) -> bool {
+        reading.aqi >= self.trigger.min_aqi || reading.pm2_5 >= pm2_5_threshold
+    }
+
+    // Whether a check at `now` is far enough from the last one to count
+    pub fn can_check_at(&self, now: i64) -> bool {
+        self.last_checked.is_none_or(|last| now - last >= MIN_CHECK_SPACING_SECS)
+    }
+
+    // Fold one check into the run; returns true once the run is long enough to pay
+    pub fn record(&mut self, hazardous: bool, now: i64) -> bool {
+        self.run = if hazardous { self.run + 1 } else { 0 };
+        self.last_checked = Some(now);
+        self.run >= self.trigger.checks
+    }
+}
+
+// One reading from the air-pollution feed
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct AirReading {
+    pub aqi:   u8,    // 1–5
+    pub pm2_5: f64,   // µg/m³
+}
+
+#[derive(Deserialize)]
+struct PollutionResponse {
+    list: Vec<PollutionEntry>,
+}
+
+#[derive(Deserialize)]
+struct PollutionEntry {
+    main:       PollutionMain,
+    components: PollutionComponents,
+}
+
+#[derive(Deserialize)]
+struct PollutionMain {
+    aqi: u8,
+}
+
+#[derive(Deserialize)]
+struct PollutionComponents {
+    pm2_5: f64,
+}
+
+// Current air pollution at a point
+pub fn air_pollution_url(base_url: &str, point: &GeoPoint, api_key: &str) -> String {
+    format!(
+        "{}/data/2.5/air_pollution?lat={}&lon={}&appid={}",
+        base_url,
+        point.lat_e6 as f64 / 1e6,
+        point.lon_e6 as f64 / 1e6,
+        api_key,
+    )
+}
+
+pub fn parse_air_pollution(body: &[u8]) -> RialoResult<AirReading> {
+    let response: PollutionResponse = serde_json::from_slice(body)
+        .map_err(|_| InsuranceError::BadResponse("Malformed air-pollution response.".into()))?;
+    let entry = response.list.into_iter().next().ok_or_else(|| InsuranceError::BadResponse("Air-pollution response has no readings.".into()))?;
+
+    let reading = AirReading { aqi: entry.main.aqi, pm2_5: entry.components.pm2_5 };
+    require!((1..=MAX_AQI).contains(&reading.aqi), InsuranceError::InvalidState("Air-quality index is out of range.".into()));
+    require!(reading.pm2_5 >= 0.0, InsuranceError::InvalidState("PM2.5 reading is negative.".into()));
+    Ok(reading)
+}
+
+// ── Entry point: make (or unmake) a template an air-quality product
+#[rialo::instruction]
+pub async fn set_template_air_quality(
+    ctx:            Context<InsuranceState>,
+    underwriter_id: UnderwriterId,
+    template_id:    TemplateId,
+    trigger:        Option<AqiTrigger>,
+) -> RialoResult<()> {
+
+    if let Some(trigger) = &trigger {
+        require!((1..=MAX_AQI).contains(&trigger.min_aqi), InsuranceError::InvalidArgument("Air-quality level must be between 1 and 5.".into()));
+        require!((1..=MAX_REQUIRED_CHECKS).contains(&trigger.checks), InsuranceError::InvalidArgument("Air-quality products need between 1 and 72 checks in a row.".into()));
+    }
+
+    let underwriter = tenant_mut(&mut ctx.state, underwriter_id, &ctx.signer)?;
+    let template = underwriter.templates.get_mut(&template_id).ok_or(InsuranceError::UnknownTemplate)?;
+    template.air_quality = trigger;
+
+    emit!(TemplateAirQualitySet { underwriter_id, template_id, trigger });
+
+    Ok(())
+}
+
+// ── Entry point: anyone checks the air at a policy's point ───
+#[rialo::instruction]
+pub async fn check_air_quality_and_pay(
+    ctx:       Context<InsuranceState>,
+    policy_id: PolicyId,
+) -> RialoResult<()> {
+
+    require_unpaused(&ctx.state.config)?;
+    let now = ctx.clock.unix_timestamp;
+    let policy = ctx.state.policies.get(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+
+    require!(policy.status == PolicyStatus::Active, InsuranceError::PolicyNotActive);
+    require!(policy.is_covered_at(now), InsuranceError::PolicyExpired);
+    let cover = policy.air.ok_or_else(|| InsuranceError::InvalidState("Policy is not air-quality cover.".into()))?;
+    let point = policy.insured_point.ok_or_else(|| InsuranceError::InvalidState("Policy has no insured point.".into()))?;
+    require!(cover.can_check_at(now), InsuranceError::InvalidState("Air quality was checked less than an hour ago.".into()));
+
+    let underwriter_id = policy.underwriter_id;
+    let underwriter = ctx.state.underwriters.get(&underwriter_id).ok_or(InsuranceError::UnknownUnderwriter)?;
+    let source = underwriter.provider.base_url_for(ctx.state.config.network_mode).to_string();
+    let call_cost = underwriter.provider.cost_per_call;
+    // Readings from a provider under a declared incident can't be trusted either way
+    require!(!under_incident(&ctx.state, &source, now), InsuranceError::DataIncident);
+
+    let lease = lease_key(&mut ctx.state, underwriter_id, now)?;
+    let url = air_pollution_url(&source, &point, &lease.key);
+
+    let response = fetch(&url, &[]).await?;
+    if !report_key(&mut ctx.state, underwriter_id, &lease, response.status(), now) {
+        return Ok(());
+    }
+    let reading = parse_air_pollution(response.body())?;
+
+    record_lae(&mut ctx.state, policy_id, LaeKind::ProviderCost, call_cost);
+
+    let policy = ctx.state.policies.get_mut(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+    let threshold = policy.threshold.to_f64();
+    let cover = policy.air.as_mut().ok_or_else(|| InsuranceError::InvalidState("Policy is not air-quality cover.".into()))?;
+    let hazardous = cover.is_hazardous(&reading, threshold);
+    let triggered = cover.record(hazardous, now);
+    let (run, required) = (cover.run, cover.trigger.checks);
+
+    emit!(AirQualityChecked { policy_id, aqi: reading.aqi, pm2_5: reading.pm2_5, hazardous, run, required });
+
+    if triggered {
+        policy.status = PolicyStatus::PaidOut;
+        settlement::pay_out(&mut ctx.state, &ctx.vault, policy_id, None, Hundredths::from_f64(reading.pm2_5), now, now)?;
+    }
+
+    Ok(())
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct TemplateAirQualitySet { pub underwriter_id: UnderwriterId, pub template_id: TemplateId, pub trigger: Option<AqiTrigger> }
+#[rialo::event] pub struct AirQualityChecked     { pub policy_id: PolicyId, pub aqi: u8, pub pm2_5: f64, pub hazardous: bool, pub run: u32, pub required: u32 }
@@ -0,0 +1,109 @@
+// ============================================================
+//  Operator alerting
+//
+//  A contract-wide registry of webhooks (e.g. the operator's
+//  monitoring endpoint) that receive signed notifications for:
+//    • solvency breaches    — a published reserve proof below 100%
+//    • provider degradation — data incidents and audit mismatches
+//    • disputes             — arbiters opening a manual override
+//    • delivery failures    — settlement notifications out of
+//                             retries (outbox.rs)
+//  Each POST body is signed with HMAC-SHA256 under a key kept in
+//  sealed storage. Delivery is best effort: a failing endpoint is
+//  reported in an event but never reverts the instruction.
+// ============================================================
+
+use rialo_sdk::crypto::hmac_sha256;
+use rialo_sdk::http::{HttpRequest, Method};
+use rialo_sdk::prelude::*;
+use rialo_sdk::secrets;
+use serde::{Deserialize, Serialize};
+
+use crate::config::ContractConfig;
+use crate::InsuranceState;
+use crate::errors::InsuranceError;
+
+const MAX_ALERT_WEBHOOKS: usize = 4;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AlertKind {
+    SolvencyBreach,
+    ProviderDegraded,
+    Dispute,
+    DeliveryFailed,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct AlertWebhook {
+    pub url:    String,
+    pub secret: String,            // name of the signing key in sealed storage
+    pub kinds:  Vec<AlertKind>,    // alerts this endpoint subscribes to
+}
+
+#[derive(Serialize)]
+struct AlertPayload<'a, T: Serialize> {
+    kind:   AlertKind,
+    at:     i64,
+    detail: &'a T,
+}
+
+// ── Entry point: admin replaces the webhook registry ─────────
+#[rialo::instruction]
+pub async fn set_alert_webhooks(
+    ctx:      Context<InsuranceState>,
+    webhooks: Vec<AlertWebhook>,
+) -> RialoResult<()> {
+
+    require!(ctx.state.config.admin == *ctx.signer, InsuranceError::Unauthorized("Only the admin can change alert webhooks.".into()));
+    require!(webhooks.len() <= MAX_ALERT_WEBHOOKS, InsuranceError::InvalidArgument("Too many alert webhooks.".into()));
+    require!(webhooks.iter().all(|w| w.url.starts_with("https://")), InsuranceError::InvalidArgument("Alert webhooks must use https.".into()));
+    require!(webhooks.iter().all(|w| !w.kinds.is_empty()), InsuranceError::InvalidArgument("Alert webhooks must subscribe to at least one alert.".into()));
+
+    // URLs can embed tokens, so only the count is published
+    emit!(AlertWebhooksSet { webhooks: webhooks.len() as u32 });
+
+    ctx.state.config.alert_webhooks = webhooks;
+
+    Ok(())
+}
+
+// Notify every webhook subscribed to `kind`
+pub(crate) async fn raise<T: Serialize>(config: &ContractConfig, kind: AlertKind, at: i64, detail: &T) {
+    let body = serde_json::to_vec(&AlertPayload { kind, at, detail }).unwrap_or_default();
+
+    let mut delivered = 0;
+    for (index, webhook) in config.alert_webhooks.iter().enumerate() {
+        if !webhook.kinds.contains(&kind) {
+            continue;
+        }
+        match deliver(webhook, &body).await {
+            Ok(()) => delivered += 1,
+            Err(_) => emit!(AlertDeliveryFailed { kind, webhook: index as u32 }),
+        }
+    }
+
+    emit!(AlertRaised { kind, at, delivered });
+}
+
+async fn deliver(webhook: &AlertWebhook, body: &[u8]) -> RialoResult<()> {
+    let key = secrets::get(&webhook.secret)?;
+    let signature: String = hmac_sha256(key.as_bytes(), body)
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect();
+
+    let response = HttpRequest::new(Method::POST, &webhook.url)
+        .header("Content-Type", "application/json")
+        .header("X-Signature", &format!("sha256={signature}"))
+        .body(body.to_vec())
+        .send()
+        .await?;
+
+    require!((200..300).contains(&response.status()), InsuranceError::InvalidState("Alert webhook rejected the notification.".into()));
+    Ok(())
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct AlertWebhooksSet    { pub webhooks: u32 }
+#[rialo::event] pub struct AlertRaised         { pub kind: AlertKind, pub at: i64, pub delivered: u32 }
+#[rialo::event] pub struct AlertDeliveryFailed { pub kind: AlertKind, pub webhook: u32 }
@@ -0,0 +1,132 @@
+// ============================================================
+//  Anomalous observations
+//
+//  A provider that reports 400 mm in an hour over Nairobi is far
+//  more likely broken than right. Next to its climatology
+//  (seasonal.rs) an underwriter can keep, per location and metric,
+//  the 99.9th percentile of the hourly readings each calendar
+//  month has seen. A check whose reading lands above it — in the
+//  location's local month, if the underwriter has recorded its
+//  UTC offset — can't settle on that reading alone:
+//    • an independent audit provider (audit.rs), not the one the
+//      reading came from, reads the same hour
+//    • if it's also above the percentile the spike is real and
+//      the check settles on it as usual
+//    • if it isn't, or no second source could answer, the check
+//      settles nothing; the next check reads afresh
+//  Either way `AnomalousObservation` is emitted with both
+//  readings. The second call is the underwriter's cost, booked as
+//  loss adjustment. Locations without a percentile on record are
+//  never screened.
+// ============================================================
+
+use rialo_sdk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::claims::{record_lae, LaeKind};
+use crate::errors::InsuranceError;
+use crate::millimeters::Hundredths;
+use crate::normalization::{location_slug, Metric};
+use crate::oracle::CallBudget;
+use crate::policy::PolicyId;
+use crate::providers::{ReadingTime, WeatherProvider};
+use crate::seasonal::{local_time, month_of};
+use crate::underwriter::{tenant_mut, UnderwriterId};
+use crate::{fetch, InsuranceState};
+
+// Highest 99.9th-percentile hourly readings, January first
+pub type MonthlyExtremes = [f64; 12];
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ExtremeTable {
+    pub metric: Metric,
+    pub p999:   MonthlyExtremes,
+}
+
+// The percentile `reading` is screened against, if it exceeds one on record
+pub fn exceeded(tables: &[ExtremeTable], metric: Metric, reading: Hundredths, observed_at: i64, utc_offset: i32) -> Option<Hundredths> {
+    let table = tables.iter().find(|t| t.metric == metric)?;
+    let p999 = Hundredths::from_f64(table.p999[month_of(local_time(observed_at, utc_offset))]);
+    (reading > p999).then_some(p999)
+}
+
+// Whether a check may settle on `reading` from `source`. An anomalous one
+// needs a second source to see the spike too, spending a call from `budget`.
+pub(crate) async fn screen(
+    state:       &mut InsuranceState,
+    policy_id:   PolicyId,
+    source:      &str,
+    reading:     Hundredths,
+    when:        ReadingTime,
+    observed_at: i64,
+    budget:      &mut CallBudget,
+) -> RialoResult<bool> {
+
+    let policy = state.policies.get(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+    let underwriter = state.underwriters.get(&policy.underwriter_id).ok_or(InsuranceError::UnknownUnderwriter)?;
+    let tables = underwriter.extremes.get(&policy.place.key()).map_or(&[][..], Vec::as_slice);
+    let Some(p999) = exceeded(tables, policy.peril, reading, observed_at, policy.utc_offset) else {
+        return Ok(true);
+    };
+
+    let (place, peril) = (policy.place.clone(), policy.peril);
+    let mode = state.config.network_mode;
+    let second_source = state.config.audit.providers
+        .iter()
+        .find(|p| p.base_url_for(mode) != source && p.api_key().is_ok())
+        .cloned();
+
+    let mut second = None;
+    if let Some(provider) = second_source.filter(|_| budget.try_spend()) {
+        let base = provider.base_url_for(mode);
+        let url = provider.kind.build_request(base, &provider.api_key()?, &place, when);
+        let response = fetch(&url, &[]).await;
+        record_lae(state, policy_id, LaeKind::ProviderCost, provider.cost_per_call);
+        second = response
+            .ok()
+            .filter(|r| (200..300).contains(&r.status()))
+            .and_then(|r| provider.kind.parse_observation(when, r.body()).ok())
+            .and_then(|o| o.metric(peril));
+    }
+
+    let confirmed = second.is_some_and(|s| s > p999);
+    emit!(AnomalousObservation { policy_id, source: source.to_string(), metric: peril, reading, p999, second, confirmed });
+
+    Ok(confirmed)
+}
+
+// ── Entry point: underwriter sets a location's monthly extremes
+#[rialo::instruction]
+pub async fn set_extremes(
+    ctx:            Context<InsuranceState>,
+    underwriter_id: UnderwriterId,
+    location:       String,
+    metric:         Metric,
+    p999:           Option<MonthlyExtremes>,   // None stops screening the metric there
+) -> RialoResult<()> {
+
+    let location = location_slug(&location);
+    require!(!location.is_empty(), InsuranceError::InvalidArgument("Location is required.".into()));
+    require!(metric.is_observed(), InsuranceError::InvalidArgument("Only readings from the weather provider can be screened.".into()));
+    if let Some(p999) = &p999 {
+        require!(p999.iter().all(|v| v.is_finite()), InsuranceError::InvalidArgument("Percentiles must be numbers.".into()));
+    }
+
+    let underwriter = tenant_mut(&mut ctx.state, underwriter_id, &ctx.signer)?;
+    let tables = underwriter.extremes.entry(location.clone()).or_default();
+    tables.retain(|t| t.metric != metric);
+    if let Some(p999) = p999 {
+        tables.push(ExtremeTable { metric, p999 });
+    }
+    if tables.is_empty() {
+        underwriter.extremes.remove(&location);
+    }
+
+    emit!(ExtremesSet { underwriter_id, location, metric, p999 });
+
+    Ok(())
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct ExtremesSet          { pub underwriter_id: UnderwriterId, pub location: String, pub metric: Metric, pub p999: Option<MonthlyExtremes> }
+#[rialo::event] pub struct AnomalousObservation { pub policy_id: PolicyId, pub source: String, pub metric: Metric, pub reading: Hundredths, pub p999: Hundredths, pub second: Option<Hundredths>, pub confirmed: bool }
@@ -0,0 +1,47 @@
+// ============================================================
+//  Multi-signature approval records
+//
+//  An action needing M-of-N sign-off is identified by the hash of
+//  its parameters. Each co-signer submits the same parameters; the
+//  record collects distinct approvers until the threshold is met,
+//  at which point the caller executes the action and clears it.
+// ============================================================
+
+use std::collections::BTreeMap;
+
+use rialo_sdk::crypto::sha256;
+use rialo_sdk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::Hash;
+use crate::errors::InsuranceError;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ApprovalRecord {
+    pub approvers: Vec<Pubkey>,   // distinct co-signers so far
+    pub opened_at: i64,           // first approval
+}
+
+// Digest identifying an action by its full parameter set
+pub fn subject_hash<T: Serialize>(action: &T) -> Hash {
+    sha256(&serde_json::to_vec(action).unwrap_or_default())
+}
+
+// Record `signer`'s approval of `subject`; returns the approval count so far
+pub fn approve(
+    approvals: &mut BTreeMap<Hash, ApprovalRecord>,
+    subject:   Hash,
+    signer:    Pubkey,
+    now:       i64,
+) -> RialoResult<usize> {
+
+    let record = approvals.entry(subject).or_insert_with(|| ApprovalRecord {
+        approvers: Vec::new(),
+        opened_at: now,
+    });
+
+    require!(!record.approvers.contains(&signer), InsuranceError::InvalidState("Signer already approved this action.".into()));
+    record.approvers.push(signer);
+
+    Ok(record.approvers.len())
+}
@@ -0,0 +1,225 @@
+// ============================================================
+//  Arbiter oracle override
+//
+//  When every provider is down or clearly wrong, the arbiters
+//  (an M-of-N multi-sig configured by the admin) can jointly
+//  submit a manual observation for one policy. It settles through
+//  the normal payout path but is logged separately from API
+//  observations, carries a hash of the off-chain justification,
+//  and is rate-limited per policy so abuse stays visible and
+//  bounded.
+//
+//  Manual settlement is the last resort, for a policy no provider
+//  will ever read again — the API is gone for good, or its
+//  location no longer resolves — so its payout isn't stuck. The
+//  arbiters jointly settle it, once, on a reading they publish
+//  the evidence for at a URI: the normal payout formula applies,
+//  on the hour of settlement, or on the last covered hour once
+//  cover has ended. ManuallySettled sets it apart from triggers
+//  the oracle drove.
+// ============================================================
+
+use rialo_sdk::crypto::sha256;
+use rialo_sdk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::alerts::{self, AlertKind};
+use crate::approvals::{approve, subject_hash};
+use crate::errors::InsuranceError;
+use crate::millimeters::Hundredths;
+use crate::money::Ralo;
+use crate::policy::{Policy, PolicyId, PolicyStatus};
+use crate::whitelist::{self, Role};
+use crate::{settlement, Hash, InsuranceState};
+
+const MAX_MANUAL_OBSERVATIONS_PER_POLICY: usize = 3;
+const MANUAL_OBSERVATION_COOLDOWN_SECS:   i64   = 24 * 60 * 60;
+pub const MAX_EVIDENCE_URI_LEN:           usize = 256;
+
+// Applied manual observations — kept apart from oracle readings
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ManualObservation {
+    pub policy_id:          PolicyId,
+    pub rainfall_mm:        f64,
+    pub justification_hash: Hash,          // digest of the off-chain evidence
+    pub approvers:          Vec<Pubkey>,   // arbiters who signed off
+    pub applied_at:         i64,
+    pub triggered:          bool,
+    pub evidence_uri:       Option<String>,   // set on a manual settlement; the hash is of the URI
+}
+
+// The exact parameters every co-signing arbiter must agree on
+#[derive(Serialize)]
+struct ManualObservationAction {
+    action:             &'static str,
+    policy_id:          PolicyId,
+    rainfall_mm:        f64,
+    justification_hash: Hash,
+}
+
+#[derive(Serialize)]
+struct ManualSettlementAction<'a> {
+    action:       &'static str,
+    policy_id:    PolicyId,
+    rainfall_mm:  f64,
+    evidence_uri: &'a str,
+}
+
+// When a manual settlement reads the weather: now while cover runs, the
+// last covered second once it has ended; never before cover starts
+pub fn settlement_time(policy: &Policy, now: i64) -> Option<i64> {
+    let (start, end) = (policy.activated_at?, policy.coverage_end()?);
+    (now >= start).then(|| now.min(end - 1))
+}
+
+// ── Entry point: arbiter submits / co-signs a manual reading ─
+#[rialo::instruction]
+pub async fn submit_manual_observation(
+    ctx:                Context<InsuranceState>,
+    policy_id:          PolicyId,
+    rainfall_mm:        f64,
+    justification_hash: Hash,
+) -> RialoResult<()> {
+
+    let now = ctx.clock.unix_timestamp;
+    let state = &mut ctx.state;
+
+    require!(state.config.arbiters.contains(&ctx.signer), InsuranceError::Unauthorized("Signer is not an arbiter.".into()));
+    whitelist::require_role(state, &ctx.signer, Role::Arbiter)?;
+    require!(rainfall_mm >= 0.0, InsuranceError::InvalidState("Rainfall cannot be negative.".into()));
+
+    let policy = state.policies.get(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+    require!(policy.status == PolicyStatus::Active, InsuranceError::PolicyNotActive);
+
+    // Rate limits: a handful per policy, at most one a day
+    let history: Vec<&ManualObservation> = state.manual_observations
+        .iter()
+        .filter(|o| o.policy_id == policy_id)
+        .collect();
+    require!(history.len() < MAX_MANUAL_OBSERVATIONS_PER_POLICY, InsuranceError::InvalidState("Manual observation limit reached for this policy.".into()));
+    if let Some(last) = history.last() {
+        require!(now - last.applied_at >= MANUAL_OBSERVATION_COOLDOWN_SECS, InsuranceError::InvalidState("Manual observation submitted too recently.".into()));
+    }
+
+    let subject = subject_hash(&ManualObservationAction {
+        action: "submit_manual_observation",
+        policy_id,
+        rainfall_mm,
+        justification_hash,
+    });
+    let approvals = approve(&mut state.approvals, subject, *ctx.signer, now)?;
+    let required  = state.config.arbiter_threshold as usize;
+
+    let submitted = ManualObservationSubmitted {
+        policy_id,
+        arbiter: *ctx.signer,
+        rainfall_mm,
+        justification_hash,
+        approvals: approvals as u32,
+        required:  required as u32,
+    };
+
+    // The first signature opens the override — that is the dispute operators need to hear about
+    if approvals == 1 {
+        alerts::raise(&state.config, AlertKind::Dispute, now, &submitted).await;
+    }
+    emit!(submitted);
+
+    if approvals < required {
+        return Ok(());
+    }
+
+    let record = state.approvals.remove(&subject).ok_or_else(|| InsuranceError::InvalidState("Approval record missing.".into()))?;
+    let triggered = settlement::settle(state, &ctx.vault, policy_id, None, Hundredths::from_f64(rainfall_mm), now, now)?;
+
+    state.manual_observations.push(ManualObservation {
+        policy_id,
+        rainfall_mm,
+        justification_hash,
+        approvers:  record.approvers,
+        applied_at: now,
+        triggered,
+        evidence_uri: None,
+    });
+
+    emit!(ManualObservationApplied { policy_id, rainfall_mm, justification_hash, triggered });
+
+    Ok(())
+}
+
+// ── Entry point: arbiters settle a policy no provider can read ─
+#[rialo::instruction]
+pub async fn manual_settle(
+    ctx:          Context<InsuranceState>,
+    policy_id:    PolicyId,
+    rainfall_mm:  f64,
+    evidence_uri: String,   // where the evidence for the reading is published: https:// or ipfs://
+) -> RialoResult<()> {
+
+    let now = ctx.clock.unix_timestamp;
+    let state = &mut ctx.state;
+
+    require!(state.config.arbiters.contains(&ctx.signer), InsuranceError::Unauthorized("Signer is not an arbiter.".into()));
+    whitelist::require_role(state, &ctx.signer, Role::Arbiter)?;
+    require!(rainfall_mm.is_finite() && rainfall_mm >= 0.0, InsuranceError::InvalidState("Rainfall cannot be negative.".into()));
+    require!(evidence_uri.len() <= MAX_EVIDENCE_URI_LEN, InsuranceError::InvalidArgument("Evidence URI is longer than 256 bytes.".into()));
+    require!(evidence_uri.starts_with("https://") || evidence_uri.starts_with("ipfs://"), InsuranceError::InvalidArgument("Evidence URI must be https:// or ipfs://.".into()));
+
+    let policy = state.policies.get(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+    require!(policy.status == PolicyStatus::Active, InsuranceError::PolicyNotActive);
+    require!(policy.settles_on_readings(), InsuranceError::InvalidState("Policy does not settle on a reading.".into()));
+    let observed_at = settlement_time(policy, now).ok_or(InsuranceError::PolicyNotActive)?;
+    let settled = state.manual_observations.iter().any(|o| o.policy_id == policy_id && o.evidence_uri.is_some());
+    require!(!settled, InsuranceError::InvalidState("Policy has already been settled manually.".into()));
+
+    let subject = subject_hash(&ManualSettlementAction {
+        action: "manual_settle",
+        policy_id,
+        rainfall_mm,
+        evidence_uri: &evidence_uri,
+    });
+    let approvals = approve(&mut state.approvals, subject, *ctx.signer, now)?;
+    let required  = state.config.arbiter_threshold as usize;
+
+    let submitted = ManualSettlementSubmitted {
+        policy_id,
+        arbiter:      *ctx.signer,
+        rainfall_mm,
+        evidence_uri: evidence_uri.clone(),
+        approvals:    approvals as u32,
+        required:     required as u32,
+    };
+    if approvals == 1 {
+        alerts::raise(&state.config, AlertKind::Dispute, now, &submitted).await;
+    }
+    emit!(submitted);
+
+    if approvals < required {
+        return Ok(());
+    }
+
+    let record = state.approvals.remove(&subject).ok_or_else(|| InsuranceError::InvalidState("Approval record missing.".into()))?;
+    let paid_before = state.policies.get(&policy_id).map_or(Ralo::ZERO, |p| p.paid_out);
+    let triggered = settlement::settle(state, &ctx.vault, policy_id, None, Hundredths::from_f64(rainfall_mm), observed_at, now)?;
+    let paid = state.policies.get(&policy_id).map_or(Ralo::ZERO, |p| p.paid_out.saturating_sub(paid_before));
+
+    state.manual_observations.push(ManualObservation {
+        policy_id,
+        rainfall_mm,
+        justification_hash: sha256(evidence_uri.as_bytes()),
+        approvers:          record.approvers.clone(),
+        applied_at:         now,
+        triggered,
+        evidence_uri:       Some(evidence_uri.clone()),
+    });
+
+    emit!(ManuallySettled { policy_id, rainfall_mm, evidence_uri, approvers: record.approvers, observed_at, triggered, paid });
+
+    Ok(())
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct ManualObservationSubmitted { pub policy_id: PolicyId, pub arbiter: Pubkey, pub rainfall_mm: f64, pub justification_hash: Hash, pub approvals: u32, pub required: u32 }
+#[rialo::event] pub struct ManualObservationApplied   { pub policy_id: PolicyId, pub rainfall_mm: f64, pub justification_hash: Hash, pub triggered: bool }
+#[rialo::event] pub struct ManualSettlementSubmitted  { pub policy_id: PolicyId, pub arbiter: Pubkey, pub rainfall_mm: f64, pub evidence_uri: String, pub approvals: u32, pub required: u32 }
+#[rialo::event] pub struct ManuallySettled            { pub policy_id: PolicyId, pub rainfall_mm: f64, pub evidence_uri: String, pub approvers: Vec<Pubkey>, pub observed_at: i64, pub triggered: bool, pub paid: Ralo }
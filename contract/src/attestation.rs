@@ -0,0 +1,190 @@
+// ============================================================
+//  Loss attestation
+//
+//  Some regulated products can't pay on an index alone: the
+//  beneficiary has to attest that a loss actually happened. A
+//  template can require it, and each payout on its policies then
+//  waits for one:
+//    trigger            → nothing is paid yet; the policy waits in
+//                         PendingAttestation with the payout it is
+//                         owed, and `AttestationRequired` says so
+//    attest_loss        → the beneficiary signs in the hash of
+//                         their attestation (the document itself
+//                         stays off-chain), and the payout settles
+//                         there and then, under the round that
+//                         triggered it
+//    lapse_attestation  → an attestation not given within
+//                         ATTESTATION_WINDOW_SECS of the trigger
+//                         forfeits the payout: anyone may then
+//                         expire the policy and hand its reserve
+//                         back to the underwriter
+//  An attestation covers the one settlement it was given for:
+//  graded and tiered cover ask again for each step. Every hash is
+//  kept on the policy, next to the round it answered.
+// ============================================================
+
+use rialo_sdk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::InsuranceError;
+use crate::fx::DeferredPayout;
+use crate::millimeters::Hundredths;
+use crate::config::require_unpaused;
+use crate::money::Ralo;
+use crate::observations::CheckId;
+use crate::policy::{Policy, PolicyId, PolicyStatus};
+use crate::underwriter::{tenant_mut, TemplateId, UnderwriterId};
+use crate::{mints, settlement, Hash, InsuranceState};
+
+// How long the beneficiary has to attest a triggered loss
+pub const ATTESTATION_WINDOW_SECS: i64 = 30 * 24 * 60 * 60;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Attestation {
+    pub round_id:    Option<CheckId>,   // settlement it was given for
+    pub hash:        Hash,              // hash of the signed attestation
+    pub attested_at: i64,
+}
+
+// Attestation state carried by a policy sold under an attesting template
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct AttestationCover {
+    pub pending:      Option<DeferredPayout>,   // payout waiting on the beneficiary
+    pub attestations: Vec<Attestation>,         // in the order given
+    #[serde(default)]
+    pub deadline:     Option<i64>,              // attest by; unset if parked before there was one
+}
+
+impl AttestationCover {
+    pub fn attested(&self, round_id: Option<CheckId>) -> bool {
+        self.attestations.iter().any(|a| a.round_id == round_id)
+    }
+
+    // When the waiting payout lapses. One parked without a deadline runs
+    // the window from the reading that triggered it.
+    pub fn lapses_at(&self) -> Option<i64> {
+        let pending = self.pending.as_ref()?;
+        Some(self.deadline.unwrap_or(pending.observed_at + ATTESTATION_WINDOW_SECS))
+    }
+}
+
+// Forfeit a payout whose attestation is overdue: the policy expires unpaid and
+// the cover it still held is returned, for the caller to release from the
+// underwriter's reserve
+pub fn lapse(policy: &mut Policy, now: i64) -> RialoResult<(Option<CheckId>, Ralo)> {
+    require!(policy.status == PolicyStatus::PendingAttestation, InsuranceError::InvalidState("Policy has no payout waiting on an attestation.".into()));
+    let cover = policy.attestation.as_mut().ok_or_else(|| InsuranceError::InvalidState("Policy does not need attestation.".into()))?;
+    let lapses_at = cover.lapses_at().ok_or_else(|| InsuranceError::InvalidState("Policy has no payout waiting on an attestation.".into()))?;
+    require!(now >= lapses_at, InsuranceError::InvalidState("The beneficiary can still attest this loss.".into()));
+
+    let pending = cover.pending.take().ok_or_else(|| InsuranceError::InvalidState("Policy has no payout waiting on an attestation.".into()))?;
+    cover.deadline = None;
+    policy.status = PolicyStatus::Expired;
+
+    Ok((pending.round_id, policy.coverage_remaining()))
+}
+
+// Whether a payout may go ahead; if it needs an attestation it hasn't got,
+// parks it on the policy and returns false
+pub(crate) fn await_attestation(
+    state:       &mut InsuranceState,
+    policy_id:   PolicyId,
+    round_id:    Option<CheckId>,
+    share_bps:   u64,
+    reading:     Hundredths,
+    observed_at: i64,
+    now:         i64,
+) -> RialoResult<bool> {
+
+    let policy = state.policies.get_mut(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+    let Some(cover) = policy.attestation.as_mut() else {
+        return Ok(true);
+    };
+    if cover.attested(round_id) {
+        return Ok(true);
+    }
+
+    cover.pending = Some(DeferredPayout::new(round_id, share_bps, reading, observed_at));
+    cover.deadline = Some(now + ATTESTATION_WINDOW_SECS);
+    policy.status = PolicyStatus::PendingAttestation;
+
+    emit!(AttestationRequired { policy_id, round_id, beneficiary: policy.owner, reading, deadline: now + ATTESTATION_WINDOW_SECS });
+    Ok(false)
+}
+
+// ── Entry point: require (or stop requiring) loss attestation on a template
+#[rialo::instruction]
+pub async fn set_template_attestation(
+    ctx:            Context<InsuranceState>,
+    underwriter_id: UnderwriterId,
+    template_id:    TemplateId,
+    required:       bool,
+) -> RialoResult<()> {
+
+    let underwriter = tenant_mut(&mut ctx.state, underwriter_id, &ctx.signer)?;
+    let template = underwriter.templates.get_mut(&template_id).ok_or(InsuranceError::UnknownTemplate)?;
+    template.attestation_required = required;
+
+    emit!(TemplateAttestationSet { underwriter_id, template_id, required });
+
+    Ok(())
+}
+
+// ── Entry point: beneficiary attests a loss; the waiting payout settles
+#[rialo::instruction]
+pub async fn attest_loss(
+    ctx:              Context<InsuranceState>,
+    policy_id:        PolicyId,
+    attestation_hash: Hash,
+) -> RialoResult<()> {
+
+    require_unpaused(&ctx.state.config)?;
+    let now = ctx.clock.unix_timestamp;
+    let policy = ctx.state.policies.get_mut(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+
+    require!(policy.owner == *ctx.signer, InsuranceError::Unauthorized("Only the beneficiary can attest a loss.".into()));
+    require!(policy.status == PolicyStatus::PendingAttestation, InsuranceError::InvalidState("Policy has no payout waiting on an attestation.".into()));
+    require!(attestation_hash != Hash::default(), InsuranceError::InvalidArgument("Attestation hash is empty.".into()));
+    let cover = policy.attestation.as_mut().ok_or_else(|| InsuranceError::InvalidState("Policy does not need attestation.".into()))?;
+    require!(cover.lapses_at().is_none_or(|at| now < at), InsuranceError::InvalidState("The attestation window has closed.".into()));
+    let pending = cover.pending.take().ok_or_else(|| InsuranceError::InvalidState("Policy has no payout waiting on an attestation.".into()))?;
+    cover.deadline = None;
+
+    cover.attestations.push(Attestation { round_id: pending.round_id, hash: attestation_hash, attested_at: now });
+    // Back to live cover to settle; a USD payout can still defer on the price
+    policy.status = PolicyStatus::Active;
+
+    emit!(LossAttested { policy_id, round_id: pending.round_id, attestation_hash });
+
+    // Paid and announced under the round that triggered it, not the attestation
+    settlement::pay_share(&mut ctx.state, &ctx.vault, policy_id, pending.round_id, pending.share_bps, pending.value, pending.observed_at, now)?;
+
+    Ok(())
+}
+
+// ── Entry point: anyone expires a policy whose attestation is overdue
+#[rialo::instruction]
+pub async fn lapse_attestation(
+    ctx:       Context<InsuranceState>,
+    policy_id: PolicyId,
+) -> RialoResult<()> {
+
+    require_unpaused(&ctx.state.config)?;
+    let now = ctx.clock.unix_timestamp;
+    let policy = ctx.state.policies.get_mut(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+    let (round_id, released) = lapse(policy, now)?;
+
+    let underwriter = ctx.state.underwriters.get_mut(&policy.underwriter_id).ok_or(InsuranceError::UnknownUnderwriter)?;
+    underwriter.reserved = underwriter.reserved.saturating_sub(released);
+    mints::release(&mut ctx.state, policy_id);
+
+    emit!(AttestationLapsed { policy_id, round_id, released });
+
+    Ok(())
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct TemplateAttestationSet { pub underwriter_id: UnderwriterId, pub template_id: TemplateId, pub required: bool }
+#[rialo::event] pub struct AttestationRequired    { pub policy_id: PolicyId, pub round_id: Option<CheckId>, pub beneficiary: Pubkey, pub reading: Hundredths, pub deadline: i64 }
+#[rialo::event] pub struct LossAttested           { pub policy_id: PolicyId, pub round_id: Option<CheckId>, pub attestation_hash: Hash }
+#[rialo::event] pub struct AttestationLapsed      { pub policy_id: PolicyId, pub round_id: Option<CheckId>, pub released: Ralo }
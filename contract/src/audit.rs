@@ -0,0 +1,141 @@
+// ============================================================
+//  Audit re-checks
+//
+//  A weighted random sample of settled readings is re-fetched
+//  from a different provider, after the fact and without holding
+//  up settlement. Triggered readings — the ones that moved money —
+//  are drawn at a higher rate than quiet ones. Each re-check emits
+//  `AuditMatch` or `AuditMismatch`; mismatches are kept on the
+//  check record as evidence for disputes.
+// ============================================================
+
+use rialo_sdk::crypto::sha256;
+use rialo_sdk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::alerts::{self, AlertKind};
+use crate::claims::{record_lae, LaeKind};
+use crate::errors::InsuranceError;
+use crate::millimeters::Hundredths;
+use crate::normalization::Station;
+use crate::observations::CheckId;
+use crate::policy::PolicyId;
+use crate::providers::{ReadingTime, WeatherProvider};
+use crate::underwriter::ProviderConfig;
+use crate::{fetch, InsuranceState};
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct AuditConfig {
+    pub providers:          Vec<ProviderConfig>,   // independent sources used for re-checks
+    pub sample_rate_bps:    u64,                   // chance a non-triggering reading is drawn
+    pub triggered_rate_bps: u64,                   // chance a triggering reading is drawn
+    pub tolerance_mm:       f64,                   // largest difference still counted as a match
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct AuditOutcome {
+    pub source:      String,
+    pub station:     Station,       // where the audit provider says it read it
+    #[serde(default)]
+    pub reading:     Hundredths,    // in hundredths of the peril's unit
+    #[serde(rename = "rainfall_mm", default, skip_serializing)]
+    pub old_reading: Option<f64>,   // layout 5 and earlier only: now `reading` (migrations.rs)
+    pub matched:     bool,
+}
+
+// Decide at record time whether a check is sampled for audit
+pub fn draw(config: &AuditConfig, check_id: CheckId, policy_id: PolicyId, observed_at: i64, triggered: bool) -> bool {
+    let rate_bps = if triggered { config.triggered_rate_bps } else { config.sample_rate_bps };
+    if rate_bps == 0 {
+        return false;
+    }
+
+    let mut seed = Vec::with_capacity(24);
+    seed.extend_from_slice(&check_id.to_le_bytes());
+    seed.extend_from_slice(&policy_id.to_le_bytes());
+    seed.extend_from_slice(&observed_at.to_le_bytes());
+    let digest = sha256(&seed);
+
+    let roll = u64::from_le_bytes(digest[..8].try_into().unwrap_or_default()) % 10_000;
+    roll < rate_bps
+}
+
+// ── Entry point: admin configures audit sources and sampling ─
+#[rialo::instruction]
+pub async fn set_audit_config(
+    ctx:    Context<InsuranceState>,
+    config: AuditConfig,
+) -> RialoResult<()> {
+
+    require!(ctx.state.config.admin == *ctx.signer, InsuranceError::Unauthorized("Only the admin can change audit settings.".into()));
+    require!(config.sample_rate_bps <= 10_000, InsuranceError::InvalidArgument("Sample rate must be at most 100%.".into()));
+    require!(config.triggered_rate_bps <= 10_000, InsuranceError::InvalidArgument("Triggered sample rate must be at most 100%.".into()));
+    require!(config.tolerance_mm >= 0.0, InsuranceError::InvalidState("Audit tolerance cannot be negative.".into()));
+
+    emit!(AuditConfigChanged {
+        providers:          config.providers.len() as u32,
+        sample_rate_bps:    config.sample_rate_bps,
+        triggered_rate_bps: config.triggered_rate_bps,
+        tolerance_mm:       config.tolerance_mm,
+    });
+
+    ctx.state.config.audit = config;
+
+    Ok(())
+}
+
+// ── Entry point: anyone runs a drawn audit re-check ──────────
+#[rialo::instruction]
+pub async fn audit_check(
+    ctx:       Context<InsuranceState>,
+    policy_id: PolicyId,
+    check_id:  CheckId,
+) -> RialoResult<()> {
+
+    let mode = ctx.state.config.network_mode;
+    let record = ctx.state.checks.get(&check_id).ok_or_else(|| InsuranceError::UnknownRecord("Unknown check.".into()))?;
+
+    require!(record.policy_id == policy_id, InsuranceError::InvalidState("Check does not belong to this policy.".into()));
+    require!(record.audit_selected, InsuranceError::InvalidState("Check was not drawn for audit.".into()));
+    require!(record.audit.is_none(), InsuranceError::InvalidState("Check has already been audited.".into()));
+
+    // Must come from somewhere other than the reading being audited
+    let provider = ctx.state.config.audit.providers
+        .iter()
+        .find(|p| p.base_url_for(mode) != record.source)
+        .ok_or_else(|| InsuranceError::InvalidState("No independent audit provider configured.".into()))?;
+
+    let source = provider.base_url_for(mode).to_string();
+    let when = ReadingTime::Hour(record.observed_at);
+    let policy    = ctx.state.policies.get(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+    let url = provider.kind.build_request(&source, &provider.api_key()?, &policy.place, when);
+    let call_cost = provider.cost_per_call;
+    let original  = record.reading;
+    let peril     = policy.peril;
+    let tolerance = Hundredths::from_f64(ctx.state.config.audit.tolerance_mm);
+
+    let response = fetch(&url, &[]).await?;
+    let observation = provider.kind.parse_observation(when, response.body())?;
+    let audited = observation.metric(peril).ok_or_else(|| InsuranceError::BadResponse("Audit provider has no reading for this peril.".into()))?;
+    let matched = audited.0.abs_diff(original.0) <= tolerance.0.unsigned_abs();
+
+    if let Some(record) = ctx.state.checks.get_mut(&check_id) {
+        record.audit = Some(AuditOutcome { source: source.clone(), station: observation.station, reading: audited, old_reading: None, matched });
+    }
+    record_lae(&mut ctx.state, policy_id, LaeKind::ProviderCost, call_cost);
+
+    if matched {
+        emit!(AuditMatch { policy_id, check_id, source, original, audited });
+    } else {
+        let mismatch = AuditMismatch { policy_id, check_id, source, original, audited };
+        alerts::raise(&ctx.state.config, AlertKind::ProviderDegraded, ctx.clock.unix_timestamp, &mismatch).await;
+        emit!(mismatch);
+    }
+
+    Ok(())
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct AuditConfigChanged { pub providers: u32, pub sample_rate_bps: u64, pub triggered_rate_bps: u64, pub tolerance_mm: f64 }
+#[rialo::event] pub struct AuditMatch         { pub policy_id: PolicyId, pub check_id: CheckId, pub source: String, pub original: Hundredths, pub audited: Hundredths }
+#[rialo::event] pub struct AuditMismatch      { pub policy_id: PolicyId, pub check_id: CheckId, pub source: String, pub original: Hundredths, pub audited: Hundredths }
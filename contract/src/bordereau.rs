@@ -0,0 +1,159 @@
+// ============================================================
+//  Claims bordereaux
+//
+//  Reinsurers reconcile a parametric book from a bordereau: one
+//  line per claim paid in a period, with the policy, the index
+//  reading that triggered it and what was paid gross and ceded.
+//  Building it by hand from events is the biggest operating cost
+//  of a programme, so the contract builds it from its own
+//  settlement receipts (receipts.rs).
+//
+//  Cession is a quota share on a risks-attaching basis: a tenant
+//  sets the share of each claim it cedes, and a policy keeps the
+//  share in force when it was written, so a treaty change never
+//  reprices claims already on the book. An export is returned to
+//  the caller and announced with a digest of its lines, so the
+//  copy a reinsurer receives off-chain can be checked against
+//  the chain.
+// ============================================================
+
+use rialo_sdk::crypto::sha256;
+use rialo_sdk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::InsuranceError;
+use crate::millimeters::Hundredths;
+use crate::money::Ralo;
+use crate::normalization::Metric;
+use crate::observations::CheckId;
+use crate::policy::{Policy, PolicyId};
+use crate::receipts::SettlementReceipt;
+use crate::underwriter::{tenant_mut, TemplateId, UnderwriterId};
+use crate::{Hash, InsuranceState};
+
+// Keeps an export inside one instruction's return size
+pub const MAX_BORDEREAU_LINES: usize = 500;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct BordereauLine {
+    pub policy_id:   PolicyId,
+    pub template_id: TemplateId,
+    pub paid_to:     Pubkey,            // account the claim was paid to
+    pub location:    String,
+    pub peril:       Metric,
+    pub threshold:   Hundredths,
+    pub index_value: Hundredths,        // reading that settled the claim
+    pub observed_at: i64,               // when that reading was taken
+    pub round_id:    Option<CheckId>,   // provider check it came from, if logged
+    pub paid_at:     i64,               // trigger date: when the claim was paid
+    pub gross:       Ralo,
+    pub ceded:       Ralo,              // gross × the policy's ceded share
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Bordereau {
+    pub underwriter_id: UnderwriterId,
+    pub period_start:   i64,
+    pub period_end:     i64,               // exclusive
+    pub lines:          Vec<BordereauLine>,
+    pub gross:          Ralo,
+    pub ceded:          Ralo,
+    pub digest:         Hash,              // sha256 of the lines as JSON
+}
+
+// One claim as a bordereau line
+pub fn bordereau_line(policy: &Policy, paid_to: Pubkey, receipt: &SettlementReceipt) -> BordereauLine {
+    BordereauLine {
+        policy_id:   receipt.policy_id,
+        template_id: policy.template_id,
+        paid_to,
+        location:    policy.location.clone(),
+        peril:       policy.peril,
+        threshold:   policy.threshold,
+        index_value: receipt.value,
+        observed_at: receipt.observed_at,
+        round_id:    receipt.round_id,
+        paid_at:     receipt.tx_time,
+        gross:       receipt.amount,
+        ceded:       receipt.amount.bps(policy.ceded_bps),
+    }
+}
+
+pub fn lines_digest(lines: &[BordereauLine]) -> Hash {
+    sha256(&serde_json::to_vec(lines).unwrap_or_default())
+}
+
+// Every claim a tenant paid in [period_start, period_end), in the order paid
+pub fn build(state: &InsuranceState, underwriter_id: UnderwriterId, period_start: i64, period_end: i64) -> RialoResult<Bordereau> {
+    let mut lines: Vec<BordereauLine> = state.receipts
+        .iter()
+        .flat_map(|(payee, receipts)| receipts.iter().map(move |r| (*payee, r)))
+        .filter(|(_, r)| (period_start..period_end).contains(&r.tx_time) && r.review.is_none())
+        .filter_map(|(payee, r)| {
+            let policy = state.policies.get(&r.policy_id)?;
+            (policy.underwriter_id == underwriter_id).then(|| bordereau_line(policy, payee, r))
+        })
+        .collect();
+    require!(lines.len() <= MAX_BORDEREAU_LINES, InsuranceError::InvalidArgument("Too many claims in the period; export a shorter one.".into()));
+    lines.sort_by_key(|l| (l.paid_at, l.policy_id));
+
+    Ok(Bordereau {
+        underwriter_id,
+        period_start,
+        period_end,
+        gross:  lines.iter().map(|l| l.gross).sum(),
+        ceded:  lines.iter().map(|l| l.ceded).sum(),
+        digest: lines_digest(&lines),
+        lines,
+    })
+}
+
+// ── Entry point: tenant sets the quota share it cedes ────────
+#[rialo::instruction]
+pub async fn set_ceded_share(
+    ctx:            Context<InsuranceState>,
+    underwriter_id: UnderwriterId,
+    ceded_bps:      u64,
+) -> RialoResult<()> {
+
+    require!(ceded_bps <= 10_000, InsuranceError::InvalidArgument("Ceded share must be at most 100%.".into()));
+
+    let underwriter = tenant_mut(&mut ctx.state, underwriter_id, &ctx.signer)?;
+    underwriter.ceded_bps = ceded_bps;
+
+    emit!(CededShareSet { underwriter_id, ceded_bps });
+
+    Ok(())
+}
+
+// ── Entry point: tenant exports a period's claims bordereau ──
+#[rialo::instruction]
+pub async fn export_claims_bordereau(
+    ctx:            Context<InsuranceState>,
+    underwriter_id: UnderwriterId,
+    period_start:   i64,
+    period_end:     i64,
+) -> RialoResult<Bordereau> {
+
+    require!(period_start < period_end, InsuranceError::InvalidArgument("Bordereau period must end after it starts.".into()));
+    require!(period_end <= ctx.clock.unix_timestamp, InsuranceError::InvalidState("Bordereau period hasn't ended yet.".into()));
+    tenant_mut(&mut ctx.state, underwriter_id, &ctx.signer)?;
+
+    let bordereau = build(&ctx.state, underwriter_id, period_start, period_end)?;
+
+    emit!(BordereauExported {
+        underwriter_id,
+        period_start,
+        period_end,
+        lines:  bordereau.lines.len() as u32,
+        gross:  bordereau.gross,
+        ceded:  bordereau.ceded,
+        digest: bordereau.digest,
+    });
+
+    Ok(bordereau)
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct CededShareSet     { pub underwriter_id: UnderwriterId, pub ceded_bps: u64 }
+#[rialo::event] pub struct BordereauExported { pub underwriter_id: UnderwriterId, pub period_start: i64, pub period_end: i64, pub lines: u32, pub gross: Ralo, pub ceded: Ralo, pub digest: Hash }
@@ -0,0 +1,219 @@
+// ============================================================
+//  Multi-peril bundles
+//
+//  A delivery company worried about rain, wind and heat shouldn't
+//  need three overlapping policies, three premiums and three
+//  reserves. A template can bundle riders onto its own peril:
+//  each rider is another reading from the same provider response
+//  with its own threshold and a sub-limit, a share of the one
+//  aggregate payout every peril in the bundle draws on.
+//
+//    peril triggers → its sub-limit becomes payable, once
+//    policy pays    → the sum of triggered sub-limits, capped at
+//                      the aggregate limit (the policy payout)
+//
+//  So rain (60%) and wind (60%) both triggering pay the whole
+//  payout, not 120% of it. Reaching the aggregate settles the
+//  policy; short of it the policy stays live for the perils that
+//  haven't triggered. The policy's own peril is the first in the
+//  bundle, at the threshold the customer bought.
+//
+//  Riders settle on the tenant's own provider reading. Consensus
+//  sources (consensus.rs), arbiter overrides and held readings
+//  only ever carry the policy's own peril, and policies that need
+//  two providers' readings (consensus.rs) don't settle riders.
+// ============================================================
+
+use rialo_sdk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::consensus::single_source_allowed;
+use crate::errors::InsuranceError;
+use crate::incidents::under_incident;
+use crate::millimeters::Hundredths;
+use crate::normalization::{Comparison, Metric, Observation};
+use crate::observations::CheckId;
+use crate::policy::{PolicyId, PolicyStatus};
+use crate::settlement::{self, FULL_SHARE_BPS};
+use crate::underwriter::{tenant_mut, TemplateId, UnderwriterId};
+use crate::InsuranceState;
+
+pub const MAX_RIDERS: usize = 3;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct BundledPeril {
+    pub metric:        Metric,
+    pub comparison:    Comparison,    // side of the threshold that pays
+    #[serde(default)]
+    pub trigger_at:    Hundredths,    // threshold in hundredths of the metric's unit
+    #[serde(rename = "threshold", default, skip_serializing)]
+    pub old_threshold: Option<f64>,   // layout 5 and earlier only: now `trigger_at` (migrations.rs)
+    pub sub_limit_bps: u64,           // share of the aggregate limit this peril can pay
+}
+
+impl BundledPeril {
+    pub fn new(metric: Metric, comparison: Comparison, trigger_at: Hundredths, sub_limit_bps: u64) -> Self {
+        BundledPeril { metric, comparison, trigger_at, old_threshold: None, sub_limit_bps }
+    }
+}
+
+// A template's bundle: the sub-limit on its own peril, and the riders
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct BundleTerms {
+    pub sub_limit_bps: u64,
+    pub riders:        Vec<BundledPeril>,
+}
+
+// Bundle state carried by a policy sold under a bundled template
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct BundleCover {
+    pub perils:    Vec<BundledPeril>,   // the policy's own peril first
+    pub triggered: Vec<Metric>,         // perils whose sub-limit is payable
+}
+
+impl BundleCover {
+    pub fn new(own: BundledPeril, riders: Vec<BundledPeril>) -> Self {
+        let mut perils = vec![own];
+        perils.extend(riders);
+        BundleCover { perils, triggered: Vec::new() }
+    }
+
+    // File a reading of `metric`; true if it newly triggers a peril in the bundle
+    pub fn record(&mut self, metric: Metric, reading: Hundredths) -> bool {
+        let Some(peril) = self.perils.iter().find(|p| p.metric == metric) else {
+            return false;
+        };
+        if self.triggered.contains(&metric) || !peril.comparison.is_met(reading, peril.trigger_at) {
+            return false;
+        }
+        self.triggered.push(metric);
+        true
+    }
+
+    // Share of the aggregate limit payable: triggered sub-limits, capped at all of it
+    pub fn share_bps(&self) -> u64 {
+        let triggered: u64 = self.perils
+            .iter()
+            .filter(|p| self.triggered.contains(&p.metric))
+            .map(|p| p.sub_limit_bps)
+            .sum();
+        triggered.min(FULL_SHARE_BPS)
+    }
+}
+
+// Terms a tenant may bundle onto a template written on `own`
+pub fn check_terms(own: Metric, terms: &BundleTerms) -> RialoResult<()> {
+    require!(!terms.riders.is_empty(), InsuranceError::InvalidArgument("A bundle needs at least one rider.".into()));
+    require!(terms.riders.len() <= MAX_RIDERS, InsuranceError::InvalidArgument("Too many riders.".into()));
+
+    let valid_limit = |bps: u64| (1..=FULL_SHARE_BPS).contains(&bps);
+    require!(valid_limit(terms.sub_limit_bps), InsuranceError::InvalidArgument("Sub-limits must be between 0.01% and 100% of the payout.".into()));
+
+    let mut metrics = vec![own];
+    for rider in &terms.riders {
+        require!(rider.metric.is_observed(), InsuranceError::InvalidArgument("Riders must be read from the weather provider.".into()));
+        require!(!metrics.contains(&rider.metric), InsuranceError::InvalidState("Each peril can appear in a bundle once.".into()));
+        require!(valid_limit(rider.sub_limit_bps), InsuranceError::InvalidArgument("Sub-limits must be between 0.01% and 100% of the payout.".into()));
+        metrics.push(rider.metric);
+    }
+    Ok(())
+}
+
+// Apply readings taken at `observed_at` to a bundled policy; pays the share now
+// due and returns true when any of them triggered a peril
+pub(crate) fn settle_readings(
+    state:       &mut InsuranceState,
+    vault:       &Vault,
+    policy_id:   PolicyId,
+    round_id:    Option<CheckId>,
+    readings:    &[(Metric, Hundredths)],
+    observed_at: i64,
+    now:         i64,
+) -> RialoResult<bool> {
+
+    let policy = state.policies.get_mut(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+    if policy.status != PolicyStatus::Active || !policy.is_covered_at(observed_at) {
+        return Ok(false);
+    }
+    let Some(bundle) = policy.bundle.as_mut() else {
+        return Ok(false);
+    };
+
+    let mut newly = Vec::new();
+    for &(metric, reading) in readings {
+        if bundle.record(metric, reading) {
+            newly.push((metric, reading));
+        }
+    }
+    if newly.is_empty() {
+        return Ok(false);
+    }
+    let share_bps = bundle.share_bps();
+
+    for &(metric, reading) in &newly {
+        emit!(BundledPerilTriggered { policy_id, metric, reading, share_bps });
+    }
+    let (_, reading) = newly[0];
+    settlement::pay_share(state, vault, policy_id, round_id, share_bps, reading, observed_at, now)?;
+
+    Ok(true)
+}
+
+// Settle a bundled policy's riders on the rest of an observation its own peril was read from
+pub(crate) fn settle_riders(
+    state:       &mut InsuranceState,
+    vault:       &Vault,
+    policy_id:   PolicyId,
+    source:      &str,
+    observation: &Observation,
+    observed_at: i64,
+    now:         i64,
+) -> RialoResult<()> {
+
+    let Some(policy) = state.policies.get(&policy_id) else {
+        return Ok(());
+    };
+    let Some(bundle) = &policy.bundle else {
+        return Ok(());
+    };
+    // A reading an incident holds back can't settle riders either, nor
+    // can one provider's reading settle cover that needs two
+    if under_incident(state, source, observed_at) || !single_source_allowed(&state.config, policy) {
+        return Ok(());
+    }
+
+    let readings: Vec<(Metric, Hundredths)> = bundle.perils[1..]
+        .iter()
+        .filter_map(|p| observation.metric(p.metric).map(|reading| (p.metric, reading)))
+        .collect();
+    settle_readings(state, vault, policy_id, None, &readings, observed_at, now)?;
+
+    Ok(())
+}
+
+// ── Entry point: bundle riders onto (or off) a template ──────
+#[rialo::instruction]
+pub async fn set_template_bundle(
+    ctx:            Context<InsuranceState>,
+    underwriter_id: UnderwriterId,
+    template_id:    TemplateId,
+    bundle:         Option<BundleTerms>,
+) -> RialoResult<()> {
+
+    let underwriter = tenant_mut(&mut ctx.state, underwriter_id, &ctx.signer)?;
+    let template = underwriter.templates.get_mut(&template_id).ok_or(InsuranceError::UnknownTemplate)?;
+    if let Some(terms) = &bundle {
+        require!(template.is_plain(), InsuranceError::InvalidState("Template already has its own trigger.".into()));
+        check_terms(template.peril(), terms)?;
+    }
+
+    emit!(TemplateBundleSet { underwriter_id, template_id, bundle: bundle.clone() });
+
+    template.bundle = bundle;
+
+    Ok(())
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct TemplateBundleSet     { pub underwriter_id: UnderwriterId, pub template_id: TemplateId, pub bundle: Option<BundleTerms> }
+#[rialo::event] pub struct BundledPerilTriggered { pub policy_id: PolicyId, pub metric: Metric, pub reading: Hundredths, pub share_bps: u64 }
@@ -0,0 +1,215 @@
+// ============================================================
+//  Claims ledger
+//
+//  Running totals kept per underwriter so loss-ratio reporting
+//  reflects the true economics of the book: premiums earned vs.
+//  claims paid *plus* loss-adjustment expenses (LAE) — the cost
+//  of operating the contract itself:
+//    • check fees   — keeper fees for weather checks and voiding lapses
+//    • provider     — estimated weather-API cost per HTTP call
+//    • disputes     — bounties paid out while resolving disputes
+//
+//  The same totals are kept per template, with how many policies
+//  the product sold and how long its payouts took from the
+//  reading to the transfer, so a product's numbers can be read
+//  off chain state when deciding what to change about it.
+// ============================================================
+
+use std::collections::BTreeMap;
+
+use rialo_sdk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::InsuranceError;
+use crate::money::Ralo;
+use crate::policy::PolicyId;
+use crate::underwriter::{TemplateId, UnderwriterId};
+use crate::InsuranceState;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LaeKind {
+    CheckFee,
+    ProviderCost,
+    DisputeBounty,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default)]
+pub struct LaeBreakdown {
+    pub check_fees:       Ralo,
+    pub provider_costs:   Ralo,
+    pub dispute_bounties: Ralo,
+}
+
+impl LaeBreakdown {
+    pub fn add(&mut self, kind: LaeKind, amount: Ralo) {
+        match kind {
+            LaeKind::CheckFee      => self.check_fees       += amount,
+            LaeKind::ProviderCost  => self.provider_costs   += amount,
+            LaeKind::DisputeBounty => self.dispute_bounties += amount,
+        }
+    }
+
+    pub fn total(&self) -> Ralo {
+        self.check_fees + self.provider_costs + self.dispute_bounties
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct ClaimsLedger {
+    pub premiums_earned: Ralo,           // premium that cleared escrow
+    pub claims_paid:     Ralo,           // payouts sent to policyholders
+    pub claims_count:    u64,
+    pub lae:             LaeBreakdown,   // operating costs across the whole book
+    pub templates:       BTreeMap<TemplateId, TemplateLedger>,   // the same by product
+}
+
+// Running totals for one of an underwriter's products
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default)]
+pub struct TemplateLedger {
+    pub policies_sold:   u64,            // policies whose premium cleared
+    pub premiums_earned: Ralo,
+    pub claims_paid:     Ralo,
+    pub claims_count:    u64,            // policies that were paid anything
+    pub payments:        u64,            // payouts, each graded or tiered step counted
+    pub settle_secs:     u64,            // reading-to-transfer time, summed over those payouts
+    pub lae:             LaeBreakdown,
+}
+
+impl TemplateLedger {
+    pub fn loss_ratio_bps(&self) -> u64 {
+        ratio_bps(self.claims_paid, self.premiums_earned)
+    }
+
+    pub fn combined_ratio_bps(&self) -> u64 {
+        ratio_bps(self.claims_paid + self.lae.total(), self.premiums_earned)
+    }
+
+    // Mean time from a paying reading to its transfer; None before the first payout
+    pub fn avg_settle_secs(&self) -> Option<u64> {
+        (self.payments > 0).then(|| self.settle_secs / self.payments)
+    }
+}
+
+impl ClaimsLedger {
+    pub fn template_mut(&mut self, template_id: TemplateId) -> &mut TemplateLedger {
+        self.templates.entry(template_id).or_default()
+    }
+
+    // Claims / premiums, in basis points
+    pub fn loss_ratio_bps(&self) -> u64 {
+        ratio_bps(self.claims_paid, self.premiums_earned)
+    }
+
+    // (Claims + LAE) / premiums, in basis points
+    pub fn combined_ratio_bps(&self) -> u64 {
+        ratio_bps(self.claims_paid + self.lae.total(), self.premiums_earned)
+    }
+}
+
+fn ratio_bps(numerator: Ralo, premiums: Ralo) -> u64 {
+    if premiums.is_zero() {
+        return 0;
+    }
+    ((numerator.base_units() as u128 * 10_000) / premiums.base_units() as u128) as u64
+}
+
+// Charge an operating cost to a policy and its underwriter's ledger
+pub fn record_lae(state: &mut InsuranceState, policy_id: PolicyId, kind: LaeKind, amount: Ralo) {
+    if amount.is_zero() {
+        return;
+    }
+    let Some(policy) = state.policies.get_mut(&policy_id) else { return };
+    policy.lae.add(kind, amount);
+
+    if let Some(underwriter) = state.underwriters.get_mut(&policy.underwriter_id) {
+        underwriter.claims.lae.add(kind, amount);
+        underwriter.claims.template_mut(policy.template_id).lae.add(kind, amount);
+    }
+
+    emit!(LaeRecorded { policy_id, kind, amount });
+}
+
+// ── Views ────────────────────────────────────────────────────
+#[derive(Serialize, Clone, Debug)]
+pub struct LossRatioReport {
+    pub underwriter_id:     UnderwriterId,
+    pub premiums_earned:    Ralo,
+    pub claims_paid:        Ralo,
+    pub claims_count:       u64,
+    pub lae:                LaeBreakdown,
+    pub loss_ratio_bps:     u64,   // claims only
+    pub combined_ratio_bps: u64,   // claims + LAE
+}
+
+#[rialo::view]
+pub fn get_loss_ratio(
+    ctx:            Context<InsuranceState>,
+    underwriter_id: UnderwriterId,
+) -> RialoResult<LossRatioReport> {
+
+    let underwriter = ctx.state.underwriters.get(&underwriter_id).ok_or(InsuranceError::UnknownUnderwriter)?;
+    let ledger = &underwriter.claims;
+
+    Ok(LossRatioReport {
+        underwriter_id,
+        premiums_earned:    ledger.premiums_earned,
+        claims_paid:        ledger.claims_paid,
+        claims_count:       ledger.claims_count,
+        lae:                ledger.lae,
+        loss_ratio_bps:     ledger.loss_ratio_bps(),
+        combined_ratio_bps: ledger.combined_ratio_bps(),
+    })
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct TemplatePerformance {
+    pub underwriter_id:     UnderwriterId,
+    pub template_id:        TemplateId,
+    pub policies_sold:      u64,
+    pub premiums_earned:    Ralo,
+    pub claims_paid:        Ralo,
+    pub claims_count:       u64,
+    pub lae:                LaeBreakdown,
+    pub loss_ratio_bps:     u64,           // claims only
+    pub combined_ratio_bps: u64,           // claims + LAE
+    pub avg_settle_secs:    Option<u64>,   // reading to transfer, per payout
+}
+
+#[rialo::view]
+pub fn get_template_performance(
+    ctx:            Context<InsuranceState>,
+    underwriter_id: UnderwriterId,
+    template_id:    TemplateId,
+) -> RialoResult<TemplatePerformance> {
+
+    let underwriter = ctx.state.underwriters.get(&underwriter_id).ok_or(InsuranceError::UnknownUnderwriter)?;
+    require!(underwriter.templates.contains_key(&template_id), InsuranceError::UnknownTemplate);
+    let ledger = underwriter.claims.templates.get(&template_id).copied().unwrap_or_default();
+
+    Ok(TemplatePerformance {
+        underwriter_id,
+        template_id,
+        policies_sold:      ledger.policies_sold,
+        premiums_earned:    ledger.premiums_earned,
+        claims_paid:        ledger.claims_paid,
+        claims_count:       ledger.claims_count,
+        lae:                ledger.lae,
+        loss_ratio_bps:     ledger.loss_ratio_bps(),
+        combined_ratio_bps: ledger.combined_ratio_bps(),
+        avg_settle_secs:    ledger.avg_settle_secs(),
+    })
+}
+
+#[rialo::view]
+pub fn get_policy_lae(
+    ctx:       Context<InsuranceState>,
+    policy_id: PolicyId,
+) -> RialoResult<LaeBreakdown> {
+
+    let policy = ctx.state.policies.get(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+
+    Ok(policy.lae)
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct LaeRecorded { pub policy_id: PolicyId, pub kind: LaeKind, pub amount: Ralo }
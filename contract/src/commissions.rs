@@ -0,0 +1,108 @@
+// ============================================================
+//  Broker commission
+//
+//  Brokers and referrers sell much of this cover, and are paid a
+//  cut of the premium for it. setup_policy can name the broker and
+//  their rate in basis points; when the premium clears escrow the
+//  commission comes off it alongside the levies (levies.rs) and is
+//  sent straight to the broker, and the underwriter keeps the rest.
+//
+//  The admin caps commissions contract-wide: a highest rate, and
+//  optionally a most any one policy pays. Until caps are set no
+//  commission is written. A rate above the cap is refused when the
+//  policy is written; a cap lowered after that applies when the
+//  premium clears. A customer can't name themselves as broker —
+//  that would just be a discount on the premium.
+// ============================================================
+
+use rialo_sdk::prelude::*;
+use rialo_sdk::token::transfer;
+use serde::{Deserialize, Serialize};
+
+use crate::config::ContractConfig;
+use crate::errors::InsuranceError;
+use crate::money::Ralo;
+use crate::policy::PolicyId;
+use crate::InsuranceState;
+
+// Levies can take up to 20% of a premium; the underwriter always keeps at least 30%
+const MAX_COMMISSION_BPS: u64 = 5_000;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Commission {
+    pub broker:   Pubkey,   // account the commission is paid to
+    pub rate_bps: u64,      // share of the cleared premium
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CommissionCaps {
+    pub max_bps:    u64,            // highest rate a policy may pay
+    pub max_amount: Option<Ralo>,   // most one policy's commission may come to
+}
+
+// Commission on a cleared `premium`, held to the caps in force
+pub fn commission_due(premium: Ralo, rate_bps: u64, caps: &CommissionCaps) -> Ralo {
+    let due = premium.bps(rate_bps.min(caps.max_bps));
+    caps.max_amount.map_or(due, |max| due.min(max))
+}
+
+// Guard for writing a policy that pays `commission`
+pub(crate) fn check_commission(config: &ContractConfig, owner: &Pubkey, commission: &Commission) -> RialoResult<()> {
+    let caps = config.commission_caps.ok_or_else(|| InsuranceError::InvalidState("Broker commissions are not enabled.".into()))?;
+
+    require!(commission.rate_bps > 0, InsuranceError::InvalidArgument("Commission rate must be non-zero.".into()));
+    require!(commission.rate_bps <= caps.max_bps, InsuranceError::InvalidState(format!("Commission exceeds the cap of {} basis points.", caps.max_bps)));
+    require!(commission.broker != *owner, InsuranceError::InvalidArgument("A policyholder can't be paid commission on their own policy.".into()));
+    Ok(())
+}
+
+// ── Entry point: admin caps broker commissions ───────────────
+#[rialo::instruction]
+pub async fn set_commission_caps(
+    ctx:  Context<InsuranceState>,
+    caps: Option<CommissionCaps>,   // None: no new policy pays a commission, and none is paid as premiums clear
+) -> RialoResult<()> {
+
+    let config = &mut ctx.state.config;
+
+    require!(config.admin == *ctx.signer, InsuranceError::Unauthorized("Only the admin can change commission caps.".into()));
+    if let Some(caps) = &caps {
+        require!((1..=MAX_COMMISSION_BPS).contains(&caps.max_bps), InsuranceError::InvalidArgument("Commission cap must be between 1 and 5000 basis points.".into()));
+        require!(caps.max_amount.is_none_or(|max| !max.is_zero()), InsuranceError::InvalidArgument("Commission amount cap must be non-zero.".into()));
+    }
+
+    config.commission_caps = caps;
+
+    emit!(CommissionCapsSet { caps });
+
+    Ok(())
+}
+
+// Pay the broker's commission on a policy's cleared premium; returns the amount sent
+pub(crate) fn pay_commission(state: &mut InsuranceState, vault: &Vault, policy_id: PolicyId) -> RialoResult<Ralo> {
+    let caps = state.config.commission_caps;
+    let policy = state.policies.get_mut(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+    let (Some(commission), Some(caps)) = (policy.commission, caps) else {
+        return Ok(Ralo::ZERO);
+    };
+
+    let amount = commission_due(policy.premium_paid, commission.rate_bps, &caps);
+    if !amount.is_zero() {
+        transfer(vault, &commission.broker, amount.base_units())?;
+    }
+    policy.brokerage = Some(amount);
+
+    emit!(CommissionPaid {
+        policy_id,
+        broker:   commission.broker,
+        rate_bps: commission.rate_bps,
+        premium:  policy.premium_paid,
+        amount,
+    });
+
+    Ok(amount)
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct CommissionCapsSet { pub caps: Option<CommissionCaps> }
+#[rialo::event] pub struct CommissionPaid    { pub policy_id: PolicyId, pub broker: Pubkey, pub rate_bps: u64, pub premium: Ralo, pub amount: Ralo }
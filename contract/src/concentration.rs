@@ -0,0 +1,183 @@
+// ============================================================
+//  Geographic concentration
+//
+//  Per-location caps stop a single city from sinking a tenant,
+//  but a dozen neighbouring towns can take the same storm. This
+//  aggregates the whole book's outstanding exposure — payouts
+//  still reserved for live and pending policies — into geohash-4
+//  cells (roughly 39 × 20 km), for the operator's heat map and
+//  for solvency checks on concentration across locations.
+//
+//  A policy is placed by its insured point, or else by the public
+//  bucket of its sealed metadata (metadata.rs) when that bucket is
+//  at least a cell fine. City-name cover with neither is reported
+//  as unlocated rather than guessed at.
+//
+//  Concentration on one customer is capped outright: the admin can
+//  limit what a single beneficiary may be owed across every live
+//  and pending policy, with every tenant, so a compromised or
+//  colluding customer who games a reading can only take so much.
+//  That cap is one of the risk limits (config.rs), beside the cap
+//  on how many such policies one holder has.
+// ============================================================
+
+use std::collections::BTreeMap;
+
+use rialo_sdk::prelude::*;
+use serde::Serialize;
+
+use crate::config::ContractConfig;
+use crate::errors::InsuranceError;
+use crate::geo::geohash;
+use crate::money::Ralo;
+use crate::policy::{Policy, PolicyStatus};
+use crate::InsuranceState;
+
+pub const HEATMAP_PRECISION: usize = 4;
+
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct ExposureCell {
+    pub geohash:  String,
+    pub policies: u32,
+    pub exposure: Ralo,   // outstanding payouts on policies in the cell
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct ExposureMap {
+    pub cells:              Vec<ExposureCell>,   // geohash order, empty cells omitted
+    pub unlocated:          Ralo,                // exposure on policies with no cell
+    pub unlocated_policies: u32,
+}
+
+// Payout still owed if the policy triggers; nothing once it has settled or ended
+pub fn outstanding_exposure(policy: &Policy) -> Ralo {
+    match policy.status {
+        PolicyStatus::PendingPayment | PolicyStatus::Active | PolicyStatus::PayoutDeferred | PolicyStatus::PendingAttestation
+        | PolicyStatus::PendingPayout => {
+            policy.coverage_remaining()
+        }
+        _ => Ralo::ZERO,
+    }
+}
+
+// Geohash-4 cell a policy is reported under, if it can be placed
+pub fn exposure_cell(policy: &Policy) -> Option<String> {
+    if let Some(point) = policy.insured_point {
+        return Some(geohash(&point, HEATMAP_PRECISION));
+    }
+    policy.sealed
+        .as_ref()
+        .and_then(|sealed| sealed.geohash.get(..HEATMAP_PRECISION))
+        .map(str::to_string)
+}
+
+pub fn exposure_map(state: &InsuranceState) -> ExposureMap {
+    let mut cells: BTreeMap<String, (u32, Ralo)> = BTreeMap::new();
+    let mut unlocated = Ralo::ZERO;
+    let mut unlocated_policies = 0;
+
+    for policy in state.policies.values() {
+        let exposure = outstanding_exposure(policy);
+        if exposure.is_zero() {
+            continue;
+        }
+        match exposure_cell(policy) {
+            Some(cell) => {
+                let entry = cells.entry(cell).or_default();
+                entry.0 += 1;
+                entry.1 += exposure;
+            }
+            None => {
+                unlocated += exposure;
+                unlocated_policies += 1;
+            }
+        }
+    }
+
+    ExposureMap {
+        cells: cells
+            .into_iter()
+            .map(|(geohash, (policies, exposure))| ExposureCell { geohash, policies, exposure })
+            .collect(),
+        unlocated,
+        unlocated_policies,
+    }
+}
+
+// Payouts still owed to `beneficiary` across the whole book
+pub fn beneficiary_exposure<'a>(policies: impl IntoIterator<Item = &'a Policy>, beneficiary: &Pubkey) -> Ralo {
+    policies
+        .into_iter()
+        .filter(|p| p.owner == *beneficiary)
+        .map(outstanding_exposure)
+        .sum()
+}
+
+// Whether writing `payout` more to `beneficiary` stays inside the admin's cap
+pub(crate) fn check_beneficiary_cap(state: &InsuranceState, beneficiary: &Pubkey, payout: Ralo) -> RialoResult<()> {
+    let Some(cap) = state.config.limits().max_coverage_per_holder else {
+        return Ok(());
+    };
+    let outstanding = beneficiary_exposure(state.policies.values(), beneficiary);
+    require!(outstanding + payout <= cap, InsuranceError::InvalidState("Payout would take the beneficiary over its cap across all policies.".into()));
+    Ok(())
+}
+
+// Policies `holder` has that could still pay
+pub fn holder_policies<'a>(policies: impl IntoIterator<Item = &'a Policy>, holder: &Pubkey) -> u32 {
+    policies
+        .into_iter()
+        .filter(|p| p.owner == *holder && !outstanding_exposure(p).is_zero())
+        .count() as u32
+}
+
+// Whether `holder` can take on one more policy under the risk limits
+pub(crate) fn check_policy_limit(state: &InsuranceState, holder: &Pubkey) -> RialoResult<()> {
+    let Some(max) = state.config.limits().max_policies else {
+        return Ok(());
+    };
+    require!(holder_policies(state.policies.values(), holder) < max, InsuranceError::InvalidState(format!("A holder may have at most {max} live policies.")));
+    Ok(())
+}
+
+#[rialo::view]
+pub fn get_exposure_map(ctx: Context<InsuranceState>) -> RialoResult<ExposureMap> {
+    Ok(exposure_map(&ctx.state))
+}
+
+#[rialo::view]
+pub fn get_beneficiary_exposure(ctx: Context<InsuranceState>, beneficiary: Pubkey) -> RialoResult<Ralo> {
+    Ok(beneficiary_exposure(ctx.state.policies.values(), &beneficiary))
+}
+
+// Set or lift the per-beneficiary cap, leaving the other risk limits as they are
+pub fn apply_beneficiary_cap(config: &mut ContractConfig, cap: Option<Ralo>) -> RialoResult<()> {
+    require!(cap.is_none_or(|amount| !amount.is_zero()), InsuranceError::InvalidArgument("Beneficiary cap must be non-zero.".into()));
+
+    config.beneficiary_cap = cap;
+
+    emit!(BeneficiaryCapSet { cap });
+
+    Ok(())
+}
+
+// ── Entry point: admin sets (or lifts) the per-beneficiary cap ─
+//
+//  The cap is one of the risk limits (config.rs); this changes it
+//  alone. Limits the admin never set keep following the network
+//  mode, so this doesn't pin them to the mode's current defaults.
+//
+#[rialo::instruction]
+pub async fn set_beneficiary_cap(
+    ctx: Context<InsuranceState>,
+    cap: Option<Ralo>,
+) -> RialoResult<()> {
+
+    let config = &mut ctx.state.config;
+
+    require!(config.admin == *ctx.signer, InsuranceError::Unauthorized("Only the admin can change the beneficiary cap.".into()));
+    apply_beneficiary_cap(config, cap)
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct BeneficiaryCapSet { pub cap: Option<Ralo> }
@@ -0,0 +1,218 @@
+// ============================================================
+//  Compound conditions
+//
+//  Some clients need a trigger no single threshold can express:
+//  "rain ≥ 15 mm AND wind ≥ 60 km/h", or "rain ≥ 30 mm OR
+//  temperature ≤ 0 °C". A template can carry a condition tree in
+//  place of its threshold:
+//    Reading  — one metric against a threshold, on a side
+//    All      — every sub-condition holds
+//    Any      — at least one does
+//  nested no deeper than MAX_CONDITION_DEPTH. Policies sold under
+//  it are checked as usual, but the whole provider response is
+//  read against the tree: the policy's own peril at the reading
+//  the check settled on (corroborated, where consensus.rs asks for
+//  it), every other metric straight from the tenant's provider.
+//  A metric the response doesn't carry counts as not met.
+//  `CompoundConditionEvaluated` reports each reading's value and
+//  whether it held, triggered or not. Holding the condition pays
+//  the whole payout.
+//
+//  The other readings can't be parked with it, so while an
+//  incident covers the provider a compound policy's checks are
+//  booked and dropped; the next check reads again.
+// ============================================================
+
+use rialo_sdk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::claims::{record_lae, LaeKind};
+use crate::errors::InsuranceError;
+use crate::evaluation::Evaluation;
+use crate::incidents::under_incident;
+use crate::millimeters::Hundredths;
+use crate::money::Ralo;
+use crate::normalization::{Comparison, Metric, Observation};
+use crate::observations::{record_check, upcoming_check_id};
+use crate::policy::{PolicyId, PolicyStatus};
+use crate::underwriter::{tenant_mut, TemplateId, UnderwriterId};
+use crate::{settlement, InsuranceState};
+
+pub const MAX_CONDITION_DEPTH: usize = 3;
+pub const MAX_CONDITION_READINGS: usize = 6;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum Condition {
+    Reading { metric: Metric, comparison: Comparison, threshold: Hundredths },
+    All(Vec<Condition>),
+    Any(Vec<Condition>),
+}
+
+// A condition as layout 5 and earlier wrote it, thresholds in f64 of the
+// metric's unit. Decode-only: migrations.rs puts it on the fixed-point scale.
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub enum LegacyCondition {
+    Reading { metric: Metric, comparison: Comparison, threshold: f64 },
+    All(Vec<LegacyCondition>),
+    Any(Vec<LegacyCondition>),
+}
+
+impl From<LegacyCondition> for Condition {
+    fn from(condition: LegacyCondition) -> Self {
+        match condition {
+            LegacyCondition::Reading { metric, comparison, threshold } => {
+                Condition::Reading { metric, comparison, threshold: Hundredths::from_f64(threshold) }
+            }
+            LegacyCondition::All(parts) => Condition::All(parts.into_iter().map(Condition::from).collect()),
+            LegacyCondition::Any(parts) => Condition::Any(parts.into_iter().map(Condition::from).collect()),
+        }
+    }
+}
+
+// One reading's part in an evaluation
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct ConditionTerm {
+    pub metric:     Metric,
+    pub comparison: Comparison,
+    pub threshold:  Hundredths,
+    pub reading:    Option<Hundredths>,   // None: the response had no such reading
+    pub met:        bool,
+}
+
+impl Condition {
+    // Levels of nesting, a lone reading being one
+    pub fn depth(&self) -> usize {
+        match self {
+            Condition::Reading { .. } => 1,
+            Condition::All(parts) | Condition::Any(parts) => 1 + parts.iter().map(Condition::depth).max().unwrap_or(0),
+        }
+    }
+
+    pub fn readings(&self) -> usize {
+        match self {
+            Condition::Reading { .. } => 1,
+            Condition::All(parts) | Condition::Any(parts) => parts.iter().map(Condition::readings).sum(),
+        }
+    }
+
+    // Whether the condition holds on the readings `read` gives; every reading is
+    // compared, so `terms` gets all of them in order
+    pub fn evaluate(&self, read: &dyn Fn(Metric) -> Option<Hundredths>, terms: &mut Vec<ConditionTerm>) -> bool {
+        match self {
+            Condition::Reading { metric, comparison, threshold } => {
+                let reading = read(*metric);
+                let met = reading.is_some_and(|r| comparison.is_met(r, *threshold));
+                terms.push(ConditionTerm { metric: *metric, comparison: *comparison, threshold: *threshold, reading, met });
+                met
+            }
+            Condition::All(parts) => {
+                let met: Vec<bool> = parts.iter().map(|part| part.evaluate(read, terms)).collect();
+                met.into_iter().all(|m| m)
+            }
+            Condition::Any(parts) => {
+                let met: Vec<bool> = parts.iter().map(|part| part.evaluate(read, terms)).collect();
+                met.into_iter().any(|m| m)
+            }
+        }
+    }
+
+    pub fn validate(&self) -> RialoResult<()> {
+        require!(self.depth() <= MAX_CONDITION_DEPTH, InsuranceError::InvalidArgument("Condition is nested too deeply.".into()));
+        require!(self.readings() <= MAX_CONDITION_READINGS, InsuranceError::InvalidArgument("Condition compares too many readings.".into()));
+        self.check_parts()
+    }
+
+    fn check_parts(&self) -> RialoResult<()> {
+        match self {
+            Condition::Reading { metric, .. } => {
+                require!(metric.is_observed(), InsuranceError::InvalidState("Conditions can only compare readings from the weather provider.".into()));
+            }
+            Condition::All(parts) | Condition::Any(parts) => {
+                require!(parts.len() >= 2, InsuranceError::InvalidArgument("AND and OR need at least two sub-conditions.".into()));
+                for part in parts {
+                    part.check_parts()?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn is_compound(state: &InsuranceState, policy_id: PolicyId) -> bool {
+    state.policies.get(&policy_id).is_some_and(|p| p.compound.is_some())
+}
+
+// Book the call, then settle a compound policy on a provider response, its own
+// peril at `reading`
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn settle_observation(
+    state:       &mut InsuranceState,
+    vault:       &Vault,
+    policy_id:   PolicyId,
+    source:      &str,
+    observation: &Observation,
+    reading:     Hundredths,
+    observed_at: i64,
+    now:         i64,
+    call_cost:   Ralo,
+) -> RialoResult<()> {
+
+    record_lae(state, policy_id, LaeKind::ProviderCost, call_cost);
+    if under_incident(state, source, observed_at) {
+        return Ok(());
+    }
+
+    let policy = state.policies.get(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+    let Some(condition) = &policy.compound else {
+        return Ok(());
+    };
+    let live = policy.status == PolicyStatus::Active && policy.is_covered_at(observed_at);
+    let (peril, paid_before) = (policy.peril, policy.paid_out);
+
+    let mut terms = Vec::new();
+    let read = |metric: Metric| if metric == peril { Some(reading) } else { observation.metric(metric) };
+    let met = condition.evaluate(&read, &mut terms);
+
+    emit!(CompoundConditionEvaluated { policy_id, met, terms });
+
+    let round_id = upcoming_check_id(state);
+    let decision = if met && live {
+        settlement::pay_out(state, vault, policy_id, Some(round_id), reading, observed_at, now)?;
+        let paid = state.policies.get(&policy_id).map_or(Ralo::ZERO, |p| p.paid_out.saturating_sub(paid_before));
+        Evaluation::Triggered { paid }
+    } else {
+        Evaluation::NotMet
+    };
+    record_check(state, policy_id, source, observation.station.clone(), observation.response, reading, observed_at, decision);
+
+    Ok(())
+}
+
+// ── Entry point: put a compound condition on a template ──────
+#[rialo::instruction]
+pub async fn set_template_condition(
+    ctx:            Context<InsuranceState>,
+    underwriter_id: UnderwriterId,
+    template_id:    TemplateId,
+    condition:      Option<Condition>,
+) -> RialoResult<()> {
+
+    let underwriter = tenant_mut(&mut ctx.state, underwriter_id, &ctx.signer)?;
+    let template = underwriter.templates.get_mut(&template_id).ok_or(InsuranceError::UnknownTemplate)?;
+    if let Some(condition) = &condition {
+        require!(template.compound.is_some() || template.is_plain(), InsuranceError::InvalidState("Template already has its own trigger.".into()));
+        require!(template.bundle.is_none(), InsuranceError::InvalidState("Bundled perils and compound conditions don't mix.".into()));
+        require!(template.smoothing.is_none() && template.forecast_min_bps.is_none(), InsuranceError::InvalidState("Compound conditions settle on raw readings.".into()));
+        condition.validate()?;
+    }
+
+    emit!(TemplateConditionSet { underwriter_id, template_id, condition: condition.clone() });
+
+    template.compound = condition;
+
+    Ok(())
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct TemplateConditionSet       { pub underwriter_id: UnderwriterId, pub template_id: TemplateId, pub condition: Option<Condition> }
+#[rialo::event] pub struct CompoundConditionEvaluated { pub policy_id: PolicyId, pub met: bool, pub terms: Vec<ConditionTerm> }
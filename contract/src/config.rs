@@ -0,0 +1,417 @@
+// ============================================================
+//  Contract-wide configuration
+//
+//  `network_mode` lets the exact same contract code run on DevNet
+//  against a deterministic test weather service:
+//    • DevNet  → providers are queried at their sandbox base URL,
+//                safety caps are relaxed so scenarios can use
+//                tiny thresholds and large payouts
+//    • MainNet → production endpoints, production caps
+//
+//  Emergency stop: when a provider key leaks or an upstream API
+//  starts returning garbage, the admin can `pause` the contract.
+//  While paused no policy is written and no reading is taken
+//  (live, historical, backfill or final), so nothing pays on the
+//  bad data. Everything that only returns money — refunds, voids,
+//  expiry of unpaid policies — keeps working. Coverage windows
+//  keep running; after `unpause`, historical checks can catch up
+//  on the paused hours within the lookback.
+//
+//  Risk limits: each network mode comes with default caps — the
+//  lowest rainfall threshold and the largest payout a template or
+//  policy may carry, and no cap on how many policies one holder
+//  has. The admin can replace them with set_limits, which also
+//  sets the per-holder cap on coverage (concentration.rs), so
+//  they're tuned without a redeploy. Once set, the admin's limits
+//  hold whatever the network mode.
+// ============================================================
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use rialo_sdk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::alerts::AlertWebhook;
+use crate::audit::AuditConfig;
+use crate::commissions::CommissionCaps;
+use crate::errors::InsuranceError;
+use crate::governance::{require_ungoverned, GovernanceConfig};
+use crate::impairment::OutageRefundTerms;
+use crate::keepers::KeeperRewards;
+use crate::levies::Levy;
+use crate::millimeters::Hundredths;
+use crate::migrations::{self, STATE_VERSION};
+use crate::mints::{Mint, MintInfo};
+use crate::rate_limits::RateLimits;
+use crate::self_dealing::{SelfDealingOverride, SelfDealingPolicy};
+use crate::money::Ralo;
+use crate::underwriter::ProviderConfig;
+use crate::whitelist::Role;
+use crate::InsuranceState;
+
+pub const DEFAULT_PAYMENT_GRACE_SECS: i64 = 3 * 24 * 60 * 60;
+pub const DEFAULT_MAX_COVERAGE_SECS:  i64 = 365 * 24 * 60 * 60;
+pub const DEFAULT_MAX_LOOKBACK_SECS:  i64 = 7 * 24 * 60 * 60;
+pub const DEFAULT_FINALIZE_SECS:      i64 = 3 * 24 * 60 * 60;
+pub const DEFAULT_GEOCODE_TTL_SECS:   i64 = 30 * 24 * 60 * 60;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum NetworkMode {
+    DevNet,
+    #[default]
+    MainNet,
+}
+
+// Kinds of non-weather data source a product can settle on. Weather
+// providers stay per-underwriter; these feeds are public and shared.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FeedKind {
+    #[cfg(feature = "storm")]
+    StormTrack,   // active tropical cyclones (NHC CurrentStorms.json)
+    #[cfg(feature = "flood")]
+    RiverGauge,   // river gauge heights (USGS instantaneous values)
+    RaloUsd,      // RALO/USD spot price, for USD-denominated payouts
+}
+
+// Caps enforced on every template and policy
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct Limits {
+    #[serde(default)]
+    pub min_threshold:           Hundredths,     // lowest rainfall trigger, in hundredths of a mm
+    #[serde(rename = "min_threshold_mm", default, skip_serializing)]
+    pub old_min_threshold:       Option<f64>,    // layout 3 and earlier only: now `min_threshold` (migrations.rs)
+    pub max_payout:              Ralo,
+    pub max_policies:            Option<u32>,    // most live and pending policies one holder may have
+    #[serde(default)]
+    pub max_coverage_per_holder: Option<Ralo>,   // most one beneficiary may be owed across the book; kept in `beneficiary_cap`, unset in stored limits
+}
+
+impl NetworkMode {
+    pub fn limits(self) -> Limits {
+        match self {
+            NetworkMode::MainNet => Limits { min_threshold: Hundredths(10), old_min_threshold: None, max_payout: Ralo::whole(200),       max_policies: None, max_coverage_per_holder: None },
+            NetworkMode::DevNet  => Limits { min_threshold: Hundredths(1),  old_min_threshold: None, max_payout: Ralo::whole(1_000_000), max_policies: None, max_coverage_per_holder: None },
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct ContractConfig {
+    pub admin:              Pubkey,                               // key allowed to change contract-wide settings
+    pub network_mode:       NetworkMode,
+    pub limits:             Option<Limits>,                       // admin's risk limits; the network mode's defaults until set
+    pub initialized:        bool,
+    pub paused:             bool,                                 // emergency stop: no new policies, no readings settled
+    pub arbiters:           Vec<Pubkey>,                          // multi-sig allowed to submit manual observations
+    pub arbiter_threshold:  u8,                                   // approvals required out of `arbiters`
+    pub payment_grace_secs: i64,                                  // how long a policy may sit unpaid before it can be voided
+    pub lapse_reward:       Ralo,                                 // paid to whoever voids a lapsed policy
+    pub keeper_rewards:     Option<KeeperRewards>,                // paid to whoever runs a live check (keepers.rs)
+    pub max_coverage_secs:  i64,                                  // longest coverage window a policy may buy
+    pub max_lookback_secs:  i64,                                  // how far back a historical check may look
+    pub finalize_secs:      i64,                                  // after coverage ends, how long a final check may still settle
+    pub min_premium:        Ralo,                                 // dust floor: smallest premium worth writing
+    pub min_payout:         Ralo,                                 // dust floor: smallest payout worth writing
+    pub audit:              AuditConfig,                          // independent re-check sources and sampling rates
+    pub alert_webhooks:     Vec<AlertWebhook>,                    // operator endpoints for signed operational alerts
+    pub levies:             BTreeMap<String, Vec<Levy>>,          // premium levies by ISO country code
+    pub feeds:              BTreeMap<FeedKind, ProviderConfig>,   // non-weather data sources, shared by every tenant
+    pub governance:         Option<GovernanceConfig>,             // once set, pooled underwriters vote on governed parameters
+    pub outage_refund:      Option<OutageRefundTerms>,            // refund owed on policies a provider outage left un-settleable
+    pub dual_control_above: Option<Ralo>,                         // withdrawals this large need two keys (dual_control.rs)
+    pub geocode_ttl_secs:   i64,                                  // how long a location's geocode may be reused (geocoding.rs)
+    pub geohash_key_len:    u8,                                   // geohash characters a named place's key carries; 0 = none (geocoding.rs)
+    #[serde(default)]
+    pub beneficiary_cap:    Option<Ralo>,                         // most one beneficiary may be owed across the book; limits() reports it (concentration.rs)
+    pub relayers:           Vec<Pubkey>,                          // off-chain relayers that deliver settlement notifications (outbox.rs)
+    pub diversity_above:    Option<Ralo>,                         // payouts above this settle on two providers' readings (consensus.rs)
+    pub payout_mints:       BTreeMap<Mint, MintInfo>,             // tokens other than RALO policies can pay out in (mints.rs)
+    pub self_dealing:       SelfDealingPolicy,                    // whether beneficiaries holding control keys are allowed (self_dealing.rs)
+    pub conflict_overrides: Vec<SelfDealingOverride>,             // self-dealing overrides, one conflicted policy each
+    pub batch_levies:       bool,                                 // accrue premium levies for sweep_fees instead of paying each (levies.rs)
+    pub rate_limits:        RateLimits,                           // hourly caps on checks run, per caller and overall (rate_limits.rs)
+    pub enforced_roles:     BTreeSet<Role>,                       // roles only whitelisted accounts may act in; empty = open (whitelist.rs)
+    pub commission_caps:    Option<CommissionCaps>,               // highest broker commission a policy may pay; None = no commissions (commissions.rs)
+}
+
+impl ContractConfig {
+    // The admin's limits, or the network mode's until set_limits is called,
+    // with the per-beneficiary cap laid over either
+    pub fn limits(&self) -> Limits {
+        let limits = self.limits.unwrap_or_else(|| self.network_mode.limits());
+        Limits { max_coverage_per_holder: self.beneficiary_cap, ..limits }
+    }
+}
+
+// ── Entry point: one-time deployment setup ───────────────────
+#[rialo::instruction]
+pub async fn initialize(
+    ctx:          Context<InsuranceState>,
+    network_mode: NetworkMode,
+) -> RialoResult<()> {
+
+    let config = &mut ctx.state.config;
+
+    require!(!config.initialized, InsuranceError::InvalidState("Contract already initialized.".into()));
+    ctx.state.version = STATE_VERSION;
+
+    config.admin              = *ctx.signer;
+    config.network_mode       = network_mode;
+    config.initialized        = true;
+    config.payment_grace_secs = DEFAULT_PAYMENT_GRACE_SECS;
+    config.max_coverage_secs  = DEFAULT_MAX_COVERAGE_SECS;
+    config.max_lookback_secs  = DEFAULT_MAX_LOOKBACK_SECS;
+    config.finalize_secs      = DEFAULT_FINALIZE_SECS;
+    config.geocode_ttl_secs   = DEFAULT_GEOCODE_TTL_SECS;
+
+    emit!(ContractInitialized { admin: config.admin, network_mode });
+
+    Ok(())
+}
+
+// ── Entry point: switch between DevNet and MainNet behaviour ─
+#[rialo::instruction]
+pub async fn set_network_mode(
+    ctx:          Context<InsuranceState>,
+    network_mode: NetworkMode,
+) -> RialoResult<()> {
+
+    let config = &mut ctx.state.config;
+
+    require!(config.initialized, InsuranceError::InvalidState("Contract not initialized.".into()));
+    require!(config.admin == *ctx.signer, InsuranceError::Unauthorized("Only the admin can change the network mode.".into()));
+
+    config.network_mode = network_mode;
+
+    emit!(NetworkModeChanged { network_mode });
+
+    Ok(())
+}
+
+// Guard for everything the emergency stop halts
+pub(crate) fn require_unpaused(config: &ContractConfig) -> RialoResult<()> {
+    require!(!config.paused, InsuranceError::ContractPaused);
+    Ok(())
+}
+
+// ── Entry point: emergency stop ──────────────────────────────
+#[rialo::instruction]
+pub async fn pause(ctx: Context<InsuranceState>) -> RialoResult<()> {
+    let config = &mut ctx.state.config;
+
+    require!(config.admin == *ctx.signer, InsuranceError::Unauthorized("Only the admin can pause the contract.".into()));
+    require!(!config.paused, InsuranceError::InvalidState("Contract is already paused.".into()));
+
+    config.paused = true;
+
+    emit!(ContractPaused { at: ctx.clock.unix_timestamp });
+
+    Ok(())
+}
+
+// ── Entry point: lift the emergency stop ─────────────────────
+#[rialo::instruction]
+pub async fn unpause(ctx: Context<InsuranceState>) -> RialoResult<()> {
+    migrations::require_current(&ctx.state)?;
+    let config = &mut ctx.state.config;
+
+    require!(config.admin == *ctx.signer, InsuranceError::Unauthorized("Only the admin can unpause the contract.".into()));
+    require!(config.paused, InsuranceError::InvalidState("Contract is not paused.".into()));
+
+    config.paused = false;
+
+    emit!(ContractUnpaused { at: ctx.clock.unix_timestamp });
+
+    Ok(())
+}
+
+// ── Entry point: replace the arbiter multi-sig ───────────────
+#[rialo::instruction]
+pub async fn set_arbiters(
+    ctx:       Context<InsuranceState>,
+    arbiters:  Vec<Pubkey>,
+    threshold: u8,
+) -> RialoResult<()> {
+
+    let config = &mut ctx.state.config;
+
+    require!(config.admin == *ctx.signer, InsuranceError::Unauthorized("Only the admin can change the arbiters.".into()));
+    require!(threshold > 0, InsuranceError::InvalidArgument("Arbiter threshold must be at least 1.".into()));
+    require!(threshold as usize <= arbiters.len(), InsuranceError::InvalidState("Arbiter threshold exceeds the number of arbiters.".into()));
+
+    let mut deduped = arbiters.clone();
+    deduped.sort();
+    deduped.dedup();
+    require!(deduped.len() == arbiters.len(), InsuranceError::InvalidState("Arbiter list contains duplicates.".into()));
+
+    config.arbiters          = arbiters.clone();
+    config.arbiter_threshold = threshold;
+
+    emit!(ArbitersChanged { arbiters, threshold });
+
+    Ok(())
+}
+
+// ── Entry point: tune lapse voiding ──────────────────────────
+#[rialo::instruction]
+pub async fn set_lapse_settings(
+    ctx:                Context<InsuranceState>,
+    payment_grace_secs: i64,
+    lapse_reward:       Ralo,
+) -> RialoResult<()> {
+
+    let config = &mut ctx.state.config;
+
+    require!(config.admin == *ctx.signer, InsuranceError::Unauthorized("Only the admin can change lapse settings.".into()));
+    require_ungoverned(config)?;
+
+    apply_lapse_settings(config, payment_grace_secs, lapse_reward)
+}
+
+pub(crate) fn apply_lapse_settings(config: &mut ContractConfig, payment_grace_secs: i64, lapse_reward: Ralo) -> RialoResult<()> {
+    require!(payment_grace_secs > 0, InsuranceError::InvalidArgument("Grace period must be positive.".into()));
+
+    config.payment_grace_secs = payment_grace_secs;
+    config.lapse_reward       = lapse_reward;
+
+    emit!(LapseSettingsChanged { payment_grace_secs, lapse_reward });
+
+    Ok(())
+}
+
+// ── Entry point: bound risk horizon and historical-API spend ─
+#[rialo::instruction]
+pub async fn set_coverage_limits(
+    ctx:               Context<InsuranceState>,
+    max_coverage_secs: i64,
+    max_lookback_secs: i64,
+    finalize_secs:     i64,
+) -> RialoResult<()> {
+
+    let config = &mut ctx.state.config;
+
+    require!(config.admin == *ctx.signer, InsuranceError::Unauthorized("Only the admin can change coverage limits.".into()));
+    require!(max_coverage_secs > 0, InsuranceError::InvalidArgument("Maximum coverage must be positive.".into()));
+    require!(max_lookback_secs >= 0, InsuranceError::InvalidState("Maximum lookback cannot be negative.".into()));
+    require!(finalize_secs >= 0, InsuranceError::InvalidState("Finalize window cannot be negative.".into()));
+    // The final check reads the last covered hour, so it has to stay within reach of history
+    require!(finalize_secs <= max_lookback_secs, InsuranceError::InvalidState("Finalize window cannot exceed the maximum lookback.".into()));
+
+    config.max_coverage_secs = max_coverage_secs;
+    config.max_lookback_secs = max_lookback_secs;
+    config.finalize_secs     = finalize_secs;
+
+    emit!(CoverageLimitsChanged { max_coverage_secs, max_lookback_secs, finalize_secs });
+
+    Ok(())
+}
+
+// ── Entry point: tune the risk limits ────────────────────────
+//
+//  Templates already published keep their own caps, but every policy
+//  written from now on is held to these. set_beneficiary_cap
+//  (concentration.rs) changes the per-holder cap alone; until this
+//  is called the other limits follow the network mode.
+//
+#[rialo::instruction]
+pub async fn set_limits(
+    ctx:                     Context<InsuranceState>,
+    min_threshold_mm:        f64,
+    max_payout:              Ralo,
+    max_policies:            Option<u32>,
+    max_coverage_per_holder: Option<Ralo>,
+) -> RialoResult<()> {
+
+    let config = &mut ctx.state.config;
+
+    require!(config.admin == *ctx.signer, InsuranceError::Unauthorized("Only the admin can change risk limits.".into()));
+    apply_limits(config, Limits { min_threshold: Hundredths::from_f64(min_threshold_mm), old_min_threshold: None, max_payout, max_policies, max_coverage_per_holder })
+}
+
+// Check and store the admin's risk limits; every setter of them comes through here
+pub fn apply_limits(config: &mut ContractConfig, limits: Limits) -> RialoResult<()> {
+    let Limits { min_threshold, max_payout, max_policies, max_coverage_per_holder, .. } = limits;
+
+    require!(min_threshold.is_positive(), InsuranceError::InvalidArgument("Minimum threshold must be positive.".into()));
+    require!(!max_payout.is_zero(), InsuranceError::InvalidArgument("Maximum payout must be non-zero.".into()));
+    require!(max_payout >= config.min_payout, InsuranceError::InvalidState("Maximum payout is below the payout floor.".into()));
+    require!(max_policies != Some(0), InsuranceError::InvalidArgument("Policy limit must be at least 1.".into()));
+    require!(max_coverage_per_holder.is_none_or(|cap| !cap.is_zero()), InsuranceError::InvalidArgument("Coverage limit must be non-zero.".into()));
+
+    config.limits          = Some(Limits { max_coverage_per_holder: None, ..limits });
+    config.beneficiary_cap = max_coverage_per_holder;
+
+    emit!(LimitsChanged { min_threshold, max_payout, max_policies, max_coverage_per_holder });
+
+    Ok(())
+}
+
+// ── Entry point: turn away policies too small to be worth writing
+//
+//  A policy costs storage and provider calls whatever its size; below
+//  these floors the premium can't pay for them (see quote.rs).
+//
+#[rialo::instruction]
+pub async fn set_policy_floors(
+    ctx:         Context<InsuranceState>,
+    min_premium: Ralo,
+    min_payout:  Ralo,
+) -> RialoResult<()> {
+
+    let config = &mut ctx.state.config;
+
+    require!(config.admin == *ctx.signer, InsuranceError::Unauthorized("Only the admin can change policy floors.".into()));
+    require_ungoverned(config)?;
+
+    apply_policy_floors(config, min_premium, min_payout)
+}
+
+pub(crate) fn apply_policy_floors(config: &mut ContractConfig, min_premium: Ralo, min_payout: Ralo) -> RialoResult<()> {
+    require!(min_payout <= config.limits().max_payout, InsuranceError::InvalidState("Payout floor exceeds the maximum payout.".into()));
+
+    config.min_premium = min_premium;
+    config.min_payout  = min_payout;
+
+    emit!(PolicyFloorsChanged { min_premium, min_payout });
+
+    Ok(())
+}
+
+// ── Entry point: admin sets (or removes) a shared data feed ─
+#[rialo::instruction]
+pub async fn set_data_feed(
+    ctx:      Context<InsuranceState>,
+    kind:     FeedKind,
+    provider: Option<ProviderConfig>,
+) -> RialoResult<()> {
+
+    let config = &mut ctx.state.config;
+
+    require!(config.admin == *ctx.signer, InsuranceError::Unauthorized("Only the admin can change data feeds.".into()));
+    require_ungoverned(config)?;
+
+    apply_data_feed(config, kind, provider);
+
+    Ok(())
+}
+
+pub(crate) fn apply_data_feed(config: &mut ContractConfig, kind: FeedKind, provider: Option<ProviderConfig>) {
+    emit!(DataFeedSet { kind, base_url: provider.as_ref().map(|p| p.base_url.clone()) });
+
+    match provider {
+        Some(provider) => config.feeds.insert(kind, provider),
+        None           => config.feeds.remove(&kind),
+    };
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct ContractInitialized   { pub admin: Pubkey, pub network_mode: NetworkMode }
+#[rialo::event] pub struct NetworkModeChanged    { pub network_mode: NetworkMode }
+#[rialo::event] pub struct ArbitersChanged       { pub arbiters: Vec<Pubkey>, pub threshold: u8 }
+#[rialo::event] pub struct LapseSettingsChanged  { pub payment_grace_secs: i64, pub lapse_reward: Ralo }
+#[rialo::event] pub struct DataFeedSet           { pub kind: FeedKind, pub base_url: Option<String> }
+#[rialo::event] pub struct CoverageLimitsChanged { pub max_coverage_secs: i64, pub max_lookback_secs: i64, pub finalize_secs: i64 }
+#[rialo::event] pub struct PolicyFloorsChanged   { pub min_premium: Ralo, pub min_payout: Ralo }
+#[rialo::event] pub struct LimitsChanged         { pub min_threshold: Hundredths, pub max_payout: Ralo, pub max_policies: Option<u32>, pub max_coverage_per_holder: Option<Ralo> }
+#[rialo::event] pub struct ContractPaused        { pub at: i64 }
+#[rialo::event] pub struct ContractUnpaused      { pub at: i64 }
@@ -0,0 +1,298 @@
+// ============================================================
+//  Multi-source consensus
+//
+//  One provider response is one point of failure: a hiccup, a
+//  stale station or a bad deploy on their side moves money. A
+//  tenant can name up to two more weather sources, each read with
+//  its own API and parser (providers.rs), and live checks then
+//  settle on the median of every source that answered instead of
+//  the tenant's own provider alone:
+//    • fewer answers than the quorum → the check settles nothing
+//      and `QuorumNotMet` says why
+//    • answers further apart than the allowed spread still settle
+//      on the median, and `SourceDisagreement` is raised with
+//      every reading so the outlier can be chased
+//  Each source keeps its own slot in the hourly cache (cache.rs),
+//  so a city's round pays for each source once per hour.
+//
+//  Historical checks and backfills read the tenant's own provider
+//  only — the other sources' history isn't guaranteed to line up
+//  hour for hour.
+//
+//  Source diversity: a policy paying more than the admin's
+//  `diversity_above` settles only on readings from at least
+//  two distinct providers (APIs, not just URLs) — a quorum of
+//  mirrors of one feed shares its failures. Such policies are
+//  only sold by tenants whose sources span two providers, settle
+//  on live checks alone (history is single-provider, so they take
+//  no historical, backfilled or final check) and don't settle
+//  bundled riders, which are read from the tenant's own provider.
+// ============================================================
+
+use rialo_sdk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::alerts::{self, AlertKind};
+use crate::claims::{record_lae, LaeKind};
+use crate::config::ContractConfig;
+use crate::errors::InsuranceError;
+use crate::millimeters::Hundredths;
+use crate::money::Ralo;
+use crate::normalization::{Location, Metric};
+use crate::oracle::CallBudget;
+use crate::policy::{Policy, PolicyId};
+use crate::providers::{ProviderKind, ReadingTime, WeatherProvider};
+use crate::underwriter::{tenant_mut, ProviderConfig, Underwriter, UnderwriterId};
+use crate::{fetch, InsuranceState};
+
+// On top of the tenant's own provider
+pub const MAX_EXTRA_SOURCES: usize = 2;
+
+// Distinct providers a large payout has to be read from
+pub const MIN_DIVERSE_PROVIDERS: usize = 2;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Consensus {
+    pub sources:    Vec<ProviderConfig>,   // read alongside the tenant's own provider
+    pub quorum:     u8,                    // answers needed, own provider included
+    pub max_spread: f64,                   // largest gap between readings before they disagree
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SourceReading {
+    pub source:  String,
+    pub reading: Hundredths,
+}
+
+// Middle reading; the mean of the middle two, rounded towards zero, for an even count
+pub fn median(readings: &[Hundredths]) -> Option<Hundredths> {
+    let mut sorted = readings.to_vec();
+    sorted.sort();
+    let mid = sorted.len() / 2;
+    match sorted.len() {
+        0 => None,
+        n if n % 2 == 1 => Some(sorted[mid]),
+        _ => Some(Hundredths(sorted[mid - 1].0.midpoint(sorted[mid].0))),
+    }
+}
+
+// Gap between the highest and lowest reading
+pub fn spread(readings: &[Hundredths]) -> Hundredths {
+    match (readings.iter().min(), readings.iter().max()) {
+        (Some(low), Some(high)) => Hundredths(high.0.saturating_sub(low.0)),
+        _ => Hundredths::ZERO,
+    }
+}
+
+// Whether a policy paying `payout` has to settle on more than one provider
+pub fn needs_diverse_sources(config: &ContractConfig, payout: Ralo) -> bool {
+    config.diversity_above.is_some_and(|above| payout > above)
+}
+
+pub fn distinct_providers(kinds: &[ProviderKind]) -> usize {
+    let mut distinct: Vec<ProviderKind> = Vec::new();
+    for kind in kinds {
+        if !distinct.contains(kind) {
+            distinct.push(*kind);
+        }
+    }
+    distinct.len()
+}
+
+// Providers a tenant's live checks can read from, own provider included
+pub fn tenant_providers(underwriter: &Underwriter) -> usize {
+    let mut kinds = vec![underwriter.provider.kind];
+    if let Some(consensus) = &underwriter.consensus {
+        kinds.extend(consensus.sources.iter().map(|p| p.kind));
+    }
+    distinct_providers(&kinds)
+}
+
+// Whether a policy may settle on a reading from the tenant's own provider alone
+pub fn single_source_allowed(config: &ContractConfig, policy: &Policy) -> bool {
+    !needs_diverse_sources(config, policy.payout_amount)
+}
+
+// Provider calls a live check of `location` will spend on the other sources
+pub(crate) fn calls_needed(state: &InsuranceState, underwriter_id: UnderwriterId, location: &str, now: i64) -> u32 {
+    let Some(consensus) = state.underwriters.get(&underwriter_id).and_then(|u| u.consensus.as_ref()) else {
+        return 0;
+    };
+    let mode = state.config.network_mode;
+    consensus.sources
+        .iter()
+        .filter(|p| state.weather_cache.get(p.base_url_for(mode), location, now).is_none())
+        .count() as u32
+}
+
+// One other source's current reading; None if it didn't answer usefully
+async fn read_source(
+    state:     &mut InsuranceState,
+    provider:  &ProviderConfig,
+    policy_id: PolicyId,
+    place:     &Location,
+    peril:     Metric,
+    now:       i64,
+    budget:    &mut CallBudget,
+) -> RialoResult<Option<Hundredths>> {
+
+    // A provider that can't query the policy's station has nothing to say about it
+    if matches!(place, Location::Station(_)) && !provider.kind.queries_stations() {
+        return Ok(None);
+    }
+    let source = provider.base_url_for(state.config.network_mode).to_string();
+    let location = place.key();
+    if let Some(observation) = state.weather_cache.get(&source, &location, now) {
+        return Ok(observation.metric(peril));
+    }
+    // A source whose key can't be unsealed hasn't answered either
+    let Ok(api_key) = provider.api_key() else {
+        return Ok(None);
+    };
+    if !budget.try_spend() {
+        return Ok(None);
+    }
+
+    let url = provider.kind.build_request(&source, &api_key, place, ReadingTime::Current);
+    let response = fetch(&url, &[]).await;
+    record_lae(state, policy_id, LaeKind::ProviderCost, provider.cost_per_call);
+
+    let Some(response) = response.ok().filter(|r| (200..300).contains(&r.status())) else {
+        state.metrics.http_failures += 1;
+        return Ok(None);
+    };
+    let Ok(observation) = provider.kind.parse_observation(ReadingTime::Current, response.body()) else {
+        return Ok(None);
+    };
+    state.weather_cache.insert(&source, &location, now, observation.clone());
+
+    Ok(observation.metric(peril))
+}
+
+// The reading a live check settles on: `own` alone for a tenant without
+// consensus, otherwise the median of every source that answered. None if
+// too few answered, or too few providers for the payout, to settle.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn corroborate(
+    state:          &mut InsuranceState,
+    underwriter_id: UnderwriterId,
+    policy_id:      PolicyId,
+    place:          &Location,
+    peril:          Metric,
+    own:            SourceReading,
+    now:            i64,
+    budget:         &mut CallBudget,
+) -> RialoResult<Option<Hundredths>> {
+
+    let underwriter = state.underwriters.get(&underwriter_id).ok_or(InsuranceError::UnknownUnderwriter)?;
+    let own_kind = underwriter.provider.kind;
+    let consensus = underwriter.consensus.clone();
+    let payout = state.policies.get(&policy_id).ok_or(InsuranceError::UnknownPolicy)?.payout_amount;
+    let diverse = needs_diverse_sources(&state.config, payout);
+
+    let Some(consensus) = consensus else {
+        if diverse {
+            emit!(SourceDiversityNotMet { policy_id, providers: 1, required: MIN_DIVERSE_PROVIDERS as u32 });
+            return Ok(None);
+        }
+        return Ok(Some(own.reading));
+    };
+
+    let mut readings = vec![own];
+    let mut kinds = vec![own_kind];
+    for provider in &consensus.sources {
+        if let Some(reading) = read_source(state, provider, policy_id, place, peril, now, budget).await? {
+            let source = provider.base_url_for(state.config.network_mode).to_string();
+            readings.push(SourceReading { source, reading });
+            kinds.push(provider.kind);
+        }
+    }
+
+    if readings.len() < consensus.quorum as usize {
+        emit!(QuorumNotMet { policy_id, answered: readings.len() as u32, quorum: consensus.quorum as u32 });
+        return Ok(None);
+    }
+    let providers = distinct_providers(&kinds);
+    if diverse && providers < MIN_DIVERSE_PROVIDERS {
+        emit!(SourceDiversityNotMet { policy_id, providers: providers as u32, required: MIN_DIVERSE_PROVIDERS as u32 });
+        return Ok(None);
+    }
+
+    let values: Vec<Hundredths> = readings.iter().map(|r| r.reading).collect();
+    let settled = median(&values).ok_or_else(|| InsuranceError::InvalidState("No source answered.".into()))?;
+    let spread = spread(&values);
+    let max_spread = Hundredths::from_f64(consensus.max_spread);
+
+    if spread > max_spread {
+        let disagreement = SourceDisagreement { policy_id, readings: readings.clone(), spread, max_spread };
+        alerts::raise(&state.config, AlertKind::ProviderDegraded, now, &disagreement).await;
+        emit!(disagreement);
+    }
+    emit!(SourcesAggregated { policy_id, readings, median: settled });
+
+    Ok(Some(settled))
+}
+
+// ── Entry point: tenant sets (or drops) its consensus sources ─
+#[rialo::instruction]
+pub async fn set_consensus(
+    ctx:            Context<InsuranceState>,
+    underwriter_id: UnderwriterId,
+    consensus:      Option<Consensus>,
+) -> RialoResult<()> {
+
+    if let Some(consensus) = &consensus {
+        let sources = consensus.sources.len();
+        require!((1..=MAX_EXTRA_SOURCES).contains(&sources), InsuranceError::InvalidState("Consensus needs one or two other sources.".into()));
+        require!((1..=sources + 1).contains(&(consensus.quorum as usize)), InsuranceError::InvalidArgument("Quorum must be between 1 and the number of sources.".into()));
+        require!(consensus.max_spread.is_finite() && consensus.max_spread >= 0.0, InsuranceError::InvalidState("Allowed spread cannot be negative.".into()));
+    }
+
+    let mode = ctx.state.config.network_mode;
+    let underwriter = tenant_mut(&mut ctx.state, underwriter_id, &ctx.signer)?;
+    if let Some(consensus) = &consensus {
+        let mut urls = vec![underwriter.provider.base_url_for(mode)];
+        for provider in &consensus.sources {
+            let url = provider.base_url_for(mode);
+            require!(!urls.contains(&url), InsuranceError::InvalidArgument("Consensus sources must be distinct from each other and the tenant's provider.".into()));
+            urls.push(url);
+        }
+    }
+
+    emit!(ConsensusSet {
+        underwriter_id,
+        sources:    consensus.as_ref().map_or(0, |c| c.sources.len() as u32),
+        quorum:     consensus.as_ref().map_or(1, |c| c.quorum),
+        max_spread: consensus.as_ref().map_or(0.0, |c| c.max_spread),
+    });
+
+    underwriter.consensus = consensus;
+
+    Ok(())
+}
+
+// ── Entry point: admin sets the payout above which sources must be diverse
+#[rialo::instruction]
+pub async fn set_source_diversity(
+    ctx:   Context<InsuranceState>,
+    above: Option<Ralo>,
+) -> RialoResult<()> {
+
+    let config = &mut ctx.state.config;
+
+    require!(config.admin == *ctx.signer, InsuranceError::Unauthorized("Only the admin can change source diversity.".into()));
+
+    config.diversity_above = above;
+
+    emit!(SourceDiversitySet { above });
+
+    Ok(())
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct ConsensusSet          { pub underwriter_id: UnderwriterId, pub sources: u32, pub quorum: u8, pub max_spread: f64 }
+#[rialo::event] pub struct SourcesAggregated     { pub policy_id: PolicyId, pub readings: Vec<SourceReading>, pub median: Hundredths }
+#[rialo::event] pub struct SourceDisagreement    { pub policy_id: PolicyId, pub readings: Vec<SourceReading>, pub spread: Hundredths, pub max_spread: Hundredths }
+#[rialo::event] pub struct QuorumNotMet          { pub policy_id: PolicyId, pub answered: u32, pub quorum: u32 }
+#[rialo::event] pub struct SourceDiversityNotMet { pub policy_id: PolicyId, pub providers: u32, pub required: u32 }
+#[rialo::event] pub struct SourceDiversitySet    { pub above: Option<Ralo> }
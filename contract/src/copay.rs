@@ -0,0 +1,80 @@
+// ============================================================
+//  Co-payment
+//
+//  A customer who can carry part of a loss themselves can buy
+//  cheaper cover: with a co-pay of X% they absorb X% of every
+//  payout the policy calculates, and the premium drops by the
+//  same X%. The owner sets it on an unpaid policy, once, before
+//  any premium has come in; the share the customer retains is
+//  released from the underwriter's reserve at the same time.
+//
+//  Each trigger still calculates its payout on the full cover —
+//  a tier, a curve share or the whole amount — and the co-pay is
+//  taken off that. What was retained is itemized next to what was
+//  paid in `PolicyTriggered`. Rounding follows actuarial.rs: the
+//  retained share and the reduced premium round down, so the
+//  customer never absorbs, or pays, a base unit more than agreed.
+// ============================================================
+
+use rialo_sdk::prelude::*;
+
+use crate::actuarial::{charge_share, FULL_BPS};
+use crate::errors::InsuranceError;
+use crate::money::Ralo;
+use crate::policy::{Policy, PolicyId, PolicyStatus};
+use crate::InsuranceState;
+
+pub const MAX_COPAY_BPS: u64 = 5_000;
+
+// Part of the calculated payout `gross` the customer absorbs
+pub fn retained(gross: Ralo, copay_bps: u64) -> Ralo {
+    gross.bps(copay_bps)
+}
+
+// Part of the calculated payout `gross` the policy pays
+pub fn net(gross: Ralo, copay_bps: u64) -> Ralo {
+    gross - retained(gross, copay_bps)
+}
+
+// Put a co-pay on an unpaid policy and cut its premium to match; returns
+// the retained share of the cover, for the underwriter's reserve to release
+pub fn take_copay(policy: &mut Policy, copay_bps: u64, min_premium: Ralo) -> RialoResult<Ralo> {
+    require!(policy.status == PolicyStatus::PendingPayment, InsuranceError::InvalidState("Co-pay is fixed once coverage starts.".into()));
+    require!(policy.premium_paid.is_zero(), InsuranceError::InvalidState("Co-pay is fixed once premium payments start.".into()));
+    require!(policy.copay_bps == 0, InsuranceError::InvalidState("Co-pay is already set.".into()));
+    require!((1..=MAX_COPAY_BPS).contains(&copay_bps), InsuranceError::InvalidArgument("Co-pay must be between 0.01% and 50%.".into()));
+
+    let premium = charge_share(policy.premium_amount, FULL_BPS - copay_bps, FULL_BPS);
+    require!(premium >= min_premium, InsuranceError::InvalidState("Co-pay would take the premium below the minimum.".into()));
+
+    policy.copay_bps      = copay_bps;
+    policy.premium_amount = premium;
+
+    Ok(retained(policy.payout_amount, copay_bps))
+}
+
+// ── Entry point: owner takes a co-pay on an unpaid policy ────
+#[rialo::instruction]
+pub async fn set_copay(
+    ctx:       Context<InsuranceState>,
+    policy_id: PolicyId,
+    copay_bps: u64,
+) -> RialoResult<()> {
+
+    let min_premium = ctx.state.config.min_premium;
+    let policy = ctx.state.policies.get_mut(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+
+    require!(policy.owner == *ctx.signer, InsuranceError::Unauthorized("Only the policy owner can take a co-pay.".into()));
+    let released = take_copay(policy, copay_bps, min_premium)?;
+    let premium = policy.premium_amount;
+
+    let underwriter = ctx.state.underwriters.get_mut(&policy.underwriter_id).ok_or(InsuranceError::UnknownUnderwriter)?;
+    underwriter.reserved -= released;
+
+    emit!(CopaySet { policy_id, copay_bps, premium, released });
+
+    Ok(())
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct CopaySet { pub policy_id: PolicyId, pub copay_bps: u64, pub premium: Ralo, pub released: Ralo }
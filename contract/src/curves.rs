@@ -0,0 +1,195 @@
+// ============================================================
+//  Graded payout curves
+//
+//  All-or-nothing cover pays nothing at 49 mm and everything at
+//  50. A policy can instead be written on a curve that grades the
+//  payout by how far past its threshold a reading goes:
+//    • Flat   — the whole payout once the threshold is met
+//    • Tiers  — e.g. 10 mm → 25%, 25 mm → 60%, 50 mm → 100%;
+//               the furthest tier a reading reaches is paid
+//    • Linear — from nothing at the threshold up to the whole
+//               payout at `full_at`, in proportion
+//    • Deductible — only the rain past `deductible` counts: once
+//               the threshold is met, the share is how far the
+//               reading is past the deductible, in proportion to
+//               `full_at`'s distance from it. The deductible sits
+//               short of the threshold, so the threshold pays from
+//               its first millimetre
+//    • Franchise — nothing short of the threshold; at and past it
+//               every millimetre counts, from zero up to the whole
+//               payout at `full_at`. Only on cover that pays
+//               above a positive threshold
+//  Shares only ratchet up, like storm tiers: a later, worse
+//  reading tops the payout up to its share, a milder one pays
+//  nothing more. Reaching 100% settles the policy; a policy that
+//  never does is finalized as usual and releases what it didn't
+//  pay.
+//
+//  Curves grade single readings, so they sit on plain threshold
+//  products only — not on streaks, accumulations, normals or
+//  products with a trigger of their own.
+// ============================================================
+
+use serde::{Deserialize, Serialize};
+
+use crate::millimeters::Hundredths;
+use crate::normalization::Comparison;
+use crate::quote::QuoteError;
+use crate::settlement::FULL_SHARE_BPS;
+
+pub const MAX_PAYOUT_TIERS: usize = 8;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct PayoutTier {
+    pub at:        Hundredths,   // reading that reaches the tier, in hundredths of the peril's unit
+    pub share_bps: u64,          // share of the payout it pays (10_000 = all)
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub enum PayoutCurve {
+    #[default]
+    Flat,
+    Tiers(Vec<PayoutTier>),                                        // mildest first
+    Linear { full_at: Hundredths },                                // whole payout at and beyond `full_at`
+    Deductible { deductible: Hundredths, full_at: Hundredths },    // only the reading past `deductible` counts
+    Franchise { full_at: Hundredths },                             // the whole reading counts once the threshold is met
+}
+
+// A curve as layout 5 and earlier wrote it, in f64 of the peril's unit.
+// Decode-only: migrations.rs puts it on the fixed-point scale.
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub enum LegacyPayoutCurve {
+    Flat,
+    Tiers(Vec<LegacyPayoutTier>),
+    Linear { full_at: f64 },
+    Deductible { deductible: f64, full_at: f64 },
+    Franchise { full_at: f64 },
+}
+
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct LegacyPayoutTier {
+    pub at:        f64,
+    pub share_bps: u64,
+}
+
+impl From<LegacyPayoutCurve> for PayoutCurve {
+    fn from(curve: LegacyPayoutCurve) -> Self {
+        match curve {
+            LegacyPayoutCurve::Flat => PayoutCurve::Flat,
+            LegacyPayoutCurve::Tiers(tiers) => PayoutCurve::Tiers(tiers
+                .into_iter()
+                .map(|t| PayoutTier { at: Hundredths::from_f64(t.at), share_bps: t.share_bps })
+                .collect()),
+            LegacyPayoutCurve::Linear { full_at } => PayoutCurve::Linear { full_at: Hundredths::from_f64(full_at) },
+            LegacyPayoutCurve::Deductible { deductible, full_at } => PayoutCurve::Deductible {
+                deductible: Hundredths::from_f64(deductible),
+                full_at:    Hundredths::from_f64(full_at),
+            },
+            LegacyPayoutCurve::Franchise { full_at } => PayoutCurve::Franchise { full_at: Hundredths::from_f64(full_at) },
+        }
+    }
+}
+
+// Curve state carried by a policy written on a graded curve
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct GradedCover {
+    #[serde(default)]
+    pub payout_curve: PayoutCurve,
+    #[serde(rename = "curve", default, skip_serializing)]
+    pub old_curve:    Option<LegacyPayoutCurve>,   // layout 5 and earlier only: now `payout_curve` (migrations.rs)
+    pub paid_bps:     u64,                         // highest share paid so far
+}
+
+impl GradedCover {
+    pub fn new(payout_curve: PayoutCurve) -> Self {
+        GradedCover { payout_curve, old_curve: None, paid_bps: 0 }
+    }
+}
+
+// How far `reading` is past `threshold` on the paying side, in hundredths; None if short of it
+fn beyond(comparison: Comparison, threshold: Hundredths, reading: Hundredths) -> Option<u64> {
+    if !comparison.is_met(reading, threshold) {
+        return None;
+    }
+    Some(reading.0.abs_diff(threshold.0))
+}
+
+// `past` over `span`, as a share capped at the whole payout
+fn proportion(past: u64, span: u64) -> u64 {
+    (past as u128 * FULL_SHARE_BPS as u128 / span.max(1) as u128).min(FULL_SHARE_BPS as u128) as u64
+}
+
+impl PayoutCurve {
+    pub fn is_flat(&self) -> bool {
+        *self == PayoutCurve::Flat
+    }
+
+    // Share of the payout a reading is worth under a policy's threshold
+    pub fn share_bps(&self, comparison: Comparison, threshold: Hundredths, reading: Hundredths) -> u64 {
+        let Some(past) = beyond(comparison, threshold, reading) else {
+            return 0;
+        };
+        match self {
+            PayoutCurve::Flat => FULL_SHARE_BPS,
+            PayoutCurve::Tiers(tiers) => tiers
+                .iter()
+                .filter(|t| comparison.is_met(reading, t.at))
+                .map(|t| t.share_bps)
+                .max()
+                .unwrap_or(0),
+            PayoutCurve::Linear { full_at } => {
+                proportion(past, full_at.0.abs_diff(threshold.0))
+            }
+            PayoutCurve::Deductible { deductible, full_at } => {
+                proportion(reading.0.abs_diff(deductible.0), full_at.0.abs_diff(deductible.0))
+            }
+            PayoutCurve::Franchise { full_at } => {
+                proportion(reading.0.unsigned_abs(), full_at.0.unsigned_abs())
+            }
+        }
+    }
+
+    // Every tier past the threshold and further out than the last, paying
+    // more; a ramp that ends past the threshold, and a deductible short of it
+    pub fn validate(&self, comparison: Comparison, threshold: Hundredths) -> Result<(), QuoteError> {
+        let past = |at: Hundredths| beyond(comparison, threshold, at);
+        match self {
+            PayoutCurve::Flat => Ok(()),
+            PayoutCurve::Tiers(tiers) => {
+                if tiers.is_empty() || tiers.len() > MAX_PAYOUT_TIERS {
+                    return Err(QuoteError::InvalidPayoutCurve);
+                }
+                let mut last: Option<(u64, u64)> = None;
+                for tier in tiers {
+                    let Some(distance) = past(tier.at) else {
+                        return Err(QuoteError::InvalidPayoutCurve);
+                    };
+                    let ascending = last.is_none_or(|(d, s)| distance > d && tier.share_bps > s);
+                    if !(1..=FULL_SHARE_BPS).contains(&tier.share_bps) || !ascending {
+                        return Err(QuoteError::InvalidPayoutCurve);
+                    }
+                    last = Some((distance, tier.share_bps));
+                }
+                Ok(())
+            }
+            PayoutCurve::Linear { full_at } => match past(*full_at) {
+                Some(distance) if distance > 0 => Ok(()),
+                _ => Err(QuoteError::InvalidPayoutCurve),
+            },
+            PayoutCurve::Deductible { deductible, full_at } => {
+                // Short of the threshold: the threshold itself mustn't count as past it
+                if beyond(comparison, *deductible, threshold).is_none_or(|d| d == 0) {
+                    return Err(QuoteError::InvalidDeductible);
+                }
+                match past(*full_at) {
+                    Some(distance) if distance > 0 => Ok(()),
+                    _ => Err(QuoteError::InvalidPayoutCurve),
+                }
+            }
+            PayoutCurve::Franchise { full_at } => match past(*full_at) {
+                Some(distance) if distance > 0 && comparison == Comparison::AtOrAbove && threshold.is_positive() => Ok(()),
+                _ => Err(QuoteError::InvalidPayoutCurve),
+            },
+        }
+    }
+}
@@ -0,0 +1,260 @@
+// ============================================================
+//  Dashboard views
+//
+//  Read-only answers for the block explorer and frontends, so a
+//  policy dashboard needs neither raw account deserialization nor
+//  its own copy of the payout formula:
+//    • get_policy_status  — where a policy stands: cover window,
+//                           what it has paid and has left to pay
+//    • get_vault_balance  — what the vault holds, and how much of
+//                           it underwriters have set aside
+//    • preview_payout     — what a reading would pay a policy right
+//                           now, worked out by the settlement code
+//                           itself on a copy of the policy
+//    • explain_policy     — why a policy hasn't paid: its latest
+//                           check against the threshold, the time
+//                           left, and how far a trigger that builds
+//                           over several readings has got
+// ============================================================
+
+use rialo_sdk::prelude::*;
+use rialo_sdk::token::balance;
+use serde::Serialize;
+
+use crate::errors::InsuranceError;
+use crate::evaluation::Evaluation;
+use crate::millimeters::Hundredths;
+use crate::money::Ralo;
+use crate::normalization::{Comparison, Metric};
+use crate::observations::CheckId;
+use crate::policy::{Policy, PolicyId, PolicyStatus};
+use crate::settlement::{top_up, FULL_SHARE_BPS};
+use crate::InsuranceState;
+
+#[derive(Serialize, Clone, Debug)]
+pub struct PolicyStatusView {
+    pub policy_id:           PolicyId,
+    pub status:              PolicyStatus,
+    pub owner:               Pubkey,
+    pub peril:               Metric,
+    pub comparison:          Comparison,
+    pub threshold:           Hundredths,
+    pub coverage_start:      Option<i64>,
+    pub coverage_end:        Option<i64>,
+    pub covered_now:         bool,
+    pub payout:              Ralo,
+    pub paid_out:            Ralo,
+    pub coverage_remaining:  Ralo,          // still payable, net of co-pay
+    pub premium_outstanding: Ralo,
+    pub next_check_at:       Option<i64>,   // earliest the next live check may run
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct VaultBalance {
+    pub balance:           Ralo,   // RALO the vault holds
+    pub capital:           Ralo,   // underwriter capital, reserved or not
+    pub reserved:          Ralo,   // set aside for live policies' payouts
+    pub escrowed_premiums: Ralo,   // premiums waiting on activation
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct PayoutPreview {
+    pub reading:   Hundredths,
+    pub triggers:  bool,          // the reading would pay something
+    pub share_bps: u64,           // share of the cover it would bring the policy to
+    pub due:       Ralo,          // sent, net of co-pay and past payments
+    pub copay:     Ralo,          // newly absorbed by the customer
+    pub mint_due:  Option<u64>,   // mint-paying policies: tokens sent instead (mints.rs)
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct LatestCheck {
+    pub check_id:    CheckId,
+    pub reading:     Hundredths,
+    pub observed_at: i64,
+    pub decision:    Evaluation,
+}
+
+// One reason a policy hasn't paid (yet)
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub enum Unmet {
+    AwaitingPremium      { outstanding: Ralo },
+    OutsideCoverage      { coverage_start: Option<i64>, coverage_end: Option<i64> },
+    Closed,                                                         // settled, lapsed, expired, withdrawn or cancelled
+    NoCheckYet,
+    BelowThreshold       { reading: Hundredths },                   // the latest check fell short
+    ForecastUnconfirmed  { reading: Hundredths },                   // met, without the forecast behind it (forecasts.rs)
+    SmoothingWarmingUp   { readings: u32, required: u32 },          // window still filling (smoothing.rs)
+    StreakBuilding       { hours: u32, required: u32 },             // wet hours in a row so far
+    Accumulating         { total_mm: f64, threshold: Hundredths },  // rain over the rolling window
+    BelowNormal          { percent_of_normal: f64, threshold: Hundredths },
+    HeldByIncident       { readings: u32 },                         // parked until their provider's incident clears
+    AwaitingAttestation,                                            // triggered; the owner has to attest_loss
+    DisputeWindow        { payable_at: i64 },                       // triggered; pays once the window runs
+    PayoutDeferred,                                                 // triggered; retry_payout once the price is fair
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct PolicyExplanation {
+    pub policy_id:      PolicyId,
+    pub status:         PolicyStatus,
+    pub comparison:     Comparison,
+    pub threshold:      Hundredths,
+    pub latest:         Option<LatestCheck>,
+    pub secs_remaining: Option<i64>,   // of the coverage window, once it has started
+    pub paid_out:       Ralo,
+    pub unmet:          Vec<Unmet>,    // empty: nothing stands in the way of the next check paying
+}
+
+// Why `policy_id` hasn't paid, as of `now`
+pub fn explain(state: &InsuranceState, policy_id: PolicyId, now: i64) -> RialoResult<PolicyExplanation> {
+    let policy = state.policies.get(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+
+    let latest = state.claims_history
+        .get(&policy_id)
+        .and_then(|ids| ids.last())
+        .and_then(|id| state.checks.get(id).map(|c| LatestCheck { check_id: *id, reading: c.reading, observed_at: c.observed_at, decision: c.decision }));
+
+    let mut unmet = Vec::new();
+    match policy.status {
+        PolicyStatus::PendingPayment     => unmet.push(Unmet::AwaitingPremium { outstanding: policy.premium_outstanding() }),
+        PolicyStatus::PendingAttestation => unmet.push(Unmet::AwaitingAttestation),
+        PolicyStatus::PayoutDeferred     => unmet.push(Unmet::PayoutDeferred),
+        PolicyStatus::PendingPayout      => {
+            let payable_at = policy.dispute.as_ref().and_then(|c| c.pending).map_or(now, |c| c.payable_at);
+            unmet.push(Unmet::DisputeWindow { payable_at });
+        }
+        PolicyStatus::Active if !policy.is_covered_at(now) => {
+            unmet.push(Unmet::OutsideCoverage { coverage_start: policy.activated_at, coverage_end: policy.coverage_end() });
+        }
+        PolicyStatus::Active => explain_trigger(state, policy_id, policy, latest.as_ref(), &mut unmet),
+        _ => unmet.push(Unmet::Closed),
+    }
+
+    Ok(PolicyExplanation {
+        policy_id,
+        status:         policy.status,
+        comparison:     policy.comparison,
+        threshold:      policy.threshold,
+        latest,
+        secs_remaining: policy.coverage_end().map(|end| (end - now).max(0)),
+        paid_out:       policy.paid_out,
+        unmet,
+    })
+}
+
+// Where live cover's trigger stands: the latest check, then any progress
+// the trigger builds up over readings
+fn explain_trigger(state: &InsuranceState, policy_id: PolicyId, policy: &Policy, latest: Option<&LatestCheck>, unmet: &mut Vec<Unmet>) {
+    let held = state.held_observations.iter().filter(|o| o.policy_id == policy_id).count() as u32;
+    if held > 0 {
+        unmet.push(Unmet::HeldByIncident { readings: held });
+    }
+
+    match latest {
+        None => unmet.push(Unmet::NoCheckYet),
+        Some(check) => match check.decision {
+            Evaluation::NotMet      => unmet.push(Unmet::BelowThreshold { reading: check.reading }),
+            Evaluation::Unconfirmed => unmet.push(Unmet::ForecastUnconfirmed { reading: check.reading }),
+            Evaluation::WarmingUp   => {
+                let index = policy.smoothing.as_ref();
+                let (readings, required) = index.map_or((0, 0), |i| (i.readings.len() as u32, i.smoothing.samples()));
+                unmet.push(Unmet::SmoothingWarmingUp { readings, required });
+            }
+            Evaluation::Triggered { .. } => {}
+        },
+    }
+
+    let threshold = policy.threshold;
+    if let Some(streak) = policy.streak.filter(|s| !s.is_complete()) {
+        unmet.push(Unmet::StreakBuilding { hours: streak.hours(), required: streak.required_hours });
+    }
+    if let Some(accumulation) = &policy.accumulation {
+        unmet.push(Unmet::Accumulating { total_mm: accumulation.latest_total_mm().to_mm(), threshold });
+    }
+    if let Some(normal) = &policy.normal {
+        unmet.push(Unmet::BelowNormal { percent_of_normal: normal.percent_of_normal(latest.map_or(0, |c| c.observed_at)), threshold });
+    }
+}
+
+// What `reading` would pay `policy` if it settled at `at`. Streaks, normals
+// and rolling windows count it on top of what they have already recorded.
+pub fn preview(policy: &Policy, reading: Hundredths, at: i64) -> PayoutPreview {
+    let share_bps = match &policy.graded {
+        Some(graded) => {
+            let share = graded.payout_curve.share_bps(policy.comparison, policy.threshold, reading);
+            let live = policy.status == PolicyStatus::Active && policy.is_covered_at(at);
+            if live && share > graded.paid_bps { share } else { 0 }
+        }
+        None if policy.clone().apply_reading(reading, at) => FULL_SHARE_BPS,
+        None => 0,
+    };
+
+    let (due, copay) = if share_bps == 0 { (Ralo::ZERO, Ralo::ZERO) } else { top_up(policy, share_bps) };
+    let mint_due = policy.payout_mint.as_ref().map(|m| m.due(policy.paid_out + due, policy.payout_amount));
+
+    PayoutPreview { reading, triggers: !due.is_zero(), share_bps, due, copay, mint_due }
+}
+
+#[rialo::view]
+pub fn get_policy_status(
+    ctx:       Context<InsuranceState>,
+    policy_id: PolicyId,
+) -> RialoResult<PolicyStatusView> {
+
+    let now = ctx.clock.unix_timestamp;
+    let policy = ctx.state.policies.get(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+
+    Ok(PolicyStatusView {
+        policy_id,
+        status:              policy.status,
+        owner:               policy.owner,
+        peril:               policy.peril,
+        comparison:          policy.comparison,
+        threshold:           policy.threshold,
+        coverage_start:      policy.activated_at,
+        coverage_end:        policy.coverage_end(),
+        covered_now:         policy.is_covered_at(now),
+        payout:              policy.payout_amount,
+        paid_out:            policy.paid_out,
+        coverage_remaining:  policy.coverage_remaining(),
+        premium_outstanding: policy.premium_outstanding(),
+        next_check_at:       policy.last_checked.map(|t| t + policy.min_check_secs),
+    })
+}
+
+#[rialo::view]
+pub fn get_vault_balance(ctx: Context<InsuranceState>) -> RialoResult<VaultBalance> {
+    let underwriters = ctx.state.underwriters.values();
+
+    Ok(VaultBalance {
+        balance:           Ralo(balance(&ctx.vault)),
+        capital:           underwriters.clone().map(|u| u.capital).sum(),
+        reserved:          underwriters.map(|u| u.reserved).sum(),
+        escrowed_premiums: ctx.state.policies.values().map(|p| p.escrowed_premium()).sum(),
+    })
+}
+
+#[rialo::view]
+pub fn explain_policy(
+    ctx:       Context<InsuranceState>,
+    policy_id: PolicyId,
+) -> RialoResult<PolicyExplanation> {
+
+    explain(&ctx.state, policy_id, ctx.clock.unix_timestamp)
+}
+
+#[rialo::view]
+pub fn preview_payout(
+    ctx:         Context<InsuranceState>,
+    policy_id:   PolicyId,
+    rainfall_mm: f64,
+) -> RialoResult<PayoutPreview> {
+
+    let policy = ctx.state.policies.get(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+
+    require!(policy.settles_on_readings(), InsuranceError::InvalidState("Policy doesn't settle on a single reading.".into()));
+    require!(policy.bundle.is_none(), InsuranceError::InvalidState("Bundled cover settles on every bundled peril's reading at once.".into()));
+
+    Ok(preview(policy, Hundredths::from_f64(rainfall_mm), ctx.clock.unix_timestamp))
+}
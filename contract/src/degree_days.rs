@@ -0,0 +1,182 @@
+// ============================================================
+//  Degree-day cover
+//
+//  For energy retailers, greenhouses and anyone else whose costs
+//  follow how far a season runs from mild, not any one hour of
+//  it. A product counts one index from a base temperature:
+//    • heating degree-days — degrees below the base
+//    • cooling degree-days — degrees above the base
+//  Each check reads the current air temperature from the tenant's
+//  provider and counts its hour once, adding the hour's degrees
+//  past the base as 1/24 of a degree-day. Hours no check reads add
+//  nothing, so keepers check the policy hourly through its window.
+//
+//  The policy threshold is the strike, in degree-days. Each
+//  degree-day beyond it pays the product's tick, a share of the
+//  payout; the payout tops up as degree-days accumulate, and the
+//  policy settles once it reaches the full amount.
+// ============================================================
+
+use rialo_sdk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::claims::{record_lae, LaeKind};
+use crate::config::require_unpaused;
+use crate::errors::InsuranceError;
+use crate::incidents::under_incident;
+use crate::keys::{lease_key, report_key};
+use crate::millimeters::Hundredths;
+use crate::policy::{PolicyId, PolicyStatus};
+use crate::providers::{ReadingTime, WeatherProvider};
+use crate::settlement::{self, FULL_SHARE_BPS};
+use crate::underwriter::{tenant_mut, TemplateId, UnderwriterId};
+use crate::{fetch, provider_headers, InsuranceState};
+
+const SECS_PER_HOUR: i64 = 60 * 60;
+const HOURS_PER_DAY: f64 = 24.0;
+
+// Base temperatures a product can count from, °C
+const MIN_BASE_C: f64 = -20.0;
+const MAX_BASE_C: f64 = 35.0;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DegreeDayIndex {
+    Heating,   // degrees below the base
+    Cooling,   // degrees above the base
+}
+
+impl DegreeDayIndex {
+    // Degrees a reading runs past `base_c` on this index's side; none on the other
+    pub fn degrees(self, temperature_c: f64, base_c: f64) -> f64 {
+        let degrees = match self {
+            DegreeDayIndex::Heating => base_c - temperature_c,
+            DegreeDayIndex::Cooling => temperature_c - base_c,
+        };
+        degrees.max(0.0)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct DegreeDayTrigger {
+    pub index:    DegreeDayIndex,
+    pub base_c:   f64,   // temperature degrees are counted from, e.g. 18 °C
+    pub tick_bps: u64,   // share of the payout each degree-day beyond the strike pays
+}
+
+// Degree-day state carried by a policy sold under a degree-day template
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct DegreeDayCover {
+    pub trigger:     DegreeDayTrigger,
+    pub degree_days: f64,           // accumulated over the window so far
+    pub hours:       u32,           // hours read so far
+    pub last_hour:   Option<i64>,   // hour of the last counted reading
+    pub paid_bps:    u64,           // share of the payout paid so far
+}
+
+impl DegreeDayCover {
+    pub fn new(trigger: DegreeDayTrigger) -> Self {
+        DegreeDayCover { trigger, degree_days: 0.0, hours: 0, last_hour: None, paid_bps: 0 }
+    }
+
+    // Whether a reading at `now` falls in an hour not yet counted
+    pub fn can_check_at(&self, now: i64) -> bool {
+        self.last_hour.is_none_or(|last| now.div_euclid(SECS_PER_HOUR) > last)
+    }
+
+    // Fold one hourly reading in; returns the degree-days accumulated
+    pub fn record(&mut self, temperature_c: f64, now: i64) -> f64 {
+        self.last_hour = Some(now.div_euclid(SECS_PER_HOUR));
+        self.hours += 1;
+        self.degree_days += self.trigger.index.degrees(temperature_c, self.trigger.base_c) / HOURS_PER_DAY;
+        self.degree_days
+    }
+
+    // Share of the payout the degree-days so far have earned beyond `strike`
+    pub fn share_bps(&self, strike: f64) -> u64 {
+        let beyond = (self.degree_days - strike).max(0.0);
+        ((beyond * self.trigger.tick_bps as f64) as u64).min(FULL_SHARE_BPS)
+    }
+}
+
+// ── Entry point: make (or unmake) a template a degree-day product
+#[rialo::instruction]
+pub async fn set_template_degree_days(
+    ctx:            Context<InsuranceState>,
+    underwriter_id: UnderwriterId,
+    template_id:    TemplateId,
+    trigger:        Option<DegreeDayTrigger>,
+) -> RialoResult<()> {
+
+    if let Some(trigger) = &trigger {
+        require!((MIN_BASE_C..=MAX_BASE_C).contains(&trigger.base_c), InsuranceError::InvalidArgument("Base temperature must be between -20 and 35 °C.".into()));
+        require!((1..=FULL_SHARE_BPS).contains(&trigger.tick_bps), InsuranceError::InvalidArgument("Tick must be between 1 and 10000 basis points of the payout.".into()));
+    }
+
+    let underwriter = tenant_mut(&mut ctx.state, underwriter_id, &ctx.signer)?;
+    let template = underwriter.templates.get_mut(&template_id).ok_or(InsuranceError::UnknownTemplate)?;
+    template.degree_days = trigger;
+
+    emit!(TemplateDegreeDaysSet { underwriter_id, template_id, trigger });
+
+    Ok(())
+}
+
+// ── Entry point: anyone reads this hour's temperature for a policy
+#[rialo::instruction]
+pub async fn check_degree_days_and_pay(
+    ctx:       Context<InsuranceState>,
+    policy_id: PolicyId,
+) -> RialoResult<()> {
+
+    require_unpaused(&ctx.state.config)?;
+    let now = ctx.clock.unix_timestamp;
+    let policy = ctx.state.policies.get(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+
+    require!(policy.status == PolicyStatus::Active, InsuranceError::PolicyNotActive);
+    require!(policy.is_covered_at(now), InsuranceError::PolicyExpired);
+    let cover = policy.degree_days.ok_or_else(|| InsuranceError::InvalidState("Policy is not degree-day cover.".into()))?;
+    require!(cover.can_check_at(now), InsuranceError::InvalidState("This hour has already been counted.".into()));
+
+    let underwriter_id = policy.underwriter_id;
+    let underwriter = ctx.state.underwriters.get(&underwriter_id).ok_or(InsuranceError::UnknownUnderwriter)?;
+    let source = underwriter.provider.base_url_for(ctx.state.config.network_mode).to_string();
+    require!(!under_incident(&ctx.state, &source, now), InsuranceError::DataIncident);
+
+    let lease = lease_key(&mut ctx.state, underwriter_id, now)?;
+    let policy = ctx.state.policies.get(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+    let underwriter = ctx.state.underwriters.get(&underwriter_id).ok_or(InsuranceError::UnknownUnderwriter)?;
+    let provider = &underwriter.provider;
+    let url = provider.kind.build_request(&source, &lease.key, &policy.place, ReadingTime::Current);
+    let headers = provider_headers(underwriter, policy.template_id)?;
+    let call_cost = provider.cost_per_call;
+    let kind = provider.kind;
+
+    let response = fetch(&url, &headers).await?;
+    if !report_key(&mut ctx.state, underwriter_id, &lease, response.status(), now) {
+        return Ok(());
+    }
+    let observation = kind.parse_observation(ReadingTime::Current, response.body())?;
+    let temperature_c = observation.temperature_c.ok_or_else(|| InsuranceError::BadResponse("Weather response has no temperature reading.".into()))?;
+
+    record_lae(&mut ctx.state, policy_id, LaeKind::ProviderCost, call_cost);
+
+    let policy = ctx.state.policies.get_mut(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+    let strike = policy.threshold.to_f64();
+    let cover = policy.degree_days.as_mut().ok_or_else(|| InsuranceError::InvalidState("Policy is not degree-day cover.".into()))?;
+    let degree_days = cover.record(temperature_c, now);
+    let (share_bps, paid_bps) = (cover.share_bps(strike), cover.paid_bps);
+
+    emit!(DegreeDaysChecked { policy_id, temperature_c, degree_days, strike, share_bps, paid_bps });
+
+    // Shares only ratchet up: degree-days never come off the total
+    if share_bps > paid_bps {
+        cover.paid_bps = share_bps;
+        settlement::pay_share(&mut ctx.state, &ctx.vault, policy_id, None, share_bps, Hundredths::from_f64(degree_days), now, now)?;
+    }
+
+    Ok(())
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct TemplateDegreeDaysSet { pub underwriter_id: UnderwriterId, pub template_id: TemplateId, pub trigger: Option<DegreeDayTrigger> }
+#[rialo::event] pub struct DegreeDaysChecked     { pub policy_id: PolicyId, pub temperature_c: f64, pub degree_days: f64, pub strike: f64, pub share_bps: u64, pub paid_bps: u64 }
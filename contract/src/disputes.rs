@@ -0,0 +1,298 @@
+// ============================================================
+//  Dispute windows
+//
+//  By default a trigger pays there and then. A template can put a
+//  dispute window in front of its payouts instead, so a bad
+//  reading can be challenged before money moves:
+//    trigger          → nothing is paid yet; the policy waits in
+//                       PendingPayout with the payout it is owed,
+//                       and `ClaimPending` says when it can be paid
+//    dispute_claim    → within the window, the owner or an arbiter
+//                       has the reading re-read by a second source
+//                       (an independent audit provider, audit.rs).
+//                       If it also sits on the paying side of the
+//                       threshold the payout is confirmed and paid
+//                       at once; if not, it's refuted and dropped,
+//                       and the policy goes back to live cover
+//    finalize_payout  → after an undisputed window, anyone pays it
+//  Either way it pays under the round that triggered it. A claim
+//  can be disputed once; the second source's answer stands. The
+//  call is the underwriter's cost, booked as loss adjustment.
+//
+//  An arbiter can go further with reevaluate_settlement, naming the
+//  audit providers (by base URL) to re-read the settled hour from.
+//  None may be the source the reading came from, and every one has
+//  to answer; their median is the verdict:
+//    • a claim still in its window is confirmed and paid, or
+//      refuted and dropped, as a dispute would
+//    • a payout already made stays made; the verdict is filed as
+//      a second receipt for the settlement (receipts.rs), which
+//      confirms the first or supersedes its reading
+//  A settlement is re-evaluated once, at the underwriter's cost.
+// ============================================================
+
+use rialo_sdk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::claims::{record_lae, LaeKind};
+use crate::config::require_unpaused;
+use crate::errors::InsuranceError;
+use crate::fx::DeferredPayout;
+use crate::millimeters::Hundredths;
+use crate::observations::CheckId;
+use crate::policy::{PolicyId, PolicyStatus};
+use crate::providers::{ReadingTime, WeatherProvider};
+use crate::receipts::{self, SettlementKey, SettlementReview};
+use crate::underwriter::{tenant_mut, TemplateId, UnderwriterId};
+use crate::whitelist::{self, Role};
+use crate::{consensus, fetch, settlement, InsuranceState};
+
+pub const MAX_DISPUTE_WINDOW_SECS: i64 = 7 * 24 * 60 * 60;
+
+// A triggered payout waiting out its dispute window
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct PendingClaim {
+    pub payout:     DeferredPayout,
+    pub payable_at: i64,   // end of the dispute window
+}
+
+// Dispute state carried by a policy sold under a template with a window
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct DisputeCover {
+    pub window_secs: i64,
+    pub pending:     Option<PendingClaim>,
+    pub cleared:     Vec<Option<CheckId>>,   // rounds whose payout came through its window
+}
+
+impl DisputeCover {
+    pub fn new(window_secs: i64) -> Self {
+        DisputeCover { window_secs, pending: None, cleared: Vec::new() }
+    }
+
+    pub fn is_cleared(&self, round_id: Option<CheckId>) -> bool {
+        self.cleared.contains(&round_id)
+    }
+}
+
+// Whether a payout may go ahead; if its window hasn't run, parks it on the
+// policy and returns false
+pub(crate) fn await_window(
+    state:       &mut InsuranceState,
+    policy_id:   PolicyId,
+    round_id:    Option<CheckId>,
+    share_bps:   u64,
+    reading:     Hundredths,
+    observed_at: i64,
+    now:         i64,
+) -> RialoResult<bool> {
+
+    let policy = state.policies.get_mut(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+    let Some(cover) = policy.dispute.as_mut() else {
+        return Ok(true);
+    };
+    if cover.is_cleared(round_id) {
+        return Ok(true);
+    }
+
+    let payable_at = now + cover.window_secs;
+    cover.pending = Some(PendingClaim { payout: DeferredPayout::new(round_id, share_bps, reading, observed_at), payable_at });
+    policy.status = PolicyStatus::PendingPayout;
+
+    emit!(ClaimPending { policy_id, round_id, reading, payable_at });
+    Ok(false)
+}
+
+// Clear a pending claim's round and pay it
+pub(crate) fn release(state: &mut InsuranceState, vault: &Vault, policy_id: PolicyId, now: i64) -> RialoResult<()> {
+    let policy = state.policies.get_mut(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+    let cover = policy.dispute.as_mut().ok_or_else(|| InsuranceError::InvalidState("Policy has no dispute window.".into()))?;
+    let claim = cover.pending.take().ok_or_else(|| InsuranceError::InvalidState("Policy has no payout waiting on its dispute window.".into()))?;
+    cover.cleared.push(claim.payout.round_id);
+    // Back to live cover to settle; a USD payout can still defer on the price
+    policy.status = PolicyStatus::Active;
+
+    let p = claim.payout;
+    settlement::pay_share(state, vault, policy_id, p.round_id, p.share_bps, p.value, p.observed_at, now)?;
+    Ok(())
+}
+
+// ── Entry point: put a dispute window in front of a template's payouts
+#[rialo::instruction]
+pub async fn set_template_dispute_window(
+    ctx:            Context<InsuranceState>,
+    underwriter_id: UnderwriterId,
+    template_id:    TemplateId,
+    window_secs:    Option<i64>,
+) -> RialoResult<()> {
+
+    require!(window_secs.is_none_or(|w| (1..=MAX_DISPUTE_WINDOW_SECS).contains(&w)), InsuranceError::InvalidArgument("Dispute window must be between one second and a week.".into()));
+
+    let underwriter = tenant_mut(&mut ctx.state, underwriter_id, &ctx.signer)?;
+    let template = underwriter.templates.get_mut(&template_id).ok_or(InsuranceError::UnknownTemplate)?;
+    template.dispute_window_secs = window_secs;
+
+    emit!(TemplateDisputeWindowSet { underwriter_id, template_id, window_secs });
+
+    Ok(())
+}
+
+// ── Entry point: owner or arbiter has a second source re-read a claim
+#[rialo::instruction]
+pub async fn dispute_claim(
+    ctx:       Context<InsuranceState>,
+    policy_id: PolicyId,
+) -> RialoResult<()> {
+
+    let now = ctx.clock.unix_timestamp;
+    let mode = ctx.state.config.network_mode;
+    let is_arbiter = ctx.state.config.arbiters.contains(&ctx.signer);
+    let policy = ctx.state.policies.get(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+
+    require!(policy.owner == *ctx.signer || is_arbiter, InsuranceError::Unauthorized("Only the owner or an arbiter can dispute a claim.".into()));
+    let role = if policy.owner == *ctx.signer { Role::Policyholder } else { Role::Arbiter };
+    whitelist::require_role(&ctx.state, &ctx.signer, role)?;
+    require!(policy.status == PolicyStatus::PendingPayout, InsuranceError::InvalidState("Policy has no payout waiting on its dispute window.".into()));
+    let claim = policy.dispute.as_ref().and_then(|c| c.pending).ok_or_else(|| InsuranceError::InvalidState("Policy has no payout waiting on its dispute window.".into()))?;
+    require!(now < claim.payable_at, InsuranceError::InvalidState("Dispute window has closed; finalize the payout instead.".into()));
+
+    // The second source must be one the reading didn't come from
+    let original = claim.payout.round_id.and_then(|id| ctx.state.checks.get(&id)).map(|r| r.source.clone());
+    let provider = ctx.state.config.audit.providers
+        .iter()
+        .find(|p| original.as_deref() != Some(p.base_url_for(mode)))
+        .ok_or_else(|| InsuranceError::InvalidState("No independent source configured to dispute against.".into()))?;
+
+    let source = provider.base_url_for(mode).to_string();
+    let when = ReadingTime::Hour(claim.payout.observed_at);
+    let url = provider.kind.build_request(&source, &provider.api_key()?, &policy.place, when);
+    let call_cost = provider.cost_per_call;
+    let kind = provider.kind;
+    let (peril, comparison, threshold) = (policy.peril, policy.comparison, policy.threshold);
+
+    let response = fetch(&url, &[]).await?;
+    let observation = kind.parse_observation(when, response.body())?;
+    let second = observation.metric(peril).ok_or_else(|| InsuranceError::BadResponse("Second source has no reading for this peril.".into()))?;
+    record_lae(&mut ctx.state, policy_id, LaeKind::ProviderCost, call_cost);
+
+    let confirmed = comparison.is_met(second, threshold);
+    emit!(ClaimDisputed { policy_id, round_id: claim.payout.round_id, disputed_by: *ctx.signer, source, original: claim.payout.value, second, confirmed });
+
+    if confirmed {
+        return release(&mut ctx.state, &ctx.vault, policy_id, now);
+    }
+
+    refute(&mut ctx.state, policy_id)
+}
+
+// Drop a pending claim whose reading didn't stand up; the cover runs on
+pub(crate) fn refute(state: &mut InsuranceState, policy_id: PolicyId) -> RialoResult<()> {
+    let policy = state.policies.get_mut(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+    if let Some(cover) = policy.dispute.as_mut() {
+        cover.pending = None;
+    }
+    policy.status = PolicyStatus::Active;
+
+    Ok(())
+}
+
+// ── Entry point: anyone pays a claim once its window has run ─
+#[rialo::instruction]
+pub async fn finalize_payout(
+    ctx:       Context<InsuranceState>,
+    policy_id: PolicyId,
+) -> RialoResult<()> {
+
+    require_unpaused(&ctx.state.config)?;
+    let now = ctx.clock.unix_timestamp;
+    let policy = ctx.state.policies.get(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+
+    require!(policy.status == PolicyStatus::PendingPayout, InsuranceError::InvalidState("Policy has no payout waiting on its dispute window.".into()));
+    let claim = policy.dispute.as_ref().and_then(|c| c.pending).ok_or_else(|| InsuranceError::InvalidState("Policy has no payout waiting on its dispute window.".into()))?;
+    require!(now >= claim.payable_at, InsuranceError::InvalidState("Dispute window is still open.".into()));
+
+    release(&mut ctx.state, &ctx.vault, policy_id, now)
+}
+
+// ── Entry point: arbiter re-reads a settlement on chosen sources
+//
+//  Returns whether the re-read confirms the trigger.
+//
+#[rialo::instruction]
+pub async fn reevaluate_settlement(
+    ctx:       Context<InsuranceState>,
+    policy_id: PolicyId,
+    round_id:  Option<CheckId>,
+    providers: Vec<String>,
+) -> RialoResult<bool> {
+
+    require_unpaused(&ctx.state.config)?;
+    let now = ctx.clock.unix_timestamp;
+    let mode = ctx.state.config.network_mode;
+
+    require!(ctx.state.config.arbiters.contains(&ctx.signer), InsuranceError::Unauthorized("Only an arbiter can re-evaluate a settlement.".into()));
+    whitelist::require_role(&ctx.state, &ctx.signer, Role::Arbiter)?;
+    require!(!providers.is_empty(), InsuranceError::InvalidArgument("Name at least one provider to re-evaluate against.".into()));
+
+    let policy = ctx.state.policies.get(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+    let key = SettlementKey { policy_id, round_id };
+    let pending = policy.dispute.as_ref()
+        .and_then(|c| c.pending)
+        .filter(|c| policy.status == PolicyStatus::PendingPayout && c.payout.round_id == round_id);
+
+    // A claim in its window, or the receipt of one already paid
+    let (reading, observed_at, payee) = match pending {
+        Some(claim) => (claim.payout.value, claim.payout.observed_at, None),
+        None => {
+            let (payee, receipt) = receipts::find_receipt(&ctx.state, key).ok_or_else(|| InsuranceError::InvalidState("No settlement for that policy and round.".into()))?;
+            require!(!receipts::is_reviewed(&ctx.state, key), InsuranceError::InvalidState("Settlement has already been re-evaluated.".into()));
+            (receipt.value, receipt.observed_at, Some(payee))
+        }
+    };
+
+    let original = round_id.and_then(|id| ctx.state.checks.get(&id)).map(|r| r.source.clone());
+    let mut sources = Vec::new();
+    for url in &providers {
+        let provider = ctx.state.config.audit.providers
+            .iter()
+            .find(|p| p.base_url_for(mode) == url)
+            .ok_or_else(|| InsuranceError::InvalidState("Provider is not a configured audit source.".into()))?;
+        require!(original.as_deref() != Some(url.as_str()), InsuranceError::InvalidState("The settlement's own source can't re-evaluate it.".into()));
+        require!(!sources.iter().any(|(s, _)| s == url), InsuranceError::InvalidArgument("Provider named twice.".into()));
+        sources.push((url.clone(), provider.clone()));
+    }
+
+    let when = ReadingTime::Hour(observed_at);
+    let (place, peril, comparison, threshold) = (policy.place.clone(), policy.peril, policy.comparison, policy.threshold);
+    let mut readings = Vec::new();
+    for (source, provider) in &sources {
+        let url = provider.kind.build_request(source, &provider.api_key()?, &place, when);
+        let response = fetch(&url, &[]).await?;
+        let observation = provider.kind.parse_observation(when, response.body())?;
+        readings.push(observation.metric(peril).ok_or_else(|| InsuranceError::BadResponse("A re-evaluation source has no reading for this peril.".into()))?);
+        record_lae(&mut ctx.state, policy_id, LaeKind::ProviderCost, provider.cost_per_call);
+    }
+
+    let second = consensus::median(&readings).ok_or_else(|| InsuranceError::InvalidState("No re-evaluation readings.".into()))?;
+    let confirmed = comparison.is_met(second, threshold);
+    let sources: Vec<String> = sources.into_iter().map(|(s, _)| s).collect();
+
+    emit!(SettlementReevaluated { policy_id, round_id, reevaluated_by: *ctx.signer, sources: sources.clone(), original: reading, second, confirmed });
+
+    match payee {
+        // Paid already: the verdict goes on record beside the first receipt
+        Some(payee) => {
+            let review = SettlementReview { sources, original_value: reading, old_original: None, confirmed };
+            receipts::file_review(&mut ctx.state, payee, key, second, observed_at, now, review);
+        }
+        None if confirmed => release(&mut ctx.state, &ctx.vault, policy_id, now)?,
+        None => refute(&mut ctx.state, policy_id)?,
+    }
+
+    Ok(confirmed)
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct TemplateDisputeWindowSet { pub underwriter_id: UnderwriterId, pub template_id: TemplateId, pub window_secs: Option<i64> }
+#[rialo::event] pub struct ClaimPending             { pub policy_id: PolicyId, pub round_id: Option<CheckId>, pub reading: Hundredths, pub payable_at: i64 }
+#[rialo::event] pub struct ClaimDisputed            { pub policy_id: PolicyId, pub round_id: Option<CheckId>, pub disputed_by: Pubkey, pub source: String, pub original: Hundredths, pub second: Hundredths, pub confirmed: bool }
+#[rialo::event] pub struct SettlementReevaluated    { pub policy_id: PolicyId, pub round_id: Option<CheckId>, pub reevaluated_by: Pubkey, pub sources: Vec<String>, pub original: Hundredths, pub second: Hundredths, pub confirmed: bool }
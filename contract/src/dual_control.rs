@@ -0,0 +1,142 @@
+// ============================================================
+//  Dual control on capital withdrawals
+//
+//  One hot key used to be able to move a tenant's whole vault.
+//  Above an amount the admin sets, a withdrawal now needs two
+//  distinct keys to sign off before it pays: any two of the
+//  tenant's authority, the contract admin and the tenant's
+//  co-signers. Sign-offs are collected in the same approval
+//  records the arbiter multi-sig uses (approvals.rs), keyed on
+//  the withdrawal request, so a cancelled or re-sized request
+//  starts again from zero. Co-signers are appointed by the admin:
+//  a tenant able to name its own would still be one key.
+//
+//  Withdrawals below the amount, or with no amount set, are
+//  executed by the tenant's authority alone, as before.
+// ============================================================
+
+use rialo_sdk::prelude::*;
+use serde::Serialize;
+
+use crate::approvals::{approve, subject_hash};
+use crate::errors::InsuranceError;
+use crate::money::Ralo;
+use crate::underwriter::{Underwriter, UnderwriterId, WithdrawalRequest};
+use crate::{Hash, InsuranceState};
+
+// Distinct sign-offs a withdrawal under dual control needs
+pub const REQUIRED_SIGN_OFFS: usize = 2;
+pub const MAX_CO_SIGNERS:     usize = 3;
+
+#[derive(Serialize)]
+struct WithdrawalAction {
+    action:         &'static str,
+    underwriter_id: UnderwriterId,
+    amount:         Ralo,
+    requested_at:   i64,
+}
+
+// Approval record a withdrawal request's sign-offs collect under
+pub fn withdrawal_subject(underwriter_id: UnderwriterId, request: &WithdrawalRequest) -> Hash {
+    subject_hash(&WithdrawalAction {
+        action:       "execute_withdrawal",
+        underwriter_id,
+        amount:       request.amount,
+        requested_at: request.requested_at,
+    })
+}
+
+// Whether `amount` is large enough to need a second key
+pub fn needs_dual_control(threshold: Option<Ralo>, amount: Ralo) -> bool {
+    threshold.is_some_and(|above| amount >= above)
+}
+
+// Keys whose sign-off counts towards a tenant's withdrawals
+pub fn can_sign_off(underwriter: &Underwriter, admin: &Pubkey, signer: &Pubkey) -> bool {
+    underwriter.authority == *signer || admin == signer || underwriter.co_signers.contains(signer)
+}
+
+// Record `signer`'s sign-off on a tenant's pending withdrawal. Returns true
+// once it may pay; the sign-offs are cleared then.
+pub(crate) fn sign_off_withdrawal(
+    state:          &mut InsuranceState,
+    underwriter_id: UnderwriterId,
+    signer:         Pubkey,
+    now:            i64,
+) -> RialoResult<bool> {
+
+    let underwriter = state.underwriters.get(&underwriter_id).ok_or(InsuranceError::UnknownUnderwriter)?;
+    let request = underwriter.withdrawal.ok_or_else(|| InsuranceError::InvalidState("No withdrawal pending.".into()))?;
+
+    if !needs_dual_control(state.config.dual_control_above, request.amount) {
+        require!(underwriter.authority == signer, InsuranceError::Unauthorized("Signer does not administer this underwriter.".into()));
+        return Ok(true);
+    }
+    require!(can_sign_off(underwriter, &state.config.admin, &signer), InsuranceError::Unauthorized("Signer can't sign off this underwriter's withdrawals.".into()));
+
+    let subject = withdrawal_subject(underwriter_id, &request);
+    let sign_offs = approve(&mut state.approvals, subject, signer, now)?;
+
+    emit!(WithdrawalSignedOff {
+        underwriter_id,
+        signer,
+        amount:    request.amount,
+        sign_offs: sign_offs as u32,
+        required:  REQUIRED_SIGN_OFFS as u32,
+    });
+
+    if sign_offs < REQUIRED_SIGN_OFFS {
+        return Ok(false);
+    }
+    state.approvals.remove(&subject);
+
+    Ok(true)
+}
+
+// ── Entry point: admin sets (or lifts) the dual-control amount ─
+#[rialo::instruction]
+pub async fn set_dual_control(
+    ctx:   Context<InsuranceState>,
+    above: Option<Ralo>,
+) -> RialoResult<()> {
+
+    let config = &mut ctx.state.config;
+
+    require!(config.admin == *ctx.signer, InsuranceError::Unauthorized("Only the admin can change dual control.".into()));
+    require!(above.is_none_or(|amount| !amount.is_zero()), InsuranceError::InvalidArgument("Dual-control amount must be non-zero.".into()));
+
+    config.dual_control_above = above;
+
+    emit!(DualControlSet { above });
+
+    Ok(())
+}
+
+// ── Entry point: admin appoints a tenant's withdrawal co-signers
+#[rialo::instruction]
+pub async fn set_co_signers(
+    ctx:            Context<InsuranceState>,
+    underwriter_id: UnderwriterId,
+    co_signers:     Vec<Pubkey>,
+) -> RialoResult<()> {
+
+    let state = &mut ctx.state;
+
+    require!(state.config.admin == *ctx.signer, InsuranceError::Unauthorized("Only the admin can appoint co-signers.".into()));
+    require!(co_signers.len() <= MAX_CO_SIGNERS, InsuranceError::InvalidArgument("Too many co-signers.".into()));
+    let distinct = co_signers.iter().enumerate().all(|(i, key)| !co_signers[..i].contains(key));
+    require!(distinct, InsuranceError::InvalidArgument("Co-signers must be distinct.".into()));
+
+    let underwriter = state.underwriters.get_mut(&underwriter_id).ok_or(InsuranceError::UnknownUnderwriter)?;
+    require!(!co_signers.contains(&underwriter.authority), InsuranceError::InvalidState("The tenant's authority already signs off.".into()));
+    underwriter.co_signers = co_signers.clone();
+
+    emit!(CoSignersSet { underwriter_id, co_signers });
+
+    Ok(())
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct DualControlSet      { pub above: Option<Ralo> }
+#[rialo::event] pub struct CoSignersSet        { pub underwriter_id: UnderwriterId, pub co_signers: Vec<Pubkey> }
+#[rialo::event] pub struct WithdrawalSignedOff { pub underwriter_id: UnderwriterId, pub signer: Pubkey, pub amount: Ralo, pub sign_offs: u32, pub required: u32 }
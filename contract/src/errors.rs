@@ -0,0 +1,178 @@
+// ============================================================
+//  Contract errors
+//
+//  Every failure the contract returns carries a stable code, so
+//  a frontend can match on what went wrong instead of parsing
+//  messages. `InsuranceError` names the failures callers act on —
+//  an unknown id, a closed policy, a capital shortfall, a provider
+//  that didn't answer — and sorts the rest into broad kinds, each
+//  with its own code and the human-readable detail:
+//    Unauthorized    — the signer may not do this
+//    InvalidArgument — the request itself is malformed
+//    InvalidState    — valid, but not now or not for this record
+//    BadResponse     — an outside service answered unusably
+//  The typed errors of quoting (quote.rs), the check cooldown
+//  (throttle.rs) and rate limits (rate_limits.rs) keep their
+//  codes, and fold in unchanged. Codes never move once shipped:
+//  new variants take new numbers. Failure events carry the code
+//  of what went wrong.
+// ============================================================
+
+use rialo_sdk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::geo::CoordinateError;
+use crate::millimeters::Hundredths;
+use crate::money::{format_ralo, Ralo};
+use crate::normalization::format_mm;
+use crate::quote::QuoteError;
+use crate::rate_limits::{RateLimitExceeded, RATE_LIMIT_EXCEEDED_CODE};
+use crate::resilience::FetchError;
+use crate::throttle::{CheckThrottled, CHECK_THROTTLED_CODE};
+use crate::whitelist::Role;
+
+// After the quote errors, the check cooldown's and the rate limits'
+const INSURANCE_ERROR_BASE: u32 = 6_200;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum InsuranceError {
+    // Lookups
+    UnknownPolicy,
+    UnknownUnderwriter,
+    UnknownTemplate,
+    UnknownSubscription,
+    UnknownRecord(String),                 // any other id that isn't on record
+    // Who may act
+    Unauthorized(String),
+    NotWhitelisted(Role),
+    ContractPaused,
+    // Policy lifecycle
+    AlreadyPaidOut,
+    PolicyNotActive,
+    PolicyExpired,                         // its coverage has ended
+    PremiumNotCleared,
+    // Terms, limits and capital
+    BelowMinThreshold { min: Hundredths },
+    PayoutCapExceeded { max: Ralo },
+    VaultInsolvent(String),
+    InvalidArgument(String),
+    ArithmeticOverflow,                    // a ledger amount would wrap
+    // Weather data and outside services
+    HttpError(u16),                        // the provider answered with a non-2xx status
+    ProviderTimeout,
+    BadResponse(String),
+    DataIncident,
+    CallBudgetExhausted,
+    // Everything else that can't happen now
+    InvalidState(String),
+    // Typed errors with codes of their own
+    Quote(QuoteError),
+    Throttled(CheckThrottled),
+    RateLimited(RateLimitExceeded),
+}
+
+impl InsuranceError {
+    pub fn code(&self) -> u32 {
+        let index = match self {
+            InsuranceError::UnknownPolicy             => 0,
+            InsuranceError::UnknownUnderwriter        => 1,
+            InsuranceError::UnknownTemplate           => 2,
+            InsuranceError::UnknownSubscription       => 3,
+            InsuranceError::UnknownRecord(_)          => 4,
+            InsuranceError::Unauthorized(_)           => 10,
+            InsuranceError::NotWhitelisted(_)         => 11,
+            InsuranceError::ContractPaused            => 12,
+            InsuranceError::AlreadyPaidOut            => 20,
+            InsuranceError::PolicyNotActive           => 21,
+            InsuranceError::PolicyExpired             => 22,
+            InsuranceError::PremiumNotCleared         => 23,
+            InsuranceError::BelowMinThreshold { .. }  => 30,
+            InsuranceError::PayoutCapExceeded { .. }  => 31,
+            InsuranceError::VaultInsolvent(_)         => 32,
+            InsuranceError::InvalidArgument(_)        => 33,
+            InsuranceError::ArithmeticOverflow        => 34,
+            InsuranceError::HttpError(_)              => 40,
+            InsuranceError::ProviderTimeout           => 41,
+            InsuranceError::BadResponse(_)            => 42,
+            InsuranceError::DataIncident              => 43,
+            InsuranceError::CallBudgetExhausted       => 44,
+            InsuranceError::InvalidState(_)           => 50,
+            InsuranceError::Quote(error)              => return error.code(),
+            InsuranceError::Throttled(_)              => return CHECK_THROTTLED_CODE,
+            InsuranceError::RateLimited(_)            => return RATE_LIMIT_EXCEEDED_CODE,
+        };
+        INSURANCE_ERROR_BASE + index
+    }
+
+    pub fn message(&self) -> String {
+        match self {
+            InsuranceError::UnknownPolicy                => "Unknown policy.".into(),
+            InsuranceError::UnknownUnderwriter           => "Unknown underwriter.".into(),
+            InsuranceError::UnknownTemplate              => "Unknown template.".into(),
+            InsuranceError::UnknownSubscription          => "Unknown subscription.".into(),
+            InsuranceError::NotWhitelisted(role)         => format!("Account is not whitelisted as a {}.", role.name()),
+            InsuranceError::ContractPaused               => "Contract is paused.".into(),
+            InsuranceError::AlreadyPaidOut               => "Policy already paid out.".into(),
+            InsuranceError::PolicyNotActive              => "Policy is not active.".into(),
+            InsuranceError::PolicyExpired                => "Policy coverage has ended.".into(),
+            InsuranceError::PremiumNotCleared            => "Policy premium has not cleared.".into(),
+            InsuranceError::BelowMinThreshold { min }    => format!("Threshold is below the network minimum of {}.", format_mm(min.to_f64())),
+            InsuranceError::PayoutCapExceeded { max }    => format!("Payout exceeds the network maximum of {}.", format_ralo(*max)),
+            InsuranceError::ArithmeticOverflow           => "Amount is out of range for the ledger.".into(),
+            InsuranceError::HttpError(status)            => format!("Weather provider answered with HTTP {status}."),
+            InsuranceError::ProviderTimeout              => "Weather provider didn't answer in time.".into(),
+            InsuranceError::DataIncident                 => "Provider is under a data incident.".into(),
+            InsuranceError::CallBudgetExhausted          => "HTTP call budget exhausted.".into(),
+            InsuranceError::Quote(error)                 => error.message(),
+            InsuranceError::Throttled(error)             => error.message(),
+            InsuranceError::RateLimited(error)           => error.message(),
+            InsuranceError::UnknownRecord(detail)
+            | InsuranceError::Unauthorized(detail)
+            | InsuranceError::VaultInsolvent(detail)
+            | InsuranceError::InvalidArgument(detail)
+            | InsuranceError::BadResponse(detail)
+            | InsuranceError::InvalidState(detail)       => detail.clone(),
+        }
+    }
+}
+
+impl From<InsuranceError> for RialoError {
+    fn from(error: InsuranceError) -> Self {
+        RialoError::custom(error.code(), error.message())
+    }
+}
+
+impl From<QuoteError> for InsuranceError {
+    fn from(error: QuoteError) -> Self {
+        InsuranceError::Quote(error)
+    }
+}
+
+impl From<CheckThrottled> for InsuranceError {
+    fn from(error: CheckThrottled) -> Self {
+        InsuranceError::Throttled(error)
+    }
+}
+
+impl From<RateLimitExceeded> for InsuranceError {
+    fn from(error: RateLimitExceeded) -> Self {
+        InsuranceError::RateLimited(error)
+    }
+}
+
+impl From<FetchError> for InsuranceError {
+    fn from(error: FetchError) -> Self {
+        match error {
+            FetchError::Timeout           => InsuranceError::ProviderTimeout,
+            FetchError::RateLimited       => InsuranceError::HttpError(429),
+            FetchError::BadStatus(status) => InsuranceError::HttpError(status),
+            FetchError::ParseError        => InsuranceError::BadResponse("Weather provider's answer couldn't be read.".into()),
+        }
+    }
+}
+
+impl From<CoordinateError> for InsuranceError {
+    fn from(error: CoordinateError) -> Self {
+        InsuranceError::InvalidArgument(error.message().into())
+    }
+}
@@ -0,0 +1,406 @@
+// ============================================================
+//  Premium escrow
+//
+//  Premiums are paid into the contract vault but stay in escrow
+//  until the policy activates. This module owns the payment
+//  (direct or pulled from a pre-approved allowance), withdrawal,
+//  lapse and cancellation paths plus the custody statement views
+//  corporate customers use to reconcile funds-in-flight against
+//  their books.
+// ============================================================
+
+use rialo_sdk::prelude::*;
+use rialo_sdk::token::{deposit, transfer, transfer_from};
+use serde::Serialize;
+
+use crate::actuarial::cancellation_refund;
+use crate::bundles::BundledPeril;
+use crate::claims::{record_lae, LaeBreakdown, LaeKind};
+use crate::commissions::pay_commission;
+use crate::conditions::Condition;
+use crate::copay;
+use crate::curves::PayoutCurve;
+use crate::errors::InsuranceError;
+use crate::fx::UsdPayout;
+use crate::levies::{collect_levies, LevyLine};
+use crate::millimeters::Hundredths;
+use crate::mints::{self, MintAmount};
+use crate::money::{format_ralo, Ralo};
+use crate::normalization::{Comparison, Metric};
+use crate::policy::{Policy, PolicyId, PolicyStatus};
+use crate::routes::RouteTerms;
+use crate::underwriter::{TemplateId, UnderwriterId};
+use crate::{Hash, InsuranceState};
+
+// ── Entry point: pay (part of) a policy premium into escrow ──
+#[rialo::instruction]
+pub async fn pay_premium(
+    ctx:       Context<InsuranceState>,
+    policy_id: PolicyId,
+    amount:    Ralo,
+) -> RialoResult<()> {
+
+    let now = ctx.clock.unix_timestamp;
+
+    check_payment(&ctx.state, policy_id, amount)?;
+    deposit(&ctx.signer, &ctx.vault, amount.base_units())?;
+
+    credit_premium(&mut ctx.state, &ctx.vault, policy_id, *ctx.signer, amount, now)
+}
+
+// ── Entry point: pull the premium from the owner's allowance ─
+//
+//  For brokered policies: the customer pre-approves a token
+//  allowance to the contract, and anyone (typically the broker)
+//  can then collect the outstanding premium from the customer's
+//  own account.
+//
+#[rialo::instruction]
+pub async fn collect_premium(
+    ctx:       Context<InsuranceState>,
+    policy_id: PolicyId,
+) -> RialoResult<()> {
+
+    let now = ctx.clock.unix_timestamp;
+    collect_from_allowance(&mut ctx.state, &ctx.vault, policy_id, now)
+}
+
+// Pull whatever premium is outstanding from the owner's allowance
+pub(crate) fn collect_from_allowance(
+    state:     &mut InsuranceState,
+    vault:     &Vault,
+    policy_id: PolicyId,
+    now:       i64,
+) -> RialoResult<()> {
+
+    let policy = state.policies.get(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+    let owner  = policy.owner;
+    let amount = policy.premium_outstanding();
+
+    check_payment(state, policy_id, amount)?;
+    transfer_from(&owner, vault, amount.base_units())?;
+
+    credit_premium(state, vault, policy_id, owner, amount, now)
+}
+
+fn check_payment(state: &InsuranceState, policy_id: PolicyId, amount: Ralo) -> RialoResult<()> {
+    let policy = state.policies.get(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+
+    require!(policy.status == PolicyStatus::PendingPayment, InsuranceError::InvalidState("Policy is not awaiting payment.".into()));
+    require!(!amount.is_zero(), InsuranceError::InvalidArgument("Payment must be non-zero.".into()));
+    require!(
+        !policy.needs_insured_point() || policy.insured_point.is_some(),
+        InsuranceError::InvalidState("Set the insured point before paying for this cover.".into()),
+    );
+    require!(amount <= policy.premium_outstanding(), InsuranceError::InvalidState(format!("Payment exceeds outstanding premium of {}.", format_ralo(policy.premium_outstanding()))));
+    Ok(())
+}
+
+// Book a premium payment already in the vault; activates the policy once it clears
+fn credit_premium(
+    state:     &mut InsuranceState,
+    vault:     &Vault,
+    policy_id: PolicyId,
+    payer:     Pubkey,
+    amount:    Ralo,
+    now:       i64,
+) -> RialoResult<()> {
+
+    let policy = state.policies.get_mut(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+    let activated = policy.record_premium(amount, now);
+
+    emit!(PremiumPaid {
+        policy_id,
+        payer,
+        owner:       policy.owner,
+        amount,
+        outstanding: policy.premium_outstanding(),
+    });
+
+    if !activated {
+        return Ok(());
+    }
+
+    // Statutory levies come off the cleared premium first (see levies.rs),
+    // then the broker's commission (see commissions.rs)
+    let levied = collect_levies(state, vault, policy_id, now)?;
+    let commission = pay_commission(state, vault, policy_id)?;
+    let policy = state.policies.get(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+    let net_premium = policy.premium_paid
+        .checked_sub(levied)
+        .and_then(|rest| rest.checked_sub(commission))
+        .ok_or(InsuranceError::ArithmeticOverflow)?;
+
+    // The rest leaves escrow and becomes underwriter capital
+    if let Some(underwriter) = state.underwriters.get_mut(&policy.underwriter_id) {
+        underwriter.capital = underwriter.capital.checked_add(net_premium).ok_or(InsuranceError::ArithmeticOverflow)?;
+        underwriter.claims.premiums_earned += net_premium;
+        let product = underwriter.claims.template_mut(policy.template_id);
+        product.policies_sold   += 1;
+        product.premiums_earned += net_premium;
+    }
+
+    emit!(PolicyActivated {
+        policy_id,
+        owner:   policy.owner,
+        premium: policy.premium_paid,
+    });
+
+    // One self-contained payload is the certificate of insurance. A
+    // renewal paid early is certified for the window it will cover.
+    let coverage_start = policy.activated_at.unwrap_or(now);
+    emit!(CoverageCertificate {
+        policy_id,
+        underwriter_id: policy.underwriter_id,
+        template_id:    policy.template_id,
+        beneficiary:    policy.owner,
+        location:       policy.location.clone(),
+        peril:          policy.peril,
+        threshold:      policy.threshold,
+        payout:         policy.payout_amount,
+        curve:          policy.graded.as_ref().map(|g| g.payout_curve.clone()),
+        copay_bps:      policy.copay_bps,
+        net_payout:     copay::net(policy.payout_amount, policy.copay_bps),
+        perils:         policy.bundle.as_ref().map(|b| b.perils.clone()),
+        comparison:     policy.comparison,
+        route:          policy.route.as_ref().map(|r| r.terms()),
+        condition:      policy.compound.clone(),
+        payout_mint:    policy.payout_mint_terms(),
+        usd_payout:     policy.usd_payout,
+        premium:        policy.premium_amount,
+        coverage_start,
+        coverage_end:   coverage_start + policy.coverage_secs,
+        terms_hash:     policy.terms_hash(policy_id),
+    });
+
+    Ok(())
+}
+
+// ── Entry point: owner pulls escrow back before activation ───
+#[rialo::instruction]
+pub async fn withdraw_premium(
+    ctx:       Context<InsuranceState>,
+    policy_id: PolicyId,
+) -> RialoResult<()> {
+
+    let policy = ctx.state.policies.get_mut(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+
+    require!(policy.owner == *ctx.signer, InsuranceError::Unauthorized("Only the policy owner can withdraw premium.".into()));
+
+    let refund = policy.refundable_premium();
+    require!(!refund.is_zero(), InsuranceError::InvalidState("Nothing refundable on this policy.".into()));
+
+    transfer(&ctx.vault, &policy.owner, refund.base_units())?;
+    policy.premium_paid -= refund;
+    policy.status = PolicyStatus::Withdrawn;
+
+    // The withdrawn policy no longer needs capital set aside; a co-pay's share was released when it was set
+    if let Some(underwriter) = ctx.state.underwriters.get_mut(&policy.underwriter_id) {
+        underwriter.reserved = underwriter.reserved.saturating_sub(policy.coverage_remaining());
+    }
+
+    emit!(PremiumWithdrawn { policy_id, owner: policy.owner, amount: refund });
+
+    mints::release(&mut ctx.state, policy_id);
+
+    Ok(())
+}
+
+// ── Entry point: owner cancels live cover before any trigger ─
+//
+//  The premium for the time left in the window comes back out of
+//  the underwriter's capital, rounded in the customer's favour
+//  (actuarial.rs), and the reserve is released. Levies and any
+//  broker commission were paid on at activation, so the refund is
+//  of the premium net of them. A renewal paid early can be
+//  cancelled before its cover starts, for all of that net premium.
+//
+#[rialo::instruction]
+pub async fn cancel_policy(
+    ctx:       Context<InsuranceState>,
+    policy_id: PolicyId,
+) -> RialoResult<()> {
+
+    let now = ctx.clock.unix_timestamp;
+    let policy = ctx.state.policies.get_mut(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+
+    require!(policy.owner == *ctx.signer, InsuranceError::Unauthorized("Only the policy owner can cancel it.".into()));
+    let (refund, released) = cancellation(policy, now)?;
+
+    let underwriter = ctx.state.underwriters.get_mut(&policy.underwriter_id).ok_or(InsuranceError::UnknownUnderwriter)?;
+    underwriter.reserved = underwriter.reserved.saturating_sub(released);
+    require!(underwriter.free_capital() >= refund, InsuranceError::VaultInsolvent("Underwriter can't cover the refund yet.".into()));
+
+    transfer(&ctx.vault, &policy.owner, refund.base_units())?;
+
+    // Refunded premium was never earned
+    underwriter.capital -= refund;
+    underwriter.claims.premiums_earned = underwriter.claims.premiums_earned.saturating_sub(refund);
+    let product = underwriter.claims.template_mut(policy.template_id);
+    product.premiums_earned = product.premiums_earned.saturating_sub(refund);
+    policy.premium_paid -= refund;
+    policy.status = PolicyStatus::Cancelled;
+
+    emit!(PolicyCancelled { policy_id, owner: policy.owner, refund, released });
+
+    mints::release(&mut ctx.state, policy_id);
+
+    Ok(())
+}
+
+// What cancelling `policy` at `now` refunds to the owner and releases from the reserve
+pub fn cancellation(policy: &Policy, now: i64) -> RialoResult<(Ralo, Ralo)> {
+    require!(policy.status == PolicyStatus::Active, InsuranceError::InvalidState("Only live cover can be cancelled; withdraw the premium before activation.".into()));
+    require!(policy.paid_out.is_zero(), InsuranceError::InvalidState("Policy has already paid out.".into()));
+    let activated_at = policy.activated_at.ok_or_else(|| InsuranceError::InvalidState("Policy has no coverage window.".into()))?;
+    require!(policy.coverage_end().is_some_and(|end| now < end), InsuranceError::InvalidState("Coverage has ended; finalize the policy instead.".into()));
+
+    let levied: Ralo = policy.levies.iter().map(|l| l.amount).sum();
+    let withheld = levied + policy.brokerage.unwrap_or(Ralo::ZERO);
+    let net_premium = policy.premium_paid.saturating_sub(withheld);
+
+    // Cover that hasn't started yet has earned nothing
+    let elapsed = (now - activated_at).max(0);
+    let refund = cancellation_refund(net_premium, net_premium, elapsed, policy.coverage_secs);
+
+    Ok((refund, policy.coverage_remaining()))
+}
+
+// ── Entry point: anyone voids a policy left unpaid too long ─
+//
+//  Permissionless so the book stays clean without an operator cron:
+//  the caller earns the configured lapse reward, paid from the
+//  underwriter's capital and booked as a keeper fee.
+//
+#[rialo::instruction]
+pub async fn void_lapsed(
+    ctx:       Context<InsuranceState>,
+    policy_id: PolicyId,
+) -> RialoResult<()> {
+
+    let now    = ctx.clock.unix_timestamp;
+    let grace  = ctx.state.config.payment_grace_secs;
+    let reward = ctx.state.config.lapse_reward;
+
+    let policy = ctx.state.policies.get_mut(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+
+    require!(policy.is_lapsed(now, grace), InsuranceError::InvalidState("Policy is still within its payment grace period.".into()));
+
+    // Any partial payment goes back to the owner
+    let refund = policy.escrowed_premium();
+    if !refund.is_zero() {
+        transfer(&ctx.vault, &policy.owner, refund.base_units())?;
+        policy.premium_paid -= refund;
+    }
+    policy.status = PolicyStatus::Lapsed;
+
+    let underwriter = ctx.state.underwriters.get_mut(&policy.underwriter_id).ok_or(InsuranceError::UnknownUnderwriter)?;
+    underwriter.reserved = underwriter.reserved.saturating_sub(policy.coverage_remaining());
+
+    // Never dip into capital backing other policies
+    let reward = reward.min(underwriter.free_capital());
+    if !reward.is_zero() {
+        transfer(&ctx.vault, &ctx.signer, reward.base_units())?;
+        underwriter.capital -= reward;
+    }
+
+    emit!(PolicyLapsed { policy_id, owner: policy.owner, keeper: *ctx.signer, refunded: refund, reward });
+
+    record_lae(&mut ctx.state, policy_id, LaeKind::CheckFee, reward);
+    mints::release(&mut ctx.state, policy_id);
+
+    Ok(())
+}
+
+// ── Custody statement views ──────────────────────────────────
+#[derive(Serialize, Clone, Debug)]
+pub struct EscrowLine {
+    pub policy_id:          PolicyId,
+    pub status:             PolicyStatus,
+    pub pending_activation: Ralo,
+    pub refundable:         Ralo,
+    pub locked:             Ralo,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct EscrowStatement {
+    pub owner:              Pubkey,
+    pub pending_activation: Ralo,  // premium received for policies not yet active
+    pub refundable:         Ralo,  // premium the owner could reclaim right now
+    pub locked:             Ralo,  // premium committed to live or settled coverage
+    pub lines:              Vec<EscrowLine>,
+}
+
+#[rialo::view]
+pub fn get_escrow_statement(
+    ctx:   Context<InsuranceState>,
+    owner: Pubkey,
+) -> RialoResult<EscrowStatement> {
+
+    let lines: Vec<EscrowLine> = ctx.state.policies
+        .iter()
+        .filter(|(_, p)| p.owner == owner)
+        .map(|(id, p)| EscrowLine {
+            policy_id:          *id,
+            status:             p.status,
+            pending_activation: p.escrowed_premium(),
+            refundable:         p.refundable_premium(),
+            locked:             p.locked_premium(),
+        })
+        .collect();
+
+    Ok(EscrowStatement {
+        owner,
+        pending_activation: lines.iter().map(|l| l.pending_activation).sum(),
+        refundable:         lines.iter().map(|l| l.refundable).sum(),
+        locked:             lines.iter().map(|l| l.locked).sum(),
+        lines,
+    })
+}
+
+// ── Per-policy financials ────────────────────────────────────
+#[derive(Serialize, Clone, Debug)]
+pub struct PolicyFinancials {
+    pub policy_id:    PolicyId,
+    pub status:       PolicyStatus,
+    pub premium:      Ralo,            // quoted premium
+    pub seasonal_bps: u64,             // seasonal factor the premium was quoted at
+    pub premium_paid: Ralo,            // received so far
+    pub levies:       Vec<LevyLine>,   // withheld at activation and paid to levy accounts
+    pub commission:   Ralo,            // withheld at activation and paid to the broker
+    pub net_premium:  Ralo,            // what the underwriter kept
+    pub payout:       Ralo,
+    pub lae:          LaeBreakdown,
+}
+
+#[rialo::view]
+pub fn get_policy_financials(
+    ctx:       Context<InsuranceState>,
+    policy_id: PolicyId,
+) -> RialoResult<PolicyFinancials> {
+
+    let policy = ctx.state.policies.get(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+    let levied: Ralo = policy.levies.iter().map(|l| l.amount).sum();
+    let commission = policy.brokerage.unwrap_or(Ralo::ZERO);
+
+    Ok(PolicyFinancials {
+        policy_id,
+        status:       policy.status,
+        premium:      policy.premium_amount,
+        seasonal_bps: policy.seasonal_bps,
+        premium_paid: policy.premium_paid,
+        levies:       policy.levies.clone(),
+        commission,
+        net_premium:  policy.locked_premium().saturating_sub(levied + commission),
+        payout:       policy.payout_amount,
+        lae:          policy.lae,
+    })
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct PremiumPaid         { pub policy_id: PolicyId, pub payer: Pubkey, pub owner: Pubkey, pub amount: Ralo, pub outstanding: Ralo }
+#[rialo::event] pub struct PolicyActivated     { pub policy_id: PolicyId, pub owner: Pubkey, pub premium: Ralo }
+#[rialo::event] pub struct CoverageCertificate { pub policy_id: PolicyId, pub underwriter_id: UnderwriterId, pub template_id: TemplateId, pub beneficiary: Pubkey, pub location: String, pub peril: Metric, pub threshold: Hundredths, pub payout: Ralo, pub curve: Option<PayoutCurve>, pub copay_bps: u64, pub net_payout: Ralo, pub perils: Option<Vec<BundledPeril>>, pub comparison: Comparison, pub route: Option<RouteTerms>, pub condition: Option<Condition>, pub payout_mint: Option<MintAmount>, pub usd_payout: Option<UsdPayout>, pub premium: Ralo, pub coverage_start: i64, pub coverage_end: i64, pub terms_hash: Hash }
+#[rialo::event] pub struct PremiumWithdrawn    { pub policy_id: PolicyId, pub owner: Pubkey, pub amount: Ralo }
+#[rialo::event] pub struct PolicyLapsed        { pub policy_id: PolicyId, pub owner: Pubkey, pub keeper: Pubkey, pub refunded: Ralo, pub reward: Ralo }
+#[rialo::event] pub struct PolicyCancelled     { pub policy_id: PolicyId, pub owner: Pubkey, pub refund: Ralo, pub released: Ralo }
@@ -0,0 +1,85 @@
+// ============================================================
+//  Reading evaluation
+//
+//  What happens to a provider reading once it's been fetched and
+//  isn't parked behind an incident. Stages run in order, and any
+//  of them can stop the reading short of a payout:
+//    1. smooth   — a smoothing template's policies file the reading
+//                  and go on with the rolling statistic; nothing
+//                  settles until the window fills (smoothing.rs)
+//    2. confirm  — an exceedance needs the forecast behind it on a
+//                  forecast-confirmed policy (forecasts.rs)
+//    3. settle   — the threshold comparison and the payout
+//                  (settlement.rs)
+//  Live and historical checks share it, as do held readings that
+//  an incident has released.
+// ============================================================
+
+use rialo_sdk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::millimeters::Hundredths;
+use crate::money::Ralo;
+use crate::observations::CheckId;
+use crate::policy::PolicyId;
+use crate::{forecasts, settlement, InsuranceState};
+
+// How a reading came out — the decision its check is logged with
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Evaluation {
+    Triggered { paid: Ralo },   // settled; nothing paid if a USD payout was deferred
+    NotMet,                     // compared with the threshold, and fell short
+    Unconfirmed,                // met the threshold without the forecast to back it
+    WarmingUp,                  // smoothing had no value for it yet, or the reading added nothing
+}
+
+impl Evaluation {
+    pub fn triggered(self) -> bool {
+        matches!(self, Evaluation::Triggered { .. })
+    }
+
+    pub fn paid(self) -> Ralo {
+        match self {
+            Evaluation::Triggered { paid } => paid,
+            _                              => Ralo::ZERO,
+        }
+    }
+}
+
+// Run a reading through each stage; `round_id` books any payment
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn evaluate(
+    state:       &mut InsuranceState,
+    vault:       &Vault,
+    policy_id:   PolicyId,
+    round_id:    CheckId,
+    reading:     Hundredths,
+    observed_at: i64,
+    now:         i64,
+) -> RialoResult<Evaluation> {
+
+    let smoothing = state.policies.get_mut(&policy_id).and_then(|p| p.smoothing.as_mut());
+    let reading = match smoothing {
+        None => reading,
+        // The statistic is computed in f64 and settles on its nearest hundredth
+        Some(index) => match index.record(reading.to_f64(), observed_at) {
+            Some(smoothed) => Hundredths::from_f64(smoothed),
+            None => return Ok(Evaluation::WarmingUp),
+        },
+    };
+
+    if forecasts::unconfirmed(state, policy_id, reading, observed_at) {
+        return Ok(Evaluation::Unconfirmed);
+    }
+
+    let paid_before = paid_out(state, policy_id);
+    if settlement::settle(state, vault, policy_id, Some(round_id), reading, observed_at, now)? {
+        Ok(Evaluation::Triggered { paid: paid_out(state, policy_id).saturating_sub(paid_before) })
+    } else {
+        Ok(Evaluation::NotMet)
+    }
+}
+
+fn paid_out(state: &InsuranceState, policy_id: PolicyId) -> Ralo {
+    state.policies.get(&policy_id).map_or(Ralo::ZERO, |p| p.paid_out)
+}
@@ -0,0 +1,222 @@
+// ============================================================
+//  Custom trigger evaluators
+//
+//  Escape hatch for bespoke enterprise deals — structured payout
+//  curves, blended indices — that the built-in triggers can't
+//  express. A template can hand its trigger to a registered
+//  `CustomEvaluator`: each check reads the tenant's provider as
+//  usual, posts the normalized observation to the evaluator, and
+//  gets back the share of the payout now due. Shares top up like
+//  storm tiers and reaching 100% settles the policy.
+//
+//  The SDK has no calls into other programs, so an evaluator is
+//  an evaluation service reached over HTTPS and pinned to the
+//  hash of the code the admin reviewed: every answer must echo
+//  that hash, or the check is refused. Tenants propose
+//  evaluators; none can back a template until the admin approves
+//  it, and revoking one halts settlement of its policies.
+// ============================================================
+
+use rialo_sdk::http::{HttpRequest, Method};
+use rialo_sdk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::claims::{record_lae, LaeKind};
+use crate::config::require_unpaused;
+use crate::errors::InsuranceError;
+use crate::incidents::under_incident;
+use crate::keys::{lease_key, report_key};
+use crate::millimeters::Hundredths;
+use crate::normalization::{Comparison, Metric, Observation};
+use crate::policy::{PolicyId, PolicyStatus};
+use crate::providers::{ReadingTime, WeatherProvider};
+use crate::settlement::{self, FULL_SHARE_BPS};
+use crate::underwriter::{tenant_mut, TemplateId, UnderwriterId};
+use crate::{fetch, provider_headers, Hash, InsuranceState};
+
+pub type EvaluatorId = u64;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CustomEvaluator {
+    pub underwriter_id: UnderwriterId,   // tenant that proposed it; only its templates can use it
+    pub url:            String,          // evaluation endpoint
+    pub code_hash:      Hash,            // build the admin reviewed; echoed in every answer
+    pub approved:       bool,            // set by the admin
+}
+
+// Evaluator state carried by a policy sold under an evaluated template
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EvaluatorCover {
+    pub evaluator_id: EvaluatorId,
+    pub paid_bps:     u64,           // highest share paid so far
+}
+
+impl EvaluatorCover {
+    pub fn new(evaluator_id: EvaluatorId) -> Self {
+        EvaluatorCover { evaluator_id, paid_bps: 0 }
+    }
+}
+
+// What the evaluator is asked to price
+#[derive(Serialize, Debug)]
+pub struct EvaluationRequest {
+    pub policy_id:   PolicyId,
+    pub peril:       Metric,
+    pub threshold:   f64,
+    pub observation: Observation,
+    pub observed_at: i64,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct EvaluationResponse {
+    pub code_hash:  Hash,
+    pub payout_bps: u64,   // share of the payout due in total, 10 000 = all of it
+}
+
+// Check an answer against the reviewed build; the share it reports
+pub fn accept_evaluation(evaluator: &CustomEvaluator, body: &[u8]) -> RialoResult<u64> {
+    let response: EvaluationResponse = serde_json::from_slice(body)
+        .map_err(|_| InsuranceError::BadResponse("Malformed evaluator response.".into()))?;
+    require!(response.code_hash == evaluator.code_hash, InsuranceError::InvalidState("Evaluator is not running the approved code.".into()));
+    require!(response.payout_bps <= FULL_SHARE_BPS, InsuranceError::InvalidState("Evaluator returned a share above 100%.".into()));
+    Ok(response.payout_bps)
+}
+
+// ── Entry point: tenant proposes an evaluator for review ─────
+#[rialo::instruction]
+pub async fn propose_evaluator(
+    ctx:            Context<InsuranceState>,
+    underwriter_id: UnderwriterId,
+    url:            String,
+    code_hash:      Hash,
+) -> RialoResult<EvaluatorId> {
+
+    require!(url.starts_with("https://"), InsuranceError::InvalidArgument("Evaluator endpoint must use HTTPS.".into()));
+    tenant_mut(&mut ctx.state, underwriter_id, &ctx.signer)?;
+
+    let state = &mut ctx.state;
+    let evaluator_id = state.next_evaluator_id;
+    state.next_evaluator_id += 1;
+
+    state.evaluators.insert(evaluator_id, CustomEvaluator { underwriter_id, url: url.clone(), code_hash, approved: false });
+
+    emit!(EvaluatorProposed { evaluator_id, underwriter_id, url, code_hash });
+
+    Ok(evaluator_id)
+}
+
+// ── Entry point: admin approves (or revokes) an evaluator ────
+#[rialo::instruction]
+pub async fn approve_evaluator(
+    ctx:          Context<InsuranceState>,
+    evaluator_id: EvaluatorId,
+    approved:     bool,
+) -> RialoResult<()> {
+
+    require!(ctx.state.config.admin == *ctx.signer, InsuranceError::Unauthorized("Only the admin can approve evaluators.".into()));
+    let evaluator = ctx.state.evaluators.get_mut(&evaluator_id).ok_or_else(|| InsuranceError::UnknownRecord("Unknown evaluator.".into()))?;
+    evaluator.approved = approved;
+
+    emit!(EvaluatorApprovalSet { evaluator_id, approved });
+
+    Ok(())
+}
+
+// ── Entry point: hand (or take back) a template's trigger ────
+#[rialo::instruction]
+pub async fn set_template_evaluator(
+    ctx:            Context<InsuranceState>,
+    underwriter_id: UnderwriterId,
+    template_id:    TemplateId,
+    evaluator_id:   Option<EvaluatorId>,
+) -> RialoResult<()> {
+
+    if let Some(id) = evaluator_id {
+        let evaluator = ctx.state.evaluators.get(&id).ok_or_else(|| InsuranceError::UnknownRecord("Unknown evaluator.".into()))?;
+        require!(evaluator.underwriter_id == underwriter_id, InsuranceError::InvalidState("Evaluator belongs to another underwriter.".into()));
+        require!(evaluator.approved, InsuranceError::InvalidState("Evaluator has not been approved.".into()));
+    }
+
+    let underwriter = tenant_mut(&mut ctx.state, underwriter_id, &ctx.signer)?;
+    let template = underwriter.templates.get_mut(&template_id).ok_or(InsuranceError::UnknownTemplate)?;
+    // The evaluator is the whole trigger; it reads the same observation a rainfall check would
+    if evaluator_id.is_some() {
+        let plain_rain = template.peril() == Metric::Rainfall && template.comparison == Comparison::AtOrAbove && template.continuous_hours.is_none()
+            && template.rolling_hours.is_none() && !template.rain_normal;
+        require!(plain_rain, InsuranceError::InvalidState("Template already has its own trigger.".into()));
+    }
+    template.evaluator = evaluator_id;
+
+    emit!(TemplateEvaluatorSet { underwriter_id, template_id, evaluator_id });
+
+    Ok(())
+}
+
+// ── Entry point: anyone has a policy's evaluator price this hour
+#[rialo::instruction]
+pub async fn check_evaluator_and_pay(
+    ctx:       Context<InsuranceState>,
+    policy_id: PolicyId,
+) -> RialoResult<()> {
+
+    require_unpaused(&ctx.state.config)?;
+    let now = ctx.clock.unix_timestamp;
+    let policy = ctx.state.policies.get(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+
+    require!(policy.status == PolicyStatus::Active, InsuranceError::PolicyNotActive);
+    require!(policy.is_covered_at(now), InsuranceError::PolicyExpired);
+    let cover = policy.evaluator.ok_or_else(|| InsuranceError::InvalidState("Policy is not evaluated by a custom evaluator.".into()))?;
+
+    let evaluator = ctx.state.evaluators.get(&cover.evaluator_id).ok_or_else(|| InsuranceError::UnknownRecord("Unknown evaluator.".into()))?.clone();
+    require!(evaluator.approved, InsuranceError::InvalidState("Evaluator approval has been revoked.".into()));
+
+    let underwriter_id = policy.underwriter_id;
+    let underwriter = ctx.state.underwriters.get(&underwriter_id).ok_or(InsuranceError::UnknownUnderwriter)?;
+    let source = underwriter.provider.base_url_for(ctx.state.config.network_mode).to_string();
+    require!(!under_incident(&ctx.state, &source, now), InsuranceError::DataIncident);
+
+    let lease = lease_key(&mut ctx.state, underwriter_id, now)?;
+    let policy = ctx.state.policies.get(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+    let underwriter = ctx.state.underwriters.get(&underwriter_id).ok_or(InsuranceError::UnknownUnderwriter)?;
+    let provider = &underwriter.provider;
+    let url = provider.kind.build_request(&source, &lease.key, &policy.place, ReadingTime::Current);
+    let headers = provider_headers(underwriter, policy.template_id)?;
+    let call_cost = provider.cost_per_call;
+    let kind = provider.kind;
+    let (peril, threshold) = (policy.peril, policy.threshold.to_f64());
+
+    let response = fetch(&url, &headers).await?;
+    if !report_key(&mut ctx.state, underwriter_id, &lease, response.status(), now) {
+        return Ok(());
+    }
+    let observation = kind.parse_observation(ReadingTime::Current, response.body())?;
+
+    record_lae(&mut ctx.state, policy_id, LaeKind::ProviderCost, call_cost);
+
+    let request = EvaluationRequest { policy_id, peril, threshold, observation, observed_at: now };
+    let answer = HttpRequest::new(Method::POST, &evaluator.url)
+        .header("Content-Type", "application/json")
+        .body(serde_json::to_vec(&request).unwrap_or_default())
+        .send()
+        .await?;
+    require!((200..300).contains(&answer.status()), InsuranceError::InvalidState("Evaluator rejected the request.".into()));
+    let payout_bps = accept_evaluation(&evaluator, answer.body())?;
+
+    emit!(EvaluatorChecked { policy_id, evaluator_id: cover.evaluator_id, payout_bps, paid_bps: cover.paid_bps });
+
+    // Shares only ratchet up; the receipt commits to the share that was paid
+    if payout_bps > cover.paid_bps {
+        if let Some(cover) = ctx.state.policies.get_mut(&policy_id).and_then(|p| p.evaluator.as_mut()) {
+            cover.paid_bps = payout_bps;
+        }
+        settlement::pay_share(&mut ctx.state, &ctx.vault, policy_id, None, payout_bps, Hundredths::from_f64(payout_bps as f64), now, now)?;
+    }
+
+    Ok(())
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct EvaluatorProposed    { pub evaluator_id: EvaluatorId, pub underwriter_id: UnderwriterId, pub url: String, pub code_hash: Hash }
+#[rialo::event] pub struct EvaluatorApprovalSet { pub evaluator_id: EvaluatorId, pub approved: bool }
+#[rialo::event] pub struct TemplateEvaluatorSet { pub underwriter_id: UnderwriterId, pub template_id: TemplateId, pub evaluator_id: Option<EvaluatorId> }
+#[rialo::event] pub struct EvaluatorChecked     { pub policy_id: PolicyId, pub evaluator_id: EvaluatorId, pub payout_bps: u64, pub paid_bps: u64 }
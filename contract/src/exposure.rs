@@ -0,0 +1,231 @@
+// ============================================================
+//  Heat-hours cover
+//
+//  For agricultural labour cooperatives, whose crews stop work
+//  in extreme heat or sun. Each check takes an hourly snapshot
+//  from OpenWeatherMap's One Call endpoint at the insured point:
+//    • current air temperature and the day's forecast maximum
+//    • current UV index
+//  A product is written on one index, with the policy threshold
+//  in °C or UV units. Every snapshot hour at or above the
+//  threshold adds an exposure hour — hours need not be
+//  consecutive — and the payout goes out once the policy has
+//  accumulated the product's number of hours. Only the first
+//  snapshot in an hour counts.
+// ============================================================
+
+use rialo_sdk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::claims::{record_lae, LaeKind};
+use crate::config::require_unpaused;
+use crate::errors::InsuranceError;
+use crate::geo::GeoPoint;
+use crate::incidents::under_incident;
+use crate::keys::{lease_key, report_key};
+use crate::millimeters::Hundredths;
+use crate::normalization::Metric;
+use crate::oracle::{require_metric, PINNED_PARAMS};
+use crate::policy::{PolicyId, PolicyStatus};
+use crate::underwriter::{tenant_mut, TemplateId, UnderwriterId};
+use crate::{fetch, settlement, InsuranceState};
+
+const SECS_PER_HOUR:      i64 = 60 * 60;
+const MAX_EXPOSURE_HOURS: u32 = 24 * 31;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExposureIndex {
+    AirTemperature,   // °C
+    UvIndex,          // WHO UV index
+}
+
+impl ExposureIndex {
+    pub fn peril(self) -> Metric {
+        match self {
+            ExposureIndex::AirTemperature => Metric::HeatHours,
+            ExposureIndex::UvIndex        => Metric::UvHours,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ExposureTrigger {
+    pub index: ExposureIndex,
+    pub hours: u32,             // exposure hours needed to pay
+}
+
+// Exposure state carried by a policy sold under a heat-hours template
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ExposureCover {
+    pub trigger:   ExposureTrigger,
+    pub hours:     u32,           // snapshot hours at or above the threshold so far
+    pub last_hour: Option<i64>,   // hour of the last counted snapshot
+}
+
+impl ExposureCover {
+    pub fn new(trigger: ExposureTrigger) -> Self {
+        ExposureCover { trigger, hours: 0, last_hour: None }
+    }
+
+    // Whether a snapshot at `now` falls in an hour not yet counted
+    pub fn can_snapshot_at(&self, now: i64) -> bool {
+        self.last_hour.is_none_or(|last| now.div_euclid(SECS_PER_HOUR) > last)
+    }
+
+    // Fold one hourly snapshot in; returns true once enough hours have accumulated
+    pub fn record(&mut self, snapshot: &ExposureSnapshot, threshold: f64, now: i64) -> bool {
+        self.last_hour = Some(now.div_euclid(SECS_PER_HOUR));
+        if snapshot.value(self.trigger.index) >= threshold {
+            self.hours += 1;
+        }
+        self.hours >= self.trigger.hours
+    }
+}
+
+// One snapshot from the One Call feed
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct ExposureSnapshot {
+    pub temperature_c: f64,
+    pub daily_max_c:   f64,   // today's forecast maximum
+    pub uv_index:      f64,
+}
+
+impl ExposureSnapshot {
+    pub fn value(&self, index: ExposureIndex) -> f64 {
+        match index {
+            ExposureIndex::AirTemperature => self.temperature_c,
+            ExposureIndex::UvIndex        => self.uv_index,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct OneCallResponse {
+    current: OneCallCurrent,
+    daily:   Vec<OneCallDaily>,
+}
+
+#[derive(Deserialize)]
+struct OneCallCurrent {
+    temp: f64,   // °C with units=metric
+    uvi:  f64,
+}
+
+#[derive(Deserialize)]
+struct OneCallDaily {
+    temp: OneCallDailyTemp,
+}
+
+#[derive(Deserialize)]
+struct OneCallDailyTemp {
+    max: f64,
+}
+
+// Current conditions and today's forecast at a point, metric units
+pub fn one_call_url(base_url: &str, point: &GeoPoint, api_key: &str) -> String {
+    format!(
+        "{}/data/3.0/onecall?lat={}&lon={}&exclude=minutely,hourly,alerts&appid={}&{}",
+        base_url,
+        point.lat_e6 as f64 / 1e6,
+        point.lon_e6 as f64 / 1e6,
+        api_key,
+        PINNED_PARAMS,
+    )
+}
+
+pub fn parse_exposure_snapshot(body: &[u8]) -> RialoResult<ExposureSnapshot> {
+    let response: OneCallResponse = serde_json::from_slice(body)
+        .map_err(|_| InsuranceError::BadResponse("Malformed One Call response.".into()))?;
+    let today = response.daily.first().ok_or_else(|| InsuranceError::BadResponse("One Call response has no daily forecast.".into()))?;
+
+    require!(response.current.uvi >= 0.0, InsuranceError::InvalidState("UV index reading is negative.".into()));
+    require_metric(Some(response.current.temp.max(today.temp.max)))?;
+    Ok(ExposureSnapshot {
+        temperature_c: response.current.temp,
+        daily_max_c:   today.temp.max,
+        uv_index:      response.current.uvi,
+    })
+}
+
+// ── Entry point: make (or unmake) a template a heat-hours product
+#[rialo::instruction]
+pub async fn set_template_exposure(
+    ctx:            Context<InsuranceState>,
+    underwriter_id: UnderwriterId,
+    template_id:    TemplateId,
+    trigger:        Option<ExposureTrigger>,
+) -> RialoResult<()> {
+
+    if let Some(trigger) = &trigger {
+        require!((1..=MAX_EXPOSURE_HOURS).contains(&trigger.hours), InsuranceError::InvalidArgument("Heat-hours products need between 1 and 744 hours.".into()));
+    }
+
+    let underwriter = tenant_mut(&mut ctx.state, underwriter_id, &ctx.signer)?;
+    let template = underwriter.templates.get_mut(&template_id).ok_or(InsuranceError::UnknownTemplate)?;
+    template.exposure = trigger;
+
+    emit!(TemplateExposureSet { underwriter_id, template_id, trigger });
+
+    Ok(())
+}
+
+// ── Entry point: anyone takes this hour's snapshot for a policy
+#[rialo::instruction]
+pub async fn check_exposure_and_pay(
+    ctx:       Context<InsuranceState>,
+    policy_id: PolicyId,
+) -> RialoResult<()> {
+
+    require_unpaused(&ctx.state.config)?;
+    let now = ctx.clock.unix_timestamp;
+    let policy = ctx.state.policies.get(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+
+    require!(policy.status == PolicyStatus::Active, InsuranceError::PolicyNotActive);
+    require!(policy.is_covered_at(now), InsuranceError::PolicyExpired);
+    let cover = policy.exposure.ok_or_else(|| InsuranceError::InvalidState("Policy is not heat-hours cover.".into()))?;
+    let point = policy.insured_point.ok_or_else(|| InsuranceError::InvalidState("Policy has no insured point.".into()))?;
+    require!(cover.can_snapshot_at(now), InsuranceError::InvalidState("This hour has already been counted.".into()));
+
+    let underwriter_id = policy.underwriter_id;
+    let underwriter = ctx.state.underwriters.get(&underwriter_id).ok_or(InsuranceError::UnknownUnderwriter)?;
+    let source = underwriter.provider.base_url_for(ctx.state.config.network_mode).to_string();
+    let call_cost = underwriter.provider.cost_per_call;
+    require!(!under_incident(&ctx.state, &source, now), InsuranceError::DataIncident);
+
+    let lease = lease_key(&mut ctx.state, underwriter_id, now)?;
+    let url = one_call_url(&source, &point, &lease.key);
+
+    let response = fetch(&url, &[]).await?;
+    if !report_key(&mut ctx.state, underwriter_id, &lease, response.status(), now) {
+        return Ok(());
+    }
+    let snapshot = parse_exposure_snapshot(response.body())?;
+
+    record_lae(&mut ctx.state, policy_id, LaeKind::ProviderCost, call_cost);
+
+    let policy = ctx.state.policies.get_mut(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+    let threshold = policy.threshold.to_f64();
+    let cover = policy.exposure.as_mut().ok_or_else(|| InsuranceError::InvalidState("Policy is not heat-hours cover.".into()))?;
+    let triggered = cover.record(&snapshot, threshold, now);
+    let (hours, required, index) = (cover.hours, cover.trigger.hours, cover.trigger.index);
+
+    emit!(ExposureChecked {
+        policy_id,
+        temperature_c: snapshot.temperature_c,
+        daily_max_c:   snapshot.daily_max_c,
+        uv_index:      snapshot.uv_index,
+        hours,
+        required,
+    });
+
+    if triggered {
+        policy.status = PolicyStatus::PaidOut;
+        settlement::pay_out(&mut ctx.state, &ctx.vault, policy_id, None, Hundredths::from_f64(snapshot.value(index)), now, now)?;
+    }
+
+    Ok(())
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct TemplateExposureSet { pub underwriter_id: UnderwriterId, pub template_id: TemplateId, pub trigger: Option<ExposureTrigger> }
+#[rialo::event] pub struct ExposureChecked     { pub policy_id: PolicyId, pub temperature_c: f64, pub daily_max_c: f64, pub uv_index: f64, pub hours: u32, pub required: u32 }
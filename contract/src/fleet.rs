@@ -0,0 +1,78 @@
+// ============================================================
+//  Fleet cover
+//
+//  A logistics company with fifty depots shouldn't need fifty
+//  transactions. setup_policies_batch writes one policy per set of
+//  terms, for the signer, each validated, quoted and reserved
+//  exactly as setup_policy would, in order — so every item's
+//  capital check already sees what the items before it reserved.
+//  On a failing item the batch either:
+//    • Atomic  — fails as a whole, and nothing is written
+//    • Partial — skips it and writes the rest; the event lists the
+//                indexes that were skipped, each with its error code
+//  Once written, the batch must also leave the vault holding at
+//  least every payout reserved against it, or none of it stands.
+// ============================================================
+
+use rialo_sdk::prelude::*;
+use rialo_sdk::token::balance;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::InsuranceError;
+use crate::money::Ralo;
+use crate::policy::{PolicyId, PolicyTerms};
+use crate::{config, write_policy, InsuranceState};
+
+pub const MAX_BATCH_POLICIES: usize = 64;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BatchMode {
+    Atomic,    // one bad item fails the batch
+    Partial,   // bad items are skipped
+}
+
+// An item a partial batch skipped, and the code of why
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BatchFailure {
+    pub index: u32,
+    pub code:  u32,
+}
+
+// ── Entry point: customer sets up a fleet's policies at once ─
+#[rialo::instruction]
+pub async fn setup_policies_batch(
+    ctx:             Context<InsuranceState>,
+    items:           Vec<PolicyTerms>,
+    mode:            BatchMode,
+    allow_duplicate: bool,   // as setup_policy; items on the same location also count as duplicates
+) -> RialoResult<Vec<PolicyId>> {
+
+    let now = ctx.clock.unix_timestamp;
+    config::require_unpaused(&ctx.state.config)?;
+
+    require!(!items.is_empty(), InsuranceError::InvalidArgument("Batch has no policies.".into()));
+    require!(items.len() <= MAX_BATCH_POLICIES, InsuranceError::InvalidArgument(format!("A batch holds at most {MAX_BATCH_POLICIES} policies.")));
+
+    let owner = *ctx.signer;
+    let mut policy_ids = Vec::new();
+    let mut failed = Vec::new();
+    for (index, terms) in items.into_iter().enumerate() {
+        match write_policy(&mut ctx.state, owner, None, terms, allow_duplicate, now).await {
+            Ok(policy_id) => policy_ids.push(policy_id),
+            Err(error) if mode == BatchMode::Atomic => return Err(error),
+            Err(error) => failed.push(BatchFailure { index: index as u32, code: error.code }),
+        }
+    }
+    require!(!policy_ids.is_empty(), InsuranceError::InvalidState("No policy in the batch could be written.".into()));
+
+    // Aggregate solvency: the vault has to back everything now reserved
+    let reserved: Ralo = ctx.state.underwriters.values().map(|u| u.reserved).sum();
+    require!(Ralo(balance(&ctx.vault)) >= reserved, InsuranceError::VaultInsolvent("Vault can't back the batch's payouts on top of existing cover.".into()));
+
+    emit!(BatchPoliciesCreated { owner, policy_ids: policy_ids.clone(), failed });
+
+    Ok(policy_ids)
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct BatchPoliciesCreated { pub owner: Pubkey, pub policy_ids: Vec<PolicyId>, pub failed: Vec<BatchFailure> }
@@ -0,0 +1,197 @@
+// ============================================================
+//  Forecast confirmation
+//
+//  One station reporting a cloudburst nobody saw coming is more
+//  often a blocked gauge or a bad sample than a flood. A template
+//  can ask for the weather to have been forecast too: its policies
+//  pay on an observed exceedance only if the provider's forecast,
+//  taken before the hour in question, gave at least the template's
+//  chance of rain for it.
+//
+//    record_forecast → the provider's upcoming slots (3-hourly on
+//                      OpenWeatherMap, hourly on WeatherAPI.com)
+//                      and their chance of rain, on the policy
+//    exceedance      → pays if the slot it fell in was forecast at
+//                      or above the template's chance; otherwise
+//                      `TriggerUnconfirmed` and the cover stays live
+//
+//  Only slots that hadn't started when the forecast was taken are
+//  kept, so a forecast recorded mid-storm can't confirm that storm.
+//  A newer forecast replaces what an older one said about slots
+//  still to come. Anyone can record one, as often as they like to
+//  pay the provider call; a policy nobody recorded a forecast for
+//  can't trigger.
+//
+//  Offered on plain rainfall products paying above the threshold.
+//  Arbiter overrides settle without a forecast, and bundle riders
+//  (other perils) aren't confirmed.
+// ============================================================
+
+use rialo_sdk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::claims::{record_lae, LaeKind};
+use crate::errors::InsuranceError;
+use crate::keys::{lease_key, report_key};
+use crate::millimeters::Hundredths;
+use crate::normalization::{Comparison, Metric};
+use crate::policy::{PolicyId, PolicyStatus};
+use crate::providers::WeatherProvider;
+use crate::underwriter::{tenant_mut, TemplateId, UnderwriterId};
+use crate::{fetch, provider_headers, InsuranceState};
+
+pub const FULL_CHANCE_BPS:    u64   = 10_000;
+pub const MAX_FORECAST_SLOTS: usize = 256;
+
+// One forecast period and the chance of rain the provider gave it
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct ForecastSlot {
+    pub start:           i64,
+    pub secs:            i64,
+    pub rain_chance_bps: u64,
+}
+
+impl ForecastSlot {
+    pub fn contains(&self, at: i64) -> bool {
+        (self.start..self.start + self.secs).contains(&at)
+    }
+}
+
+// Forecast state carried by a policy sold under a confirming template
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ForecastCover {
+    pub min_chance_bps: u64,                 // chance of rain an exceedance needs forecast
+    pub slots:          Vec<ForecastSlot>,   // in start order
+    pub last_recorded:  Option<i64>,
+}
+
+impl ForecastCover {
+    pub fn new(min_chance_bps: u64) -> Self {
+        ForecastCover { min_chance_bps, slots: Vec::new(), last_recorded: None }
+    }
+
+    // File a forecast taken at `issued_at`; returns the slots it set
+    pub fn record(&mut self, forecast: &[ForecastSlot], issued_at: i64) -> usize {
+        let upcoming: Vec<ForecastSlot> = forecast.iter().filter(|s| s.start >= issued_at).copied().collect();
+        self.slots.retain(|s| s.start < issued_at);
+        self.slots.extend(upcoming.iter().copied());
+        self.slots.sort_by_key(|s| s.start);
+        if self.slots.len() > MAX_FORECAST_SLOTS {
+            self.slots.drain(..self.slots.len() - MAX_FORECAST_SLOTS);
+        }
+        self.last_recorded = Some(issued_at);
+        upcoming.len()
+    }
+
+    // Chance of rain forecast for the slot containing `at`, if one was recorded
+    pub fn chance_at(&self, at: i64) -> Option<u64> {
+        self.slots.iter().rev().find(|s| s.contains(at)).map(|s| s.rain_chance_bps)
+    }
+
+    pub fn confirms(&self, at: i64) -> bool {
+        self.chance_at(at).is_some_and(|chance| chance >= self.min_chance_bps)
+    }
+}
+
+// A 0–1 chance of rain, in bps
+pub fn chance_bps(fraction: f64) -> u64 {
+    (fraction.clamp(0.0, 1.0) * FULL_CHANCE_BPS as f64).round() as u64
+}
+
+// Whether a reading that would trigger a policy lacks the forecast to back it;
+// announces it if so. The caller settles only when this returns false.
+pub(crate) fn unconfirmed(state: &InsuranceState, policy_id: PolicyId, reading: Hundredths, observed_at: i64) -> bool {
+    let Some(policy) = state.policies.get(&policy_id) else {
+        return false;
+    };
+    let Some(forecast) = &policy.forecast else {
+        return false;
+    };
+    let live = policy.status == PolicyStatus::Active && policy.is_covered_at(observed_at);
+    if !live || !policy.comparison.is_met(reading, policy.threshold) || forecast.confirms(observed_at) {
+        return false;
+    }
+
+    emit!(TriggerUnconfirmed {
+        policy_id,
+        reading,
+        observed_at,
+        forecast_chance_bps: forecast.chance_at(observed_at),
+        required_bps:        forecast.min_chance_bps,
+    });
+    true
+}
+
+// ── Entry point: require forecast confirmation on a template ─
+#[rialo::instruction]
+pub async fn set_template_forecast_confirmation(
+    ctx:            Context<InsuranceState>,
+    underwriter_id: UnderwriterId,
+    template_id:    TemplateId,
+    min_chance_bps: Option<u64>,
+) -> RialoResult<()> {
+
+    require!(min_chance_bps.is_none_or(|bps| (1..=FULL_CHANCE_BPS).contains(&bps)), InsuranceError::InvalidArgument("Forecast chance must be between 0.01% and 100%.".into()));
+
+    let underwriter = tenant_mut(&mut ctx.state, underwriter_id, &ctx.signer)?;
+    let template = underwriter.templates.get_mut(&template_id).ok_or(InsuranceError::UnknownTemplate)?;
+    if min_chance_bps.is_some() {
+        require!(template.is_plain() && template.peril() == Metric::Rainfall, InsuranceError::InvalidState("Forecast confirmation is only offered on plain rainfall products.".into()));
+        require!(template.comparison == Comparison::AtOrAbove, InsuranceError::InvalidState("Forecast confirmation is only offered on cover paying above the threshold.".into()));
+    }
+    template.forecast_min_bps = min_chance_bps;
+
+    emit!(TemplateForecastConfirmationSet { underwriter_id, template_id, min_chance_bps });
+
+    Ok(())
+}
+
+// ── Entry point: anyone records the provider's forecast for a policy
+#[rialo::instruction]
+pub async fn record_forecast(
+    ctx:       Context<InsuranceState>,
+    policy_id: PolicyId,
+) -> RialoResult<()> {
+
+    let now = ctx.clock.unix_timestamp;
+    let policy = ctx.state.policies.get(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+
+    require!(policy.forecast.is_some(), InsuranceError::InvalidState("Policy does not need forecast confirmation.".into()));
+    let live = match policy.status {
+        PolicyStatus::PendingPayment => true,
+        PolicyStatus::Active         => policy.coverage_end().is_none_or(|end| now < end),
+        _                            => false,
+    };
+    require!(live, InsuranceError::InvalidState("Policy is no longer live.".into()));
+
+    let underwriter_id = policy.underwriter_id;
+    let underwriter = ctx.state.underwriters.get(&underwriter_id).ok_or(InsuranceError::UnknownUnderwriter)?;
+    let provider = &underwriter.provider;
+    let source = provider.base_url_for(ctx.state.config.network_mode).to_string();
+    let headers = provider_headers(underwriter, policy.template_id)?;
+    let (kind, call_cost, place) = (provider.kind, provider.cost_per_call, policy.place.clone());
+
+    let lease = lease_key(&mut ctx.state, underwriter_id, now)?;
+    let url = kind.forecast_request(&source, &lease.key, &place);
+
+    let response = fetch(&url, &headers).await?;
+    if !report_key(&mut ctx.state, underwriter_id, &lease, response.status(), now) {
+        return Ok(());
+    }
+    let slots = kind.parse_forecast(response.body())?;
+
+    record_lae(&mut ctx.state, policy_id, LaeKind::ProviderCost, call_cost);
+
+    let policy = ctx.state.policies.get_mut(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+    let forecast = policy.forecast.as_mut().ok_or_else(|| InsuranceError::InvalidState("Policy does not need forecast confirmation.".into()))?;
+    let recorded = forecast.record(&slots, now);
+
+    emit!(ForecastRecorded { policy_id, issued_at: now, slots: recorded as u32 });
+
+    Ok(())
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct TemplateForecastConfirmationSet { pub underwriter_id: UnderwriterId, pub template_id: TemplateId, pub min_chance_bps: Option<u64> }
+#[rialo::event] pub struct ForecastRecorded                { pub policy_id: PolicyId, pub issued_at: i64, pub slots: u32 }
+#[rialo::event] pub struct TriggerUnconfirmed              { pub policy_id: PolicyId, pub reading: Hundredths, pub observed_at: i64, pub forecast_chance_bps: Option<u64>, pub required_bps: u64 }
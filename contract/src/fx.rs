@@ -0,0 +1,345 @@
+// ============================================================
+//  USD-denominated payouts
+//
+//  A customer whose losses are in dollars can have a policy pay
+//  a USD amount, converted to RALO when it triggers. The RALO
+//  payout reserved at setup stays the ceiling: the underwriter's
+//  collateral is RALO, so a falling price can't draw on capital
+//  beyond it.
+//
+//  Conversion is where a bad print costs real money, so the spot
+//  price is checked against a time-weighted average of the prints
+//  recorded on-chain (the shared RALO/USD feed, config.rs):
+//    • keepers record prints with `record_price`, at most one a
+//      minute, and the last hour's prints make up the TWAP
+//    • a trigger converts at the newest print, if it is fresh and
+//      within the policy's slippage limit of the TWAP
+//    • a check whose reading is past the threshold of a policy
+//      that hasn't converted yet fetches a print from the feed
+//      first, if the newest is stale, spending a call from the
+//      check's budget — so a trigger converts at the price of the
+//      moment, not the last keeper's
+//    • otherwise the payout is deferred, not lost: the policy
+//      waits in PayoutDeferred and `retry_payout` records a fresh
+//      print and tries the conversion again
+//  The rate a payout converted at stays on the policy and goes
+//  out with every PolicyTriggered it pays.
+// ============================================================
+
+use rialo_sdk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::config::{require_unpaused, FeedKind};
+use crate::copay;
+use crate::errors::InsuranceError;
+use crate::millimeters::Hundredths;
+use crate::money::{Ralo, BASE_UNITS_PER_RALO};
+use crate::observations::CheckId;
+use crate::oracle::CallBudget;
+use crate::policy::{PolicyId, PolicyStatus};
+use crate::{fetch, settlement, InsuranceState};
+
+pub const PRICE_TWAP_SECS:   i64 = 60 * 60;
+pub const MAX_SPOT_AGE_SECS: i64 = 5 * 60;
+
+const MIN_PRICE_SPACING_SECS: i64   = 60;
+const MAX_PRICE_POINTS:       usize = 90;      // more than an hour of prints at the minimum spacing
+const MAX_SLIPPAGE_BPS:       u64   = 2_000;   // 20%
+const MICRO_USD_PER_CENT:     u128  = 10_000;
+
+// One RALO/USD print
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PricePoint {
+    pub micro_usd:   u64,   // USD per RALO, in millionths of a dollar
+    pub observed_at: i64,
+}
+
+// Recent prints, oldest first
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct PriceHistory {
+    pub points: Vec<PricePoint>,
+}
+
+impl PriceHistory {
+    pub fn latest(&self) -> Option<PricePoint> {
+        self.points.last().copied()
+    }
+
+    // Keep a print; false if it comes too soon after the last one
+    pub fn record(&mut self, point: PricePoint) -> bool {
+        if self.latest().is_some_and(|last| point.observed_at < last.observed_at + MIN_PRICE_SPACING_SECS) {
+            return false;
+        }
+        self.points.push(point);
+        if self.points.len() > MAX_PRICE_POINTS {
+            self.points.remove(0);
+        }
+        true
+    }
+
+    // Average price over the last `PRICE_TWAP_SECS`, each print weighted by
+    // how long it stood; None until the prints span part of the window
+    pub fn twap(&self, now: i64) -> Option<u64> {
+        let start = now - PRICE_TWAP_SECS;
+        let mut weighted: u128 = 0;
+        let mut span: i64 = 0;
+
+        for (i, point) in self.points.iter().enumerate() {
+            let until = self.points.get(i + 1).map_or(now, |next| next.observed_at).min(now);
+            let from = point.observed_at.max(start);
+            if until <= from {
+                continue;
+            }
+            weighted += point.micro_usd as u128 * (until - from) as u128;
+            span += until - from;
+        }
+
+        (span > 0).then(|| (weighted / span as u128) as u64)
+    }
+}
+
+// A policy's USD payout terms
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct UsdPayout {
+    pub usd_cents:        u64,                     // payout owed, in US cents
+    pub max_slippage_bps: u64,                     // how far spot may sit from the TWAP at conversion
+    pub converted:        Option<Ralo>,            // RALO payout fixed by the conversion
+    pub rate:             Option<u64>,             // micro-USD per RALO it was fixed at
+    pub deferred:         Option<DeferredPayout>,  // share waiting on a fair price
+}
+
+// A payout the slippage guard held back
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct DeferredPayout {
+    pub round_id:    Option<CheckId>,   // settlement the payout belongs to (receipts.rs)
+    pub share_bps:   u64,
+    #[serde(default)]
+    pub value:       Hundredths,        // reading it settled on, in hundredths of the peril's unit
+    #[serde(rename = "reading", default, skip_serializing)]
+    pub old_reading: Option<f64>,       // layout 5 and earlier only: now `value` (migrations.rs)
+    pub observed_at: i64,
+}
+
+impl DeferredPayout {
+    pub fn new(round_id: Option<CheckId>, share_bps: u64, value: Hundredths, observed_at: i64) -> Self {
+        DeferredPayout { round_id, share_bps, value, old_reading: None, observed_at }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConversionError {
+    NoPrice,                                    // no print, or none fresh enough to call spot
+    NoTwap,                                     // not enough history to compare against
+    Slippage { spot: u64, twap: u64 },          // spot too far from the TWAP
+}
+
+// RALO worth `usd_cents` at `micro_usd` per RALO
+pub fn usd_to_ralo(usd_cents: u64, micro_usd: u64) -> Ralo {
+    if micro_usd == 0 {
+        return Ralo::ZERO;
+    }
+    let base_units = usd_cents as u128 * MICRO_USD_PER_CENT * BASE_UNITS_PER_RALO as u128 / micro_usd as u128;
+    Ralo(base_units.min(u64::MAX as u128) as u64)
+}
+
+// Spot's distance from the TWAP, in basis points of the TWAP
+pub fn deviation_bps(spot: u64, twap: u64) -> u64 {
+    if twap == 0 {
+        return u64::MAX;
+    }
+    (spot.abs_diff(twap) as u128 * 10_000 / twap as u128).min(u64::MAX as u128) as u64
+}
+
+// Convert a USD payout at the newest print, guarded by the TWAP
+pub fn convert(history: &PriceHistory, usd_cents: u64, max_slippage_bps: u64, now: i64) -> Result<Ralo, ConversionError> {
+    let spot = history.latest()
+        .filter(|p| now - p.observed_at <= MAX_SPOT_AGE_SECS)
+        .ok_or(ConversionError::NoPrice)?;
+    let twap = history.twap(now).ok_or(ConversionError::NoTwap)?;
+
+    if deviation_bps(spot.micro_usd, twap) > max_slippage_bps {
+        return Err(ConversionError::Slippage { spot: spot.micro_usd, twap });
+    }
+    Ok(usd_to_ralo(usd_cents, spot.micro_usd))
+}
+
+// Fix the RALO payout of a USD policy before its first payment. Returns
+// false, with the payout deferred, when the guard refuses the price.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn lock_conversion(
+    state:       &mut InsuranceState,
+    policy_id:   PolicyId,
+    round_id:    Option<CheckId>,
+    share_bps:   u64,
+    reading:     Hundredths,
+    observed_at: i64,
+    now:         i64,
+) -> RialoResult<bool> {
+
+    let policy = state.policies.get_mut(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+    let copay_bps = policy.copay_bps;
+    let Some(usd) = policy.usd_payout.as_mut() else {
+        return Ok(true);
+    };
+    if usd.converted.is_some() {
+        return Ok(true);
+    }
+
+    match convert(&state.ralo_usd, usd.usd_cents, usd.max_slippage_bps, now) {
+        Ok(amount) => {
+            // The reserve only ever covered the RALO ceiling; release what the conversion doesn't need
+            let payout = amount.min(policy.payout_amount);
+            let released = copay::net(policy.payout_amount, copay_bps).saturating_sub(copay::net(payout, copay_bps));
+            let micro_usd = state.ralo_usd.latest().map_or(0, |p| p.micro_usd);
+            usd.converted = Some(payout);
+            usd.rate      = Some(micro_usd);
+            usd.deferred  = None;
+            policy.payout_amount = payout;
+
+            let underwriter = state.underwriters.get_mut(&policy.underwriter_id).ok_or(InsuranceError::UnknownUnderwriter)?;
+            underwriter.reserved -= released;
+
+            emit!(PayoutConverted { policy_id, usd_cents: usd.usd_cents, payout, micro_usd });
+            Ok(true)
+        }
+        Err(error) => {
+            usd.deferred  = Some(DeferredPayout::new(round_id, share_bps, reading, observed_at));
+            policy.status = PolicyStatus::PayoutDeferred;
+
+            let (spot, twap) = match error {
+                ConversionError::Slippage { spot, twap } => (Some(spot), Some(twap)),
+                _ => (state.ralo_usd.latest().map(|p| p.micro_usd), state.ralo_usd.twap(now)),
+            };
+            emit!(PayoutConversionDeferred { policy_id, spot, twap });
+            Ok(false)
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct PriceResponse {
+    ralo: UsdQuote,
+}
+
+#[derive(Deserialize)]
+struct UsdQuote {
+    usd: f64,
+}
+
+// CoinGecko-style simple price endpoint
+pub fn price_url(base_url: &str) -> String {
+    format!("{base_url}/simple/price?ids=ralo&vs_currencies=usd")
+}
+
+pub fn parse_price(body: &[u8]) -> RialoResult<u64> {
+    let response: PriceResponse = serde_json::from_slice(body).map_err(|_| InsuranceError::BadResponse("Malformed price response.".into()))?;
+    let usd = response.ralo.usd;
+    require!(usd.is_finite() && usd > 0.0, InsuranceError::InvalidState("Price feed returned no price.".into()));
+    Ok((usd * 1_000_000.0).round() as u64)
+}
+
+// Fetch a print from the price feed and keep it
+async fn fetch_price(state: &mut InsuranceState, now: i64) -> RialoResult<PricePoint> {
+    let provider = state.config.feeds.get(&FeedKind::RaloUsd).ok_or_else(|| InsuranceError::InvalidState("No RALO/USD price feed configured.".into()))?;
+    let url = price_url(provider.base_url_for(state.config.network_mode));
+
+    let response = fetch(&url, &[]).await?;
+    let point = PricePoint { micro_usd: parse_price(response.body())?, observed_at: now };
+
+    if state.ralo_usd.record(point) {
+        emit!(PriceRecorded { micro_usd: point.micro_usd, observed_at: now });
+    }
+    Ok(point)
+}
+
+// Before a reading that may trigger an unconverted USD policy settles, make
+// sure there's a fresh spot to convert at, spending a call from `budget`. A
+// fetch that fails leaves the guard to defer the payout as it would anyway.
+pub(crate) async fn refresh_spot(state: &mut InsuranceState, policy_id: PolicyId, reading: Hundredths, now: i64, budget: &mut CallBudget) {
+    let Some(policy) = state.policies.get(&policy_id) else {
+        return;
+    };
+    let unconverted = policy.usd_payout.is_some_and(|usd| usd.converted.is_none());
+    let may_trigger = policy.comparison.is_met(reading, policy.threshold);
+    let fresh = state.ralo_usd.latest().is_some_and(|p| now - p.observed_at <= MAX_SPOT_AGE_SECS);
+    if !unconverted || !may_trigger || fresh || !budget.try_spend() {
+        return;
+    }
+    if fetch_price(state, now).await.is_err() {
+        emit!(SpotRefreshFailed { policy_id });
+    }
+}
+
+// ── Entry point: anyone records a RALO/USD print ─────────────
+#[rialo::instruction]
+pub async fn record_price(ctx: Context<InsuranceState>) -> RialoResult<()> {
+    let now = ctx.clock.unix_timestamp;
+    require!(
+        ctx.state.ralo_usd.latest().is_none_or(|last| now >= last.observed_at + MIN_PRICE_SPACING_SECS),
+        InsuranceError::InvalidState("A price was recorded less than a minute ago.".into()),
+    );
+    fetch_price(&mut ctx.state, now).await?;
+    Ok(())
+}
+
+// ── Entry point: owner denominates an unpaid policy in USD ───
+#[rialo::instruction]
+pub async fn set_usd_payout(
+    ctx:              Context<InsuranceState>,
+    policy_id:        PolicyId,
+    usd_cents:        u64,
+    max_slippage_bps: u64,
+) -> RialoResult<()> {
+
+    let now = ctx.clock.unix_timestamp;
+    let twap = ctx.state.ralo_usd.twap(now).ok_or_else(|| InsuranceError::InvalidState("No RALO/USD price history yet.".into()))?;
+    let policy = ctx.state.policies.get_mut(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+
+    require!(policy.owner == *ctx.signer, InsuranceError::Unauthorized("Only the policy owner can change its payout currency.".into()));
+    require!(policy.status == PolicyStatus::PendingPayment, InsuranceError::InvalidState("Payout currency is fixed once coverage starts.".into()));
+    require!(policy.payout_mint.is_none(), InsuranceError::InvalidState("Policy pays out in another mint, not RALO.".into()));
+    require!(usd_cents > 0, InsuranceError::InvalidArgument("USD payout must be positive.".into()));
+    require!((1..=MAX_SLIPPAGE_BPS).contains(&max_slippage_bps), InsuranceError::InvalidArgument("Slippage limit must be between 0.01% and 20%.".into()));
+    // Sold at today's price, the USD amount must fit inside the reserved RALO payout
+    require!(usd_to_ralo(usd_cents, twap) <= policy.payout_amount, InsuranceError::InvalidState("USD payout exceeds the reserved payout at today's price.".into()));
+
+    policy.usd_payout = Some(UsdPayout { usd_cents, max_slippage_bps, converted: None, rate: None, deferred: None });
+
+    emit!(UsdPayoutSet { policy_id, usd_cents, max_slippage_bps });
+
+    Ok(())
+}
+
+// ── Entry point: anyone retries a deferred USD payout ────────
+#[rialo::instruction]
+pub async fn retry_payout(
+    ctx:       Context<InsuranceState>,
+    policy_id: PolicyId,
+) -> RialoResult<()> {
+
+    require_unpaused(&ctx.state.config)?;
+    let now = ctx.clock.unix_timestamp;
+    let policy = ctx.state.policies.get(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+
+    require!(policy.status == PolicyStatus::PayoutDeferred, InsuranceError::InvalidState("Policy has no deferred payout.".into()));
+    let deferred = policy.usd_payout.and_then(|usd| usd.deferred).ok_or_else(|| InsuranceError::InvalidState("Policy has no deferred payout.".into()))?;
+
+    // A fresh print is the spot the retry converts at
+    fetch_price(&mut ctx.state, now).await?;
+
+    // Back to live cover for the guard; it defers again if the price is still off
+    if let Some(policy) = ctx.state.policies.get_mut(&policy_id) {
+        policy.status = PolicyStatus::Active;
+    }
+    // Paid and announced under the round that triggered it, not the retry
+    settlement::pay_share(&mut ctx.state, &ctx.vault, policy_id, deferred.round_id, deferred.share_bps, deferred.value, deferred.observed_at, now)?;
+
+    Ok(())
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct PriceRecorded            { pub micro_usd: u64, pub observed_at: i64 }
+#[rialo::event] pub struct UsdPayoutSet             { pub policy_id: PolicyId, pub usd_cents: u64, pub max_slippage_bps: u64 }
+#[rialo::event] pub struct PayoutConverted          { pub policy_id: PolicyId, pub usd_cents: u64, pub payout: Ralo, pub micro_usd: u64 }
+#[rialo::event] pub struct PayoutConversionDeferred { pub policy_id: PolicyId, pub spot: Option<u64>, pub twap: Option<u64> }
+#[rialo::event] pub struct SpotRefreshFailed        { pub policy_id: PolicyId }
@@ -0,0 +1,166 @@
+// ============================================================
+//  Setup-time geocoding
+//
+//  Point perils are read at coordinates, and a policy written on
+//  one starts from where the tenant's provider places its city.
+//  Those fixes are kept per tenant and location so every policy
+//  in a city doesn't pay for the same lookup — but a provider
+//  that moves its city centre moves the measurement point, and a
+//  cache that never forgets would silently keep writing risk at
+//  the old one. A fix is therefore only reused up to the admin's
+//  maximum age and only from the source that produced it; past
+//  that, or when a tenant forces a refresh, the provider is asked
+//  again and the move is announced with its distance.
+//
+//  A policy keeps the point it was written at; a refresh only
+//  changes where new policies start.
+//
+//  The admin can also have named places keyed by where they are:
+//  with geohash keys on, a new policy's location key is its
+//  name's slug with the geocode's geohash appended, standing in
+//  for any country qualifier (normalization.rs). A place that
+//  can't be geocoded keeps its plain slug. Pricing tables stay
+//  keyed by the plain slug.
+// ============================================================
+
+use rialo_sdk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::InsuranceError;
+use crate::geo::{haversine_km, GeoPoint, MAX_GEOHASH_LEN};
+use crate::keys::{lease_key, report_key};
+use crate::normalization::{canonical_location, geohashed_slug, Location};
+use crate::providers::WeatherProvider;
+use crate::underwriter::{tenant_mut, UnderwriterId};
+use crate::{fetch, InsuranceState};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Geocode {
+    pub point:       GeoPoint,
+    pub source:      String,   // base URL that answered
+    pub resolved_at: i64,
+}
+
+impl Geocode {
+    // Whether the fix can still be reused for `source` at `now`
+    pub fn is_fresh(&self, source: &str, now: i64, ttl_secs: i64) -> bool {
+        self.source == source && now - self.resolved_at <= ttl_secs
+    }
+}
+
+// Where the tenant's provider places `location`: the cached fix while it is
+// fresh and not `force`d, otherwise a new lookup that replaces it
+pub(crate) async fn resolve(
+    state:          &mut InsuranceState,
+    underwriter_id: UnderwriterId,
+    location:       &str,
+    now:            i64,
+    force:          bool,
+) -> RialoResult<GeoPoint> {
+
+    let ttl_secs = state.config.geocode_ttl_secs;
+    let underwriter = state.underwriters.get(&underwriter_id).ok_or(InsuranceError::UnknownUnderwriter)?;
+    let source = underwriter.provider.base_url_for(state.config.network_mode).to_string();
+    let previous = underwriter.geocodes.get(location).cloned();
+    if let Some(cached) = previous.as_ref().filter(|g| !force && g.is_fresh(&source, now, ttl_secs)) {
+        return Ok(cached.point);
+    }
+
+    let lease = lease_key(state, underwriter_id, now)?;
+    let kind = state.underwriters.get(&underwriter_id).ok_or(InsuranceError::UnknownUnderwriter)?.provider.kind;
+    let url = kind.geocode_request(&source, &lease.key, location);
+
+    let response = fetch(&url, &[]).await?;
+    require!(report_key(state, underwriter_id, &lease, response.status(), now), InsuranceError::InvalidState("Provider rejected the geocoding key.".into()));
+    let point = kind.parse_geocode(response.body())?;
+
+    let underwriter = state.underwriters.get_mut(&underwriter_id).ok_or(InsuranceError::UnknownUnderwriter)?;
+    underwriter.geocodes.insert(location.to_string(), Geocode { point, source, resolved_at: now });
+
+    let moved_m = previous.as_ref().map(|g| haversine_km(&g.point, &point));
+    emit!(LocationGeocoded {
+        underwriter_id,
+        location: location.to_string(),
+        point,
+        previous: previous.map(|g| g.point),
+        moved_m,
+    });
+
+    Ok(point)
+}
+
+// Key a new policy at `place` is kept under: its slug, or with geohash keys
+// on, a named place's slug with its geohash
+pub(crate) async fn location_key(state: &mut InsuranceState, underwriter_id: UnderwriterId, place: &Location, now: i64) -> String {
+    let precision = state.config.geohash_key_len as usize;
+    let Location::City(name) = place else {
+        return place.key();
+    };
+    if precision == 0 {
+        return place.key();
+    }
+    match resolve(state, underwriter_id, name, now, false).await {
+        Ok(point) => geohashed_slug(name, &point, precision),
+        Err(_)    => place.key(),
+    }
+}
+
+// ── Entry point: admin sets how long a geocode may be reused ─
+#[rialo::instruction]
+pub async fn set_geocode_ttl(
+    ctx:      Context<InsuranceState>,
+    ttl_secs: i64,
+) -> RialoResult<()> {
+
+    let config = &mut ctx.state.config;
+
+    require!(config.admin == *ctx.signer, InsuranceError::Unauthorized("Only the admin can change the geocode age limit.".into()));
+    require!(ttl_secs >= 0, InsuranceError::InvalidState("Geocode age limit cannot be negative.".into()));
+
+    config.geocode_ttl_secs = ttl_secs;
+
+    emit!(GeocodeTtlSet { ttl_secs });
+
+    Ok(())
+}
+
+// ── Entry point: admin turns geohash location keys on or off ─
+#[rialo::instruction]
+pub async fn set_geohash_keys(
+    ctx:       Context<InsuranceState>,
+    precision: u8,   // geohash characters appended; 0 turns them off
+) -> RialoResult<()> {
+
+    let config = &mut ctx.state.config;
+
+    require!(config.admin == *ctx.signer, InsuranceError::Unauthorized("Only the admin can change location keys.".into()));
+    require!(precision as usize <= MAX_GEOHASH_LEN, InsuranceError::InvalidArgument("Geohashes are at most 12 characters.".into()));
+
+    // Existing policies keep the key they were written under
+    config.geohash_key_len = precision;
+
+    emit!(GeohashKeysSet { precision });
+
+    Ok(())
+}
+
+// ── Entry point: tenant re-reads a location's geocode now ────
+#[rialo::instruction]
+pub async fn refresh_geocode(
+    ctx:            Context<InsuranceState>,
+    underwriter_id: UnderwriterId,
+    location:       String,
+) -> RialoResult<GeoPoint> {
+
+    let now = ctx.clock.unix_timestamp;
+    let location = canonical_location(&location);
+    require!(!location.is_empty(), InsuranceError::InvalidArgument("Location is required.".into()));
+    tenant_mut(&mut ctx.state, underwriter_id, &ctx.signer)?;
+
+    resolve(&mut ctx.state, underwriter_id, &location, now, true).await
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct GeocodeTtlSet    { pub ttl_secs: i64 }
+#[rialo::event] pub struct GeohashKeysSet   { pub precision: u8 }
+#[rialo::event] pub struct LocationGeocoded { pub underwriter_id: UnderwriterId, pub location: String, pub point: GeoPoint, pub previous: Option<GeoPoint>, pub moved_m: Option<u64> }
@@ -0,0 +1,216 @@
+// ============================================================
+//  Parameter governance
+//
+//  Progressive decentralization: the admin key starts out setting
+//  every contract-wide parameter, then hands a selected set over
+//  to the underwriters whose capital backs the book. Once
+//  governance is enabled — one way, it can't be switched back —
+//  the admin setters for those parameters refuse, and they change
+//  only through propose → vote → execute:
+//    • any funded tenant proposes a change
+//    • tenants vote, weighted by their stake, until voting ends
+//    • after a timelock anyone executes it, if enough stake voted
+//      (quorum) and more of it voted for than against
+//
+//  A tenant's stake is its vault capital, less any withdrawal on
+//  its way out; a risk pool (pools.rs) votes its members' pooled
+//  capital as the one tenant it is. Votes are weighed when cast;
+//  withdrawals need notice (underwriter.rs), so capital can't vote
+//  and leave within one proposal.
+// ============================================================
+
+use rialo_sdk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::config::{apply_data_feed, apply_lapse_settings, apply_policy_floors, ContractConfig, FeedKind};
+use crate::errors::InsuranceError;
+use crate::keepers::{apply_keeper_rewards, KeeperRewards};
+use crate::levies::{apply_country_levies, Levy};
+use crate::money::Ralo;
+use crate::underwriter::{tenant_mut, ProviderConfig, Underwriter, UnderwriterId};
+use crate::InsuranceState;
+
+pub type ProposalId = u64;
+
+const MIN_VOTING_SECS:   i64 = 24 * 60 * 60;
+const MIN_TIMELOCK_SECS: i64 = 24 * 60 * 60;
+const MIN_QUORUM_BPS:    u64 = 1_000;   // 10% of stake
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GovernanceConfig {
+    pub voting_secs:   i64,   // how long a proposal is open for votes
+    pub timelock_secs: i64,   // wait between the end of voting and execution
+    pub quorum_bps:    u64,   // share of all stake that must vote
+}
+
+// The parameters underwriters govern once governance is enabled
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub enum ParameterChange {
+    LapseSettings { payment_grace_secs: i64, lapse_reward: Ralo },
+    PolicyFloors  { min_premium: Ralo, min_payout: Ralo },
+    CountryLevies { country: String, levies: Vec<Levy> },
+    DataFeed      { kind: FeedKind, provider: Option<ProviderConfig> },
+    KeeperRewards { rewards: Option<KeeperRewards> },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Proposal {
+    pub change:        ParameterChange,
+    pub proposer:      UnderwriterId,
+    pub voting_ends:   i64,
+    pub executable_at: i64,                  // voting end plus the timelock
+    pub votes_for:     Ralo,                 // stake in favour
+    pub votes_against: Ralo,                 // stake against
+    pub voters:        Vec<UnderwriterId>,   // tenants that have voted
+    pub executed:      bool,
+}
+
+impl Proposal {
+    // Enough of `total_stake` voted, and more of it in favour
+    pub fn passes(&self, total_stake: Ralo, quorum_bps: u64) -> bool {
+        let turnout = self.votes_for + self.votes_against;
+        turnout >= total_stake.bps(quorum_bps) && self.votes_for > self.votes_against
+    }
+}
+
+// Capital a tenant votes with: what stays in its vault
+pub fn stake(underwriter: &Underwriter) -> Ralo {
+    let withdrawing = underwriter.withdrawal.map_or(Ralo::ZERO, |w| w.amount);
+    underwriter.capital.saturating_sub(withdrawing)
+}
+
+// Admin setters for governed parameters call this first
+pub(crate) fn require_ungoverned(config: &ContractConfig) -> RialoResult<()> {
+    require!(config.governance.is_none(), InsuranceError::InvalidState("This parameter is now set by underwriter vote.".into()));
+    Ok(())
+}
+
+// ── Entry point: admin hands governed parameters to the underwriters
+#[rialo::instruction]
+pub async fn enable_governance(
+    ctx:        Context<InsuranceState>,
+    governance: GovernanceConfig,
+) -> RialoResult<()> {
+
+    let config = &mut ctx.state.config;
+
+    require!(config.admin == *ctx.signer, InsuranceError::Unauthorized("Only the admin can enable governance.".into()));
+    require!(config.governance.is_none(), InsuranceError::InvalidState("Governance is already enabled.".into()));
+    require!(governance.voting_secs >= MIN_VOTING_SECS, InsuranceError::InvalidArgument("Voting must stay open for at least a day.".into()));
+    require!(governance.timelock_secs >= MIN_TIMELOCK_SECS, InsuranceError::InvalidArgument("Timelock must be at least a day.".into()));
+    require!((MIN_QUORUM_BPS..=10_000).contains(&governance.quorum_bps), InsuranceError::InvalidArgument("Quorum must be between 10% and 100% of stake.".into()));
+
+    config.governance = Some(governance);
+
+    emit!(GovernanceEnabled { voting_secs: governance.voting_secs, timelock_secs: governance.timelock_secs, quorum_bps: governance.quorum_bps });
+
+    Ok(())
+}
+
+// ── Entry point: a funded tenant proposes a parameter change ─
+#[rialo::instruction]
+pub async fn propose(
+    ctx:            Context<InsuranceState>,
+    underwriter_id: UnderwriterId,
+    change:         ParameterChange,
+) -> RialoResult<ProposalId> {
+
+    let now = ctx.clock.unix_timestamp;
+    let governance = ctx.state.config.governance.ok_or_else(|| InsuranceError::InvalidState("Governance is not enabled.".into()))?;
+
+    let underwriter = tenant_mut(&mut ctx.state, underwriter_id, &ctx.signer)?;
+    require!(!stake(underwriter).is_zero(), InsuranceError::Unauthorized("Only underwriters with capital can propose.".into()));
+
+    let state = &mut ctx.state;
+    let proposal_id = state.next_proposal_id;
+    state.next_proposal_id += 1;
+
+    let voting_ends = now + governance.voting_secs;
+    state.proposals.insert(proposal_id, Proposal {
+        change:        change.clone(),
+        proposer:      underwriter_id,
+        voting_ends,
+        executable_at: voting_ends + governance.timelock_secs,
+        votes_for:     Ralo::ZERO,
+        votes_against: Ralo::ZERO,
+        voters:        Vec::new(),
+        executed:      false,
+    });
+
+    emit!(ProposalCreated { proposal_id, proposer: underwriter_id, change, voting_ends });
+
+    Ok(proposal_id)
+}
+
+// ── Entry point: a tenant votes its stake on a proposal ──────
+#[rialo::instruction]
+pub async fn vote(
+    ctx:            Context<InsuranceState>,
+    proposal_id:    ProposalId,
+    underwriter_id: UnderwriterId,
+    support:        bool,
+) -> RialoResult<()> {
+
+    let now = ctx.clock.unix_timestamp;
+    let weight = stake(tenant_mut(&mut ctx.state, underwriter_id, &ctx.signer)?);
+    require!(!weight.is_zero(), InsuranceError::InvalidState("Underwriter has no stake to vote with.".into()));
+
+    let proposal = ctx.state.proposals.get_mut(&proposal_id).ok_or_else(|| InsuranceError::UnknownRecord("Unknown proposal.".into()))?;
+    require!(now < proposal.voting_ends, InsuranceError::InvalidState("Voting on this proposal has ended.".into()));
+    require!(!proposal.voters.contains(&underwriter_id), InsuranceError::InvalidState("Underwriter already voted on this proposal.".into()));
+
+    proposal.voters.push(underwriter_id);
+    if support {
+        proposal.votes_for += weight;
+    } else {
+        proposal.votes_against += weight;
+    }
+
+    emit!(VoteCast { proposal_id, underwriter_id, support, weight });
+
+    Ok(())
+}
+
+// ── Entry point: anyone executes a passed proposal after its timelock
+#[rialo::instruction]
+pub async fn execute(
+    ctx:         Context<InsuranceState>,
+    proposal_id: ProposalId,
+) -> RialoResult<()> {
+
+    let now = ctx.clock.unix_timestamp;
+    let state = &mut ctx.state;
+    let governance = state.config.governance.ok_or_else(|| InsuranceError::InvalidState("Governance is not enabled.".into()))?;
+
+    let proposal = state.proposals.get(&proposal_id).ok_or_else(|| InsuranceError::UnknownRecord("Unknown proposal.".into()))?;
+    require!(!proposal.executed, InsuranceError::InvalidState("Proposal already executed.".into()));
+    require!(now >= proposal.executable_at, InsuranceError::InvalidState("Proposal is still timelocked.".into()));
+
+    // Quorum is measured against the stake backing the book today
+    let total_stake = state.underwriters.values().map(stake).fold(Ralo::ZERO, |sum, s| sum + s);
+    require!(proposal.passes(total_stake, governance.quorum_bps), InsuranceError::InvalidState("Proposal did not pass.".into()));
+
+    let change = proposal.change.clone();
+    let config = &mut state.config;
+    match change {
+        ParameterChange::LapseSettings { payment_grace_secs, lapse_reward } => apply_lapse_settings(config, payment_grace_secs, lapse_reward)?,
+        ParameterChange::PolicyFloors { min_premium, min_payout }           => apply_policy_floors(config, min_premium, min_payout)?,
+        ParameterChange::CountryLevies { country, levies }                  => apply_country_levies(config, &country, levies)?,
+        ParameterChange::DataFeed { kind, provider }                        => apply_data_feed(config, kind, provider),
+        ParameterChange::KeeperRewards { rewards }                          => apply_keeper_rewards(config, rewards)?,
+    }
+
+    if let Some(proposal) = state.proposals.get_mut(&proposal_id) {
+        proposal.executed = true;
+    }
+
+    emit!(ProposalExecuted { proposal_id });
+
+    Ok(())
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct GovernanceEnabled { pub voting_secs: i64, pub timelock_secs: i64, pub quorum_bps: u64 }
+#[rialo::event] pub struct ProposalCreated   { pub proposal_id: ProposalId, pub proposer: UnderwriterId, pub change: ParameterChange, pub voting_ends: i64 }
+#[rialo::event] pub struct VoteCast          { pub proposal_id: ProposalId, pub underwriter_id: UnderwriterId, pub support: bool, pub weight: Ralo }
+#[rialo::event] pub struct ProposalExecuted  { pub proposal_id: ProposalId }
@@ -0,0 +1,186 @@
+// ============================================================
+//  Health self-check
+//
+//  One instruction a monitor can call on a schedule and alert on
+//  regressions from, instead of stitching the contract's health
+//  together from views:
+//    • provider — one current-conditions call through a tenant's
+//      provider account, at a city it already covers so the
+//      reading lands in the hourly cache (cache.rs) and isn't
+//      wasted
+//    • config   — settings that contradict each other
+//    • solvency — the vault against its liabilities (reserve.rs)
+//    • backlog  — settlements waiting on something: readings held
+//      by an incident, deferred USD payouts, and policies past
+//      their finalize window that nobody has closed out
+//  The report is returned and published as a `HealthReport` event.
+//  Calls are spaced out so a monitor can't burn a tenant's quota.
+// ============================================================
+
+use rialo_sdk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::InsuranceError;
+use crate::keys::{lease_key, report_key};
+use crate::normalization::Location;
+use crate::policy::PolicyStatus;
+use crate::providers::{ReadingTime, WeatherProvider};
+use crate::reserve::proof_of_reserve;
+use crate::underwriter::UnderwriterId;
+use crate::{fetch, provider_headers, InsuranceState};
+
+const MIN_HEALTH_CHECK_INTERVAL_SECS: i64 = 10 * 60;
+
+// Probed when the tenant has no live cover to probe at
+const PROBE_CITY: &str = "london";
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub enum ConfigIssue {
+    NotInitialized,
+    FinalizeBeyondLookback,                            // final checks could look further back than history allows
+    ArbiterThresholdUnreachable,                       // more approvals required than there are arbiters
+    AuditWithoutSources,                               // audits are sampled but nothing can re-check them
+    ProviderUnset { underwriter_id: UnderwriterId },   // tenant has no endpoint for the network mode
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Backlog {
+    pub held_observations: u32,   // readings parked behind a data incident
+    pub deferred_payouts:  u32,   // triggered USD payouts waiting on a fair price
+    pub attestations_due:  u32,   // triggered payouts waiting on the beneficiary's attestation
+    pub payouts_pending:   u32,   // triggered payouts in their dispute window
+    pub unfinalized:       u32,   // active policies past their finalize window
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Health {
+    pub underwriter_id:     UnderwriterId,
+    pub provider_status:    Option<u16>,        // HTTP status of the probe; None if the call failed outright
+    pub provider_ok:        bool,
+    pub config_issues:      Vec<ConfigIssue>,
+    pub solvency_ratio_bps: u64,                // vault / liabilities (10_000 = exactly funded)
+    pub backlog:            Backlog,
+    pub healthy:            bool,               // every check passed and nothing is backed up
+}
+
+// Settings that contradict each other
+pub fn config_issues(state: &InsuranceState) -> Vec<ConfigIssue> {
+    let config = &state.config;
+    let mut issues = Vec::new();
+
+    if !config.initialized {
+        issues.push(ConfigIssue::NotInitialized);
+    }
+    if config.finalize_secs > config.max_lookback_secs {
+        issues.push(ConfigIssue::FinalizeBeyondLookback);
+    }
+    if config.arbiter_threshold as usize > config.arbiters.len() {
+        issues.push(ConfigIssue::ArbiterThresholdUnreachable);
+    }
+    let sampled = config.audit.sample_rate_bps > 0 || config.audit.triggered_rate_bps > 0;
+    if sampled && config.audit.providers.is_empty() {
+        issues.push(ConfigIssue::AuditWithoutSources);
+    }
+    for (id, underwriter) in &state.underwriters {
+        if underwriter.provider.base_url_for(config.network_mode).is_empty() {
+            issues.push(ConfigIssue::ProviderUnset { underwriter_id: *id });
+        }
+    }
+    issues
+}
+
+// Settlements waiting on something at `now`
+pub fn backlog(state: &InsuranceState, now: i64) -> Backlog {
+    let finalize_secs = state.config.finalize_secs;
+
+    Backlog {
+        held_observations: state.held_observations.len() as u32,
+        deferred_payouts:  state.policies.values().filter(|p| p.status == PolicyStatus::PayoutDeferred).count() as u32,
+        attestations_due:  state.policies.values().filter(|p| p.status == PolicyStatus::PendingAttestation).count() as u32,
+        payouts_pending:   state.policies.values().filter(|p| p.status == PolicyStatus::PendingPayout).count() as u32,
+        unfinalized:       state.policies
+            .values()
+            .filter(|p| p.status == PolicyStatus::Active)
+            .filter(|p| p.coverage_end().is_some_and(|end| now > end + finalize_secs))
+            .count() as u32,
+    }
+}
+
+// ── Entry point: anyone runs the self-check against a tenant's provider
+#[rialo::instruction]
+pub async fn health_check(
+    ctx:            Context<InsuranceState>,
+    underwriter_id: UnderwriterId,
+) -> RialoResult<Health> {
+
+    let now = ctx.clock.unix_timestamp;
+    let state = &mut ctx.state;
+
+    require!(now - state.last_health_check >= MIN_HEALTH_CHECK_INTERVAL_SECS, InsuranceError::InvalidState("Health was checked too recently.".into()));
+    state.last_health_check = now;
+
+    // ── Provider: one call, at a city the tenant already covers ──
+    let underwriter = state.underwriters.get(&underwriter_id).ok_or(InsuranceError::UnknownUnderwriter)?;
+    let live = state.policies
+        .values()
+        .find(|p| p.underwriter_id == underwriter_id && p.status == PolicyStatus::Active);
+    let (probe, headers) = match live {
+        Some(policy) => (policy.place.clone(), provider_headers(underwriter, policy.template_id)?),
+        None         => (Location::City(PROBE_CITY.into()), Vec::new()),
+    };
+    let source = underwriter.provider.base_url_for(state.config.network_mode).to_string();
+    let kind = underwriter.provider.kind;
+
+    let lease = lease_key(state, underwriter_id, now)?;
+    let url = kind.build_request(&source, &lease.key, &probe, ReadingTime::Current);
+    let (provider_status, provider_ok) = match fetch(&url, &headers).await {
+        Err(_) => (None, false),
+        Ok(response) => {
+            let status = response.status();
+            let accepted = report_key(state, underwriter_id, &lease, status, now) && (200..300).contains(&status);
+            match kind.parse_observation(ReadingTime::Current, response.body()) {
+                Ok(observation) if accepted => {
+                    state.weather_cache.insert(&source, &probe.key(), now, observation);
+                    (Some(status), true)
+                }
+                _ => (Some(status), false),
+            }
+        }
+    };
+
+    if !provider_ok {
+        state.metrics.http_failures += 1;
+    }
+
+    // ── Config, solvency and backlog ──────────────────────────
+    let config_issues = config_issues(state);
+    let solvency_ratio_bps = proof_of_reserve(state, &ctx.vault).solvency_ratio_bps;
+    let backlog = backlog(state, now);
+    let backed_up = backlog.held_observations > 0 || backlog.deferred_payouts > 0 || backlog.unfinalized > 0;
+
+    let health = Health {
+        underwriter_id,
+        provider_status,
+        provider_ok,
+        healthy: provider_ok && config_issues.is_empty() && solvency_ratio_bps >= 10_000 && !backed_up,
+        config_issues,
+        solvency_ratio_bps,
+        backlog,
+    };
+
+    emit!(HealthReport {
+        at:                 now,
+        underwriter_id,
+        provider_status:    health.provider_status,
+        provider_ok:        health.provider_ok,
+        config_issues:      health.config_issues.clone(),
+        solvency_ratio_bps: health.solvency_ratio_bps,
+        backlog:            health.backlog.clone(),
+        healthy:            health.healthy,
+    });
+
+    Ok(health)
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct HealthReport { pub at: i64, pub underwriter_id: UnderwriterId, pub provider_status: Option<u16>, pub provider_ok: bool, pub config_issues: Vec<ConfigIssue>, pub solvency_ratio_bps: u64, pub backlog: Backlog, pub healthy: bool }
@@ -0,0 +1,106 @@
+// ============================================================
+//  Claims history
+//
+//  Why did a policy pay — or not? Every provider check logged
+//  against a policy (observations.rs) is also filed, in the order
+//  it was made, on the policy's claims ledger: when the reading
+//  was taken, what it was, where it came from, what the
+//  evaluation decided (evaluation.rs) and what was paid on it.
+//  The ledger is only ever appended to.
+//
+//  `get_claims_history` reads it a page at a time, oldest first,
+//  so a frontend or an auditor can replay a policy's checks
+//  without scraping events RPC nodes may since have pruned.
+//  Arbiter overrides keep their own log (arbiter.rs). Once a
+//  policy's observations are archived (retention.rs) its ledger
+//  still counts every check, but the entries are read off the
+//  archive events instead.
+// ============================================================
+
+use std::collections::BTreeMap;
+
+use rialo_sdk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::InsuranceError;
+use crate::evaluation::Evaluation;
+use crate::millimeters::Hundredths;
+use crate::money::Ralo;
+use crate::normalization::Station;
+use crate::observations::{CheckId, CheckRecord};
+use crate::policy::PolicyId;
+use crate::InsuranceState;
+
+pub const CLAIMS_PAGE_SIZE: usize = 25;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ClaimsHistoryEntry {
+    pub check_id:    CheckId,
+    pub observed_at: i64,
+    pub reading:     Hundredths,   // reading as the provider gave it, before any smoothing
+    pub source:      String,       // provider base URL
+    pub station:     Station,
+    pub decision:    Evaluation,
+    pub paid:        Ralo,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ClaimsHistoryPage {
+    pub policy_id: PolicyId,
+    pub page:      u32,                       // from zero
+    pub pages:     u32,
+    pub total:     u32,                       // checks on the ledger
+    pub entries:   Vec<ClaimsHistoryEntry>,   // oldest first
+}
+
+// File a logged check on its policy's ledger
+pub(crate) fn append(state: &mut InsuranceState, policy_id: PolicyId, check_id: CheckId) {
+    state.claims_history.entry(policy_id).or_default().push(check_id);
+}
+
+// Page `page` of a ledger, read off the check log
+pub fn claims_history_page(
+    policy_id: PolicyId,
+    ledger:    &[CheckId],
+    checks:    &BTreeMap<CheckId, CheckRecord>,
+    page:      u32,
+) -> ClaimsHistoryPage {
+
+    let entries = ledger
+        .iter()
+        .skip(page as usize * CLAIMS_PAGE_SIZE)
+        .take(CLAIMS_PAGE_SIZE)
+        .filter_map(|check_id| {
+            let record = checks.get(check_id)?;
+            Some(ClaimsHistoryEntry {
+                check_id:    *check_id,
+                observed_at: record.observed_at,
+                reading:     record.reading,
+                source:      record.source.clone(),
+                station:     record.station.clone(),
+                decision:    record.decision,
+                paid:        record.decision.paid(),
+            })
+        })
+        .collect();
+
+    ClaimsHistoryPage {
+        policy_id,
+        page,
+        pages:   ledger.len().div_ceil(CLAIMS_PAGE_SIZE) as u32,
+        total:   ledger.len() as u32,
+        entries,
+    }
+}
+
+#[rialo::view]
+pub fn get_claims_history(
+    ctx:       Context<InsuranceState>,
+    policy_id: PolicyId,
+    page:      u32,
+) -> RialoResult<ClaimsHistoryPage> {
+
+    require!(ctx.state.policies.contains_key(&policy_id), InsuranceError::UnknownPolicy);
+    let ledger = ctx.state.claims_history.get(&policy_id).map_or(&[][..], Vec::as_slice);
+    Ok(claims_history_page(policy_id, ledger, &ctx.state.checks, page))
+}
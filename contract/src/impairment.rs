@@ -0,0 +1,163 @@
+// ============================================================
+//  Impaired policies
+//
+//  A policy whose provider was down for most of its window was
+//  sold cover the contract couldn't settle. Instead of leaving
+//  that to a support ticket, the outcome is defined up front:
+//  when a policy expires, the declared data incidents on its
+//  tenant's provider (incidents.rs) are measured against its
+//  coverage window, and a policy that was un-settleable for at
+//  least the admin's threshold is classified as impaired. The
+//  owner of an impaired policy can then claim back a set share
+//  of the net premium from the underwriter's capital, rounded in
+//  the owner's favour (actuarial.rs).
+//
+//  Overlapping incidents count once, and an incident still open
+//  at expiry counts up to the end of coverage. A policy that
+//  paid out was settled and is never impaired.
+// ============================================================
+
+use rialo_sdk::prelude::*;
+use rialo_sdk::token::transfer;
+use serde::{Deserialize, Serialize};
+
+use crate::actuarial;
+use crate::errors::InsuranceError;
+use crate::incidents::DataIncident;
+use crate::money::Ralo;
+use crate::policy::{PolicyId, PolicyStatus};
+use crate::InsuranceState;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OutageRefundTerms {
+    pub min_outage_bps: u64,   // share of a policy's window the provider must have been out
+    pub refund_bps:     u64,   // share of the net premium an impaired policy gets back
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Impairment {
+    pub outage_bps: u64,    // share of the window the provider was out
+    pub refund:     Ralo,   // owed to the owner under the terms at expiry
+    pub refunded:   bool,
+}
+
+// Seconds of [start, end) that `source` spent under any incident
+pub fn outage_secs(incidents: &[DataIncident], source: &str, start: i64, end: i64) -> i64 {
+    let mut spans: Vec<(i64, i64)> = incidents
+        .iter()
+        .filter(|i| i.source == source)
+        .map(|i| (i.start.max(start), i.end.unwrap_or(end).min(end)))
+        .filter(|(from, until)| until > from)
+        .collect();
+    spans.sort_unstable();
+
+    let mut total = 0;
+    let mut covered_to = start;
+    for (from, until) in spans {
+        let from = from.max(covered_to);
+        if until > from {
+            total += until - from;
+            covered_to = until;
+        }
+    }
+    total
+}
+
+// Outage as a share of the window, in basis points
+pub fn outage_bps(incidents: &[DataIncident], source: &str, start: i64, end: i64) -> u64 {
+    if end <= start {
+        return 0;
+    }
+    (outage_secs(incidents, source, start, end) as u128 * 10_000 / (end - start) as u128) as u64
+}
+
+// Classify a policy that has just expired
+pub(crate) fn assess(state: &mut InsuranceState, policy_id: PolicyId) {
+    let Some(terms) = state.config.outage_refund else {
+        return;
+    };
+    let Some(policy) = state.policies.get(&policy_id) else {
+        return;
+    };
+    let (Some(start), Some(end)) = (policy.activated_at, policy.coverage_end()) else {
+        return;
+    };
+    let Some(underwriter) = state.underwriters.get(&policy.underwriter_id) else {
+        return;
+    };
+
+    let source = underwriter.provider.base_url_for(state.config.network_mode);
+    let outage_bps = outage_bps(&state.incidents, source, start, end);
+    if policy.status != PolicyStatus::Expired || outage_bps < terms.min_outage_bps {
+        return;
+    }
+
+    let levied: Ralo = policy.levies.iter().map(|l| l.amount).sum();
+    let refund = actuarial::refund_bps(policy.premium_paid.saturating_sub(levied), terms.refund_bps);
+    let owner = policy.owner;
+
+    if let Some(policy) = state.policies.get_mut(&policy_id) {
+        policy.impairment = Some(Impairment { outage_bps, refund, refunded: false });
+    }
+
+    emit!(PolicyImpaired { policy_id, owner, outage_bps, refund });
+}
+
+// ── Entry point: admin sets (or withdraws) the outage refund terms
+#[rialo::instruction]
+pub async fn set_outage_refund_terms(
+    ctx:   Context<InsuranceState>,
+    terms: Option<OutageRefundTerms>,
+) -> RialoResult<()> {
+
+    let config = &mut ctx.state.config;
+
+    require!(config.admin == *ctx.signer, InsuranceError::Unauthorized("Only the admin can change outage refund terms.".into()));
+    if let Some(terms) = terms {
+        require!((1..=10_000).contains(&terms.min_outage_bps), InsuranceError::InvalidArgument("Outage threshold must be between 0.01% and 100%.".into()));
+        require!((1..=10_000).contains(&terms.refund_bps), InsuranceError::InvalidArgument("Refund must be between 0.01% and 100% of the premium.".into()));
+    }
+
+    config.outage_refund = terms;
+
+    emit!(OutageRefundTermsSet { terms });
+
+    Ok(())
+}
+
+// ── Entry point: owner of an impaired policy claims the refund
+#[rialo::instruction]
+pub async fn claim_outage_refund(
+    ctx:       Context<InsuranceState>,
+    policy_id: PolicyId,
+) -> RialoResult<()> {
+
+    let state = &mut ctx.state;
+    let policy = state.policies.get_mut(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+
+    require!(policy.owner == *ctx.signer, InsuranceError::Unauthorized("Only the policy owner can claim its refund.".into()));
+    let impairment = policy.impairment.as_mut().ok_or_else(|| InsuranceError::InvalidState("Policy is not impaired.".into()))?;
+    require!(!impairment.refunded, InsuranceError::InvalidState("Outage refund already claimed.".into()));
+
+    let refund = impairment.refund;
+    let underwriter = state.underwriters.get_mut(&policy.underwriter_id).ok_or(InsuranceError::UnknownUnderwriter)?;
+    require!(underwriter.free_capital() >= refund, InsuranceError::VaultInsolvent("Underwriter can't cover the refund yet.".into()));
+
+    transfer(&ctx.vault, &policy.owner, refund.base_units())?;
+
+    // Refunded premium was never really earned
+    underwriter.capital -= refund;
+    underwriter.claims.premiums_earned = underwriter.claims.premiums_earned.saturating_sub(refund);
+    let product = underwriter.claims.template_mut(policy.template_id);
+    product.premiums_earned = product.premiums_earned.saturating_sub(refund);
+    impairment.refunded = true;
+
+    emit!(OutageRefundPaid { policy_id, owner: policy.owner, amount: refund });
+
+    Ok(())
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct OutageRefundTermsSet { pub terms: Option<OutageRefundTerms> }
+#[rialo::event] pub struct PolicyImpaired       { pub policy_id: PolicyId, pub owner: Pubkey, pub outage_bps: u64, pub refund: Ralo }
+#[rialo::event] pub struct OutageRefundPaid     { pub policy_id: PolicyId, pub owner: Pubkey, pub amount: Ralo }
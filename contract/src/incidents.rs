@@ -0,0 +1,150 @@
+// ============================================================
+//  Provider data-quality incidents
+//
+//  When a weather provider declares an outage or bad-data period,
+//  the admin flags that provider (by base URL) for the affected
+//  time span. Readings observed inside a flagged span are stored
+//  but never settle while the flag is up; once it clears they are
+//  re-evaluated in order, exactly as if they had just arrived.
+// ============================================================
+
+use rialo_sdk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::alerts::{self, AlertKind};
+use crate::config::require_unpaused;
+use crate::errors::InsuranceError;
+use crate::millimeters::Hundredths;
+use crate::normalization::{ResponseDigest, Station};
+use crate::observations::{record_check, upcoming_check_id};
+use crate::policy::PolicyId;
+use crate::{evaluation, InsuranceState};
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DataIncident {
+    pub source: String,        // provider base URL
+    pub start:  i64,
+    pub end:    Option<i64>,   // None while the incident is ongoing
+}
+
+impl DataIncident {
+    pub fn covers(&self, source: &str, at: i64) -> bool {
+        self.source == source && at >= self.start && self.end.is_none_or(|end| at < end)
+    }
+}
+
+// A reading that arrived during an incident, waiting to be re-evaluated
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct HeldObservation {
+    pub policy_id:   PolicyId,
+    pub source:      String,
+    pub station:     Station,
+    pub response:    Option<ResponseDigest>,
+    #[serde(default)]
+    pub reading:     Hundredths,    // in hundredths of the peril's unit
+    #[serde(rename = "rainfall_mm", default, skip_serializing)]
+    pub old_reading: Option<f64>,   // layout 5 and earlier only: now `reading` (migrations.rs)
+    pub observed_at: i64,
+}
+
+pub fn under_incident(state: &InsuranceState, source: &str, at: i64) -> bool {
+    state.incidents.iter().any(|i| i.covers(source, at))
+}
+
+// ── Entry point: admin flags a provider incident ─────────────
+#[rialo::instruction]
+pub async fn declare_data_incident(
+    ctx:    Context<InsuranceState>,
+    source: String,
+    start:  i64,
+    end:    Option<i64>,
+) -> RialoResult<()> {
+
+    require!(ctx.state.config.admin == *ctx.signer, InsuranceError::Unauthorized("Only the admin can declare data incidents.".into()));
+    require!(end.is_none_or(|end| end > start), InsuranceError::InvalidArgument("Incident must end after it starts.".into()));
+
+    let incident = DataIncident { source: source.clone(), start, end };
+    alerts::raise(&ctx.state.config, AlertKind::ProviderDegraded, ctx.clock.unix_timestamp, &incident).await;
+    ctx.state.incidents.push(incident);
+
+    emit!(DataIncidentDeclared { source, start, end });
+
+    Ok(())
+}
+
+// ── Entry point: admin ends an ongoing incident ──────────────
+#[rialo::instruction]
+pub async fn clear_data_incident(
+    ctx:    Context<InsuranceState>,
+    source: String,
+) -> RialoResult<()> {
+
+    let now = ctx.clock.unix_timestamp;
+
+    require!(ctx.state.config.admin == *ctx.signer, InsuranceError::Unauthorized("Only the admin can clear data incidents.".into()));
+
+    let mut cleared = false;
+    for incident in ctx.state.incidents.iter_mut().filter(|i| i.source == source && i.end.is_none()) {
+        incident.end = Some(now.max(incident.start + 1));
+        cleared = true;
+    }
+    require!(cleared, InsuranceError::InvalidState("No ongoing incident for this provider.".into()));
+
+    emit!(DataIncidentCleared { source: source.clone(), end: now });
+
+    release(&mut ctx.state, &ctx.vault, &source, now)
+}
+
+// ── Entry point: anyone re-evaluates readings once a flag lapses
+//
+//  Covers incidents declared with a fixed end time, which clear
+//  without an admin call.
+//
+#[rialo::instruction]
+pub async fn release_held_observations(
+    ctx:    Context<InsuranceState>,
+    source: String,
+) -> RialoResult<()> {
+
+    require_unpaused(&ctx.state.config)?;
+    let now = ctx.clock.unix_timestamp;
+
+    require!(!under_incident(&ctx.state, &source, now), InsuranceError::InvalidState("Provider is still under a data incident.".into()));
+
+    release(&mut ctx.state, &ctx.vault, &source, now)
+}
+
+// Settle every held reading from `source`, oldest first. Cleared while
+// paused they stay held, for release_held_observations once unpaused.
+fn release(state: &mut InsuranceState, vault: &Vault, source: &str, now: i64) -> RialoResult<()> {
+    if state.config.paused || under_incident(state, source, now) {
+        return Ok(());
+    }
+
+    let (held, kept): (Vec<_>, Vec<_>) = state.held_observations
+        .drain(..)
+        .partition(|o| o.source == source);
+    state.held_observations = kept;
+
+    for observation in held {
+        let round_id = upcoming_check_id(state);
+        let outcome = evaluation::evaluate(state, vault, observation.policy_id, round_id, observation.reading, observation.observed_at, now)?;
+        record_check(state, observation.policy_id, &observation.source, observation.station, observation.response, observation.reading, observation.observed_at, outcome);
+        let triggered = outcome.triggered();
+
+        emit!(HeldObservationReleased {
+            policy_id:   observation.policy_id,
+            reading:     observation.reading,
+            observed_at: observation.observed_at,
+            triggered,
+        });
+    }
+
+    Ok(())
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct DataIncidentDeclared    { pub source: String, pub start: i64, pub end: Option<i64> }
+#[rialo::event] pub struct DataIncidentCleared     { pub source: String, pub end: i64 }
+#[rialo::event] pub struct ObservationHeld         { pub policy_id: PolicyId, pub source: String, pub reading: Hundredths, pub observed_at: i64 }
+#[rialo::event] pub struct HeldObservationReleased { pub policy_id: PolicyId, pub reading: Hundredths, pub observed_at: i64, pub triggered: bool }
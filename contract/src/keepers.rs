@@ -0,0 +1,137 @@
+// ============================================================
+//  Keeper rewards
+//
+//  "Anyone can call check_weather_and_pay" only settles policies
+//  if someone does. The contract can pay whoever runs a live
+//  check — a single one, or each policy in a check round:
+//    • a check that pays out earns the trigger reward, flat or a
+//      share of what it paid
+//    • a check that doesn't can earn a small check fee, to cover
+//      the caller's gas
+//  Rewards come out of the policy's underwriter's free capital,
+//  never the reserve backing its policies, and are booked as
+//  check fees in its loss-adjustment expense (claims.rs).
+//
+//  Spam is held off by the check cooldown (throttle.rs): a policy
+//  can be checked, and so earn a fee, once per interval. A check
+//  that hits the cooldown fails, and earns nothing. Check fees on
+//  one policy also stop once they reach the configured share of
+//  its premium, so a long-running quiet policy can't cost its
+//  underwriter more than the premium paid for it.
+//
+//  Set by the admin, or by governance once it's enabled.
+// ============================================================
+
+use rialo_sdk::prelude::*;
+use rialo_sdk::token::transfer;
+use serde::{Deserialize, Serialize};
+
+use crate::claims::{record_lae, LaeKind};
+use crate::config::ContractConfig;
+use crate::errors::InsuranceError;
+use crate::governance::require_ungoverned;
+use crate::money::Ralo;
+use crate::policy::PolicyId;
+use crate::InsuranceState;
+
+pub const MAX_TRIGGER_REWARD_BPS: u64 = 1_000;    // 10% of the payout
+pub const MAX_FEE_CAP_BPS:        u64 = 10_000;   // the whole premium
+
+// What a check that pays out earns its caller
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TriggerReward {
+    Flat(Ralo),
+    PayoutBps(u64),
+}
+
+impl TriggerReward {
+    pub fn amount(self, paid: Ralo) -> Ralo {
+        match self {
+            TriggerReward::Flat(amount)   => amount,
+            TriggerReward::PayoutBps(bps) => paid.bps(bps),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KeeperRewards {
+    pub trigger:     TriggerReward,   // for a check that pays out
+    pub check_fee:   Ralo,            // for one that doesn't; zero for none
+    pub fee_cap_bps: u64,             // check fees on a policy stop at this share of its premium
+}
+
+// What a check on a policy earns, given what it paid and the check fees
+// the policy has already cost
+pub fn keeper_reward(rewards: &KeeperRewards, paid: Ralo, premium: Ralo, fees_so_far: Ralo) -> Ralo {
+    if !paid.is_zero() {
+        return rewards.trigger.amount(paid);
+    }
+    let cap = premium.bps(rewards.fee_cap_bps);
+    rewards.check_fee.min(cap.saturating_sub(fees_so_far))
+}
+
+// Pay the caller of a live check on `policy_id` that paid `paid`, within the
+// underwriter's free capital
+pub(crate) fn reward_keeper(
+    state:     &mut InsuranceState,
+    vault:     &Vault,
+    policy_id: PolicyId,
+    keeper:    &Pubkey,
+    paid:      Ralo,
+) -> RialoResult<()> {
+
+    let Some(rewards) = state.config.keeper_rewards else {
+        return Ok(());
+    };
+    let policy = state.policies.get(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+    let reward = keeper_reward(&rewards, paid, policy.premium_paid, policy.lae.check_fees);
+
+    let underwriter = state.underwriters.get_mut(&policy.underwriter_id).ok_or(InsuranceError::UnknownUnderwriter)?;
+    // Never dip into capital backing other policies
+    let reward = reward.min(underwriter.free_capital());
+    if reward.is_zero() {
+        return Ok(());
+    }
+    transfer(vault, keeper, reward.base_units())?;
+    underwriter.capital -= reward;
+
+    record_lae(state, policy_id, LaeKind::CheckFee, reward);
+
+    emit!(KeeperRewarded { policy_id, keeper: *keeper, triggered: !paid.is_zero(), reward });
+
+    Ok(())
+}
+
+// ── Entry point: admin sets (or stops) keeper rewards ────────
+#[rialo::instruction]
+pub async fn set_keeper_rewards(
+    ctx:     Context<InsuranceState>,
+    rewards: Option<KeeperRewards>,
+) -> RialoResult<()> {
+
+    let config = &mut ctx.state.config;
+
+    require!(config.admin == *ctx.signer, InsuranceError::Unauthorized("Only the admin can change keeper rewards.".into()));
+    require_ungoverned(config)?;
+
+    apply_keeper_rewards(config, rewards)
+}
+
+pub(crate) fn apply_keeper_rewards(config: &mut ContractConfig, rewards: Option<KeeperRewards>) -> RialoResult<()> {
+    if let Some(rewards) = rewards {
+        if let TriggerReward::PayoutBps(bps) = rewards.trigger {
+            require!(bps <= MAX_TRIGGER_REWARD_BPS, InsuranceError::InvalidArgument("Trigger reward can be at most 10% of the payout.".into()));
+        }
+        require!(rewards.fee_cap_bps <= MAX_FEE_CAP_BPS, InsuranceError::InvalidArgument("Check fees can't be capped above the premium.".into()));
+    }
+
+    config.keeper_rewards = rewards;
+
+    emit!(KeeperRewardsSet { rewards });
+
+    Ok(())
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct KeeperRewardsSet { pub rewards: Option<KeeperRewards> }
+#[rialo::event] pub struct KeeperRewarded   { pub policy_id: PolicyId, pub keeper: Pubkey, pub triggered: bool, pub reward: Ralo }
@@ -0,0 +1,228 @@
+// ============================================================
+//  API key pools
+//
+//  A single provider key caps a tenant's checks at that key's
+//  daily quota. A tenant can instead give its weather provider a
+//  pool of keys: calls go round-robin across the pool, each key
+//  is metered against its own daily quota (UTC days), and a key
+//  is skipped once it is spent. An empty pool means the
+//  provider's own key, unmetered, as before.
+//
+//  Keys never sit in contract state, where anyone reading the
+//  account could copy them: the provider config and the pool hold
+//  names of secrets in sealed storage, read just in time for each
+//  call. Rotating a key means sealing the new one under a new
+//  name and pointing the tenant at it with `rotate_api_key`; the
+//  old key can then be revoked at the provider without a gap.
+//
+//  Key health is tracked next to provider health (incidents.rs):
+//    • 429 from the provider rests the key until the UTC day ends
+//    • 401 / 403 disables it until the pool is replaced
+//  A rejected call ends the check without settling — and without
+//  failing, so the health update isn't rolled back with it; the
+//  next check draws the next healthy key.
+// ============================================================
+
+use rialo_sdk::prelude::*;
+use rialo_sdk::secrets;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::InsuranceError;
+use crate::underwriter::{tenant_mut, UnderwriterId};
+use crate::InsuranceState;
+
+const MAX_POOLED_KEYS: usize = 16;
+const SECS_PER_DAY:    i64   = 24 * 60 * 60;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PooledKey {
+    pub secret:       String,        // name of the provider API key in sealed storage
+    pub daily_quota:  u32,           // calls the provider allows per UTC day
+    pub day:          i64,           // UTC day `used_today` counts
+    pub used_today:   u32,
+    pub rested_until: Option<i64>,   // quota exhausted at the provider; skipped until then
+    pub disabled:     bool,          // rejected as invalid
+    pub last_status:  Option<u16>,   // HTTP status of the last call made with it
+}
+
+impl PooledKey {
+    pub fn new(secret: String, daily_quota: u32) -> Self {
+        PooledKey { secret, daily_quota, day: 0, used_today: 0, rested_until: None, disabled: false, last_status: None }
+    }
+
+    fn calls_left(&self, now: i64) -> u32 {
+        if now.div_euclid(SECS_PER_DAY) != self.day {
+            return self.daily_quota;
+        }
+        self.daily_quota.saturating_sub(self.used_today)
+    }
+
+    pub fn is_available(&self, now: i64) -> bool {
+        !self.disabled && self.rested_until.is_none_or(|until| now >= until) && self.calls_left(now) > 0
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct KeyPool {
+    pub keys: Vec<PooledKey>,
+    pub next: usize,            // slot the next round-robin search starts from
+}
+
+impl KeyPool {
+    // Draw the next available key and meter the call against it; returns its slot
+    pub fn draw(&mut self, now: i64) -> Option<usize> {
+        let count = self.keys.len();
+        let slot = (0..count).map(|i| (self.next + i) % count).find(|&slot| self.keys[slot].is_available(now))?;
+
+        let key = &mut self.keys[slot];
+        let today = now.div_euclid(SECS_PER_DAY);
+        if key.day != today {
+            key.day = today;
+            key.used_today = 0;
+        }
+        key.used_today += 1;
+        self.next = (slot + 1) % count;
+
+        Some(slot)
+    }
+
+    // Record the provider's answer to a call made with `slot`; false if it rejected the key
+    pub fn report(&mut self, slot: usize, status: u16, now: i64) -> bool {
+        let Some(key) = self.keys.get_mut(slot) else {
+            return true;
+        };
+        key.last_status = Some(status);
+        match status {
+            429 => {
+                key.rested_until = Some((now.div_euclid(SECS_PER_DAY) + 1) * SECS_PER_DAY);
+                false
+            }
+            401 | 403 => {
+                key.disabled = true;
+                false
+            }
+            _ => true,
+        }
+    }
+}
+
+// Key a provider call is made with, unsealed for this call only; `slot` is None
+// for the provider's own key
+pub struct KeyLease {
+    pub key:  String,
+    pub slot: Option<usize>,
+}
+
+// Take the key for a tenant's next provider call
+pub(crate) fn lease_key(state: &mut InsuranceState, underwriter_id: UnderwriterId, now: i64) -> RialoResult<KeyLease> {
+    let underwriter = state.underwriters.get_mut(&underwriter_id).ok_or(InsuranceError::UnknownUnderwriter)?;
+    if underwriter.key_pool.keys.is_empty() {
+        return Ok(KeyLease { key: underwriter.provider.api_key()?, slot: None });
+    }
+
+    let slot = underwriter.key_pool.draw(now).ok_or_else(|| InsuranceError::InvalidState("Every key in the provider key pool is spent or disabled.".into()))?;
+    Ok(KeyLease { key: secrets::get(&underwriter.key_pool.keys[slot].secret)?, slot: Some(slot) })
+}
+
+// Record how the provider answered a leased key; false if the call should be abandoned
+pub(crate) fn report_key(state: &mut InsuranceState, underwriter_id: UnderwriterId, lease: &KeyLease, status: u16, now: i64) -> bool {
+    let Some(slot) = lease.slot else {
+        return true;
+    };
+    let Some(underwriter) = state.underwriters.get_mut(&underwriter_id) else {
+        return true;
+    };
+
+    let accepted = underwriter.key_pool.report(slot, status, now);
+    if !accepted {
+        emit!(ProviderKeyRejected { underwriter_id, slot: slot as u32, status });
+    }
+    accepted
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PooledKeySpec {
+    pub secret:      String,   // name of the key in sealed storage
+    pub daily_quota: u32,
+}
+
+// The key itself is never returned
+#[derive(Serialize, Clone, Debug)]
+pub struct KeyHealth {
+    pub slot:         u32,
+    pub daily_quota:  u32,
+    pub calls_left:   u32,           // today, by the contract's own metering
+    pub rested_until: Option<i64>,
+    pub disabled:     bool,
+    pub last_status:  Option<u16>,
+}
+
+// ── Entry point: tenant replaces its provider key pool ───────
+#[rialo::instruction]
+pub async fn set_key_pool(
+    ctx:            Context<InsuranceState>,
+    underwriter_id: UnderwriterId,
+    keys:           Vec<PooledKeySpec>,
+) -> RialoResult<()> {
+
+    require!(keys.len() <= MAX_POOLED_KEYS, InsuranceError::InvalidArgument("Too many keys in the pool.".into()));
+    require!(keys.iter().all(|k| !k.secret.is_empty() && k.daily_quota > 0), InsuranceError::InvalidState("Pooled keys need a sealed key and a daily quota.".into()));
+
+    let underwriter = tenant_mut(&mut ctx.state, underwriter_id, &ctx.signer)?;
+    underwriter.key_pool = KeyPool {
+        keys: keys.into_iter().map(|k| PooledKey::new(k.secret, k.daily_quota)).collect(),
+        next: 0,
+    };
+
+    emit!(KeyPoolSet { underwriter_id, keys: underwriter.key_pool.keys.len() as u32 });
+
+    Ok(())
+}
+
+// ── Entry point: tenant points its provider at a newly sealed key
+#[rialo::instruction]
+pub async fn rotate_api_key(
+    ctx:            Context<InsuranceState>,
+    underwriter_id: UnderwriterId,
+    api_key_secret: String,
+) -> RialoResult<()> {
+
+    require!(!api_key_secret.is_empty(), InsuranceError::InvalidState("Name the sealed secret holding the new key.".into()));
+    // Fail here rather than on the next check if the secret isn't there
+    secrets::get(&api_key_secret)?;
+
+    let underwriter = tenant_mut(&mut ctx.state, underwriter_id, &ctx.signer)?;
+    underwriter.provider.api_key_secret = api_key_secret;
+
+    emit!(ApiKeyRotated { underwriter_id });
+
+    Ok(())
+}
+
+#[rialo::view]
+pub fn get_key_health(
+    ctx:            Context<InsuranceState>,
+    underwriter_id: UnderwriterId,
+) -> RialoResult<Vec<KeyHealth>> {
+
+    let now = ctx.clock.unix_timestamp;
+    let underwriter = ctx.state.underwriters.get(&underwriter_id).ok_or(InsuranceError::UnknownUnderwriter)?;
+
+    Ok(underwriter.key_pool.keys
+        .iter()
+        .enumerate()
+        .map(|(slot, key)| KeyHealth {
+            slot:         slot as u32,
+            daily_quota:  key.daily_quota,
+            calls_left:   key.calls_left(now),
+            rested_until: key.rested_until,
+            disabled:     key.disabled,
+            last_status:  key.last_status,
+        })
+        .collect())
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct KeyPoolSet          { pub underwriter_id: UnderwriterId, pub keys: u32 }
+#[rialo::event] pub struct ApiKeyRotated       { pub underwriter_id: UnderwriterId }
+#[rialo::event] pub struct ProviderKeyRejected { pub underwriter_id: UnderwriterId, pub slot: u32, pub status: u16 }
@@ -0,0 +1,253 @@
+// ============================================================
+//  Premium levies
+//
+//  Many of the markets the product targets charge statutory levies
+//  on insurance premiums (regulator fees, policyholder-protection
+//  funds, training levies). The admin configures the levies per
+//  country and each one's collecting account; an underwriter marks
+//  the country a template is sold in. When a premium clears escrow
+//  every levy is carved out of it and sent straight to its account,
+//  and the policy keeps the line items for its financials.
+//
+//  On busy days that is a transfer per levy per premium. The admin
+//  can batch them instead: levies still come off each premium and
+//  onto its line items, but accrue in a bucket per collecting
+//  account, and `sweep_fees` — anyone, at most hourly — pays the
+//  bucket out in one transfer per account and emits a `FeesSwept`
+//  snapshot reconciling what it paid against what accrued.
+// ============================================================
+
+use std::collections::BTreeMap;
+
+use rialo_sdk::prelude::*;
+use rialo_sdk::token::{balance, transfer};
+use serde::{Deserialize, Serialize};
+
+use crate::config::ContractConfig;
+use crate::errors::InsuranceError;
+use crate::governance::require_ungoverned;
+use crate::money::Ralo;
+use crate::policy::PolicyId;
+use crate::underwriter::{tenant_mut, TemplateId, UnderwriterId};
+use crate::InsuranceState;
+
+// Combined levies may never take more than this share of a premium
+const MAX_COUNTRY_LEVY_BPS: u64 = 2_000;
+
+const MIN_SWEEP_INTERVAL_SECS: i64 = 60 * 60;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Levy {
+    pub name:      String,   // e.g. "IRA policyholders' fund"
+    pub rate_bps:  u64,      // share of the cleared premium
+    pub recipient: Pubkey,   // account the levy is paid to
+}
+
+// A levy as actually charged on one policy
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct LevyLine {
+    pub name:      String,
+    pub country:   String,
+    pub rate_bps:  u64,
+    pub recipient: Pubkey,
+    pub amount:    Ralo,
+}
+
+// Levies accrued while batching is on, waiting for the next sweep
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct LevyBucket {
+    pub owed:        BTreeMap<Pubkey, Ralo>,   // per collecting account
+    pub lines:       u32,                      // levy lines accrued since the last sweep
+    pub since:       Option<i64>,              // first accrual since the last sweep
+    pub last_sweep:  i64,
+    pub swept_total: Ralo,                     // everything swept out, ever
+}
+
+impl LevyBucket {
+    pub fn accrue(&mut self, recipient: Pubkey, amount: Ralo, now: i64) {
+        *self.owed.entry(recipient).or_insert(Ralo::ZERO) += amount;
+        self.lines += 1;
+        self.since.get_or_insert(now);
+    }
+
+    pub fn total(&self) -> Ralo {
+        self.owed.values().copied().sum()
+    }
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct SweptLevy {
+    pub recipient: Pubkey,
+    pub amount:    Ralo,
+}
+
+// ISO 3166-1 alpha-2, upper case
+fn country_code(country: &str) -> RialoResult<String> {
+    let code = country.trim().to_ascii_uppercase();
+    require!(code.len() == 2 && code.bytes().all(|b| b.is_ascii_uppercase()), InsuranceError::InvalidArgument("Country must be a two-letter ISO code.".into()));
+    Ok(code)
+}
+
+// ── Entry point: admin sets the levies charged in a country ──
+#[rialo::instruction]
+pub async fn set_country_levies(
+    ctx:     Context<InsuranceState>,
+    country: String,
+    levies:  Vec<Levy>,
+) -> RialoResult<()> {
+
+    require!(ctx.state.config.admin == *ctx.signer, InsuranceError::Unauthorized("Only the admin can change levies.".into()));
+    require_ungoverned(&ctx.state.config)?;
+
+    apply_country_levies(&mut ctx.state.config, &country, levies)
+}
+
+pub(crate) fn apply_country_levies(config: &mut ContractConfig, country: &str, levies: Vec<Levy>) -> RialoResult<()> {
+    let country = country_code(country)?;
+    let total_bps: u64 = levies.iter().map(|l| l.rate_bps).sum();
+    require!(total_bps <= MAX_COUNTRY_LEVY_BPS, InsuranceError::InvalidState("Combined levies exceed the maximum share of premium.".into()));
+
+    emit!(CountryLeviesSet { country: country.clone(), levies: levies.len() as u32, total_bps });
+
+    if levies.is_empty() {
+        config.levies.remove(&country);
+    } else {
+        config.levies.insert(country, levies);
+    }
+
+    Ok(())
+}
+
+// ── Entry point: underwriter marks where a template is sold ──
+#[rialo::instruction]
+pub async fn set_template_jurisdiction(
+    ctx:            Context<InsuranceState>,
+    underwriter_id: UnderwriterId,
+    template_id:    TemplateId,
+    country:        Option<String>,
+) -> RialoResult<()> {
+
+    let country = country.as_deref().map(country_code).transpose()?;
+
+    let underwriter = tenant_mut(&mut ctx.state, underwriter_id, &ctx.signer)?;
+    let template = underwriter.templates.get_mut(&template_id).ok_or(InsuranceError::UnknownTemplate)?;
+    template.jurisdiction = country.clone();
+
+    emit!(TemplateJurisdictionSet { underwriter_id, template_id, country });
+
+    Ok(())
+}
+
+// ── Entry point: admin turns levy batching on or off ─────────
+#[rialo::instruction]
+pub async fn set_levy_batching(
+    ctx:     Context<InsuranceState>,
+    enabled: bool,
+) -> RialoResult<()> {
+
+    let config = &mut ctx.state.config;
+
+    require!(config.admin == *ctx.signer, InsuranceError::Unauthorized("Only the admin can change levy batching.".into()));
+
+    // Turning it off leaves anything accrued for the next sweep
+    config.batch_levies = enabled;
+
+    emit!(LevyBatchingSet { enabled });
+
+    Ok(())
+}
+
+// Pay out the levies on a policy's cleared premium — or accrue them, when
+// batching is on; returns the total withheld
+pub(crate) fn collect_levies(state: &mut InsuranceState, vault: &Vault, policy_id: PolicyId, now: i64) -> RialoResult<Ralo> {
+    let policy = state.policies.get(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+
+    let country = state.underwriters
+        .get(&policy.underwriter_id)
+        .and_then(|u| u.templates.get(&policy.template_id))
+        .and_then(|t| t.jurisdiction.clone());
+    let Some(country) = country else {
+        return Ok(Ralo::ZERO);
+    };
+
+    let premium = policy.premium_paid;
+    let lines: Vec<LevyLine> = state.config.levies
+        .get(&country)
+        .into_iter()
+        .flatten()
+        .map(|levy| LevyLine {
+            name:      levy.name.clone(),
+            country:   country.clone(),
+            rate_bps:  levy.rate_bps,
+            recipient: levy.recipient,
+            amount:    premium.bps(levy.rate_bps),
+        })
+        .collect();
+
+    let batched = state.config.batch_levies;
+    for line in &lines {
+        if batched {
+            state.levy_bucket.accrue(line.recipient, line.amount, now);
+        } else if !line.amount.is_zero() {
+            transfer(vault, &line.recipient, line.amount.base_units())?;
+        }
+        emit!(LevyCollected {
+            policy_id,
+            name:      line.name.clone(),
+            country:   line.country.clone(),
+            recipient: line.recipient,
+            amount:    line.amount,
+        });
+    }
+
+    let total = lines.iter().map(|l| l.amount).sum();
+    if let Some(policy) = state.policies.get_mut(&policy_id) {
+        policy.levies = lines;
+    }
+
+    Ok(total)
+}
+
+// ── Entry point: anyone sweeps accrued levies, at most hourly ─
+#[rialo::instruction]
+pub async fn sweep_fees(ctx: Context<InsuranceState>) -> RialoResult<Ralo> {
+
+    let now = ctx.clock.unix_timestamp;
+    let bucket = &mut ctx.state.levy_bucket;
+
+    require!(now - bucket.last_sweep >= MIN_SWEEP_INTERVAL_SECS, InsuranceError::InvalidState("Levies were swept too recently.".into()));
+
+    let accrued = bucket.total();
+    let mut swept = Vec::new();
+    for (&recipient, &amount) in &bucket.owed {
+        if !amount.is_zero() {
+            transfer(&ctx.vault, &recipient, amount.base_units())?;
+        }
+        swept.push(SweptLevy { recipient, amount });
+    }
+    let total: Ralo = swept.iter().map(|s| s.amount).sum();
+    require!(total == accrued, InsuranceError::InvalidState("Swept levies don't reconcile with the bucket.".into()));
+
+    let snapshot = FeesSwept {
+        at:            now,
+        since:         bucket.since,
+        last_sweep:    bucket.last_sweep,
+        lines:         bucket.lines,
+        swept,
+        total,
+        swept_to_date: bucket.swept_total + total,
+        vault_balance: Ralo(balance(&ctx.vault)),
+    };
+    *bucket = LevyBucket { last_sweep: now, swept_total: snapshot.swept_to_date, ..LevyBucket::default() };
+
+    emit!(snapshot);
+
+    Ok(total)
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct CountryLeviesSet        { pub country: String, pub levies: u32, pub total_bps: u64 }
+#[rialo::event] pub struct TemplateJurisdictionSet { pub underwriter_id: UnderwriterId, pub template_id: TemplateId, pub country: Option<String> }
+#[rialo::event] pub struct LevyCollected           { pub policy_id: PolicyId, pub name: String, pub country: String, pub recipient: Pubkey, pub amount: Ralo }
+#[rialo::event] pub struct LevyBatchingSet         { pub enabled: bool }
+#[rialo::event] pub struct FeesSwept               { pub at: i64, pub since: Option<i64>, pub last_sweep: i64, pub lines: u32, pub swept: Vec<SweptLevy>, pub total: Ralo, pub swept_to_date: Ralo, pub vault_balance: Ralo }
@@ -5,76 +5,494 @@
 //
 //  What this contract does:
 //    1. A delivery company registers a policy (location + rain threshold + payout amount)
-//    2. Anyone can call check_weather_and_pay()
-//    3. Contract fetches LIVE weather data from OpenWeatherMap
-//    4. If rainfall >= threshold → pays the delivery company automatically
+//    2. The company pays the premium into escrow — the policy activates once it clears
+//    3. Anyone can call check_weather_and_pay(policy_id)
+//    4. Contract fetches LIVE weather data from OpenWeatherMap
+//    5. If rainfall >= threshold → pays the delivery company automatically
 //
-//  Safety caps applied at setup: minimum threshold = 0.1 mm, maximum payout = 200 RALO
+//  Safety caps applied at setup: the admin's risk limits — minimum threshold, maximum
+//  payout, policies and cover per holder — or, until set, the network mode's defaults
+//  of 0.1 mm and 200 RALO on MainNet, relaxed on DevNet (see config.rs)
+//  Underwriters are independent tenants — each brings its own vault, templates,
+//  weather provider and pricing (see underwriter.rs)
 // ============================================================
 
+use std::collections::{BTreeMap, BTreeSet};
+
 use rialo_sdk::prelude::*;
-use rialo_sdk::http::{HttpRequest, Method};
-use rialo_sdk::token::transfer;
-use serde::{Deserialize, Serialize};
+
+// Pure types and arithmetic, shared with off-chain tools (core/)
+pub use rialo_weather_core::{actuarial, cache, geo, millimeters, money, normalization, streak};
+
+pub mod accumulation;
+pub mod actions;
+pub mod advances;
+#[cfg(feature = "air-quality")]
+pub mod air;
+pub mod alerts;
+pub mod anomalies;
+pub mod approvals;
+pub mod arbiter;
+pub mod attestation;
+pub mod audit;
+pub mod bordereau;
+pub mod bundles;
+pub mod claims;
+pub mod commissions;
+pub mod concentration;
+pub mod conditions;
+pub mod config;
+pub mod consensus;
+pub mod copay;
+pub mod curves;
+pub mod dashboard;
+#[cfg(feature = "degree-days")]
+pub mod degree_days;
+pub mod disputes;
+pub mod dual_control;
+pub mod errors;
+pub mod escrow;
+pub mod evaluation;
+pub mod evaluators;
+#[cfg(feature = "heat")]
+pub mod exposure;
+pub mod fleet;
+pub mod forecasts;
+pub mod fx;
+pub mod geocoding;
+pub mod health;
+pub mod governance;
+pub mod history;
+pub mod impairment;
+pub mod incidents;
+pub mod keepers;
+pub mod keys;
+pub mod levies;
+pub mod metadata;
+pub mod metrics;
+pub mod migrations;
+pub mod mints;
+pub mod no_claims;
+pub mod normals;
+pub mod notes;
+pub mod observations;
+pub mod oracle;
+pub mod outbox;
+pub mod owners;
+pub mod policy;
+pub mod pools;
+pub mod postmortems;
+pub mod product_docs;
+pub mod profiles;
+pub mod providers;
+pub mod quote;
+pub mod rate_limits;
+pub mod receipts;
+pub mod renewals;
+pub mod reserve;
+pub mod resilience;
+pub mod retention;
+pub mod routes;
+pub mod seasonal;
+pub mod self_dealing;
+#[cfg(feature = "flood")]
+pub mod river;
+pub mod settlement;
+#[cfg(feature = "sim")]
+pub mod sim;
+pub mod smoothing;
+#[cfg(feature = "storm")]
+pub mod storm;
+pub mod stress;
+pub mod subscriptions;
+pub mod throttle;
+pub mod transfers;
+pub mod transport;
+pub mod underwriter;
+pub mod whitelist;
+
+pub use accumulation::*;
+pub use actions::*;
+pub use advances::*;
+#[cfg(feature = "air-quality")]
+pub use air::*;
+pub use alerts::*;
+pub use anomalies::*;
+pub use arbiter::*;
+pub use attestation::*;
+pub use audit::*;
+pub use bordereau::*;
+pub use bundles::*;
+pub use claims::*;
+pub use commissions::*;
+pub use concentration::*;
+pub use conditions::*;
+pub use config::*;
+pub use copay::*;
+pub use dashboard::*;
+#[cfg(feature = "degree-days")]
+pub use degree_days::*;
+pub use disputes::*;
+pub use dual_control::*;
+pub use errors::*;
+pub use escrow::*;
+pub use evaluators::*;
+#[cfg(feature = "heat")]
+pub use exposure::*;
+pub use fleet::*;
+pub use forecasts::*;
+pub use fx::*;
+pub use governance::*;
+pub use history::*;
+pub use impairment::*;
+pub use incidents::*;
+pub use keepers::*;
+pub use keys::*;
+pub use levies::*;
+pub use metadata::*;
+pub use metrics::*;
+pub use migrations::*;
+pub use mints::*;
+pub use money::*;
+pub use no_claims::*;
+pub use normals::*;
+pub use notes::*;
+pub use observations::*;
+pub use outbox::*;
+pub use owners::*;
+pub use pools::*;
+pub use postmortems::*;
+pub use product_docs::*;
+pub use profiles::*;
+pub use quote::*;
+pub use rate_limits::*;
+pub use receipts::*;
+pub use renewals::*;
+pub use reserve::*;
+pub use retention::*;
+pub use routes::*;
+pub use seasonal::*;
+pub use self_dealing::*;
+#[cfg(feature = "flood")]
+pub use river::*;
+pub use smoothing::*;
+#[cfg(feature = "storm")]
+pub use storm::*;
+pub use stress::*;
+pub use subscriptions::*;
+pub use throttle::*;
+pub use transfers::*;
+pub use underwriter::*;
+pub use whitelist::*;
+use approvals::ApprovalRecord;
+use cache::WeatherCache;
+use curves::PayoutCurve;
+use geo::GeoPoint;
+use millimeters::{Hundredths, Millimeters};
+use normalization::{Location, RainIntensity, ResponseDigest, Station};
+use oracle::CallBudget;
+use policy::{PayoutSpec, Policy, PolicyId, PolicyStatus, PolicyTerms};
+use providers::{ReadingTime, WeatherProvider};
+use resilience::FetchError;
+use streak::RainStreak;
+use transport::HttpReply;
+
+// sha256 digest — evidence hashes, approval subjects
+pub type Hash = [u8; 32];
 
 // ── Storage layout ───────────────────────────────────────────
+//
+//  The SDK gives a contract a single state account, so config and
+//  policy data share it. Everything contract-wide lives in the
+//  self-contained `config` field — it borrows nothing from the maps
+//  below, so it can move to its own account once the SDK supports
+//  several.
+//
 #[rialo::state]
 pub struct InsuranceState {
-    pub delivery_company: Pubkey,    // wallet that receives the payout
-    pub location:       String,      // city name, e.g. "Nairobi"
-    pub threshold_mm:   f64,         // rainfall threshold in mm (supports fractional values)
-    pub payout_amount:  u64,         // tokens to send when triggered
-    pub is_paid_out:    bool,        // guard — can only pay once
-    pub api_key:        String,      // OpenWeatherMap API key (set at deploy)
-}
-
-// ── Helper structs for parsing the weather API response ──────
-#[derive(Deserialize)]
-struct WeatherResponse {
-    rain: Option<RainData>,
-}
-
-#[derive(Deserialize)]
-struct RainData {
-    #[serde(rename = "1h")]
-    one_hour: Option<f64>,
+    #[serde(default)]
+    pub version:             u8,                                        // layout the state was last migrated to (migrations.rs)
+    pub config:              ContractConfig,                            // admin + network mode
+    pub underwriters:        BTreeMap<UnderwriterId, Underwriter>,      // independent tenants
+    pub next_underwriter_id: UnderwriterId,
+    pub risk_pools:          BTreeMap<UnderwriterId, RiskPool>,         // tenants whose capital members share (pools.rs)
+    pub policies:            BTreeMap<PolicyId, Policy>,                // every policy ever registered
+    #[serde(default)]
+    pub policies_by_owner:   BTreeMap<Pubkey, Vec<PolicyId>>,           // each owner's policy ids, in id order (owners.rs)
+    pub next_policy_id:      PolicyId,                                  // id handed to the next setup_policy call
+    pub subscriptions:       BTreeMap<SubscriptionId, Subscription>,    // recurring cover, renewed each period (subscriptions.rs)
+    pub next_sub_id:         SubscriptionId,                            // id handed to the next create_subscription call
+    pub approvals:           BTreeMap<Hash, ApprovalRecord>,            // open multi-sig actions
+    pub manual_observations: Vec<ManualObservation>,                    // arbiter overrides, never mixed with API readings
+    pub weather_cache:       WeatherCache,                              // this hour's readings, shared across policies
+    pub incidents:           Vec<DataIncident>,                         // provider-declared bad-data periods
+    pub postmortems:         BTreeMap<PostmortemId, Postmortem>,        // filed incident write-ups, never edited
+    pub next_postmortem_id:  PostmortemId,
+    pub held_observations:   Vec<HeldObservation>,                      // readings parked until their incident clears
+    pub check_meter:         CheckMeter,                                // checks run this hour, by caller (rate_limits.rs)
+    pub levy_bucket:         LevyBucket,                                // levies accrued for the next sweep_fees (levies.rs)
+    pub last_reserve_proof:  i64,                                       // last published proof-of-reserve event
+    pub last_health_check:   i64,                                       // last health_check run, for spacing them out
+    pub metrics:             ContractMetrics,                           // running operating counters (metrics.rs)
+    pub checks:              BTreeMap<CheckId, CheckRecord>,            // settled provider readings, by check id
+    pub next_check_id:       CheckId,
+    pub archived_checks:     BTreeMap<CheckId, ArchivedCheck>,          // hashes left of archived policies' checks (retention.rs)
+    pub claims_history:      BTreeMap<PolicyId, Vec<CheckId>>,          // each policy's logged checks, in the order made
+    pub policy_notes:        Vec<PolicyNote>,                           // append-only trail of manual decisions
+    pub whitelist:           BTreeMap<Pubkey, BTreeSet<Role>>,          // roles the admin has approved each account for (whitelist.rs)
+    pub receipts:            BTreeMap<Pubkey, Vec<SettlementReceipt>>,  // every payout, by the account paid
+    pub settlements:         BTreeSet<SettlementKey>,                   // settlements PolicyTriggered was emitted for
+    pub outbox:              BTreeMap<OutboxId, OutboxRecord>,          // settlement notifications not yet delivered
+    pub next_outbox_id:      OutboxId,
+    pub evaluators:          BTreeMap<EvaluatorId, CustomEvaluator>,    // bespoke trigger services, admin-approved
+    pub next_evaluator_id:   EvaluatorId,
+    pub proposals:           BTreeMap<ProposalId, Proposal>,            // governed parameter changes, open and executed
+    pub next_proposal_id:    ProposalId,
+    pub ralo_usd:            PriceHistory,                              // recent RALO/USD prints, for USD payouts
 }
 
 // ── Entry point 1: Delivery company sets up their policy ─────
 #[rialo::instruction]
+#[allow(clippy::too_many_arguments)]
 pub async fn setup_policy(
-    ctx:            Context<InsuranceState>,
-    location:       String,
-    threshold_mm:   f64,
-    payout_amount:  u64,
-    api_key:        String,
-) -> RialoResult<()> {
+    ctx:             Context<InsuranceState>,
+    underwriter_id:  UnderwriterId,
+    template_id:     TemplateId,
+    location:        Location,             // city name, coordinates or the provider's city id
+    threshold_mm:    f64,
+    payout:          PayoutSpec,
+    curve:           PayoutCurve,          // flat, or graded by how far past the threshold a reading goes
+    coverage_secs:   i64,                  // coverage length, counted from activation
+    allow_duplicate: bool,                 // deliberately layer cover on an already-covered risk
+    on_behalf_of:    Option<Pubkey>,       // broker setup: the customer who owns and pays for the policy
+    payout_mint:     Option<MintAmount>,   // pay out in a registered mint's tokens instead of RALO (mints.rs)
+    route:           Option<RouteTerms>,   // more stops, read worst-of or best-of with `location` (routes.rs)
+    commission:      Option<Commission>,   // broker's cut of the premium, paid as it clears (commissions.rs)
+) -> RialoResult<PolicyId> {
+
+    let now = ctx.clock.unix_timestamp;
+    config::require_unpaused(&ctx.state.config)?;
+
+    // A broker signs for the customer; the customer still owns and pays for the policy
+    let owner  = on_behalf_of.unwrap_or(*ctx.signer);
+    let broker = on_behalf_of.map(|_| *ctx.signer);
+
+    let terms = PolicyTerms {
+        underwriter_id,
+        template_id,
+        location,
+        threshold:     Hundredths::from_f64(threshold_mm),
+        old_threshold: None,
+        payout,
+        payout_curve:  curve,
+        old_curve:     None,
+        coverage_secs,
+        payout_mint,
+        route,
+        commission,
+    };
+    let policy_id = write_policy(&mut ctx.state, owner, broker, terms, allow_duplicate, now).await?;
+
+    // Brokered policies pull the premium straight from the customer's allowance
+    if broker.is_some() {
+        escrow::collect_from_allowance(&mut ctx.state, &ctx.vault, policy_id, now)?;
+    }
+
+    Ok(policy_id)
+}
+
+// Quote and write a policy for `owner` on `terms`, waiting on its premium;
+// subscriptions (subscriptions.rs) write each period's policy here too
+pub(crate) async fn write_policy(
+    state:           &mut InsuranceState,
+    owner:           Pubkey,
+    broker:          Option<Pubkey>,
+    terms:           PolicyTerms,
+    allow_duplicate: bool,
+    now:             i64,
+) -> RialoResult<PolicyId> {
 
-    let state = &mut ctx.state;
+    let PolicyTerms { underwriter_id, template_id, location, threshold, payout, payout_curve: curve, coverage_secs, payout_mint, route, commission, .. } = terms;
+    whitelist::require_role(state, &owner, Role::Policyholder)?;
+    if let Some(commission) = &commission {
+        commissions::check_commission(&state.config, &owner, commission)?;
+    }
+
+    // Caps, template terms, pricing, dust floors and capital (see quote.rs)
+    let place = location.canonical();
+    let table_key = place.key();
+    let quoted = quote::quote(state, underwriter_id, template_id, &table_key, threshold, payout, &curve, coverage_secs, now)?;
+    // A route quotes each of its stops the same way (see routes.rs)
+    let route = route
+        .map(|terms| routes::quote_route(state, underwriter_id, template_id, &place, terms, threshold, &curve, coverage_secs, &quoted, now))
+        .transpose()?;
+    let Quote { peril, payout: payout_amount, premium, seasonal_bps, .. } = quoted;
+    let premium_amount = route.as_ref().map_or(premium, |(_, premium)| *premium);
+    if matches!(place, Location::Station(_)) {
+        let kind = state.underwriters.get(&underwriter_id).ok_or(InsuranceError::UnknownUnderwriter)?.provider.kind;
+        require!(kind.queries_stations(), InsuranceError::InvalidArgument("This underwriter's weather provider can't be queried by station.".into()));
+    }
+
+    // Caches and ledgers go by the policy's own key (see geocoding.rs)
+    let location = geocoding::location_key(state, underwriter_id, &place, now).await;
+    let route = match route {
+        Some((terms, _)) => {
+            let own = routes::RouteStop::new(place.clone(), location.clone());
+            Some(routes::write_route(state, underwriter_id, own, terms, now).await)
+        }
+        None => None,
+    };
+
+    // Catch double-buying by mistake; layering has to be asked for explicitly
+    if !allow_duplicate {
+        let duplicate = state.policies
+            .values()
+            .any(|p| p.covers_same_risk(&owner, &location, peril, now));
+        require!(!duplicate, InsuranceError::InvalidState("A live policy already covers this location and peril. Set allow_duplicate to layer cover.".into()));
+    }
+    concentration::check_beneficiary_cap(state, &owner, payout_amount)?;
+    concentration::check_policy_limit(state, &owner)?;
+    let conflicted = self_dealing::vet(state, underwriter_id, &owner)?;
+    // The underwriter's pool of the mint must hold the payout, untouched by other policies
+    if let Some(requested) = &payout_mint {
+        mints::check_reserve(state, underwriter_id, requested)?;
+    }
 
-    require!(!state.is_paid_out, "Policy already triggered.");
+    // Every check has passed: nothing above has spent an override or a
+    // reservation, so a refused policy (fleet.rs) leaves the state as it
+    // found it, bar the geocodes it cached
+    if let Some(key) = conflicted {
+        self_dealing::record(state, underwriter_id, state.next_policy_id, &owner, key);
+    }
+    let payout_mint = payout_mint.map(|requested| mints::reserve(state, underwriter_id, requested)).transpose()?;
 
-    // Enforce sensible caps to avoid bankrupting the contract
-    require!(threshold_mm >= 0.1, "Threshold must be at least 0.1 mm.");
-    require!(payout_amount <= 200, "Payout must be at most 200 RALO tokens.");
+    let underwriter = state.underwriters.get_mut(&underwriter_id).ok_or(InsuranceError::UnknownUnderwriter)?;
+    let template = underwriter.templates.get(&template_id).ok_or(InsuranceError::UnknownTemplate)?;
+    let normals = underwriter.rain_normals.get(&table_key).copied().filter(|_| template.rain_normal);
+    let utc_offset = underwriter.utc_offsets.get(&table_key).copied().unwrap_or(0);
+    underwriter.reserved += payout_amount;
 
-    state.delivery_company = *ctx.signer;
-    state.location      = location;
-    state.threshold_mm  = threshold_mm;
-    state.payout_amount = payout_amount;
-    state.api_key       = api_key;
-    state.is_paid_out   = false;
+    let policy_id = state.next_policy_id;
+    state.next_policy_id += 1;
+
+    let mut policy = Policy::new(
+        underwriter_id,
+        template_id,
+        owner,
+        location,
+        threshold,
+        payout_amount,
+        premium_amount,
+    );
+    policy.place          = place;
+    policy.broker         = broker.or(commission.map(|c| c.broker));
+    policy.commission     = commission;
+    policy.payout_mint    = payout_mint;
+    policy.peril          = peril;
+    policy.comparison     = template.comparison;
+    policy.seasonal_bps   = seasonal_bps;
+    policy.ceded_bps      = underwriter.ceded_bps;
+    policy.created_at     = now;
+    policy.coverage_secs  = coverage_secs;
+    policy.min_check_secs = template.check_interval.unwrap_or(throttle::DEFAULT_MIN_CHECK_SECS);
+    policy.streak         = template.continuous_hours.map(RainStreak::new);
+    policy.accumulation   = template.rolling_hours.map(|h| accumulation::RollingRain::new(h).with_max_age(template.rolling_max_age));
+    policy.evaluator      = template.evaluator.map(evaluators::EvaluatorCover::new);
+    policy.utc_offset     = utc_offset;
+    policy.normal         = normals.map(|n| normals::NormalCover { utc_offset, ..normals::NormalCover::new(n) });
+    policy.graded         = (!curve.is_flat()).then(|| curves::GradedCover::new(curve));
+    policy.route          = route;
+    policy.bundle         = template.bundle.clone().map(|terms| {
+        let own = bundles::BundledPeril::new(peril, template.comparison, threshold, terms.sub_limit_bps);
+        bundles::BundleCover::new(own, terms.riders)
+    });
+    policy.compound       = template.compound.clone();
+    policy.forecast       = template.forecast_min_bps.map(forecasts::ForecastCover::new);
+    policy.smoothing      = template.smoothing.map(smoothing::SmoothedIndex::new);
+    policy.attestation    = template.attestation_required.then(attestation::AttestationCover::default);
+    policy.dispute        = template.dispute_window_secs.map(disputes::DisputeCover::new);
+    policy.no_claim       = template.no_claim_bonus_bps.map(no_claims::NoClaimBonus::new);
+    #[cfg(feature = "storm")]
+    {
+        policy.storm = template.storm_tiers.clone().map(storm::StormCover::new);
+    }
+    #[cfg(feature = "air-quality")]
+    {
+        policy.air = template.air_quality.map(air::AirCover::new);
+    }
+    #[cfg(feature = "heat")]
+    {
+        policy.exposure = template.exposure.map(exposure::ExposureCover::new);
+    }
+    #[cfg(feature = "degree-days")]
+    {
+        policy.degree_days = template.degree_days.map(degree_days::DegreeDayCover::new);
+    }
+    // Point perils start at the pinned coordinates, or the provider's geocode
+    // of the city. A failed lookup leaves the point for the owner to set.
+    if policy.needs_insured_point() {
+        policy.insured_point = match &policy.place {
+            Location::Point(point) => Some(*point),
+            Location::City(name)   => geocoding::resolve(state, underwriter_id, name, now, false).await.ok(),
+            Location::CityId(_) | Location::Station(_) => None,
+        };
+    }
 
     emit!(PolicyCreated {
-        delivery_company: state.delivery_company,
-        location:     state.location.clone(),
-        threshold_mm: state.threshold_mm,
-        payout:       state.payout_amount,
+        policy_id,
+        underwriter_id,
+        delivery_company: policy.owner,
+        broker:       policy.broker,
+        location:     policy.location.clone(),
+        threshold:    policy.threshold,
+        payout:       policy.payout_amount,
+        premium:      policy.premium_amount,
     });
 
+    state.policies.insert(policy_id, policy);
+    owners::index(state, owner, policy_id);
+
+    Ok(policy_id)
+}
+
+// ── Entry point 1b: Owner fixes the insured point ────────────
+//
+//  Storm-track, air-quality and heat-hours cover are read at
+//  coordinates, not at the city name — they can't be paid for
+//  until this is set.
+//
+#[rialo::instruction]
+pub async fn set_insured_point(
+    ctx:       Context<InsuranceState>,
+    policy_id: PolicyId,
+    lat_e6:    i32,
+    lon_e6:    i32,
+) -> RialoResult<()> {
+
+    let point = GeoPoint::new(lat_e6, lon_e6).map_err(InsuranceError::from)?;
+    let policy = ctx.state.policies.get_mut(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+
+    require!(policy.owner == *ctx.signer, InsuranceError::Unauthorized("Only the policy owner can set the insured point.".into()));
+    require!(policy.needs_insured_point(), InsuranceError::InvalidState("Policy is not written on an insured point.".into()));
+    // Moving the point once cover is live would let a buyer chase the hazard
+    require!(policy.status == PolicyStatus::PendingPayment, InsuranceError::InvalidState("Insured point is fixed once the policy activates.".into()));
+    policy.insured_point = Some(point);
+
+    emit!(InsuredPointSet { policy_id, point });
+
     Ok(())
 }
 
+// One policy from the registry, full terms and state
+#[rialo::view]
+pub fn get_policy(
+    ctx:       Context<InsuranceState>,
+    policy_id: PolicyId,
+) -> RialoResult<Policy> {
+
+    Ok(ctx.state.policies.get(&policy_id).ok_or(InsuranceError::UnknownPolicy)?.clone())
+}
+
 // ── Entry point 2: Check weather and pay if threshold is met ─
 //
 //  This is the key Rialo feature:
@@ -83,61 +501,520 @@ pub async fn setup_policy(
 //
 #[rialo::instruction]
 pub async fn check_weather_and_pay(
-    ctx: Context<InsuranceState>,
+    ctx:       Context<InsuranceState>,
+    policy_id: PolicyId,
 ) -> RialoResult<()> {
 
-    let state = &mut ctx.state;
+    config::require_unpaused(&ctx.state.config)?;
+    let policy = ctx.state.policies.get(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
 
-    // Guard: don't pay twice
-    require!(!state.is_paid_out, "Policy already paid out.");
+    // Guard: only live coverage can trigger, and never twice
+    require!(policy.status != PolicyStatus::PaidOut, InsuranceError::AlreadyPaidOut);
+    require!(policy.status == PolicyStatus::Active, InsuranceError::PremiumNotCleared);
+    require!(policy.is_weather_cover(), InsuranceError::InvalidState("Policy does not settle on weather readings.".into()));
 
-    // ── Step 1: Build the OpenWeatherMap API URL ──────────────
-    let url = format!(
-        "https://api.openweathermap.org/data/2.5/weather?q={}&appid={}&units=metric",
-        state.location,
-        state.api_key,
-    );
+    let now = ctx.clock.unix_timestamp;
+    require!(policy.is_covered_at(now), InsuranceError::PolicyExpired);
+    policy.check_throttle(now)?;
+
+    let paid_before = policy.paid_out;
+    rate_limits::admit(&mut ctx.state, &ctx.signer, now)?;
+    let mut budget = CallBudget::per_instruction();
+    let checked = check_current(&mut ctx.state, &ctx.vault, policy_id, now, &mut budget, None).await?;
+    require!(checked, InsuranceError::CallBudgetExhausted);
+    throttle::stamp(&mut ctx.state, policy_id, now);
+
+    // The caller earns the keeper reward, if one is set (see keepers.rs)
+    let paid = paid_since(&ctx.state, policy_id, paid_before);
+    keepers::reward_keeper(&mut ctx.state, &ctx.vault, policy_id, &ctx.signer, paid)?;
+
+    Ok(())
+}
+
+// ── Entry point 2b: Check every live policy in one round ─────
+//
+//  Walks live policies in id order. Cached readings are free; each
+//  cache miss spends one provider call from the instruction budget
+//  (see oracle.rs). The round stops after `max_items` policies, once
+//  the caller reaches a check rate limit (rate_limits.rs), or once
+//  the next one needs a call the budget cannot cover, and
+//  returns the last policy it finished — pass that back as
+//  `start_after` to carry on. Ids only grow, so a resumed round
+//  never revisits a policy.
+//
+#[rialo::instruction]
+pub async fn check_all_policies(
+    ctx:         Context<InsuranceState>,
+    start_after: Option<PolicyId>,
+    max_items:   u32,
+) -> RialoResult<Option<PolicyId>> {
 
-    // ── Step 2: Make the HTTP call — native Rialo feature ─────
-    //    On any other chain this would need Chainlink, an oracle
-    //    contract, a keeper, and a relay. Here it's one line.
-    let response = HttpRequest::new(Method::GET, &url)
-        .send()
-        .await?;
-
-    // ── Step 3: Parse the response ────────────────────────────
-    let weather: WeatherResponse = response.json()?;
-
-    let rainfall_mm = weather
-        .rain
-        .and_then(|r| r.one_hour)
-        .unwrap_or(0.0);
-
-    emit!(WeatherChecked {
-        location:    state.location.clone(),
-        rainfall_mm: rainfall_mm,
-        threshold:   state.threshold_mm,
+    config::require_unpaused(&ctx.state.config)?;
+    require!(max_items > 0, InsuranceError::InvalidArgument("max_items must be at least one.".into()));
+
+    let now = ctx.clock.unix_timestamp;
+    let mut budget = CallBudget::per_instruction();
+
+    let due: Vec<PolicyId> = policy::policies_after(&ctx.state.policies, start_after)
+        .filter(|(_, p)| p.status == PolicyStatus::Active && p.is_weather_cover() && p.is_covered_at(now))
+        .filter(|(_, p)| p.next_check_at(now).is_none())
+        .map(|(id, _)| *id)
+        .collect();
+
+    let mut checked = 0;
+    let mut last = start_after;
+    for policy_id in due {
+        let paid_before = ctx.state.policies.get(&policy_id).map_or(Ralo::ZERO, |p| p.paid_out);
+        if checked == max_items
+            || rate_limits::admit(&mut ctx.state, &ctx.signer, now).is_err()
+            || !check_current(&mut ctx.state, &ctx.vault, policy_id, now, &mut budget, None).await? {
+            emit!(CheckRoundPaused { checked, resume_after: last });
+            return Ok(last);
+        }
+        throttle::stamp(&mut ctx.state, policy_id, now);
+        let paid = paid_since(&ctx.state, policy_id, paid_before);
+        keepers::reward_keeper(&mut ctx.state, &ctx.vault, policy_id, &ctx.signer, paid)?;
+        checked += 1;
+        last = Some(policy_id);
+    }
+
+    emit!(CheckRoundCompleted { checked });
+
+    Ok(None)
+}
+
+// ── Entry point 2c: Check every live policy in one location ──
+//
+//  Ten policies in Nairobi read the same weather, so a keeper can
+//  check them together: the first policy on each provider pays for
+//  the call and the rest read it from the cache (cache.rs). One
+//  WeatherChecked goes out per provider reading, and each policy's
+//  outcome in its own CheckRecorded. Policies match on the place
+//  they were written on, whatever geohash their key carries. Like
+//  a round, it pauses on a rate limit or a spent budget and
+//  returns the last policy it finished, to pass back as
+//  `start_after`.
+//
+#[rialo::instruction]
+pub async fn check_location(
+    ctx:         Context<InsuranceState>,
+    location:    Location,
+    start_after: Option<PolicyId>,
+) -> RialoResult<Option<PolicyId>> {
+
+    config::require_unpaused(&ctx.state.config)?;
+
+    let now = ctx.clock.unix_timestamp;
+    let key = location.canonical().key();
+    let mut budget = CallBudget::per_instruction();
+
+    let due: Vec<PolicyId> = policy::policies_after(&ctx.state.policies, start_after)
+        .filter(|(_, p)| p.place.key() == key)
+        .filter(|(_, p)| p.status == PolicyStatus::Active && p.is_weather_cover() && p.is_covered_at(now))
+        .filter(|(_, p)| p.next_check_at(now).is_none())
+        .map(|(id, _)| *id)
+        .collect();
+    require!(start_after.is_some() || !due.is_empty(), InsuranceError::InvalidState("No live policy in that location is due a check.".into()));
+
+    let mut announced = BTreeSet::new();
+    let mut checked = 0;
+    let mut last = start_after;
+    for policy_id in due {
+        let paid_before = ctx.state.policies.get(&policy_id).map_or(Ralo::ZERO, |p| p.paid_out);
+        if rate_limits::admit(&mut ctx.state, &ctx.signer, now).is_err()
+            || !check_current(&mut ctx.state, &ctx.vault, policy_id, now, &mut budget, Some(&mut announced)).await? {
+            emit!(CheckRoundPaused { checked, resume_after: last });
+            return Ok(last);
+        }
+        throttle::stamp(&mut ctx.state, policy_id, now);
+        let paid = paid_since(&ctx.state, policy_id, paid_before);
+        keepers::reward_keeper(&mut ctx.state, &ctx.vault, policy_id, &ctx.signer, paid)?;
+        checked += 1;
+        last = Some(policy_id);
+    }
+
+    emit!(CheckRoundCompleted { checked });
+
+    Ok(None)
+}
+
+// Read this hour's weather for a live policy and settle on it.
+// Returns false, untouched, if a provider call is needed but the budget is spent.
+// A location check passes the sources it has announced a reading for already.
+async fn check_current(
+    state:     &mut InsuranceState,
+    vault:     &Vault,
+    policy_id: PolicyId,
+    now:       i64,
+    budget:    &mut CallBudget,
+    announced: Option<&mut BTreeSet<String>>,
+) -> RialoResult<bool> {
+
+    let policy = state.policies.get(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+    // A route reads every stop and settles on the decisive one (see routes.rs)
+    if policy.route.is_some() {
+        return routes::check_route(state, vault, policy_id, ReadingTime::Current, now, now, budget).await;
+    }
+    let underwriter = state.underwriters.get(&policy.underwriter_id).ok_or(InsuranceError::UnknownUnderwriter)?;
+
+    let underwriter_id = policy.underwriter_id;
+    let template_id    = policy.template_id;
+    let location       = policy.location.clone();
+    let place          = policy.place.clone();
+    let peril          = policy.peril;
+    let threshold      = policy.threshold;
+    let source         = underwriter.provider.base_url_for(state.config.network_mode).to_string();
+
+    // ── Step 1: Reuse this hour's reading for the city if another
+    //    policy already paid for it (see cache.rs)
+    let cached = state.weather_cache.get(&source, &location, now);
+    let from_cache = cached.is_some();
+
+    // A corroborated check reads every source or none (see consensus.rs)
+    let corroborating = consensus::calls_needed(state, underwriter_id, &location, now);
+    if budget.remaining() < corroborating + u32::from(!from_cache) {
+        return Ok(false);
+    }
+
+    let (observation, call_cost) = match cached {
+        Some(observation) => (observation, Ralo::ZERO),
+        None => {
+            // ── Step 2: Build the provider's request URLs ─────────
+            //    DevNet deployments hit the provider's sandbox instead;
+            //    MainNet ones may have a mirror to fall back on; tenants
+            //    with a key pool rotate keys (see keys.rs)
+            let lease = keys::lease_key(state, underwriter_id, now)?;
+            let underwriter = state.underwriters.get(&underwriter_id).ok_or(InsuranceError::UnknownUnderwriter)?;
+            let provider = &underwriter.provider;
+            let urls: Vec<String> = provider.endpoints_for(state.config.network_mode)
+                .into_iter()
+                .map(|base| provider.kind.build_request(base, &lease.key, &place, ReadingTime::Current))
+                .collect();
+            let headers = provider_headers(underwriter, template_id)?;
+            let (kind, cost_per_call) = (provider.kind, provider.cost_per_call);
+
+            // ── Step 3: Make the HTTP call — native Rialo feature ─
+            //    On any other chain this would need Chainlink, an oracle
+            //    contract, a keeper, and a relay. Here it's one line,
+            //    retried if the provider stumbles (see resilience.rs).
+            //    The budget check above leaves room for the first try.
+            let fetched = resilience::fetch_with_retry(&urls, &headers, budget, corroborating).await;
+            if let Some(status) = fetched.status() {
+                if !keys::report_key(state, underwriter_id, &lease, status, now) {
+                    return Ok(true);
+                }
+            }
+            let call_cost = cost_per_call.times(fetched.attempts as u64);
+
+            // ── Step 4: Parse the response ────────────────────────
+            let parsed = fetched.result.and_then(|response| {
+                kind.parse_observation(ReadingTime::Current, response.body())
+                    .map(|observation| observation.with_response(response.digest()))
+                    .map_err(|_| FetchError::ParseError)
+            });
+            let observation = match parsed {
+                Ok(observation) => observation,
+                Err(reason) => {
+                    resilience::report_failure(state, policy_id, &source, fetched.attempts, reason);
+                    claims::record_lae(state, policy_id, LaeKind::ProviderCost, call_cost);
+                    return Ok(true);
+                }
+            };
+            state.weather_cache.insert(&source, &location, now, observation.clone());
+
+            (observation, call_cost)
+        }
+    };
+    let reading = observation.metric(peril).ok_or_else(|| InsuranceError::BadResponse("Weather response has no reading for this peril.".into()))?;
+
+    if announced.is_none_or(|sources| sources.insert(source.clone())) {
+        emit!(WeatherChecked {
+            policy_id,
+            location:  location.clone(),
+            rainfall_mm: observation.rainfall_mm,
+            intensity: normalization::rain_intensity(observation.rainfall_mm),
+            reading,
+            threshold,
+            cached:    from_cache,
+            response:  observation.response,
+        });
+    }
+
+    // ── Step 4b: Take the median with the tenant's other sources, if any
+    let own = consensus::SourceReading { source: source.clone(), reading };
+    let Some(reading) = consensus::corroborate(state, underwriter_id, policy_id, &place, peril, own, now, budget).await? else {
+        claims::record_lae(state, policy_id, LaeKind::ProviderCost, call_cost);
+        return Ok(true);
+    };
+
+    // ── Step 4c: A reading far past the location's record needs a
+    //    second source to see it too (see anomalies.rs)
+    if !anomalies::screen(state, policy_id, &source, reading, ReadingTime::Current, now, budget).await? {
+        claims::record_lae(state, policy_id, LaeKind::ProviderCost, call_cost);
+        return Ok(true);
+    }
+
+    // ── Step 4d: A USD payout this reading may trigger converts at
+    //    a fresh RALO/USD spot (see fx.rs)
+    fx::refresh_spot(state, policy_id, reading, now, budget).await;
+
+    // ── Step 5: Evaluate the condition ────────────────────────
+    //    A compound one reads the rest of the response too (see conditions.rs)
+    if conditions::is_compound(state, policy_id) {
+        conditions::settle_observation(state, vault, policy_id, &source, &observation, reading, now, now, call_cost)?;
+        return Ok(true);
+    }
+
+    // ── Step 6: Pay out — automatically (see settlement.rs) ───
+    evaluate_reading(state, vault, policy_id, &source, observation.station.clone(), observation.response, reading, now, now, call_cost)?;
+
+    // ── Step 6b: Settle any bundled perils on the same response
+    bundles::settle_riders(state, vault, policy_id, &source, &observation, now, now)?;
+
+    Ok(true)
+}
+
+// ── Entry point 3: Settle on an hour already past ────────────
+//
+//  For checks a keeper missed: reads the provider's hourly history
+//  for a moment inside the coverage window, no further back than
+//  the configured lookback.
+//
+#[rialo::instruction]
+pub async fn check_weather_historical(
+    ctx:       Context<InsuranceState>,
+    policy_id: PolicyId,
+    at:        i64,
+) -> RialoResult<()> {
+
+    config::require_unpaused(&ctx.state.config)?;
+    let now = ctx.clock.unix_timestamp;
+    let policy = ctx.state.policies.get(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+
+    require!(policy.status == PolicyStatus::Active, InsuranceError::PolicyNotActive);
+    require!(policy.is_weather_cover(), InsuranceError::InvalidState("Policy does not settle on weather readings.".into()));
+    require!(at <= now, InsuranceError::InvalidState("Historical check cannot look into the future.".into()));
+    require!(now - at <= ctx.state.config.max_lookback_secs, InsuranceError::InvalidState("Historical check looks back too far.".into()));
+    require!(policy.is_covered_at(at), InsuranceError::InvalidState("Requested time is outside the coverage window.".into()));
+    require!(consensus::single_source_allowed(&ctx.state.config, policy), InsuranceError::InvalidState("Policy settles on live readings from two providers only.".into()));
+    rate_limits::admit(&mut ctx.state, &ctx.signer, now)?;
+
+    check_historical(&mut ctx.state, &ctx.vault, policy_id, at, now, &mut CallBudget::per_instruction()).await?;
+
+    Ok(())
+}
+
+// ── Entry point 4: Close out a policy once coverage has ended ─
+//
+//  Permissionless, so no policy lingers Active forever. Within the
+//  finalize window a last historical check reads the final covered
+//  hour and may still pay — for rainfall-vs-normal cover, every
+//  hour of the last month a keeper missed (normals.rs), over as
+//  many calls as that takes. After that the policy simply expires.
+//  Either way an untriggered policy ends Expired and its reserve
+//  goes back to the underwriter's free capital.
+//
+#[rialo::instruction]
+pub async fn finalize(
+    ctx:       Context<InsuranceState>,
+    policy_id: PolicyId,
+) -> RialoResult<()> {
+
+    // Expiring now would forfeit the final check the pause is holding back
+    config::require_unpaused(&ctx.state.config)?;
+    let now = ctx.clock.unix_timestamp;
+    let policy = ctx.state.policies.get(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+
+    require!(policy.status == PolicyStatus::Active, InsuranceError::PolicyNotActive);
+    let end = policy.coverage_end().ok_or_else(|| InsuranceError::InvalidState("Policy has no coverage window.".into()))?;
+    require!(now >= end, InsuranceError::InvalidState("Coverage has not ended yet.".into()));
+
+    // Cover that needs two providers can't take a single-provider final check
+    let in_finalize_window = now - end <= ctx.state.config.finalize_secs
+        && consensus::single_source_allowed(&ctx.state.config, policy);
+    if in_finalize_window && policy.normal.is_some() {
+        // Accumulating cover reads every hour a keeper missed before it can expire
+        if normals::backfill(&mut ctx.state, &ctx.vault, policy_id, now).await?.remaining > 0 {
+            return Ok(());
+        }
+    } else if in_finalize_window && policy.is_weather_cover()
+        // A last check the provider refused leaves the policy open for another try
+        && !check_historical(&mut ctx.state, &ctx.vault, policy_id, end - 1, now, &mut CallBudget::per_instruction()).await? {
+        return Ok(());
+    }
+
+    // An incident may be holding a reading that could still pay out
+    let held = ctx.state.held_observations.iter().any(|o| o.policy_id == policy_id);
+    require!(!held, InsuranceError::InvalidState("Policy has readings held by a data incident.".into()));
+
+    let policy = ctx.state.policies.get_mut(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+    if policy.status != PolicyStatus::Active {
+        return Ok(());
+    }
+    policy.status = PolicyStatus::Expired;
+
+    let released = policy.coverage_remaining();
+    if let Some(underwriter) = ctx.state.underwriters.get_mut(&policy.underwriter_id) {
+        underwriter.reserved = underwriter.reserved.saturating_sub(released);
+    }
+
+    emit!(PolicyExpired { policy_id, owner: policy.owner, coverage_end: end, released });
+    mints::release(&mut ctx.state, policy_id);
+
+    // Cover the provider couldn't settle for long enough earns a refund
+    impairment::assess(&mut ctx.state, policy_id);
+    // Cover that never paid may hand part of its premium back
+    no_claims::assess(&mut ctx.state, policy_id);
+
+    Ok(())
+}
+
+// Read the provider's history for `at` and settle on it, spending calls from
+// `budget`. Returns false, untouched, if the budget couldn't cover a call or
+// the provider rejected the key, and false if no usable answer came back.
+pub(crate) async fn check_historical(
+    state:     &mut InsuranceState,
+    vault:     &Vault,
+    policy_id: PolicyId,
+    at:        i64,
+    now:       i64,
+    budget:    &mut CallBudget,
+) -> RialoResult<bool> {
+
+    if budget.remaining() == 0 {
+        return Ok(false);
+    }
+    let policy = state.policies.get(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+    if policy.route.is_some() {
+        return routes::check_route(state, vault, policy_id, ReadingTime::Hour(at), at, now, budget).await;
+    }
+    let underwriter_id = policy.underwriter_id;
+    let lease = keys::lease_key(state, underwriter_id, now)?;
+
+    let policy = state.policies.get(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+    let underwriter = state.underwriters.get(&underwriter_id).ok_or(InsuranceError::UnknownUnderwriter)?;
+
+    let provider = &underwriter.provider;
+    let mode = state.config.network_mode;
+    let source = provider.base_url_for(mode).to_string();
+    let urls: Vec<String> = provider.endpoints_for(mode)
+        .into_iter()
+        .map(|base| provider.kind.build_request(base, &lease.key, &policy.place, ReadingTime::Hour(at)))
+        .collect();
+    let headers = provider_headers(underwriter, policy.template_id)?;
+    let (kind, cost_per_call) = (provider.kind, provider.cost_per_call);
+    let (peril, threshold) = (policy.peril, policy.threshold);
+
+    let fetched = resilience::fetch_with_retry(&urls, &headers, budget, 0).await;
+    if let Some(status) = fetched.status() {
+        if !keys::report_key(state, underwriter_id, &lease, status, now) {
+            return Ok(false);
+        }
+    }
+    let call_cost = cost_per_call.times(fetched.attempts as u64);
+    let parsed = fetched.result.and_then(|response| {
+        kind.parse_observation(ReadingTime::Hour(at), response.body())
+            .map(|observation| observation.with_response(response.digest()))
+            .map_err(|_| FetchError::ParseError)
+    });
+    let observation = match parsed {
+        Ok(observation) => observation,
+        Err(reason) => {
+            resilience::report_failure(state, policy_id, &source, fetched.attempts, reason);
+            claims::record_lae(state, policy_id, LaeKind::ProviderCost, call_cost);
+            return Ok(false);
+        }
+    };
+    let reading = observation.metric(peril).ok_or_else(|| InsuranceError::BadResponse("Weather history has no reading for this peril.".into()))?;
+
+    emit!(HistoricalWeatherChecked {
+        policy_id,
+        at,
+        rainfall_mm: observation.rainfall_mm,
+        intensity: normalization::rain_intensity(observation.rainfall_mm),
+        reading,
+        threshold,
+        response:  observation.response,
     });
 
-    // ── Step 4: Evaluate the condition ────────────────────────
-    if rainfall_mm >= state.threshold_mm {
+    if !anomalies::screen(state, policy_id, &source, reading, ReadingTime::Hour(at), at, budget).await? {
+        claims::record_lae(state, policy_id, LaeKind::ProviderCost, call_cost);
+        return Ok(true);
+    }
+    fx::refresh_spot(state, policy_id, reading, now, budget).await;
+    if conditions::is_compound(state, policy_id) {
+        conditions::settle_observation(state, vault, policy_id, &source, &observation, reading, at, now, call_cost)?;
+        return Ok(true);
+    }
+    evaluate_reading(state, vault, policy_id, &source, observation.station.clone(), observation.response, reading, at, now, call_cost)?;
+    bundles::settle_riders(state, vault, policy_id, &source, &observation, at, now)?;
 
-        // ── Step 5: Pay out — automatically ───────────────────
-        transfer(&ctx.vault, &state.delivery_company, state.payout_amount)?;
+    Ok(true)
+}
 
-        state.is_paid_out = true;
+// Licensed feeds: the product's header profile, secrets read just in time
+pub(crate) fn provider_headers(underwriter: &Underwriter, template_id: TemplateId) -> RialoResult<Vec<(String, String)>> {
+    match underwriter.templates.get(&template_id).and_then(|t| t.provider_profile) {
+        Some(profile_id) => underwriter.profiles
+            .get(&profile_id)
+            .ok_or_else(|| InsuranceError::UnknownRecord("Unknown provider profile.".into()))?
+            .resolve_headers(),
+        None => Ok(Vec::new()),
+    }
+}
 
-        emit!(PolicyTriggered {
-            delivery_company: state.delivery_company,
-            rainfall_mm: rainfall_mm,
-            payout:      state.payout_amount,
+// What a policy has been paid beyond `before`
+fn paid_since(state: &InsuranceState, policy_id: PolicyId, before: Ralo) -> Ralo {
+    state.policies.get(&policy_id).map_or(Ralo::ZERO, |p| p.paid_out.saturating_sub(before))
+}
+
+pub(crate) async fn fetch(url: &str, headers: &[(String, String)]) -> RialoResult<HttpReply> {
+    transport::get(url, headers, None).await
+}
+
+// Book the call, then settle the reading — or park it while its provider is under an incident
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn evaluate_reading(
+    state:       &mut InsuranceState,
+    vault:       &Vault,
+    policy_id:   PolicyId,
+    source:      &str,
+    station:     Station,
+    response:    Option<ResponseDigest>,
+    reading:     Hundredths,
+    observed_at: i64,
+    now:         i64,
+    call_cost:   Ralo,
+) -> RialoResult<()> {
+
+    // Every uncached check costs the book an API call, triggered or not
+    claims::record_lae(state, policy_id, LaeKind::ProviderCost, call_cost);
+
+    if incidents::under_incident(state, source, observed_at) {
+        state.held_observations.push(HeldObservation {
+            policy_id,
+            source: source.to_string(),
+            station,
+            response,
+            reading,
+            old_reading: None,
+            observed_at,
         });
+        emit!(ObservationHeld { policy_id, source: source.to_string(), reading, observed_at });
+        return Ok(());
+    }
+
+    let round_id = observations::upcoming_check_id(state);
+    let outcome = evaluation::evaluate(state, vault, policy_id, round_id, reading, observed_at, now)?;
+    observations::record_check(state, policy_id, source, station, response, reading, observed_at, outcome);
 
-    } else {
+    if outcome == evaluation::Evaluation::NotMet {
         // Condition not met — no action, no cost, no fuss
+        let threshold = state.policies.get(&policy_id).map_or(Hundredths::ZERO, |p| p.threshold);
         emit!(ConditionNotMet {
-            rainfall_mm: rainfall_mm,
-            threshold:   state.threshold_mm,
+            policy_id,
+            rainfall_mm: reading,
+            threshold,
         });
     }
 
@@ -145,7 +1022,12 @@ pub async fn check_weather_and_pay(
 }
 
 // ── Events (visible in block explorer & frontend) ────────────
-#[rialo::event] pub struct PolicyCreated   { pub delivery_company: Pubkey, pub location: String, pub threshold_mm: f64, pub payout: u64 }
-#[rialo::event] pub struct WeatherChecked  { pub location: String, pub rainfall_mm: f64, pub threshold: f64 }
-#[rialo::event] pub struct PolicyTriggered { pub delivery_company: Pubkey, pub rainfall_mm: f64, pub payout: u64 }
-#[rialo::event] pub struct ConditionNotMet { pub rainfall_mm: f64, pub threshold: f64 }
+#[rialo::event] pub struct PolicyCreated            { pub policy_id: PolicyId, pub underwriter_id: UnderwriterId, pub delivery_company: Pubkey, pub broker: Option<Pubkey>, pub location: String, pub threshold: Hundredths, pub payout: Ralo, pub premium: Ralo }
+#[rialo::event] pub struct InsuredPointSet          { pub policy_id: PolicyId, pub point: GeoPoint }
+#[rialo::event] pub struct WeatherChecked           { pub policy_id: PolicyId, pub location: String, pub rainfall_mm: Millimeters, pub intensity: RainIntensity, pub reading: Hundredths, pub threshold: Hundredths, pub cached: bool, pub response: Option<ResponseDigest> }
+#[rialo::event] pub struct PolicyTriggered          { pub policy_id: PolicyId, pub round_id: Option<CheckId>, pub delivery_company: Pubkey, pub rainfall_mm: Hundredths, pub payout: Ralo, pub copay: Ralo, pub total_paid: Ralo, pub coverage_remaining: Ralo, pub ralo_usd: Option<u64> }
+#[rialo::event] pub struct ConditionNotMet          { pub policy_id: PolicyId, pub rainfall_mm: Hundredths, pub threshold: Hundredths }
+#[rialo::event] pub struct CheckRoundPaused         { pub checked: u32, pub resume_after: Option<PolicyId> }
+#[rialo::event] pub struct CheckRoundCompleted      { pub checked: u32 }
+#[rialo::event] pub struct PolicyExpired            { pub policy_id: PolicyId, pub owner: Pubkey, pub coverage_end: i64, pub released: Ralo }
+#[rialo::event] pub struct HistoricalWeatherChecked { pub policy_id: PolicyId, pub at: i64, pub rainfall_mm: Millimeters, pub intensity: RainIntensity, pub reading: Hundredths, pub threshold: Hundredths, pub response: Option<ResponseDigest> }
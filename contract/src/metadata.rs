@@ -0,0 +1,93 @@
+// ============================================================
+//  Private policy metadata
+//
+//  Some customers don't want competitors reading from the chain
+//  which depots they insure. A policy can carry a sealed record
+//  of its real site — depot address, coordinates, notes — that
+//  only the owner and the underwriter can open, next to a coarse
+//  public geohash bucket that exposure reporting uses instead.
+//
+//  The SDK has no on-chain confidentiality primitive, so sealing
+//  happens client-side as an envelope: the record is encrypted
+//  under a fresh data key, and that key is wrapped to each
+//  reader's public key. The contract stores the envelope, checks
+//  who it is wrapped for, and never sees the plaintext.
+// ============================================================
+
+use rialo_sdk::crypto::sha256;
+use rialo_sdk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::InsuranceError;
+use crate::geo::{geohash, is_geohash};
+use crate::policy::PolicyId;
+use crate::{Hash, InsuranceState};
+
+// Five characters is a ~5 km cell — enough for accumulation, too coarse to find a depot
+const MAX_PUBLIC_GEOHASH_LEN: usize = 5;
+const MAX_CIPHERTEXT_BYTES:   usize = 1_024;
+const MAX_WRAPPED_KEY_BYTES:  usize = 128;
+const MAX_RECIPIENTS:         usize = 4;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct WrappedKey {
+    pub recipient:   Pubkey,
+    pub wrapped_key: Vec<u8>,   // data key sealed to the recipient's public key
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SealedMetadata {
+    pub geohash:    String,            // public bucket the policy is reported under
+    pub ciphertext: Vec<u8>,           // encrypted site details
+    pub nonce:      Vec<u8>,
+    pub recipients: Vec<WrappedKey>,   // always the owner and the underwriter
+}
+
+// ── Entry point: owner attaches (or replaces) sealed metadata ─
+#[rialo::instruction]
+pub async fn set_private_metadata(
+    ctx:       Context<InsuranceState>,
+    policy_id: PolicyId,
+    sealed:    SealedMetadata,
+) -> RialoResult<()> {
+
+    let policy = ctx.state.policies.get(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+    require!(policy.owner == *ctx.signer, InsuranceError::Unauthorized("Only the policy owner can set private metadata.".into()));
+
+    let bucket = &sealed.geohash;
+    require!(is_geohash(bucket) && bucket.len() <= MAX_PUBLIC_GEOHASH_LEN, InsuranceError::InvalidArgument("Public bucket must be a geohash of at most 5 characters.".into()));
+    // A point peril's coordinates are public already; the bucket must not contradict them
+    if let Some(point) = policy.insured_point {
+        require!(geohash(&point, bucket.len()) == *bucket, InsuranceError::InvalidState("Public bucket does not contain the insured point.".into()));
+    }
+
+    require!(!sealed.ciphertext.is_empty() && sealed.ciphertext.len() <= MAX_CIPHERTEXT_BYTES, InsuranceError::InvalidArgument("Sealed metadata must be between 1 and 1024 bytes.".into()));
+    require!(sealed.recipients.len() <= MAX_RECIPIENTS, InsuranceError::InvalidArgument("Too many metadata recipients.".into()));
+    require!(sealed.recipients.iter().all(|r| r.wrapped_key.len() <= MAX_WRAPPED_KEY_BYTES), InsuranceError::InvalidArgument("Wrapped key is too long.".into()));
+
+    let authority = ctx.state.underwriters.get(&policy.underwriter_id).map(|u| u.authority).ok_or(InsuranceError::UnknownUnderwriter)?;
+    let readable_by = |key: &Pubkey| sealed.recipients.iter().any(|r| r.recipient == *key);
+    require!(readable_by(&policy.owner) && readable_by(&authority), InsuranceError::InvalidArgument("Sealed metadata must be readable by the owner and the underwriter.".into()));
+
+    let digest: Hash = sha256(&sealed.ciphertext);
+    emit!(PrivateMetadataSet { policy_id, geohash: sealed.geohash.clone(), digest });
+
+    if let Some(policy) = ctx.state.policies.get_mut(&policy_id) {
+        policy.sealed = Some(sealed);
+    }
+
+    Ok(())
+}
+
+#[rialo::view]
+pub fn get_private_metadata(
+    ctx:       Context<InsuranceState>,
+    policy_id: PolicyId,
+) -> RialoResult<Option<SealedMetadata>> {
+
+    let policy = ctx.state.policies.get(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+    Ok(policy.sealed.clone())
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct PrivateMetadataSet { pub policy_id: PolicyId, pub geohash: String, pub digest: Hash }
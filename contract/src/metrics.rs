@@ -0,0 +1,49 @@
+// ============================================================
+//  Operating metrics
+//
+//  A handful of running counters kept in state, so an operator can
+//  see the contract at work without standing up an indexer:
+//    • checks        — readings booked as checks (observations.rs)
+//    • http_failures — provider calls that came to nothing once
+//                      their retries ran out: a live or historical
+//                      check, a consensus source, a forecast or a
+//                      health probe
+//    • settlements   — payments sent to beneficiaries
+//  Each counter moves with the change it counts, so an instruction
+//  that fails takes its counts back with it. `get_metrics` adds
+//  the average checks per policy.
+// ============================================================
+
+use rialo_sdk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::InsuranceState;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ContractMetrics {
+    pub checks:        u64,
+    pub http_failures: u64,
+    pub settlements:   u64,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct MetricsView {
+    pub checks:            u64,
+    pub http_failures:     u64,
+    pub settlements:       u64,
+    pub policies:          u64,
+    pub checks_per_policy: f64,   // 0 before the first policy
+}
+
+pub fn view(state: &InsuranceState) -> MetricsView {
+    let ContractMetrics { checks, http_failures, settlements } = state.metrics;
+    let policies = state.policies.len() as u64;
+    let checks_per_policy = if policies == 0 { 0.0 } else { checks as f64 / policies as f64 };
+
+    MetricsView { checks, http_failures, settlements, policies, checks_per_policy }
+}
+
+#[rialo::view]
+pub fn get_metrics(ctx: Context<InsuranceState>) -> RialoResult<MetricsView> {
+    Ok(view(&ctx.state))
+}
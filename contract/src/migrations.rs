@@ -0,0 +1,220 @@
+// ============================================================
+//  State versioning
+//
+//  The state account outlives every build of the contract that
+//  writes it, so a new build has to read what the last one left.
+//  `version` on the state records the layout it was last brought
+//  up to, and the layout only ever grows in ways the previous one
+//  still decodes into:
+//    • a new field is an Option, or carries #[serde(default)], so
+//      an account written without it decodes with it unset
+//    • fields are never renamed, retyped or reused; a field that
+//      needs a new type gets a new name, and the old one stays on
+//      as a decode-only Option for its version's step to move over
+//  Where an unset default is wrong for records already written,
+//  the version that adds the field brings a migration step that
+//  puts it right. Version 0 is the last layout written before
+//  versioning, which had no version field.
+//
+//  An upgrade runs while the contract is paused: pause, deploy,
+//  migrate_state, unpause. migrate_state runs every step from the
+//  stored version up to STATE_VERSION, in order, once each, and
+//  the contract won't unpause on a state behind the code.
+//  tests/migrations.rs decodes a fixture of each earlier version.
+// ============================================================
+
+use rialo_sdk::prelude::*;
+
+use crate::bundles::BundledPeril;
+use crate::config::NetworkMode;
+use crate::errors::InsuranceError;
+use crate::fx::DeferredPayout;
+use crate::millimeters::Hundredths;
+use crate::{owners, InsuranceState};
+
+pub const STATE_VERSION: u8 = 6;
+
+type Step = fn(&mut InsuranceState);
+
+// Step n brings a state at version n up to n + 1
+const STEPS: [Step; STATE_VERSION as usize] = [from_v0, from_v1, from_v2, from_v3, from_v4, from_v5];
+
+// Version 1 adds only the version itself: the other fields added since
+// the last unversioned layout are all optional, and unset is right for them
+fn from_v0(_state: &mut InsuranceState) {}
+
+// Version 2 indexes policies by owner; an empty index would hide every policy already written
+fn from_v1(state: &mut InsuranceState) {
+    state.policies_by_owner = owners::build_index(&state.policies);
+}
+
+// Version 3 moved the per-beneficiary cap into the risk limits, and version 5
+// moves it back out, so a state still at 2 keeps it where it is
+fn from_v2(_state: &mut InsuranceState) {}
+
+// Version 4 holds thresholds as hundredths; the f64 ones written before decode to zero
+fn from_v3(state: &mut InsuranceState) {
+    for policy in state.policies.values_mut() {
+        if let Some(mm) = policy.old_threshold.take() {
+            policy.threshold = Hundredths::from_f64(mm);
+        }
+    }
+    for template in state.underwriters.values_mut().flat_map(|u| u.templates.values_mut()) {
+        if let Some(mm) = template.old_min_threshold.take() {
+            template.min_threshold = Hundredths::from_f64(mm);
+        }
+    }
+    if let Some(limits) = state.config.limits.as_mut() {
+        if let Some(mm) = limits.old_min_threshold.take() {
+            limits.min_threshold = Hundredths::from_f64(mm);
+        }
+    }
+}
+
+// Version 5 keeps the per-beneficiary cap in its own field again. Setting it
+// alone under versions 3 and 4 stored the network mode's defaults as the
+// admin's limits; limits that match a mode's defaults go back to following it.
+fn from_v4(state: &mut InsuranceState) {
+    let config = &mut state.config;
+    let Some(limits) = config.limits.as_mut() else {
+        return;
+    };
+    if let Some(cap) = limits.max_coverage_per_holder.take() {
+        config.beneficiary_cap = Some(cap);
+    }
+    if [NetworkMode::MainNet, NetworkMode::DevNet].iter().any(|mode| mode.limits() == *limits) {
+        config.limits = None;
+    }
+}
+
+// Version 6 carries readings, and the curves, riders and conditions products
+// compare them with, as hundredths; the f64 ones written before decode to zero
+fn from_v5(state: &mut InsuranceState) {
+    for template in state.underwriters.values_mut().flat_map(|u| u.templates.values_mut()) {
+        if let Some(condition) = template.old_condition.take() {
+            template.compound = Some(condition.into());
+        }
+        for rider in template.bundle.iter_mut().flat_map(|b| b.riders.iter_mut()) {
+            bundled(rider);
+        }
+    }
+    for policy in state.policies.values_mut() {
+        if let Some(condition) = policy.old_condition.take() {
+            policy.compound = Some(condition.into());
+        }
+        if let Some(graded) = policy.graded.as_mut() {
+            if let Some(curve) = graded.old_curve.take() {
+                graded.payout_curve = curve.into();
+            }
+        }
+        for peril in policy.bundle.iter_mut().flat_map(|b| b.perils.iter_mut()) {
+            bundled(peril);
+        }
+        for stop in policy.route.iter_mut().flat_map(|r| r.stops.iter_mut()) {
+            if let Some(reading) = stop.old_last_reading.take() {
+                stop.last_value = Some(Hundredths::from_f64(reading));
+            }
+        }
+        if let Some(payout) = policy.attestation.as_mut().and_then(|a| a.pending.as_mut()) {
+            deferred(payout);
+        }
+        if let Some(payout) = policy.usd_payout.as_mut().and_then(|u| u.deferred.as_mut()) {
+            deferred(payout);
+        }
+        if let Some(claim) = policy.dispute.as_mut().and_then(|d| d.pending.as_mut()) {
+            deferred(&mut claim.payout);
+        }
+    }
+    for terms in state.subscriptions.values_mut().map(|s| &mut s.terms) {
+        if let Some(mm) = terms.old_threshold.take() {
+            terms.threshold = Hundredths::from_f64(mm);
+        }
+        if let Some(curve) = terms.old_curve.take() {
+            terms.payout_curve = curve.into();
+        }
+    }
+    for receipt in state.receipts.values_mut().flatten() {
+        if let Some(reading) = receipt.old_reading.take() {
+            receipt.value = Hundredths::from_f64(reading);
+        }
+        if let Some(review) = receipt.review.as_mut() {
+            if let Some(original) = review.old_original.take() {
+                review.original_value = Hundredths::from_f64(original);
+            }
+        }
+    }
+    for record in state.outbox.values_mut() {
+        if let Some(reading) = record.old_reading.take() {
+            record.value = Hundredths::from_f64(reading);
+        }
+    }
+    for check in state.checks.values_mut() {
+        if let Some(reading) = check.old_reading.take() {
+            check.reading = Hundredths::from_f64(reading);
+        }
+        if let Some(audit) = check.audit.as_mut() {
+            if let Some(reading) = audit.old_reading.take() {
+                audit.reading = Hundredths::from_f64(reading);
+            }
+        }
+    }
+    for held in &mut state.held_observations {
+        if let Some(reading) = held.old_reading.take() {
+            held.reading = Hundredths::from_f64(reading);
+        }
+    }
+}
+
+fn bundled(peril: &mut BundledPeril) {
+    if let Some(threshold) = peril.old_threshold.take() {
+        peril.trigger_at = Hundredths::from_f64(threshold);
+    }
+}
+
+fn deferred(payout: &mut DeferredPayout) {
+    if let Some(reading) = payout.old_reading.take() {
+        payout.value = Hundredths::from_f64(reading);
+    }
+}
+
+// Bring a decoded state up to STATE_VERSION; returns the version it was at
+pub fn migrate(state: &mut InsuranceState) -> RialoResult<u8> {
+    let from = state.version;
+    require!(from <= STATE_VERSION, InsuranceError::InvalidState("State was written by a newer version of the contract.".into()));
+
+    for step in &STEPS[from as usize..] {
+        step(state);
+        state.version += 1;
+    }
+    Ok(from)
+}
+
+// Guard for unpause: the code can't run on a layout it hasn't migrated
+pub(crate) fn require_current(state: &InsuranceState) -> RialoResult<()> {
+    require!(state.version == STATE_VERSION, InsuranceError::InvalidState("State is behind this version of the contract; run migrate_state first.".into()));
+    Ok(())
+}
+
+// ── Entry point: admin upgrades the state after a deploy ─────
+#[rialo::instruction]
+pub async fn migrate_state(ctx: Context<InsuranceState>) -> RialoResult<u8> {
+    let state = &mut ctx.state;
+
+    require!(state.config.admin == *ctx.signer, InsuranceError::Unauthorized("Only the admin can migrate the state.".into()));
+    require!(state.config.paused, InsuranceError::InvalidState("Pause the contract before migrating its state.".into()));
+    require!(state.version != STATE_VERSION, InsuranceError::InvalidState("State is already at the current version.".into()));
+
+    let from = migrate(state)?;
+
+    emit!(StateMigrated { from, to: STATE_VERSION, at: ctx.clock.unix_timestamp });
+
+    Ok(STATE_VERSION)
+}
+
+#[rialo::view]
+pub fn get_state_version(ctx: Context<InsuranceState>) -> RialoResult<u8> {
+    Ok(ctx.state.version)
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct StateMigrated { pub from: u8, pub to: u8, pub at: i64 }
@@ -0,0 +1,264 @@
+// ============================================================
+//  Payout mints
+//
+//  A policy can pay out in a token other than RALO — a dollar
+//  stablecoin, say — from a mint the admin has registered. Its
+//  RALO terms don't change: the premium is RALO, the RALO payout
+//  prices the cover and stays reserved against the underwriter's
+//  capital, so every cap and ratio reads as it always has. What
+//  the customer is sent on a trigger is the policy's mint amount
+//  instead, out of a pool of that mint the underwriter keeps in
+//  the vault:
+//    • fund_mint_pool / withdraw_mint_pool move the tenant's
+//      tokens in and out; only unreserved tokens can leave
+//    • setup_policy reserves the mint amount in the pool, and
+//      refuses the policy if the pool can't cover it
+//    • each payment sends the share of the mint amount its RALO
+//      top-up stands for; the RALO itself is released back to the
+//      underwriter's free capital rather than sent
+//    • when cover ends, whatever of the reservation is left goes
+//      back to the pool
+//  A policy pays in one currency: mint payouts and USD payouts
+//  (fx.rs) don't mix. The vault's balance of each mint is the sum
+//  of the tenants' pools (reserve.rs).
+// ============================================================
+
+use std::collections::BTreeMap;
+
+use rialo_sdk::prelude::*;
+use rialo_sdk::token::{deposit_mint, transfer_mint};
+use serde::{Deserialize, Serialize};
+
+use crate::errors::InsuranceError;
+use crate::money::Ralo;
+use crate::policy::PolicyId;
+use crate::underwriter::{tenant_mut, Underwriter, UnderwriterId};
+use crate::whitelist::{self, Role};
+use crate::InsuranceState;
+
+pub type Mint = Pubkey;
+
+pub const MAX_MINT_DECIMALS: u8 = 18;
+
+// A token the contract can pay out in
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct MintInfo {
+    pub symbol:   String,
+    pub decimals: u8,
+}
+
+// One tenant's tokens of a mint, in the mint's base units
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MintPool {
+    pub balance:  u64,   // held in the vault for this tenant
+    pub reserved: u64,   // set aside for mint-paying policies
+}
+
+impl MintPool {
+    pub fn free(&self) -> u64 {
+        self.balance.saturating_sub(self.reserved)
+    }
+}
+
+// What a policy asks to be paid in, at setup
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MintAmount {
+    pub mint:   Mint,
+    pub amount: u64,   // sent in full for the full payout, in the mint's base units
+}
+
+// A policy's mint payout terms, and how far through them it is
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MintPayout {
+    pub mint:     Mint,
+    pub amount:   u64,   // for the full payout, before any co-pay
+    pub paid:     u64,   // sent so far
+    pub reserved: u64,   // still set aside in the underwriter's pool
+}
+
+impl MintPayout {
+    // Tokens owed once the policy has been paid `paid_out` of its RALO `payout`,
+    // net of what was already sent
+    pub fn due(&self, paid_out: Ralo, payout: Ralo) -> u64 {
+        if payout.is_zero() {
+            return 0;
+        }
+        let owed = self.amount as u128 * paid_out.base_units() as u128 / payout.base_units() as u128;
+        (owed.min(self.amount as u128) as u64).saturating_sub(self.paid)
+    }
+}
+
+// Whether the underwriter's pool can set `requested` aside, touching nothing
+pub(crate) fn check_reserve(
+    state:          &InsuranceState,
+    underwriter_id: UnderwriterId,
+    requested:      &MintAmount,
+) -> RialoResult<()> {
+
+    require!(state.config.payout_mints.contains_key(&requested.mint), InsuranceError::InvalidState("Payout mint is not registered.".into()));
+    require!(requested.amount > 0, InsuranceError::InvalidArgument("Mint payout must be positive.".into()));
+
+    let underwriter = state.underwriters.get(&underwriter_id).ok_or(InsuranceError::UnknownUnderwriter)?;
+    let free = underwriter.mint_pools.get(&requested.mint).map_or(0, MintPool::free);
+    require!(free >= requested.amount, InsuranceError::VaultInsolvent("Underwriter's pool of the payout mint can't cover this policy.".into()));
+
+    Ok(())
+}
+
+// Set `requested` aside in the underwriter's pool for a new policy
+pub(crate) fn reserve(
+    state:          &mut InsuranceState,
+    underwriter_id: UnderwriterId,
+    requested:      MintAmount,
+) -> RialoResult<MintPayout> {
+
+    check_reserve(state, underwriter_id, &requested)?;
+
+    let underwriter = state.underwriters.get_mut(&underwriter_id).ok_or(InsuranceError::UnknownUnderwriter)?;
+    let pool = underwriter.mint_pools.entry(requested.mint).or_default();
+    pool.reserved += requested.amount;
+
+    Ok(MintPayout { mint: requested.mint, amount: requested.amount, paid: 0, reserved: requested.amount })
+}
+
+// Send `to` the tokens owed once `paid_out` of `payout` has been paid, out
+// of the reservation. Returns the amount sent.
+pub(crate) fn pay(
+    underwriter: &mut Underwriter,
+    vault:       &Vault,
+    policy_id:   PolicyId,
+    terms:       &mut MintPayout,
+    to:          &Pubkey,
+    paid_out:    Ralo,
+    payout:      Ralo,
+) -> RialoResult<u64> {
+
+    let due = terms.due(paid_out, payout).min(terms.reserved);
+    if due == 0 {
+        return Ok(0);
+    }
+    transfer_mint(&terms.mint, vault, to, due)?;
+
+    let pool = underwriter.mint_pools.entry(terms.mint).or_default();
+    pool.balance  -= due;
+    pool.reserved -= due;
+    terms.paid     += due;
+    terms.reserved -= due;
+
+    emit!(MintPayoutSent { policy_id, mint: terms.mint, amount: due, to: *to });
+
+    Ok(due)
+}
+
+// Give a policy's remaining reservation back to its underwriter's pool, once
+// its cover has ended
+pub(crate) fn release(state: &mut InsuranceState, policy_id: PolicyId) {
+    let Some(policy) = state.policies.get_mut(&policy_id) else {
+        return;
+    };
+    let Some(terms) = policy.payout_mint.as_mut().filter(|t| t.reserved > 0) else {
+        return;
+    };
+    if let Some(pool) = state.underwriters.get_mut(&policy.underwriter_id).and_then(|u| u.mint_pools.get_mut(&terms.mint)) {
+        pool.reserved = pool.reserved.saturating_sub(terms.reserved);
+    }
+    emit!(MintReservationReleased { policy_id, mint: terms.mint, amount: terms.reserved });
+    terms.reserved = 0;
+}
+
+// The vault's balance of each registered mint: every tenant's pool added up
+pub fn mint_balances(state: &InsuranceState) -> BTreeMap<Mint, MintPool> {
+    let mut balances: BTreeMap<Mint, MintPool> = state.config.payout_mints.keys().map(|m| (*m, MintPool::default())).collect();
+    for (mint, pool) in state.underwriters.values().flat_map(|u| &u.mint_pools) {
+        let total = balances.entry(*mint).or_default();
+        total.balance  += pool.balance;
+        total.reserved += pool.reserved;
+    }
+    balances
+}
+
+// ── Entry point: admin registers (or updates) a payout mint ──
+#[rialo::instruction]
+pub async fn register_payout_mint(
+    ctx:      Context<InsuranceState>,
+    mint:     Mint,
+    symbol:   String,
+    decimals: u8,
+) -> RialoResult<()> {
+
+    let config = &mut ctx.state.config;
+
+    require!(config.admin == *ctx.signer, InsuranceError::Unauthorized("Only the admin can register payout mints.".into()));
+    require!(!symbol.trim().is_empty(), InsuranceError::InvalidState("Mint needs a symbol.".into()));
+    require!(decimals <= MAX_MINT_DECIMALS, InsuranceError::InvalidArgument("Mint can have at most 18 decimals.".into()));
+
+    config.payout_mints.insert(mint, MintInfo { symbol: symbol.clone(), decimals });
+
+    emit!(PayoutMintRegistered { mint, symbol, decimals });
+
+    Ok(())
+}
+
+// ── Entry point: deposit tokens of a mint into a tenant's pool
+#[rialo::instruction]
+pub async fn fund_mint_pool(
+    ctx:            Context<InsuranceState>,
+    underwriter_id: UnderwriterId,
+    mint:           Mint,
+    amount:         u64,
+) -> RialoResult<()> {
+
+    require!(ctx.state.config.payout_mints.contains_key(&mint), InsuranceError::InvalidState("Payout mint is not registered.".into()));
+    whitelist::require_role(&ctx.state, &ctx.signer, Role::Underwriter)?;
+    let underwriter = tenant_mut(&mut ctx.state, underwriter_id, &ctx.signer)?;
+
+    require!(amount > 0, InsuranceError::InvalidArgument("Deposit must be non-zero.".into()));
+
+    deposit_mint(&mint, &ctx.signer, &ctx.vault, amount)?;
+    let pool = underwriter.mint_pools.entry(mint).or_default();
+    pool.balance += amount;
+
+    emit!(MintPoolFunded { underwriter_id, mint, amount, balance: pool.balance });
+
+    Ok(())
+}
+
+// ── Entry point: take unreserved tokens out of a tenant's pool
+#[rialo::instruction]
+pub async fn withdraw_mint_pool(
+    ctx:            Context<InsuranceState>,
+    underwriter_id: UnderwriterId,
+    mint:           Mint,
+    amount:         u64,
+) -> RialoResult<()> {
+
+    let underwriter = tenant_mut(&mut ctx.state, underwriter_id, &ctx.signer)?;
+    let pool = underwriter.mint_pools.get_mut(&mint).ok_or_else(|| InsuranceError::InvalidState("Underwriter holds none of this mint.".into()))?;
+
+    require!(amount > 0, InsuranceError::InvalidArgument("Withdrawal must be non-zero.".into()));
+    require!(amount <= pool.free(), InsuranceError::VaultInsolvent("Withdrawal exceeds the unreserved tokens in the pool.".into()));
+
+    transfer_mint(&mint, &ctx.vault, &underwriter.authority, amount)?;
+    pool.balance -= amount;
+
+    emit!(MintPoolWithdrawn { underwriter_id, mint, amount, balance: pool.balance });
+
+    Ok(())
+}
+
+#[rialo::view]
+pub fn get_mint_pools(
+    ctx:            Context<InsuranceState>,
+    underwriter_id: UnderwriterId,
+) -> RialoResult<BTreeMap<Mint, MintPool>> {
+
+    let underwriter = ctx.state.underwriters.get(&underwriter_id).ok_or(InsuranceError::UnknownUnderwriter)?;
+    Ok(underwriter.mint_pools.clone())
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct PayoutMintRegistered    { pub mint: Mint, pub symbol: String, pub decimals: u8 }
+#[rialo::event] pub struct MintPoolFunded          { pub underwriter_id: UnderwriterId, pub mint: Mint, pub amount: u64, pub balance: u64 }
+#[rialo::event] pub struct MintPoolWithdrawn       { pub underwriter_id: UnderwriterId, pub mint: Mint, pub amount: u64, pub balance: u64 }
+#[rialo::event] pub struct MintPayoutSent          { pub policy_id: PolicyId, pub mint: Mint, pub amount: u64, pub to: Pubkey }
+#[rialo::event] pub struct MintReservationReleased { pub policy_id: PolicyId, pub mint: Mint, pub amount: u64 }
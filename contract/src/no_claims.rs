@@ -0,0 +1,128 @@
+// ============================================================
+//  No-claim bonus
+//
+//  A product can hand part of the premium back when the weather
+//  never came: an underwriter puts a no-claim bonus on a template,
+//  as a share of the premium, and every policy written under it
+//  carries that share. When such a policy expires having paid
+//  nothing — no trigger, no forecast advance, no partial grade —
+//  the bonus is worked out on the premium net of levies and of
+//  any outage refund (impairment.rs), rounded in the owner's
+//  favour, and the owner can collect it with
+//  claim_no_loss_refund from the underwriter's capital.
+//
+//  The bonus is priced into the premium by the underwriter, so it
+//  isn't reserved when the policy is written; a claim waits until
+//  the underwriter's free capital covers it.
+// ============================================================
+
+use rialo_sdk::prelude::*;
+use rialo_sdk::token::transfer;
+use serde::{Deserialize, Serialize};
+
+use crate::actuarial;
+use crate::errors::InsuranceError;
+use crate::money::Ralo;
+use crate::policy::{Policy, PolicyId, PolicyStatus};
+use crate::underwriter::{tenant_mut, TemplateId, UnderwriterId};
+use crate::InsuranceState;
+
+// Bonus carried by a policy sold under a template that offers one
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NoClaimBonus {
+    pub refund_bps: u64,            // share of the net premium handed back
+    pub due:        Option<Ralo>,   // set at expiry if the policy never paid
+    pub refunded:   bool,
+}
+
+impl NoClaimBonus {
+    pub fn new(refund_bps: u64) -> Self {
+        NoClaimBonus { refund_bps, due: None, refunded: false }
+    }
+}
+
+// What an expired policy's bonus comes to, if it earned one
+pub fn earned(policy: &Policy) -> Option<Ralo> {
+    let bonus = policy.no_claim?;
+    if policy.status != PolicyStatus::Expired || !policy.paid_out.is_zero() {
+        return None;
+    }
+
+    let levied: Ralo = policy.levies.iter().map(|l| l.amount).sum();
+    let outage_refund = policy.impairment.map_or(Ralo::ZERO, |i| i.refund);
+    let net = policy.premium_paid.saturating_sub(levied).saturating_sub(outage_refund);
+    Some(actuarial::refund_bps(net, bonus.refund_bps)).filter(|r| !r.is_zero())
+}
+
+// Settle the bonus of a policy that has just expired
+pub(crate) fn assess(state: &mut InsuranceState, policy_id: PolicyId) {
+    let Some(policy) = state.policies.get_mut(&policy_id) else {
+        return;
+    };
+    let Some(refund) = earned(policy) else {
+        return;
+    };
+    let Some(bonus) = policy.no_claim.as_mut() else {
+        return;
+    };
+    bonus.due = Some(refund);
+
+    emit!(NoClaimBonusEarned { policy_id, owner: policy.owner, refund });
+}
+
+// ── Entry point: put a no-claim bonus on a template ──────────
+#[rialo::instruction]
+pub async fn set_template_no_claim_bonus(
+    ctx:            Context<InsuranceState>,
+    underwriter_id: UnderwriterId,
+    template_id:    TemplateId,
+    refund_bps:     Option<u64>,
+) -> RialoResult<()> {
+
+    require!(refund_bps.is_none_or(|bps| (1..=10_000).contains(&bps)), InsuranceError::InvalidArgument("Bonus must be between 0.01% and 100% of the premium.".into()));
+
+    let underwriter = tenant_mut(&mut ctx.state, underwriter_id, &ctx.signer)?;
+    let template = underwriter.templates.get_mut(&template_id).ok_or(InsuranceError::UnknownTemplate)?;
+    template.no_claim_bonus_bps = refund_bps;
+
+    emit!(TemplateNoClaimBonusSet { underwriter_id, template_id, refund_bps });
+
+    Ok(())
+}
+
+// ── Entry point: owner of a claim-free policy takes the bonus ─
+#[rialo::instruction]
+pub async fn claim_no_loss_refund(
+    ctx:       Context<InsuranceState>,
+    policy_id: PolicyId,
+) -> RialoResult<()> {
+
+    let state = &mut ctx.state;
+    let policy = state.policies.get_mut(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+
+    require!(policy.owner == *ctx.signer, InsuranceError::Unauthorized("Only the policy owner can claim its refund.".into()));
+    let bonus = policy.no_claim.as_mut().ok_or_else(|| InsuranceError::InvalidState("Policy has no no-claim bonus.".into()))?;
+    let refund = bonus.due.ok_or_else(|| InsuranceError::InvalidState("Policy hasn't earned its no-claim bonus.".into()))?;
+    require!(!bonus.refunded, InsuranceError::InvalidState("No-claim bonus already claimed.".into()));
+
+    let underwriter = state.underwriters.get_mut(&policy.underwriter_id).ok_or(InsuranceError::UnknownUnderwriter)?;
+    require!(underwriter.free_capital() >= refund, InsuranceError::VaultInsolvent("Underwriter can't cover the refund yet.".into()));
+
+    transfer(&ctx.vault, &policy.owner, refund.base_units())?;
+
+    // Handed back, so never earned
+    underwriter.capital -= refund;
+    underwriter.claims.premiums_earned = underwriter.claims.premiums_earned.saturating_sub(refund);
+    let product = underwriter.claims.template_mut(policy.template_id);
+    product.premiums_earned = product.premiums_earned.saturating_sub(refund);
+    bonus.refunded = true;
+
+    emit!(NoClaimBonusPaid { policy_id, owner: policy.owner, amount: refund });
+
+    Ok(())
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct TemplateNoClaimBonusSet { pub underwriter_id: UnderwriterId, pub template_id: TemplateId, pub refund_bps: Option<u64> }
+#[rialo::event] pub struct NoClaimBonusEarned      { pub policy_id: PolicyId, pub owner: Pubkey, pub refund: Ralo }
+#[rialo::event] pub struct NoClaimBonusPaid        { pub policy_id: PolicyId, pub owner: Pubkey, pub amount: Ralo }
@@ -0,0 +1,255 @@
+// ============================================================
+//  Rainfall against the monthly normal
+//
+//  "Pays when this month's rain reaches 200% of normal" — cover
+//  a customer can buy without knowing what 180 mm means in their
+//  city. Next to its seasonal pricing factors (seasonal.rs) an
+//  underwriter can keep each location's climatological normal:
+//  mean rainfall per calendar month, in mm. A template marked as
+//  a normal-deviation product reads the policy threshold as a
+//  percentage of that normal, and each policy keeps the month's
+//  running total of hourly readings, one per hour, starting
+//  again every calendar month — the location's, if the
+//  underwriter has recorded its UTC offset (seasonal.rs).
+//
+//  The normals are copied onto the policy at setup, so the terms
+//  a customer bought can't move under live cover.
+//
+//  A month-to-date total under-counts every hour a keeper missed.
+//  The cover remembers which hours of the month it has counted,
+//  and `backfill_readings` reads the provider's history for the
+//  ones it hasn't — any order, within the lookback, a call budget
+//  at a time. Finalize backfills the last month before it will
+//  expire a policy, so a gap can't cost the customer the payout.
+// ============================================================
+
+use rialo_sdk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::config::require_unpaused;
+use crate::consensus::single_source_allowed;
+use crate::errors::InsuranceError;
+use crate::incidents::under_incident;
+use crate::millimeters::{Hundredths, Millimeters};
+use crate::normalization::{location_slug, Comparison, Metric};
+use crate::oracle::CallBudget;
+use crate::policy::{PolicyId, PolicyStatus};
+use crate::rate_limits;
+use crate::seasonal::{local_time, month_index, month_of};
+use crate::underwriter::{tenant_mut, TemplateId, UnderwriterId};
+use crate::{check_historical, InsuranceState};
+
+const SECS_PER_HOUR: i64 = 60 * 60;
+
+// Mean rainfall in mm, January first
+pub type MonthlyNormals = [f64; 12];
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BackfillProgress {
+    pub filled:    u32,   // hours read this call
+    pub remaining: u32,   // hours still missing that history can supply
+}
+
+// Month-to-date state carried by a policy sold under a normal-deviation template
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct NormalCover {
+    pub normals_mm: MonthlyNormals,   // copied from the underwriter at setup
+    pub month:      i64,              // month index `month_mm` is for (see seasonal.rs)
+    pub month_mm:   Millimeters,      // rain read so far this month
+    pub counted:    Vec<(i64, i64)>,  // hours read this month, as sorted [from, to) runs
+    pub utc_offset: i32,              // minutes east of UTC the location's months turn over at
+}
+
+impl NormalCover {
+    pub fn new(normals_mm: MonthlyNormals) -> Self {
+        NormalCover { normals_mm, month: 0, month_mm: Millimeters::ZERO, counted: Vec::new(), utc_offset: 0 }
+    }
+
+    // Month index and calendar month of a unix time, on the location's calendar
+    fn month_index_at(&self, unix: i64) -> i64 {
+        month_index(local_time(unix, self.utc_offset))
+    }
+
+    fn month_at(&self, unix: i64) -> usize {
+        month_of(local_time(unix, self.utc_offset))
+    }
+
+    pub fn is_counted(&self, hour: i64) -> bool {
+        self.counted.iter().any(|&(from, to)| (from..to).contains(&hour))
+    }
+
+    fn count(&mut self, hour: i64) {
+        let at = self.counted.partition_point(|&(_, to)| to < hour);
+        match self.counted.get_mut(at) {
+            Some(run) if run.0 <= hour + 1 => {
+                run.0 = run.0.min(hour);
+                run.1 = run.1.max(hour + 1);
+            }
+            _ => self.counted.insert(at, (hour, hour + 1)),
+        }
+        // Filling the last gap between two runs joins them
+        if let Some(&(from, to)) = self.counted.get(at + 1) {
+            if self.counted[at].1 >= from {
+                self.counted[at].1 = to;
+                self.counted.remove(at + 1);
+            }
+        }
+    }
+
+    // Uncounted hours of [start, end) in the month `end` falls in, oldest first
+    pub fn missing_hours(&self, start: i64, end: i64, limit: usize) -> Vec<i64> {
+        let month = self.month_index_at(end - 1);
+        let started = self.month == month;
+        (start.div_euclid(SECS_PER_HOUR)..=(end - 1).div_euclid(SECS_PER_HOUR))
+            .filter(|&hour| self.month_index_at(hour * SECS_PER_HOUR) == month)
+            .filter(|&hour| !started || !self.is_counted(hour))
+            .take(limit)
+            .collect()
+    }
+
+    // This month's rain as a percentage of its normal
+    pub fn percent_of_normal(&self, now: i64) -> f64 {
+        let normal = self.normals_mm[self.month_at(now)];
+        if normal <= 0.0 {
+            return 0.0;
+        }
+        self.month_mm.to_mm() * 100.0 / normal
+    }
+
+    // Fold one hourly reading in; returns true once the month reaches `percent` of normal.
+    // Hours already counted and hours of a month already closed add nothing.
+    pub fn record(&mut self, rainfall: Millimeters, now: i64, percent: Hundredths) -> bool {
+        let hour = now.div_euclid(SECS_PER_HOUR);
+        let month = self.month_index_at(now);
+        if month < self.month || (month == self.month && self.is_counted(hour)) {
+            return false;
+        }
+
+        if month != self.month {
+            self.month = month;
+            self.month_mm = Millimeters::ZERO;
+            self.counted.clear();
+        }
+        self.month_mm += rainfall;
+        self.count(hour);
+
+        // month / normal * 100 >= percent, in hundredths of a mm and of a percent
+        let normal = Millimeters::from_mm(self.normals_mm[self.month_at(now)]);
+        let percent = percent.0.max(0) as u128;
+        normal > Millimeters::ZERO && self.month_mm.0 as u128 * 10_000 >= percent * normal.0 as u128
+    }
+}
+
+// Read the provider's history for a policy's uncounted hours, oldest first,
+// until the instruction's call budget runs out. Hours older than the lookback
+// can't be read, and hours under a data incident wait for the incident.
+pub(crate) async fn backfill(state: &mut InsuranceState, vault: &Vault, policy_id: PolicyId, now: i64) -> RialoResult<BackfillProgress> {
+    let policy = state.policies.get(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+    let (Some(normal), Some(activated_at), Some(end)) = (&policy.normal, policy.activated_at, policy.coverage_end()) else {
+        return Ok(BackfillProgress { filled: 0, remaining: 0 });
+    };
+    let start = activated_at.max(now - state.config.max_lookback_secs);
+    let end = end.min(now);
+    if end <= start {
+        return Ok(BackfillProgress { filled: 0, remaining: 0 });
+    }
+
+    let underwriter = state.underwriters.get(&policy.underwriter_id).ok_or(InsuranceError::UnknownUnderwriter)?;
+    let source = underwriter.provider.base_url_for(state.config.network_mode).to_string();
+    let missing: Vec<i64> = normal.missing_hours(start, end, usize::MAX)
+        .into_iter()
+        .map(|hour| (hour * SECS_PER_HOUR).max(start))
+        .filter(|&at| !under_incident(state, &source, at))
+        .collect();
+
+    let mut budget = CallBudget::per_instruction();
+    let mut filled = 0;
+    for &at in &missing {
+        if !check_historical(state, vault, policy_id, at, now, &mut budget).await? {
+            break;
+        }
+        filled += 1;
+        // A backfilled hour can trigger the payout; nothing is missing from a settled policy
+        if state.policies.get(&policy_id).is_none_or(|p| p.status != PolicyStatus::Active) {
+            return Ok(BackfillProgress { filled, remaining: 0 });
+        }
+    }
+
+    let progress = BackfillProgress { filled, remaining: (missing.len() as u32).saturating_sub(filled) };
+    emit!(ReadingsBackfilled { policy_id, filled: progress.filled, remaining: progress.remaining });
+
+    Ok(progress)
+}
+
+// ── Entry point: anyone reads a policy's missed hours from history
+#[rialo::instruction]
+pub async fn backfill_readings(
+    ctx:       Context<InsuranceState>,
+    policy_id: PolicyId,
+) -> RialoResult<BackfillProgress> {
+
+    require_unpaused(&ctx.state.config)?;
+    let now = ctx.clock.unix_timestamp;
+    let policy = ctx.state.policies.get(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+
+    require!(policy.status == PolicyStatus::Active, InsuranceError::PolicyNotActive);
+    require!(policy.normal.is_some(), InsuranceError::InvalidState("Policy does not accumulate rainfall.".into()));
+    require!(single_source_allowed(&ctx.state.config, policy), InsuranceError::InvalidState("Policy settles on live readings from two providers only.".into()));
+    rate_limits::admit(&mut ctx.state, &ctx.signer, now)?;
+
+    backfill(&mut ctx.state, &ctx.vault, policy_id, now).await
+}
+
+// ── Entry point: underwriter sets a location's monthly normals ─
+#[rialo::instruction]
+pub async fn set_rainfall_normals(
+    ctx:            Context<InsuranceState>,
+    underwriter_id: UnderwriterId,
+    location:       String,
+    normals_mm:     Option<MonthlyNormals>,
+) -> RialoResult<()> {
+
+    let location = location_slug(&location);
+    require!(!location.is_empty(), InsuranceError::InvalidArgument("Location is required.".into()));
+    if let Some(normals) = &normals_mm {
+        require!(normals.iter().all(|mm| mm.is_finite() && *mm >= 0.0), InsuranceError::InvalidArgument("Rainfall normals must be zero or more mm.".into()));
+    }
+
+    let underwriter = tenant_mut(&mut ctx.state, underwriter_id, &ctx.signer)?;
+    match normals_mm {
+        Some(normals) => underwriter.rain_normals.insert(location.clone(), normals),
+        None          => underwriter.rain_normals.remove(&location),
+    };
+
+    emit!(RainfallNormalsSet { underwriter_id, location, normals_mm });
+
+    Ok(())
+}
+
+// ── Entry point: make (or unmake) a template a normal-deviation product
+#[rialo::instruction]
+pub async fn set_template_rain_normal(
+    ctx:            Context<InsuranceState>,
+    underwriter_id: UnderwriterId,
+    template_id:    TemplateId,
+    enabled:        bool,
+) -> RialoResult<()> {
+
+    let underwriter = tenant_mut(&mut ctx.state, underwriter_id, &ctx.signer)?;
+    let template = underwriter.templates.get_mut(&template_id).ok_or(InsuranceError::UnknownTemplate)?;
+    if enabled {
+        let plain_rain = template.peril() == Metric::Rainfall && template.comparison == Comparison::AtOrAbove && template.continuous_hours.is_none()
+            && template.rolling_hours.is_none() && template.evaluator.is_none();
+        require!(plain_rain, InsuranceError::InvalidState("Template already has its own trigger.".into()));
+    }
+    template.rain_normal = enabled;
+
+    emit!(TemplateRainNormalSet { underwriter_id, template_id, enabled });
+
+    Ok(())
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct RainfallNormalsSet    { pub underwriter_id: UnderwriterId, pub location: String, pub normals_mm: Option<MonthlyNormals> }
+#[rialo::event] pub struct TemplateRainNormalSet { pub underwriter_id: UnderwriterId, pub template_id: TemplateId, pub enabled: bool }
+#[rialo::event] pub struct ReadingsBackfilled    { pub policy_id: PolicyId, pub filled: u32, pub remaining: u32 }
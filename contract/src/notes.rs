@@ -0,0 +1,64 @@
+// ============================================================
+//  Policy notes
+//
+//  An append-only trail of manual decisions on a policy — dispute
+//  rulings, overrides, exceptions granted. Each note is only the
+//  hash of an off-chain document, plus who anchored it and when;
+//  notes can never be edited or removed.
+// ============================================================
+
+use rialo_sdk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::InsuranceError;
+use crate::policy::PolicyId;
+use crate::{Hash, InsuranceState};
+
+const MAX_NOTES_PER_POLICY: usize = 32;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PolicyNote {
+    pub policy_id: PolicyId,
+    pub author:    Pubkey,   // underwriter authority or arbiter
+    pub note_hash: Hash,     // digest of the off-chain document
+    pub at:        i64,
+}
+
+// ── Entry point: underwriter or arbiter anchors a note ───────
+#[rialo::instruction]
+pub async fn append_note(
+    ctx:       Context<InsuranceState>,
+    policy_id: PolicyId,
+    note_hash: Hash,
+) -> RialoResult<()> {
+
+    let now = ctx.clock.unix_timestamp;
+    let policy = ctx.state.policies.get(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+
+    let is_underwriter = ctx.state.underwriters
+        .get(&policy.underwriter_id)
+        .is_some_and(|u| u.authority == *ctx.signer);
+    let is_arbiter = ctx.state.config.arbiters.contains(&ctx.signer);
+    require!(is_underwriter || is_arbiter, InsuranceError::Unauthorized("Only the underwriter or an arbiter can add notes.".into()));
+
+    let count = ctx.state.policy_notes.iter().filter(|n| n.policy_id == policy_id).count();
+    require!(count < MAX_NOTES_PER_POLICY, InsuranceError::InvalidState("Note limit reached for this policy.".into()));
+
+    ctx.state.policy_notes.push(PolicyNote { policy_id, author: *ctx.signer, note_hash, at: now });
+
+    emit!(NoteAppended { policy_id, author: *ctx.signer, note_hash, at: now });
+
+    Ok(())
+}
+
+#[rialo::view]
+pub fn get_policy_notes(
+    ctx:       Context<InsuranceState>,
+    policy_id: PolicyId,
+) -> RialoResult<Vec<PolicyNote>> {
+
+    Ok(ctx.state.policy_notes.iter().filter(|n| n.policy_id == policy_id).cloned().collect())
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct NoteAppended { pub policy_id: PolicyId, pub author: Pubkey, pub note_hash: Hash, pub at: i64 }
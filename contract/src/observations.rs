@@ -0,0 +1,90 @@
+// ============================================================
+//  Observation log
+//
+//  Every provider reading that reaches settlement gets a
+//  `CheckId` and a record of what was read, from where — the
+//  provider, and the station and point it says it read — and what
+//  it decided. Audits, disputes and settlement receipts refer back
+//  to these records, and each policy's claims ledger lists its own
+//  (history.rs).
+//  Arbiter overrides keep their own log (see arbiter.rs).
+// ============================================================
+
+use rialo_sdk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::audit::{self, AuditOutcome};
+use crate::evaluation::Evaluation;
+use crate::history;
+use crate::millimeters::Hundredths;
+use crate::normalization::{ResponseDigest, Station};
+use crate::policy::PolicyId;
+use crate::InsuranceState;
+
+pub type CheckId = u64;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CheckRecord {
+    pub policy_id:      PolicyId,
+    pub source:         String,                 // provider base URL
+    pub location:       String,                 // canonical location queried
+    pub station:        Station,                // where the provider says it read it
+    pub response:       Option<ResponseDigest>, // raw response the reading was parsed from
+    #[serde(default)]
+    pub reading:        Hundredths,             // in hundredths of the peril's unit
+    #[serde(rename = "rainfall_mm", default, skip_serializing)]
+    pub old_reading:    Option<f64>,            // layout 5 and earlier only: now `reading` (migrations.rs)
+    pub observed_at:    i64,
+    pub decision:       Evaluation,             // what the reading settled, and what it paid
+    pub audit_selected: bool,                   // drawn for an independent re-check
+    pub audit:          Option<AuditOutcome>,   // set once the re-check has run
+}
+
+// Id the next logged check will get — a reading settles under it before it is logged
+pub fn upcoming_check_id(state: &InsuranceState) -> CheckId {
+    state.next_check_id
+}
+
+// Log a settled reading; returns its id
+#[allow(clippy::too_many_arguments)]
+pub fn record_check(
+    state:       &mut InsuranceState,
+    policy_id:   PolicyId,
+    source:      &str,
+    station:     Station,
+    response:    Option<ResponseDigest>,
+    reading:     Hundredths,
+    observed_at: i64,
+    decision:    Evaluation,
+) -> CheckId {
+
+    let check_id = state.next_check_id;
+    state.next_check_id += 1;
+    state.metrics.checks += 1;
+
+    let location = state.policies.get(&policy_id).map(|p| p.location.clone()).unwrap_or_default();
+    let triggered = decision.triggered();
+    let audit_selected = audit::draw(&state.config.audit, check_id, policy_id, observed_at, triggered);
+
+    state.checks.insert(check_id, CheckRecord {
+        policy_id,
+        source: source.to_string(),
+        location,
+        station: station.clone(),
+        response,
+        reading,
+        old_reading: None,
+        observed_at,
+        decision,
+        audit_selected,
+        audit: None,
+    });
+    history::append(state, policy_id, check_id);
+
+    emit!(CheckRecorded { check_id, policy_id, station, reading, observed_at, triggered, audit_selected });
+
+    check_id
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct CheckRecorded { pub check_id: CheckId, pub policy_id: PolicyId, pub station: Station, pub reading: Hundredths, pub observed_at: i64, pub triggered: bool, pub audit_selected: bool }
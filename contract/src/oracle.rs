@@ -0,0 +1,305 @@
+// ============================================================
+//  Weather oracle — request building and response parsing
+//
+//  Kept separate from the instructions so the exact bytes the
+//  contract acts on can be parsed the same way off-chain (tests,
+//  the DevNet fixture service, auditors replaying a check).
+//  Also owns the per-instruction budget on provider calls, so a
+//  round that fans out over many policies stops before the runtime
+//  limit instead of failing half-way.
+//
+//  Every request pins metric units and English, rather than
+//  trusting the account's defaults — OpenWeatherMap answers in
+//  Kelvin without `units`. Its responses don't say which units
+//  they are in, so a temperature no metric reading could reach is
+//  taken as a sign the pin was ignored, and the response refused.
+//
+//  Nor does it always report rain the same way. The hour's rain is
+//  read from the first of these the response carries:
+//    1. `rain.1h`
+//    2. `rain.3h`, as an hourly rate (a third of it)
+//    3. `precipitation`, in the same 1h-then-3h order, less any
+//       snow reported beside it
+//    4. `snow`, when the `weather[].id` condition codes report only
+//       rain or drizzle — liquid precipitation filed under snow
+//  Failing all four the hour was dry, unless the condition codes
+//  say it rained: then the response is refused rather than read
+//  as 0 mm, which would count against a legitimate claim.
+// ============================================================
+
+use rialo_sdk::prelude::*;
+use serde::Deserialize;
+
+use crate::errors::InsuranceError;
+use crate::geo::GeoPoint;
+use crate::millimeters::Millimeters;
+use crate::normalization::{ms_to_kmh, Location, Observation, Station};
+
+// Hard cap on provider calls a single instruction may make
+pub const MAX_HTTP_CALLS_PER_INSTRUCTION: u32 = 8;
+
+// Units and language pinned on every OpenWeatherMap request
+pub const PINNED_PARAMS: &str = "units=metric&lang=en";
+
+// Above any air temperature on record; a reading past it isn't in °C
+pub const MAX_PLAUSIBLE_TEMP_C: f64 = 70.0;
+
+// Length of one step of the 5-day forecast
+pub const FORECAST_STEP_SECS: i64 = 3 * 60 * 60;
+
+// Provider calls an instruction still has left
+#[derive(Clone, Copy, Debug)]
+pub struct CallBudget {
+    remaining: u32,
+}
+
+impl CallBudget {
+    pub fn per_instruction() -> Self {
+        CallBudget { remaining: MAX_HTTP_CALLS_PER_INSTRUCTION }
+    }
+
+    pub fn remaining(&self) -> u32 {
+        self.remaining
+    }
+
+    // Take one call from the budget; false once it is used up
+    pub fn try_spend(&mut self) -> bool {
+        if self.remaining == 0 {
+            return false;
+        }
+        self.remaining -= 1;
+        true
+    }
+}
+
+// ── Helper structs for parsing the weather API response ──────
+#[derive(Deserialize)]
+struct WeatherResponse {
+    rain:          Option<RainData>,
+    snow:          Option<RainData>,   // same shape as rain
+    precipitation: Option<RainData>,   // rain and snow together, where given
+    #[serde(default)]
+    weather:       Vec<ConditionData>,
+    main:          Option<MainData>,
+    wind:          Option<WindData>,
+    id:            Option<u64>,        // city id the query resolved to
+    coord:         Option<CoordData>,
+    base:          Option<String>,     // data source, e.g. "stations"
+}
+
+#[derive(Deserialize)]
+struct CoordData {
+    lat: f64,
+    lon: f64,
+}
+
+#[derive(Deserialize)]
+struct HistoryResponse {
+    list: Vec<WeatherResponse>,
+}
+
+#[derive(Deserialize)]
+struct RainData {
+    #[serde(rename = "1h")]
+    one_hour:    Option<f64>,
+    #[serde(rename = "3h")]
+    three_hours: Option<f64>,
+}
+
+impl RainData {
+    // Millimetres per hour: the last hour's amount, else a third of the last three hours'
+    fn hourly(&self) -> Option<f64> {
+        self.one_hour.or(self.three_hours.map(|mm| mm / 3.0))
+    }
+}
+
+#[derive(Deserialize)]
+struct ConditionData {
+    id: u16,   // OpenWeatherMap condition code
+}
+
+#[derive(Deserialize)]
+struct MainData {
+    temp:     Option<f64>,   // °C with units=metric
+    humidity: Option<f64>,   // %
+}
+
+#[derive(Deserialize)]
+struct WindData {
+    speed: Option<f64>,      // m/s with units=metric
+}
+
+#[derive(Deserialize)]
+struct ForecastResponse {
+    list: Vec<ForecastEntry>,
+}
+
+#[derive(Deserialize)]
+struct ForecastEntry {
+    dt:   i64,                 // start of the 3-hour step
+    pop:  Option<f64>,         // probability of precipitation, 0–1
+    rain: Option<StepRain>,
+}
+
+#[derive(Deserialize)]
+struct StepRain {
+    #[serde(rename = "3h")]
+    three_hours: Option<f64>,
+}
+
+// One step of the 5-day forecast
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ForecastStep {
+    pub start:       i64,
+    pub rain_chance: f64,   // 0–1
+    pub rainfall_mm: f64,   // predicted over the whole step
+}
+
+impl ForecastStep {
+    // Predicted rain per hour, comparable with an hourly threshold
+    pub fn hourly_mm(&self) -> f64 {
+        self.rainfall_mm * 3_600.0 / FORECAST_STEP_SECS as f64
+    }
+}
+
+// Query parameters naming a location. OpenWeatherMap can't query a station,
+// so policies pinned to one are never written against it (queries_stations).
+pub fn location_query(location: &Location) -> String {
+    match location {
+        Location::City(name)  => format!("q={name}"),
+        Location::Point(p)    => format!("lat={}&lon={}", p.lat_e6 as f64 / 1e6, p.lon_e6 as f64 / 1e6),
+        Location::CityId(id)  => format!("id={id}"),
+        Location::Station(id) => format!("station={id}"),
+    }
+}
+
+// Current-conditions endpoint for a location, metric units
+pub fn current_weather_url(base_url: &str, location: &Location, api_key: &str) -> String {
+    format!(
+        "{}/data/2.5/weather?{}&appid={}&{}",
+        base_url,
+        location_query(location),
+        api_key,
+        PINNED_PARAMS,
+    )
+}
+
+// Hourly history for a location, starting at `at` (unix seconds)
+pub fn historical_weather_url(base_url: &str, location: &Location, at: i64, api_key: &str) -> String {
+    format!(
+        "{}/data/2.5/history/city?{}&type=hour&start={}&cnt=1&appid={}&{}",
+        base_url,
+        location_query(location),
+        at,
+        api_key,
+        PINNED_PARAMS,
+    )
+}
+
+// 5-day forecast in 3-hour steps for a location, metric units
+pub fn forecast_url(base_url: &str, location: &Location, api_key: &str) -> String {
+    format!(
+        "{}/data/2.5/forecast?{}&appid={}&{}",
+        base_url,
+        location_query(location),
+        api_key,
+        PINNED_PARAMS,
+    )
+}
+
+// Forecast steps in the order given; a missing `rain` block means a dry step
+pub fn parse_forecast(body: &[u8]) -> RialoResult<Vec<ForecastStep>> {
+    let forecast: ForecastResponse = serde_json::from_slice(body)
+        .map_err(|_| InsuranceError::BadResponse("Malformed forecast response.".into()))?;
+
+    Ok(forecast.list
+        .into_iter()
+        .map(|entry| ForecastStep {
+            start:       entry.dt,
+            rain_chance: entry.pop.unwrap_or(0.0),
+            rainfall_mm: entry.rain.and_then(|r| r.three_hours).unwrap_or(0.0),
+        })
+        .collect())
+}
+
+// Refuse a response whose temperature can't be °C
+pub fn require_metric(temp: Option<f64>) -> RialoResult<()> {
+    require!(temp.is_none_or(|t| t <= MAX_PLAUSIBLE_TEMP_C), InsuranceError::BadResponse("Weather response isn't in metric units.".into()));
+    Ok(())
+}
+
+// Condition codes: 2xx thunderstorm, 3xx drizzle, 5xx rain, 6xx snow
+fn is_liquid(code: u16) -> bool {
+    matches!(code, 200..=202 | 230..=232 | 300..=399 | 500..=599)
+}
+
+fn is_frozen(code: u16) -> bool {
+    (600..=699).contains(&code)
+}
+
+// The hour's rain and snow, in mm, by the precedence in the header
+fn precipitation(weather: &WeatherResponse) -> RialoResult<(f64, f64)> {
+    let liquid = weather.weather.iter().any(|c| is_liquid(c.id));
+    let frozen = weather.weather.iter().any(|c| is_frozen(c.id));
+    let snow = weather.snow.as_ref().and_then(RainData::hourly);
+
+    // `precipitation` counts the snow too; only what's left of it is rain
+    let rain = weather.rain.as_ref().and_then(RainData::hourly).or_else(|| {
+        let total = weather.precipitation.as_ref().and_then(RainData::hourly)?;
+        Some((total - snow.unwrap_or(0.0)).max(0.0))
+    });
+
+    match (rain, snow) {
+        (Some(rain), snow) => Ok((rain, snow.unwrap_or(0.0))),
+        (None, Some(snow)) if liquid && !frozen => Ok((snow, 0.0)),
+        (None, snow) => {
+            require!(!liquid, InsuranceError::BadResponse("Weather response reports rain but no amount.".into()));
+            Ok((0.0, snow.unwrap_or(0.0)))
+        }
+    }
+}
+
+// Full normalized observation, including derived feels-like metrics
+pub fn parse_observation(body: &[u8]) -> RialoResult<Observation> {
+    let weather: WeatherResponse = serde_json::from_slice(body)
+        .map_err(|_| InsuranceError::BadResponse("Malformed weather response.".into()))?;
+    require_metric(weather.main.as_ref().and_then(|m| m.temp))?;
+
+    normalize(weather)
+}
+
+fn normalize(weather: WeatherResponse) -> RialoResult<Observation> {
+    let (rainfall_mm, snowfall_mm) = precipitation(&weather)?;
+
+    let station = Station {
+        station_id:  weather.id.map(|id| id.to_string()),
+        coord:       weather.coord.and_then(|c| GeoPoint::from_degrees(c.lat, c.lon)),
+        data_source: weather.base,
+    };
+
+    let main = weather.main.as_ref();
+
+    Ok(Observation::new(
+        Millimeters::from_mm(rainfall_mm),
+        main.and_then(|m| m.temp),
+        main.and_then(|m| m.humidity),
+        weather.wind.and_then(|w| w.speed).map(ms_to_kmh),
+    )
+    .with_snowfall(Some(snowfall_mm))
+    .with_station(station))
+}
+
+// The first hour of a history response — same shape as current conditions
+pub fn parse_historical_observation(body: &[u8]) -> RialoResult<Observation> {
+    let history: HistoryResponse = serde_json::from_slice(body)
+        .map_err(|_| InsuranceError::BadResponse("Malformed weather history response.".into()))?;
+    let hour = history.list.into_iter().next().ok_or_else(|| InsuranceError::BadResponse("Weather history response has no readings.".into()))?;
+    require_metric(hour.main.as_ref().and_then(|m| m.temp))?;
+
+    normalize(hour)
+}
+
+// Rainfall over the last hour
+pub fn parse_rainfall(body: &[u8]) -> RialoResult<Millimeters> {
+    parse_observation(body).map(|o| o.rainfall_mm)
+}
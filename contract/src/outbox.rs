@@ -0,0 +1,231 @@
+// ============================================================
+//  Settlement outbox
+//
+//  `PolicyTriggered` is fire-and-forget: a webhook or customer
+//  notification built on it has no way to say it never arrived.
+//  Every settlement therefore also files an `OutboxRecord` in
+//  state, and an off-chain relayer the admin has registered works
+//  through them:
+//    • delivered → `mark_delivered` removes the record
+//    • failed    → `report_delivery_failure` counts the attempt,
+//                  keeps the error and schedules the next try,
+//                  backing off exponentially from a minute
+//  After `MAX_DELIVERY_ATTEMPTS` the record is dead-lettered: it
+//  stays in state, out of the relayer's queue, and an alert goes
+//  out until the admin requeues it. What is undelivered, and why,
+//  is always readable on-chain.
+// ============================================================
+
+use rialo_sdk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::alerts::{self, AlertKind};
+use crate::errors::InsuranceError;
+use crate::millimeters::Hundredths;
+use crate::money::Ralo;
+use crate::policy::PolicyId;
+use crate::receipts::SettlementKey;
+use crate::InsuranceState;
+
+pub type OutboxId = u64;
+
+pub const MAX_DELIVERY_ATTEMPTS: u32 = 8;
+pub const MAX_RELAYERS: usize = 4;
+
+const FIRST_RETRY_SECS: i64 = 60;
+const MAX_RETRY_SECS:   i64 = 24 * 60 * 60;
+const MAX_ERROR_LEN:    usize = 256;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct OutboxRecord {
+    pub id:            OutboxId,
+    pub key:           SettlementKey,    // the settlement to announce
+    pub owner:         Pubkey,           // account that was paid
+    pub payout:        Ralo,
+    #[serde(default)]
+    pub value:         Hundredths,       // index value it settled on, in hundredths of its unit
+    #[serde(rename = "reading", default, skip_serializing)]
+    pub old_reading:   Option<f64>,      // layout 5 and earlier only: now `value` (migrations.rs)
+    pub created_at:    i64,
+    pub attempts:      u32,              // failed deliveries so far
+    pub last_error:    Option<String>,   // as the relayer reported it
+    pub next_attempt:  i64,              // not due before this
+    pub dead_lettered: bool,             // out of retries; waits for the admin
+}
+
+impl OutboxRecord {
+    pub fn new(id: OutboxId, key: SettlementKey, owner: Pubkey, payout: Ralo, value: Hundredths, now: i64) -> Self {
+        OutboxRecord {
+            id,
+            key,
+            owner,
+            payout,
+            value,
+            old_reading:   None,
+            created_at:    now,
+            attempts:      0,
+            last_error:    None,
+            next_attempt:  now,
+            dead_lettered: false,
+        }
+    }
+
+    pub fn is_due(&self, now: i64) -> bool {
+        !self.dead_lettered && now >= self.next_attempt
+    }
+
+    // Count a failed delivery at `now`; returns true if that was the last retry
+    pub fn record_failure(&mut self, error: &str, now: i64) -> bool {
+        self.attempts += 1;
+        self.last_error = Some(error.chars().take(MAX_ERROR_LEN).collect());
+        self.dead_lettered = self.attempts >= MAX_DELIVERY_ATTEMPTS;
+        self.next_attempt = now + retry_delay_secs(self.attempts);
+        self.dead_lettered
+    }
+}
+
+// Wait before the next try after `attempts` failures: a minute, doubling, capped at a day
+pub fn retry_delay_secs(attempts: u32) -> i64 {
+    let doublings = attempts.saturating_sub(1).min(20);
+    (FIRST_RETRY_SECS << doublings).min(MAX_RETRY_SECS)
+}
+
+// File the outbox record for a settlement just announced
+pub(crate) fn enqueue(state: &mut InsuranceState, key: SettlementKey, owner: Pubkey, payout: Ralo, reading: Hundredths, now: i64) {
+    let id = state.next_outbox_id;
+    state.next_outbox_id += 1;
+    state.outbox.insert(id, OutboxRecord::new(id, key, owner, payout, reading, now));
+
+    emit!(OutboxEnqueued { outbox_id: id, policy_id: key.policy_id });
+}
+
+fn require_relayer(state: &InsuranceState, signer: &Pubkey) -> RialoResult<()> {
+    require!(state.config.relayers.contains(signer), InsuranceError::Unauthorized("Only a registered relayer can report deliveries.".into()));
+    Ok(())
+}
+
+// ── Entry point: admin replaces the relayer set ──────────────
+#[rialo::instruction]
+pub async fn set_relayers(
+    ctx:      Context<InsuranceState>,
+    relayers: Vec<Pubkey>,
+) -> RialoResult<()> {
+
+    let config = &mut ctx.state.config;
+
+    require!(config.admin == *ctx.signer, InsuranceError::Unauthorized("Only the admin can change the relayers.".into()));
+    require!(relayers.len() <= MAX_RELAYERS, InsuranceError::InvalidArgument("Too many relayers.".into()));
+
+    let mut deduped = relayers.clone();
+    deduped.sort();
+    deduped.dedup();
+    require!(deduped.len() == relayers.len(), InsuranceError::InvalidState("Relayer list contains duplicates.".into()));
+
+    config.relayers = relayers.clone();
+
+    emit!(RelayersChanged { relayers });
+
+    Ok(())
+}
+
+// ── Entry point: relayer confirms a record was delivered ─────
+#[rialo::instruction]
+pub async fn mark_delivered(
+    ctx:       Context<InsuranceState>,
+    outbox_id: OutboxId,
+) -> RialoResult<()> {
+
+    let now = ctx.clock.unix_timestamp;
+    require_relayer(&ctx.state, &ctx.signer)?;
+
+    let record = ctx.state.outbox.remove(&outbox_id).ok_or_else(|| InsuranceError::UnknownRecord("Unknown outbox record.".into()))?;
+
+    emit!(OutboxDelivered { outbox_id, policy_id: record.key.policy_id, attempts: record.attempts + 1, at: now });
+
+    Ok(())
+}
+
+// ── Entry point: relayer reports a delivery that failed ──────
+#[rialo::instruction]
+pub async fn report_delivery_failure(
+    ctx:       Context<InsuranceState>,
+    outbox_id: OutboxId,
+    error:     String,
+) -> RialoResult<()> {
+
+    let now = ctx.clock.unix_timestamp;
+    require_relayer(&ctx.state, &ctx.signer)?;
+
+    let record = ctx.state.outbox.get_mut(&outbox_id).ok_or_else(|| InsuranceError::UnknownRecord("Unknown outbox record.".into()))?;
+    require!(!record.dead_lettered, InsuranceError::InvalidState("Outbox record is dead-lettered; requeue it first.".into()));
+
+    let dead = record.record_failure(&error, now);
+    let failed = OutboxDeliveryFailed {
+        outbox_id,
+        policy_id:    record.key.policy_id,
+        attempts:     record.attempts,
+        next_attempt: (!dead).then_some(record.next_attempt),
+        error:        record.last_error.clone().unwrap_or_default(),
+    };
+
+    if dead {
+        alerts::raise(&ctx.state.config, AlertKind::DeliveryFailed, now, &failed).await;
+    }
+    emit!(failed);
+
+    Ok(())
+}
+
+// ── Entry point: admin puts a dead-lettered record back in the queue
+#[rialo::instruction]
+pub async fn requeue_outbox(
+    ctx:       Context<InsuranceState>,
+    outbox_id: OutboxId,
+) -> RialoResult<()> {
+
+    let now = ctx.clock.unix_timestamp;
+    require!(ctx.state.config.admin == *ctx.signer, InsuranceError::Unauthorized("Only the admin can requeue outbox records.".into()));
+
+    let record = ctx.state.outbox.get_mut(&outbox_id).ok_or_else(|| InsuranceError::UnknownRecord("Unknown outbox record.".into()))?;
+    require!(record.dead_lettered, InsuranceError::InvalidState("Outbox record is still being retried.".into()));
+
+    record.attempts      = 0;
+    record.dead_lettered = false;
+    record.next_attempt  = now;
+
+    emit!(OutboxRequeued { outbox_id });
+
+    Ok(())
+}
+
+// Records a relayer should deliver at `now`, oldest first
+#[rialo::view]
+pub fn get_due_outbox(
+    ctx:   Context<InsuranceState>,
+    now:   i64,
+    limit: u32,
+) -> RialoResult<Vec<OutboxRecord>> {
+
+    Ok(ctx.state.outbox
+        .values()
+        .filter(|r| r.is_due(now))
+        .take(limit as usize)
+        .cloned()
+        .collect())
+}
+
+// Records out of retries, waiting on the admin
+#[rialo::view]
+pub fn get_dead_letters(
+    ctx: Context<InsuranceState>,
+) -> RialoResult<Vec<OutboxRecord>> {
+
+    Ok(ctx.state.outbox.values().filter(|r| r.dead_lettered).cloned().collect())
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct RelayersChanged      { pub relayers: Vec<Pubkey> }
+#[rialo::event] pub struct OutboxEnqueued       { pub outbox_id: OutboxId, pub policy_id: PolicyId }
+#[rialo::event] pub struct OutboxDelivered      { pub outbox_id: OutboxId, pub policy_id: PolicyId, pub attempts: u32, pub at: i64 }
+#[rialo::event] pub struct OutboxDeliveryFailed { pub outbox_id: OutboxId, pub policy_id: PolicyId, pub attempts: u32, pub next_attempt: Option<i64>, pub error: String }
+#[rialo::event] pub struct OutboxRequeued       { pub outbox_id: OutboxId }
@@ -0,0 +1,126 @@
+// ============================================================
+//  Policies by owner
+//
+//  "Show me every policy and payout for this wallet" shouldn't
+//  mean reading every policy the contract holds. The state keeps
+//  an index from each owner to the ids of the policies they hold,
+//  in id order: a policy is filed under its owner when written,
+//  and moves with it when transferred (transfers.rs). Policies are
+//  never deleted, so a closed one stays on its holder's list, its
+//  status saying so.
+//
+//  `list_policies_by_owner` reads the list a page at a time, as
+//  compact summaries, from just after the cursor — the last id of
+//  the previous page — so a frontend can walk a large book without
+//  skipping or repeating a policy when new ones are written. The
+//  payouts themselves are on the receipts (receipts.rs).
+// ============================================================
+
+use std::collections::BTreeMap;
+
+use rialo_sdk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::millimeters::Hundredths;
+use crate::money::Ralo;
+use crate::normalization::Metric;
+use crate::policy::{Policy, PolicyId, PolicyStatus};
+use crate::InsuranceState;
+
+pub const OWNER_PAGE_SIZE: usize = 50;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PolicySummary {
+    pub policy_id:    PolicyId,
+    pub status:       PolicyStatus,
+    pub location:     String,
+    pub peril:        Metric,
+    pub threshold:    Hundredths,
+    pub payout:       Ralo,
+    pub paid_out:     Ralo,
+    pub premium:      Ralo,
+    pub coverage_end: Option<i64>,   // None until the premium clears
+}
+
+impl PolicySummary {
+    pub fn of(policy_id: PolicyId, policy: &Policy) -> Self {
+        PolicySummary {
+            policy_id,
+            status:       policy.status,
+            location:     policy.location.clone(),
+            peril:        policy.peril,
+            threshold:    policy.threshold,
+            payout:       policy.payout_amount,
+            paid_out:     policy.paid_out,
+            premium:      policy.premium_amount,
+            coverage_end: policy.coverage_end(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct OwnerPolicyPage {
+    pub owner:    Pubkey,
+    pub total:    u32,                  // policies the owner holds
+    pub policies: Vec<PolicySummary>,   // in id order
+    pub next:     Option<PolicyId>,     // cursor for the next page; None on the last
+}
+
+// File `policy_id` under `owner`, keeping the list in id order
+pub(crate) fn index(state: &mut InsuranceState, owner: Pubkey, policy_id: PolicyId) {
+    let ids = state.policies_by_owner.entry(owner).or_default();
+    let at = ids.partition_point(|&id| id < policy_id);
+    if ids.get(at) != Some(&policy_id) {
+        ids.insert(at, policy_id);
+    }
+}
+
+// Take `policy_id` off `owner`'s list
+pub(crate) fn unindex(state: &mut InsuranceState, owner: &Pubkey, policy_id: PolicyId) {
+    if let Some(ids) = state.policies_by_owner.get_mut(owner) {
+        ids.retain(|&id| id != policy_id);
+        if ids.is_empty() {
+            state.policies_by_owner.remove(owner);
+        }
+    }
+}
+
+// The whole index, read off the policies themselves
+pub fn build_index(policies: &BTreeMap<PolicyId, Policy>) -> BTreeMap<Pubkey, Vec<PolicyId>> {
+    let mut index: BTreeMap<Pubkey, Vec<PolicyId>> = BTreeMap::new();
+    for (id, policy) in policies {
+        index.entry(policy.owner).or_default().push(*id);
+    }
+    index
+}
+
+// The page of `ids` just after `cursor`
+pub fn owner_page(
+    owner:    Pubkey,
+    ids:      &[PolicyId],
+    policies: &BTreeMap<PolicyId, Policy>,
+    cursor:   Option<PolicyId>,
+) -> OwnerPolicyPage {
+
+    let from = cursor.map_or(0, |cursor| ids.partition_point(|&id| id <= cursor));
+    let page = &ids[from..(from + OWNER_PAGE_SIZE).min(ids.len())];
+    let more = from + page.len() < ids.len();
+
+    OwnerPolicyPage {
+        owner,
+        total:    ids.len() as u32,
+        policies: page.iter().filter_map(|id| policies.get(id).map(|p| PolicySummary::of(*id, p))).collect(),
+        next:     page.last().copied().filter(|_| more),
+    }
+}
+
+#[rialo::view]
+pub fn list_policies_by_owner(
+    ctx:    Context<InsuranceState>,
+    owner:  Pubkey,
+    cursor: Option<PolicyId>,   // last id of the previous page; None for the first
+) -> RialoResult<OwnerPolicyPage> {
+
+    let ids = ctx.state.policies_by_owner.get(&owner).map_or(&[][..], Vec::as_slice);
+    Ok(owner_page(owner, ids, &ctx.state.policies, cursor))
+}
@@ -0,0 +1,478 @@
+// ============================================================
+//  Policy records
+//
+//  Every policy lives in the `policies` map on `InsuranceState`,
+//  keyed by a monotonically increasing `PolicyId`.
+//
+//  Lifecycle:
+//    PendingPayment ──(premium fully paid)──► Active ──(trigger)──► PaidOut
+//          │
+//          ├──(owner withdraws premium)──► Withdrawn
+//          └──(grace period passes unpaid)──► Lapsed
+//
+//  A partial trigger (graded curves, storm tiers, evaluators) pays
+//  its share and leaves the policy Active with the rest of its
+//  cover for a later, bigger event.
+// ============================================================
+
+use std::collections::BTreeMap;
+use std::ops::Bound;
+
+use rialo_sdk::crypto::sha256;
+use rialo_sdk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::accumulation::RollingRain;
+use crate::advances::TriggerMode;
+#[cfg(feature = "air-quality")]
+use crate::air::AirCover;
+use crate::attestation::AttestationCover;
+use crate::bundles::{BundleCover, BundledPeril};
+use crate::claims::LaeBreakdown;
+use crate::commissions::Commission;
+use crate::conditions::{Condition, LegacyCondition};
+use crate::copay;
+use crate::curves::{GradedCover, LegacyPayoutCurve, PayoutCurve};
+#[cfg(feature = "degree-days")]
+use crate::degree_days::DegreeDayCover;
+use crate::disputes::DisputeCover;
+use crate::evaluators::EvaluatorCover;
+#[cfg(feature = "heat")]
+use crate::exposure::ExposureCover;
+use crate::forecasts::ForecastCover;
+use crate::fx::UsdPayout;
+use crate::geo::GeoPoint;
+use crate::impairment::Impairment;
+use crate::levies::LevyLine;
+use crate::metadata::SealedMetadata;
+use crate::millimeters::Hundredths;
+use crate::mints::{MintAmount, MintPayout};
+use crate::money::Ralo;
+use crate::normalization::{Comparison, Location, Metric};
+use crate::no_claims::NoClaimBonus;
+use crate::normals::NormalCover;
+use crate::quote::QuoteError;
+use crate::routes::{RouteCover, RouteTerms};
+use crate::seasonal::NEUTRAL_FACTOR_BPS;
+use crate::smoothing::SmoothedIndex;
+#[cfg(feature = "storm")]
+use crate::storm::StormCover;
+use crate::streak::RainStreak;
+use crate::throttle::DEFAULT_MIN_CHECK_SECS;
+use crate::underwriter::{TemplateId, UnderwriterId};
+use crate::Hash;
+
+pub use rialo_weather_core::status::PolicyStatus;
+
+pub type PolicyId = u64;
+
+// Policies strictly after `start_after`, in id order — the cursor batch operations page by
+pub fn policies_after(
+    policies:    &BTreeMap<PolicyId, Policy>,
+    start_after: Option<PolicyId>,
+) -> impl Iterator<Item = (&PolicyId, &Policy)> {
+    let from = start_after.map_or(Bound::Unbounded, Bound::Excluded);
+    policies.range((from, Bound::Unbounded))
+}
+
+// How the customer states the payout at setup
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum PayoutSpec {
+    Absolute(Ralo),                                       // pay exactly this amount
+    PremiumMultiple { premium: Ralo, multiple_pct: u64 }, // pay premium × multiple_pct / 100 (10× = 1000)
+}
+
+impl PayoutSpec {
+    // Resolve to (payout, premium) under an underwriter's premium rate
+    pub fn resolve(self, premium_rate_bps: u64) -> Result<(Ralo, Ralo), QuoteError> {
+        match self {
+            PayoutSpec::Absolute(payout) => {
+                Ok((payout, payout.bps(premium_rate_bps)))
+            }
+            PayoutSpec::PremiumMultiple { premium, multiple_pct } => {
+                if multiple_pct < 100 {
+                    return Err(QuoteError::MultipleBelowOne);
+                }
+
+                let scaled = premium.base_units().checked_mul(multiple_pct).ok_or(QuoteError::MultipleOverflow)?;
+                let payout = Ralo(scaled / 100);
+
+                // The premium offered must still meet the underwriter's price for that payout
+                if premium < payout.bps(premium_rate_bps) {
+                    return Err(QuoteError::PremiumBelowRate);
+                }
+                Ok((payout, premium))
+            }
+        }
+    }
+}
+
+// What a policy is written on, as setup_policy takes it
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PolicyTerms {
+    pub underwriter_id: UnderwriterId,
+    pub template_id:    TemplateId,
+    pub location:       Location,
+    #[serde(default)]
+    pub threshold:      Hundredths,                  // in hundredths of the peril's unit
+    #[serde(rename = "threshold_mm", default, skip_serializing)]
+    pub old_threshold:  Option<f64>,                 // layout 5 and earlier only: now `threshold` (migrations.rs)
+    pub payout:         PayoutSpec,
+    #[serde(default)]
+    pub payout_curve:   PayoutCurve,
+    #[serde(rename = "curve", default, skip_serializing)]
+    pub old_curve:      Option<LegacyPayoutCurve>,   // layout 5 and earlier only: now `payout_curve` (migrations.rs)
+    pub coverage_secs:  i64,
+    pub payout_mint:    Option<MintAmount>,
+    pub route:          Option<RouteTerms>,
+    pub commission:     Option<Commission>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Policy {
+    pub underwriter_id: UnderwriterId,            // tenant carrying the risk
+    pub template_id:    TemplateId,               // product template the policy was sold under
+    pub owner:          Pubkey,                   // wallet that pays the premium and receives the payout
+    pub broker:         Option<Pubkey>,           // set when a broker arranged the policy for the owner
+    pub pending_owner:  Option<Pubkey>,           // account a two-step transfer is offered to (transfers.rs)
+    pub location:       String,                   // location key, e.g. "nairobi", or "nairobi@kzf0t" with geohash keys on (geocoding.rs)
+    pub place:          Location,                 // what provider queries are pinned to: name, coordinates or city id
+    pub insured_point:  Option<GeoPoint>,         // insured coordinates for point perils, fixed before activation
+    pub peril:          Metric,                   // index the threshold is written on
+    pub comparison:     Comparison,               // side of the threshold that pays, copied from the template
+    #[serde(default)]
+    pub threshold:      Hundredths,               // threshold in hundredths of the peril's unit — of a mm for rainfall
+    #[serde(rename = "threshold_mm", default, skip_serializing)]
+    pub old_threshold:  Option<f64>,              // layout 3 and earlier only: now `threshold` (migrations.rs)
+    pub streak:         Option<RainStreak>,       // continuous-rain products: hours on the paying side of the threshold
+    pub normal:         Option<NormalCover>,      // normal-deviation products: rain so far this month against its normal
+    pub utc_offset:     i32,                      // insured location's local time, minutes east of UTC, fixed at setup (seasonal.rs)
+    pub accumulation:   Option<RollingRain>,      // accumulating products: hourly rain over the rolling window
+    pub evaluator:      Option<EvaluatorCover>,   // bespoke products: custom evaluator and share paid so far
+    pub graded:         Option<GradedCover>,      // graded payouts: curve the share is read off, and share paid so far
+    pub bundle:         Option<BundleCover>,      // multi-peril products: bundled perils, sub-limits and which have triggered
+    pub route:          Option<RouteCover>,       // route cover: every stop read, and how they're aggregated (routes.rs)
+    #[serde(default)]
+    pub compound:       Option<Condition>,        // compound products: AND/OR of readings that pays in place of the threshold
+    #[serde(rename = "condition", default, skip_serializing)]
+    pub old_condition:  Option<LegacyCondition>,  // layout 5 and earlier only: now `compound` (migrations.rs)
+    pub forecast:       Option<ForecastCover>,    // forecast-confirmed products: recorded chance of rain by slot
+    pub trigger_mode:   TriggerMode,              // observed readings only, or a forecast advance first (advances.rs)
+    pub smoothing:      Option<SmoothedIndex>,    // smoothed products: the readings in the rolling window (smoothing.rs)
+    pub attestation:    Option<AttestationCover>, // attesting products: payout waiting on the beneficiary, and attestations given
+    pub dispute:        Option<DisputeCover>,     // products with a dispute window: payout waiting it out, and rounds cleared
+    pub no_claim:       Option<NoClaimBonus>,     // products with a no-claim bonus: its share, and what expiry left owed (no_claims.rs)
+    #[cfg(feature = "storm")]
+    pub storm:          Option<StormCover>,       // storm-track products: payout tiers and closest approach
+    #[cfg(feature = "air-quality")]
+    pub air:            Option<AirCover>,         // air-quality products: run of hazardous checks
+    #[cfg(feature = "heat")]
+    pub exposure:       Option<ExposureCover>,    // heat-hours products: exposure hours accumulated
+    #[cfg(feature = "degree-days")]
+    pub degree_days:    Option<DegreeDayCover>,   // degree-day products: degree-days accumulated, and share paid so far
+    pub payout_amount:  Ralo,                     // tokens to send when triggered
+    pub paid_out:       Ralo,                     // tokens sent so far (graded and tiered cover can pay in steps)
+    pub copay_bps:      u64,                      // share of each calculated payout the customer absorbs (copay.rs)
+    pub copay_retained: Ralo,                     // calculated payout absorbed by the customer so far
+    pub usd_payout:     Option<UsdPayout>,        // USD-denominated payout, converted at trigger within the ceiling above
+    pub payout_mint:    Option<MintPayout>,       // paid in another mint's tokens instead of RALO (mints.rs)
+    pub premium_amount: Ralo,                     // tokens owed before coverage starts
+    pub seasonal_bps:   u64,                      // seasonal factor the premium was quoted at (10 000 = 1×)
+    pub ceded_bps:      u64,                      // quota share of claims ceded to reinsurers, fixed when written
+    pub premium_paid:   Ralo,                     // tokens received so far (held in escrow until activation)
+    pub status:         PolicyStatus,
+    pub created_at:     i64,                      // setup time; the payment grace period runs from here
+    pub activated_at:   Option<i64>,              // when the premium cleared and coverage started
+    pub starts_at:      Option<i64>,              // renewals: coverage starts no earlier than the renewed policy ends (renewals.rs)
+    pub coverage_secs:  i64,                      // how long coverage runs once active
+    pub min_check_secs: i64,                      // cooldown between live checks, copied from the template (throttle.rs)
+    pub last_checked:   Option<i64>,              // when the last live check ran
+    pub sealed:         Option<SealedMetadata>,   // encrypted site details, with a public geohash bucket
+    pub levies:         Vec<LevyLine>,            // levies withheld from the premium at activation
+    pub commission:     Option<Commission>,       // broker's cut of the premium (commissions.rs)
+    pub brokerage:      Option<Ralo>,             // commission withheld from the premium at activation
+    pub lae:            LaeBreakdown,             // operating costs incurred on this policy
+    pub impairment:     Option<Impairment>,       // set at expiry if an outage left it un-settleable
+    pub archived_at:    Option<i64>,              // when its check records were archived down to hashes (retention.rs)
+    pub renewed_by:     Option<PolicyId>,         // the policy that renewed this one
+}
+
+impl Policy {
+    // A freshly quoted policy, waiting for its premium
+    pub fn new(
+        underwriter_id: UnderwriterId,
+        template_id:    TemplateId,
+        owner:          Pubkey,
+        location:       String,
+        threshold:      Hundredths,
+        payout_amount:  Ralo,
+        premium_amount: Ralo,
+    ) -> Self {
+        Policy {
+            underwriter_id,
+            template_id,
+            owner,
+            broker:         None,
+            pending_owner:  None,
+            place:          Location::City(location.clone()),
+            location,
+            insured_point:  None,
+            peril:          Metric::Rainfall,
+            comparison:     Comparison::AtOrAbove,
+            threshold,
+            old_threshold:  None,
+            streak:         None,
+            normal:         None,
+            utc_offset:     0,
+            accumulation:   None,
+            evaluator:      None,
+            graded:         None,
+            bundle:         None,
+            route:          None,
+            compound:       None,
+            old_condition:  None,
+            forecast:       None,
+            trigger_mode:   TriggerMode::Observed,
+            smoothing:      None,
+            attestation:    None,
+            dispute:        None,
+            no_claim:       None,
+            #[cfg(feature = "storm")]
+            storm:          None,
+            #[cfg(feature = "air-quality")]
+            air:            None,
+            #[cfg(feature = "heat")]
+            exposure:       None,
+            #[cfg(feature = "degree-days")]
+            degree_days:    None,
+            payout_amount,
+            paid_out:       Ralo::ZERO,
+            copay_bps:      0,
+            copay_retained: Ralo::ZERO,
+            usd_payout:     None,
+            payout_mint:    None,
+            premium_amount,
+            seasonal_bps:   NEUTRAL_FACTOR_BPS,
+            ceded_bps:      0,
+            premium_paid:   Ralo::ZERO,
+            status:         PolicyStatus::PendingPayment,
+            created_at:     0,
+            activated_at:   None,
+            starts_at:      None,
+            coverage_secs:  0,
+            min_check_secs: DEFAULT_MIN_CHECK_SECS,
+            last_checked:   None,
+            sealed:         None,
+            levies:         Vec::new(),
+            commission:     None,
+            brokerage:      None,
+            lae:            LaeBreakdown::default(),
+            impairment:     None,
+            archived_at:    None,
+            renewed_by:     None,
+        }
+    }
+
+    // Credit a premium payment received at `now`. Returns true when it
+    // completes the premium and the policy activates; coverage starts then,
+    // or when the policy it renews ends if that's later.
+    pub fn record_premium(&mut self, amount: Ralo, now: i64) -> bool {
+        self.premium_paid += amount;
+
+        if self.status == PolicyStatus::PendingPayment && self.premium_outstanding().is_zero() {
+            self.status       = PolicyStatus::Active;
+            self.activated_at = Some(self.starts_at.map_or(now, |start| start.max(now)));
+            return true;
+        }
+        false
+    }
+
+    // End of the coverage window, once the policy has activated
+    pub fn coverage_end(&self) -> Option<i64> {
+        self.activated_at.map(|start| start + self.coverage_secs)
+    }
+
+    // Whether a reading observed at `at` falls inside the coverage window
+    pub fn is_covered_at(&self, at: i64) -> bool {
+        match (self.activated_at, self.coverage_end()) {
+            (Some(start), Some(end)) => at >= start && at < end,
+            _ => false,
+        }
+    }
+
+    // Apply a reading of the policy's peril taken at `now` to live
+    // coverage. Returns true when it triggers and the policy moves to
+    // PaidOut: a single reading on the paying side of the threshold, or
+    // for continuous-rain products the reading that completes the
+    // streak. Readings outside the coverage window never count.
+    pub fn apply_reading(&mut self, reading: Hundredths, now: i64) -> bool {
+        if !self.settles_on_readings() || self.status != PolicyStatus::Active || !self.is_covered_at(now) {
+            return false;
+        }
+
+        let met = self.comparison.is_met(reading, self.threshold);
+        let triggered = match (&mut self.streak, &mut self.normal, &mut self.accumulation) {
+            (Some(streak), _, _) => {
+                streak.record(met, now);
+                streak.is_complete()
+            }
+            // The threshold is a percentage of the month's normal rain
+            (None, Some(normal), _) => normal.record(reading.into(), now, self.threshold),
+            // The threshold is rain over the rolling window
+            (None, None, Some(accumulation)) => accumulation.record(reading.into(), now, self.threshold),
+            (None, None, None) => met,
+        };
+
+        if triggered {
+            self.status = PolicyStatus::PaidOut;
+        }
+        triggered
+    }
+
+    // Weather checks (live, historical, rounds) only settle cover on a
+    // reading the provider's weather response carries
+    pub fn is_weather_cover(&self) -> bool {
+        self.peril.is_observed() && self.evaluator.is_none()
+    }
+
+    // Threshold readings settle every peril except the point perils,
+    // which keep their own trigger state (storm.rs, air.rs, exposure.rs),
+    // degree-days, which settle on the index accumulated (degree_days.rs),
+    // and bespoke products, whose evaluator decides (evaluators.rs)
+    pub fn settles_on_readings(&self) -> bool {
+        !self.needs_insured_point() && !self.is_degree_day_cover() && self.evaluator.is_none()
+    }
+
+    pub fn is_degree_day_cover(&self) -> bool {
+        match self.peril {
+            #[cfg(feature = "degree-days")]
+            Metric::DegreeDays => true,
+            _ => false,
+        }
+    }
+
+    // Perils read at coordinates rather than a city name
+    pub fn needs_insured_point(&self) -> bool {
+        match self.peril {
+            #[cfg(feature = "storm")]
+            Metric::StormTrack => true,
+            #[cfg(feature = "air-quality")]
+            Metric::AirQuality => true,
+            #[cfg(feature = "heat")]
+            Metric::HeatHours | Metric::UvHours => true,
+            _ => false,
+        }
+    }
+
+    // Still unpaid once the grace period has run out
+    pub fn is_lapsed(&self, now: i64, grace_secs: i64) -> bool {
+        self.status == PolicyStatus::PendingPayment && now - self.created_at >= grace_secs
+    }
+
+    // A new policy's coverage can only start from `now`, so it overlaps
+    // any unpaid policy and any active one whose window is still open.
+    pub fn covers_same_risk(&self, owner: &Pubkey, location: &str, peril: Metric, now: i64) -> bool {
+        let live = match self.status {
+            PolicyStatus::PendingPayment => true,
+            PolicyStatus::Active         => self.coverage_end().is_some_and(|end| end > now),
+            _ => false,
+        };
+        live && self.owner == *owner && self.location == location && self.peril == peril
+    }
+
+    // Digest of every binding term — printed on the certificate so any
+    // document generated from it can be checked against the chain.
+    pub fn terms_hash(&self, policy_id: PolicyId) -> Hash {
+        let terms = CoverageTerms {
+            policy_id,
+            underwriter_id: self.underwriter_id,
+            template_id:    self.template_id,
+            beneficiary:    self.owner,
+            location:       &self.location,
+            peril:          self.peril,
+            threshold:      self.threshold,
+            payout:         self.payout_amount,
+            premium:        self.premium_amount,
+            coverage_start: self.activated_at,
+            coverage_end:   self.coverage_end(),
+            curve:          self.graded.as_ref().map(|g| &g.payout_curve),
+            copay_bps:      (self.copay_bps > 0).then_some(self.copay_bps),
+            perils:         self.bundle.as_ref().map(|b| b.perils.as_slice()),
+            comparison:     (self.comparison != Comparison::default()).then_some(self.comparison),
+            route:          self.route.as_ref().map(RouteCover::terms),
+            condition:      self.compound.as_ref(),
+            payout_mint:    self.payout_mint_terms(),
+            usd_payout:     self.usd_payout.as_ref().map(|u| (u.usd_cents, u.max_slippage_bps)),
+        };
+        sha256(&serde_json::to_vec(&terms).unwrap_or_default())
+    }
+
+    // The mint payout as it was asked for at setup
+    pub fn payout_mint_terms(&self) -> Option<MintAmount> {
+        self.payout_mint.map(|m| MintAmount { mint: m.mint, amount: m.amount })
+    }
+
+    // Payout still available to later triggers, net of the co-pay
+    pub fn coverage_remaining(&self) -> Ralo {
+        copay::net(self.payout_amount, self.copay_bps).saturating_sub(self.paid_out)
+    }
+
+    pub fn premium_outstanding(&self) -> Ralo {
+        self.premium_amount.saturating_sub(self.premium_paid)
+    }
+
+    // Premium sitting in escrow for a policy that has not activated yet.
+    pub fn escrowed_premium(&self) -> Ralo {
+        match self.status {
+            PolicyStatus::PendingPayment => self.premium_paid,
+            _ => Ralo::ZERO,
+        }
+    }
+
+    // Premium the owner could get back today. Only unactivated
+    // policies are refundable; once coverage starts the premium is earned.
+    pub fn refundable_premium(&self) -> Ralo {
+        self.escrowed_premium()
+    }
+
+    // Premium committed to coverage that can no longer be withdrawn.
+    pub fn locked_premium(&self) -> Ralo {
+        match self.status {
+            PolicyStatus::Active | PolicyStatus::PaidOut | PolicyStatus::Expired | PolicyStatus::PayoutDeferred | PolicyStatus::Cancelled
+            | PolicyStatus::PendingAttestation | PolicyStatus::PendingPayout => self.premium_paid,
+            _ => Ralo::ZERO,
+        }
+    }
+}
+
+// Fixed field order for the terms digest
+#[derive(Serialize)]
+struct CoverageTerms<'a> {
+    policy_id:      PolicyId,
+    underwriter_id: UnderwriterId,
+    template_id:    TemplateId,
+    beneficiary:    Pubkey,
+    location:       &'a str,
+    peril:          Metric,
+    threshold:      Hundredths,
+    payout:         Ralo,
+    premium:        Ralo,
+    coverage_start: Option<i64>,
+    coverage_end:   Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    curve:          Option<&'a PayoutCurve>,   // flat cover hashes as it did before curves
+    #[serde(skip_serializing_if = "Option::is_none")]
+    copay_bps:      Option<u64>,               // likewise cover without a co-pay
+    #[serde(skip_serializing_if = "Option::is_none")]
+    perils:         Option<&'a [BundledPeril]>,   // and single-peril cover
+    #[serde(skip_serializing_if = "Option::is_none")]
+    comparison:     Option<Comparison>,           // and cover paying at or above the threshold
+    #[serde(skip_serializing_if = "Option::is_none")]
+    route:          Option<RouteTerms>,           // and cover at one location
+    #[serde(skip_serializing_if = "Option::is_none")]
+    condition:      Option<&'a Condition>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    payout_mint:    Option<MintAmount>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    usd_payout:     Option<(u64, u64)>,           // USD cents, and the slippage limit they convert under
+}
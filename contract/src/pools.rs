@@ -0,0 +1,172 @@
+// ============================================================
+//  Risk pools
+//
+//  Underwriters that would rather share a book than each carry
+//  their own can pool capital. A pool is an ordinary tenant — its
+//  authority runs its templates, provider and pricing like any
+//  other — whose capital is owned by its members through pool
+//  shares:
+//    • join_pool moves free capital from a member tenant into the
+//      pool and issues shares at the pool's current value per
+//      share, so joiners neither gain nor lose by it
+//    • premiums the pool earns and payouts it makes land on its
+//      capital, so every member's slice of both is its share of
+//      the pool
+//    • exit_pool redeems shares at the current value, back into
+//      the member's own capital — up to the pool's free capital,
+//      so the reserve behind its live policies stays put
+//  Capital moves between tenants inside the vault; no tokens
+//  change hands. A pool's own capital only leaves through its
+//  members: the authority can't withdraw it (underwriter.rs).
+// ============================================================
+
+use std::collections::BTreeMap;
+
+use rialo_sdk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::InsuranceError;
+use crate::money::Ralo;
+use crate::underwriter::{tenant_mut, UnderwriterId};
+use crate::InsuranceState;
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct RiskPool {
+    pub members:      BTreeMap<UnderwriterId, u64>,   // pool shares held by each member tenant
+    pub total_shares: u64,
+}
+
+impl RiskPool {
+    // Shares `amount` buys into a pool holding `capital`; the first joiner
+    // gets one share per base unit
+    pub fn shares_for(&self, capital: Ralo, amount: Ralo) -> u64 {
+        if self.total_shares == 0 || capital.is_zero() {
+            return amount.base_units();
+        }
+        (amount.base_units() as u128 * self.total_shares as u128 / capital.base_units() as u128) as u64
+    }
+
+    // What `shares` of a pool holding `capital` are worth
+    pub fn value_of(&self, capital: Ralo, shares: u64) -> Ralo {
+        if self.total_shares == 0 {
+            return Ralo::ZERO;
+        }
+        Ralo((capital.base_units() as u128 * shares as u128 / self.total_shares as u128) as u64)
+    }
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct PoolMember {
+    pub member: UnderwriterId,
+    pub shares: u64,
+    pub value:  Ralo,   // at the pool's current capital
+}
+
+// ── Entry point: tenant turns itself into a risk pool ────────
+#[rialo::instruction]
+pub async fn create_risk_pool(
+    ctx:     Context<InsuranceState>,
+    pool_id: UnderwriterId,
+) -> RialoResult<()> {
+
+    let pooled = ctx.state.risk_pools.contains_key(&pool_id);
+    let underwriter = tenant_mut(&mut ctx.state, pool_id, &ctx.signer)?;
+
+    require!(!pooled, InsuranceError::InvalidState("Underwriter is already a risk pool.".into()));
+    require!(underwriter.capital.is_zero(), InsuranceError::InvalidArgument("A pool starts out empty; its capital comes from members.".into()));
+
+    ctx.state.risk_pools.insert(pool_id, RiskPool::default());
+
+    emit!(RiskPoolCreated { pool_id, authority: *ctx.signer });
+
+    Ok(())
+}
+
+// ── Entry point: member moves free capital into a pool ───────
+#[rialo::instruction]
+pub async fn join_pool(
+    ctx:       Context<InsuranceState>,
+    pool_id:   UnderwriterId,
+    member_id: UnderwriterId,
+    amount:    Ralo,
+) -> RialoResult<()> {
+
+    require!(pool_id != member_id, InsuranceError::InvalidState("A pool can't join itself.".into()));
+    require!(!ctx.state.risk_pools.contains_key(&member_id), InsuranceError::InvalidState("Members are tenants, not other pools.".into()));
+    let member = tenant_mut(&mut ctx.state, member_id, &ctx.signer)?;
+
+    require!(!amount.is_zero(), InsuranceError::InvalidArgument("Deposit must be non-zero.".into()));
+    require!(amount <= member.free_capital(), InsuranceError::VaultInsolvent("Deposit exceeds the member's unreserved capital.".into()));
+    member.capital -= amount;
+
+    let pool = ctx.state.risk_pools.get_mut(&pool_id).ok_or_else(|| InsuranceError::UnknownRecord("Unknown risk pool.".into()))?;
+    let underwriter = ctx.state.underwriters.get_mut(&pool_id).ok_or(InsuranceError::UnknownUnderwriter)?;
+    require!(pool.total_shares == 0 || !underwriter.capital.is_zero(), InsuranceError::InvalidState("Pool has lost its capital; its shares are worthless.".into()));
+
+    let shares = pool.shares_for(underwriter.capital, amount);
+    require!(shares > 0, InsuranceError::InvalidState("Deposit is too small to buy a pool share.".into()));
+    *pool.members.entry(member_id).or_default() += shares;
+    pool.total_shares   += shares;
+    underwriter.capital += amount;
+
+    emit!(PoolJoined { pool_id, member_id, amount, shares, total_shares: pool.total_shares });
+
+    Ok(())
+}
+
+// ── Entry point: member redeems pool shares for capital ──────
+#[rialo::instruction]
+pub async fn exit_pool(
+    ctx:       Context<InsuranceState>,
+    pool_id:   UnderwriterId,
+    member_id: UnderwriterId,
+    shares:    u64,
+) -> RialoResult<()> {
+
+    tenant_mut(&mut ctx.state, member_id, &ctx.signer)?;
+
+    let pool = ctx.state.risk_pools.get_mut(&pool_id).ok_or_else(|| InsuranceError::UnknownRecord("Unknown risk pool.".into()))?;
+    let held = pool.members.get(&member_id).copied().unwrap_or(0);
+    require!(shares > 0, InsuranceError::InvalidArgument("Redeem at least one share.".into()));
+    require!(shares <= held, InsuranceError::InvalidState("Member holds fewer pool shares than that.".into()));
+
+    let underwriter = ctx.state.underwriters.get_mut(&pool_id).ok_or(InsuranceError::UnknownUnderwriter)?;
+    let amount = pool.value_of(underwriter.capital, shares);
+    // Capital backing the pool's live policies stays in it
+    require!(amount <= underwriter.free_capital(), InsuranceError::VaultInsolvent("Pool's unreserved capital can't cover this redemption yet.".into()));
+
+    underwriter.capital -= amount;
+    pool.total_shares   -= shares;
+    if shares == held {
+        pool.members.remove(&member_id);
+    } else {
+        pool.members.insert(member_id, held - shares);
+    }
+
+    let member = ctx.state.underwriters.get_mut(&member_id).ok_or(InsuranceError::UnknownUnderwriter)?;
+    member.capital += amount;
+
+    emit!(PoolExited { pool_id, member_id, amount, shares, total_shares: pool.total_shares });
+
+    Ok(())
+}
+
+#[rialo::view]
+pub fn get_pool_members(
+    ctx:     Context<InsuranceState>,
+    pool_id: UnderwriterId,
+) -> RialoResult<Vec<PoolMember>> {
+
+    let pool = ctx.state.risk_pools.get(&pool_id).ok_or_else(|| InsuranceError::UnknownRecord("Unknown risk pool.".into()))?;
+    let capital = ctx.state.underwriters.get(&pool_id).map_or(Ralo::ZERO, |u| u.capital);
+
+    Ok(pool.members
+        .iter()
+        .map(|(&member, &shares)| PoolMember { member, shares, value: pool.value_of(capital, shares) })
+        .collect())
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct RiskPoolCreated { pub pool_id: UnderwriterId, pub authority: Pubkey }
+#[rialo::event] pub struct PoolJoined      { pub pool_id: UnderwriterId, pub member_id: UnderwriterId, pub amount: Ralo, pub shares: u64, pub total_shares: u64 }
+#[rialo::event] pub struct PoolExited      { pub pool_id: UnderwriterId, pub member_id: UnderwriterId, pub amount: Ralo, pub shares: u64, pub total_shares: u64 }
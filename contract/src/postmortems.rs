@@ -0,0 +1,112 @@
+// ============================================================
+//  Incident postmortems
+//
+//  When something goes wrong operationally — a provider outage, a
+//  bad-data period (incidents.rs), a misconfigured tenant — the
+//  admin files a postmortem on-chain: the window it covered, how
+//  many policies it touched, the hash of the published write-up
+//  and a reference to the remediation policy applied.
+//
+//  Filing one links it from every settlement receipt (receipts.rs)
+//  on a reading taken inside the window — from the named provider,
+//  if the postmortem names one — so a customer looking at a payout
+//  can find what was known to be wrong when it settled. Postmortems
+//  are never edited; a correction is filed as a new one.
+// ============================================================
+
+use rialo_sdk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::InsuranceError;
+use crate::receipts::SettlementReceipt;
+use crate::{Hash, InsuranceState};
+
+pub type PostmortemId = u64;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Postmortem {
+    pub source:            Option<String>,   // provider base URL, if the incident was a provider's
+    pub window_start:      i64,
+    pub window_end:        i64,
+    pub affected_policies: u32,
+    pub report_hash:       Hash,             // hash of the published write-up
+    pub remediation:       String,           // remediation policy applied, e.g. a document reference
+    pub recorded_at:       i64,
+    pub receipts_linked:   u32,              // settlement receipts it was linked from when filed
+}
+
+// Whether a receipt settled on a reading from inside the postmortem's window
+// (and from its provider, if it names one); `source` is where the reading came from
+pub fn touches(postmortem: &Postmortem, receipt: &SettlementReceipt, source: Option<&str>) -> bool {
+    let in_window = receipt.observed_at >= postmortem.window_start && receipt.observed_at < postmortem.window_end;
+    in_window && postmortem.source.as_deref().is_none_or(|named| source == Some(named))
+}
+
+// ── Entry point: admin files an incident postmortem ──────────
+#[rialo::instruction]
+pub async fn record_postmortem(
+    ctx:               Context<InsuranceState>,
+    source:            Option<String>,
+    window_start:      i64,
+    window_end:        i64,
+    affected_policies: u32,
+    report_hash:       Hash,
+    remediation:       String,
+) -> RialoResult<PostmortemId> {
+
+    let now = ctx.clock.unix_timestamp;
+    let state = &mut ctx.state;
+
+    require!(state.config.admin == *ctx.signer, InsuranceError::Unauthorized("Only the admin can record postmortems.".into()));
+    require!(window_end > window_start, InsuranceError::InvalidArgument("Incident window must end after it starts.".into()));
+    require!(report_hash != Hash::default(), InsuranceError::InvalidArgument("Postmortem report hash is empty.".into()));
+    require!(!remediation.trim().is_empty(), InsuranceError::InvalidState("Postmortem needs a remediation reference.".into()));
+
+    let postmortem_id = state.next_postmortem_id;
+    state.next_postmortem_id += 1;
+
+    let mut postmortem = Postmortem {
+        source,
+        window_start,
+        window_end,
+        affected_policies,
+        report_hash,
+        remediation,
+        recorded_at:     now,
+        receipts_linked: 0,
+    };
+
+    let checks = &state.checks;
+    for receipt in state.receipts.values_mut().flatten() {
+        let source = receipt.round_id.and_then(|id| checks.get(&id)).map(|r| r.source.as_str());
+        if touches(&postmortem, receipt, source) {
+            receipt.postmortems.push(postmortem_id);
+            postmortem.receipts_linked += 1;
+        }
+    }
+
+    emit!(PostmortemRecorded {
+        postmortem_id,
+        source:            postmortem.source.clone(),
+        window_start,
+        window_end,
+        affected_policies,
+        report_hash,
+        receipts_linked:   postmortem.receipts_linked,
+    });
+    state.postmortems.insert(postmortem_id, postmortem);
+
+    Ok(postmortem_id)
+}
+
+#[rialo::view]
+pub fn get_postmortem(
+    ctx:           Context<InsuranceState>,
+    postmortem_id: PostmortemId,
+) -> RialoResult<Postmortem> {
+
+    Ok(ctx.state.postmortems.get(&postmortem_id).ok_or_else(|| InsuranceError::UnknownRecord("Unknown postmortem.".into()))?.clone())
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct PostmortemRecorded { pub postmortem_id: PostmortemId, pub source: Option<String>, pub window_start: i64, pub window_end: i64, pub affected_policies: u32, pub report_hash: Hash, pub receipts_linked: u32 }
@@ -0,0 +1,97 @@
+// ============================================================
+//  Product documents
+//
+//  A purchase flow has to show the customer the wording that
+//  binds them, and it has to be the wording written for the
+//  parameters the contract will actually settle on. Each template
+//  carries its documents — policy wording, key facts, a schedule
+//  — as a URI and the SHA-256 of the file behind it, so a frontend
+//  can fetch the document, check the hash, and refuse to sell on
+//  anything else. The underwriter replaces the whole set at once;
+//  every change bumps the template's document version, which
+//  get_product_docs reports with the set.
+// ============================================================
+
+use rialo_sdk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::InsuranceError;
+use crate::underwriter::{tenant_mut, TemplateId, UnderwriterId};
+use crate::{Hash, InsuranceState};
+
+pub const MAX_PRODUCT_DOCS:  usize = 8;
+pub const MAX_DOC_LABEL_LEN: usize = 64;
+pub const MAX_DOC_URI_LEN:   usize = 256;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ProductDoc {
+    pub label: String,   // what the document is, e.g. "Policy wording"
+    pub uri:   String,   // where it's published: https:// or ipfs://
+    pub hash:  Hash,     // SHA-256 of the document's bytes
+}
+
+impl ProductDoc {
+    pub fn validate(&self) -> RialoResult<()> {
+        require!(!self.label.trim().is_empty() && self.label.len() <= MAX_DOC_LABEL_LEN, InsuranceError::InvalidArgument("Document label must be 1 to 64 bytes.".into()));
+        require!(self.uri.len() <= MAX_DOC_URI_LEN, InsuranceError::InvalidArgument("Document URI is longer than 256 bytes.".into()));
+        require!(self.uri.starts_with("https://") || self.uri.starts_with("ipfs://"), InsuranceError::InvalidArgument("Document URI must be https:// or ipfs://.".into()));
+        require!(self.hash != [0; 32], InsuranceError::InvalidArgument("Document hash is missing.".into()));
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct ProductDocs {
+    pub underwriter_id: UnderwriterId,
+    pub template_id:    TemplateId,
+    pub name:           String,
+    pub version:        u32,               // bumped by every change to the set
+    pub docs:           Vec<ProductDoc>,
+}
+
+// ── Entry point: underwriter publishes a template's documents
+#[rialo::instruction]
+pub async fn set_template_docs(
+    ctx:            Context<InsuranceState>,
+    underwriter_id: UnderwriterId,
+    template_id:    TemplateId,
+    docs:           Vec<ProductDoc>,
+) -> RialoResult<()> {
+
+    require!(docs.len() <= MAX_PRODUCT_DOCS, InsuranceError::InvalidArgument(format!("A template carries at most {MAX_PRODUCT_DOCS} documents.")));
+    for (i, doc) in docs.iter().enumerate() {
+        doc.validate()?;
+        require!(docs[..i].iter().all(|d| d.label != doc.label), InsuranceError::InvalidArgument("Document labels must be distinct.".into()));
+    }
+
+    let underwriter = tenant_mut(&mut ctx.state, underwriter_id, &ctx.signer)?;
+    let template = underwriter.templates.get_mut(&template_id).ok_or(InsuranceError::UnknownTemplate)?;
+    template.docs = docs;
+    template.docs_version += 1;
+
+    emit!(TemplateDocsSet { underwriter_id, template_id, version: template.docs_version, docs: template.docs.clone() });
+
+    Ok(())
+}
+
+#[rialo::view]
+pub fn get_product_docs(
+    ctx:            Context<InsuranceState>,
+    underwriter_id: UnderwriterId,
+    template_id:    TemplateId,
+) -> RialoResult<ProductDocs> {
+
+    let underwriter = ctx.state.underwriters.get(&underwriter_id).ok_or(InsuranceError::UnknownUnderwriter)?;
+    let template = underwriter.templates.get(&template_id).ok_or(InsuranceError::UnknownTemplate)?;
+
+    Ok(ProductDocs {
+        underwriter_id,
+        template_id,
+        name:    template.name.clone(),
+        version: template.docs_version,
+        docs:    template.docs.clone(),
+    })
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct TemplateDocsSet { pub underwriter_id: UnderwriterId, pub template_id: TemplateId, pub version: u32, pub docs: Vec<ProductDoc> }
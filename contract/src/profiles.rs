@@ -0,0 +1,109 @@
+// ============================================================
+//  Provider profiles
+//
+//  Enterprise data licenses usually identify the licensee with
+//  custom request headers (client ID, contract number, licence
+//  key). An underwriter defines named profiles of such headers and
+//  points templates at them, so each product line can query its
+//  own licensed feed. Header values can be stored inline or name
+//  a secret in sealed storage, which is only read at request time
+//  and never lands in contract state or events.
+// ============================================================
+
+use rialo_sdk::prelude::*;
+use rialo_sdk::secrets;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::InsuranceError;
+use crate::underwriter::{tenant_mut, TemplateId, UnderwriterId};
+use crate::InsuranceState;
+
+pub type ProfileId = u64;
+
+const MAX_PROFILE_HEADERS: usize = 8;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub enum HeaderValue {
+    Plain(String),    // non-sensitive, e.g. a public client ID
+    Sealed(String),   // name of a secret in sealed storage
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct HeaderSpec {
+    pub name:  String,
+    pub value: HeaderValue,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ProviderProfile {
+    pub name:    String,            // e.g. "Acme Weather enterprise"
+    pub headers: Vec<HeaderSpec>,
+}
+
+impl ProviderProfile {
+    // Header pairs to send, with sealed values read just in time
+    pub fn resolve_headers(&self) -> RialoResult<Vec<(String, String)>> {
+        self.headers
+            .iter()
+            .map(|h| {
+                let value = match &h.value {
+                    HeaderValue::Plain(v)  => v.clone(),
+                    HeaderValue::Sealed(s) => secrets::get(s)?,
+                };
+                Ok((h.name.clone(), value))
+            })
+            .collect()
+    }
+}
+
+// ── Entry point: underwriter defines a header profile ────────
+#[rialo::instruction]
+pub async fn add_provider_profile(
+    ctx:            Context<InsuranceState>,
+    underwriter_id: UnderwriterId,
+    name:           String,
+    headers:        Vec<HeaderSpec>,
+) -> RialoResult<ProfileId> {
+
+    require!(headers.len() <= MAX_PROFILE_HEADERS, InsuranceError::InvalidArgument("Too many headers in profile.".into()));
+    require!(headers.iter().all(|h| !h.name.is_empty()), InsuranceError::InvalidArgument("Header names must be non-empty.".into()));
+
+    let underwriter = tenant_mut(&mut ctx.state, underwriter_id, &ctx.signer)?;
+
+    let profile_id = underwriter.next_profile_id;
+    underwriter.next_profile_id += 1;
+
+    // Only header names are published — values may be licence terms
+    let header_names = headers.iter().map(|h| h.name.clone()).collect();
+    underwriter.profiles.insert(profile_id, ProviderProfile { name: name.clone(), headers });
+
+    emit!(ProviderProfileAdded { underwriter_id, profile_id, name, header_names });
+
+    Ok(profile_id)
+}
+
+// ── Entry point: attach (or detach) a profile to a template ──
+#[rialo::instruction]
+pub async fn set_template_profile(
+    ctx:            Context<InsuranceState>,
+    underwriter_id: UnderwriterId,
+    template_id:    TemplateId,
+    profile_id:     Option<ProfileId>,
+) -> RialoResult<()> {
+
+    let underwriter = tenant_mut(&mut ctx.state, underwriter_id, &ctx.signer)?;
+
+    if let Some(id) = profile_id {
+        require!(underwriter.profiles.contains_key(&id), InsuranceError::UnknownRecord("Unknown provider profile.".into()));
+    }
+    let template = underwriter.templates.get_mut(&template_id).ok_or(InsuranceError::UnknownTemplate)?;
+    template.provider_profile = profile_id;
+
+    emit!(TemplateProfileSet { underwriter_id, template_id, profile_id });
+
+    Ok(())
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct ProviderProfileAdded { pub underwriter_id: UnderwriterId, pub profile_id: ProfileId, pub name: String, pub header_names: Vec<String> }
+#[rialo::event] pub struct TemplateProfileSet   { pub underwriter_id: UnderwriterId, pub template_id: TemplateId, pub profile_id: Option<ProfileId> }
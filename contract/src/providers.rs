@@ -0,0 +1,508 @@
+// ============================================================
+//  Weather providers
+//
+//  Every weather API a tenant (or the audit pool) can point at
+//  implements `WeatherProvider`: how to ask for a reading, how to
+//  read the answer, how it geocodes a location, what it forecasts
+//  (forecasts.rs) and which perils it can back. `ProviderKind`
+//  is the enum stored in a `ProviderConfig` and dispatches to the
+//  implementations, so adding a provider means one new impl and
+//  one new variant — and `supports` matches on every `Metric`
+//  without a wildcard, so a new peril doesn't compile until each
+//  provider has said whether it can read it. Requests pin their
+//  units and language (oracle.rs), so a provider changing its
+//  defaults can't change what a reading means. City-level data can
+//  be too coarse for crop cover; Weatherbit also answers for one
+//  named station, so a policy there can be pinned to the station
+//  beside the field (Location::Station).
+// ============================================================
+
+use rialo_sdk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::InsuranceError;
+use crate::forecasts::{chance_bps, ForecastSlot};
+use crate::geo::GeoPoint;
+use crate::millimeters::Millimeters;
+use crate::normalization::{ms_to_kmh, Location, Metric, Observation, Station};
+use crate::oracle;
+use crate::seasonal::civil_date;
+
+const SECS_PER_HOUR: i64 = 60 * 60;
+
+// Language pinned on every WeatherAPI.com request; it has no units switch,
+// answering in fields named for their unit (`precip_mm`, `temp_c`)
+const WEATHERAPI_PINNED_PARAMS: &str = "lang=en";
+
+// Metric units (mm, °C, m/s) and English on every Weatherbit request
+const WEATHERBIT_PINNED_PARAMS: &str = "units=M&lang=en";
+
+// Which reading a request is for
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReadingTime {
+    Current,
+    Hour(i64),   // the hour containing this unix time
+}
+
+pub trait WeatherProvider {
+    fn build_request(&self, base_url: &str, api_key: &str, location: &Location, when: ReadingTime) -> String;
+    fn parse_observation(&self, when: ReadingTime, body: &[u8]) -> RialoResult<Observation>;
+    // Where the provider places a location name (see geocoding.rs)
+    fn geocode_request(&self, base_url: &str, api_key: &str, location: &str) -> String;
+    fn parse_geocode(&self, body: &[u8]) -> RialoResult<GeoPoint>;
+    // Upcoming periods and their chance of rain
+    fn forecast_request(&self, base_url: &str, api_key: &str, location: &Location) -> String;
+    fn parse_forecast(&self, body: &[u8]) -> RialoResult<Vec<ForecastSlot>>;
+    // Whether policies on `peril` can be written against this provider
+    fn supports(&self, peril: Metric) -> bool;
+    // Whether requests can name a single weather station (Location::Station)
+    fn queries_stations(&self) -> bool;
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ProviderKind {
+    #[default]
+    OpenWeatherMap,
+    WeatherApi,       // weatherapi.com
+    Weatherbit,       // weatherbit.io
+}
+
+impl ProviderKind {
+    fn provider(self) -> &'static dyn WeatherProvider {
+        match self {
+            ProviderKind::OpenWeatherMap => &OpenWeatherMap,
+            ProviderKind::WeatherApi     => &WeatherApi,
+            ProviderKind::Weatherbit     => &Weatherbit,
+        }
+    }
+}
+
+impl WeatherProvider for ProviderKind {
+    fn build_request(&self, base_url: &str, api_key: &str, location: &Location, when: ReadingTime) -> String {
+        self.provider().build_request(base_url, api_key, location, when)
+    }
+
+    fn parse_observation(&self, when: ReadingTime, body: &[u8]) -> RialoResult<Observation> {
+        self.provider().parse_observation(when, body)
+    }
+
+    fn geocode_request(&self, base_url: &str, api_key: &str, location: &str) -> String {
+        self.provider().geocode_request(base_url, api_key, location)
+    }
+
+    fn parse_geocode(&self, body: &[u8]) -> RialoResult<GeoPoint> {
+        self.provider().parse_geocode(body)
+    }
+
+    fn forecast_request(&self, base_url: &str, api_key: &str, location: &Location) -> String {
+        self.provider().forecast_request(base_url, api_key, location)
+    }
+
+    fn parse_forecast(&self, body: &[u8]) -> RialoResult<Vec<ForecastSlot>> {
+        self.provider().parse_forecast(body)
+    }
+
+    fn supports(&self, peril: Metric) -> bool {
+        self.provider().supports(peril)
+    }
+
+    fn queries_stations(&self) -> bool {
+        self.provider().queries_stations()
+    }
+}
+
+// ── OpenWeatherMap ───────────────────────────────────────────
+pub struct OpenWeatherMap;
+
+impl WeatherProvider for OpenWeatherMap {
+    fn build_request(&self, base_url: &str, api_key: &str, location: &Location, when: ReadingTime) -> String {
+        match when {
+            ReadingTime::Current  => oracle::current_weather_url(base_url, location, api_key),
+            ReadingTime::Hour(at) => oracle::historical_weather_url(base_url, location, at, api_key),
+        }
+    }
+
+    fn parse_observation(&self, when: ReadingTime, body: &[u8]) -> RialoResult<Observation> {
+        match when {
+            ReadingTime::Current  => oracle::parse_observation(body),
+            ReadingTime::Hour(_)  => oracle::parse_historical_observation(body),
+        }
+    }
+
+    fn geocode_request(&self, base_url: &str, api_key: &str, location: &str) -> String {
+        format!("{base_url}/geo/1.0/direct?q={location}&limit=1&appid={api_key}")
+    }
+
+    fn parse_geocode(&self, body: &[u8]) -> RialoResult<GeoPoint> {
+        first_match(body)
+    }
+
+    fn forecast_request(&self, base_url: &str, api_key: &str, location: &Location) -> String {
+        oracle::forecast_url(base_url, location, api_key)
+    }
+
+    fn parse_forecast(&self, body: &[u8]) -> RialoResult<Vec<ForecastSlot>> {
+        Ok(oracle::parse_forecast(body)?
+            .iter()
+            .map(|step| ForecastSlot { start: step.start, secs: oracle::FORECAST_STEP_SECS, rain_chance_bps: chance_bps(step.rain_chance) })
+            .collect())
+    }
+
+    fn supports(&self, peril: Metric) -> bool {
+        match peril {
+            Metric::Rainfall    => true,
+            #[cfg(feature = "cold-chain")]
+            Metric::Temperature => true,
+            #[cfg(feature = "heat")]
+            Metric::HeatIndex   => true,
+            #[cfg(feature = "heat")]
+            Metric::HeatHours | Metric::UvHours => true,   // One Call
+            #[cfg(feature = "wind")]
+            Metric::WindChill   => true,
+            #[cfg(feature = "wind")]
+            Metric::WindSpeed   => true,
+            #[cfg(feature = "snow")]
+            Metric::Snowfall    => true,
+            #[cfg(feature = "air-quality")]
+            Metric::AirQuality  => true,                   // air-pollution endpoint
+            #[cfg(feature = "degree-days")]
+            Metric::DegreeDays  => true,                   // current temperature
+            // Read from the shared feeds, whatever the tenant's provider
+            #[cfg(feature = "storm")]
+            Metric::StormTrack  => true,
+            #[cfg(feature = "flood")]
+            Metric::RiverLevel  => true,
+        }
+    }
+
+    fn queries_stations(&self) -> bool {
+        false
+    }
+}
+
+// ── WeatherAPI.com ───────────────────────────────────────────
+pub struct WeatherApi;
+
+#[derive(Deserialize)]
+struct WeatherApiCurrent {
+    location: Option<WeatherApiLocation>,
+    current:  WeatherApiHour,
+}
+
+// History and forecast responses share a shape
+#[derive(Deserialize)]
+struct WeatherApiHistory {
+    location: Option<WeatherApiLocation>,
+    forecast: WeatherApiForecast,
+}
+
+// No station ids: WeatherAPI.com interpolates to the resolved point
+#[derive(Deserialize)]
+struct WeatherApiLocation {
+    lat: Option<f64>,
+    lon: Option<f64>,
+}
+
+impl WeatherApiLocation {
+    fn station(location: Option<&WeatherApiLocation>) -> Station {
+        Station {
+            station_id:  None,
+            coord:       location.and_then(|l| GeoPoint::from_degrees(l.lat?, l.lon?)),
+            data_source: None,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct WeatherApiForecast {
+    forecastday: Vec<WeatherApiDay>,
+}
+
+#[derive(Deserialize)]
+struct WeatherApiDay {
+    hour: Vec<WeatherApiHour>,
+}
+
+#[derive(Deserialize)]
+struct WeatherApiHour {
+    time_epoch:     Option<i64>,   // history and forecast only
+    precip_mm:      f64,
+    temp_c:         Option<f64>,
+    humidity:       Option<f64>,
+    wind_kph:       Option<f64>,
+    chance_of_rain: Option<f64>,   // forecast only, percent
+}
+
+impl WeatherApiHour {
+    fn observation(&self) -> Observation {
+        Observation::new(Millimeters::from_mm(self.precip_mm), self.temp_c, self.humidity, self.wind_kph)
+    }
+}
+
+impl WeatherProvider for WeatherApi {
+    fn build_request(&self, base_url: &str, api_key: &str, location: &Location, when: ReadingTime) -> String {
+        match when {
+            // q takes a name, "lat,lon" or "id:<city id>" — the forms of Location::text
+            ReadingTime::Current  => format!("{base_url}/v1/current.json?key={api_key}&q={}&{WEATHERAPI_PINNED_PARAMS}", location.text()),
+            ReadingTime::Hour(at) => format!("{base_url}/v1/history.json?key={api_key}&q={}&unixdt={at}&{WEATHERAPI_PINNED_PARAMS}", location.text()),
+        }
+    }
+
+    fn parse_observation(&self, when: ReadingTime, body: &[u8]) -> RialoResult<Observation> {
+        match when {
+            ReadingTime::Current => {
+                let response: WeatherApiCurrent = serde_json::from_slice(body)
+                    .map_err(|_| InsuranceError::BadResponse("Malformed weather response.".into()))?;
+                let station = WeatherApiLocation::station(response.location.as_ref());
+                Ok(response.current.observation().with_station(station))
+            }
+            // History returns the whole local day; pick the requested hour
+            ReadingTime::Hour(at) => {
+                let response: WeatherApiHistory = serde_json::from_slice(body)
+                    .map_err(|_| InsuranceError::BadResponse("Malformed weather history response.".into()))?;
+                let hour_start = at - at.rem_euclid(SECS_PER_HOUR);
+                let station = WeatherApiLocation::station(response.location.as_ref());
+                let observation = response.forecast.forecastday
+                    .iter()
+                    .flat_map(|day| &day.hour)
+                    .find(|hour| hour.time_epoch == Some(hour_start))
+                    .map(|hour| hour.observation().with_station(station))
+                    .ok_or_else(|| InsuranceError::BadResponse("Weather history response has no reading for that hour.".into()))?;
+                Ok(observation)
+            }
+        }
+    }
+
+    fn geocode_request(&self, base_url: &str, api_key: &str, location: &str) -> String {
+        format!("{base_url}/v1/search.json?key={api_key}&q={location}")
+    }
+
+    fn parse_geocode(&self, body: &[u8]) -> RialoResult<GeoPoint> {
+        first_match(body)
+    }
+
+    fn forecast_request(&self, base_url: &str, api_key: &str, location: &Location) -> String {
+        format!("{base_url}/v1/forecast.json?key={api_key}&q={}&days=3&{WEATHERAPI_PINNED_PARAMS}", location.text())
+    }
+
+    fn parse_forecast(&self, body: &[u8]) -> RialoResult<Vec<ForecastSlot>> {
+        let response: WeatherApiHistory = serde_json::from_slice(body)
+            .map_err(|_| InsuranceError::BadResponse("Malformed forecast response.".into()))?;
+        Ok(response.forecast.forecastday
+            .iter()
+            .flat_map(|day| &day.hour)
+            .filter_map(|hour| Some(ForecastSlot {
+                start:           hour.time_epoch?,
+                secs:            SECS_PER_HOUR,
+                rain_chance_bps: chance_bps(hour.chance_of_rain? / 100.0),
+            }))
+            .collect())
+    }
+
+    fn supports(&self, peril: Metric) -> bool {
+        match peril {
+            Metric::Rainfall    => true,
+            #[cfg(feature = "cold-chain")]
+            Metric::Temperature => true,
+            #[cfg(feature = "heat")]
+            Metric::HeatIndex   => true,
+            #[cfg(feature = "heat")]
+            Metric::HeatHours | Metric::UvHours => false,
+            #[cfg(feature = "wind")]
+            Metric::WindChill   => true,
+            #[cfg(feature = "wind")]
+            Metric::WindSpeed   => true,
+            #[cfg(feature = "snow")]
+            Metric::Snowfall    => false,                  // current.json has no snow reading
+            #[cfg(feature = "air-quality")]
+            Metric::AirQuality  => false,
+            #[cfg(feature = "degree-days")]
+            Metric::DegreeDays  => true,
+            #[cfg(feature = "storm")]
+            Metric::StormTrack  => true,
+            #[cfg(feature = "flood")]
+            Metric::RiverLevel  => true,
+        }
+    }
+
+    fn queries_stations(&self) -> bool {
+        false
+    }
+}
+
+// ── Weatherbit ───────────────────────────────────────────────
+pub struct Weatherbit;
+
+// Current, history and forecast responses all list readings under `data`
+#[derive(Deserialize)]
+struct WeatherbitResponse {
+    data: Vec<WeatherbitReading>,
+}
+
+#[derive(Deserialize)]
+struct WeatherbitReading {
+    ts:       Option<i64>,      // history and forecast only
+    precip:   Option<f64>,      // mm in the hour; absent or null when dry
+    snow:     Option<f64>,      // mm in the hour
+    temp:     Option<f64>,
+    rh:       Option<f64>,
+    wind_spd: Option<f64>,      // m/s
+    pop:      Option<f64>,      // forecast only, percent
+    station:  Option<String>,   // nearest reporting station, or the one asked for
+    lat:      Option<f64>,
+    lon:      Option<f64>,
+}
+
+impl WeatherbitReading {
+    fn observation(&self) -> Observation {
+        let station = Station {
+            station_id:  self.station.clone(),
+            coord:       self.lat.zip(self.lon).and_then(|(lat, lon)| GeoPoint::from_degrees(lat, lon)),
+            data_source: None,
+        };
+        Observation::new(
+            Millimeters::from_mm(self.precip.unwrap_or(0.0)),
+            self.temp,
+            self.rh,
+            self.wind_spd.map(ms_to_kmh),
+        )
+        .with_snowfall(Some(self.snow.unwrap_or(0.0)))
+        .with_station(station)
+    }
+}
+
+impl Weatherbit {
+    fn location_query(location: &Location) -> String {
+        match location {
+            Location::City(name)  => format!("city={name}"),
+            Location::Point(p)    => format!("lat={}&lon={}", p.lat_e6 as f64 / 1e6, p.lon_e6 as f64 / 1e6),
+            Location::CityId(id)  => format!("city_id={id}"),
+            Location::Station(id) => format!("station={id}"),
+        }
+    }
+
+    // History dates are UTC hours, "YYYY-MM-DD:HH"
+    fn hour_param(at: i64) -> String {
+        let (year, month, day) = civil_date(at);
+        format!("{year:04}-{:02}-{day:02}:{:02}", month + 1, at.rem_euclid(24 * SECS_PER_HOUR) / SECS_PER_HOUR)
+    }
+
+    fn parse(body: &[u8], malformed: &'static str) -> RialoResult<Vec<WeatherbitReading>> {
+        let response: WeatherbitResponse = serde_json::from_slice(body).map_err(|_| InsuranceError::BadResponse(malformed.into()))?;
+        Ok(response.data)
+    }
+}
+
+impl WeatherProvider for Weatherbit {
+    fn build_request(&self, base_url: &str, api_key: &str, location: &Location, when: ReadingTime) -> String {
+        let query = Weatherbit::location_query(location);
+        match when {
+            ReadingTime::Current  => format!("{base_url}/v2.0/current?{query}&key={api_key}&{WEATHERBIT_PINNED_PARAMS}"),
+            ReadingTime::Hour(at) => {
+                let hour_start = at - at.rem_euclid(SECS_PER_HOUR);
+                format!(
+                    "{base_url}/v2.0/history/hourly?{query}&start_date={}&end_date={}&tz=utc&key={api_key}&{WEATHERBIT_PINNED_PARAMS}",
+                    Weatherbit::hour_param(hour_start),
+                    Weatherbit::hour_param(hour_start + SECS_PER_HOUR),
+                )
+            }
+        }
+    }
+
+    fn parse_observation(&self, when: ReadingTime, body: &[u8]) -> RialoResult<Observation> {
+        match when {
+            ReadingTime::Current => {
+                let data = Weatherbit::parse(body, "Malformed weather response.")?;
+                let reading = data.first().ok_or_else(|| InsuranceError::BadResponse("Weather response has no reading.".into()))?;
+                oracle::require_metric(reading.temp)?;
+                Ok(reading.observation())
+            }
+            ReadingTime::Hour(at) => {
+                let data = Weatherbit::parse(body, "Malformed weather history response.")?;
+                let hour_start = at - at.rem_euclid(SECS_PER_HOUR);
+                let reading = data
+                    .iter()
+                    .find(|reading| reading.ts == Some(hour_start))
+                    .ok_or_else(|| InsuranceError::BadResponse("Weather history response has no reading for that hour.".into()))?;
+                oracle::require_metric(reading.temp)?;
+                Ok(reading.observation())
+            }
+        }
+    }
+
+    // No geocoder of its own: current conditions for the name say where it resolved
+    fn geocode_request(&self, base_url: &str, api_key: &str, location: &str) -> String {
+        format!("{base_url}/v2.0/current?city={location}&key={api_key}&{WEATHERBIT_PINNED_PARAMS}")
+    }
+
+    fn parse_geocode(&self, body: &[u8]) -> RialoResult<GeoPoint> {
+        let data = Weatherbit::parse(body, "Malformed geocoding response.")?;
+        let best = data.first().ok_or_else(|| InsuranceError::BadResponse("Geocoder doesn't know this location.".into()))?;
+        let point = best.lat
+            .zip(best.lon)
+            .and_then(|(lat, lon)| GeoPoint::from_degrees(lat, lon))
+            .ok_or_else(|| InsuranceError::BadResponse("Geocoder returned coordinates off the globe.".into()))?;
+        Ok(point)
+    }
+
+    fn forecast_request(&self, base_url: &str, api_key: &str, location: &Location) -> String {
+        format!("{base_url}/v2.0/forecast/hourly?{}&hours=72&key={api_key}&{WEATHERBIT_PINNED_PARAMS}", Weatherbit::location_query(location))
+    }
+
+    fn parse_forecast(&self, body: &[u8]) -> RialoResult<Vec<ForecastSlot>> {
+        Ok(Weatherbit::parse(body, "Malformed forecast response.")?
+            .iter()
+            .filter_map(|hour| Some(ForecastSlot {
+                start:           hour.ts?,
+                secs:            SECS_PER_HOUR,
+                rain_chance_bps: chance_bps(hour.pop? / 100.0),
+            }))
+            .collect())
+    }
+
+    fn supports(&self, peril: Metric) -> bool {
+        match peril {
+            Metric::Rainfall    => true,
+            #[cfg(feature = "cold-chain")]
+            Metric::Temperature => true,
+            #[cfg(feature = "heat")]
+            Metric::HeatIndex   => true,
+            #[cfg(feature = "heat")]
+            Metric::HeatHours | Metric::UvHours => false,
+            #[cfg(feature = "wind")]
+            Metric::WindChill   => true,
+            #[cfg(feature = "wind")]
+            Metric::WindSpeed   => true,
+            #[cfg(feature = "snow")]
+            Metric::Snowfall    => true,
+            #[cfg(feature = "air-quality")]
+            Metric::AirQuality  => false,                  // a separate air-quality API
+            #[cfg(feature = "degree-days")]
+            Metric::DegreeDays  => true,
+            #[cfg(feature = "storm")]
+            Metric::StormTrack  => true,
+            #[cfg(feature = "flood")]
+            Metric::RiverLevel  => true,
+        }
+    }
+
+    fn queries_stations(&self) -> bool {
+        true
+    }
+}
+
+// OpenWeatherMap's and WeatherAPI.com's geocoders answer with a best-first list of places
+#[derive(Deserialize)]
+struct GeocodeMatch {
+    lat: f64,
+    lon: f64,
+}
+
+fn first_match(body: &[u8]) -> RialoResult<GeoPoint> {
+    let matches: Vec<GeocodeMatch> = serde_json::from_slice(body)
+        .map_err(|_| InsuranceError::BadResponse("Malformed geocoding response.".into()))?;
+    let best = matches.first().ok_or_else(|| InsuranceError::BadResponse("Geocoder doesn't know this location.".into()))?;
+    let point = GeoPoint::from_degrees(best.lat, best.lon)
+        .ok_or_else(|| InsuranceError::BadResponse("Geocoder returned coordinates off the globe.".into()))?;
+    Ok(point)
+}
@@ -0,0 +1,291 @@
+// ============================================================
+//  Quoting and pre-flight validation
+//
+//  The checks on the terms themselves — caps, template terms,
+//  seasonal pricing, capital and the dust floors — live in
+//  `quote`, so on those `setup_policy` and the `preflight_policy`
+//  view can't disagree. Failures are typed: a frontend gets the
+//  variant (and the limit it hit) from the view without parsing
+//  messages, and the same variant comes back as the
+//  instruction's error code.
+//
+//  Setup checks more than the quote does, and the view doesn't
+//  see those: whether the provider can read a station, and the
+//  checks that turn on the buyer or the extras — the whitelist,
+//  the commission, duplicate cover, the cap on what one
+//  beneficiary is owed and on how many policies they hold
+//  (concentration.rs), self-dealing, the quote of each stop of a
+//  route and the mint pool's free tokens. A clean preflight
+//  can still be refused on any of them.
+//
+//  Threshold pricing: a template can name the threshold its base
+//  rate is for (`base_threshold`). A policy written at a higher
+//  threshold triggers less often and pays a proportionally lower
+//  rate; a lower one, a higher rate — within 0.25× to 4×.
+//  Cover that pays at or below its threshold (frost, drought) is
+//  priced at the base rate whatever the threshold, and the
+//  rainfall floors don't apply to it.
+//
+//  Dust floors: a policy whose premium is smaller than what its
+//  storage and provider calls cost the book is turned away at
+//  quote time (`min_premium`, `min_payout` in the config).
+// ============================================================
+
+use rialo_sdk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::consensus::{needs_diverse_sources, tenant_providers, MIN_DIVERSE_PROVIDERS};
+use crate::curves::PayoutCurve;
+use crate::millimeters::Hundredths;
+use crate::money::{format_ralo, Ralo};
+use crate::normalization::{format_mm, Comparison, Location, Metric};
+use crate::policy::PayoutSpec;
+use crate::providers::WeatherProvider;
+use crate::seasonal::{coverage_factor_bps, local_time, NEUTRAL_FACTOR_BPS};
+use crate::smoothing::can_smooth;
+use crate::underwriter::{TemplateId, UnderwriterId};
+use crate::InsuranceState;
+
+// Error codes start here so they don't collide with the SDK's own
+const QUOTE_ERROR_BASE: u32 = 6_000;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum QuoteError {
+    LocationRequired,
+    CoverageNotPositive,
+    CoverageTooLong { max_secs: i64 },
+    UnknownUnderwriter,
+    UnknownTemplate,
+    TemplateRetired,
+    UnsupportedPeril,
+    MultipleBelowOne,
+    MultipleOverflow,
+    PremiumBelowRate,
+    ThresholdBelowNetworkMin { min: Hundredths },
+    ThresholdBelowTemplateMin { min: Hundredths },
+    PayoutAboveNetworkMax { max: Ralo },
+    PayoutAboveTemplateMax { max: Ralo },
+    PayoutBelowFloor { min: Ralo },
+    PremiumBelowFloor { min: Ralo },
+    InsufficientCapital,
+    EvaluatorNotApproved,
+    NoRainfallNormal,
+    InvalidPayoutCurve,
+    CurveNeedsPlainTrigger,
+    BundleNeedsPlainTrigger,
+    NeedsDiverseSources { providers: u32 },
+    ForecastNeedsPlainTrigger,
+    SmoothingNeedsSingleReadings,
+    InvalidDeductible,
+}
+
+impl QuoteError {
+    pub fn code(&self) -> u32 {
+        let index = match self {
+            QuoteError::LocationRequired                 => 0,
+            QuoteError::CoverageNotPositive              => 1,
+            QuoteError::CoverageTooLong { .. }           => 2,
+            QuoteError::UnknownUnderwriter               => 3,
+            QuoteError::UnknownTemplate                  => 4,
+            QuoteError::TemplateRetired                  => 5,
+            QuoteError::UnsupportedPeril                 => 6,
+            QuoteError::MultipleBelowOne                 => 7,
+            QuoteError::MultipleOverflow                 => 8,
+            QuoteError::PremiumBelowRate                 => 9,
+            QuoteError::ThresholdBelowNetworkMin { .. }  => 10,
+            QuoteError::ThresholdBelowTemplateMin { .. } => 11,
+            QuoteError::PayoutAboveNetworkMax { .. }     => 12,
+            QuoteError::PayoutAboveTemplateMax { .. }    => 13,
+            QuoteError::PayoutBelowFloor { .. }          => 14,
+            QuoteError::PremiumBelowFloor { .. }         => 15,
+            QuoteError::InsufficientCapital              => 16,
+            QuoteError::EvaluatorNotApproved             => 17,
+            QuoteError::NoRainfallNormal                 => 18,
+            QuoteError::InvalidPayoutCurve               => 19,
+            QuoteError::CurveNeedsPlainTrigger           => 20,
+            QuoteError::BundleNeedsPlainTrigger          => 21,
+            QuoteError::NeedsDiverseSources { .. }       => 22,
+            QuoteError::ForecastNeedsPlainTrigger        => 23,
+            QuoteError::SmoothingNeedsSingleReadings     => 24,
+            QuoteError::InvalidDeductible                => 25,
+        };
+        QUOTE_ERROR_BASE + index
+    }
+
+    pub fn message(&self) -> String {
+        match self {
+            QuoteError::LocationRequired                     => "Location is required.".into(),
+            QuoteError::CoverageNotPositive                  => "Coverage must last longer than zero seconds.".into(),
+            QuoteError::CoverageTooLong { .. }               => "Coverage exceeds the maximum policy duration.".into(),
+            QuoteError::UnknownUnderwriter                   => "Unknown underwriter.".into(),
+            QuoteError::UnknownTemplate                      => "Unknown template.".into(),
+            QuoteError::TemplateRetired                      => "Template is no longer offered.".into(),
+            QuoteError::UnsupportedPeril                     => "Underwriter's weather provider can't back this peril.".into(),
+            QuoteError::MultipleBelowOne                     => "Payout multiple must be at least 1x the premium.".into(),
+            QuoteError::MultipleOverflow                     => "Payout multiple overflows.".into(),
+            QuoteError::PremiumBelowRate                     => "Premium is too low for this payout multiple.".into(),
+            QuoteError::ThresholdBelowNetworkMin { min }     => format!("Threshold is below the network minimum of {}.", format_mm(min.to_f64())),
+            QuoteError::ThresholdBelowTemplateMin { min }    => format!("Threshold is below the template minimum of {}.", format_mm(min.to_f64())),
+            QuoteError::PayoutAboveNetworkMax { max }        => format!("Payout exceeds the network maximum of {}.", format_ralo(*max)),
+            QuoteError::PayoutAboveTemplateMax { max }       => format!("Payout exceeds the template maximum of {}.", format_ralo(*max)),
+            QuoteError::PayoutBelowFloor { min }             => format!("Payout is below the minimum of {}.", format_ralo(*min)),
+            QuoteError::PremiumBelowFloor { min }            => format!("Premium is below the minimum of {}.", format_ralo(*min)),
+            QuoteError::InsufficientCapital                  => "Underwriter vault cannot cover this payout.".into(),
+            QuoteError::EvaluatorNotApproved                 => "Template's custom evaluator is not approved.".into(),
+            QuoteError::NoRainfallNormal                     => "Underwriter has no rainfall normals for this location.".into(),
+            QuoteError::InvalidPayoutCurve                   => "Payout curve must climb past the threshold towards 100%.".into(),
+            QuoteError::CurveNeedsPlainTrigger               => "Graded payouts are only offered on plain threshold products.".into(),
+            QuoteError::BundleNeedsPlainTrigger              => "Peril bundles are only offered on plain threshold products.".into(),
+            QuoteError::NeedsDiverseSources { providers }    => format!("Payouts this large need readings from {providers} distinct weather providers."),
+            QuoteError::ForecastNeedsPlainTrigger            => "Forecast confirmation is only offered on plain rainfall products paying above the threshold.".into(),
+            QuoteError::SmoothingNeedsSingleReadings         => "Smoothing is only offered on products that compare single readings.".into(),
+            QuoteError::InvalidDeductible                    => "Deductible must sit short of the threshold.".into(),
+        }
+    }
+}
+
+impl From<QuoteError> for RialoError {
+    fn from(error: QuoteError) -> Self {
+        RialoError::custom(error.code(), error.message())
+    }
+}
+
+pub const MIN_THRESHOLD_FACTOR_BPS: u64 = 2_500;
+pub const MAX_THRESHOLD_FACTOR_BPS: u64 = 40_000;
+
+// Rate factor for writing at `threshold` on a template priced at `base_threshold`
+pub fn threshold_factor_bps(base_threshold: Option<f64>, threshold: f64) -> u64 {
+    let Some(reference) = base_threshold else {
+        return NEUTRAL_FACTOR_BPS;
+    };
+    if threshold <= 0.0 {
+        return MAX_THRESHOLD_FACTOR_BPS;
+    }
+    let factor = (reference / threshold * NEUTRAL_FACTOR_BPS as f64).round() as u64;
+    factor.clamp(MIN_THRESHOLD_FACTOR_BPS, MAX_THRESHOLD_FACTOR_BPS)
+}
+
+// What a policy would be written at
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct Quote {
+    pub peril:         Metric,
+    pub payout:        Ralo,
+    pub premium:       Ralo,
+    pub seasonal_bps:  u64,     // seasonal factor applied to the base rate
+    pub threshold_bps: u64,     // threshold factor applied to the base rate
+}
+
+// Price a policy and run every pre-write check on it. `location` must already be canonical.
+#[allow(clippy::too_many_arguments)]
+pub fn quote(
+    state:          &InsuranceState,
+    underwriter_id: UnderwriterId,
+    template_id:    TemplateId,
+    location:       &str,
+    threshold:      Hundredths,
+    payout:         PayoutSpec,
+    curve:          &PayoutCurve,
+    coverage_secs:  i64,
+    now:            i64,
+) -> Result<Quote, QuoteError> {
+
+    let config = &state.config;
+    let limits = config.limits();
+
+    check(!location.is_empty(), QuoteError::LocationRequired)?;
+    check(coverage_secs > 0, QuoteError::CoverageNotPositive)?;
+    check(coverage_secs <= config.max_coverage_secs, QuoteError::CoverageTooLong { max_secs: config.max_coverage_secs })?;
+
+    let underwriter = state.underwriters.get(&underwriter_id).ok_or(QuoteError::UnknownUnderwriter)?;
+    let template = underwriter.templates.get(&template_id).ok_or(QuoteError::UnknownTemplate)?;
+    let peril = template.peril();
+    check(underwriter.provider.kind.supports(peril), QuoteError::UnsupportedPeril)?;
+    // Every peril in a bundle is read from the same provider response
+    if let Some(bundle) = &template.bundle {
+        check(template.is_plain(), QuoteError::BundleNeedsPlainTrigger)?;
+        check(bundle.riders.iter().all(|r| underwriter.provider.kind.supports(r.metric)), QuoteError::UnsupportedPeril)?;
+    }
+    // Forecasts give a chance of rain, so they only confirm rain exceedances (see forecasts.rs)
+    if template.forecast_min_bps.is_some() {
+        let plain_rain = template.is_plain() && peril == Metric::Rainfall && template.comparison == Comparison::AtOrAbove;
+        check(plain_rain, QuoteError::ForecastNeedsPlainTrigger)?;
+    }
+    // Smoothing stands in for a single reading, not a running total (see smoothing.rs)
+    if template.smoothing.is_some() {
+        check(can_smooth(template), QuoteError::SmoothingNeedsSingleReadings)?;
+    }
+
+    // Price for the months the cover will run through, on the location's calendar (see seasonal.rs)
+    let local_now = local_time(now, underwriter.utc_offsets.get(location).copied().unwrap_or(0));
+    let seasonal_bps = underwriter.climatology
+        .get(location)
+        .map_or(NEUTRAL_FACTOR_BPS, |f| coverage_factor_bps(f, local_now, coverage_secs));
+    let pays_above = template.comparison == Comparison::AtOrAbove;
+    let threshold_bps = threshold_factor_bps(template.base_threshold.filter(|_| pays_above), threshold.to_f64());
+    let premium_rate_bps = underwriter.fees.premium_rate_bps * seasonal_bps / NEUTRAL_FACTOR_BPS * threshold_bps / NEUTRAL_FACTOR_BPS;
+
+    // Absolute payouts are priced by the underwriter; premium multiples are checked against that price
+    let (payout, premium) = payout.resolve(premium_rate_bps)?;
+
+    // Enforce sensible caps to avoid bankrupting the contract — always on the absolute payout
+    // The network floor is a rainfall one; other indices are bounded by their templates
+    if peril == Metric::Rainfall && pays_above {
+        check(threshold >= limits.min_threshold, QuoteError::ThresholdBelowNetworkMin { min: limits.min_threshold })?;
+    }
+    check(payout <= limits.max_payout, QuoteError::PayoutAboveNetworkMax { max: limits.max_payout })?;
+
+    check(template.active, QuoteError::TemplateRetired)?;
+    check(!template.rain_normal || underwriter.rain_normals.contains_key(location), QuoteError::NoRainfallNormal)?;
+    // A revoked evaluator can't take on new risk
+    if let Some(id) = template.evaluator {
+        check(state.evaluators.get(&id).is_some_and(|e| e.approved), QuoteError::EvaluatorNotApproved)?;
+    }
+    if pays_above {
+        check(threshold >= template.min_threshold, QuoteError::ThresholdBelowTemplateMin { min: template.min_threshold })?;
+    }
+    check(payout <= template.max_payout, QuoteError::PayoutAboveTemplateMax { max: template.max_payout })?;
+    // Large payouts settle on live weather readings from two providers (see consensus.rs)
+    if needs_diverse_sources(config, payout) {
+        let diverse = template.evaluator.is_none() && peril.is_observed() && tenant_providers(underwriter) >= MIN_DIVERSE_PROVIDERS;
+        check(diverse, QuoteError::NeedsDiverseSources { providers: MIN_DIVERSE_PROVIDERS as u32 })?;
+    }
+    // Curves grade single readings; products with a trigger of their own keep it
+    if !curve.is_flat() {
+        check(template.is_plain() && template.bundle.is_none(), QuoteError::CurveNeedsPlainTrigger)?;
+        curve.validate(template.comparison, threshold)?;
+    }
+
+    // Dust: costs more to store and check than it earns
+    check(payout >= config.min_payout, QuoteError::PayoutBelowFloor { min: config.min_payout })?;
+    check(premium >= config.min_premium, QuoteError::PremiumBelowFloor { min: config.min_premium })?;
+
+    // The tenant's vault must be able to cover this payout on top of everything it already owes
+    check(underwriter.free_capital() >= payout, QuoteError::InsufficientCapital)?;
+
+    Ok(Quote { peril, payout, premium, seasonal_bps, threshold_bps })
+}
+
+fn check(condition: bool, error: QuoteError) -> Result<(), QuoteError> {
+    if condition { Ok(()) } else { Err(error) }
+}
+
+// Dry run of the quote setup_policy prices with: the quote, or the first
+// check it fails. Doesn't run setup's checks on the buyer, station or extras.
+#[rialo::view]
+#[allow(clippy::too_many_arguments)]
+pub fn preflight_policy(
+    ctx:            Context<InsuranceState>,
+    underwriter_id: UnderwriterId,
+    template_id:    TemplateId,
+    location:       Location,
+    threshold_mm:   f64,
+    payout:         PayoutSpec,
+    curve:          PayoutCurve,
+    coverage_secs:  i64,
+) -> RialoResult<Result<Quote, QuoteError>> {
+
+    let location = location.canonical().key();
+    let now = ctx.clock.unix_timestamp;
+
+    Ok(quote(&ctx.state, underwriter_id, template_id, &location, Hundredths::from_f64(threshold_mm), payout, &curve, coverage_secs, now))
+}
@@ -0,0 +1,123 @@
+// ============================================================
+//  Check rate limits
+//
+//  The per-policy cooldown (throttle.rs) spaces out checks on one
+//  policy, but nothing stopped one caller from walking every
+//  policy in the book each hour and spending the tenants' API
+//  quota on it. The admin can cap the checks run in an hour:
+//    • per caller — by the signer of the checking instruction
+//    • globally   — across every caller
+//  A check is one policy read live or from history;
+//  check_all_policies counts each policy it reads, and a
+//  backfill_readings call counts once. Counts are kept per UTC
+//  hour and start again with the next one. A single check over a
+//  limit fails with `RateLimitExceeded`, carrying the time the
+//  count resets; a round pauses where it got to, as it does when
+//  its call budget runs out.
+// ============================================================
+
+use std::collections::BTreeMap;
+
+use rialo_sdk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::InsuranceState;
+use crate::errors::InsuranceError;
+
+const SECS_PER_HOUR: i64 = 60 * 60;
+
+// Error code, after the check cooldown's
+pub const RATE_LIMIT_EXCEEDED_CODE: u32 = 6_101;
+
+// Most checks allowed per hour; None leaves that scope uncapped
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RateLimits {
+    pub per_caller: Option<u32>,
+    pub global:     Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RateLimitScope {
+    Caller,
+    Global,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RateLimitExceeded {
+    pub scope:    RateLimitScope,
+    pub limit:    u32,
+    pub reset_at: i64,   // start of the next hour, when the count starts again
+}
+
+impl RateLimitExceeded {
+    pub fn message(&self) -> String {
+        let whose = match self.scope {
+            RateLimitScope::Caller => "this caller",
+            RateLimitScope::Global => "the contract",
+        };
+        format!("Check rate limit of {} an hour reached for {}; it resets at {}.", self.limit, whose, self.reset_at)
+    }
+}
+
+impl From<RateLimitExceeded> for RialoError {
+    fn from(error: RateLimitExceeded) -> Self {
+        RialoError::custom(RATE_LIMIT_EXCEEDED_CODE, error.message())
+    }
+}
+
+// Checks run so far this hour
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct CheckMeter {
+    pub hour:    i64,                    // hours since the epoch the counts are for
+    pub total:   u32,
+    pub callers: BTreeMap<Pubkey, u32>,
+}
+
+impl CheckMeter {
+    // Count one check by `caller` at `now`, unless it would go over a limit
+    pub fn admit(&mut self, limits: &RateLimits, caller: &Pubkey, now: i64) -> Result<(), RateLimitExceeded> {
+        let hour = now.div_euclid(SECS_PER_HOUR);
+        if hour != self.hour {
+            *self = CheckMeter { hour, ..CheckMeter::default() };
+        }
+        let reset_at = (hour + 1) * SECS_PER_HOUR;
+
+        let by_caller = self.callers.get(caller).copied().unwrap_or(0);
+        if let Some(limit) = limits.per_caller.filter(|&limit| by_caller >= limit) {
+            return Err(RateLimitExceeded { scope: RateLimitScope::Caller, limit, reset_at });
+        }
+        if let Some(limit) = limits.global.filter(|&limit| self.total >= limit) {
+            return Err(RateLimitExceeded { scope: RateLimitScope::Global, limit, reset_at });
+        }
+
+        self.total += 1;
+        self.callers.insert(*caller, by_caller + 1);
+        Ok(())
+    }
+}
+
+pub(crate) fn admit(state: &mut InsuranceState, caller: &Pubkey, now: i64) -> Result<(), RateLimitExceeded> {
+    state.check_meter.admit(&state.config.rate_limits, caller, now)
+}
+
+// ── Entry point: admin sets the hourly check limits ──────────
+#[rialo::instruction]
+pub async fn set_rate_limits(
+    ctx:    Context<InsuranceState>,
+    limits: RateLimits,
+) -> RialoResult<()> {
+
+    let config = &mut ctx.state.config;
+
+    require!(config.admin == *ctx.signer, InsuranceError::Unauthorized("Only the admin can change rate limits.".into()));
+    require!(limits.per_caller.is_none_or(|l| l > 0) && limits.global.is_none_or(|l| l > 0), InsuranceError::InvalidArgument("A rate limit must allow at least one check an hour.".into()));
+
+    config.rate_limits = limits;
+
+    emit!(RateLimitsSet { limits });
+
+    Ok(())
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct RateLimitsSet { pub limits: RateLimits }
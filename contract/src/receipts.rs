@@ -0,0 +1,174 @@
+// ============================================================
+//  Settlement receipts
+//
+//  `PolicyTriggered` tells the world a payout happened, but RPC
+//  providers prune old events. Every payout also leaves a compact
+//  receipt in state, filed under the account that was paid, so a
+//  customer (or their auditor) can always prove on-chain what was
+//  paid, when, and on which reading.
+//
+//  `observation_hash` commits to the reading that settled the
+//  policy — recompute it with `observation_hash` from the policy
+//  id, the reading and its time, both kept on the receipt. The
+//  reading is kept and hashed as hundredths of its unit; receipts
+//  filed under layout 3 and earlier hashed its f64 bits.
+//  `round_id` is the provider check it came from (see
+//  observations.rs); payouts on readings that aren't logged as
+//  checks — arbiter overrides, storm tiers, air-quality and
+//  heat-hours cover — carry none. Incident
+//  postmortems filed later over the reading's window are linked
+//  from the receipt as they're recorded (postmortems.rs).
+//
+//  Accounting systems book a payout off `PolicyTriggered`, so it
+//  is emitted once per amount actually paid, however often the
+//  instruction that reaches it is retried. Graded and tiered cover
+//  can trigger several times, each with the amount it paid and the
+//  policy's running total. Each one is recorded under its
+//  `SettlementKey` — the policy and the round that settled it —
+//  and a full settlement of a key already recorded pays nothing
+//  and emits nothing.
+//
+//  A settlement an arbiter has re-evaluated after payment
+//  (disputes.rs) gets a second receipt beside the first. It
+//  pays nothing; it carries the re-read and a `review` saying
+//  whether the first receipt's reading was confirmed or superseded.
+// ============================================================
+
+use rialo_sdk::crypto::sha256;
+use rialo_sdk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::millimeters::Hundredths;
+use crate::money::Ralo;
+use crate::observations::CheckId;
+use crate::policy::PolicyId;
+use crate::postmortems::PostmortemId;
+use crate::{Hash, InsuranceState};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SettlementReceipt {
+    pub policy_id:        PolicyId,
+    pub round_id:         Option<CheckId>,     // provider check that settled it, if logged
+    pub observation_hash: Hash,                // see observation_hash
+    #[serde(default)]
+    pub value:            Hundredths,          // index value it settled on, in hundredths of its unit
+    #[serde(rename = "reading", default, skip_serializing)]
+    pub old_reading:      Option<f64>,         // layout 5 and earlier only: now `value` (migrations.rs)
+    pub observed_at:      i64,                 // when that reading was taken
+    pub amount:           Ralo,                // paid by this settlement alone
+    pub tx_time:          i64,                 // when the payout was made
+    pub postmortems:      Vec<PostmortemId>,   // incident postmortems filed on its reading's window (postmortems.rs)
+    pub review:           Option<SettlementReview>,   // set on a re-evaluation's receipt, which pays nothing
+}
+
+// An arbiter's re-evaluation of a settlement already paid
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SettlementReview {
+    pub sources:        Vec<String>,   // base URLs the hour was re-read from
+    #[serde(default)]
+    pub original_value: Hundredths,    // reading the first receipt settled on
+    #[serde(rename = "original", default, skip_serializing)]
+    pub old_original:   Option<f64>,   // layout 5 and earlier only: now `original_value` (migrations.rs)
+    pub confirmed:      bool,          // the re-read also sits on the paying side
+}
+
+// One logical settlement: a policy and the provider check that settled it, if logged
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SettlementKey {
+    pub policy_id: PolicyId,
+    pub round_id:  Option<CheckId>,
+}
+
+// Record a settlement; false if it was already recorded
+pub(crate) fn mark_settled(state: &mut InsuranceState, key: SettlementKey) -> bool {
+    state.settlements.insert(key)
+}
+
+pub fn is_settled(state: &InsuranceState, key: SettlementKey) -> bool {
+    state.settlements.contains(&key)
+}
+
+// The payout receipt for a settlement, and the account it was filed under
+pub fn find_receipt(state: &InsuranceState, key: SettlementKey) -> Option<(Pubkey, &SettlementReceipt)> {
+    state.receipts
+        .iter()
+        .flat_map(|(payee, receipts)| receipts.iter().map(move |r| (*payee, r)))
+        .find(|(_, r)| r.policy_id == key.policy_id && r.round_id == key.round_id && r.review.is_none())
+}
+
+pub fn is_reviewed(state: &InsuranceState, key: SettlementKey) -> bool {
+    state.receipts
+        .values()
+        .flatten()
+        .any(|r| r.policy_id == key.policy_id && r.round_id == key.round_id && r.review.is_some())
+}
+
+// Commitment to a reading: policy id, reading in hundredths and its time, little-endian
+pub fn observation_hash(policy_id: PolicyId, reading: Hundredths, observed_at: i64) -> Hash {
+    let mut preimage = Vec::with_capacity(24);
+    preimage.extend_from_slice(&policy_id.to_le_bytes());
+    preimage.extend_from_slice(&reading.0.to_le_bytes());
+    preimage.extend_from_slice(&observed_at.to_le_bytes());
+    sha256(&preimage)
+}
+
+// File a receipt for an amount just sent to `payee`, or for nothing when a
+// settlement finds the cover already paid in full
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn issue(
+    state:       &mut InsuranceState,
+    payee:       Pubkey,
+    policy_id:   PolicyId,
+    round_id:    Option<CheckId>,
+    reading:     Hundredths,
+    observed_at: i64,
+    amount:      Ralo,
+    tx_time:     i64,
+) {
+    state.receipts.entry(payee).or_default().push(SettlementReceipt {
+        policy_id,
+        round_id,
+        observation_hash: observation_hash(policy_id, reading, observed_at),
+        value:       reading,
+        old_reading: None,
+        observed_at,
+        amount,
+        tx_time,
+        postmortems: Vec::new(),
+        review:      None,
+    });
+}
+
+// File a re-evaluation's receipt beside the payout receipt it reviews
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn file_review(
+    state:       &mut InsuranceState,
+    payee:       Pubkey,
+    key:         SettlementKey,
+    reading:     Hundredths,
+    observed_at: i64,
+    tx_time:     i64,
+    review:      SettlementReview,
+) {
+    state.receipts.entry(payee).or_default().push(SettlementReceipt {
+        policy_id:        key.policy_id,
+        round_id:         key.round_id,
+        observation_hash: observation_hash(key.policy_id, reading, observed_at),
+        value:            reading,
+        old_reading:      None,
+        observed_at,
+        amount:           Ralo::ZERO,
+        tx_time,
+        postmortems:      Vec::new(),
+        review:           Some(review),
+    });
+}
+
+#[rialo::view]
+pub fn get_receipts(
+    ctx:   Context<InsuranceState>,
+    owner: Pubkey,
+) -> RialoResult<Vec<SettlementReceipt>> {
+
+    Ok(ctx.state.receipts.get(&owner).cloned().unwrap_or_default())
+}
@@ -0,0 +1,116 @@
+// ============================================================
+//  Policy renewal
+//
+//  A customer whose cover is running out shouldn't have to buy it
+//  again from scratch. renew_policy writes the policy's successor
+//  on the same terms — underwriter, template, location and route,
+//  threshold, payout, curve and mint — for a new coverage window, quoted and
+//  reserved at today's price exactly as setup_policy would, and
+//  carries over the broker, their commission, the insured point
+//  and any co-pay set on the old one. The co-pay is taken as
+//  set_copay would take it: the successor's premium is cut to
+//  match and the retained cover goes back to the underwriter.
+//  Everything the old window counted — a streak, the rolling rain,
+//  the month against its normal, graded shares, the checks used —
+//  starts afresh with the successor. A USD denomination isn't
+//  carried over: it's set again against today's price.
+//
+//  A policy is renewed once, by its owner:
+//    • while live, in the last RENEWAL_NOTICE_SECS of its window;
+//      however early the successor's premium clears, its cover
+//      starts when the old cover ends, so the two never overlap
+//    • or once it has expired, when cover starts as the premium
+//      clears
+//  The successor waits on its premium like any new policy.
+// ============================================================
+
+use rialo_sdk::prelude::*;
+
+use crate::copay::{self, CopaySet};
+use crate::curves::PayoutCurve;
+use crate::errors::InsuranceError;
+use crate::mints::MintAmount;
+use crate::money::Ralo;
+use crate::policy::{PayoutSpec, PolicyId, PolicyStatus, PolicyTerms};
+use crate::routes::RouteCover;
+use crate::{config, write_policy, InsuranceState};
+
+pub const RENEWAL_NOTICE_SECS: i64 = 14 * 24 * 60 * 60;
+
+// ── Entry point: owner renews a policy for a new window ──────
+#[rialo::instruction]
+pub async fn renew_policy(
+    ctx:        Context<InsuranceState>,
+    policy_id:  PolicyId,
+    new_window: i64,   // coverage length of the successor, in seconds
+) -> RialoResult<PolicyId> {
+
+    let now = ctx.clock.unix_timestamp;
+    config::require_unpaused(&ctx.state.config)?;
+
+    let policy = ctx.state.policies.get(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+    require!(policy.owner == *ctx.signer, InsuranceError::Unauthorized("Only the policy owner can renew it.".into()));
+    require!(policy.renewed_by.is_none(), InsuranceError::InvalidState("Policy has already been renewed.".into()));
+
+    let (old_start, old_end) = match (policy.activated_at, policy.coverage_end()) {
+        (Some(start), Some(end)) => (start, end),
+        _ => return Err(InsuranceError::PolicyNotActive.into()),
+    };
+    let starts_at = match policy.status {
+        PolicyStatus::Expired => None,
+        PolicyStatus::Active if old_end - now <= RENEWAL_NOTICE_SECS => Some(old_end),
+        PolicyStatus::Active => return Err(InsuranceError::InvalidState("Policy can only be renewed in the last 14 days of its cover.".into()).into()),
+        _ => return Err(InsuranceError::InvalidState("Only live or expired cover can be renewed.".into()).into()),
+    };
+
+    let terms = PolicyTerms {
+        underwriter_id: policy.underwriter_id,
+        template_id:    policy.template_id,
+        location:       policy.place.clone(),
+        threshold:      policy.threshold,
+        old_threshold:  None,
+        payout:         PayoutSpec::Absolute(policy.payout_amount),
+        payout_curve:   policy.graded.as_ref().map_or(PayoutCurve::Flat, |g| g.payout_curve.clone()),
+        old_curve:      None,
+        coverage_secs:  new_window,
+        payout_mint:    policy.payout_mint.map(|m| MintAmount { mint: m.mint, amount: m.amount }),
+        route:          policy.route.as_ref().map(RouteCover::terms),
+        commission:     policy.commission,
+    };
+    let (owner, broker, insured_point, copay_bps) = (policy.owner, policy.broker, policy.insured_point, policy.copay_bps);
+    let min_premium = ctx.state.config.min_premium;
+
+    // The successor continues this cover, so it doesn't count as buying it twice
+    let renewal_id = write_policy(&mut ctx.state, owner, broker, terms, true, now).await?;
+    let renewal = ctx.state.policies.get_mut(&renewal_id).ok_or(InsuranceError::UnknownPolicy)?;
+    renewal.starts_at     = starts_at;
+    renewal.insured_point = insured_point;
+    // Before any premium is paid, so the customer is only ever asked for the cut price
+    if copay_bps > 0 {
+        let released = copay::take_copay(renewal, copay_bps, min_premium)?;
+        let premium = renewal.premium_amount;
+        let underwriter = ctx.state.underwriters.get_mut(&renewal.underwriter_id).ok_or(InsuranceError::UnknownUnderwriter)?;
+        underwriter.reserved -= released;
+        emit!(CopaySet { policy_id: renewal_id, copay_bps, premium, released });
+    }
+    let premium = ctx.state.policies.get(&renewal_id).ok_or(InsuranceError::UnknownPolicy)?.premium_amount;
+
+    ctx.state.policies.get_mut(&policy_id).ok_or(InsuranceError::UnknownPolicy)?.renewed_by = Some(renewal_id);
+
+    let new_start = starts_at.unwrap_or(now);
+    emit!(PolicyRenewed {
+        policy_id,
+        renewal_id,
+        premium,
+        old_start,
+        old_end,
+        new_start,
+        new_end: new_start + new_window,
+    });
+
+    Ok(renewal_id)
+}
+
+// ── Events ───────────────────────────────────────────────────
+// The new window's start is the earliest cover can begin; an expired policy's successor starts when its premium clears
+#[rialo::event] pub struct PolicyRenewed { pub policy_id: PolicyId, pub renewal_id: PolicyId, pub premium: Ralo, pub old_start: i64, pub old_end: i64, pub new_start: i64, pub new_end: i64 }
@@ -0,0 +1,154 @@
+// ============================================================
+//  Proof of reserve
+//
+//  One report third parties can poll (or pick up from the
+//  periodic event) to verify the scheme is fully funded: what the
+//  vault actually holds against everything it may have to pay:
+//    • reserved      — payouts set aside for live policies
+//    • escrow        — premiums still refundable to customers
+//    • withdrawals   — underwriter capital already on its way out
+//    • levies        — batched premium levies not yet swept (levies.rs)
+//  plus the payouts waiting on held observations. Payout mints
+//  (mints.rs) are reported beside RALO: what the vault holds of
+//  each against the underwriters' pools and their reservations.
+// ============================================================
+
+use rialo_sdk::prelude::*;
+use rialo_sdk::token::{balance, mint_balance};
+use serde::Serialize;
+
+use crate::alerts::{self, AlertKind};
+use crate::errors::InsuranceError;
+use crate::mints::{mint_balances, Mint};
+use crate::money::{Ralo, NATIVE_MINT};
+use crate::InsuranceState;
+
+const MIN_PROOF_INTERVAL_SECS: i64 = 60 * 60;
+
+#[derive(Serialize, Clone, Debug)]
+pub struct MintBalance {
+    pub mint:    String,
+    pub balance: Ralo,
+}
+
+// A payout mint's holdings, in its own base units
+#[derive(Serialize, Clone, Debug)]
+pub struct MintReserve {
+    pub mint:     Mint,
+    pub symbol:   String,
+    pub held:     u64,   // the vault's balance of the mint
+    pub pooled:   u64,   // what the underwriters' pools say it should be
+    pub reserved: u64,   // set aside for mint-paying policies
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct ProofOfReserve {
+    pub balances:            Vec<MintBalance>,
+    pub mints:               Vec<MintReserve>,
+    pub reserved:            Ralo,   // payouts earmarked for live policies
+    pub escrowed_premiums:   Ralo,   // refundable to customers before activation
+    pub pending_withdrawals: Ralo,   // requested underwriter withdrawals
+    pub accrued_levies:      Ralo,   // batched levies owed to their collecting accounts
+    pub pending_settlements: Ralo,   // payouts behind readings held by a data incident
+    pub total_liabilities:   Ralo,
+    pub solvency_ratio_bps:  u64,    // vault balance / liabilities (10_000 = exactly funded)
+}
+
+pub fn proof_of_reserve(state: &InsuranceState, vault: &Vault) -> ProofOfReserve {
+    let held_balance = Ralo(balance(vault));
+
+    let reserved:            Ralo = state.underwriters.values().map(|u| u.reserved).sum();
+    let pending_withdrawals: Ralo = state.underwriters.values().filter_map(|u| u.withdrawal).map(|w| w.amount).sum();
+    let escrowed_premiums:   Ralo = state.policies.values().map(|p| p.escrowed_premium()).sum();
+    let accrued_levies:      Ralo = state.levy_bucket.total();
+
+    let mut held_policies: Vec<_> = state.held_observations.iter().map(|o| o.policy_id).collect();
+    held_policies.sort();
+    held_policies.dedup();
+    let pending_settlements: Ralo = held_policies
+        .iter()
+        .filter_map(|id| state.policies.get(id))
+        .map(|p| p.coverage_remaining())
+        .sum();
+
+    // Pending settlements are already inside `reserved`, so they are not added again
+    let total_liabilities = reserved + escrowed_premiums + pending_withdrawals + accrued_levies;
+    let solvency_ratio_bps = if total_liabilities.is_zero() {
+        10_000
+    } else {
+        (held_balance.base_units() as u128 * 10_000 / total_liabilities.base_units() as u128) as u64
+    };
+
+    let mints = mint_balances(state)
+        .into_iter()
+        .map(|(mint, pool)| MintReserve {
+            mint,
+            symbol:   state.config.payout_mints.get(&mint).map(|m| m.symbol.clone()).unwrap_or_default(),
+            held:     mint_balance(&mint, vault),
+            pooled:   pool.balance,
+            reserved: pool.reserved,
+        })
+        .collect();
+
+    ProofOfReserve {
+        balances: vec![MintBalance { mint: NATIVE_MINT.to_string(), balance: held_balance }],
+        mints,
+        reserved,
+        escrowed_premiums,
+        pending_withdrawals,
+        accrued_levies,
+        pending_settlements,
+        total_liabilities,
+        solvency_ratio_bps,
+    }
+}
+
+#[rialo::view]
+pub fn get_proof_of_reserve(ctx: Context<InsuranceState>) -> RialoResult<ProofOfReserve> {
+    Ok(proof_of_reserve(&ctx.state, &ctx.vault))
+}
+
+// ── Entry point: anyone publishes the report, at most hourly ─
+#[rialo::instruction]
+pub async fn publish_proof_of_reserve(ctx: Context<InsuranceState>) -> RialoResult<()> {
+
+    let now = ctx.clock.unix_timestamp;
+
+    require!(
+        now - ctx.state.last_reserve_proof >= MIN_PROOF_INTERVAL_SECS,
+        InsuranceError::InvalidState("Proof of reserve was published too recently.".into()),
+    );
+    ctx.state.last_reserve_proof = now;
+
+    let report = proof_of_reserve(&ctx.state, &ctx.vault);
+
+    emit!(ProofOfReservePublished {
+        at:                  now,
+        balances:            report.balances,
+        reserved:            report.reserved,
+        escrowed_premiums:   report.escrowed_premiums,
+        pending_withdrawals: report.pending_withdrawals,
+        accrued_levies:      report.accrued_levies,
+        pending_settlements: report.pending_settlements,
+        total_liabilities:   report.total_liabilities,
+        solvency_ratio_bps:  report.solvency_ratio_bps,
+    });
+
+    if report.solvency_ratio_bps < 10_000 {
+        alerts::raise(&ctx.state.config, AlertKind::SolvencyBreach, now, &SolvencyBreach {
+            total_liabilities:  report.total_liabilities,
+            solvency_ratio_bps: report.solvency_ratio_bps,
+        }).await;
+    }
+
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct SolvencyBreach {
+    total_liabilities:  Ralo,
+    solvency_ratio_bps: u64,
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct ProofOfReservePublished { pub at: i64, pub balances: Vec<MintBalance>, pub reserved: Ralo, pub escrowed_premiums: Ralo, pub pending_withdrawals: Ralo, pub accrued_levies: Ralo, pub pending_settlements: Ralo, pub total_liabilities: Ralo, pub solvency_ratio_bps: u64 }
@@ -0,0 +1,145 @@
+// ============================================================
+//  Provider call retries and fallback
+//
+//  A 500 or a stalled connection from the weather provider used
+//  to abort the check with whatever the runtime said about it.
+//  Live and historical checks now go through `fetch_with_retry`:
+//    • a timeout or a 5xx is retried, up to `MAX_ATTEMPTS` calls
+//      per endpoint; the runtime can't sleep between them, so each
+//      retry waits twice as long for its answer instead
+//    • the tenant's fallback endpoint — a mirror speaking the same
+//      API with the same key — gets the same treatment once the
+//      primary gives up (MainNet only; DevNet has the sandbox)
+//    • a 429 isn't retried: the key is spent (keys.rs), and the
+//      mirror would say the same
+//  Every attempt spends a call from the instruction's budget
+//  (oracle.rs), and retries never touch the calls a check keeps
+//  back for its consensus sources.
+//
+//  A call that still fails ends the check without settling and
+//  without failing the instruction — a round carries on to its
+//  next policy — and says why in `WeatherFetchFailed`. The policy
+//  waits out its check cooldown (throttle.rs) before the next try.
+// ============================================================
+
+use rialo_sdk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::InsuranceError;
+use crate::oracle::CallBudget;
+use crate::policy::PolicyId;
+use crate::transport::{self, HttpReply};
+use crate::InsuranceState;
+
+pub const MAX_ATTEMPTS:     u32 = 3;
+pub const FIRST_TIMEOUT_MS: u64 = 2_000;
+
+// Why a provider call produced no usable response
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FetchError {
+    Timeout,          // no answer in time, or no connection at all
+    RateLimited,      // 429
+    BadStatus(u16),   // any other non-2xx status
+    ParseError,       // an answer the provider adapter couldn't read
+}
+
+impl FetchError {
+    // None for a successful status
+    pub fn from_status(status: u16) -> Option<FetchError> {
+        match status {
+            200..=299 => None,
+            429       => Some(FetchError::RateLimited),
+            _         => Some(FetchError::BadStatus(status)),
+        }
+    }
+
+    // Worth calling the same endpoint again
+    pub fn is_transient(self) -> bool {
+        match self {
+            FetchError::Timeout          => true,
+            FetchError::BadStatus(code)  => code >= 500,
+            FetchError::RateLimited | FetchError::ParseError => false,
+        }
+    }
+
+    // HTTP status the provider answered with, if it answered
+    pub fn status(self) -> Option<u16> {
+        match self {
+            FetchError::RateLimited     => Some(429),
+            FetchError::BadStatus(code) => Some(code),
+            FetchError::Timeout | FetchError::ParseError => None,
+        }
+    }
+}
+
+// How long attempt `n` (from zero) waits for its answer
+pub fn timeout_ms(attempt: u32) -> u64 {
+    FIRST_TIMEOUT_MS << attempt
+}
+
+pub struct FetchOutcome {
+    pub result:   Result<HttpReply, FetchError>,
+    pub attempts: u32,
+}
+
+impl FetchOutcome {
+    pub fn status(&self) -> Option<u16> {
+        match &self.result {
+            Ok(response) => Some(response.status()),
+            Err(error)   => error.status(),
+        }
+    }
+}
+
+// Call each endpoint in turn, retrying transient failures, until one answers
+// with a 2xx. Spends a call from `budget` per attempt, keeping `spare` back; an
+// outcome with no attempts means the budget couldn't cover the first one.
+pub async fn fetch_with_retry(
+    urls:    &[String],
+    headers: &[(String, String)],
+    budget:  &mut CallBudget,
+    spare:   u32,
+) -> FetchOutcome {
+
+    let mut attempts = 0;
+    let mut last = FetchError::Timeout;
+    for url in urls {
+        for attempt in 0..MAX_ATTEMPTS {
+            if budget.remaining() <= spare || !budget.try_spend() {
+                return FetchOutcome { result: Err(last), attempts };
+            }
+            attempts += 1;
+
+            let error = match send(url, headers, timeout_ms(attempt)).await {
+                Ok(response) => match FetchError::from_status(response.status()) {
+                    None => return FetchOutcome { result: Ok(response), attempts },
+                    Some(error) => error,
+                },
+                Err(_) => FetchError::Timeout,
+            };
+            last = error;
+            if error == FetchError::RateLimited {
+                return FetchOutcome { result: Err(error), attempts };
+            }
+            if !error.is_transient() {
+                break;
+            }
+        }
+    }
+
+    FetchOutcome { result: Err(last), attempts }
+}
+
+async fn send(url: &str, headers: &[(String, String)], timeout_ms: u64) -> RialoResult<HttpReply> {
+    transport::get(url, headers, Some(timeout_ms)).await
+}
+
+// Count a check's provider call that came to nothing, and say why
+pub(crate) fn report_failure(state: &mut InsuranceState, policy_id: PolicyId, source: &str, attempts: u32, reason: FetchError) {
+    state.metrics.http_failures += 1;
+    let code = InsuranceError::from(reason).code();
+    emit!(WeatherFetchFailed { policy_id, source: source.to_string(), attempts, reason, code });
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct WeatherFetchFailed { pub policy_id: PolicyId, pub source: String, pub attempts: u32, pub reason: FetchError, pub code: u32 }
@@ -0,0 +1,124 @@
+// ============================================================
+//  Observation retention
+//
+//  The check log (observations.rs) is the costliest thing the
+//  contract keeps: a record per reading, for every policy ever
+//  written. Once a policy is done with — closed, past the
+//  retention period, and with nothing left that could still read
+//  its checks — anyone can archive it:
+//    • each of its check records is emitted once, in full, for
+//      indexers to keep (`ObservationArchived`)
+//    • on-chain, only the hash of each record is kept
+//  `verify_observation` checks a payload someone offers for a
+//  check against what the contract holds: the stored hash once
+//  the policy is archived, the live record before that. The
+//  payload is the record's JSON, exactly as the archive event
+//  carried it.
+//
+//  The claims ledger (history.rs) keeps the check ids, so an
+//  archived policy's ledger still counts its checks; the entries
+//  themselves come from the indexers from then on.
+// ============================================================
+
+use rialo_sdk::crypto::sha256;
+use rialo_sdk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::InsuranceError;
+use crate::observations::{CheckId, CheckRecord};
+use crate::policy::{PolicyId, PolicyStatus};
+use crate::{Hash, InsuranceState};
+
+pub const OBSERVATION_RETENTION_SECS: i64 = 90 * 24 * 60 * 60;
+
+// What is left on-chain of an archived check
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ArchivedCheck {
+    pub policy_id: PolicyId,
+    pub hash:      Hash,   // sha256 of the record's JSON
+}
+
+// The bytes a check record is archived and verified as
+pub fn observation_bytes(record: &CheckRecord) -> Vec<u8> {
+    serde_json::to_vec(record).unwrap_or_default()
+}
+
+pub fn check_hash(record: &CheckRecord) -> Hash {
+    sha256(&observation_bytes(record))
+}
+
+// Whether a policy's status means nothing more will be settled on it
+pub fn is_closed(status: PolicyStatus) -> bool {
+    matches!(
+        status,
+        PolicyStatus::PaidOut | PolicyStatus::Withdrawn | PolicyStatus::Lapsed | PolicyStatus::Expired | PolicyStatus::Cancelled
+    )
+}
+
+// ── Entry point: anyone archives a closed policy's observations
+#[rialo::instruction]
+pub async fn archive_observations(
+    ctx:       Context<InsuranceState>,
+    policy_id: PolicyId,
+) -> RialoResult<u32> {
+
+    let now = ctx.clock.unix_timestamp;
+    let state = &mut ctx.state;
+    let policy = state.policies.get(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+
+    require!(policy.archived_at.is_none(), InsuranceError::InvalidState("Policy's observations are already archived.".into()));
+    require!(is_closed(policy.status), InsuranceError::InvalidState("Only closed policies can be archived.".into()));
+    let closed_at = policy.coverage_end().unwrap_or(policy.created_at);
+    require!(now >= closed_at + OBSERVATION_RETENTION_SECS, InsuranceError::InvalidState("Policy is still within the observation retention period.".into()));
+    require!(!state.held_observations.iter().any(|o| o.policy_id == policy_id), InsuranceError::InvalidState("Policy has readings held by a data incident.".into()));
+
+    let ledger = state.claims_history.get(&policy_id).cloned().unwrap_or_default();
+    let audit_due = ledger
+        .iter()
+        .filter_map(|id| state.checks.get(id))
+        .any(|record| record.audit_selected && record.audit.is_none());
+    require!(!audit_due, InsuranceError::InvalidState("Policy has checks drawn for audit that haven't been re-checked.".into()));
+
+    let mut archived = 0;
+    for check_id in ledger {
+        let Some(record) = state.checks.remove(&check_id) else {
+            continue;
+        };
+        let payload = observation_bytes(&record);
+        state.archived_checks.insert(check_id, ArchivedCheck { policy_id, hash: sha256(&payload) });
+        emit!(ObservationArchived { policy_id, check_id, payload });
+        archived += 1;
+    }
+    if let Some(policy) = state.policies.get_mut(&policy_id) {
+        policy.archived_at = Some(now);
+    }
+
+    emit!(PolicyObservationsArchived { policy_id, checks: archived });
+
+    Ok(archived)
+}
+
+// Whether `raw_bytes` is the record of check `check_id` on `policy_id`
+#[rialo::view]
+pub fn verify_observation(
+    ctx:       Context<InsuranceState>,
+    policy_id: PolicyId,
+    check_id:  CheckId,
+    raw_bytes: Vec<u8>,
+) -> RialoResult<bool> {
+
+    let (owner, hash) = match ctx.state.archived_checks.get(&check_id) {
+        Some(archived) => (archived.policy_id, archived.hash),
+        None => {
+            let record = ctx.state.checks.get(&check_id).ok_or_else(|| InsuranceError::UnknownRecord("Unknown check.".into()))?;
+            (record.policy_id, check_hash(record))
+        }
+    };
+    require!(owner == policy_id, InsuranceError::InvalidState("Check does not belong to this policy.".into()));
+
+    Ok(sha256(&raw_bytes) == hash)
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct ObservationArchived        { pub policy_id: PolicyId, pub check_id: CheckId, pub payload: Vec<u8> }
+#[rialo::event] pub struct PolicyObservationsArchived { pub policy_id: PolicyId, pub checks: u32 }
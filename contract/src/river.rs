@@ -0,0 +1,155 @@
+// ============================================================
+//  River-level cover
+//
+//  Flood losses for delivery fleets follow the river, not the rain
+//  gauge on the depot roof: a product can instead be written on a
+//  river gauge's height at a named site. Readings come from the
+//  shared gauge feed (USGS instantaneous-values JSON) and settle
+//  through the normal threshold path, with the height in mm in
+//  place of rainfall.
+// ============================================================
+
+use rialo_sdk::prelude::*;
+use serde::Deserialize;
+
+use crate::claims::{record_lae, LaeKind};
+use crate::config::{require_unpaused, FeedKind};
+use crate::errors::InsuranceError;
+use crate::millimeters::Hundredths;
+use crate::normalization::Metric;
+use crate::policy::{PolicyId, PolicyStatus};
+use crate::underwriter::{tenant_mut, TemplateId, UnderwriterId};
+use crate::{fetch, settlement, InsuranceState};
+
+const MM_PER_FOOT: f64 = 304.8;
+
+// USGS marks missing readings with this sentinel
+const NO_DATA: f64 = -999_999.0;
+
+#[derive(Deserialize)]
+struct GaugeResponse {
+    value: GaugeValue,
+}
+
+#[derive(Deserialize)]
+struct GaugeValue {
+    #[serde(rename = "timeSeries")]
+    time_series: Vec<TimeSeries>,
+}
+
+#[derive(Deserialize)]
+struct TimeSeries {
+    variable: Variable,
+    values:   Vec<ValueSet>,
+}
+
+#[derive(Deserialize)]
+struct Variable {
+    unit: Unit,
+}
+
+#[derive(Deserialize)]
+struct Unit {
+    #[serde(rename = "unitCode")]
+    unit_code: String,
+}
+
+#[derive(Deserialize)]
+struct ValueSet {
+    value: Vec<Reading>,
+}
+
+#[derive(Deserialize)]
+struct Reading {
+    value: String,
+}
+
+// Latest gauge height (parameter 00065) at a site
+pub fn gauge_height_url(base_url: &str, site: &str) -> String {
+    format!("{base_url}/nwis/iv/?format=json&sites={site}&parameterCd=00065")
+}
+
+// Most recent gauge height in the feed, converted to mm
+pub fn parse_gauge_height_mm(body: &[u8]) -> RialoResult<f64> {
+    let response: GaugeResponse = serde_json::from_slice(body)
+        .map_err(|_| InsuranceError::BadResponse("Failed to parse gauge response.".into()))?;
+
+    let series = response.value.time_series.first().ok_or_else(|| InsuranceError::InvalidState("Gauge returned no series.".into()))?;
+    let reading = series.values
+        .first()
+        .and_then(|v| v.value.last())
+        .ok_or_else(|| InsuranceError::InvalidState("Gauge returned no readings.".into()))?;
+
+    let height: f64 = reading.value.trim().parse().map_err(|_| InsuranceError::BadResponse("Gauge height is not a number.".into()))?;
+    require!(height != NO_DATA, InsuranceError::InvalidState("Gauge has no current reading.".into()));
+
+    match series.variable.unit.unit_code.as_str() {
+        "ft" => Ok(height * MM_PER_FOOT),
+        "m"  => Ok(height * 1_000.0),
+        _    => Err(InsuranceError::BadResponse("Unsupported gauge unit.".into()).into()),
+    }
+}
+
+// ── Entry point: make (or unmake) a template a river-level product
+#[rialo::instruction]
+pub async fn set_template_river_gauge(
+    ctx:            Context<InsuranceState>,
+    underwriter_id: UnderwriterId,
+    template_id:    TemplateId,
+    site:           Option<String>,
+) -> RialoResult<()> {
+
+    // Site numbers go straight into the feed URL
+    if let Some(site) = &site {
+        require!(!site.is_empty() && site.bytes().all(|b| b.is_ascii_digit()), InsuranceError::InvalidArgument("Gauge site must be a numeric site code.".into()));
+    }
+
+    let underwriter = tenant_mut(&mut ctx.state, underwriter_id, &ctx.signer)?;
+    let template = underwriter.templates.get_mut(&template_id).ok_or(InsuranceError::UnknownTemplate)?;
+    template.river_gauge = site.clone();
+
+    emit!(TemplateRiverGaugeSet { underwriter_id, template_id, site });
+
+    Ok(())
+}
+
+// ── Entry point: anyone checks the gauge for a policy ────────
+#[rialo::instruction]
+pub async fn check_river_and_pay(
+    ctx:       Context<InsuranceState>,
+    policy_id: PolicyId,
+) -> RialoResult<()> {
+
+    require_unpaused(&ctx.state.config)?;
+    let now = ctx.clock.unix_timestamp;
+    let policy = ctx.state.policies.get(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+
+    require!(policy.peril == Metric::RiverLevel, InsuranceError::InvalidState("Policy is not river-level cover.".into()));
+    require!(policy.status == PolicyStatus::Active, InsuranceError::PolicyNotActive);
+    require!(policy.is_covered_at(now), InsuranceError::PolicyExpired);
+
+    let site = ctx.state.underwriters
+        .get(&policy.underwriter_id)
+        .and_then(|u| u.templates.get(&policy.template_id))
+        .and_then(|t| t.river_gauge.clone())
+        .ok_or_else(|| InsuranceError::InvalidState("Template has no river gauge.".into()))?;
+    let threshold = policy.threshold;
+
+    let provider = ctx.state.config.feeds.get(&FeedKind::RiverGauge).ok_or_else(|| InsuranceError::InvalidState("No river gauge feed configured.".into()))?;
+    let url = gauge_height_url(provider.base_url_for(ctx.state.config.network_mode), &site);
+    let call_cost = provider.cost_per_call;
+
+    let response = fetch(&url, &[]).await?;
+    let level_mm = parse_gauge_height_mm(response.body())?;
+
+    record_lae(&mut ctx.state, policy_id, LaeKind::ProviderCost, call_cost);
+    let triggered = settlement::settle(&mut ctx.state, &ctx.vault, policy_id, None, Hundredths::from_f64(level_mm), now, now)?;
+
+    emit!(RiverLevelChecked { policy_id, site, level_mm, threshold, triggered });
+
+    Ok(())
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct TemplateRiverGaugeSet { pub underwriter_id: UnderwriterId, pub template_id: TemplateId, pub site: Option<String> }
+#[rialo::event] pub struct RiverLevelChecked     { pub policy_id: PolicyId, pub site: String, pub level_mm: f64, pub threshold: Hundredths, pub triggered: bool }
@@ -0,0 +1,322 @@
+// ============================================================
+//  Route cover
+//
+//  A delivery route that runs through several cities is exposed
+//  to the weather in each of them, and one policy should cover
+//  the lot. A policy can list up to MAX_ROUTE_STOPS locations, its
+//  own first, with one of two ways to read them:
+//    • WorstOf — pays if ANY stop is past the threshold; it settles
+//                on the stop furthest onto the paying side
+//    • BestOf  — pays only if EVERY stop is; it settles on the stop
+//                furthest from it
+//  Each check reads every stop, cached readings free as ever, and
+//  the decisive reading then settles like any other, so streaks,
+//  curves and the rest work on it unchanged. RouteChecked reports
+//  every stop's reading. A check the budget can't finish reads
+//  nothing, and one stop the provider won't answer settles
+//  nothing.
+//
+//  Pricing: a stop is quoted like a single-location policy. The
+//  chance that any one stop triggers is at most the sum of the
+//  stops' chances, and the chance that all do at most the least of
+//  them, so WorstOf charges the sum of the stops' premiums, up to
+//  the payout, and BestOf the cheapest.
+//
+//  A route settles on the tenant's own provider: routes stay below
+//  the payouts that need two providers (consensus.rs), and aren't
+//  screened against the per-location records (anomalies.rs).
+// ============================================================
+
+use rialo_sdk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::claims::{self, LaeKind};
+use crate::consensus::needs_diverse_sources;
+use crate::curves::PayoutCurve;
+use crate::errors::InsuranceError;
+use crate::millimeters::Hundredths;
+use crate::money::Ralo;
+use crate::normalization::{Comparison, Location, Observation};
+use crate::oracle::CallBudget;
+use crate::policy::{PayoutSpec, PolicyId};
+use crate::providers::{ReadingTime, WeatherProvider};
+use crate::quote::{self, Quote};
+use crate::resilience::{self, FetchError};
+use crate::underwriter::{TemplateId, UnderwriterId};
+use crate::{evaluate_reading, fx, geocoding, keys, provider_headers, InsuranceState};
+
+pub const MAX_ROUTE_STOPS: usize = 8;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Aggregation {
+    WorstOf,   // any stop past the threshold pays
+    BestOf,    // every stop has to be
+}
+
+// The stops a policy is bought for beyond its own location, as setup_policy takes them
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RouteTerms {
+    pub stops:       Vec<Location>,
+    pub aggregation: Aggregation,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RouteStop {
+    pub place:            Location,             // as written, canonical
+    pub location:         String,               // the stop's cache and ledger key (geocoding.rs)
+    #[serde(default)]
+    pub last_value:       Option<Hundredths>,   // the stop's reading at the latest check
+    #[serde(rename = "last_reading", default, skip_serializing)]
+    pub old_last_reading: Option<f64>,          // layout 5 and earlier only: now `last_value` (migrations.rs)
+}
+
+impl RouteStop {
+    pub fn new(place: Location, location: String) -> Self {
+        RouteStop { place, location, last_value: None, old_last_reading: None }
+    }
+}
+
+// Route state carried by a policy bought for several locations
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RouteCover {
+    pub aggregation: Aggregation,
+    pub stops:       Vec<RouteStop>,   // the policy's own location first
+}
+
+impl RouteCover {
+    // The route as it would be bought again, e.g. on renewal
+    pub fn terms(&self) -> RouteTerms {
+        RouteTerms {
+            stops:       self.stops.iter().skip(1).map(|s| s.place.clone()).collect(),
+            aggregation: self.aggregation,
+        }
+    }
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct StopReading {
+    pub location: String,
+    pub reading:  Hundredths,
+}
+
+// Index of the reading that decides a route on the `comparison` side of its threshold
+pub fn decisive_stop(aggregation: Aggregation, comparison: Comparison, readings: &[Hundredths]) -> Option<usize> {
+    let furthest_onto_paying_side = |a: &Hundredths, b: &Hundredths| match comparison {
+        Comparison::AtOrAbove => a.cmp(b),
+        Comparison::AtOrBelow => b.cmp(a),
+    };
+    let indexed = readings.iter().enumerate();
+    let decisive = match aggregation {
+        Aggregation::WorstOf => indexed.max_by(|(_, a), (_, b)| furthest_onto_paying_side(a, b)),
+        Aggregation::BestOf  => indexed.min_by(|(_, a), (_, b)| furthest_onto_paying_side(a, b)),
+    };
+    decisive.map(|(i, _)| i)
+}
+
+// Premium for a route whose stops quote `premiums` on their own
+pub fn route_premium(aggregation: Aggregation, premiums: &[Ralo], payout: Ralo) -> Ralo {
+    match aggregation {
+        Aggregation::WorstOf => premiums.iter().copied().sum::<Ralo>().min(payout),
+        Aggregation::BestOf  => premiums.iter().copied().min().unwrap_or(Ralo::ZERO),
+    }
+}
+
+// Stops a route may be bought for, beyond `own`, already canonical
+pub fn check_stops(own: &Location, stops: &[Location]) -> RialoResult<()> {
+    require!(!stops.is_empty(), InsuranceError::InvalidArgument("A route needs at least one more stop.".into()));
+    require!(stops.len() < MAX_ROUTE_STOPS, InsuranceError::InvalidArgument(format!("A route has at most {MAX_ROUTE_STOPS} stops.")));
+
+    let mut keys = vec![own.key()];
+    for stop in stops {
+        require!(!stop.key().is_empty(), InsuranceError::InvalidArgument("Route stop has no location.".into()));
+        require!(!keys.contains(&stop.key()), InsuranceError::InvalidArgument("Route stops must be distinct.".into()));
+        keys.push(stop.key());
+    }
+    Ok(())
+}
+
+// A route's terms, its stops canonical, and its premium, from the quote for
+// the policy's own location; every stop has to pass the same checks it did
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn quote_route(
+    state:          &InsuranceState,
+    underwriter_id: UnderwriterId,
+    template_id:    TemplateId,
+    own:            &Location,
+    terms:          RouteTerms,
+    threshold:      Hundredths,
+    curve:          &PayoutCurve,
+    coverage_secs:  i64,
+    quoted:         &Quote,
+    now:            i64,
+) -> RialoResult<(RouteTerms, Ralo)> {
+
+    let stops: Vec<Location> = terms.stops.into_iter().map(Location::canonical).collect();
+    check_stops(own, &stops)?;
+
+    let underwriter = state.underwriters.get(&underwriter_id).ok_or(InsuranceError::UnknownUnderwriter)?;
+    let template = underwriter.templates.get(&template_id).ok_or(InsuranceError::UnknownTemplate)?;
+    // The decisive reading stands in for one reading of the policy's own peril
+    let single_reading = template.is_plain() && template.bundle.is_none() && template.forecast_min_bps.is_none();
+    require!(single_reading, InsuranceError::InvalidArgument("Route cover needs a product that settles on single readings.".into()));
+    require!(!needs_diverse_sources(&state.config, quoted.payout), InsuranceError::InvalidArgument("Payouts this large settle on one location's readings from two providers.".into()));
+    let queries_stations = underwriter.provider.kind.queries_stations();
+
+    let mut premiums = vec![quoted.premium];
+    for stop in &stops {
+        require!(queries_stations || !matches!(stop, Location::Station(_)), InsuranceError::InvalidArgument("This underwriter's weather provider can't be queried by station.".into()));
+        let stop_quote = quote::quote(state, underwriter_id, template_id, &stop.key(), threshold, PayoutSpec::Absolute(quoted.payout), curve, coverage_secs, now)?;
+        premiums.push(stop_quote.premium);
+    }
+
+    let premium = route_premium(terms.aggregation, &premiums, quoted.payout);
+    Ok((RouteTerms { stops, aggregation: terms.aggregation }, premium))
+}
+
+// The route a new policy at `own` carries, its stops keyed as the policy is
+pub(crate) async fn write_route(
+    state:          &mut InsuranceState,
+    underwriter_id: UnderwriterId,
+    own:            RouteStop,
+    terms:          RouteTerms,
+    now:            i64,
+) -> RouteCover {
+
+    let mut stops = vec![own];
+    for place in terms.stops {
+        let location = geocoding::location_key(state, underwriter_id, &place, now).await;
+        stops.push(RouteStop::new(place, location));
+    }
+    RouteCover { aggregation: terms.aggregation, stops }
+}
+
+// Read every stop of a route policy at `when` and settle on the decisive
+// reading. Returns false, untouched, if the budget can't cover every call.
+pub(crate) async fn check_route(
+    state:       &mut InsuranceState,
+    vault:       &Vault,
+    policy_id:   PolicyId,
+    when:        ReadingTime,
+    observed_at: i64,
+    now:         i64,
+    budget:      &mut CallBudget,
+) -> RialoResult<bool> {
+
+    let policy = state.policies.get(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+    let route = policy.route.clone().ok_or_else(|| InsuranceError::InvalidState("Policy has no route.".into()))?;
+    let (underwriter_id, peril, comparison) = (policy.underwriter_id, policy.peril, policy.comparison);
+    let underwriter = state.underwriters.get(&underwriter_id).ok_or(InsuranceError::UnknownUnderwriter)?;
+    let source = underwriter.provider.base_url_for(state.config.network_mode).to_string();
+
+    // Only this hour's readings are cached
+    let cached = |state: &InsuranceState, stop: &RouteStop| match when {
+        ReadingTime::Current => state.weather_cache.get(&source, &stop.location, now),
+        ReadingTime::Hour(_) => None,
+    };
+    let mut calls = route.stops.iter().filter(|s| cached(state, s).is_none()).count() as u32;
+    if budget.remaining() < calls {
+        return Ok(false);
+    }
+
+    let mut observations = Vec::new();
+    let mut call_cost = Ralo::ZERO;
+    for stop in &route.stops {
+        let observation = match cached(state, stop) {
+            Some(observation) => observation,
+            None => {
+                // Retries at one stop leave a call for each stop after it
+                calls -= 1;
+                let (observation, cost) = read_stop(state, policy_id, &source, stop, when, now, budget, calls).await?;
+                call_cost += cost;
+                let Some(observation) = observation else {
+                    claims::record_lae(state, policy_id, LaeKind::ProviderCost, call_cost);
+                    return Ok(true);
+                };
+                if matches!(when, ReadingTime::Current) {
+                    state.weather_cache.insert(&source, &stop.location, now, observation.clone());
+                }
+                observation
+            }
+        };
+        observations.push(observation);
+    }
+
+    let readings = observations
+        .iter()
+        .map(|o| o.metric(peril).ok_or_else(|| InsuranceError::BadResponse("Weather response has no reading for this peril.".into())))
+        .collect::<Result<Vec<Hundredths>, _>>()?;
+    let decisive = decisive_stop(route.aggregation, comparison, &readings).ok_or_else(|| InsuranceError::InvalidState("Route has no stops.".into()))?;
+    let reading = readings[decisive];
+
+    if let Some(cover) = state.policies.get_mut(&policy_id).and_then(|p| p.route.as_mut()) {
+        for (stop, reading) in cover.stops.iter_mut().zip(&readings) {
+            stop.last_value = Some(*reading);
+        }
+    }
+    emit!(RouteChecked {
+        policy_id,
+        aggregation: route.aggregation,
+        readings:    route.stops.iter().zip(&readings).map(|(s, r)| StopReading { location: s.location.clone(), reading: *r }).collect(),
+        decisive:    route.stops[decisive].location.clone(),
+        reading,
+        observed_at,
+    });
+
+    fx::refresh_spot(state, policy_id, reading, now, budget).await;
+    let Observation { station, response, .. } = observations.swap_remove(decisive);
+    evaluate_reading(state, vault, policy_id, &source, station, response, reading, observed_at, now, call_cost)?;
+
+    Ok(true)
+}
+
+// One stop's reading from the tenant's provider, or None if it couldn't be had,
+// with what the calls cost. Keeps `spare` calls of the budget back.
+#[allow(clippy::too_many_arguments)]
+async fn read_stop(
+    state:     &mut InsuranceState,
+    policy_id: PolicyId,
+    source:    &str,
+    stop:      &RouteStop,
+    when:      ReadingTime,
+    now:       i64,
+    budget:    &mut CallBudget,
+    spare:     u32,
+) -> RialoResult<(Option<Observation>, Ralo)> {
+
+    let policy = state.policies.get(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+    let (underwriter_id, template_id) = (policy.underwriter_id, policy.template_id);
+    let lease = keys::lease_key(state, underwriter_id, now)?;
+
+    let underwriter = state.underwriters.get(&underwriter_id).ok_or(InsuranceError::UnknownUnderwriter)?;
+    let provider = &underwriter.provider;
+    let urls: Vec<String> = provider.endpoints_for(state.config.network_mode)
+        .into_iter()
+        .map(|base| provider.kind.build_request(base, &lease.key, &stop.place, when))
+        .collect();
+    let headers = provider_headers(underwriter, template_id)?;
+    let (kind, cost_per_call) = (provider.kind, provider.cost_per_call);
+
+    let fetched = resilience::fetch_with_retry(&urls, &headers, budget, spare).await;
+    let call_cost = cost_per_call.times(fetched.attempts as u64);
+    if let Some(status) = fetched.status() {
+        if !keys::report_key(state, underwriter_id, &lease, status, now) {
+            return Ok((None, call_cost));
+        }
+    }
+
+    let parsed = fetched.result.and_then(|response| {
+        kind.parse_observation(when, response.body())
+            .map(|observation| observation.with_response(response.digest()))
+            .map_err(|_| FetchError::ParseError)
+    });
+    match parsed {
+        Ok(observation) => Ok((Some(observation), call_cost)),
+        Err(reason) => {
+            resilience::report_failure(state, policy_id, source, fetched.attempts, reason);
+            Ok((None, call_cost))
+        }
+    }
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct RouteChecked { pub policy_id: PolicyId, pub aggregation: Aggregation, pub readings: Vec<StopReading>, pub decisive: String, pub reading: Hundredths, pub observed_at: i64 }
@@ -0,0 +1,143 @@
+// ============================================================
+//  Seasonal pricing
+//
+//  A flat premium rate overcharges dry-season cover and
+//  undercharges the rains. Each underwriter can keep a
+//  climatology table: twelve monthly multipliers per location,
+//  in basis points of its base rate (10 000 = 1×). A quote's
+//  factor is the day-weighted mean over the months its coverage
+//  will run through — so a quote issued in the dry season for
+//  cover stretching into the wet season prices the wet days at
+//  the wet rate. The factor used is recorded on the policy.
+//
+//  Calendar months are the insured location's own: an underwriter
+//  can record a location's UTC offset, and the months a quote
+//  prices — and a normal-deviation policy's month-to-date total
+//  (normals.rs) — turn over at local midnight rather than UTC's.
+//  The offset is copied onto each policy at setup. Coverage
+//  windows are lengths of time from activation, so they don't
+//  move with it.
+// ============================================================
+
+use rialo_sdk::prelude::*;
+
+use crate::errors::InsuranceError;
+use crate::normalization::location_slug;
+use crate::underwriter::{tenant_mut, UnderwriterId};
+use crate::InsuranceState;
+
+pub const NEUTRAL_FACTOR_BPS: u64 = 10_000;
+
+const MIN_FACTOR_BPS: u64 = 2_500;    // 0.25×
+const MAX_FACTOR_BPS: u64 = 40_000;   // 4×
+const SECS_PER_DAY:   i64 = 24 * 60 * 60;
+
+// UTC−12:00 to UTC+14:00
+pub const MAX_UTC_OFFSET_MINS: i32 = 14 * 60;
+pub const MIN_UTC_OFFSET_MINS: i32 = -12 * 60;
+
+// January first, basis points of the base premium rate
+pub type MonthlyFactors = [u64; 12];
+
+// A unix time moved onto a local calendar `utc_offset_mins` east of UTC;
+// the calendar functions below then read local dates off it
+pub fn local_time(unix: i64, utc_offset_mins: i32) -> i64 {
+    unix + i64::from(utc_offset_mins) * 60
+}
+
+// Calendar month (0 = January) of a unix time, UTC
+pub fn month_of(unix: i64) -> usize {
+    civil_date(unix).1
+}
+
+// Months since January 1970 of a unix time, UTC — distinct for every calendar month
+pub fn month_index(unix: i64) -> i64 {
+    let (year, month, _) = civil_date(unix);
+    (year - 1970) * 12 + month as i64
+}
+
+// Year, month (0 = January) and day of the month of a unix time, UTC.
+// Civil-from-days over 400-year eras, with years starting in March.
+pub fn civil_date(unix: i64) -> (i64, usize, u32) {
+    let days = unix.div_euclid(SECS_PER_DAY) + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_from_march = (5 * day_of_year + 2) / 153;
+    let month = ((month_from_march + 2) % 12) as usize;
+    let day = (day_of_year - (153 * month_from_march + 2) / 5 + 1) as u32;
+    // January and February belong to the next civil year
+    let year = era * 400 + year_of_era + i64::from(month < 2);
+    (year, month, day)
+}
+
+// Day-weighted mean factor over coverage running from `start` for `secs`
+pub fn coverage_factor_bps(factors: &MonthlyFactors, start: i64, secs: i64) -> u64 {
+    let days = (secs + SECS_PER_DAY - 1) / SECS_PER_DAY;
+    if days <= 0 {
+        return factors[month_of(start)];
+    }
+    let total: u64 = (0..days).map(|d| factors[month_of(start + d * SECS_PER_DAY)]).sum();
+    total / days as u64
+}
+
+// ── Entry point: underwriter sets a location's monthly factors ─
+#[rialo::instruction]
+pub async fn set_climatology(
+    ctx:            Context<InsuranceState>,
+    underwriter_id: UnderwriterId,
+    location:       String,
+    factors:        Option<MonthlyFactors>,
+) -> RialoResult<()> {
+
+    let location = location_slug(&location);
+    require!(!location.is_empty(), InsuranceError::InvalidArgument("Location is required.".into()));
+    if let Some(factors) = &factors {
+        require!(
+            factors.iter().all(|f| (MIN_FACTOR_BPS..=MAX_FACTOR_BPS).contains(f)),
+            InsuranceError::InvalidArgument("Seasonal factors must be between 0.25x and 4x.".into()),
+        );
+    }
+
+    let underwriter = tenant_mut(&mut ctx.state, underwriter_id, &ctx.signer)?;
+    match factors {
+        Some(factors) => underwriter.climatology.insert(location.clone(), factors),
+        None          => underwriter.climatology.remove(&location),
+    };
+
+    emit!(ClimatologySet { underwriter_id, location, factors });
+
+    Ok(())
+}
+
+// ── Entry point: underwriter sets a location's local time ────
+#[rialo::instruction]
+pub async fn set_utc_offset(
+    ctx:             Context<InsuranceState>,
+    underwriter_id:  UnderwriterId,
+    location:        String,
+    utc_offset_mins: Option<i32>,   // minutes east of UTC; None puts the location back on UTC
+) -> RialoResult<()> {
+
+    let location = location_slug(&location);
+    require!(!location.is_empty(), InsuranceError::InvalidArgument("Location is required.".into()));
+    require!(
+        utc_offset_mins.is_none_or(|mins| (MIN_UTC_OFFSET_MINS..=MAX_UTC_OFFSET_MINS).contains(&mins)),
+        InsuranceError::InvalidArgument("UTC offset must be between -12:00 and +14:00.".into()),
+    );
+
+    let underwriter = tenant_mut(&mut ctx.state, underwriter_id, &ctx.signer)?;
+    match utc_offset_mins {
+        Some(mins) => underwriter.utc_offsets.insert(location.clone(), mins),
+        None       => underwriter.utc_offsets.remove(&location),
+    };
+
+    emit!(UtcOffsetSet { underwriter_id, location, utc_offset_mins });
+
+    Ok(())
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct ClimatologySet { pub underwriter_id: UnderwriterId, pub location: String, pub factors: Option<MonthlyFactors> }
+#[rialo::event] pub struct UtcOffsetSet   { pub underwriter_id: UnderwriterId, pub location: String, pub utc_offset_mins: Option<i32> }
@@ -0,0 +1,163 @@
+// ============================================================
+//  Self-dealing control
+//
+//  A key that can run a tenant — or the whole contract — shouldn't
+//  quietly buy cover from it: it could pick the terms, the
+//  provider and the location it is paid on. The admin can have
+//  setup_policy check the beneficiary against the tenant's
+//  authority and co-signers and the contract admin:
+//    • Allow  — no check (the default)
+//    • Flag   — the policy is written, and `SelfDealingFlagged`
+//               puts it on the record
+//    • Reject — the policy is refused
+//  A rejected pairing can still go through with an override: the
+//  admin grants one for an underwriter and a beneficiary, it lets
+//  exactly one policy through, and `SelfDealingOverridden` records
+//  that it did.
+// ============================================================
+
+use rialo_sdk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::InsuranceError;
+use crate::policy::PolicyId;
+use crate::underwriter::UnderwriterId;
+use crate::InsuranceState;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SelfDealingPolicy {
+    #[default]
+    Allow,
+    Flag,
+    Reject,
+}
+
+// Which of the keys with control over a policy its beneficiary holds
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConflictedKey {
+    UnderwriterAuthority,
+    CoSigner,
+    Admin,
+}
+
+// One policy the admin lets through despite the conflict
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SelfDealingOverride {
+    pub underwriter_id: UnderwriterId,
+    pub beneficiary:    Pubkey,
+}
+
+pub fn conflict(admin: &Pubkey, authority: &Pubkey, co_signers: &[Pubkey], beneficiary: &Pubkey) -> Option<ConflictedKey> {
+    if beneficiary == authority {
+        Some(ConflictedKey::UnderwriterAuthority)
+    } else if co_signers.contains(beneficiary) {
+        Some(ConflictedKey::CoSigner)
+    } else if beneficiary == admin {
+        Some(ConflictedKey::Admin)
+    } else {
+        None
+    }
+}
+
+// Apply the self-dealing policy to `policy_id`, about to be written by
+// `underwriter_id` for `beneficiary`
+pub(crate) fn check(
+    state:          &mut InsuranceState,
+    underwriter_id: UnderwriterId,
+    policy_id:      PolicyId,
+    beneficiary:    &Pubkey,
+) -> RialoResult<()> {
+
+    if let Some(key) = vet(state, underwriter_id, beneficiary)? {
+        record(state, underwriter_id, policy_id, beneficiary, key);
+    }
+    Ok(())
+}
+
+// Whether `beneficiary` may buy from `underwriter_id`, touching nothing.
+// Returns the conflict to put on the record, if any; a refused pairing
+// without an override is an error.
+pub(crate) fn vet(
+    state:          &InsuranceState,
+    underwriter_id: UnderwriterId,
+    beneficiary:    &Pubkey,
+) -> RialoResult<Option<ConflictedKey>> {
+
+    let mode = state.config.self_dealing;
+    if mode == SelfDealingPolicy::Allow {
+        return Ok(None);
+    }
+    let underwriter = state.underwriters.get(&underwriter_id).ok_or(InsuranceError::UnknownUnderwriter)?;
+    let key = conflict(&state.config.admin, &underwriter.authority, &underwriter.co_signers, beneficiary);
+
+    let wanted = SelfDealingOverride { underwriter_id, beneficiary: *beneficiary };
+    let overridden = state.config.conflict_overrides.contains(&wanted);
+    require!(key.is_none() || mode == SelfDealingPolicy::Flag || overridden, InsuranceError::InvalidState("Beneficiary holds a key that controls this policy's underwriter.".into()));
+
+    Ok(key)
+}
+
+// Put a conflict `vet` let through on the record, spending its override if
+// it needed one
+pub(crate) fn record(
+    state:          &mut InsuranceState,
+    underwriter_id: UnderwriterId,
+    policy_id:      PolicyId,
+    beneficiary:    &Pubkey,
+    key:            ConflictedKey,
+) {
+    if state.config.self_dealing == SelfDealingPolicy::Flag {
+        emit!(SelfDealingFlagged { policy_id, underwriter_id, beneficiary: *beneficiary, key });
+        return;
+    }
+
+    let wanted = SelfDealingOverride { underwriter_id, beneficiary: *beneficiary };
+    let overrides = &mut state.config.conflict_overrides;
+    if let Some(index) = overrides.iter().position(|o| *o == wanted) {
+        overrides.remove(index);
+    }
+
+    emit!(SelfDealingOverridden { policy_id, underwriter_id, beneficiary: *beneficiary, key });
+}
+
+// ── Entry point: admin sets the self-dealing policy ──────────
+#[rialo::instruction]
+pub async fn set_self_dealing_policy(
+    ctx:    Context<InsuranceState>,
+    policy: SelfDealingPolicy,
+) -> RialoResult<()> {
+
+    let config = &mut ctx.state.config;
+
+    require!(config.admin == *ctx.signer, InsuranceError::Unauthorized("Only the admin can change the self-dealing policy.".into()));
+
+    config.self_dealing = policy;
+
+    emit!(SelfDealingPolicySet { policy });
+
+    Ok(())
+}
+
+// ── Entry point: admin lets one conflicted policy through ────
+#[rialo::instruction]
+pub async fn grant_self_dealing_override(
+    ctx:            Context<InsuranceState>,
+    underwriter_id: UnderwriterId,
+    beneficiary:    Pubkey,
+) -> RialoResult<()> {
+
+    require!(ctx.state.config.admin == *ctx.signer, InsuranceError::Unauthorized("Only the admin can grant self-dealing overrides.".into()));
+    require!(ctx.state.underwriters.contains_key(&underwriter_id), InsuranceError::UnknownUnderwriter);
+
+    ctx.state.config.conflict_overrides.push(SelfDealingOverride { underwriter_id, beneficiary });
+
+    emit!(SelfDealingOverrideGranted { underwriter_id, beneficiary, granted_by: *ctx.signer });
+
+    Ok(())
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct SelfDealingPolicySet       { pub policy: SelfDealingPolicy }
+#[rialo::event] pub struct SelfDealingOverrideGranted { pub underwriter_id: UnderwriterId, pub beneficiary: Pubkey, pub granted_by: Pubkey }
+#[rialo::event] pub struct SelfDealingFlagged         { pub policy_id: PolicyId, pub underwriter_id: UnderwriterId, pub beneficiary: Pubkey, pub key: ConflictedKey }
+#[rialo::event] pub struct SelfDealingOverridden      { pub policy_id: PolicyId, pub underwriter_id: UnderwriterId, pub beneficiary: Pubkey, pub key: ConflictedKey }
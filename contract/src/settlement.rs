@@ -0,0 +1,255 @@
+// ============================================================
+//  Settlement
+//
+//  The single place a reading turns into money. Oracle checks
+//  and arbiter-approved manual observations both settle through
+//  here, so vault accounting, the claims ledger and settlement
+//  receipts can't drift between the two paths. USD payouts are
+//  converted here too, behind the slippage guard in fx.rs.
+//  Policies on a graded curve (curves.rs) are paid their share of
+//  the payout here as well.
+// ============================================================
+
+use rialo_sdk::prelude::*;
+use rialo_sdk::token::transfer;
+
+use crate::attestation;
+use crate::bundles;
+use crate::copay;
+use crate::disputes;
+use crate::errors::InsuranceError;
+use crate::fx;
+use crate::millimeters::{Hundredths, Millimeters};
+use crate::mints;
+use crate::money::Ralo;
+use crate::observations::CheckId;
+use crate::outbox;
+use crate::policy::{Policy, PolicyId, PolicyStatus};
+use crate::receipts::{self, SettlementKey};
+use crate::{InsuranceState, PolicyTriggered};
+
+pub const FULL_SHARE_BPS: u64 = 10_000;
+
+// Apply a reading taken at `observed_at` to a policy in a transaction at `now`;
+// pays out and returns true when it triggers. `round_id` is the check the
+// reading will be logged as, if it is one.
+pub fn settle(
+    state:       &mut InsuranceState,
+    vault:       &Vault,
+    policy_id:   PolicyId,
+    round_id:    Option<CheckId>,
+    reading:     Hundredths,
+    observed_at: i64,
+    now:         i64,
+) -> RialoResult<bool> {
+
+    let policy = state.policies.get_mut(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+    if policy.bundle.is_some() {
+        let peril = policy.peril;
+        return bundles::settle_readings(state, vault, policy_id, round_id, &[(peril, reading)], observed_at, now);
+    }
+    if policy.graded.is_some() {
+        return settle_graded(state, vault, policy_id, round_id, reading, observed_at, now);
+    }
+
+    let triggered = policy.apply_reading(reading, observed_at);
+
+    if let Some(streak) = policy.streak {
+        emit!(RainStreakUpdated { policy_id, hours: streak.hours(), required: streak.required_hours });
+    }
+    if let Some(accumulation) = &policy.accumulation {
+        emit!(RainfallAccumulated { policy_id, window_hours: accumulation.window_hours, total_mm: accumulation.latest_total_mm() });
+    }
+    if let Some(normal) = &policy.normal {
+        emit!(MonthlyRainfallUpdated { policy_id, month_mm: normal.month_mm, percent_of_normal: normal.percent_of_normal(observed_at) });
+    }
+    if !triggered {
+        return Ok(false);
+    }
+
+    pay_out(state, vault, policy_id, round_id, reading, observed_at, now)?;
+
+    Ok(true)
+}
+
+// Top a graded policy up to the share its curve puts on a reading; returns
+// true when the share went up. Reaching the full share settles it.
+fn settle_graded(
+    state:       &mut InsuranceState,
+    vault:       &Vault,
+    policy_id:   PolicyId,
+    round_id:    Option<CheckId>,
+    reading:     Hundredths,
+    observed_at: i64,
+    now:         i64,
+) -> RialoResult<bool> {
+
+    let policy = state.policies.get_mut(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+    if policy.status != PolicyStatus::Active || !policy.is_covered_at(observed_at) {
+        return Ok(false);
+    }
+    let (comparison, threshold) = (policy.comparison, policy.threshold);
+    let Some(graded) = policy.graded.as_mut() else {
+        return Ok(false);
+    };
+
+    // Shares only ratchet up
+    let share_bps = graded.payout_curve.share_bps(comparison, threshold, reading);
+    if share_bps <= graded.paid_bps {
+        return Ok(false);
+    }
+    graded.paid_bps = share_bps;
+
+    let paid = pay_share(state, vault, policy_id, round_id, share_bps, reading, observed_at, now)?;
+    emit!(GradedShareReached { policy_id, reading, share_bps, paid });
+
+    Ok(true)
+}
+
+// Send the full payout on a policy that has just moved to PaidOut, and file its receipt
+pub(crate) fn pay_out(
+    state:       &mut InsuranceState,
+    vault:       &Vault,
+    policy_id:   PolicyId,
+    round_id:    Option<CheckId>,
+    reading:     Hundredths,
+    observed_at: i64,
+    now:         i64,
+) -> RialoResult<()> {
+
+    let key = SettlementKey { policy_id, round_id };
+    if receipts::is_settled(state, key) {
+        return Ok(());
+    }
+
+    // A USD payout the slippage guard deferred triggers when retry_payout pays it
+    pay_share(state, vault, policy_id, round_id, FULL_SHARE_BPS, reading, observed_at, now)?;
+
+    Ok(())
+}
+
+// Emit PolicyTriggered for an amount just paid and the co-pay kept off it, with
+// the policy's cumulative figures, and queue it in the outbox for relayed notifications
+fn announce_trigger(state: &mut InsuranceState, key: SettlementKey, reading: Hundredths, paid: Ralo, copay: Ralo, now: i64) {
+    let Some(policy) = state.policies.get(&key.policy_id) else {
+        return;
+    };
+    let (owner, total_paid, coverage_remaining) = (policy.owner, policy.paid_out, policy.coverage_remaining());
+    let ralo_usd = policy.usd_payout.and_then(|usd| usd.rate);
+    receipts::mark_settled(state, key);
+    state.metrics.settlements += 1;
+
+    emit!(PolicyTriggered {
+        policy_id:          key.policy_id,
+        round_id:           key.round_id,
+        delivery_company:   owner,
+        rainfall_mm:        reading,
+        payout:             paid,
+        copay,
+        total_paid,
+        coverage_remaining,
+        ralo_usd,
+    });
+
+    outbox::enqueue(state, key, owner, paid, reading, now);
+}
+
+// What topping a policy up to `share_bps` of its payout sends, and the co-pay
+// the customer newly absorbs with it. The share is of the full cover; the
+// co-pay comes off it, and whatever was already paid or absorbed is netted out.
+pub fn top_up(policy: &Policy, share_bps: u64) -> (Ralo, Ralo) {
+    let gross = policy.payout_amount.bps(share_bps.min(FULL_SHARE_BPS));
+    let due = copay::net(gross, policy.copay_bps).saturating_sub(policy.paid_out);
+    let retained = copay::retained(gross, policy.copay_bps).saturating_sub(policy.copay_retained);
+    (due, retained)
+}
+
+// Top a policy up to `share_bps` of its payout, net of what it has already
+// received, then file a receipt for the top-up and announce it. A partial
+// share leaves the rest of the cover live; reaching the full share settles
+// the policy. A payout awaiting the beneficiary's attestation (attestation.rs)
+// or its dispute window (disputes.rs) pays nothing until then; a USD payout converts first (fx.rs) and
+// pays nothing while the price is refused. Returns the amount sent.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn pay_share(
+    state:       &mut InsuranceState,
+    vault:       &Vault,
+    policy_id:   PolicyId,
+    round_id:    Option<CheckId>,
+    share_bps:   u64,
+    reading:     Hundredths,
+    observed_at: i64,
+    now:         i64,
+) -> RialoResult<Ralo> {
+
+    if !attestation::await_attestation(state, policy_id, round_id, share_bps, reading, observed_at, now)? {
+        return Ok(Ralo::ZERO);
+    }
+    if !disputes::await_window(state, policy_id, round_id, share_bps, reading, observed_at, now)? {
+        return Ok(Ralo::ZERO);
+    }
+    if !fx::lock_conversion(state, policy_id, round_id, share_bps, reading, observed_at, now)? {
+        return Ok(Ralo::ZERO);
+    }
+
+    let policy = state.policies.get_mut(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+    let underwriter = state.underwriters.get_mut(&policy.underwriter_id).ok_or(InsuranceError::UnknownUnderwriter)?;
+
+    if share_bps >= FULL_SHARE_BPS {
+        policy.status = PolicyStatus::PaidOut;
+    }
+    let (due, retained) = top_up(policy, share_bps);
+    if due.is_zero() {
+        // Nothing left to send, but the full share still settles the policy:
+        // its mint reservation goes back and the receipt records the round
+        if policy.status == PolicyStatus::PaidOut {
+            let owner = policy.owner;
+            mints::release(state, policy_id);
+            receipts::issue(state, owner, policy_id, round_id, reading, observed_at, Ralo::ZERO, now);
+        }
+        return Ok(Ralo::ZERO);
+    }
+
+    let reserved = underwriter.reserved.checked_sub(due).ok_or(InsuranceError::ArithmeticOverflow)?;
+
+    // A mint-paying policy sends its tokens instead, and the RALO stays with
+    // the underwriter as free capital (mints.rs)
+    let owner = policy.owner;
+    match policy.payout_mint.as_mut() {
+        Some(terms) => {
+            mints::pay(underwriter, vault, policy_id, terms, &owner, policy.paid_out + due, policy.payout_amount)?;
+        }
+        None => {
+            let capital = underwriter.capital.checked_sub(due).ok_or(InsuranceError::ArithmeticOverflow)?;
+            transfer(vault, &owner, due.base_units())?;
+            underwriter.capital = capital;
+        }
+    }
+
+    underwriter.reserved = reserved;
+    underwriter.claims.claims_paid += due;
+    let product = underwriter.claims.template_mut(policy.template_id);
+    product.claims_paid += due;
+    product.payments    += 1;
+    product.settle_secs += now.saturating_sub(observed_at).max(0) as u64;
+    if policy.paid_out.is_zero() {
+        underwriter.claims.claims_count += 1;
+        underwriter.claims.template_mut(policy.template_id).claims_count += 1;
+    }
+    policy.paid_out += due;
+    policy.copay_retained += retained;
+    if policy.status == PolicyStatus::PaidOut {
+        mints::release(state, policy_id);
+    }
+
+    receipts::issue(state, owner, policy_id, round_id, reading, observed_at, due, now);
+    announce_trigger(state, SettlementKey { policy_id, round_id }, reading, due, retained, now);
+
+    Ok(due)
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct RainStreakUpdated      { pub policy_id: PolicyId, pub hours: u32, pub required: u32 }
+#[rialo::event] pub struct RainfallAccumulated    { pub policy_id: PolicyId, pub window_hours: u32, pub total_mm: Millimeters }
+#[rialo::event] pub struct MonthlyRainfallUpdated { pub policy_id: PolicyId, pub month_mm: Millimeters, pub percent_of_normal: f64 }
+#[rialo::event] pub struct GradedShareReached     { pub policy_id: PolicyId, pub reading: Hundredths, pub share_bps: u64, pub paid: Ralo }
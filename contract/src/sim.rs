@@ -0,0 +1,226 @@
+// ============================================================
+//  Lifecycle simulator (dev-only, `sim` feature)
+//
+//  Lets a product designer try a payout curve, threshold or
+//  co-pay against a weather series before anything touches
+//  DevNet: a scenario describes the cover, a series the weather
+//  (one reading a day, at midday), and `run` walks the policy
+//  from activation to the end of its window, in memory:
+//
+//    activate → one check a day → graded or flat settlement → expiry
+//
+//  The share a reading is worth and the amount each trigger sends
+//  come from the same code the contract settles with (curves.rs,
+//  settlement::top_up), so a timeline here pays what DevNet would.
+//  Everything around a check — provider calls, consensus, the
+//  cooldown, incidents, fx — is left out.
+//
+//  Synthetic series are deterministic: the same seed gives the
+//  same weather, so a timeline can sit in a test or a design doc.
+// ============================================================
+
+use std::fmt::Write;
+
+use rialo_sdk::prelude::*;
+
+use crate::curves::{GradedCover, PayoutCurve};
+use crate::millimeters::Hundredths;
+use crate::money::{format_ralo, Ralo};
+use crate::normalization::Comparison;
+use crate::policy::{Policy, PolicyStatus};
+use crate::settlement::{top_up, FULL_SHARE_BPS};
+
+const SECS_PER_DAY: i64 = 24 * 60 * 60;
+
+// The cover being designed
+#[derive(Clone, Debug)]
+pub struct Scenario {
+    pub threshold:  f64,
+    pub comparison: Comparison,
+    pub payout:     Ralo,
+    pub premium:    Ralo,
+    pub curve:      PayoutCurve,
+    pub copay_bps:  u64,
+    pub start:      i64,   // activation time
+    pub days:       u32,   // coverage length
+}
+
+impl Scenario {
+    // Flat cover paying at or above `threshold` over a 30-day window
+    pub fn new(threshold: f64, payout: Ralo, premium: Ralo) -> Self {
+        Scenario {
+            threshold,
+            comparison: Comparison::AtOrAbove,
+            payout,
+            premium,
+            curve:      PayoutCurve::Flat,
+            copay_bps:  0,
+            start:      0,
+            days:       30,
+        }
+    }
+}
+
+// Synthetic daily weather, in the peril's unit
+#[derive(Clone, Debug)]
+pub enum Series {
+    Constant(f64),
+    Ramp { from: f64, to: f64 },                        // straight line over the window
+    Spike { base: f64, peak: f64, day: u32 },           // one bad day on a steady background
+    Noise { mean: f64, amplitude: f64, seed: u64 },     // uniform around the mean, repeatable by seed
+    Readings(Vec<f64>),                                 // recorded or hand-written; missing days read the last one
+}
+
+impl Series {
+    pub fn reading(&self, day: u32, days: u32) -> f64 {
+        match self {
+            Series::Constant(value) => *value,
+            Series::Ramp { from, to } => {
+                let span = days.saturating_sub(1).max(1) as f64;
+                from + (to - from) * day as f64 / span
+            }
+            Series::Spike { base, peak, day: at } => if day == *at { *peak } else { *base },
+            Series::Noise { mean, amplitude, seed } => mean + amplitude * (2.0 * unit_noise(*seed, day) - 1.0),
+            Series::Readings(readings) => readings
+                .get(day as usize)
+                .or(readings.last())
+                .copied()
+                .unwrap_or(0.0),
+        }
+    }
+}
+
+// Repeatable value in [0, 1) for a seed and day (splitmix64)
+fn unit_noise(seed: u64, day: u32) -> f64 {
+    let mut z = seed.wrapping_add((day as u64 + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^= z >> 31;
+    (z >> 11) as f64 / (1u64 << 53) as f64
+}
+
+// One simulated day
+#[derive(Clone, Debug, PartialEq)]
+pub struct SimDay {
+    pub day:       u32,
+    pub reading:   f64,
+    pub share_bps: u64,    // share of the cover payable after this day's check
+    pub paid:      Ralo,   // sent on this day's check
+    pub copay:     Ralo,   // absorbed by the customer on this day's check
+    pub status:    PolicyStatus,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Timeline {
+    pub days:         Vec<SimDay>,   // ends early once the policy is paid out
+    pub premium:      Ralo,
+    pub total_paid:   Ralo,
+    pub final_status: PolicyStatus,
+}
+
+impl Timeline {
+    pub fn triggers(&self) -> impl Iterator<Item = &SimDay> {
+        self.days.iter().filter(|d| !d.paid.is_zero())
+    }
+
+    // Paid out over premium taken (10 000 = 1×)
+    pub fn loss_ratio_bps(&self) -> u64 {
+        if self.premium.is_zero() {
+            return 0;
+        }
+        (self.total_paid.0 as u128 * 10_000 / self.premium.0 as u128) as u64
+    }
+
+    // Day-by-day table, then totals
+    pub fn report(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "{:>4}  {:>9}  {:>7}  {:>14}  {:>14}  status", "day", "reading", "share", "paid", "co-pay");
+        for day in &self.days {
+            let _ = writeln!(
+                out,
+                "{:>4}  {:>9.2}  {:>6.2}%  {:>14}  {:>14}  {:?}",
+                day.day,
+                day.reading,
+                day.share_bps as f64 / 100.0,
+                format_ralo(day.paid),
+                format_ralo(day.copay),
+                day.status,
+            );
+        }
+        let _ = writeln!(
+            out,
+            "paid {} on {} premium over {} checks ({:.2}× loss ratio), ended {:?}",
+            format_ralo(self.total_paid),
+            format_ralo(self.premium),
+            self.days.len(),
+            self.loss_ratio_bps() as f64 / 10_000.0,
+            self.final_status,
+        );
+        out
+    }
+}
+
+// Run a policy written on `scenario` through `series`, one check a day
+pub fn run(scenario: &Scenario, series: &Series) -> Timeline {
+    let mut policy = Policy::new(0, 0, Pubkey::default(), "sim".into(), Hundredths::from_f64(scenario.threshold), scenario.payout, scenario.premium);
+    policy.comparison    = scenario.comparison;
+    policy.copay_bps     = scenario.copay_bps;
+    policy.coverage_secs = scenario.days as i64 * SECS_PER_DAY;
+    policy.graded        = (!scenario.curve.is_flat()).then(|| GradedCover::new(scenario.curve.clone()));
+    policy.record_premium(scenario.premium, scenario.start);
+
+    let mut days = Vec::new();
+    let mut share_bps = 0;
+    for day in 0..scenario.days {
+        let at = scenario.start + day as i64 * SECS_PER_DAY + SECS_PER_DAY / 2;
+        let reading = series.reading(day, scenario.days);
+
+        let (mut paid, mut copay) = (Ralo::ZERO, Ralo::ZERO);
+        if let Some(reached) = check(&mut policy, Hundredths::from_f64(reading), at) {
+            share_bps = reached;
+            (paid, copay) = settle(&mut policy, reached);
+        }
+        days.push(SimDay { day, reading, share_bps, paid, copay, status: policy.status });
+
+        if policy.status != PolicyStatus::Active {
+            break;
+        }
+    }
+    if policy.status == PolicyStatus::Active {
+        policy.status = PolicyStatus::Expired;
+    }
+
+    Timeline { days, premium: scenario.premium, total_paid: policy.paid_out, final_status: policy.status }
+}
+
+// The share a reading newly makes payable, as settlement::settle decides it
+fn check(policy: &mut Policy, reading: Hundredths, at: i64) -> Option<u64> {
+    if policy.status != PolicyStatus::Active || !policy.is_covered_at(at) {
+        return None;
+    }
+    let (comparison, threshold) = (policy.comparison, policy.threshold);
+    match policy.graded.as_mut() {
+        Some(graded) => {
+            let share_bps = graded.payout_curve.share_bps(comparison, threshold, reading);
+            (share_bps > graded.paid_bps).then(|| {
+                graded.paid_bps = share_bps;
+                share_bps
+            })
+        }
+        None => policy.apply_reading(reading, at).then_some(FULL_SHARE_BPS),
+    }
+}
+
+// Book a top-up the way settlement::pay_share does, without the vault
+fn settle(policy: &mut Policy, share_bps: u64) -> (Ralo, Ralo) {
+    if share_bps >= FULL_SHARE_BPS {
+        policy.status = PolicyStatus::PaidOut;
+    }
+    let (due, retained) = top_up(policy, share_bps);
+    if due.is_zero() {
+        return (Ralo::ZERO, Ralo::ZERO);
+    }
+    policy.paid_out       += due;
+    policy.copay_retained += retained;
+    (due, retained)
+}
@@ -0,0 +1,134 @@
+// ============================================================
+//  Index smoothing
+//
+//  Some readings jitter: a wind gust, one wet sample in a dry
+//  hour. A template can smooth them before they are compared with
+//  the threshold: its policies keep their last few readings and
+//  settle on a rolling statistic of them instead of the newest
+//  reading alone —
+//    • RollingMean   — the mean of the last `samples` readings
+//    • RollingMedian — their median, which ignores a lone spike
+//  A policy settles on nothing until it has `samples` readings;
+//  after that every reading moves the window on by one. Readings
+//  are ordered by when they were taken, so a historical check
+//  can fill in behind the newest one; a reading older than the
+//  whole window, or one for a moment already filed, adds nothing.
+//
+//  Smoothing is a stage of `evaluation::evaluate`, ahead of the
+//  forecast check and settlement. It's offered on products that
+//  compare single readings — not on rolling or normal-deviation
+//  cover, whose totals it would distort. Arbiter overrides and
+//  bundle riders settle on the value as given.
+// ============================================================
+
+use rialo_sdk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::InsuranceError;
+use crate::normalization::Metric;
+use crate::underwriter::{tenant_mut, Template, TemplateId, UnderwriterId};
+use crate::InsuranceState;
+
+pub const MAX_SMOOTHING_SAMPLES: u32 = 12;
+
+// How a template's readings are smoothed
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Smoothing {
+    RollingMean { samples: u32 },
+    RollingMedian { samples: u32 },
+}
+
+impl Smoothing {
+    pub fn samples(self) -> u32 {
+        match self {
+            Smoothing::RollingMean { samples } | Smoothing::RollingMedian { samples } => samples,
+        }
+    }
+
+    // The statistic over a full window of readings
+    pub fn apply(self, readings: &[f64]) -> f64 {
+        match self {
+            Smoothing::RollingMean { .. } => readings.iter().sum::<f64>() / readings.len() as f64,
+            Smoothing::RollingMedian { .. } => {
+                let mut sorted = readings.to_vec();
+                sorted.sort_by(f64::total_cmp);
+                let mid = sorted.len() / 2;
+                if sorted.len().is_multiple_of(2) { (sorted[mid - 1] + sorted[mid]) / 2.0 } else { sorted[mid] }
+            }
+        }
+    }
+}
+
+// Smoothing state carried by a policy sold under a smoothing template
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SmoothedIndex {
+    pub smoothing: Smoothing,
+    pub readings:  Vec<(i64, f64)>,   // (taken at, reading), oldest first, at most a window
+}
+
+impl SmoothedIndex {
+    pub fn new(smoothing: Smoothing) -> Self {
+        SmoothedIndex { smoothing, readings: Vec::new() }
+    }
+
+    // File a reading taken at `at`; the smoothed index once the window is
+    // full, or None if there isn't one yet or the reading added nothing
+    pub fn record(&mut self, reading: f64, at: i64) -> Option<f64> {
+        let window = self.smoothing.samples() as usize;
+        let full = self.readings.len() >= window;
+        if full && self.readings.first().is_some_and(|&(oldest, _)| at < oldest) {
+            return None;
+        }
+        let index = self.readings.partition_point(|&(t, _)| t < at);
+        if self.readings.get(index).is_some_and(|&(t, _)| t == at) {
+            return None;
+        }
+        self.readings.insert(index, (at, reading));
+        if self.readings.len() > window {
+            self.readings.remove(0);
+        }
+
+        self.value()
+    }
+
+    pub fn value(&self) -> Option<f64> {
+        if self.readings.len() < self.smoothing.samples() as usize {
+            return None;
+        }
+        let readings: Vec<f64> = self.readings.iter().map(|&(_, r)| r).collect();
+        Some(self.smoothing.apply(&readings))
+    }
+}
+
+// Whether a template settles on single readings smoothing can stand in for:
+// plain threshold products and continuous-rain ones
+pub fn can_smooth(template: &Template) -> bool {
+    template.peril() == template.metric.unwrap_or(Metric::Rainfall) && template.evaluator.is_none()
+        && template.rolling_hours.is_none() && !template.rain_normal
+}
+
+// ── Entry point: smooth (or stop smoothing) a template's readings
+#[rialo::instruction]
+pub async fn set_template_smoothing(
+    ctx:            Context<InsuranceState>,
+    underwriter_id: UnderwriterId,
+    template_id:    TemplateId,
+    smoothing:      Option<Smoothing>,
+) -> RialoResult<()> {
+
+    require!(smoothing.is_none_or(|s| (2..=MAX_SMOOTHING_SAMPLES).contains(&s.samples())), InsuranceError::InvalidArgument("Smoothing takes between 2 and 12 readings.".into()));
+
+    let underwriter = tenant_mut(&mut ctx.state, underwriter_id, &ctx.signer)?;
+    let template = underwriter.templates.get_mut(&template_id).ok_or(InsuranceError::UnknownTemplate)?;
+    if smoothing.is_some() {
+        require!(can_smooth(template), InsuranceError::InvalidState("Smoothing is only offered on products that compare single readings.".into()));
+    }
+    template.smoothing = smoothing;
+
+    emit!(TemplateSmoothingSet { underwriter_id, template_id, smoothing });
+
+    Ok(())
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct TemplateSmoothingSet { pub underwriter_id: UnderwriterId, pub template_id: TemplateId, pub smoothing: Option<Smoothing> }
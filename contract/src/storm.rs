@@ -0,0 +1,214 @@
+// ============================================================
+//  Storm-track cover
+//
+//  Parametric hurricane cover for one insured point. Each check
+//  reads the active tropical cyclones from the NHC feed
+//  (CurrentStorms.json), takes every storm's current position and
+//  Saffir–Simpson category, and pays the best tier any storm
+//  reaches:
+//    tier = (within X km, category ≥ C) → P% of the payout
+//  Tiers top up: when a later check reaches a higher tier only the
+//  difference is paid, and reaching 100% settles the policy.
+//  Distances use the fixed-point haversine in geo.rs.
+// ============================================================
+
+use rialo_sdk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::claims::{record_lae, LaeKind};
+use crate::config::{require_unpaused, FeedKind};
+use crate::errors::InsuranceError;
+use crate::geo::{haversine_km, GeoPoint, KM_SCALE};
+use crate::millimeters::Hundredths;
+use crate::money::Ralo;
+use crate::policy::{PolicyId, PolicyStatus};
+use crate::underwriter::{tenant_mut, TemplateId, UnderwriterId};
+use crate::{fetch, settlement, InsuranceState};
+
+const MAX_STORM_TIERS: usize = 5;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StormTier {
+    pub within_km:    u64,   // closest approach that qualifies
+    pub min_category: u8,    // Saffir–Simpson category, 0 = tropical storm
+    pub payout_pct:   u64,   // share of the policy payout
+}
+
+// Storm state carried by a policy sold under a storm template
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct StormCover {
+    pub tiers:     Vec<StormTier>,
+    pub paid_pct:  u64,           // highest tier paid so far
+    pub closest_m: Option<u64>,   // closest approach seen, metres
+}
+
+impl StormCover {
+    pub fn new(tiers: Vec<StormTier>) -> Self {
+        StormCover { tiers, paid_pct: 0, closest_m: None }
+    }
+}
+
+// One storm's current position from the feed
+#[derive(Clone, Debug)]
+pub struct StormFix {
+    pub id:       String,
+    pub name:     String,
+    pub category: u8,
+    pub position: GeoPoint,
+}
+
+#[derive(Deserialize)]
+struct CurrentStorms {
+    #[serde(rename = "activeStorms")]
+    active_storms: Vec<ActiveStorm>,
+}
+
+#[derive(Deserialize)]
+struct ActiveStorm {
+    id:        String,
+    name:      String,
+    intensity: String,   // max sustained wind, knots
+    #[serde(rename = "latitudeNumeric")]
+    latitude:  f64,
+    #[serde(rename = "longitudeNumeric")]
+    longitude: f64,
+}
+
+// Saffir–Simpson category from max sustained wind; 0 below hurricane strength
+pub fn saffir_simpson(wind_kt: u32) -> u8 {
+    match wind_kt {
+        137.. => 5,
+        113.. => 4,
+        96..  => 3,
+        83..  => 2,
+        64..  => 1,
+        _     => 0,
+    }
+}
+
+pub fn active_storms_url(base_url: &str) -> String {
+    format!("{base_url}/CurrentStorms.json")
+}
+
+pub fn parse_active_storms(body: &[u8]) -> RialoResult<Vec<StormFix>> {
+    let feed: CurrentStorms = serde_json::from_slice(body)
+        .map_err(|_| InsuranceError::BadResponse("Failed to parse storm feed.".into()))?;
+
+    feed.active_storms
+        .into_iter()
+        .map(|s| {
+            let wind_kt = s.intensity.trim().parse().map_err(|_| InsuranceError::BadResponse("Storm intensity is not a number.".into()))?;
+            let position = GeoPoint::new((s.latitude * 1e6).round() as i32, (s.longitude * 1e6).round() as i32).map_err(InsuranceError::from)?;
+            Ok(StormFix { id: s.id, name: s.name, category: saffir_simpson(wind_kt), position })
+        })
+        .collect()
+}
+
+fn check_tiers(tiers: &[StormTier]) -> RialoResult<()> {
+    require!(!tiers.is_empty(), InsuranceError::InvalidArgument("Storm products need at least one tier.".into()));
+    require!(tiers.len() <= MAX_STORM_TIERS, InsuranceError::InvalidArgument("Too many storm tiers.".into()));
+    for tier in tiers {
+        require!(tier.within_km > 0, InsuranceError::InvalidArgument("Storm tier radius must be positive.".into()));
+        require!(tier.min_category <= 5, InsuranceError::InvalidArgument("Storm category must be between 0 and 5.".into()));
+        require!((1..=100).contains(&tier.payout_pct), InsuranceError::InvalidArgument("Storm tier payout must be between 1% and 100%.".into()));
+    }
+    Ok(())
+}
+
+// ── Entry point: make (or unmake) a template a storm product ─
+#[rialo::instruction]
+pub async fn set_template_storm_tiers(
+    ctx:            Context<InsuranceState>,
+    underwriter_id: UnderwriterId,
+    template_id:    TemplateId,
+    tiers:          Option<Vec<StormTier>>,
+) -> RialoResult<()> {
+
+    if let Some(tiers) = &tiers {
+        check_tiers(tiers)?;
+    }
+
+    let underwriter = tenant_mut(&mut ctx.state, underwriter_id, &ctx.signer)?;
+    let template = underwriter.templates.get_mut(&template_id).ok_or(InsuranceError::UnknownTemplate)?;
+
+    emit!(TemplateStormTiersSet { underwriter_id, template_id, tiers: tiers.clone() });
+
+    template.storm_tiers = tiers;
+
+    Ok(())
+}
+
+// ── Entry point: anyone checks the storm feed for a policy ───
+#[rialo::instruction]
+pub async fn check_storm_and_pay(
+    ctx:       Context<InsuranceState>,
+    policy_id: PolicyId,
+) -> RialoResult<()> {
+
+    require_unpaused(&ctx.state.config)?;
+    let now = ctx.clock.unix_timestamp;
+    let policy = ctx.state.policies.get(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+
+    require!(policy.status == PolicyStatus::Active, InsuranceError::PolicyNotActive);
+    require!(policy.is_covered_at(now), InsuranceError::PolicyExpired);
+    let cover = policy.storm.as_ref().ok_or_else(|| InsuranceError::InvalidState("Policy is not storm cover.".into()))?;
+    let point = policy.insured_point.ok_or_else(|| InsuranceError::InvalidState("Policy has no insured point.".into()))?;
+
+    let provider = ctx.state.config.feeds.get(&FeedKind::StormTrack).ok_or_else(|| InsuranceError::InvalidState("No storm feed configured.".into()))?;
+    let url = active_storms_url(provider.base_url_for(ctx.state.config.network_mode));
+    let call_cost = provider.cost_per_call;
+
+    let response = fetch(&url, &[]).await?;
+    let fixes = parse_active_storms(response.body())?;
+
+    // Best tier any storm reaches on this check
+    let mut best: Option<(&StormFix, u64, u64)> = None;
+    let mut closest = cover.closest_m;
+    for fix in &fixes {
+        let distance = haversine_km(&point, &fix.position);
+        closest = Some(closest.map_or(distance, |c| c.min(distance)));
+
+        let pct = cover.tiers
+            .iter()
+            .filter(|t| distance <= t.within_km * KM_SCALE && fix.category >= t.min_category)
+            .map(|t| t.payout_pct)
+            .max()
+            .unwrap_or(0);
+        if pct > best.map_or(0, |(_, p, _)| p) {
+            best = Some((fix, pct, distance));
+        }
+    }
+
+    emit!(StormChecked { policy_id, storms: fixes.len() as u32, closest_m: closest });
+
+    record_lae(&mut ctx.state, policy_id, LaeKind::ProviderCost, call_cost);
+
+    let policy = ctx.state.policies.get_mut(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+    let cover = policy.storm.as_mut().ok_or_else(|| InsuranceError::InvalidState("Policy is not storm cover.".into()))?;
+    cover.closest_m = closest;
+
+    let Some((fix, pct, distance)) = best.filter(|(_, pct, _)| *pct > cover.paid_pct) else {
+        return Ok(());
+    };
+    cover.paid_pct = pct;
+
+    // Top up from what earlier tiers already paid; the receipt commits to the distance that reached the tier
+    let due = settlement::pay_share(&mut ctx.state, &ctx.vault, policy_id, None, pct * 100, Hundredths::from_f64(distance as f64), now, now)?;
+
+    emit!(StormTierReached {
+        policy_id,
+        storm_id:   fix.id.clone(),
+        storm_name: fix.name.clone(),
+        category:   fix.category,
+        distance_m: distance,
+        payout_pct: pct,
+        paid:       due,
+    });
+
+    Ok(())
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct TemplateStormTiersSet { pub underwriter_id: UnderwriterId, pub template_id: TemplateId, pub tiers: Option<Vec<StormTier>> }
+#[rialo::event] pub struct StormChecked          { pub policy_id: PolicyId, pub storms: u32, pub closest_m: Option<u64> }
+#[rialo::event] pub struct StormTierReached      { pub policy_id: PolicyId, pub storm_id: String, pub storm_name: String, pub category: u8, pub distance_m: u64, pub payout_pct: u64, pub paid: Ralo }
@@ -0,0 +1,199 @@
+// ============================================================
+//  Catastrophe stress tests
+//
+//  The exposure map (concentration.rs) says how much is written
+//  where; a stress test says what one bad day would cost. A
+//  scenario names a peril, the reading it brings and the geohash
+//  regions it hits ("100 mm across kz, sb and sc"), and every
+//  outstanding policy on that peril in those regions is assumed
+//  to see that reading:
+//    • flat cover loses what it still owes if the reading meets
+//      its threshold — streak, rolling and normal cover included,
+//      as if the event ran long enough to complete their trigger
+//    • graded cover loses the share its curve puts on the reading
+//    • bundles lose the sub-limits on every peril the scenario hits
+//  Losses are net of the co-pay and of anything already paid
+//  (settlement::top_up), then of the share ceded to reinsurers,
+//  and set against each tenant's capital.
+//
+//  Policies are placed by the finest geohash they have: their
+//  insured point, or their sealed metadata's public bucket. A
+//  scenario can opt to hit city-name cover with neither, since
+//  it can't be ruled out.
+// ============================================================
+
+use std::collections::BTreeMap;
+
+use rialo_sdk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::concentration::outstanding_exposure;
+use crate::errors::InsuranceError;
+use crate::geo::{geohash, is_geohash, MAX_GEOHASH_LEN};
+use crate::millimeters::Hundredths;
+use crate::money::Ralo;
+use crate::normalization::Metric;
+use crate::policy::Policy;
+use crate::settlement::{top_up, FULL_SHARE_BPS};
+use crate::underwriter::UnderwriterId;
+use crate::InsuranceState;
+
+pub const MAX_SCENARIO_REGIONS: usize = 64;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct StressScenario {
+    pub name:          String,
+    pub peril:         Metric,
+    pub reading:       f64,           // in the peril's unit
+    pub regions:       Vec<String>,   // geohash prefixes the event covers; empty = everywhere
+    pub hit_unlocated: bool,          // count cover that can't be placed as hit
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct TenantStress {
+    pub underwriter_id: UnderwriterId,
+    pub policies_hit:   u32,
+    pub gross_loss:     Ralo,   // payouts the scenario triggers
+    pub ceded:          Ralo,   // recoverable from reinsurers
+    pub net_loss:       Ralo,
+    pub capital:        Ralo,
+    pub shortfall:      Ralo,   // net loss the capital can't cover
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct StressReport {
+    pub tenants:      Vec<TenantStress>,   // tenants the scenario hits, in id order
+    pub policies_hit: u32,
+    pub net_loss:     Ralo,
+    pub shortfall:    Ralo,
+}
+
+// Finest geohash a policy can be placed by
+fn policy_geohash(policy: &Policy) -> Option<String> {
+    if let Some(point) = policy.insured_point {
+        return Some(geohash(&point, MAX_GEOHASH_LEN));
+    }
+    policy.sealed.as_ref().map(|sealed| sealed.geohash.clone())
+}
+
+impl StressScenario {
+    pub fn hits(&self, policy: &Policy) -> bool {
+        if self.regions.is_empty() {
+            return true;
+        }
+        match policy_geohash(policy) {
+            Some(hash) => self.regions.iter().any(|region| hash.starts_with(region.as_str())),
+            None => self.hit_unlocated,
+        }
+    }
+
+    // Share of its cover a policy hit by the scenario pays
+    pub fn share_bps(&self, policy: &Policy) -> u64 {
+        let reading = Hundredths::from_f64(self.reading);
+        if let Some(bundle) = &policy.bundle {
+            let mut bundle = bundle.clone();
+            bundle.record(self.peril, reading);
+            return bundle.share_bps();
+        }
+        if policy.peril != self.peril {
+            return 0;
+        }
+        match &policy.graded {
+            Some(graded) => graded.payout_curve.share_bps(policy.comparison, policy.threshold, reading),
+            None if policy.comparison.is_met(reading, policy.threshold) => FULL_SHARE_BPS,
+            None => 0,
+        }
+    }
+
+    // What the scenario costs a policy, before reinsurance
+    pub fn loss(&self, policy: &Policy) -> Ralo {
+        if outstanding_exposure(policy).is_zero() || !self.hits(policy) {
+            return Ralo::ZERO;
+        }
+        let (due, _) = top_up(policy, self.share_bps(policy));
+        due
+    }
+}
+
+pub fn stress_report(state: &InsuranceState, scenario: &StressScenario) -> StressReport {
+    let mut hit: BTreeMap<UnderwriterId, (u32, Ralo, Ralo)> = BTreeMap::new();
+    for policy in state.policies.values() {
+        let loss = scenario.loss(policy);
+        if loss.is_zero() {
+            continue;
+        }
+        let entry = hit.entry(policy.underwriter_id).or_default();
+        entry.0 += 1;
+        entry.1 += loss;
+        entry.2 += loss.bps(policy.ceded_bps);
+    }
+
+    let tenants: Vec<TenantStress> = hit
+        .into_iter()
+        .map(|(underwriter_id, (policies_hit, gross_loss, ceded))| {
+            let capital = state.underwriters.get(&underwriter_id).map_or(Ralo::ZERO, |u| u.capital);
+            let net_loss = gross_loss - ceded;
+            TenantStress {
+                underwriter_id,
+                policies_hit,
+                gross_loss,
+                ceded,
+                net_loss,
+                capital,
+                shortfall: net_loss.saturating_sub(capital),
+            }
+        })
+        .collect();
+
+    StressReport {
+        policies_hit: tenants.iter().map(|t| t.policies_hit).sum(),
+        net_loss:     tenants.iter().map(|t| t.net_loss).sum(),
+        shortfall:    tenants.iter().map(|t| t.shortfall).sum(),
+        tenants,
+    }
+}
+
+fn check_scenario(scenario: &StressScenario) -> RialoResult<()> {
+    require!(!scenario.name.is_empty(), InsuranceError::InvalidState("Scenario needs a name.".into()));
+    require!(scenario.reading.is_finite(), InsuranceError::InvalidArgument("Scenario reading must be a number.".into()));
+    require!(scenario.regions.len() <= MAX_SCENARIO_REGIONS, InsuranceError::InvalidArgument("Too many regions in the scenario.".into()));
+    require!(scenario.regions.iter().all(|r| is_geohash(r)), InsuranceError::InvalidArgument("Scenario regions must be geohash prefixes.".into()));
+    Ok(())
+}
+
+#[rialo::view]
+pub fn preview_stress_test(ctx: Context<InsuranceState>, scenario: StressScenario) -> RialoResult<StressReport> {
+    check_scenario(&scenario)?;
+    Ok(stress_report(&ctx.state, &scenario))
+}
+
+// ── Entry point: admin runs a scenario into the record ───────
+//
+//  Same report as the view, emitted so the risk committee has it
+//  on chain next to the book it was run against.
+//
+#[rialo::instruction]
+pub async fn stress_test(
+    ctx:      Context<InsuranceState>,
+    scenario: StressScenario,
+) -> RialoResult<StressReport> {
+
+    require!(ctx.state.config.admin == *ctx.signer, InsuranceError::Unauthorized("Only the admin can record stress tests.".into()));
+    check_scenario(&scenario)?;
+
+    let report = stress_report(&ctx.state, &scenario);
+
+    emit!(StressTestResult {
+        at:           ctx.clock.unix_timestamp,
+        scenario,
+        tenants:      report.tenants.clone(),
+        policies_hit: report.policies_hit,
+        net_loss:     report.net_loss,
+        shortfall:    report.shortfall,
+    });
+
+    Ok(report)
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct StressTestResult { pub at: i64, pub scenario: StressScenario, pub tenants: Vec<TenantStress>, pub policies_hit: u32, pub net_loss: Ralo, pub shortfall: Ralo }
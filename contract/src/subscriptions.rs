@@ -0,0 +1,172 @@
+// ============================================================
+//  Subscription cover
+//
+//  A delivery company that wants cover every month shouldn't have
+//  to buy it every month. A subscription holds the terms of one
+//  policy and the customer's standing token allowance to the
+//  contract (the same allowance collect_premium draws on):
+//    create_subscription → writes the first period's policy and
+//                          pulls its premium from the allowance
+//    renew_subscription  → once a period's coverage has ended,
+//                          anyone — a keeper, or a timer calling in
+//                          — writes the next one on the same terms,
+//                          requoted at today's price, and pulls it
+//    cancel_subscription → the owner stops renewals; the period
+//                          already paid for runs to its end
+//  A renewal the allowance can't cover is still written, and waits
+//  on its premium like any other policy: the customer can top the
+//  allowance up and anyone can collect_premium (or renew again to
+//  retry the pull) within the payment grace period. Once it lapses,
+//  the next renewal call ends the subscription.
+// ============================================================
+
+use rialo_sdk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::InsuranceError;
+use crate::escrow::collect_from_allowance;
+use crate::money::Ralo;
+use crate::policy::{PolicyId, PolicyStatus, PolicyTerms};
+use crate::{config, write_policy, InsuranceState};
+
+pub type SubscriptionId = u64;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SubscriptionEnd {
+    Cancelled,       // the owner stopped it
+    PaymentFailed,   // a renewal's premium never came through
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Subscription {
+    pub owner:      Pubkey,
+    pub terms:      PolicyTerms,
+    pub current:    PolicyId,                  // this period's policy
+    pub periods:    u32,                       // periods paid for, the first included
+    pub started_at: i64,
+    pub ended:      Option<SubscriptionEnd>,
+}
+
+// Pull a period's premium; a shortfall leaves the policy waiting on it
+fn pull_premium(
+    state:           &mut InsuranceState,
+    vault:           &Vault,
+    subscription_id: SubscriptionId,
+    policy_id:       PolicyId,
+    now:             i64,
+) -> RialoResult<()> {
+
+    let policy = state.policies.get(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+    let (premium, pay_by) = (policy.premium_outstanding(), policy.created_at + state.config.payment_grace_secs);
+
+    if let Err(error) = collect_from_allowance(state, vault, policy_id, now) {
+        emit!(SubscriptionRenewalFailed { subscription_id, policy_id, premium, pay_by, code: error.code });
+        return Ok(());
+    }
+
+    let subscription = state.subscriptions.get_mut(&subscription_id).ok_or(InsuranceError::UnknownSubscription)?;
+    subscription.periods += 1;
+
+    emit!(SubscriptionRenewed { subscription_id, policy_id, period: subscription.periods, premium });
+
+    Ok(())
+}
+
+// ── Entry point: customer subscribes to monthly cover ────────
+#[rialo::instruction]
+pub async fn create_subscription(
+    ctx:   Context<InsuranceState>,
+    terms: PolicyTerms,
+) -> RialoResult<SubscriptionId> {
+
+    let now = ctx.clock.unix_timestamp;
+    config::require_unpaused(&ctx.state.config)?;
+
+    let owner = *ctx.signer;
+    let policy_id = write_policy(&mut ctx.state, owner, None, terms.clone(), false, now).await?;
+    let premium = ctx.state.policies.get(&policy_id).map_or(Ralo::ZERO, |p| p.premium_amount);
+
+    // No subscription starts without its first premium
+    collect_from_allowance(&mut ctx.state, &ctx.vault, policy_id, now)?;
+
+    let subscription_id = ctx.state.next_sub_id;
+    ctx.state.next_sub_id += 1;
+    ctx.state.subscriptions.insert(subscription_id, Subscription {
+        owner,
+        terms,
+        current:    policy_id,
+        periods:    1,
+        started_at: now,
+        ended:      None,
+    });
+
+    emit!(SubscriptionCreated { subscription_id, owner, policy_id, premium });
+
+    Ok(subscription_id)
+}
+
+// ── Entry point: anyone writes a subscription's next period ──
+//
+//  Returns the period's policy, or None if the subscription ended
+//  because the last renewal was never paid.
+//
+#[rialo::instruction]
+pub async fn renew_subscription(
+    ctx:             Context<InsuranceState>,
+    subscription_id: SubscriptionId,
+) -> RialoResult<Option<PolicyId>> {
+
+    let now = ctx.clock.unix_timestamp;
+    config::require_unpaused(&ctx.state.config)?;
+
+    let subscription = ctx.state.subscriptions.get(&subscription_id).ok_or(InsuranceError::UnknownSubscription)?;
+    require!(subscription.ended.is_none(), InsuranceError::InvalidState("Subscription has ended.".into()));
+    let (owner, terms, current) = (subscription.owner, subscription.terms.clone(), subscription.current);
+    let policy = ctx.state.policies.get(&current).ok_or(InsuranceError::UnknownPolicy)?;
+
+    match policy.status {
+        // A renewal still waiting on its premium: try the allowance again
+        PolicyStatus::PendingPayment => {
+            pull_premium(&mut ctx.state, &ctx.vault, subscription_id, current, now)?;
+            return Ok(Some(current));
+        }
+        PolicyStatus::Lapsed | PolicyStatus::Withdrawn => {
+            let subscription = ctx.state.subscriptions.get_mut(&subscription_id).ok_or(InsuranceError::UnknownSubscription)?;
+            subscription.ended = Some(SubscriptionEnd::PaymentFailed);
+            emit!(SubscriptionCancelled { subscription_id, owner, reason: SubscriptionEnd::PaymentFailed });
+            return Ok(None);
+        }
+        _ => require!(policy.coverage_end().is_some_and(|end| end <= now), InsuranceError::InvalidState("This period's coverage hasn't ended yet.".into())),
+    }
+
+    // The previous period has ended, so the new one layers on nothing of the subscription's own
+    let policy_id = write_policy(&mut ctx.state, owner, None, terms, true, now).await?;
+    ctx.state.subscriptions.get_mut(&subscription_id).ok_or(InsuranceError::UnknownSubscription)?.current = policy_id;
+    pull_premium(&mut ctx.state, &ctx.vault, subscription_id, policy_id, now)?;
+
+    Ok(Some(policy_id))
+}
+
+// ── Entry point: owner stops renewing a subscription ─────────
+#[rialo::instruction]
+pub async fn cancel_subscription(
+    ctx:             Context<InsuranceState>,
+    subscription_id: SubscriptionId,
+) -> RialoResult<()> {
+
+    let subscription = ctx.state.subscriptions.get_mut(&subscription_id).ok_or(InsuranceError::UnknownSubscription)?;
+
+    require!(subscription.owner == *ctx.signer, InsuranceError::Unauthorized("Only the subscriber can cancel a subscription.".into()));
+    require!(subscription.ended.is_none(), InsuranceError::InvalidState("Subscription has ended.".into()));
+    subscription.ended = Some(SubscriptionEnd::Cancelled);
+
+    emit!(SubscriptionCancelled { subscription_id, owner: subscription.owner, reason: SubscriptionEnd::Cancelled });
+
+    Ok(())
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct SubscriptionCreated       { pub subscription_id: SubscriptionId, pub owner: Pubkey, pub policy_id: PolicyId, pub premium: Ralo }
+#[rialo::event] pub struct SubscriptionRenewed       { pub subscription_id: SubscriptionId, pub policy_id: PolicyId, pub period: u32, pub premium: Ralo }
+#[rialo::event] pub struct SubscriptionRenewalFailed { pub subscription_id: SubscriptionId, pub policy_id: PolicyId, pub premium: Ralo, pub pay_by: i64, pub code: u32 }
+#[rialo::event] pub struct SubscriptionCancelled     { pub subscription_id: SubscriptionId, pub owner: Pubkey, pub reason: SubscriptionEnd }
@@ -0,0 +1,94 @@
+// ============================================================
+//  Check cooldown
+//
+//  A live check can settle on any reading inside the coverage
+//  window, so calling `check_weather_and_pay` every block buys a
+//  keeper nothing but provider spend — and a chance to time its
+//  call to a spike the next reading would smooth out. Each policy
+//  therefore waits a minimum interval between live checks: its
+//  template's, or `DEFAULT_MIN_CHECK_SECS`, copied at setup so
+//  the terms can't move under live cover. A call inside the
+//  interval fails with `CheckThrottled`, carrying the time the
+//  next one is allowed; check rounds skip such policies.
+//
+//  Historical checks and backfills read hours that have already
+//  passed and aren't throttled.
+// ============================================================
+
+use rialo_sdk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::InsuranceError;
+use crate::policy::{Policy, PolicyId};
+use crate::underwriter::{tenant_mut, TemplateId, UnderwriterId};
+use crate::InsuranceState;
+
+pub const DEFAULT_MIN_CHECK_SECS: i64 = 10 * 60;
+pub const MAX_MIN_CHECK_SECS:     i64 = 24 * 60 * 60;
+
+// Error code, clear of the quote errors' range
+pub const CHECK_THROTTLED_CODE: u32 = 6_100;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CheckThrottled {
+    pub next_check_at: i64,   // earliest time the next live check is allowed
+}
+
+impl CheckThrottled {
+    pub fn message(&self) -> String {
+        format!("Policy was checked too recently; next check allowed at {}.", self.next_check_at)
+    }
+}
+
+impl From<CheckThrottled> for RialoError {
+    fn from(error: CheckThrottled) -> Self {
+        RialoError::custom(CHECK_THROTTLED_CODE, error.message())
+    }
+}
+
+impl Policy {
+    // Earliest time a live check may run; None if one may run now
+    pub fn next_check_at(&self, now: i64) -> Option<i64> {
+        self.last_checked
+            .map(|last| last + self.min_check_secs)
+            .filter(|&next| now < next)
+    }
+
+    pub fn check_throttle(&self, now: i64) -> Result<(), CheckThrottled> {
+        match self.next_check_at(now) {
+            Some(next_check_at) => Err(CheckThrottled { next_check_at }),
+            None => Ok(()),
+        }
+    }
+}
+
+// Start the cooldown after a live check ran
+pub(crate) fn stamp(state: &mut InsuranceState, policy_id: PolicyId, now: i64) {
+    if let Some(policy) = state.policies.get_mut(&policy_id) {
+        policy.last_checked = Some(now);
+    }
+}
+
+// ── Entry point: set the interval a template's policies wait between checks
+#[rialo::instruction]
+pub async fn set_template_check_interval(
+    ctx:            Context<InsuranceState>,
+    underwriter_id: UnderwriterId,
+    template_id:    TemplateId,
+    interval_secs:  Option<i64>,
+) -> RialoResult<()> {
+
+    require!(interval_secs.is_none_or(|s| (0..=MAX_MIN_CHECK_SECS).contains(&s)), InsuranceError::InvalidArgument("Check interval must be between zero and a day.".into()));
+
+    let underwriter = tenant_mut(&mut ctx.state, underwriter_id, &ctx.signer)?;
+    let template = underwriter.templates.get_mut(&template_id).ok_or(InsuranceError::UnknownTemplate)?;
+
+    template.check_interval = interval_secs;
+
+    emit!(TemplateCheckIntervalSet { underwriter_id, template_id, interval_secs });
+
+    Ok(())
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct TemplateCheckIntervalSet { pub underwriter_id: UnderwriterId, pub template_id: TemplateId, pub interval_secs: Option<i64> }
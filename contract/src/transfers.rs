@@ -0,0 +1,108 @@
+// ============================================================
+//  Policy transfers
+//
+//  Delivery companies get acquired and wallets get rotated, so the
+//  owner of a live policy can hand it — premium escrow, cover and
+//  any payout still to come — to another account:
+//    • in one step, `transfer_policy` moves it there and then
+//    • or in two, it only names the new owner, who takes it with
+//      `accept_policy_transfer` — a mistyped key can never accept,
+//      and the owner can withdraw the offer in the meantime
+//  The new owner is held to the same controls a new policy is: the
+//  whitelist (whitelist.rs), the per-beneficiary cap and policy
+//  limit (concentration.rs) and the self-dealing policy
+//  (self_dealing.rs).
+//  Receipts already issued stay with the account that was paid.
+// ============================================================
+
+use rialo_sdk::prelude::*;
+
+use crate::concentration::{check_beneficiary_cap, check_policy_limit, outstanding_exposure};
+use crate::errors::InsuranceError;
+use crate::policy::PolicyId;
+use crate::retention::is_closed;
+use crate::whitelist::{self, Role};
+use crate::{owners, self_dealing, InsuranceState};
+
+// Move a policy to `to`, under the controls a new policy would face
+fn reassign(state: &mut InsuranceState, policy_id: PolicyId, to: Pubkey) -> RialoResult<()> {
+    let policy = state.policies.get(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+    let (from, underwriter_id, exposure) = (policy.owner, policy.underwriter_id, outstanding_exposure(policy));
+
+    whitelist::require_role(state, &to, Role::Policyholder)?;
+    check_beneficiary_cap(state, &to, exposure)?;
+    check_policy_limit(state, &to)?;
+    self_dealing::check(state, underwriter_id, policy_id, &to)?;
+
+    let policy = state.policies.get_mut(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+    policy.owner         = to;
+    policy.pending_owner = None;
+    owners::unindex(state, &from, policy_id);
+    owners::index(state, to, policy_id);
+
+    emit!(PolicyTransferred { policy_id, from, to });
+
+    Ok(())
+}
+
+// ── Entry point: owner transfers a policy, or offers it ──────
+#[rialo::instruction]
+pub async fn transfer_policy(
+    ctx:             Context<InsuranceState>,
+    policy_id:       PolicyId,
+    new_beneficiary: Pubkey,
+    require_accept:  bool,   // two-step: the new owner must accept it
+) -> RialoResult<()> {
+
+    let policy = ctx.state.policies.get_mut(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+
+    require!(policy.owner == *ctx.signer, InsuranceError::Unauthorized("Only the policy owner can transfer it.".into()));
+    require!(!is_closed(policy.status), InsuranceError::InvalidState("Policy has closed; there is nothing left to transfer.".into()));
+    require!(new_beneficiary != policy.owner, InsuranceError::InvalidState("Policy already belongs to that account.".into()));
+    require!(new_beneficiary != Pubkey::default(), InsuranceError::InvalidArgument("New beneficiary is empty.".into()));
+
+    if require_accept {
+        policy.pending_owner = Some(new_beneficiary);
+        emit!(PolicyTransferOffered { policy_id, from: policy.owner, to: new_beneficiary });
+        return Ok(());
+    }
+
+    reassign(&mut ctx.state, policy_id, new_beneficiary)
+}
+
+// ── Entry point: named new owner takes an offered policy ─────
+#[rialo::instruction]
+pub async fn accept_policy_transfer(
+    ctx:       Context<InsuranceState>,
+    policy_id: PolicyId,
+) -> RialoResult<()> {
+
+    let policy = ctx.state.policies.get(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+
+    require!(policy.pending_owner == Some(*ctx.signer), InsuranceError::InvalidState("Policy has not been offered to this account.".into()));
+    require!(!is_closed(policy.status), InsuranceError::InvalidState("Policy has closed; there is nothing left to transfer.".into()));
+
+    reassign(&mut ctx.state, policy_id, *ctx.signer)
+}
+
+// ── Entry point: owner withdraws a transfer offer ────────────
+#[rialo::instruction]
+pub async fn cancel_policy_transfer(
+    ctx:       Context<InsuranceState>,
+    policy_id: PolicyId,
+) -> RialoResult<()> {
+
+    let policy = ctx.state.policies.get_mut(&policy_id).ok_or(InsuranceError::UnknownPolicy)?;
+
+    require!(policy.owner == *ctx.signer, InsuranceError::Unauthorized("Only the policy owner can withdraw a transfer offer.".into()));
+    let to = policy.pending_owner.take().ok_or_else(|| InsuranceError::InvalidState("Policy has no transfer on offer.".into()))?;
+
+    emit!(PolicyTransferCancelled { policy_id, to });
+
+    Ok(())
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct PolicyTransferOffered   { pub policy_id: PolicyId, pub from: Pubkey, pub to: Pubkey }
+#[rialo::event] pub struct PolicyTransferCancelled { pub policy_id: PolicyId, pub to: Pubkey }
+#[rialo::event] pub struct PolicyTransferred       { pub policy_id: PolicyId, pub from: Pubkey, pub to: Pubkey }
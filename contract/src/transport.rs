@@ -0,0 +1,100 @@
+// ============================================================
+//  HTTP transport
+//
+//  Every weather read leaves the contract through `get`, so there
+//  is one seam between the payout logic and the network. Built as
+//  usual it's the SDK's HttpRequest. Built with the `mock-http`
+//  feature — development only, like `sim` — nothing leaves the
+//  process: a responder registered with `mock::install` answers
+//  each URL with a status and a body, and the fetch, retry, parse
+//  and settle path runs deterministically in native tests. A
+//  fixture scenario (weather-fixture/) plugs straight in. A URL
+//  the responder won't answer behaves like a timeout.
+// ============================================================
+
+use rialo_sdk::crypto::sha256;
+use rialo_sdk::prelude::*;
+#[cfg(not(feature = "mock-http"))]
+use rialo_sdk::http::{HttpRequest, Method};
+
+#[cfg(feature = "mock-http")]
+use crate::errors::InsuranceError;
+use crate::normalization::ResponseDigest;
+
+// A provider's answer, held by value so a mock can make one
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HttpReply {
+    status: u16,
+    body:   Vec<u8>,
+}
+
+impl HttpReply {
+    pub fn new(status: u16, body: impl Into<Vec<u8>>) -> Self {
+        HttpReply { status, body: body.into() }
+    }
+
+    pub fn status(&self) -> u16 {
+        self.status
+    }
+
+    pub fn body(&self) -> &[u8] {
+        &self.body
+    }
+
+    // What a check records of this answer, so the body can be matched to it later
+    pub fn digest(&self) -> ResponseDigest {
+        ResponseDigest { sha256: sha256(&self.body), len: self.body.len() as u32, status: self.status }
+    }
+}
+
+// GET `url`, waiting at most `timeout_ms` for the answer if given
+#[cfg(not(feature = "mock-http"))]
+pub(crate) async fn get(url: &str, headers: &[(String, String)], timeout_ms: Option<u64>) -> RialoResult<HttpReply> {
+    let mut request = HttpRequest::new(Method::GET, url);
+    if let Some(timeout_ms) = timeout_ms {
+        request = request.timeout_ms(timeout_ms);
+    }
+    for (name, value) in headers {
+        request = request.header(name, value);
+    }
+    let response = request.send().await?;
+    Ok(HttpReply::new(response.status(), response.body()))
+}
+
+#[cfg(feature = "mock-http")]
+pub(crate) async fn get(url: &str, _headers: &[(String, String)], _timeout_ms: Option<u64>) -> RialoResult<HttpReply> {
+    let reply = mock::answer(url).ok_or(InsuranceError::ProviderTimeout)?;
+    Ok(reply)
+}
+
+// In-process responses for native tests
+#[cfg(feature = "mock-http")]
+pub mod mock {
+    use std::cell::RefCell;
+
+    use super::HttpReply;
+
+    type Responder = Box<dyn Fn(&str) -> Option<(u16, Vec<u8>)>>;
+
+    thread_local! {
+        static RESPONDER: RefCell<Option<Responder>> = RefCell::new(None);
+        static REQUESTS:  RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+    }
+
+    // Answer this thread's requests with `responder` from now on
+    pub fn install(responder: impl Fn(&str) -> Option<(u16, Vec<u8>)> + 'static) {
+        RESPONDER.with(|r| *r.borrow_mut() = Some(Box::new(responder)));
+        REQUESTS.with(|r| r.borrow_mut().clear());
+    }
+
+    // Every URL requested since the responder was installed, in order
+    pub fn requests() -> Vec<String> {
+        REQUESTS.with(|r| r.borrow().clone())
+    }
+
+    pub(super) fn answer(url: &str) -> Option<HttpReply> {
+        REQUESTS.with(|r| r.borrow_mut().push(url.to_string()));
+        RESPONDER.with(|r| r.borrow().as_ref().and_then(|respond| respond(url)))
+            .map(|(status, body)| HttpReply::new(status, body))
+    }
+}
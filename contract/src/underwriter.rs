@@ -0,0 +1,613 @@
+// ============================================================
+//  Underwriters (tenants)
+//
+//  One deployment can host several independent underwriters.
+//  Each one owns:
+//    • a vault ledger    — capital backing its policies
+//    • product templates — bounds its customers buy within
+//    • provider config   — weather API endpoint + the name of its
+//                          key in sealed storage (keys.rs)
+//    • fee settings      — how premiums are priced
+//
+//  Every admin instruction below is scoped to a single tenant and
+//  may only be called by that tenant's authority key.
+//
+//  Capital leaves a vault in two steps — request_withdrawal, then
+//  execute_withdrawal once a notice period has passed. The notice
+//  grows with the share of capital backing live policies, and the
+//  requested amount stops counting towards new-policy solvency the
+//  moment it is requested. Large withdrawals also need a second
+//  key to sign off before they pay (see dual_control.rs).
+// ============================================================
+
+use std::collections::BTreeMap;
+
+use rialo_sdk::prelude::*;
+use rialo_sdk::secrets;
+use rialo_sdk::token::{deposit, transfer};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "air-quality")]
+use crate::air::AqiTrigger;
+use crate::anomalies::ExtremeTable;
+use crate::bundles::BundleTerms;
+use crate::claims::ClaimsLedger;
+use crate::conditions::{Condition, LegacyCondition};
+use crate::config::NetworkMode;
+use crate::consensus::Consensus;
+#[cfg(feature = "degree-days")]
+use crate::degree_days::DegreeDayTrigger;
+use crate::dual_control::{sign_off_withdrawal, withdrawal_subject};
+use crate::errors::InsuranceError;
+use crate::evaluators::EvaluatorId;
+#[cfg(feature = "heat")]
+use crate::exposure::ExposureTrigger;
+use crate::geocoding::Geocode;
+use crate::keys::KeyPool;
+use crate::millimeters::Hundredths;
+use crate::mints::{Mint, MintPool};
+use crate::money::{format_ralo, Ralo};
+use crate::normalization::{Comparison, Metric};
+use crate::normals::MonthlyNormals;
+use crate::product_docs::ProductDoc;
+use crate::profiles::{ProfileId, ProviderProfile};
+use crate::providers::ProviderKind;
+use crate::seasonal::MonthlyFactors;
+use crate::smoothing::Smoothing;
+#[cfg(feature = "storm")]
+use crate::storm::StormTier;
+use crate::whitelist::{self, Role};
+use crate::InsuranceState;
+
+pub type UnderwriterId = u64;
+pub type TemplateId    = u64;
+
+// Notice required when every token of capital is reserved; scales down linearly
+const MAX_WITHDRAWAL_NOTICE_SECS: u64 = 7 * 24 * 60 * 60;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ProviderConfig {
+    pub kind:             ProviderKind,   // API the endpoints speak (weather providers)
+    pub base_url:         String,         // e.g. "https://api.openweathermap.org"
+    pub sandbox_base_url: String,         // mock/staging endpoint used in DevNet mode
+    pub fallback_url:     Option<String>, // mirror tried when base_url fails on MainNet (resilience.rs)
+    pub api_key_secret:   String,         // name of the provider API key in sealed storage, never the key
+    pub cost_per_call:    Ralo,           // estimated API cost per request, booked as LAE
+}
+
+impl ProviderConfig {
+    // Read just in time for a call; keyless feeds name no secret
+    pub fn api_key(&self) -> RialoResult<String> {
+        if self.api_key_secret.is_empty() {
+            return Ok(String::new());
+        }
+        secrets::get(&self.api_key_secret)
+    }
+
+    pub fn base_url_for(&self, mode: NetworkMode) -> &str {
+        match mode {
+            NetworkMode::MainNet => &self.base_url,
+            NetworkMode::DevNet  => &self.sandbox_base_url,
+        }
+    }
+
+    // Endpoints a check tries, in order
+    pub fn endpoints_for(&self, mode: NetworkMode) -> Vec<&str> {
+        let fallback = self.fallback_url.as_deref().filter(|_| mode == NetworkMode::MainNet);
+        std::iter::once(self.base_url_for(mode)).chain(fallback).collect()
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct FeeSettings {
+    pub premium_rate_bps: u64,   // premium charged, in basis points of the payout
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Template {
+    pub name:                 String,                    // product name shown to customers
+    #[serde(default)]
+    pub min_threshold:        Hundredths,                // lowest trigger this product sells, in hundredths of the unit
+    #[serde(rename = "min_threshold_mm", default, skip_serializing)]
+    pub old_min_threshold:    Option<f64>,               // layout 3 and earlier only: now `min_threshold` (migrations.rs)
+    pub max_payout:           Ralo,                      // highest payout this product sells
+    pub base_threshold:       Option<f64>,               // threshold the base rate is for; others scale it (quote.rs)
+    pub metric:               Option<Metric>,            // weather reading the threshold is on, if not rainfall
+    pub comparison:           Comparison,                // side of the threshold that pays: >= for floods, <= for frost or drought
+    pub continuous_hours:     Option<u32>,               // continuous-rain product: wet hours in a row needed to pay
+    pub rolling_hours:        Option<u32>,               // accumulating product: hours of rain summed against the threshold
+    pub rolling_max_age:      Option<u32>,               // accumulating product: hours behind the newest reading a backfill may still count
+    pub rain_normal:          bool,                      // normal-deviation product: threshold is a % of the month's normal rain
+    pub provider_profile:     Option<ProfileId>,         // licensed-feed headers sent with this product's checks
+    pub check_interval:       Option<i64>,               // seconds between live checks of a policy, if not the default (throttle.rs)
+    pub jurisdiction:         Option<String>,            // ISO country the product is sold in, for premium levies
+    pub evaluator:            Option<EvaluatorId>,       // bespoke product: custom evaluator that decides the payout share
+    pub bundle:               Option<BundleTerms>,       // multi-peril product: riders sharing the payout, with sub-limits (bundles.rs)
+    #[serde(default)]
+    pub compound:             Option<Condition>,         // compound product: AND/OR of readings that pays in place of the threshold (conditions.rs)
+    #[serde(rename = "condition", default, skip_serializing)]
+    pub old_condition:        Option<LegacyCondition>,   // layout 5 and earlier only: now `compound` (migrations.rs)
+    pub forecast_min_bps:     Option<u64>,               // forecast-confirmed product: chance of rain an exceedance needs forecast (forecasts.rs)
+    pub advance_bps:          Option<u64>,               // forecast mode on offer: most of the payout a forecast can advance (advances.rs)
+    pub smoothing:            Option<Smoothing>,         // smoothed product: rolling statistic readings settle on (smoothing.rs)
+    pub attestation_required: bool,                      // regulated product: the beneficiary attests a loss before a trigger pays (attestation.rs)
+    pub dispute_window_secs:  Option<i64>,               // two-phase product: how long a trigger can be disputed before it pays (disputes.rs)
+    pub no_claim_bonus_bps:   Option<u64>,               // no-claim product: share of the premium handed back if it never pays (no_claims.rs)
+    #[cfg(feature = "air-quality")]
+    pub air_quality:          Option<AqiTrigger>,        // air-quality product: hazard level and run of checks needed to pay
+    #[cfg(feature = "heat")]
+    pub exposure:             Option<ExposureTrigger>,   // heat-hours product: index and exposure hours needed to pay
+    #[cfg(feature = "flood")]
+    pub river_gauge:          Option<String>,            // river-level product: gauge site the threshold is read at
+    #[cfg(feature = "storm")]
+    pub storm_tiers:          Option<Vec<StormTier>>,    // storm-track product: payout tiers by distance and category
+    #[cfg(feature = "degree-days")]
+    pub degree_days:          Option<DegreeDayTrigger>,  // degree-day product: index, base temperature and tick past the strike
+    pub docs:                 Vec<ProductDoc>,           // binding wording and other documents, by URI and hash (product_docs.rs)
+    pub docs_version:         u32,                       // bumped on every change to the documents
+    pub active:               bool,                      // retired templates can't back new policies
+}
+
+impl Template {
+    // Index the policies sold under this product are written on
+    pub fn peril(&self) -> Metric {
+        #[cfg(feature = "storm")]
+        if self.storm_tiers.is_some() {
+            return Metric::StormTrack;
+        }
+        #[cfg(feature = "flood")]
+        if self.river_gauge.is_some() {
+            return Metric::RiverLevel;
+        }
+        #[cfg(feature = "air-quality")]
+        if self.air_quality.is_some() {
+            return Metric::AirQuality;
+        }
+        #[cfg(feature = "heat")]
+        if let Some(trigger) = self.exposure {
+            return trigger.index.peril();
+        }
+        #[cfg(feature = "degree-days")]
+        if self.degree_days.is_some() {
+            return Metric::DegreeDays;
+        }
+        self.metric.unwrap_or(Metric::Rainfall)
+    }
+
+    // Pays on a single reading against its threshold, with no trigger of its own
+    pub fn is_plain(&self) -> bool {
+        self.peril() == self.metric.unwrap_or(Metric::Rainfall) && self.evaluator.is_none()
+            && self.continuous_hours.is_none() && self.rolling_hours.is_none() && !self.rain_normal
+            && self.compound.is_none()
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Underwriter {
+    pub authority:        Pubkey,                                 // key allowed to administer this tenant
+    pub name:             String,
+    pub capital:          Ralo,                                   // tokens in the vault backing this tenant
+    pub reserved:         Ralo,                                   // capital earmarked for outstanding payouts
+    pub provider:         ProviderConfig,
+    pub key_pool:         KeyPool,                                // rotating provider keys; empty = provider.api_key_secret
+    pub fees:             FeeSettings,
+    pub climatology:      BTreeMap<String, MonthlyFactors>,       // seasonal premium factors per location
+    pub rain_normals:     BTreeMap<String, MonthlyNormals>,       // mean monthly rainfall per location, mm
+    pub utc_offsets:      BTreeMap<String, i32>,                  // local time per location, minutes east of UTC (seasonal.rs)
+    pub extremes:         BTreeMap<String, Vec<ExtremeTable>>,    // 99.9th-percentile readings per location and metric (anomalies.rs)
+    pub templates:        BTreeMap<TemplateId, Template>,
+    pub next_template_id: TemplateId,
+    pub profiles:         BTreeMap<ProfileId, ProviderProfile>,   // custom request headers per data licence
+    pub next_profile_id:  ProfileId,
+    pub claims:           ClaimsLedger,                           // premiums, claims and LAE across the book
+    pub withdrawal:       Option<WithdrawalRequest>,              // capital on its way out
+    pub co_signers:       Vec<Pubkey>,                            // admin-appointed keys that can sign off large withdrawals
+    pub ceded_bps:        u64,                                    // quota share of new policies' claims ceded (bordereau.rs)
+    pub geocodes:         BTreeMap<String, Geocode>,              // provider's point per location, for new policies (geocoding.rs)
+    pub consensus:        Option<Consensus>,                      // other sources live checks take the median with (consensus.rs)
+    pub mint_pools:       BTreeMap<Mint, MintPool>,               // tokens held for policies paying out in other mints (mints.rs)
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+pub struct WithdrawalRequest {
+    pub amount:        Ralo,
+    pub requested_at:  i64,
+    pub executable_at: i64,
+}
+
+impl Underwriter {
+    // Capital not yet earmarked for any policy or pending withdrawal
+    pub fn free_capital(&self) -> Ralo {
+        let withdrawing = self.withdrawal.map_or(Ralo::ZERO, |w| w.amount);
+        self.capital.saturating_sub(self.reserved).saturating_sub(withdrawing)
+    }
+
+    // Notice period proportional to outstanding exposure
+    pub fn withdrawal_notice_secs(&self) -> i64 {
+        if self.capital.is_zero() || self.reserved.is_zero() {
+            return 0;
+        }
+        let exposure = self.reserved.min(self.capital).base_units() as u128;
+        (MAX_WITHDRAWAL_NOTICE_SECS as u128 * exposure).div_ceil(self.capital.base_units() as u128) as i64
+    }
+}
+
+// Look up a tenant and check the signer administers it
+pub(crate) fn tenant_mut<'a>(
+    state:          &'a mut InsuranceState,
+    underwriter_id: UnderwriterId,
+    signer:         &Pubkey,
+) -> RialoResult<&'a mut Underwriter> {
+    let underwriter = state.underwriters.get_mut(&underwriter_id).ok_or(InsuranceError::UnknownUnderwriter)?;
+    require!(underwriter.authority == *signer, InsuranceError::Unauthorized("Signer does not administer this underwriter.".into()));
+    Ok(underwriter)
+}
+
+// ── Entry point: register a new tenant ───────────────────────
+#[rialo::instruction]
+#[allow(clippy::too_many_arguments)]
+pub async fn register_underwriter(
+    ctx:              Context<InsuranceState>,
+    name:             String,
+    kind:             ProviderKind,
+    base_url:         String,
+    sandbox_base_url: String,
+    api_key_secret:   String,
+    cost_per_call:    Ralo,
+    premium_rate_bps: u64,
+) -> RialoResult<UnderwriterId> {
+
+    let state = &mut ctx.state;
+
+    require!(premium_rate_bps <= 10_000, InsuranceError::InvalidArgument("Premium rate must be at most 100%.".into()));
+
+    let underwriter_id = state.next_underwriter_id;
+    state.next_underwriter_id += 1;
+
+    state.underwriters.insert(underwriter_id, Underwriter {
+        authority:        *ctx.signer,
+        name:             name.clone(),
+        capital:          Ralo::ZERO,
+        reserved:         Ralo::ZERO,
+        provider:         ProviderConfig { kind, base_url, sandbox_base_url, fallback_url: None, api_key_secret, cost_per_call },
+        key_pool:         KeyPool::default(),
+        fees:             FeeSettings { premium_rate_bps },
+        climatology:      BTreeMap::new(),
+        rain_normals:     BTreeMap::new(),
+        utc_offsets:      BTreeMap::new(),
+        extremes:         BTreeMap::new(),
+        templates:        BTreeMap::new(),
+        next_template_id: 0,
+        profiles:         BTreeMap::new(),
+        next_profile_id:  0,
+        claims:           ClaimsLedger::default(),
+        withdrawal:       None,
+        co_signers:       Vec::new(),
+        ceded_bps:        0,
+        geocodes:         BTreeMap::new(),
+        consensus:        None,
+        mint_pools:       BTreeMap::new(),
+    });
+
+    emit!(UnderwriterRegistered { underwriter_id, authority: *ctx.signer, name });
+
+    Ok(underwriter_id)
+}
+
+// ── Entry point: deposit capital into a tenant's vault ───────
+#[rialo::instruction]
+pub async fn fund_vault(
+    ctx:            Context<InsuranceState>,
+    underwriter_id: UnderwriterId,
+    amount:         Ralo,
+) -> RialoResult<()> {
+
+    whitelist::require_role(&ctx.state, &ctx.signer, Role::Underwriter)?;
+    let underwriter = tenant_mut(&mut ctx.state, underwriter_id, &ctx.signer)?;
+
+    require!(!amount.is_zero(), InsuranceError::InvalidArgument("Deposit must be non-zero.".into()));
+
+    deposit(&ctx.signer, &ctx.vault, amount.base_units())?;
+    underwriter.capital += amount;
+
+    emit!(VaultFunded { underwriter_id, amount, capital: underwriter.capital });
+
+    Ok(())
+}
+
+// ── Entry point: give notice of a capital withdrawal ─────────
+#[rialo::instruction]
+pub async fn request_withdrawal(
+    ctx:            Context<InsuranceState>,
+    underwriter_id: UnderwriterId,
+    amount:         Ralo,
+) -> RialoResult<()> {
+
+    let now = ctx.clock.unix_timestamp;
+    let pooled = ctx.state.risk_pools.contains_key(&underwriter_id);
+    let underwriter = tenant_mut(&mut ctx.state, underwriter_id, &ctx.signer)?;
+
+    require!(!pooled, InsuranceError::InvalidState("A risk pool's capital belongs to its members; it leaves through exit_pool.".into()));
+    require!(underwriter.withdrawal.is_none(), InsuranceError::InvalidState("A withdrawal is already pending.".into()));
+    require!(!amount.is_zero(), InsuranceError::InvalidArgument("Withdrawal must be non-zero.".into()));
+    require!(amount <= underwriter.free_capital(), InsuranceError::VaultInsolvent(format!("Withdrawal exceeds unreserved capital of {}.", format_ralo(underwriter.free_capital()))));
+
+    let request = WithdrawalRequest {
+        amount,
+        requested_at:  now,
+        executable_at: now + underwriter.withdrawal_notice_secs(),
+    };
+    underwriter.withdrawal = Some(request);
+
+    emit!(WithdrawalRequested { underwriter_id, amount, executable_at: request.executable_at });
+
+    Ok(())
+}
+
+// ── Entry point: pay out a withdrawal once notice has run ────
+#[rialo::instruction]
+pub async fn execute_withdrawal(
+    ctx:            Context<InsuranceState>,
+    underwriter_id: UnderwriterId,
+) -> RialoResult<()> {
+
+    let now = ctx.clock.unix_timestamp;
+    let underwriter = ctx.state.underwriters.get(&underwriter_id).ok_or(InsuranceError::UnknownUnderwriter)?;
+
+    let request = underwriter.withdrawal.ok_or_else(|| InsuranceError::InvalidState("No withdrawal pending.".into()))?;
+    require!(now >= request.executable_at, InsuranceError::InvalidState("Withdrawal notice period has not elapsed.".into()));
+
+    // Pays on the authority's call, or on the second sign-off under dual control
+    if !sign_off_withdrawal(&mut ctx.state, underwriter_id, *ctx.signer, now)? {
+        return Ok(());
+    }
+
+    let underwriter = ctx.state.underwriters.get_mut(&underwriter_id).ok_or(InsuranceError::UnknownUnderwriter)?;
+    transfer(&ctx.vault, &underwriter.authority, request.amount.base_units())?;
+    underwriter.capital   -= request.amount;
+    underwriter.withdrawal = None;
+
+    emit!(WithdrawalExecuted { underwriter_id, amount: request.amount, capital: underwriter.capital });
+
+    Ok(())
+}
+
+// ── Entry point: abandon a pending withdrawal ────────────────
+#[rialo::instruction]
+pub async fn cancel_withdrawal(
+    ctx:            Context<InsuranceState>,
+    underwriter_id: UnderwriterId,
+) -> RialoResult<()> {
+
+    let underwriter = tenant_mut(&mut ctx.state, underwriter_id, &ctx.signer)?;
+
+    let request = underwriter.withdrawal.take().ok_or_else(|| InsuranceError::InvalidState("No withdrawal pending.".into()))?;
+    ctx.state.approvals.remove(&withdrawal_subject(underwriter_id, &request));
+
+    emit!(WithdrawalCancelled { underwriter_id, amount: request.amount });
+
+    Ok(())
+}
+
+// ── Entry point: point a tenant at a different weather API ───
+#[rialo::instruction]
+pub async fn set_provider_config(
+    ctx:              Context<InsuranceState>,
+    underwriter_id:   UnderwriterId,
+    kind:             ProviderKind,
+    base_url:         String,
+    sandbox_base_url: String,
+    api_key_secret:   String,
+    cost_per_call:    Ralo,
+) -> RialoResult<()> {
+
+    let underwriter = tenant_mut(&mut ctx.state, underwriter_id, &ctx.signer)?;
+
+    underwriter.provider = ProviderConfig {
+        kind,
+        base_url:         base_url.clone(),
+        sandbox_base_url: sandbox_base_url.clone(),
+        fallback_url:     None,
+        api_key_secret,
+        cost_per_call,
+    };
+    // Pooled keys and the mirror belong to the old provider
+    underwriter.key_pool = KeyPool::default();
+
+    // The event carries neither the key nor its secret's name
+    emit!(ProviderConfigUpdated { underwriter_id, kind, base_url, sandbox_base_url });
+
+    Ok(())
+}
+
+// ── Entry point: give a tenant's provider a mirror to fall back on
+#[rialo::instruction]
+pub async fn set_provider_fallback(
+    ctx:            Context<InsuranceState>,
+    underwriter_id: UnderwriterId,
+    fallback_url:   Option<String>,
+) -> RialoResult<()> {
+
+    require!(fallback_url.as_ref().is_none_or(|url| url.starts_with("https://")), InsuranceError::InvalidArgument("Fallback endpoint must use HTTPS.".into()));
+
+    let underwriter = tenant_mut(&mut ctx.state, underwriter_id, &ctx.signer)?;
+    require!(fallback_url.as_ref() != Some(&underwriter.provider.base_url), InsuranceError::InvalidState("Fallback endpoint is the provider's own.".into()));
+    underwriter.provider.fallback_url = fallback_url.clone();
+
+    emit!(ProviderFallbackSet { underwriter_id, fallback_url });
+
+    Ok(())
+}
+
+// ── Entry point: reprice a tenant's new policies ─────────────
+#[rialo::instruction]
+pub async fn set_fees(
+    ctx:              Context<InsuranceState>,
+    underwriter_id:   UnderwriterId,
+    premium_rate_bps: u64,
+) -> RialoResult<()> {
+
+    let underwriter = tenant_mut(&mut ctx.state, underwriter_id, &ctx.signer)?;
+
+    require!(premium_rate_bps <= 10_000, InsuranceError::InvalidArgument("Premium rate must be at most 100%.".into()));
+
+    underwriter.fees = FeeSettings { premium_rate_bps };
+
+    emit!(FeesUpdated { underwriter_id, premium_rate_bps });
+
+    Ok(())
+}
+
+// ── Entry point: publish a product template ──────────────────
+#[rialo::instruction]
+pub async fn add_template(
+    ctx:              Context<InsuranceState>,
+    underwriter_id:   UnderwriterId,
+    name:             String,
+    min_threshold_mm: f64,
+    max_payout:       Ralo,
+    continuous_hours: Option<u32>,
+) -> RialoResult<TemplateId> {
+
+    let limits = ctx.state.config.limits();
+    let underwriter = tenant_mut(&mut ctx.state, underwriter_id, &ctx.signer)?;
+
+    let min_threshold = Hundredths::from_f64(min_threshold_mm);
+    require!(min_threshold >= limits.min_threshold, InsuranceError::BelowMinThreshold { min: limits.min_threshold });
+    require!(max_payout <= limits.max_payout, InsuranceError::PayoutCapExceeded { max: limits.max_payout });
+    require!(continuous_hours != Some(0), InsuranceError::InvalidArgument("Continuous-rain products need at least one hour.".into()));
+
+    let template_id = underwriter.next_template_id;
+    underwriter.next_template_id += 1;
+
+    underwriter.templates.insert(template_id, Template {
+        name: name.clone(),
+        min_threshold,
+        old_min_threshold: None,
+        max_payout,
+        continuous_hours,
+        rolling_hours: None,
+        rolling_max_age: None,
+        rain_normal: false,
+        base_threshold: None,
+        metric: None,
+        comparison: Comparison::AtOrAbove,
+        provider_profile: None,
+        check_interval: None,
+        jurisdiction: None,
+        evaluator: None,
+        bundle: None,
+        compound: None,
+        old_condition: None,
+        forecast_min_bps: None,
+        advance_bps: None,
+        smoothing: None,
+        attestation_required: false,
+        dispute_window_secs: None,
+        no_claim_bonus_bps: None,
+        #[cfg(feature = "air-quality")]
+        air_quality: None,
+        #[cfg(feature = "heat")]
+        exposure: None,
+        #[cfg(feature = "flood")]
+        river_gauge: None,
+        #[cfg(feature = "storm")]
+        storm_tiers: None,
+        #[cfg(feature = "degree-days")]
+        degree_days: None,
+        docs: Vec::new(),
+        docs_version: 0,
+        active: true,
+    });
+
+    emit!(TemplateAdded { underwriter_id, template_id, name, min_threshold, max_payout, continuous_hours });
+
+    Ok(template_id)
+}
+
+// ── Entry point: retire or re-enable a product template ──────
+#[rialo::instruction]
+pub async fn set_template_active(
+    ctx:            Context<InsuranceState>,
+    underwriter_id: UnderwriterId,
+    template_id:    TemplateId,
+    active:         bool,
+) -> RialoResult<()> {
+
+    let underwriter = tenant_mut(&mut ctx.state, underwriter_id, &ctx.signer)?;
+    let template = underwriter.templates.get_mut(&template_id).ok_or(InsuranceError::UnknownTemplate)?;
+
+    template.active = active;
+
+    emit!(TemplateStatusChanged { underwriter_id, template_id, active });
+
+    Ok(())
+}
+
+// ── Entry point: price a template's thresholds off a base one
+#[rialo::instruction]
+pub async fn set_template_base_threshold(
+    ctx:            Context<InsuranceState>,
+    underwriter_id: UnderwriterId,
+    template_id:    TemplateId,
+    base_threshold: Option<f64>,
+) -> RialoResult<()> {
+
+    require!(base_threshold.is_none_or(|t| t.is_finite() && t > 0.0), InsuranceError::InvalidArgument("Base threshold must be positive.".into()));
+
+    let underwriter = tenant_mut(&mut ctx.state, underwriter_id, &ctx.signer)?;
+    let template = underwriter.templates.get_mut(&template_id).ok_or(InsuranceError::UnknownTemplate)?;
+
+    template.base_threshold = base_threshold;
+
+    emit!(TemplateRepriced { underwriter_id, template_id, base_threshold });
+
+    Ok(())
+}
+
+// ── Entry point: write a template on another weather reading ─
+//
+//  Frost, wind, snow or drought cover from the same provider call a
+//  rainfall check makes: the threshold is read against `metric`, and
+//  pays at or below it if `comparison` says so. Streaks count hours
+//  on the paying side in a row — dry hours, for drought.
+//
+#[rialo::instruction]
+pub async fn set_template_metric(
+    ctx:            Context<InsuranceState>,
+    underwriter_id: UnderwriterId,
+    template_id:    TemplateId,
+    metric:         Metric,
+    comparison:     Comparison,
+) -> RialoResult<()> {
+
+    require!(metric.is_observed(), InsuranceError::InvalidState("Metric is not read from the weather provider.".into()));
+
+    let underwriter = tenant_mut(&mut ctx.state, underwriter_id, &ctx.signer)?;
+    let template = underwriter.templates.get_mut(&template_id).ok_or(InsuranceError::UnknownTemplate)?;
+    // Product triggers (storm, river, air, heat hours) take precedence over the metric
+    let plain = template.peril() == template.metric.unwrap_or(Metric::Rainfall)
+        && template.evaluator.is_none() && !template.rain_normal && template.rolling_hours.is_none();
+    require!(plain, InsuranceError::InvalidState("Template already has its own trigger.".into()));
+
+    template.metric     = Some(metric);
+    template.comparison = comparison;
+
+    emit!(TemplateMetricSet { underwriter_id, template_id, metric, comparison });
+
+    Ok(())
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct UnderwriterRegistered { pub underwriter_id: UnderwriterId, pub authority: Pubkey, pub name: String }
+#[rialo::event] pub struct VaultFunded           { pub underwriter_id: UnderwriterId, pub amount: Ralo, pub capital: Ralo }
+#[rialo::event] pub struct WithdrawalRequested   { pub underwriter_id: UnderwriterId, pub amount: Ralo, pub executable_at: i64 }
+#[rialo::event] pub struct WithdrawalExecuted    { pub underwriter_id: UnderwriterId, pub amount: Ralo, pub capital: Ralo }
+#[rialo::event] pub struct WithdrawalCancelled   { pub underwriter_id: UnderwriterId, pub amount: Ralo }
+#[rialo::event] pub struct ProviderConfigUpdated { pub underwriter_id: UnderwriterId, pub kind: ProviderKind, pub base_url: String, pub sandbox_base_url: String }
+#[rialo::event] pub struct ProviderFallbackSet   { pub underwriter_id: UnderwriterId, pub fallback_url: Option<String> }
+#[rialo::event] pub struct FeesUpdated           { pub underwriter_id: UnderwriterId, pub premium_rate_bps: u64 }
+#[rialo::event] pub struct TemplateAdded         { pub underwriter_id: UnderwriterId, pub template_id: TemplateId, pub name: String, pub min_threshold: Hundredths, pub max_payout: Ralo, pub continuous_hours: Option<u32> }
+#[rialo::event] pub struct TemplateStatusChanged { pub underwriter_id: UnderwriterId, pub template_id: TemplateId, pub active: bool }
+#[rialo::event] pub struct TemplateRepriced      { pub underwriter_id: UnderwriterId, pub template_id: TemplateId, pub base_threshold: Option<f64> }
+#[rialo::event] pub struct TemplateMetricSet     { pub underwriter_id: UnderwriterId, pub template_id: TemplateId, pub metric: Metric, pub comparison: Comparison }
@@ -0,0 +1,117 @@
+// ============================================================
+//  Whitelist
+//
+//  Enterprise deployments run closed: only companies they have
+//  approved hold cover, and only underwriters they have vetted
+//  put capital in the vault. The admin keeps a registry of
+//  accounts and the roles each is approved for:
+//    • Policyholder — may own a policy: written for it directly,
+//                     through a broker or a subscription, or
+//                     transferred to it — and dispute its claims
+//    • Underwriter  — may fund the vault, in RALO or a payout mint
+//    • Arbiter      — may act as one: manual observations and
+//                     disputes. Being on the arbiter list is still
+//                     required as well.
+//  Each role is only checked once the admin enforces it, so an
+//  open deployment never notices the registry, and accounts can
+//  be approved before enforcement is switched on.
+// ============================================================
+
+use std::collections::BTreeSet;
+
+use rialo_sdk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::InsuranceState;
+use crate::errors::InsuranceError;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Role {
+    Policyholder,
+    Underwriter,
+    Arbiter,
+}
+
+impl Role {
+    pub fn name(self) -> &'static str {
+        match self {
+            Role::Policyholder => "policyholder",
+            Role::Underwriter  => "underwriter",
+            Role::Arbiter      => "arbiter",
+        }
+    }
+}
+
+// Whether `account` may act as `role` under the enforced roles and approvals
+pub fn is_allowed(enforced: &BTreeSet<Role>, approved: Option<&BTreeSet<Role>>, role: Role) -> bool {
+    !enforced.contains(&role) || approved.is_some_and(|roles| roles.contains(&role))
+}
+
+pub(crate) fn require_role(state: &InsuranceState, account: &Pubkey, role: Role) -> RialoResult<()> {
+    let allowed = is_allowed(&state.config.enforced_roles, state.whitelist.get(account), role);
+    require!(allowed, InsuranceError::NotWhitelisted(role));
+    Ok(())
+}
+
+// ── Entry point: admin approves an account for a role ────────
+#[rialo::instruction]
+pub async fn add_to_whitelist(
+    ctx:     Context<InsuranceState>,
+    account: Pubkey,
+    role:    Role,
+) -> RialoResult<()> {
+
+    require!(ctx.state.config.admin == *ctx.signer, InsuranceError::Unauthorized("Only the admin can change the whitelist.".into()));
+
+    let added = ctx.state.whitelist.entry(account).or_default().insert(role);
+    require!(added, InsuranceError::InvalidState("Account is already whitelisted for that role.".into()));
+
+    emit!(WhitelistAdded { account, role });
+
+    Ok(())
+}
+
+// ── Entry point: admin withdraws an account's approval ───────
+#[rialo::instruction]
+pub async fn remove_from_whitelist(
+    ctx:     Context<InsuranceState>,
+    account: Pubkey,
+    role:    Role,
+) -> RialoResult<()> {
+
+    require!(ctx.state.config.admin == *ctx.signer, InsuranceError::Unauthorized("Only the admin can change the whitelist.".into()));
+
+    // Existing policies and capital stay where they are; only new actions are refused
+    let roles = ctx.state.whitelist.get_mut(&account).ok_or_else(|| InsuranceError::InvalidState("Account is not whitelisted.".into()))?;
+    require!(roles.remove(&role), InsuranceError::InvalidState("Account is not whitelisted for that role.".into()));
+    if roles.is_empty() {
+        ctx.state.whitelist.remove(&account);
+    }
+
+    emit!(WhitelistRemoved { account, role });
+
+    Ok(())
+}
+
+// ── Entry point: admin chooses which roles are enforced ──────
+#[rialo::instruction]
+pub async fn set_enforced_roles(
+    ctx:   Context<InsuranceState>,
+    roles: Vec<Role>,
+) -> RialoResult<()> {
+
+    let config = &mut ctx.state.config;
+
+    require!(config.admin == *ctx.signer, InsuranceError::Unauthorized("Only the admin can change whitelist enforcement.".into()));
+
+    config.enforced_roles = roles.into_iter().collect();
+
+    emit!(WhitelistEnforcementSet { roles: config.enforced_roles.iter().copied().collect() });
+
+    Ok(())
+}
+
+// ── Events ───────────────────────────────────────────────────
+#[rialo::event] pub struct WhitelistAdded          { pub account: Pubkey, pub role: Role }
+#[rialo::event] pub struct WhitelistRemoved        { pub account: Pubkey, pub role: Role }
+#[rialo::event] pub struct WhitelistEnforcementSet { pub roles: Vec<Role> }
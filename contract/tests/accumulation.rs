@@ -0,0 +1,95 @@
+// Rolling accumulation: hourly rain summed over a window, pruned behind it.
+
+use rialo_sdk::prelude::Pubkey;
+use rialo_weather_insurance::accumulation::RollingRain;
+use rialo_weather_insurance::millimeters::{Hundredths, Millimeters};
+use rialo_weather_insurance::money::Ralo;
+use rialo_weather_insurance::policy::{Policy, PolicyStatus};
+
+const HOUR: i64 = 60 * 60;
+const START: i64 = 1_717_200_000 - 1_717_200_000 % HOUR;
+
+fn hundredths(threshold: f64) -> Hundredths {
+    Hundredths::from_f64(threshold)
+}
+
+#[test]
+fn a_day_of_steady_rain_reaches_what_no_single_hour_does() {
+    let mut rolling = RollingRain::new(24);
+
+    let paid_at = (0..30).find(|h| rolling.record(Millimeters::from_mm(3.0), START + h * HOUR, hundredths(60.0)));
+
+    // 20 hours of 3 mm make 60 mm
+    assert_eq!(paid_at, Some(19));
+    assert_eq!(rolling.latest_total_mm(), Millimeters(6_000));
+}
+
+#[test]
+fn readings_outside_the_window_fall_away() {
+    let mut rolling = RollingRain::new(24);
+    assert!(!rolling.record(Millimeters::from_mm(40.0), START, hundredths(60.0)));
+
+    // Thirty hours on, the first downpour is no longer in any window
+    assert!(!rolling.record(Millimeters::from_mm(30.0), START + 30 * HOUR, hundredths(60.0)));
+    assert_eq!(rolling.readings, vec![(START / HOUR + 30, Millimeters(3_000))]);
+
+    // ...and can't be filed again from history
+    assert!(!rolling.record(Millimeters::from_mm(40.0), START, hundredths(60.0)));
+    assert_eq!(rolling.latest_total_mm(), Millimeters(3_000));
+}
+
+#[test]
+fn a_missed_hour_can_be_filled_once_and_any_window_counts() {
+    let mut rolling = RollingRain::new(3);
+    assert!(!rolling.record(Millimeters::from_mm(20.0), START, hundredths(50.0)));
+    assert!(!rolling.record(Millimeters::from_mm(0.0), START + 2 * HOUR, hundredths(50.0)));
+
+    // Backfilling the hour between them completes a 3-hour window
+    assert!(rolling.record(Millimeters::from_mm(30.0), START + HOUR, hundredths(50.0)));
+    assert!(!rolling.record(Millimeters::from_mm(99.0), START + HOUR + 600, hundredths(10_000.0)));
+    assert_eq!(rolling.max_total_mm(), Millimeters(5_000));
+}
+
+#[test]
+fn sums_follow_the_provider_timestamp_not_the_filing_order() {
+    let rain = [12.0, 0.0, 7.5, 3.0, 20.0, 1.0];
+    let mut in_order = RollingRain::new(4);
+    let mut shuffled = RollingRain::new(4);
+    for (h, mm) in rain.iter().enumerate() {
+        in_order.record(Millimeters::from_mm(*mm), START + h as i64 * HOUR, hundredths(1_000.0));
+    }
+    // Newest first, then the delayed backfills in no particular order
+    for h in [5, 1, 4, 0, 3, 2] {
+        shuffled.record(Millimeters::from_mm(rain[h]), START + h as i64 * HOUR + 900, hundredths(1_000.0));
+    }
+
+    assert_eq!(shuffled.readings, in_order.readings);
+    assert_eq!(shuffled.latest_total_mm(), Millimeters(3_150));
+    assert_eq!(shuffled.max_total_mm(), in_order.max_total_mm());
+}
+
+#[test]
+fn backfills_older_than_the_maximum_age_are_ignored() {
+    let mut rolling = RollingRain::new(24).with_max_age(Some(6));
+    assert!(!rolling.record(Millimeters::from_mm(10.0), START + 10 * HOUR, hundredths(40.0)));
+
+    // Within the window but past the maximum age: not filed
+    assert!(!rolling.record(Millimeters::from_mm(35.0), START + 3 * HOUR, hundredths(40.0)));
+    assert_eq!(rolling.latest_total_mm(), Millimeters(1_000));
+
+    // A recent backfill still counts
+    assert!(rolling.record(Millimeters::from_mm(30.0), START + 5 * HOUR, hundredths(40.0)));
+    assert_eq!(RollingRain::new(24).with_max_age(Some(48)).max_age_hours, 24);
+}
+
+#[test]
+fn accumulating_policies_pay_on_the_window_not_the_hour() {
+    let mut policy = Policy::new(0, 0, Pubkey::default(), "nairobi".into(), Hundredths::from_f64(50.0), Ralo::whole(100), Ralo::whole(5));
+    policy.coverage_secs = 7 * 24 * HOUR;
+    policy.accumulation = Some(RollingRain::new(24));
+    assert!(policy.record_premium(policy.premium_amount, START));
+
+    assert!(!policy.apply_reading(hundredths(30.0), START));
+    assert!(policy.apply_reading(hundredths(25.0), START + HOUR));
+    assert_eq!(policy.status, PolicyStatus::PaidOut);
+}
@@ -0,0 +1,74 @@
+// Pro-ration rounding: charges round down, refunds round up, installments add up exactly.
+
+use rialo_weather_insurance::actuarial::{
+    cancellation_refund, charge_share, earned_premium, installment, installments, refund_bps, refund_share,
+};
+use rialo_weather_insurance::money::Ralo;
+
+const DAY: i64 = 24 * 60 * 60;
+
+#[test]
+fn charges_round_down_and_refunds_round_up() {
+    assert_eq!(charge_share(Ralo(100), 1, 3), Ralo(33));
+    assert_eq!(refund_share(Ralo(100), 1, 3), Ralo(34));
+    assert_eq!(refund_bps(Ralo(999), 5_000), Ralo(500));
+
+    // Exact shares need no rounding either way
+    assert_eq!(charge_share(Ralo(90), 1, 3), Ralo(30));
+    assert_eq!(refund_share(Ralo(90), 1, 3), Ralo(30));
+}
+
+#[test]
+fn rounding_stays_within_one_base_unit() {
+    for amount in [1u64, 7, 999, 1_000_000_007, u64::MAX] {
+        for (part, whole) in [(1, 3), (2, 3), (1, 7), (5, 11), (9_999, 10_000)] {
+            let exact = amount as u128 * part as u128;
+            let charge = charge_share(Ralo(amount), part, whole).0 as u128 * whole as u128;
+            let refund = refund_share(Ralo(amount), part, whole).0 as u128 * whole as u128;
+            assert!(charge <= exact && exact - charge < whole as u128);
+            assert!(refund >= exact && refund - exact < whole as u128);
+        }
+    }
+}
+
+#[test]
+fn shares_beyond_the_whole_are_capped() {
+    assert_eq!(charge_share(Ralo(100), 5, 3), Ralo(100));
+    assert_eq!(refund_share(Ralo(100), 5, 3), Ralo(100));
+    assert_eq!(charge_share(Ralo(100), 0, 0), Ralo(100));
+}
+
+#[test]
+fn installments_sum_to_the_premium_exactly() {
+    for total in [0u64, 1, 2, 100, 1_000_000_001] {
+        for count in 1..=12u32 {
+            let parts = installments(Ralo(total), count);
+            assert_eq!(parts.len(), count as usize);
+            assert_eq!(parts.iter().copied().sum::<Ralo>(), Ralo(total), "{total} in {count}");
+
+            // No prefix charges more than its pro-rata share
+            let mut paid = Ralo::ZERO;
+            for (k, part) in parts.iter().enumerate() {
+                paid += *part;
+                assert!(paid.0 as u128 * count as u128 <= total as u128 * (k as u128 + 1));
+            }
+        }
+    }
+    assert_eq!(installments(Ralo(100), 3), vec![Ralo(33), Ralo(33), Ralo(34)]);
+    assert_eq!(installment(Ralo(100), 2, 3), Ralo(34));
+}
+
+#[test]
+fn cancelling_refunds_everything_not_earned() {
+    let premium = Ralo(1_000);
+    assert_eq!(earned_premium(premium, 10 * DAY, 30 * DAY), Ralo(333));
+    assert_eq!(cancellation_refund(premium, premium, 10 * DAY, 30 * DAY), Ralo(667));
+
+    // Paid in installments: the refund is cumulative, so nothing is lost to rounding per installment
+    let paid: Ralo = installments(premium, 3).into_iter().take(2).sum();
+    assert_eq!(paid, Ralo(666));
+    assert_eq!(cancellation_refund(premium, paid, 10 * DAY, 30 * DAY), Ralo(333));
+
+    assert_eq!(cancellation_refund(premium, premium, -DAY, 30 * DAY), premium);
+    assert_eq!(cancellation_refund(premium, premium, 31 * DAY, 30 * DAY), Ralo::ZERO);
+}
@@ -0,0 +1,56 @@
+// Forecast mode: reading the 5-day forecast and which step earns an advance.
+
+use rialo_sdk::prelude::Pubkey;
+use rialo_weather_insurance::advances::{advance_step, TriggerMode};
+use rialo_weather_insurance::millimeters::Hundredths;
+use rialo_weather_insurance::money::Ralo;
+use rialo_weather_insurance::oracle::{parse_forecast, ForecastStep};
+use rialo_weather_insurance::policy::Policy;
+use rialo_weather_insurance::settlement::{top_up, FULL_SHARE_BPS};
+
+const HOUR: i64 = 60 * 60;
+
+fn policy() -> Policy {
+    let mut policy = Policy::new(0, 0, Pubkey::default(), "mombasa".into(), Hundredths::from_f64(10.0), Ralo::whole(100), Ralo::whole(5));
+    policy.coverage_secs = 30 * 24 * HOUR;
+    policy.record_premium(Ralo::whole(5), 0);
+    policy.trigger_mode = TriggerMode::Forecast { advance_bps: 3_000 };
+    policy
+}
+
+fn step(start: i64, rainfall_mm: f64) -> ForecastStep {
+    ForecastStep { start, rain_chance: 0.9, rainfall_mm }
+}
+
+#[test]
+fn forecast_steps_read_rain_volume_and_chance() {
+    let body = br#"{ "list": [
+        { "dt": 10800, "pop": 0.8, "rain": { "3h": 36.0 } },
+        { "dt": 21600 }
+    ] }"#;
+    let steps = parse_forecast(body).unwrap();
+    assert_eq!(steps, vec![ForecastStep { start: 3 * HOUR, rain_chance: 0.8, rainfall_mm: 36.0 }, ForecastStep { start: 6 * HOUR, rain_chance: 0.0, rainfall_mm: 0.0 }]);
+    assert_eq!(steps[0].hourly_mm(), 12.0);
+}
+
+#[test]
+fn only_the_next_day_of_cover_counts() {
+    let policy = policy();
+    let now = 10 * HOUR;
+    let steps = [step(9 * HOUR, 45.0), step(30 * HOUR, 60.0), step(36 * HOUR, 90.0), step(12 * HOUR, 20.0)];
+
+    // The step under way and the wettest one within a day; the later downpour waits
+    assert_eq!(advance_step(&policy, &steps, now), Some(step(30 * HOUR, 60.0)));
+    assert_eq!(advance_step(&policy, &steps[2..], now), None);
+    assert_eq!(advance_step(&policy, &steps[3..], now), None);
+}
+
+#[test]
+fn the_observed_trigger_pays_the_rest_of_the_payout() {
+    let mut policy = policy();
+    let (advance, _) = top_up(&policy, 3_000);
+    assert_eq!(advance, Ralo::whole(30));
+
+    policy.paid_out = advance;
+    assert_eq!(top_up(&policy, FULL_SHARE_BPS).0, Ralo::whole(70));
+}
@@ -0,0 +1,57 @@
+// Parsing the OpenWeatherMap air-pollution feed and counting hazardous runs.
+
+use rialo_weather_insurance::air::{parse_air_pollution, AirCover, AirReading, AqiTrigger};
+
+const HOUR: i64 = 60 * 60;
+
+fn feed(aqi: u8, pm2_5: f64) -> String {
+    format!(
+        r#"{{ "coord": {{ "lon": 77.2167, "lat": 28.6667 }}, "list": [ {{
+            "main": {{ "aqi": {aqi} }},
+            "components": {{ "co": 1602.2, "no2": 61.69, "o3": 0.0, "so2": 19.55, "pm2_5": {pm2_5}, "pm10": 356.4, "nh3": 14.44 }},
+            "dt": 1732000000
+        }} ] }}"#
+    )
+}
+
+#[test]
+fn reads_index_and_pm2_5() {
+    let reading = parse_air_pollution(feed(5, 287.3).as_bytes()).unwrap();
+    assert_eq!(reading, AirReading { aqi: 5, pm2_5: 287.3 });
+}
+
+#[test]
+fn rejects_out_of_range_index() {
+    assert!(parse_air_pollution(feed(0, 10.0).as_bytes()).is_err());
+    assert!(parse_air_pollution(feed(6, 10.0).as_bytes()).is_err());
+    assert!(parse_air_pollution(br#"{ "list": [] }"#).is_err());
+}
+
+#[test]
+fn pays_after_consecutive_hazardous_checks() {
+    let mut cover = AirCover::new(AqiTrigger { min_aqi: 5, checks: 3 });
+    let bad = AirReading { aqi: 5, pm2_5: 40.0 };
+    let smoky = AirReading { aqi: 4, pm2_5: 180.0 };
+    let clean = AirReading { aqi: 2, pm2_5: 12.0 };
+
+    assert!(cover.is_hazardous(&bad, 150.0));
+    assert!(cover.is_hazardous(&smoky, 150.0));
+    assert!(!cover.is_hazardous(&clean, 150.0));
+
+    assert!(!cover.record(true, 0));
+    assert!(!cover.record(true, HOUR));
+    assert!(!cover.record(false, 2 * HOUR));   // a clean hour resets the run
+    assert!(!cover.record(true, 3 * HOUR));
+    assert!(!cover.record(true, 4 * HOUR));
+    assert!(cover.record(true, 5 * HOUR));
+}
+
+#[test]
+fn counted_checks_are_an_hour_apart() {
+    let mut cover = AirCover::new(AqiTrigger { min_aqi: 5, checks: 2 });
+    assert!(cover.can_check_at(0));
+
+    cover.record(true, 1_000);
+    assert!(!cover.can_check_at(1_000 + HOUR - 1));
+    assert!(cover.can_check_at(1_000 + HOUR));
+}
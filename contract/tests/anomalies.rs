@@ -0,0 +1,28 @@
+// Anomalous observations: readings screened against a location's monthly 99.9th percentile.
+
+use rialo_weather_insurance::anomalies::{exceeded, ExtremeTable};
+use rialo_weather_insurance::millimeters::Hundredths;
+use rialo_weather_insurance::normalization::Metric;
+
+// 2024-01-31 23:00 UTC
+const END_OF_JANUARY: i64 = 1_706_742_000;
+
+fn tables() -> Vec<ExtremeTable> {
+    let mut p999 = [40.0; 12];
+    p999[1] = 90.0;   // February storms
+    vec![ExtremeTable { metric: Metric::Rainfall, p999 }]
+}
+
+#[test]
+fn readings_past_the_months_percentile_are_flagged() {
+    assert_eq!(exceeded(&tables(), Metric::Rainfall, Hundredths(5_500), END_OF_JANUARY, 0), Some(Hundredths(4_000)));
+    assert_eq!(exceeded(&tables(), Metric::Rainfall, Hundredths(4_000), END_OF_JANUARY, 0), None);
+    assert_eq!(exceeded(&[], Metric::Rainfall, Hundredths(40_000), END_OF_JANUARY, 0), None);
+}
+
+#[test]
+fn the_local_month_decides_the_percentile() {
+    // Already February in Nairobi, three hours ahead
+    assert_eq!(exceeded(&tables(), Metric::Rainfall, Hundredths(5_500), END_OF_JANUARY, 180), None);
+    assert_eq!(exceeded(&tables(), Metric::Rainfall, Hundredths(9_500), END_OF_JANUARY, 180), Some(Hundredths(9_000)));
+}
@@ -0,0 +1,45 @@
+// Manual settlement: the hour the arbiters' reading settles a policy on.
+
+use rialo_sdk::prelude::Pubkey;
+use rialo_weather_insurance::arbiter::settlement_time;
+use rialo_weather_insurance::millimeters::Hundredths;
+use rialo_weather_insurance::money::Ralo;
+use rialo_weather_insurance::policy::Policy;
+
+const START: i64 = 1_700_000_000;
+const COVER: i64 = 30 * 24 * 60 * 60;
+
+fn policy() -> Policy {
+    let mut policy = Policy::new(0, 0, Pubkey::default(), "nairobi".into(), Hundredths::from_f64(20.0), Ralo::whole(100), Ralo::whole(10));
+    policy.coverage_secs = COVER;
+    policy
+}
+
+#[test]
+fn live_cover_settles_on_the_hour_of_settlement() {
+    let mut policy = policy();
+    assert!(policy.record_premium(policy.premium_amount, START));
+
+    assert_eq!(settlement_time(&policy, START + 3_600), Some(START + 3_600));
+}
+
+#[test]
+fn ended_cover_settles_on_its_last_covered_hour() {
+    let mut policy = policy();
+    assert!(policy.record_premium(policy.premium_amount, START));
+
+    let last = settlement_time(&policy, START + COVER + 86_400).unwrap();
+    assert_eq!(last, START + COVER - 1);
+    assert!(policy.is_covered_at(last));
+}
+
+#[test]
+fn cover_that_has_not_started_cannot_be_settled() {
+    assert_eq!(settlement_time(&policy(), START), None);
+
+    // A renewal paid early waits for the cover it continues
+    let mut renewal = policy();
+    renewal.starts_at = Some(START + 86_400);
+    assert!(renewal.record_premium(renewal.premium_amount, START));
+    assert_eq!(settlement_time(&renewal, START), None);
+}
@@ -0,0 +1,58 @@
+// Loss attestation: which settlements an attestation answers for, and when an
+// unanswered one lapses.
+
+use rialo_sdk::prelude::Pubkey;
+use rialo_weather_insurance::attestation::{lapse, Attestation, AttestationCover, ATTESTATION_WINDOW_SECS};
+use rialo_weather_insurance::fx::DeferredPayout;
+use rialo_weather_insurance::millimeters::Hundredths;
+use rialo_weather_insurance::money::Ralo;
+use rialo_weather_insurance::policy::{Policy, PolicyStatus};
+
+const TRIGGERED: i64 = 1_700_000_000;
+
+fn awaiting(deadline: Option<i64>) -> Policy {
+    let mut policy = Policy::new(0, 0, Pubkey::default(), "nairobi".into(), Hundredths::from_f64(20.0), Ralo::whole(100), Ralo::whole(10));
+    policy.status = PolicyStatus::PendingAttestation;
+    policy.attestation = Some(AttestationCover {
+        pending:      Some(DeferredPayout::new(Some(4), 10_000, Hundredths(250), TRIGGERED - 3_600)),
+        attestations: Vec::new(),
+        deadline,
+    });
+    policy
+}
+
+#[test]
+fn an_attestation_covers_only_the_round_it_was_given_for() {
+    let mut cover = AttestationCover::default();
+    assert!(!cover.attested(Some(4)));
+
+    cover.attestations.push(Attestation { round_id: Some(4), hash: [7; 32], attested_at: 3_600 });
+    assert!(cover.attested(Some(4)));
+    assert!(!cover.attested(Some(5)));
+    // An arbiter override settles under no round, and needs its own
+    assert!(!cover.attested(None));
+}
+
+#[test]
+fn an_overdue_attestation_expires_the_policy_and_returns_its_cover() {
+    let deadline = TRIGGERED + ATTESTATION_WINDOW_SECS;
+    let mut policy = awaiting(Some(deadline));
+
+    assert!(lapse(&mut policy, deadline - 1).is_err());
+    assert_eq!(policy.status, PolicyStatus::PendingAttestation);
+
+    assert_eq!(lapse(&mut policy, deadline).unwrap(), (Some(4), Ralo::whole(100)));
+    assert_eq!(policy.status, PolicyStatus::Expired);
+    assert_eq!(policy.attestation.as_ref().unwrap().pending, None);
+    // Lapsed once, there is nothing left to lapse
+    assert!(lapse(&mut policy, deadline).is_err());
+}
+
+#[test]
+fn a_payout_parked_before_deadlines_lapses_a_window_after_its_reading() {
+    let policy = awaiting(None);
+    let cover = policy.attestation.as_ref().unwrap();
+
+    assert_eq!(cover.lapses_at(), Some(TRIGGERED - 3_600 + ATTESTATION_WINDOW_SECS));
+    assert_eq!(AttestationCover::default().lapses_at(), None);
+}
@@ -0,0 +1,49 @@
+// Bordereau lines: a claim's receipt and policy terms, ceded at the share the policy was written at.
+
+use rialo_sdk::prelude::Pubkey;
+use rialo_weather_insurance::bordereau::{bordereau_line, lines_digest};
+use rialo_weather_insurance::millimeters::Hundredths;
+use rialo_weather_insurance::money::Ralo;
+use rialo_weather_insurance::policy::Policy;
+use rialo_weather_insurance::receipts::{observation_hash, SettlementReceipt};
+
+fn receipt(amount: Ralo) -> SettlementReceipt {
+    SettlementReceipt {
+        policy_id:        7,
+        round_id:         Some(41),
+        observation_hash: observation_hash(7, Hundredths(3_250), 1_700_000_000),
+        value:            Hundredths(3_250),
+        old_reading:      None,
+        observed_at:      1_700_000_000,
+        amount,
+        tx_time:          1_700_000_060,
+        postmortems:      Vec::new(),
+        review:           None,
+    }
+}
+
+#[test]
+fn lines_cede_the_share_the_policy_was_written_at() {
+    let mut policy = Policy::new(0, 3, Pubkey::default(), "nairobi".into(), Hundredths::from_f64(30.0), Ralo::whole(100), Ralo::whole(5));
+    policy.ceded_bps = 4_000;
+
+    let line = bordereau_line(&policy, Pubkey::default(), &receipt(Ralo::whole(100)));
+
+    assert_eq!((line.policy_id, line.template_id), (7, 3));
+    assert_eq!((line.threshold, line.index_value), (Hundredths(3_000), Hundredths(3_250)));
+    assert_eq!((line.observed_at, line.paid_at), (1_700_000_000, 1_700_000_060));
+    assert_eq!(line.gross, Ralo::whole(100));
+    assert_eq!(line.ceded, Ralo::whole(40));
+}
+
+#[test]
+fn digest_commits_to_every_line() {
+    let policy = Policy::new(0, 3, Pubkey::default(), "nairobi".into(), Hundredths::from_f64(30.0), Ralo::whole(100), Ralo::whole(5));
+    let full = bordereau_line(&policy, Pubkey::default(), &receipt(Ralo::whole(100)));
+    let partial = bordereau_line(&policy, Pubkey::default(), &receipt(Ralo::whole(50)));
+
+    assert_eq!(full.ceded, Ralo::ZERO);
+    let (forward, backward) = (vec![full.clone(), partial.clone()], vec![partial, full]);
+    assert_ne!(lines_digest(&forward[..1]), lines_digest(&backward[..1]));
+    assert_ne!(lines_digest(&forward), lines_digest(&backward));
+}
@@ -0,0 +1,57 @@
+// Multi-peril bundles: which readings trigger a peril, and the share of the aggregate limit that pays.
+
+#![cfg(feature = "wind")]
+
+use rialo_weather_insurance::bundles::{check_terms, BundleCover, BundleTerms, BundledPeril};
+use rialo_weather_insurance::millimeters::Hundredths;
+use rialo_weather_insurance::normalization::{Comparison, Metric};
+
+fn peril(metric: Metric, threshold: f64, sub_limit_bps: u64) -> BundledPeril {
+    BundledPeril::new(metric, Comparison::AtOrAbove, Hundredths::from_f64(threshold), sub_limit_bps)
+}
+
+fn bundle() -> BundleCover {
+    BundleCover::new(peril(Metric::Rainfall, 50.0, 6_000), vec![peril(Metric::WindSpeed, 20.0, 6_000)])
+}
+
+#[test]
+fn each_peril_triggers_once() {
+    let mut cover = bundle();
+    assert!(!cover.record(Metric::Rainfall, Hundredths::from_f64(49.0)));
+    assert!(cover.record(Metric::Rainfall, Hundredths::from_f64(55.0)));
+    assert!(!cover.record(Metric::Rainfall, Hundredths::from_f64(80.0)));
+    assert_eq!(cover.share_bps(), 6_000);
+}
+
+#[test]
+fn triggered_sub_limits_are_capped_at_the_aggregate() {
+    let mut cover = bundle();
+    assert!(cover.record(Metric::WindSpeed, Hundredths::from_f64(25.0)));
+    assert_eq!(cover.share_bps(), 6_000);
+    assert!(cover.record(Metric::Rainfall, Hundredths::from_f64(50.0)));
+    assert_eq!(cover.share_bps(), 10_000);
+}
+
+#[test]
+fn readings_outside_the_bundle_are_ignored() {
+    let mut cover = bundle();
+    assert!(!cover.record(Metric::WindChill, Hundredths::from_f64(-20.0)));
+    assert_eq!(cover.share_bps(), 0);
+}
+
+#[test]
+fn bundles_need_distinct_riders_with_sub_limits() {
+    let terms = |riders| BundleTerms { sub_limit_bps: 5_000, riders };
+    assert!(check_terms(Metric::Rainfall, &terms(vec![peril(Metric::WindSpeed, 20.0, 5_000)])).is_ok());
+
+    let invalid = [
+        Vec::new(),
+        vec![peril(Metric::Rainfall, 20.0, 5_000)],
+        vec![peril(Metric::WindSpeed, 20.0, 5_000), peril(Metric::WindSpeed, 30.0, 5_000)],
+        vec![peril(Metric::WindSpeed, 20.0, 0)],
+        vec![peril(Metric::WindSpeed, 20.0, 10_001)],
+    ];
+    for riders in invalid {
+        assert!(check_terms(Metric::Rainfall, &terms(riders.clone())).is_err(), "{riders:?}");
+    }
+}
@@ -0,0 +1,62 @@
+// Certificate of insurance: the terms digest answers for every binding term.
+
+use rialo_sdk::prelude::Pubkey;
+use rialo_weather_insurance::conditions::Condition;
+use rialo_weather_insurance::fx::UsdPayout;
+use rialo_weather_insurance::millimeters::Hundredths;
+use rialo_weather_insurance::mints::MintPayout;
+use rialo_weather_insurance::money::Ralo;
+use rialo_weather_insurance::normalization::{Comparison, Location, Metric};
+use rialo_weather_insurance::policy::Policy;
+use rialo_weather_insurance::routes::{Aggregation, RouteCover, RouteStop};
+
+fn policy() -> Policy {
+    Policy::new(0, 0, Pubkey::default(), "nairobi".into(), Hundredths::from_f64(20.0), Ralo::whole(100), Ralo::whole(10))
+}
+
+fn stop(city: &str) -> RouteStop {
+    RouteStop::new(Location::City(city.into()), city.into())
+}
+
+#[test]
+fn each_binding_term_changes_the_digest() {
+    let plain = policy().terms_hash(1);
+
+    let mut below = policy();
+    below.comparison = Comparison::AtOrBelow;
+
+    let mut route = policy();
+    route.route = Some(RouteCover { aggregation: Aggregation::WorstOf, stops: vec![stop("nairobi"), stop("mombasa")] });
+
+    let mut condition = policy();
+    condition.compound = Some(Condition::Reading { metric: Metric::Rainfall, comparison: Comparison::AtOrAbove, threshold: Hundredths(3_000) });
+
+    let mut mint = policy();
+    mint.payout_mint = Some(MintPayout { mint: Pubkey::new_from_array([3; 32]), amount: 500, paid: 0, reserved: 500 });
+
+    let mut usd = policy();
+    usd.usd_payout = Some(UsdPayout { usd_cents: 12_500, max_slippage_bps: 100, converted: None, rate: None, deferred: None });
+
+    let digests = [&below, &route, &condition, &mint, &usd].map(|p| p.terms_hash(1));
+    for (i, digest) in digests.iter().enumerate() {
+        assert_ne!(*digest, plain);
+        assert!(digests[i + 1..].iter().all(|other| other != digest));
+    }
+}
+
+#[test]
+fn progress_against_the_terms_leaves_the_digest_alone() {
+    let mut mint = policy();
+    mint.payout_mint = Some(MintPayout { mint: Pubkey::new_from_array([3; 32]), amount: 500, paid: 0, reserved: 500 });
+    let written = mint.terms_hash(1);
+
+    // Tokens sent and last readings are state, not terms
+    mint.payout_mint = Some(MintPayout { mint: Pubkey::new_from_array([3; 32]), amount: 500, paid: 200, reserved: 300 });
+    assert_eq!(mint.terms_hash(1), written);
+
+    let mut route = policy();
+    route.route = Some(RouteCover { aggregation: Aggregation::BestOf, stops: vec![stop("nairobi"), stop("mombasa")] });
+    let written = route.terms_hash(1);
+    route.route.as_mut().unwrap().stops[1].last_value = Some(Hundredths(450));
+    assert_eq!(route.terms_hash(1), written);
+}
@@ -0,0 +1,29 @@
+// Claims ledger: per-template loss ratios and settlement times.
+
+use rialo_weather_insurance::claims::{ClaimsLedger, LaeKind};
+use rialo_weather_insurance::money::Ralo;
+
+#[test]
+fn each_template_keeps_its_own_ratios() {
+    let mut ledger = ClaimsLedger::default();
+    let product = ledger.template_mut(2);
+    product.premiums_earned = Ralo::whole(20);
+    product.claims_paid     = Ralo::whole(5);
+    product.lae.add(LaeKind::ProviderCost, Ralo::whole(1));
+
+    let product = ledger.templates[&2];
+    assert_eq!(product.loss_ratio_bps(), 2_500);
+    assert_eq!(product.combined_ratio_bps(), 3_000);
+    assert!(!ledger.templates.contains_key(&1));
+}
+
+#[test]
+fn settlement_time_is_averaged_over_payouts() {
+    let mut ledger = ClaimsLedger::default();
+    let product = ledger.template_mut(0);
+    assert_eq!(product.avg_settle_secs(), None);
+
+    product.payments    = 3;
+    product.settle_secs = 90 + 600 + 3_600;
+    assert_eq!(product.avg_settle_secs(), Some(1_430));
+}
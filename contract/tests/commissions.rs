@@ -0,0 +1,23 @@
+// Broker commission: the cut of a cleared premium, held to the admin's caps.
+
+use rialo_weather_insurance::commissions::{commission_due, CommissionCaps};
+use rialo_weather_insurance::money::Ralo;
+
+#[test]
+fn commission_is_the_rate_of_the_cleared_premium() {
+    let caps = CommissionCaps { max_bps: 2_000, max_amount: None };
+    assert_eq!(commission_due(Ralo::whole(50), 1_000, &caps), Ralo::whole(5));
+}
+
+#[test]
+fn a_cap_lowered_after_writing_holds_when_the_premium_clears() {
+    let caps = CommissionCaps { max_bps: 500, max_amount: None };
+    assert_eq!(commission_due(Ralo::whole(60), 1_000, &caps), Ralo::whole(3));
+}
+
+#[test]
+fn a_large_premium_stops_at_the_amount_cap() {
+    let caps = CommissionCaps { max_bps: 2_000, max_amount: Some(Ralo::whole(40)) };
+    assert_eq!(commission_due(Ralo::whole(1_000), 1_000, &caps), Ralo::whole(40));
+    assert_eq!(commission_due(Ralo::whole(100), 1_000, &caps), Ralo::whole(10));
+}
@@ -0,0 +1,120 @@
+// Placing policies in geohash-4 cells for the exposure heat map, and
+// what one beneficiary is owed and holds across the book.
+
+use rialo_sdk::prelude::Pubkey;
+use rialo_weather_insurance::concentration::{apply_beneficiary_cap, beneficiary_exposure, exposure_cell, holder_policies, outstanding_exposure};
+use rialo_weather_insurance::config::{apply_limits, ContractConfig, Limits, NetworkMode};
+use rialo_weather_insurance::geo::GeoPoint;
+use rialo_weather_insurance::metadata::SealedMetadata;
+use rialo_weather_insurance::millimeters::Hundredths;
+use rialo_weather_insurance::money::Ralo;
+use rialo_weather_insurance::policy::{Policy, PolicyStatus};
+
+fn policy() -> Policy {
+    Policy::new(0, 0, Pubkey::default(), "nairobi".into(), Hundredths::from_f64(10.0), Ralo::whole(100), Ralo::whole(5))
+}
+
+fn sealed(geohash: &str) -> SealedMetadata {
+    SealedMetadata { geohash: geohash.into(), ciphertext: vec![1], nonce: Vec::new(), recipients: Vec::new() }
+}
+
+#[test]
+fn policies_are_placed_by_point_then_by_public_bucket() {
+    let mut by_point = policy();
+    by_point.insured_point = Some(GeoPoint::new(-1_292_100, 36_821_900).unwrap());   // Nairobi CBD
+    by_point.sealed = Some(sealed("kzf0z"));
+    assert_eq!(exposure_cell(&by_point).as_deref(), Some("kzf0"));
+
+    let mut by_bucket = policy();
+    by_bucket.sealed = Some(sealed("u4pru"));
+    assert_eq!(exposure_cell(&by_bucket).as_deref(), Some("u4pr"));
+
+    // A bucket coarser than a cell, or none at all, can't be placed
+    let mut coarse = policy();
+    coarse.sealed = Some(sealed("u4p"));
+    assert_eq!(exposure_cell(&coarse), None);
+    assert_eq!(exposure_cell(&policy()), None);
+}
+
+#[test]
+fn only_live_and_pending_policies_carry_exposure() {
+    let mut live = policy();
+    assert_eq!(outstanding_exposure(&live), Ralo::whole(100));
+
+    live.status = PolicyStatus::Active;
+    live.paid_out = Ralo::whole(40);   // storm tiers pay in steps
+    assert_eq!(outstanding_exposure(&live), Ralo::whole(60));
+
+    live.status = PolicyStatus::Expired;
+    assert_eq!(outstanding_exposure(&live), Ralo::ZERO);
+}
+
+#[test]
+fn a_beneficiary_is_owed_what_its_live_policies_still_owe() {
+    let pending = policy();
+    let mut part_paid = policy();
+    part_paid.status = PolicyStatus::Active;
+    part_paid.paid_out = Ralo::whole(30);
+    let mut settled = policy();
+    settled.status = PolicyStatus::PaidOut;
+
+    let book = [pending, part_paid, settled];
+    assert_eq!(beneficiary_exposure(&book, &Pubkey::default()), Ralo::whole(170));
+    assert_eq!(beneficiary_exposure(&[], &Pubkey::default()), Ralo::ZERO);
+}
+
+#[test]
+fn only_policies_that_could_still_pay_count_against_the_limit() {
+    let mut exhausted = policy();
+    exhausted.status = PolicyStatus::Active;
+    exhausted.paid_out = Ralo::whole(100);
+    let mut expired = policy();
+    expired.status = PolicyStatus::Expired;
+
+    let book = [policy(), policy(), exhausted, expired];
+    assert_eq!(holder_policies(&book, &Pubkey::default()), 2);
+    assert_eq!(holder_policies(&book, &Pubkey::new_from_array([1; 32])), 0);
+}
+
+#[test]
+fn admin_limits_replace_the_network_defaults() {
+    let mut config = ContractConfig::default();
+    assert_eq!(config.limits(), NetworkMode::MainNet.limits());
+    assert_eq!(config.limits().max_payout, Ralo::whole(200));
+
+    let tuned = Limits { min_threshold: Hundredths(50), old_min_threshold: None, max_payout: Ralo::whole(500), max_policies: Some(3), max_coverage_per_holder: None };
+    config.limits = Some(tuned);
+    config.network_mode = NetworkMode::DevNet;
+    assert_eq!(config.limits(), tuned);
+}
+
+#[test]
+fn the_beneficiary_cap_lives_with_the_other_limits_and_changes_alone() {
+    let mut config = ContractConfig::default();
+    let tuned = Limits { min_threshold: Hundredths(50), old_min_threshold: None, max_payout: Ralo::whole(500), max_policies: Some(3), max_coverage_per_holder: None };
+    apply_limits(&mut config, tuned).unwrap();
+
+    // Setting the cap leaves the limits set before it
+    apply_beneficiary_cap(&mut config, Some(Ralo::whole(800))).unwrap();
+    assert_eq!(config.limits(), Limits { max_coverage_per_holder: Some(Ralo::whole(800)), ..tuned });
+
+    // ...and retuning those from what's in force keeps the cap
+    let retuned = Limits { max_payout: Ralo::whole(600), ..config.limits() };
+    apply_limits(&mut config, retuned).unwrap();
+    assert_eq!(config.limits().max_coverage_per_holder, Some(Ralo::whole(800)));
+
+    apply_beneficiary_cap(&mut config, None).unwrap();
+    assert_eq!(config.limits(), Limits { max_payout: Ralo::whole(600), ..tuned });
+
+    assert!(apply_beneficiary_cap(&mut config, Some(Ralo::ZERO)).is_err());
+}
+
+#[test]
+fn a_beneficiary_cap_alone_leaves_the_limits_following_the_network() {
+    let mut config = ContractConfig { network_mode: NetworkMode::DevNet, ..ContractConfig::default() };
+    apply_beneficiary_cap(&mut config, Some(Ralo::whole(800))).unwrap();
+    assert_eq!(config.limits, None);
+
+    config.network_mode = NetworkMode::MainNet;
+    assert_eq!(config.limits(), Limits { max_coverage_per_holder: Some(Ralo::whole(800)), ..NetworkMode::MainNet.limits() });
+}
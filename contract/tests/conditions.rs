@@ -0,0 +1,53 @@
+// Compound conditions: AND/OR trees read against a whole provider response.
+
+#![cfg(all(feature = "wind", feature = "cold-chain"))]
+
+use rialo_weather_insurance::conditions::{Condition, MAX_CONDITION_DEPTH};
+use rialo_weather_insurance::millimeters::Hundredths;
+use rialo_weather_insurance::normalization::{Comparison, Metric};
+
+fn reading(metric: Metric, comparison: Comparison, threshold: f64) -> Condition {
+    Condition::Reading { metric, comparison, threshold: Hundredths::from_f64(threshold) }
+}
+
+#[test]
+fn every_sub_condition_is_reported_whichever_way_it_goes() {
+    // rain ≥ 15 mm AND wind ≥ 60 km/h
+    let storm = Condition::All(vec![
+        reading(Metric::Rainfall, Comparison::AtOrAbove, 15.0),
+        reading(Metric::WindSpeed, Comparison::AtOrAbove, 60.0),
+    ]);
+    let read = |metric: Metric| match metric {
+        Metric::Rainfall => Some(Hundredths(2_000)),
+        Metric::WindSpeed => Some(Hundredths(4_000)),
+        _ => None,
+    };
+
+    let mut terms = Vec::new();
+    assert!(!storm.evaluate(&read, &mut terms));
+    assert_eq!(terms.len(), 2);
+    assert!(terms[0].met && !terms[1].met);
+    assert_eq!(terms[1].reading, Some(Hundredths(4_000)));
+
+    // rain ≥ 30 mm OR temperature ≤ 0 °C; no temperature in the response counts as not met
+    let either = Condition::Any(vec![
+        reading(Metric::Rainfall, Comparison::AtOrAbove, 30.0),
+        reading(Metric::Temperature, Comparison::AtOrBelow, 0.0),
+    ]);
+    let mut terms = Vec::new();
+    assert!(!either.evaluate(&read, &mut terms));
+    assert_eq!(terms[1].reading, None);
+}
+
+#[test]
+fn trees_are_kept_shallow_and_real() {
+    let mut nested = reading(Metric::Rainfall, Comparison::AtOrAbove, 10.0);
+    for _ in 0..MAX_CONDITION_DEPTH {
+        nested = Condition::Any(vec![nested, reading(Metric::Rainfall, Comparison::AtOrAbove, 50.0)]);
+    }
+    assert_eq!(nested.depth(), MAX_CONDITION_DEPTH + 1);
+    assert!(nested.validate().is_err());
+
+    let lonely = Condition::All(vec![reading(Metric::Rainfall, Comparison::AtOrAbove, 10.0)]);
+    assert!(lonely.validate().is_err());
+}
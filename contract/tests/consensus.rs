@@ -0,0 +1,44 @@
+// Median and spread across weather sources, and which payouts need more than one provider.
+
+use rialo_weather_insurance::config::ContractConfig;
+use rialo_weather_insurance::consensus::{distinct_providers, median, needs_diverse_sources, spread};
+use rialo_weather_insurance::millimeters::Hundredths;
+use rialo_weather_insurance::money::Ralo;
+use rialo_weather_insurance::providers::ProviderKind;
+
+#[test]
+fn one_bad_source_cant_move_the_median() {
+    assert_eq!(median(&[Hundredths(400), Hundredths(25_000), Hundredths(500)]), Some(Hundredths(500)));
+    assert_eq!(median(&[Hundredths(0), Hundredths(400), Hundredths(500)]), Some(Hundredths(400)));
+    assert_eq!(median(&[Hundredths(750)]), Some(Hundredths(750)));
+    assert_eq!(median(&[]), None);
+}
+
+#[test]
+fn two_sources_settle_halfway() {
+    assert_eq!(median(&[Hundredths(600), Hundredths(400)]), Some(Hundredths(500)));
+    assert_eq!(median(&[Hundredths(601), Hundredths(400)]), Some(Hundredths(500)));
+}
+
+#[test]
+fn spread_is_the_gap_between_the_extremes() {
+    assert_eq!(spread(&[Hundredths(400), Hundredths(25_000), Hundredths(500)]), Hundredths(24_600));
+    assert_eq!(spread(&[Hundredths(300)]), Hundredths::ZERO);
+    assert_eq!(spread(&[]), Hundredths::ZERO);
+}
+
+#[test]
+fn mirrors_of_one_provider_count_once() {
+    assert_eq!(distinct_providers(&[ProviderKind::OpenWeatherMap, ProviderKind::OpenWeatherMap]), 1);
+    assert_eq!(distinct_providers(&[ProviderKind::OpenWeatherMap, ProviderKind::WeatherApi, ProviderKind::OpenWeatherMap]), 2);
+}
+
+#[test]
+fn only_payouts_above_the_line_need_diverse_sources() {
+    let mut config = ContractConfig::default();
+    assert!(!needs_diverse_sources(&config, Ralo::whole(1_000)));
+
+    config.diversity_above = Some(Ralo::whole(100));
+    assert!(!needs_diverse_sources(&config, Ralo::whole(100)));
+    assert!(needs_diverse_sources(&config, Ralo::whole(101)));
+}
@@ -0,0 +1,70 @@
+// Co-payment: the share of each calculated payout the customer absorbs.
+
+use rialo_sdk::prelude::Pubkey;
+use rialo_weather_insurance::copay::{net, retained, take_copay};
+use rialo_weather_insurance::millimeters::Hundredths;
+use rialo_weather_insurance::money::Ralo;
+use rialo_weather_insurance::policy::Policy;
+
+#[test]
+fn copay_splits_each_payout_without_losing_a_unit() {
+    assert_eq!(retained(Ralo::whole(100), 2_000), Ralo::whole(20));
+    assert_eq!(net(Ralo::whole(100), 2_000), Ralo::whole(80));
+
+    // The retained share rounds down, so the remainder goes to the customer
+    assert_eq!(retained(Ralo(999), 3_333), Ralo(332));
+    assert_eq!(net(Ralo(999), 3_333), Ralo(667));
+
+    for gross in [0u64, 1, 7, 999, 1_000_000_007] {
+        assert_eq!(retained(Ralo(gross), 1_234) + net(Ralo(gross), 1_234), Ralo(gross));
+    }
+}
+
+#[test]
+fn no_copay_pays_everything() {
+    assert_eq!(net(Ralo::whole(100), 0), Ralo::whole(100));
+    assert_eq!(retained(Ralo::whole(100), 0), Ralo::ZERO);
+}
+
+#[test]
+fn remaining_cover_is_net_of_the_copay() {
+    let mut policy = Policy::new(0, 0, Pubkey::default(), "nairobi".into(), Hundredths::from_f64(20.0), Ralo::whole(100), Ralo::whole(5));
+    policy.copay_bps = 2_500;
+    assert_eq!(policy.coverage_remaining(), Ralo::whole(75));
+
+    // A 40% tier pays 30 of its calculated 40
+    policy.paid_out = net(Ralo::whole(40), policy.copay_bps);
+    assert_eq!(policy.paid_out, Ralo::whole(30));
+    assert_eq!(policy.coverage_remaining(), Ralo::whole(45));
+}
+
+#[test]
+fn withdrawing_a_copay_policy_hands_back_exactly_the_reserve_it_took() {
+    let before = Ralo::whole(500);
+    let mut policy = Policy::new(0, 0, Pubkey::default(), "nairobi".into(), Hundredths::from_f64(20.0), Ralo::whole(100), Ralo::whole(5));
+
+    // Writing the policy reserves its whole cover; the co-pay releases the retained share
+    let mut reserved = before + policy.payout_amount;
+    reserved -= take_copay(&mut policy, 2_500, Ralo(1)).unwrap();
+    assert_eq!(reserved, before + Ralo::whole(75));
+
+    // Part of the premium comes in, then the owner withdraws: only the net cover is still held
+    policy.record_premium(Ralo::whole(2), 1_700_000_000);
+    assert_eq!(policy.refundable_premium(), Ralo::whole(2));
+    reserved -= policy.coverage_remaining();
+    assert_eq!(reserved, before);
+}
+
+#[test]
+fn a_renewal_quoted_at_the_same_price_takes_the_same_copay() {
+    let mut old = Policy::new(0, 0, Pubkey::default(), "nairobi".into(), Hundredths::from_f64(20.0), Ralo::whole(100), Ralo::whole(8));
+    take_copay(&mut old, 2_000, Ralo(1)).unwrap();
+
+    // The successor is written at the gross quote, then the co-pay carried over
+    let mut renewal = Policy::new(0, 0, Pubkey::default(), "nairobi".into(), Hundredths::from_f64(20.0), Ralo::whole(100), Ralo::whole(8));
+    let released = take_copay(&mut renewal, old.copay_bps, Ralo(1)).unwrap();
+
+    assert_eq!(released, Ralo::whole(20));
+    assert_eq!(renewal.premium_amount, old.premium_amount);
+    assert_eq!(renewal.coverage_remaining(), old.coverage_remaining());
+}
@@ -0,0 +1,116 @@
+// Graded payout curves: the share a reading is worth, and which curves a policy can be written on.
+
+use rialo_weather_insurance::curves::{PayoutCurve, PayoutTier};
+use rialo_weather_insurance::millimeters::Hundredths;
+use rialo_weather_insurance::normalization::Comparison;
+use rialo_weather_insurance::quote::QuoteError;
+
+fn hundredths(threshold: f64) -> Hundredths {
+    Hundredths::from_f64(threshold)
+}
+
+fn tiers() -> PayoutCurve {
+    PayoutCurve::Tiers(vec![
+        PayoutTier { at: hundredths(10.0), share_bps: 2_500 },
+        PayoutTier { at: hundredths(25.0), share_bps: 6_000 },
+        PayoutTier { at: hundredths(50.0), share_bps: 10_000 },
+    ])
+}
+
+#[test]
+fn flat_cover_pays_all_or_nothing() {
+    let flat = PayoutCurve::Flat;
+    assert_eq!(flat.share_bps(Comparison::AtOrAbove, hundredths(50.0), hundredths(49.99)), 0);
+    assert_eq!(flat.share_bps(Comparison::AtOrAbove, hundredths(50.0), hundredths(50.0)), 10_000);
+}
+
+#[test]
+fn tiers_pay_the_furthest_tier_reached() {
+    let curve = tiers();
+    assert_eq!(curve.share_bps(Comparison::AtOrAbove, hundredths(10.0), hundredths(9.99)), 0);
+    assert_eq!(curve.share_bps(Comparison::AtOrAbove, hundredths(10.0), hundredths(10.0)), 2_500);
+    assert_eq!(curve.share_bps(Comparison::AtOrAbove, hundredths(10.0), hundredths(30.0)), 6_000);
+    assert_eq!(curve.share_bps(Comparison::AtOrAbove, hundredths(10.0), hundredths(80.0)), 10_000);
+}
+
+#[test]
+fn linear_cover_pays_in_proportion() {
+    let curve = PayoutCurve::Linear { full_at: hundredths(60.0) };
+    assert_eq!(curve.share_bps(Comparison::AtOrAbove, hundredths(20.0), hundredths(19.0)), 0);
+    assert_eq!(curve.share_bps(Comparison::AtOrAbove, hundredths(20.0), hundredths(20.0)), 0);
+    assert_eq!(curve.share_bps(Comparison::AtOrAbove, hundredths(20.0), hundredths(30.0)), 2_500);
+    assert_eq!(curve.share_bps(Comparison::AtOrAbove, hundredths(20.0), hundredths(60.0)), 10_000);
+    assert_eq!(curve.share_bps(Comparison::AtOrAbove, hundredths(20.0), hundredths(95.0)), 10_000);
+}
+
+#[test]
+fn curves_grade_cover_that_pays_below_its_threshold() {
+    // Frost: nothing above 0°, all of it at -10° or colder
+    let curve = PayoutCurve::Linear { full_at: hundredths(-10.0) };
+    assert_eq!(curve.share_bps(Comparison::AtOrBelow, hundredths(0.0), hundredths(1.0)), 0);
+    assert_eq!(curve.share_bps(Comparison::AtOrBelow, hundredths(0.0), hundredths(-4.0)), 4_000);
+    assert_eq!(curve.share_bps(Comparison::AtOrBelow, hundredths(0.0), hundredths(-12.0)), 10_000);
+    assert_eq!(curve.validate(Comparison::AtOrBelow, hundredths(0.0)), Ok(()));
+}
+
+#[test]
+fn curves_must_climb_past_the_threshold() {
+    assert_eq!(tiers().validate(Comparison::AtOrAbove, hundredths(10.0)), Ok(()));
+    assert_eq!(PayoutCurve::Flat.validate(Comparison::AtOrAbove, hundredths(10.0)), Ok(()));
+
+    let invalid = [
+        // A tier short of the threshold
+        PayoutCurve::Tiers(vec![PayoutTier { at: hundredths(5.0), share_bps: 5_000 }]),
+        // Shares that fall as the rain gets worse
+        PayoutCurve::Tiers(vec![PayoutTier { at: hundredths(10.0), share_bps: 6_000 }, PayoutTier { at: hundredths(25.0), share_bps: 2_500 }]),
+        // Tiers out of order
+        PayoutCurve::Tiers(vec![PayoutTier { at: hundredths(25.0), share_bps: 2_500 }, PayoutTier { at: hundredths(10.0), share_bps: 6_000 }]),
+        PayoutCurve::Tiers(vec![PayoutTier { at: hundredths(20.0), share_bps: 10_001 }]),
+        PayoutCurve::Tiers(vec![PayoutTier { at: hundredths(20.0), share_bps: 0 }]),
+        PayoutCurve::Tiers(Vec::new()),
+        PayoutCurve::Linear { full_at: hundredths(10.0) },
+        PayoutCurve::Linear { full_at: hundredths(5.0) },
+    ];
+    for curve in invalid {
+        assert_eq!(curve.validate(Comparison::AtOrAbove, hundredths(10.0)), Err(QuoteError::InvalidPayoutCurve), "{curve:?}");
+    }
+}
+
+#[test]
+fn a_deductible_only_counts_rain_past_it() {
+    // Triggers at 30 mm; rain past 20 mm counts, all of it by 60 mm
+    let curve = PayoutCurve::Deductible { deductible: hundredths(20.0), full_at: hundredths(60.0) };
+    assert_eq!(curve.share_bps(Comparison::AtOrAbove, hundredths(30.0), hundredths(29.99)), 0);
+    assert_eq!(curve.share_bps(Comparison::AtOrAbove, hundredths(30.0), hundredths(30.0)), 2_500);
+    assert_eq!(curve.share_bps(Comparison::AtOrAbove, hundredths(30.0), hundredths(40.0)), 5_000);
+    assert_eq!(curve.share_bps(Comparison::AtOrAbove, hundredths(30.0), hundredths(60.0)), 10_000);
+    assert_eq!(curve.share_bps(Comparison::AtOrAbove, hundredths(30.0), hundredths(90.0)), 10_000);
+    assert_eq!(curve.validate(Comparison::AtOrAbove, hundredths(30.0)), Ok(()));
+}
+
+#[test]
+fn a_franchise_pays_every_millimetre_once_met() {
+    // Nothing under 20 mm; at 20 mm the first 20 count too
+    let curve = PayoutCurve::Franchise { full_at: hundredths(80.0) };
+    assert_eq!(curve.share_bps(Comparison::AtOrAbove, hundredths(20.0), hundredths(19.99)), 0);
+    assert_eq!(curve.share_bps(Comparison::AtOrAbove, hundredths(20.0), hundredths(20.0)), 2_500);
+    assert_eq!(curve.share_bps(Comparison::AtOrAbove, hundredths(20.0), hundredths(40.0)), 5_000);
+    assert_eq!(curve.share_bps(Comparison::AtOrAbove, hundredths(20.0), hundredths(80.0)), 10_000);
+    assert_eq!(curve.validate(Comparison::AtOrAbove, hundredths(20.0)), Ok(()));
+    assert_eq!(curve.validate(Comparison::AtOrBelow, hundredths(20.0)), Err(QuoteError::InvalidPayoutCurve));
+}
+
+#[test]
+fn a_deductible_must_sit_short_of_the_threshold() {
+    for deductible in [30.0, 45.0] {
+        let curve = PayoutCurve::Deductible { deductible: hundredths(deductible), full_at: hundredths(60.0) };
+        assert_eq!(curve.validate(Comparison::AtOrAbove, hundredths(30.0)), Err(QuoteError::InvalidDeductible), "{deductible}");
+    }
+    // Drought: the deductible sits above a pays-below threshold
+    let drought = PayoutCurve::Deductible { deductible: hundredths(10.0), full_at: hundredths(0.0) };
+    assert_eq!(drought.validate(Comparison::AtOrBelow, hundredths(5.0)), Ok(()));
+    assert_eq!(drought.share_bps(Comparison::AtOrBelow, hundredths(5.0), hundredths(5.0)), 5_000);
+
+    let short_ramp = PayoutCurve::Deductible { deductible: hundredths(20.0), full_at: hundredths(30.0) };
+    assert_eq!(short_ramp.validate(Comparison::AtOrAbove, hundredths(30.0)), Err(QuoteError::InvalidPayoutCurve));
+}
@@ -0,0 +1,40 @@
+// Dashboard views: payout previews worked out by the settlement code.
+
+use rialo_sdk::prelude::Pubkey;
+use rialo_weather_insurance::dashboard::preview;
+use rialo_weather_insurance::millimeters::Hundredths;
+use rialo_weather_insurance::money::Ralo;
+use rialo_weather_insurance::policy::Policy;
+
+const HOUR: i64 = 60 * 60;
+
+fn live_policy() -> Policy {
+    let mut policy = Policy::new(0, 0, Pubkey::default(), "nairobi".into(), Hundredths::from_f64(20.0), Ralo::whole(100), Ralo::whole(5));
+    policy.coverage_secs = 30 * 24 * HOUR;
+    policy.record_premium(Ralo::whole(5), 0);
+    policy
+}
+
+#[test]
+fn preview_pays_past_the_threshold_net_of_copay() {
+    let mut policy = live_policy();
+    policy.copay_bps = 2_000;
+
+    let dry = preview(&policy, Hundredths(1_200), HOUR);
+    assert!(!dry.triggers);
+    assert_eq!(dry.due, Ralo::ZERO);
+
+    let wet = preview(&policy, Hundredths(2_500), HOUR);
+    assert!(wet.triggers);
+    assert_eq!(wet.due, Ralo::whole(80));
+    assert_eq!(wet.copay, Ralo::whole(20));
+
+    // Previewing leaves the policy as it was
+    assert_eq!(policy.paid_out, Ralo::ZERO);
+}
+
+#[test]
+fn preview_pays_nothing_outside_cover() {
+    let policy = live_policy();
+    assert!(!preview(&policy, Hundredths(2_500), 31 * 24 * HOUR).triggers);
+}
@@ -0,0 +1,51 @@
+// Accumulating heating and cooling degree-days and reading the payout share off them.
+
+use rialo_weather_insurance::degree_days::{DegreeDayCover, DegreeDayIndex, DegreeDayTrigger};
+
+const HOUR: i64 = 60 * 60;
+
+fn cover(index: DegreeDayIndex, tick_bps: u64) -> DegreeDayCover {
+    DegreeDayCover::new(DegreeDayTrigger { index, base_c: 18.0, tick_bps })
+}
+
+#[test]
+fn each_index_counts_only_its_own_side_of_the_base() {
+    assert_eq!(DegreeDayIndex::Heating.degrees(6.0, 18.0), 12.0);
+    assert_eq!(DegreeDayIndex::Heating.degrees(25.0, 18.0), 0.0);
+    assert_eq!(DegreeDayIndex::Cooling.degrees(25.0, 18.0), 7.0);
+    assert_eq!(DegreeDayIndex::Cooling.degrees(6.0, 18.0), 0.0);
+}
+
+#[test]
+fn a_day_of_hourly_readings_adds_its_mean_degrees() {
+    let mut cover = cover(DegreeDayIndex::Heating, 100);
+    for hour in 0..24 {
+        cover.record(if hour < 12 { 8.0 } else { 12.0 }, hour * HOUR);
+    }
+
+    // 12 hours 10° under the base, 12 hours 6° under: a mean of 8°
+    assert!((cover.degree_days - 8.0).abs() < 1e-9);
+    assert_eq!(cover.hours, 24);
+}
+
+#[test]
+fn pays_by_the_tick_beyond_the_strike_up_to_the_full_payout() {
+    let mut cover = cover(DegreeDayIndex::Cooling, 500);
+    cover.degree_days = 9.5;
+    assert_eq!(cover.share_bps(10.0), 0);
+
+    cover.degree_days = 13.0;
+    assert_eq!(cover.share_bps(10.0), 1_500);
+
+    cover.degree_days = 40.0;
+    assert_eq!(cover.share_bps(10.0), 10_000);
+}
+
+#[test]
+fn each_hour_counts_once() {
+    let mut cover = cover(DegreeDayIndex::Heating, 100);
+    cover.record(0.0, HOUR + 60);
+
+    assert!(!cover.can_check_at(2 * HOUR - 1));
+    assert!(cover.can_check_at(2 * HOUR));
+}
@@ -0,0 +1,26 @@
+// Dispute windows: which rounds have come through, and what a waiting claim still owes.
+
+use rialo_sdk::prelude::*;
+use rialo_weather_insurance::concentration::outstanding_exposure;
+use rialo_weather_insurance::disputes::DisputeCover;
+use rialo_weather_insurance::millimeters::Hundredths;
+use rialo_weather_insurance::money::Ralo;
+use rialo_weather_insurance::policy::{Policy, PolicyStatus};
+
+#[test]
+fn a_cleared_window_covers_only_its_own_round() {
+    let mut cover = DisputeCover::new(3_600);
+    assert!(!cover.is_cleared(Some(4)));
+
+    cover.cleared.push(Some(4));
+    assert!(cover.is_cleared(Some(4)));
+    assert!(!cover.is_cleared(Some(5)));
+    assert!(!cover.is_cleared(None));
+}
+
+#[test]
+fn a_claim_in_its_window_still_counts_as_exposure() {
+    let mut policy = Policy::new(0, 0, Pubkey::default(), "nairobi".into(), Hundredths::from_f64(30.0), Ralo::whole(100), Ralo::whole(5));
+    policy.status = PolicyStatus::PendingPayout;
+    assert_eq!(outstanding_exposure(&policy), Ralo::whole(100));
+}
@@ -0,0 +1,36 @@
+// Dual control: which withdrawals need a second key, and what their sign-offs collect under.
+
+use std::collections::BTreeMap;
+
+use rialo_sdk::prelude::Pubkey;
+use rialo_weather_insurance::approvals::approve;
+use rialo_weather_insurance::dual_control::{needs_dual_control, withdrawal_subject};
+use rialo_weather_insurance::money::Ralo;
+use rialo_weather_insurance::underwriter::WithdrawalRequest;
+
+fn request(amount: u64, requested_at: i64) -> WithdrawalRequest {
+    WithdrawalRequest { amount: Ralo::whole(amount), requested_at, executable_at: requested_at }
+}
+
+#[test]
+fn only_withdrawals_at_or_above_the_amount_need_two_keys() {
+    let above = Some(Ralo::whole(1_000));
+
+    assert!(!needs_dual_control(above, Ralo::whole(999)));
+    assert!(needs_dual_control(above, Ralo::whole(1_000)));
+    assert!(!needs_dual_control(None, Ralo::whole(1_000_000)));
+}
+
+#[test]
+fn sign_offs_are_per_request_and_per_key() {
+    // A re-sized or re-requested withdrawal starts from zero
+    let subject = withdrawal_subject(0, &request(5_000, 100));
+    assert_ne!(subject, withdrawal_subject(0, &request(5_001, 100)));
+    assert_ne!(subject, withdrawal_subject(0, &request(5_000, 101)));
+    assert_ne!(subject, withdrawal_subject(1, &request(5_000, 100)));
+
+    // The same key can't sign off twice
+    let mut approvals = BTreeMap::new();
+    assert_eq!(approve(&mut approvals, subject, Pubkey::default(), 100).unwrap(), 1);
+    assert!(approve(&mut approvals, subject, Pubkey::default(), 101).is_err());
+}
@@ -0,0 +1,40 @@
+// Contract errors: the codes frontends match on, and what folds into them.
+
+use rialo_sdk::prelude::RialoError;
+use rialo_weather_insurance::errors::InsuranceError;
+use rialo_weather_insurance::millimeters::Hundredths;
+use rialo_weather_insurance::money::Ralo;
+use rialo_weather_insurance::quote::QuoteError;
+use rialo_weather_insurance::resilience::FetchError;
+use rialo_weather_insurance::throttle::{CheckThrottled, CHECK_THROTTLED_CODE};
+
+#[test]
+fn codes_stay_where_they_shipped() {
+    assert_eq!(InsuranceError::UnknownPolicy.code(), 6_200);
+    assert_eq!(InsuranceError::AlreadyPaidOut.code(), 6_220);
+    assert_eq!(InsuranceError::PolicyExpired.code(), 6_222);
+    assert_eq!(InsuranceError::BelowMinThreshold { min: Hundredths(500) }.code(), 6_230);
+    assert_eq!(InsuranceError::PayoutCapExceeded { max: Ralo::whole(1_000) }.code(), 6_231);
+    assert_eq!(InsuranceError::VaultInsolvent("Vault is short.".into()).code(), 6_232);
+    assert_eq!(InsuranceError::HttpError(503).code(), 6_240);
+    assert_eq!(InsuranceError::InvalidState("Not now.".into()).code(), 6_250);
+}
+
+#[test]
+fn typed_errors_keep_their_own_codes() {
+    let quote = QuoteError::PremiumBelowRate;
+    assert_eq!(InsuranceError::from(quote.clone()).code(), quote.code());
+
+    let throttled = CheckThrottled { next_check_at: 1_700_000_600 };
+    assert_eq!(InsuranceError::from(throttled).code(), CHECK_THROTTLED_CODE);
+
+    assert_eq!(InsuranceError::from(FetchError::BadStatus(502)), InsuranceError::HttpError(502));
+    assert_eq!(InsuranceError::from(FetchError::Timeout), InsuranceError::ProviderTimeout);
+}
+
+#[test]
+fn contract_errors_carry_code_and_detail() {
+    let error: RialoError = InsuranceError::Unauthorized("Only the admin can pause.".into()).into();
+    assert_eq!(error.code, 6_210);
+    assert_eq!(error.message, "Only the admin can pause.");
+}
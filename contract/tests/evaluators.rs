@@ -0,0 +1,37 @@
+// Custom evaluator answers: pinned to the reviewed build, shares capped at the full payout.
+
+use rialo_sdk::prelude::Pubkey;
+use rialo_weather_insurance::evaluators::{accept_evaluation, CustomEvaluator, EvaluatorCover};
+use rialo_weather_insurance::millimeters::Hundredths;
+use rialo_weather_insurance::money::Ralo;
+use rialo_weather_insurance::policy::Policy;
+
+fn evaluator() -> CustomEvaluator {
+    CustomEvaluator { underwriter_id: 0, url: "https://eval.example.com".into(), code_hash: [7; 32], approved: true }
+}
+
+fn answer(code_hash: [u8; 32], payout_bps: u64) -> Vec<u8> {
+    serde_json::to_vec(&serde_json::json!({ "code_hash": code_hash, "payout_bps": payout_bps })).unwrap()
+}
+
+#[test]
+fn answers_from_the_approved_build_are_accepted() {
+    assert_eq!(accept_evaluation(&evaluator(), &answer([7; 32], 2_500)).unwrap(), 2_500);
+    assert_eq!(accept_evaluation(&evaluator(), &answer([7; 32], 10_000)).unwrap(), 10_000);
+}
+
+#[test]
+fn answers_from_another_build_or_above_the_payout_are_refused() {
+    assert!(accept_evaluation(&evaluator(), &answer([8; 32], 2_500)).is_err());
+    assert!(accept_evaluation(&evaluator(), &answer([7; 32], 10_001)).is_err());
+    assert!(accept_evaluation(&evaluator(), b"not json").is_err());
+}
+
+#[test]
+fn evaluated_policies_stay_out_of_rainfall_settlement() {
+    let mut policy = Policy::new(0, 0, Pubkey::default(), "nairobi".into(), Hundredths::from_f64(10.0), Ralo::whole(100), Ralo::whole(5));
+    policy.evaluator = Some(EvaluatorCover::new(3));
+
+    assert!(!policy.is_weather_cover());
+    assert!(!policy.settles_on_readings());
+}
@@ -0,0 +1,56 @@
+// Parsing One Call snapshots and accumulating heat-hours.
+
+use rialo_weather_insurance::exposure::{parse_exposure_snapshot, ExposureCover, ExposureIndex, ExposureSnapshot, ExposureTrigger};
+
+const HOUR: i64 = 60 * 60;
+
+fn feed(temp: f64, uvi: f64, max: f64) -> String {
+    format!(
+        r#"{{ "lat": 0.3476, "lon": 32.5825, "timezone": "Africa/Kampala",
+            "current": {{ "dt": 1710000000, "temp": {temp}, "feels_like": 38.1, "humidity": 30, "uvi": {uvi} }},
+            "daily": [ {{ "dt": 1709978400, "temp": {{ "min": 21.4, "max": {max} }}, "uvi": 11.2 }} ] }}"#
+    )
+}
+
+fn snapshot(temperature_c: f64, uv_index: f64) -> ExposureSnapshot {
+    ExposureSnapshot { temperature_c, daily_max_c: temperature_c, uv_index }
+}
+
+#[test]
+fn reads_temperature_uv_and_daily_max() {
+    let snapshot = parse_exposure_snapshot(feed(36.2, 10.4, 38.9).as_bytes()).unwrap();
+    assert_eq!(snapshot, ExposureSnapshot { temperature_c: 36.2, daily_max_c: 38.9, uv_index: 10.4 });
+}
+
+#[test]
+fn rejects_missing_daily_forecast() {
+    let body = r#"{ "current": { "temp": 30.0, "uvi": 5.0 }, "daily": [] }"#;
+    assert!(parse_exposure_snapshot(body.as_bytes()).is_err());
+}
+
+#[test]
+fn hours_accumulate_without_being_consecutive() {
+    let mut cover = ExposureCover::new(ExposureTrigger { index: ExposureIndex::AirTemperature, hours: 3 });
+
+    assert!(!cover.record(&snapshot(36.0, 4.0), 35.0, 0));
+    assert!(!cover.record(&snapshot(31.0, 4.0), 35.0, HOUR));   // a cooler hour doesn't reset
+    assert!(!cover.record(&snapshot(35.0, 4.0), 35.0, 2 * HOUR));
+    assert!(cover.record(&snapshot(37.5, 4.0), 35.0, 5 * HOUR));
+    assert_eq!(cover.hours, 3);
+}
+
+#[test]
+fn uv_products_read_the_uv_index() {
+    let mut cover = ExposureCover::new(ExposureTrigger { index: ExposureIndex::UvIndex, hours: 1 });
+    assert!(!cover.record(&snapshot(40.0, 7.9), 8.0, 0));
+    assert!(cover.record(&snapshot(25.0, 11.0), 8.0, HOUR));
+}
+
+#[test]
+fn each_hour_counts_once() {
+    let mut cover = ExposureCover::new(ExposureTrigger { index: ExposureIndex::AirTemperature, hours: 2 });
+    cover.record(&snapshot(36.0, 0.0), 35.0, HOUR + 60);
+
+    assert!(!cover.can_snapshot_at(2 * HOUR - 1));
+    assert!(cover.can_snapshot_at(2 * HOUR));
+}
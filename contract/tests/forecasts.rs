@@ -0,0 +1,52 @@
+// Forecast confirmation: reading both providers' forecasts and which
+// exceedances a recorded forecast backs.
+
+use rialo_weather_insurance::forecasts::{ForecastCover, ForecastSlot};
+use rialo_weather_insurance::providers::{ProviderKind, WeatherProvider};
+
+const HOUR: i64 = 60 * 60;
+
+fn slot(start: i64, chance: u64) -> ForecastSlot {
+    ForecastSlot { start, secs: 3 * HOUR, rain_chance_bps: chance }
+}
+
+#[test]
+fn both_providers_forecasts_parse_to_slots() {
+    let owm = br#"{ "list": [ { "dt": 10800, "pop": 0.62 }, { "dt": 21600, "pop": 0 } ] }"#;
+    assert_eq!(ProviderKind::OpenWeatherMap.parse_forecast(owm).unwrap(), vec![slot(3 * HOUR, 6_200), slot(6 * HOUR, 0)]);
+
+    let weatherapi = br#"{ "forecast": { "forecastday": [ { "hour": [
+        { "time_epoch": 3600, "precip_mm": 0.0, "chance_of_rain": 85 },
+        { "time_epoch": 7200, "precip_mm": 0.0 }
+    ] } ] } }"#;
+    assert_eq!(
+        ProviderKind::WeatherApi.parse_forecast(weatherapi).unwrap(),
+        vec![ForecastSlot { start: HOUR, secs: HOUR, rain_chance_bps: 8_500 }],
+    );
+    assert!(ProviderKind::OpenWeatherMap.parse_forecast(b"{}").is_err());
+}
+
+#[test]
+fn only_a_forecast_taken_beforehand_confirms() {
+    let mut cover = ForecastCover::new(6_000);
+    assert!(!cover.confirms(4 * HOUR));
+
+    // Taken mid-slot: the slot already under way isn't kept
+    assert_eq!(cover.record(&[slot(0, 9_000), slot(3 * HOUR, 7_000), slot(6 * HOUR, 2_000)], HOUR), 2);
+    assert!(!cover.confirms(HOUR));
+    assert!(cover.confirms(4 * HOUR));
+    assert!(!cover.confirms(7 * HOUR));
+    assert_eq!(cover.chance_at(7 * HOUR), Some(2_000));
+}
+
+#[test]
+fn a_newer_forecast_replaces_only_the_slots_still_to_come() {
+    let mut cover = ForecastCover::new(6_000);
+    cover.record(&[slot(3 * HOUR, 7_000), slot(6 * HOUR, 2_000)], 0);
+    cover.record(&[slot(6 * HOUR, 8_000), slot(9 * HOUR, 1_000)], 4 * HOUR);
+
+    assert!(cover.confirms(4 * HOUR));
+    assert!(cover.confirms(7 * HOUR));
+    assert!(!cover.confirms(10 * HOUR));
+    assert_eq!(cover.slots.len(), 3);
+}
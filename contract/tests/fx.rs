@@ -0,0 +1,51 @@
+// RALO/USD prints, the TWAP and the slippage guard on USD payouts.
+
+use rialo_weather_insurance::fx::{convert, usd_to_ralo, ConversionError, PriceHistory, PricePoint, PRICE_TWAP_SECS};
+use rialo_weather_insurance::money::Ralo;
+
+const NOW: i64 = 1_710_000_000;
+const MINUTE: i64 = 60;
+
+fn history(prints: &[(u64, i64)]) -> PriceHistory {
+    let mut history = PriceHistory::default();
+    for &(micro_usd, observed_at) in prints {
+        assert!(history.record(PricePoint { micro_usd, observed_at }));
+    }
+    history
+}
+
+#[test]
+fn usd_converts_at_the_print() {
+    // $50.00 at $0.25 a token is 200 tokens
+    assert_eq!(usd_to_ralo(5_000, 250_000), Ralo::whole(200));
+}
+
+#[test]
+fn twap_weights_prints_by_how_long_they_stood() {
+    // $0.20 for 45 minutes, then $0.40 for the last 15
+    let history = history(&[(200_000, NOW - PRICE_TWAP_SECS), (400_000, NOW - 15 * MINUTE)]);
+    assert_eq!(history.twap(NOW), Some(250_000));
+    // Prints closer together than a minute aren't kept
+    let mut history = history;
+    assert!(!history.record(PricePoint { micro_usd: 1, observed_at: NOW - 15 * MINUTE + 30 }));
+}
+
+#[test]
+fn wild_spot_print_is_refused_until_the_price_settles() {
+    let steady = [(250_000, NOW - 50 * MINUTE), (250_000, NOW - 30 * MINUTE), (250_000, NOW - 2 * MINUTE)];
+    let mut prints = steady.to_vec();
+    prints.push((500_000, NOW - MINUTE));
+    let spiked = history(&prints);
+
+    match convert(&spiked, 5_000, 500, NOW) {
+        Err(ConversionError::Slippage { spot, twap }) => {
+            assert_eq!(spot, 500_000);
+            assert!(twap < 260_000);
+        }
+        other => panic!("expected a slippage refusal, got {other:?}"),
+    }
+
+    // A stale newest print isn't spot at all
+    assert_eq!(convert(&history(&steady), 5_000, 500, NOW + 10 * MINUTE), Err(ConversionError::NoPrice));
+    assert_eq!(convert(&history(&steady), 5_000, 500, NOW), Ok(Ralo::whole(200)));
+}
@@ -0,0 +1,92 @@
+// Fixed-point haversine checked against a double-precision reference.
+// The contract never runs the f64 version; it only anchors the tests.
+
+use rialo_weather_insurance::geo::{geohash, haversine_km, is_geohash, within_km, GeoPoint, KM_SCALE};
+
+fn point(lat: f64, lon: f64) -> GeoPoint {
+    GeoPoint::new((lat * 1e6).round() as i32, (lon * 1e6).round() as i32).unwrap()
+}
+
+// Reference distance in metres, same Earth radius as the contract
+fn reference_m(a: &GeoPoint, b: &GeoPoint) -> f64 {
+    let rad = |e6: i32| (e6 as f64 / 1e6).to_radians();
+    let (lat1, lat2) = (rad(a.lat_e6), rad(b.lat_e6));
+    let d_lat = lat2 - lat1;
+    let d_lon = rad(b.lon_e6) - rad(a.lon_e6);
+    let h = (d_lat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (d_lon / 2.0).sin().powi(2);
+    2.0 * 6_371_009.0 * h.sqrt().asin()
+}
+
+fn assert_close(a: GeoPoint, b: GeoPoint) {
+    let fixed = haversine_km(&a, &b) as f64;
+    let reference = reference_m(&a, &b);
+    assert!((fixed - reference).abs() <= 1.0, "fixed {fixed} m vs reference {reference} m");
+}
+
+#[test]
+fn same_point_is_zero() {
+    let nairobi = point(-1.286389, 36.817223);
+    assert_eq!(haversine_km(&nairobi, &nairobi), 0);
+}
+
+#[test]
+fn matches_reference_on_known_routes() {
+    assert_close(point(-1.286389, 36.817223), point(-4.043477, 39.668206));   // Nairobi – Mombasa
+    assert_close(point(51.507400, -0.127800), point(48.856600, 2.352200));    // London – Paris
+    assert_close(point(6.524379, 3.379206), point(5.603717, -0.186964));      // Lagos – Accra
+    assert_close(point(35.689487, 139.691711), point(-33.868820, 151.209290)); // Tokyo – Sydney
+}
+
+#[test]
+fn short_distances_keep_precision() {
+    assert_close(point(-1.286389, 36.817223), point(-1.286389, 36.818223));
+    assert_close(point(-1.286389, 36.817223), point(-1.295389, 36.817223));
+}
+
+#[test]
+fn crosses_the_antimeridian() {
+    assert_close(point(-17.713371, 178.065033), point(-13.759029, -172.104629));   // Fiji – Samoa
+}
+
+#[test]
+fn antipodes_are_half_the_circumference() {
+    let d = haversine_km(&point(0.0, 0.0), &point(0.0, 180.0));
+    assert_eq!(d / KM_SCALE, 20_015);
+}
+
+#[test]
+fn symmetric() {
+    let a = point(-26.204103, 28.047305);
+    let b = point(-33.924870, 18.424055);
+    assert_eq!(haversine_km(&a, &b), haversine_km(&b, &a));
+}
+
+#[test]
+fn radius_check_is_inclusive() {
+    let insured = point(0.0, 0.0);
+    let storm   = point(0.0, 1.0);             // ~111.2 km east along the equator
+    assert!(within_km(&insured, &storm, 112));
+    assert!(!within_km(&insured, &storm, 111));
+}
+
+#[test]
+fn rejects_out_of_range_coordinates() {
+    assert!(GeoPoint::new(90_000_001, 0).is_err());
+    assert!(GeoPoint::new(0, -180_000_001).is_err());
+}
+
+#[test]
+fn geohash_matches_reference_encodings() {
+    assert_eq!(geohash(&point(42.6, -5.6), 5), "ezs42");
+    assert_eq!(geohash(&point(57.64911, 10.40744), 11), "u4pruydqqvj");
+    assert_eq!(geohash(&point(-1.286389, 36.817223), 5), "kzf0t");
+}
+
+#[test]
+fn geohash_prefixes_nest() {
+    let depot = point(-1.286389, 36.817223);
+    assert!(geohash(&depot, 9).starts_with(&geohash(&depot, 4)));
+    assert!(is_geohash("kzf0t"));
+    assert!(!is_geohash("kzf0a"));   // 'a' is not in the alphabet
+    assert!(!is_geohash(""));
+}
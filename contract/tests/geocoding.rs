@@ -0,0 +1,74 @@
+// Geocoder requests and responses, when a cached fix is reused, and location keys.
+
+use rialo_weather_insurance::geo::{geohash, GeoPoint};
+use rialo_weather_insurance::geocoding::Geocode;
+use rialo_weather_insurance::normalization::{geohashed_slug, location_slug, Location};
+use rialo_weather_insurance::providers::{ProviderKind, WeatherProvider};
+
+const DAY: i64 = 24 * 60 * 60;
+
+fn nairobi() -> Geocode {
+    Geocode {
+        point:       GeoPoint::new(-1_286_389, 36_817_223).unwrap(),
+        source:      "https://api.test".into(),
+        resolved_at: 1_000 * DAY,
+    }
+}
+
+#[test]
+fn both_providers_geocode_to_their_best_match() {
+    let body = br#"[{ "name": "Nairobi", "lat": -1.286389, "lon": 36.817223 }, { "name": "Nairobi Hill", "lat": -1.3, "lon": 36.8 }]"#;
+    for kind in [ProviderKind::OpenWeatherMap, ProviderKind::WeatherApi] {
+        assert_eq!(kind.parse_geocode(body).unwrap(), nairobi().point);
+    }
+
+    assert_eq!(
+        ProviderKind::OpenWeatherMap.geocode_request("https://api.test", "k", "nairobi"),
+        "https://api.test/geo/1.0/direct?q=nairobi&limit=1&appid=k",
+    );
+    assert_eq!(
+        ProviderKind::WeatherApi.geocode_request("https://api.test", "k", "nairobi"),
+        "https://api.test/v1/search.json?key=k&q=nairobi",
+    );
+}
+
+#[test]
+fn unknown_or_impossible_places_are_errors() {
+    let kind = ProviderKind::OpenWeatherMap;
+    assert!(kind.parse_geocode(b"[]").is_err());
+    assert!(kind.parse_geocode(br#"[{ "lat": 91.0, "lon": 0.0 }]"#).is_err());
+    assert!(kind.parse_geocode(b"not json").is_err());
+}
+
+#[test]
+fn a_fix_is_reused_only_while_young_and_from_the_same_source() {
+    let fix = nairobi();
+    let ttl = 30 * DAY;
+
+    assert!(fix.is_fresh("https://api.test", fix.resolved_at + ttl, ttl));
+    assert!(!fix.is_fresh("https://api.test", fix.resolved_at + ttl + 1, ttl));
+    assert!(!fix.is_fresh("https://sandbox.test", fix.resolved_at, ttl));
+}
+
+#[test]
+fn spellings_of_one_name_share_a_slug() {
+    for spelling in ["Nairobi", "  nairobi ", "NAIROBI", "Nair%C3%B3bi", "Nairóbi"] {
+        assert_eq!(location_slug(spelling), "nairobi");
+    }
+    assert_eq!(location_slug("São Paulo, BR"), "sao-paulo-br");
+    assert_eq!(location_slug("New%20York"), "new-york");
+    assert_eq!(location_slug("100%"), "100");
+    assert_eq!(Location::City("  New   York ".into()).key(), "new-york");
+}
+
+#[test]
+fn geohash_keys_replace_the_country_qualifier() {
+    let point = nairobi().point;
+    let key = format!("nairobi@{}", geohash(&point, 5));
+    assert_eq!(geohashed_slug("Nairobi, KE", &point, 5), key);
+    assert_eq!(geohashed_slug("nairobi", &point, 5), key);
+
+    // Only a two-letter code is a country; anything else is part of the name
+    let springfield = GeoPoint::new(39_801_700, -89_643_700).unwrap();
+    assert_eq!(geohashed_slug("Springfield, Illinois", &springfield, 5), format!("springfield-illinois@{}", geohash(&springfield, 5)));
+}
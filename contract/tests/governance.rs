@@ -0,0 +1,35 @@
+// Governance votes: quorum of all stake, then a simple majority of the stake that voted.
+
+use rialo_weather_insurance::governance::{ParameterChange, Proposal};
+use rialo_weather_insurance::money::Ralo;
+
+fn proposal(votes_for: u64, votes_against: u64) -> Proposal {
+    Proposal {
+        change:        ParameterChange::PolicyFloors { min_premium: Ralo::whole(1), min_payout: Ralo::whole(10) },
+        proposer:      0,
+        voting_ends:   0,
+        executable_at: 0,
+        votes_for:     Ralo::whole(votes_for),
+        votes_against: Ralo::whole(votes_against),
+        voters:        Vec::new(),
+        executed:      false,
+    }
+}
+
+#[test]
+fn proposals_need_quorum_of_all_stake() {
+    let total_stake = Ralo::whole(1_000);
+
+    // 30% quorum: 250 RALO turning out is not enough, 300 is
+    assert!(!proposal(250, 0).passes(total_stake, 3_000));
+    assert!(proposal(200, 100).passes(total_stake, 3_000));
+}
+
+#[test]
+fn ties_and_majorities_against_fail() {
+    let total_stake = Ralo::whole(1_000);
+
+    assert!(!proposal(300, 300).passes(total_stake, 3_000));
+    assert!(!proposal(200, 400).passes(total_stake, 3_000));
+    assert!(proposal(301, 300).passes(total_stake, 3_000));
+}
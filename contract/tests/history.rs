@@ -0,0 +1,55 @@
+// Claims history: reading a policy's ledger of checks a page at a time.
+
+use std::collections::BTreeMap;
+
+use rialo_weather_insurance::evaluation::Evaluation;
+use rialo_weather_insurance::history::{claims_history_page, CLAIMS_PAGE_SIZE};
+use rialo_weather_insurance::millimeters::Hundredths;
+use rialo_weather_insurance::money::Ralo;
+use rialo_weather_insurance::normalization::Station;
+use rialo_weather_insurance::observations::{CheckId, CheckRecord};
+
+fn check(reading: f64, observed_at: i64, decision: Evaluation) -> CheckRecord {
+    CheckRecord {
+        policy_id:      7,
+        source:         "https://api.openweathermap.org".into(),
+        location:       "nairobi".into(),
+        station:        Station::default(),
+        response:       None,
+        reading:        Hundredths::from_f64(reading),
+        old_reading:    None,
+        observed_at,
+        decision,
+        audit_selected: false,
+        audit:          None,
+    }
+}
+
+#[test]
+fn entries_say_what_each_check_decided_and_paid() {
+    let mut checks = BTreeMap::new();
+    checks.insert(3, check(12.0, 3_600, Evaluation::NotMet));
+    checks.insert(9, check(64.0, 7_200, Evaluation::Triggered { paid: Ralo::whole(100) }));
+
+    let page = claims_history_page(7, &[3, 9], &checks, 0);
+    assert_eq!((page.total, page.pages), (2, 1));
+    assert_eq!(page.entries.iter().map(|e| e.check_id).collect::<Vec<_>>(), vec![3, 9]);
+    assert_eq!(page.entries[0].paid, Ralo::ZERO);
+    assert_eq!(page.entries[1].decision, Evaluation::Triggered { paid: Ralo::whole(100) });
+    assert_eq!(page.entries[1].paid, Ralo::whole(100));
+}
+
+#[test]
+fn long_ledgers_page_oldest_first() {
+    let ledger: Vec<CheckId> = (0..CLAIMS_PAGE_SIZE as u64 + 3).collect();
+    let checks: BTreeMap<_, _> = ledger.iter().map(|&id| (id, check(1.0, id as i64, Evaluation::NotMet))).collect();
+
+    let first = claims_history_page(7, &ledger, &checks, 0);
+    assert_eq!(first.pages, 2);
+    assert_eq!(first.entries.len(), CLAIMS_PAGE_SIZE);
+    assert_eq!(first.entries[0].check_id, 0);
+
+    let last = claims_history_page(7, &ledger, &checks, 1);
+    assert_eq!(last.entries.iter().map(|e| e.check_id).collect::<Vec<_>>(), vec![25, 26, 27]);
+    assert!(claims_history_page(7, &ledger, &checks, 2).entries.is_empty());
+}
@@ -0,0 +1,33 @@
+// Outage time measured against a policy's coverage window.
+
+use rialo_weather_insurance::impairment::{outage_bps, outage_secs};
+use rialo_weather_insurance::incidents::DataIncident;
+
+const PROVIDER: &str = "https://api.openweathermap.org";
+const HOUR: i64 = 60 * 60;
+
+fn incident(source: &str, start: i64, end: Option<i64>) -> DataIncident {
+    DataIncident { source: source.to_string(), start, end }
+}
+
+#[test]
+fn overlapping_incidents_count_once() {
+    let incidents = [
+        incident(PROVIDER, 2 * HOUR, Some(6 * HOUR)),
+        incident(PROVIDER, 4 * HOUR, Some(8 * HOUR)),
+        incident("https://other.example", 0, Some(10 * HOUR)),
+    ];
+    assert_eq!(outage_secs(&incidents, PROVIDER, 0, 10 * HOUR), 6 * HOUR);
+    assert_eq!(outage_bps(&incidents, PROVIDER, 0, 10 * HOUR), 6_000);
+}
+
+#[test]
+fn outage_is_clipped_to_the_window() {
+    // Started before cover and still open at expiry
+    let incidents = [incident(PROVIDER, -5 * HOUR, None)];
+    assert_eq!(outage_secs(&incidents, PROVIDER, 0, 10 * HOUR), 10 * HOUR);
+    assert_eq!(outage_bps(&incidents, PROVIDER, 0, 10 * HOUR), 10_000);
+    // Over before cover began
+    let incidents = [incident(PROVIDER, -5 * HOUR, Some(0))];
+    assert_eq!(outage_bps(&incidents, PROVIDER, 0, 10 * HOUR), 0);
+}
@@ -0,0 +1,26 @@
+// Keeper rewards: what a live check earns its caller.
+
+use rialo_weather_insurance::keepers::{keeper_reward, KeeperRewards, TriggerReward};
+use rialo_weather_insurance::money::Ralo;
+
+fn rewards(trigger: TriggerReward) -> KeeperRewards {
+    KeeperRewards { trigger, check_fee: Ralo::whole(1).bps(100), fee_cap_bps: 1_000 }
+}
+
+#[test]
+fn a_trigger_earns_the_flat_reward_or_its_share_of_the_payout() {
+    let paid = Ralo::whole(100);
+    assert_eq!(keeper_reward(&rewards(TriggerReward::Flat(Ralo::whole(2))), paid, Ralo::whole(5), Ralo::ZERO), Ralo::whole(2));
+    assert_eq!(keeper_reward(&rewards(TriggerReward::PayoutBps(50)), paid, Ralo::whole(5), Ralo::ZERO), Ralo::whole(1).bps(5_000));
+}
+
+#[test]
+fn quiet_checks_earn_the_fee_until_the_premium_cap() {
+    let rewards = rewards(TriggerReward::PayoutBps(50));
+    let premium = Ralo::whole(1);   // fees stop at 0.1 RALO
+    let fee = Ralo::whole(1).bps(100);
+
+    assert_eq!(keeper_reward(&rewards, Ralo::ZERO, premium, Ralo::ZERO), fee);
+    assert_eq!(keeper_reward(&rewards, Ralo::ZERO, premium, fee.times(9) + fee.bps(5_000)), fee.bps(5_000));
+    assert_eq!(keeper_reward(&rewards, Ralo::ZERO, premium, fee.times(10)), Ralo::ZERO);
+}
@@ -0,0 +1,52 @@
+// Provider key pools: round-robin draws, daily quotas, and resting or disabling rejected keys.
+
+use rialo_weather_insurance::keys::{KeyPool, PooledKey};
+
+const DAY: i64 = 24 * 60 * 60;
+const NOON: i64 = 19_700 * DAY + DAY / 2;
+
+fn pool(quotas: &[u32]) -> KeyPool {
+    KeyPool {
+        keys: quotas.iter().enumerate().map(|(i, q)| PooledKey::new(format!("key-{i}"), *q)).collect(),
+        next: 0,
+    }
+}
+
+#[test]
+fn draws_rotate_and_skip_spent_keys() {
+    let mut pool = pool(&[1, 2]);
+
+    assert_eq!(pool.draw(NOON), Some(0));
+    assert_eq!(pool.draw(NOON), Some(1));
+    // Key 0 has used its one call today
+    assert_eq!(pool.draw(NOON), Some(1));
+    assert_eq!(pool.draw(NOON), None);
+
+    // Quotas reset at the UTC day boundary
+    assert_eq!(pool.draw(NOON + DAY / 2), Some(0));
+}
+
+#[test]
+fn rate_limited_keys_rest_until_tomorrow() {
+    let mut pool = pool(&[100, 100]);
+
+    assert_eq!(pool.draw(NOON), Some(0));
+    assert!(!pool.report(0, 429, NOON));
+    assert_eq!(pool.keys[0].rested_until, Some(NOON + DAY / 2));
+
+    assert_eq!(pool.draw(NOON), Some(1));
+    assert_eq!(pool.draw(NOON), Some(1));
+    assert_eq!(pool.draw(NOON + DAY / 2), Some(0));
+}
+
+#[test]
+fn rejected_keys_stay_disabled() {
+    let mut pool = pool(&[100, 100]);
+
+    assert!(pool.report(1, 200, NOON));
+    assert!(!pool.report(0, 401, NOON));
+
+    for day in 0..3 {
+        assert_eq!(pool.draw(NOON + day * DAY), Some(1));
+    }
+}
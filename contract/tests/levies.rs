@@ -0,0 +1,22 @@
+// Batched levies: accrual into the per-account bucket swept by sweep_fees.
+
+use rialo_sdk::prelude::Pubkey;
+use rialo_weather_insurance::levies::LevyBucket;
+use rialo_weather_insurance::money::Ralo;
+
+#[test]
+fn bucket_accrues_per_collecting_account() {
+    let (regulator, fund) = (Pubkey::new_from_array([1; 32]), Pubkey::new_from_array([2; 32]));
+    let mut bucket = LevyBucket::default();
+
+    bucket.accrue(regulator, Ralo::whole(2), 1_000);
+    bucket.accrue(fund, Ralo::whole(1), 1_500);
+    bucket.accrue(regulator, Ralo::whole(3), 2_000);
+
+    assert_eq!(bucket.owed[&regulator], Ralo::whole(5));
+    assert_eq!(bucket.owed[&fund], Ralo::whole(1));
+    assert_eq!(bucket.total(), Ralo::whole(6));
+    assert_eq!(bucket.lines, 3);
+    // The period a sweep reports starts at the first accrual after the last one
+    assert_eq!(bucket.since, Some(1_000));
+}
@@ -0,0 +1,177 @@
+// End-to-end policy lifecycles against the deterministic weather fixture:
+// the fixture serves each scenario day over HTTP, the contract's own
+// parser reads it, and the policy state machine decides.
+
+use rialo_sdk::prelude::Pubkey;
+use rialo_weather_fixture::{http_get, FixtureServer, Scenario};
+use rialo_weather_insurance::escrow::cancellation;
+use rialo_weather_insurance::millimeters::Hundredths;
+use rialo_weather_insurance::money::Ralo;
+use rialo_weather_insurance::normalization::{Comparison, Location};
+use rialo_weather_insurance::oracle;
+use rialo_weather_insurance::policy::{Policy, PolicyStatus};
+
+const CITY: &str = "Nairobi";
+
+fn new_policy(threshold_mm: f64) -> Policy {
+    let mut policy = Policy::new(0, 0, Pubkey::default(), CITY.to_string(), Hundredths::from_f64(threshold_mm), Ralo::whole(100), Ralo::whole(10));
+    policy.coverage_secs = 30 * 24 * 60 * 60;
+    policy
+}
+
+// One keeper check: fetch today's weather and apply it. Returns whether it paid.
+fn check(server: &FixtureServer, policy: &mut Policy) -> bool {
+    let url = oracle::current_weather_url(&server.base_url(), &Location::City(CITY.into()), "test-key");
+    let (status, body) = http_get(&url).expect("fixture unreachable");
+    assert_eq!(status, 200);
+
+    let rainfall_mm = oracle::parse_rainfall(&body).expect("fixture body should parse");
+    policy.apply_reading(rainfall_mm.into(), 0)
+}
+
+// Run a check on every day of the scenario, returning the days that paid
+fn run(scenario: Scenario, days: usize, policy: &mut Policy) -> Vec<usize> {
+    let server = FixtureServer::start(scenario).unwrap();
+    let mut paid = Vec::new();
+
+    for day in 1..=days {
+        server.set_day(day);
+        if check(&server, policy) {
+            paid.push(day);
+        }
+    }
+    paid
+}
+
+fn activated(mut policy: Policy) -> Policy {
+    assert!(policy.record_premium(policy.premium_amount, 0));
+    assert_eq!(policy.status, PolicyStatus::Active);
+    policy
+}
+
+#[test]
+fn dry_week_then_storm_pays_once_on_day_five() {
+    let mut policy = activated(new_policy(20.0));
+
+    let paid = run(Scenario::named("storm-day-5").unwrap(), 7, &mut policy);
+
+    assert_eq!(paid, vec![5]);
+    assert_eq!(policy.status, PolicyStatus::PaidOut);
+}
+
+#[test]
+fn dry_week_never_pays() {
+    let mut policy = activated(new_policy(0.1));
+
+    let paid = run(Scenario::named("dry-week").unwrap(), 7, &mut policy);
+
+    assert!(paid.is_empty());
+    assert_eq!(policy.status, PolicyStatus::Active);
+}
+
+#[test]
+fn drought_cover_pays_on_the_first_dry_day() {
+    let mut policy = activated(new_policy(0.0));
+    policy.comparison = Comparison::AtOrBelow;
+
+    let paid = run(Scenario::named("dry-week").unwrap(), 7, &mut policy);
+
+    assert_eq!(paid, vec![1]);
+    assert_eq!(policy.status, PolicyStatus::PaidOut);
+}
+
+#[test]
+fn drizzle_below_threshold_does_not_pay() {
+    let mut policy = activated(new_policy(5.0));
+
+    let paid = run(Scenario::named("drizzle").unwrap(), 7, &mut policy);
+
+    assert!(paid.is_empty());
+}
+
+#[test]
+fn unpaid_policy_ignores_storm() {
+    let mut policy = new_policy(20.0);
+
+    let paid = run(Scenario::named("storm-day-5").unwrap(), 7, &mut policy);
+
+    assert!(paid.is_empty());
+    assert_eq!(policy.status, PolicyStatus::PendingPayment);
+}
+
+#[test]
+fn partial_premium_keeps_policy_pending() {
+    let mut policy = new_policy(20.0);
+
+    assert!(!policy.record_premium(Ralo::whole(4), 0));
+    assert_eq!(policy.premium_outstanding(), Ralo::whole(6));
+    assert!(policy.record_premium(Ralo::whole(6), 0));
+    assert_eq!(policy.status, PolicyStatus::Active);
+}
+
+#[test]
+fn a_renewal_paid_early_starts_when_the_old_cover_ends() {
+    let old_end = 30 * 24 * 60 * 60;
+    let mut renewal = new_policy(20.0);
+    renewal.starts_at = Some(old_end);
+
+    assert!(renewal.record_premium(renewal.premium_amount, old_end - 3_600));
+    assert_eq!(renewal.activated_at, Some(old_end));
+    assert!(!renewal.is_covered_at(old_end - 1));
+    assert!(renewal.is_covered_at(old_end));
+
+    // Paid after the old cover lapsed, it starts as the premium clears
+    let mut late = new_policy(20.0);
+    late.starts_at = Some(old_end);
+    assert!(late.record_premium(late.premium_amount, old_end + 60));
+    assert_eq!(late.activated_at, Some(old_end + 60));
+}
+
+#[test]
+fn a_renewal_paid_early_can_be_cancelled_before_it_starts() {
+    let old_end = 30 * 24 * 60 * 60;
+    let mut renewal = new_policy(20.0);
+    renewal.starts_at = Some(old_end);
+    assert!(renewal.record_premium(renewal.premium_amount, old_end - 86_400));
+
+    // Not yet covering anything, so the whole premium comes back
+    assert_eq!(cancellation(&renewal, old_end - 3_600).unwrap(), (Ralo::whole(10), Ralo::whole(100)));
+
+    // Halfway through its own window half of it does
+    let (refund, _) = cancellation(&renewal, old_end + renewal.coverage_secs / 2).unwrap();
+    assert_eq!(refund, Ralo::whole(5));
+    assert!(cancellation(&renewal, old_end + renewal.coverage_secs).is_err());
+}
+
+#[test]
+fn partial_payouts_leave_the_rest_of_the_cover() {
+    let mut policy = activated(new_policy(20.0));
+    assert_eq!(policy.coverage_remaining(), Ralo::whole(100));
+
+    policy.paid_out = Ralo::whole(40);
+    assert_eq!(policy.coverage_remaining(), Ralo::whole(60));
+
+    policy.paid_out = Ralo::whole(100);
+    assert_eq!(policy.coverage_remaining(), Ralo::ZERO);
+}
+
+#[test]
+fn outage_day_surfaces_as_bad_status() {
+    let server = FixtureServer::start(Scenario::named("outage").unwrap()).unwrap();
+    server.set_day(3);
+
+    let url = oracle::current_weather_url(&server.base_url(), &Location::City(CITY.into()), "test-key");
+    let (status, _) = http_get(&url).unwrap();
+
+    assert_eq!(status, 503);
+}
+
+#[test]
+fn scenario_clock_moves_over_http() {
+    let server = FixtureServer::start(Scenario::named("storm-day-5").unwrap()).unwrap();
+
+    http_get(&format!("{}/_fixture/day/4", server.base_url())).unwrap();
+    http_get(&format!("{}/_fixture/advance", server.base_url())).unwrap();
+
+    assert_eq!(server.day(), 5);
+}
@@ -0,0 +1,177 @@
+// State versioning: a state account written by an earlier layout still
+// decodes, and migrating it brings it up to the current version once.
+
+use rialo_sdk::prelude::Pubkey;
+use rialo_weather_insurance::conditions::Condition;
+use rialo_weather_insurance::config::{Limits, NetworkMode};
+use rialo_weather_insurance::curves::{PayoutCurve, PayoutTier};
+use rialo_weather_insurance::migrations::{migrate, STATE_VERSION};
+use rialo_weather_insurance::millimeters::Hundredths;
+use rialo_weather_insurance::money::Ralo;
+use rialo_weather_insurance::normalization::{Comparison, Metric};
+use rialo_weather_insurance::policy::PolicyStatus;
+use rialo_weather_insurance::InsuranceState;
+
+// The last layout written before versioning
+const STATE_V0: &str = include_str!("fixtures/state_v0.json");
+// Versioned, before policies were indexed by owner
+const STATE_V1: &str = include_str!("fixtures/state_v1.json");
+// Before the per-beneficiary cap joined the risk limits
+const STATE_V2: &str = include_str!("fixtures/state_v2.json");
+// Thresholds still written as f64 millimetres
+const STATE_V3: &str = include_str!("fixtures/state_v3.json");
+// Readings, curves, riders and conditions still written as f64
+const STATE_V5: &str = include_str!("fixtures/state_v5.json");
+
+fn decode(json: &str) -> InsuranceState {
+    serde_json::from_str(json).expect("an earlier layout should still decode")
+}
+
+#[test]
+fn a_version_0_state_decodes_with_its_policies_intact() {
+    let state = decode(STATE_V0);
+
+    assert_eq!(state.version, 0);
+    assert!(state.config.paused);
+    assert_eq!(state.next_policy_id, 1);
+
+    let policy = &state.policies[&0];
+    assert_eq!(policy.status, PolicyStatus::Active);
+    assert_eq!(policy.payout_amount, Ralo::whole(100));
+    assert_eq!(policy.coverage_end(), Some(1_700_003_600 + 2_592_000));
+    // Fields added since decode as unset
+    assert_eq!(policy.starts_at, None);
+    assert_eq!(policy.renewed_by, None);
+    assert!(policy.route.is_none());
+    assert!(state.config.limits.is_none());
+}
+
+#[test]
+fn migrating_brings_the_state_up_to_the_current_version_once() {
+    let mut state = decode(STATE_V0);
+
+    assert_eq!(migrate(&mut state).ok(), Some(0));
+    assert_eq!(state.version, STATE_VERSION);
+    assert_eq!(migrate(&mut state).ok(), Some(STATE_VERSION));
+    assert_eq!(state.version, STATE_VERSION);
+    assert_eq!(state.policies.len(), 1);
+}
+
+#[test]
+fn migrating_a_version_1_state_indexes_its_policies_by_owner() {
+    let mut state = decode(STATE_V1);
+    assert_eq!(state.version, 1);
+    assert!(state.policies_by_owner.is_empty());
+
+    assert_eq!(migrate(&mut state).ok(), Some(1));
+    assert_eq!(state.policies_by_owner[&Pubkey::new_from_array([9; 32])], vec![0]);
+}
+
+#[test]
+fn migrating_a_version_2_state_keeps_its_beneficiary_cap() {
+    let mut state = decode(STATE_V2);
+    assert_eq!(state.config.beneficiary_cap, Some(Ralo::whole(500)));
+
+    assert_eq!(migrate(&mut state).ok(), Some(2));
+    let limits = state.config.limits();
+    assert_eq!(limits.max_coverage_per_holder, Some(Ralo::whole(500)));
+    assert_eq!((limits.max_payout, limits.max_policies), (Ralo::whole(300), Some(3)));
+    assert_eq!(limits.min_threshold, Hundredths(50));
+    assert_eq!(state.config.beneficiary_cap, Some(Ralo::whole(500)));
+}
+
+#[test]
+fn migrating_a_version_3_state_moves_its_thresholds_to_hundredths() {
+    let mut state = decode(STATE_V3);
+    let template = &state.underwriters[&0].templates[&0];
+    assert_eq!((template.min_threshold, template.old_min_threshold), (Hundredths::ZERO, Some(12.5)));
+
+    assert_eq!(migrate(&mut state).ok(), Some(3));
+    let template = &state.underwriters[&0].templates[&0];
+    assert_eq!((template.min_threshold, template.old_min_threshold), (Hundredths(1_250), None));
+    let limits = state.config.limits();
+    assert_eq!((limits.min_threshold, limits.old_min_threshold), (Hundredths(50), None));
+    assert_eq!(limits.max_coverage_per_holder, Some(Ralo::whole(500)));
+    assert_eq!(state.config.beneficiary_cap, Some(Ralo::whole(500)));
+
+    // A policy written before then keeps the threshold it was sold at
+    let mut state = decode(STATE_V0);
+    migrate(&mut state).unwrap();
+    assert_eq!((state.policies[&0].threshold, state.policies[&0].old_threshold), (Hundredths(2_000), None));
+}
+
+#[test]
+fn migrating_a_version_5_state_moves_its_readings_to_hundredths() {
+    let mut state = decode(STATE_V5);
+    assert_eq!(state.policies[&0].graded.as_ref().unwrap().payout_curve, PayoutCurve::default());
+    assert_eq!(state.checks[&0].reading, Hundredths::ZERO);
+
+    assert_eq!(migrate(&mut state).ok(), Some(5));
+    assert_eq!(state.version, STATE_VERSION);
+
+    let reading = |threshold| Condition::Reading { metric: Metric::Rainfall, comparison: Comparison::AtOrAbove, threshold };
+    let condition = Condition::All(vec![reading(Hundredths(1_250)), Condition::Any(vec![reading(Hundredths(3_025))])]);
+    assert_eq!(state.underwriters[&0].templates[&0].compound.as_ref(), Some(&condition));
+
+    let policy = &state.policies[&0];
+    assert_eq!(policy.compound.as_ref(), Some(&condition));
+    assert_eq!(policy.graded.as_ref().unwrap().payout_curve, PayoutCurve::Tiers(vec![
+        PayoutTier { at: Hundredths(2_550), share_bps: 5_000 },
+        PayoutTier { at: Hundredths(4_000), share_bps: 10_000 },
+    ]));
+    let perils: Vec<_> = policy.bundle.as_ref().unwrap().perils.iter().map(|peril| peril.trigger_at).collect();
+    assert_eq!(perils, [Hundredths(2_000), Hundredths(4_050)]);
+    let stops: Vec<_> = policy.route.as_ref().unwrap().stops.iter().map(|stop| stop.last_value).collect();
+    assert_eq!(stops, [Some(Hundredths(4_125)), Some(Hundredths(350))]);
+    // A payout parked behind an attestation, a conversion or a dispute keeps the reading it settled on
+    assert_eq!(policy.attestation.as_ref().unwrap().pending.unwrap().value, Hundredths(4_125));
+    assert_eq!(policy.usd_payout.as_ref().unwrap().deferred.unwrap().value, Hundredths(4_125));
+    assert_eq!(policy.dispute.as_ref().unwrap().pending.as_ref().unwrap().payout.value, Hundredths(4_125));
+
+    let terms = &state.subscriptions[&0].terms;
+    assert_eq!((terms.threshold, terms.old_threshold), (Hundredths(2_000), None));
+    assert_eq!(terms.payout_curve, PayoutCurve::Deductible { deductible: Hundredths(1_000), full_at: Hundredths(6_050) });
+
+    let receipts = &state.receipts[&Pubkey::new_from_array([9; 32])];
+    assert_eq!(receipts[0].value, Hundredths(4_125));
+    let review = receipts[1].review.as_ref().unwrap();
+    assert_eq!((receipts[1].value, review.original_value), (Hundredths(3_950), Hundredths(4_125)));
+    assert_eq!(state.outbox[&0].value, Hundredths(4_125));
+    assert_eq!((state.checks[&0].reading, state.checks[&0].old_reading), (Hundredths(4_125), None));
+    assert_eq!(state.held_observations[0].reading, Hundredths(2_775));
+}
+
+#[test]
+fn migrating_unpins_network_defaults_stored_alongside_a_beneficiary_cap() {
+    // Under versions 3 and 4 setting the cap alone stored the mode's defaults with it
+    let mut state = decode(STATE_V3);
+    let pinned = Limits { max_coverage_per_holder: Some(Ralo::whole(500)), ..NetworkMode::MainNet.limits() };
+    state.config.limits = Some(pinned);
+
+    migrate(&mut state).unwrap();
+    assert_eq!(state.config.limits, None);
+    assert_eq!(state.config.beneficiary_cap, Some(Ralo::whole(500)));
+
+    state.config.network_mode = NetworkMode::DevNet;
+    assert_eq!(state.config.limits(), Limits { max_coverage_per_holder: Some(Ralo::whole(500)), ..NetworkMode::DevNet.limits() });
+}
+
+#[test]
+fn a_migrated_state_round_trips() {
+    let mut state = decode(STATE_V0);
+    migrate(&mut state).unwrap();
+
+    let encoded = serde_json::to_string(&state).unwrap();
+    let decoded = decode(&encoded);
+    assert_eq!(decoded.version, STATE_VERSION);
+    assert_eq!(decoded.policies[&0].activated_at, Some(1_700_003_600));
+    assert_eq!(decoded.policies[&0].threshold, Hundredths(2_000));
+}
+
+#[test]
+fn a_state_from_a_newer_contract_is_refused() {
+    let mut state = decode(STATE_V0);
+    state.version = STATE_VERSION + 1;
+    assert!(migrate(&mut state).is_err());
+    assert_eq!(state.version, STATE_VERSION + 1);
+}
@@ -0,0 +1,71 @@
+// Fixed-point rainfall: conversion from provider readings, and display.
+
+use rialo_weather_insurance::millimeters::{to_hundredths, Hundredths, Millimeters};
+
+#[test]
+fn every_hundredth_converts_exactly() {
+    for n in 0..=1_000_000u64 {
+        assert_eq!(Millimeters::from_mm(n as f64 / 100.0), Millimeters(n), "{n} hundredths");
+    }
+}
+
+#[test]
+fn every_half_hundredth_rounds_up() {
+    for n in 0..=100_000u64 {
+        let half = (2 * n + 1) as f64 / 200.0;
+        assert_eq!(Millimeters::from_mm(half), Millimeters(n + 1), "{half} mm");
+    }
+}
+
+#[test]
+fn rounding_follows_the_written_decimal() {
+    // Binary approximations of these sit just under the half
+    assert_eq!(Millimeters::from_mm(1.005), Millimeters(101));
+    assert_eq!(Millimeters::from_mm(2.675), Millimeters(268));
+    assert_eq!(Millimeters::from_mm(0.004), Millimeters(0));
+    assert_eq!(Millimeters::from_mm(0.0049), Millimeters(0));
+    assert_eq!(Millimeters::from_mm(0.1 + 0.2), Millimeters(30));
+}
+
+#[test]
+fn nonsense_readings_are_no_rain_or_saturate() {
+    assert_eq!(Millimeters::from_mm(-3.2), Millimeters::ZERO);
+    assert_eq!(Millimeters::from_mm(f64::NAN), Millimeters::ZERO);
+    assert_eq!(Millimeters::from_mm(f64::INFINITY), Millimeters(u64::MAX));
+    assert_eq!(Millimeters(u64::MAX) + Millimeters(1), Millimeters(u64::MAX));
+}
+
+#[test]
+fn signed_values_round_away_from_zero() {
+    assert_eq!(to_hundredths(12.345), 1_235);
+    assert_eq!(to_hundredths(-12.345), -1_235);
+    assert_eq!(to_hundredths(-0.004), 0);
+    assert_eq!(to_hundredths(f64::NAN), 0);
+}
+
+#[test]
+fn thresholds_hold_below_zero_and_compare_with_rain() {
+    let frost = Hundredths::from_f64(-2.5);
+    assert_eq!(frost, Hundredths(-250));
+    assert_eq!(frost.to_f64(), -2.5);
+    assert!(frost < Hundredths::ZERO && !frost.is_positive());
+
+    assert_eq!(Hundredths::from(Millimeters(1_250)), Hundredths::from_f64(12.5));
+    assert!(Hundredths::from(Millimeters(u64::MAX)) > Hundredths::from_f64(1e9));
+}
+
+#[test]
+fn thresholds_display_signed_without_a_unit() {
+    assert_eq!(Hundredths(-250).to_string(), "-2.5");
+    assert_eq!(Hundredths(-5).to_string(), "-0.05");
+    assert_eq!(Hundredths(1_200).to_string(), "12");
+    assert_eq!(Hundredths::ZERO.to_string(), "0");
+}
+
+#[test]
+fn displays_without_trailing_zeros() {
+    assert_eq!(Millimeters(1_250).to_string(), "12.5 mm");
+    assert_eq!(Millimeters(1_205).to_string(), "12.05 mm");
+    assert_eq!(Millimeters(1_200).to_string(), "12 mm");
+    assert_eq!(Millimeters(5).to_string(), "0.05 mm");
+}
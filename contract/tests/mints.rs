@@ -0,0 +1,30 @@
+// Payout mints: the tokens each RALO payment stands for, and what a pool can spare.
+
+use rialo_sdk::prelude::*;
+use rialo_weather_insurance::mints::{MintPayout, MintPool};
+use rialo_weather_insurance::money::Ralo;
+
+fn terms(amount: u64) -> MintPayout {
+    MintPayout { mint: Pubkey::new_from_array([7; 32]), amount, paid: 0, reserved: amount }
+}
+
+#[test]
+fn payments_send_the_mint_share_of_the_ralo_paid() {
+    let payout = Ralo::whole(100);
+    let mut terms = terms(250_000_000);   // 250 tokens at six decimals
+
+    // A graded step to 40%, then the rest
+    assert_eq!(terms.due(Ralo::whole(40), payout), 100_000_000);
+    terms.paid = 100_000_000;
+    assert_eq!(terms.due(payout, payout), 150_000_000);
+
+    // Nothing more once the full amount has gone, whatever the RALO says
+    terms.paid = 250_000_000;
+    assert_eq!(terms.due(payout.times(2), payout), 0);
+}
+
+#[test]
+fn only_unreserved_tokens_are_free() {
+    assert_eq!(MintPool { balance: 500, reserved: 200 }.free(), 300);
+    assert_eq!(MintPool { balance: 100, reserved: 200 }.free(), 0);
+}
@@ -0,0 +1,21 @@
+// Money: the checked ledger arithmetic never wraps.
+
+use rialo_weather_insurance::errors::InsuranceError;
+use rialo_weather_insurance::money::Ralo;
+
+#[test]
+fn checked_arithmetic_refuses_to_wrap() {
+    assert_eq!(Ralo::whole(2).checked_add(Ralo::whole(3)), Some(Ralo::whole(5)));
+    assert_eq!(Ralo(u64::MAX).checked_add(Ralo(1)), None);
+
+    assert_eq!(Ralo::whole(5).checked_sub(Ralo::whole(5)), Some(Ralo::ZERO));
+    assert_eq!(Ralo::whole(5).checked_sub(Ralo::whole(6)), None);
+
+    assert_eq!(Ralo(7).checked_times(3), Some(Ralo(21)));
+    assert_eq!(Ralo(u64::MAX / 2 + 1).checked_times(2), None);
+}
+
+#[test]
+fn a_wrapped_amount_has_its_own_code() {
+    assert_eq!(InsuranceError::ArithmeticOverflow.code(), 6_234);
+}
@@ -0,0 +1,31 @@
+// No-claim bonus: what a policy that expired without paying hands back.
+
+use rialo_sdk::prelude::Pubkey;
+use rialo_weather_insurance::millimeters::Hundredths;
+use rialo_weather_insurance::money::Ralo;
+use rialo_weather_insurance::no_claims::{earned, NoClaimBonus};
+use rialo_weather_insurance::policy::{Policy, PolicyStatus};
+
+fn expired(refund_bps: u64) -> Policy {
+    let mut policy = Policy::new(0, 3, Pubkey::default(), "nairobi".into(), Hundredths::from_f64(30.0), Ralo::whole(100), Ralo::whole(10));
+    policy.premium_paid = Ralo::whole(10);
+    policy.status = PolicyStatus::Expired;
+    policy.no_claim = Some(NoClaimBonus::new(refund_bps));
+    policy
+}
+
+#[test]
+fn claim_free_expiry_earns_the_bonus_share() {
+    assert_eq!(earned(&expired(2_500)), Some(Ralo::whole(10).bps(2_500)));
+    assert_eq!(earned(&Policy { no_claim: None, ..expired(2_500) }), None);
+}
+
+#[test]
+fn any_payment_or_live_cover_forfeits_it() {
+    let mut paid = expired(2_500);
+    paid.paid_out = Ralo::whole(20);
+    assert_eq!(earned(&paid), None);
+
+    let live = Policy { status: PolicyStatus::Active, ..expired(2_500) };
+    assert_eq!(earned(&live), None);
+}
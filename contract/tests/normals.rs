@@ -0,0 +1,79 @@
+// Month-to-date rainfall against the monthly normal.
+
+use rialo_weather_insurance::millimeters::{Hundredths, Millimeters};
+use rialo_weather_insurance::normals::{MonthlyNormals, NormalCover};
+use rialo_weather_insurance::seasonal::month_index;
+
+const HOUR: i64 = 60 * 60;
+
+// 2024-03-01T00:00:00Z and 2024-04-01T00:00:00Z
+const MAR_1_2024: i64 = 1_709_251_200;
+const APR_1_2024: i64 = 1_711_929_600;
+
+// 100 mm normal in March, 50 mm in April, dry otherwise
+const NORMALS: MonthlyNormals = [0.0, 0.0, 100.0, 50.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0];
+
+// Pays once the month has seen twice its normal rain
+const TWICE_NORMAL: Hundredths = Hundredths(20_000);
+
+#[test]
+fn month_index_counts_months_since_the_epoch() {
+    assert_eq!(month_index(0), 0);
+    assert_eq!(month_index(MAR_1_2024), 54 * 12 + 2);
+    assert_eq!(month_index(APR_1_2024) - month_index(APR_1_2024 - 1), 1);
+}
+
+#[test]
+fn triggers_once_the_month_reaches_the_percentage() {
+    let mut cover = NormalCover::new(NORMALS);
+    assert!(!cover.record(Millimeters::from_mm(120.0), MAR_1_2024, TWICE_NORMAL));
+    // A second reading in the same hour isn't counted
+    assert!(!cover.record(Millimeters::from_mm(120.0), MAR_1_2024 + 60, TWICE_NORMAL));
+    assert!(cover.record(Millimeters::from_mm(80.0), MAR_1_2024 + HOUR, TWICE_NORMAL));
+    assert_eq!(cover.percent_of_normal(MAR_1_2024 + HOUR), 200.0);
+}
+
+#[test]
+fn total_starts_again_each_month() {
+    let mut cover = NormalCover::new(NORMALS);
+    assert!(!cover.record(Millimeters::from_mm(90.0), APR_1_2024 - HOUR, TWICE_NORMAL));
+    assert!(!cover.record(Millimeters::from_mm(60.0), APR_1_2024, TWICE_NORMAL));
+    assert_eq!(cover.month_mm, Millimeters(6_000));
+    assert!(cover.record(Millimeters::from_mm(40.0), APR_1_2024 + HOUR, TWICE_NORMAL));
+}
+
+#[test]
+fn month_turns_over_at_local_midnight() {
+    // UTC+10: April starts ten hours before it does in UTC
+    let mut cover = NormalCover { utc_offset: 10 * 60, ..NormalCover::new(NORMALS) };
+    assert!(!cover.record(Millimeters::from_mm(90.0), APR_1_2024 - 11 * HOUR, TWICE_NORMAL));
+    assert!(!cover.record(Millimeters::from_mm(60.0), APR_1_2024 - 10 * HOUR, TWICE_NORMAL));
+    assert_eq!(cover.month_mm, Millimeters(6_000));
+    assert_eq!(cover.percent_of_normal(APR_1_2024 - 10 * HOUR), 120.0);
+}
+
+#[test]
+fn backfilled_hours_count_in_any_order() {
+    let mut cover = NormalCover::new(NORMALS);
+    assert!(!cover.record(Millimeters::from_mm(50.0), MAR_1_2024 + 5 * HOUR, TWICE_NORMAL));
+    assert_eq!(cover.missing_hours(MAR_1_2024, MAR_1_2024 + 6 * HOUR, 10).len(), 5);
+
+    // An hour before the last reading still counts, once
+    assert!(!cover.record(Millimeters::from_mm(50.0), MAR_1_2024 + 2 * HOUR, TWICE_NORMAL));
+    assert!(!cover.record(Millimeters::from_mm(50.0), MAR_1_2024 + 2 * HOUR, TWICE_NORMAL));
+    assert_eq!(cover.month_mm, Millimeters(10_000));
+
+    let first = MAR_1_2024 / HOUR;
+    assert_eq!(cover.missing_hours(MAR_1_2024, MAR_1_2024 + 6 * HOUR, 10), vec![first, first + 1, first + 3, first + 4]);
+    assert!(cover.record(Millimeters::from_mm(100.0), MAR_1_2024 + 3 * HOUR, TWICE_NORMAL));
+}
+
+#[test]
+fn closed_month_takes_no_backfill() {
+    let mut cover = NormalCover::new(NORMALS);
+    assert!(!cover.record(Millimeters::from_mm(10.0), APR_1_2024, TWICE_NORMAL));
+    assert!(!cover.record(Millimeters::from_mm(500.0), APR_1_2024 - HOUR, TWICE_NORMAL));
+    assert_eq!(cover.month_mm, Millimeters(1_000));
+    // Only April's hours are missing from a window ending in April
+    assert_eq!(cover.missing_hours(APR_1_2024 - 2 * HOUR, APR_1_2024 + 2 * HOUR, 10), vec![APR_1_2024 / HOUR + 1]);
+}
@@ -0,0 +1,60 @@
+// Reading the hour's rain from each shape an OpenWeatherMap response reports it in.
+
+use rialo_weather_insurance::millimeters::Millimeters;
+use rialo_weather_insurance::oracle::{parse_historical_observation, parse_observation, parse_rainfall};
+
+#[test]
+fn the_last_hour_wins_over_the_last_three() {
+    let body = br#"{ "rain": { "1h": 2.5, "3h": 9.0 } }"#;
+    assert_eq!(parse_rainfall(body).unwrap(), Millimeters(250));
+}
+
+#[test]
+fn three_hours_of_rain_reads_as_an_hourly_rate() {
+    let body = br#"{ "rain": { "3h": 9.0 }, "weather": [ { "id": 501 } ] }"#;
+    assert_eq!(parse_rainfall(body).unwrap(), Millimeters(300));
+
+    let history = br#"{ "list": [ { "rain": { "3h": 4.5 } } ] }"#;
+    assert_eq!(parse_historical_observation(history).unwrap().rainfall_mm, Millimeters(150));
+}
+
+#[test]
+fn precipitation_counts_as_rain_less_the_snow() {
+    let body = br#"{ "precipitation": { "1h": 1.2 }, "weather": [ { "id": 301 } ] }"#;
+    assert_eq!(parse_rainfall(body).unwrap(), Millimeters(120));
+
+    let body = br#"{ "precipitation": { "1h": 5.0 }, "snow": { "1h": 2.0 }, "weather": [ { "id": 616 } ] }"#;
+    let observation = parse_observation(body).unwrap();
+    assert_eq!(observation.rainfall_mm, Millimeters(300));
+    assert_eq!(observation.snowfall_mm, Some(2.0));
+}
+
+#[test]
+fn drizzle_filed_under_snow_reads_as_rain() {
+    let body = br#"{ "snow": { "1h": 0.8 }, "weather": [ { "id": 300 } ] }"#;
+    let observation = parse_observation(body).unwrap();
+    assert_eq!(observation.rainfall_mm, Millimeters(80));
+    assert_eq!(observation.snowfall_mm, Some(0.0));
+
+    // Sleet is snow as well as rain, so the snow stays snow
+    let body = br#"{ "snow": { "1h": 0.8 }, "rain": { "1h": 0.4 }, "weather": [ { "id": 300 }, { "id": 611 } ] }"#;
+    let observation = parse_observation(body).unwrap();
+    assert_eq!(observation.rainfall_mm, Millimeters(40));
+    assert_eq!(observation.snowfall_mm, Some(0.8));
+
+    let body = br#"{ "snow": { "3h": 6.0 }, "weather": [ { "id": 601 } ] }"#;
+    let observation = parse_observation(body).unwrap();
+    assert_eq!(observation.rainfall_mm, Millimeters::ZERO);
+    assert_eq!(observation.snowfall_mm, Some(2.0));
+}
+
+#[test]
+fn rain_with_no_amount_is_refused_not_read_as_dry() {
+    let body = br#"{ "weather": [ { "id": 502 } ], "main": { "temp": 14.0 } }"#;
+    assert!(parse_observation(body).is_err());
+
+    // Clouds without a rain block are a dry hour
+    let body = br#"{ "weather": [ { "id": 804 } ], "main": { "temp": 14.0 } }"#;
+    assert_eq!(parse_rainfall(body).unwrap(), Millimeters::ZERO);
+    assert_eq!(parse_rainfall(b"{}").unwrap(), Millimeters::ZERO);
+}
@@ -0,0 +1,61 @@
+// Outbox retries: backoff between failed deliveries, and dead-lettering.
+
+use rialo_sdk::prelude::Pubkey;
+use rialo_weather_insurance::millimeters::Hundredths;
+use rialo_weather_insurance::money::Ralo;
+use rialo_weather_insurance::outbox::{retry_delay_secs, OutboxRecord, MAX_DELIVERY_ATTEMPTS};
+use rialo_weather_insurance::receipts::SettlementKey;
+
+const NOW: i64 = 1_700_000_000;
+
+fn record() -> OutboxRecord {
+    let key = SettlementKey { policy_id: 7, round_id: Some(41) };
+    OutboxRecord::new(0, key, Pubkey::default(), Ralo::whole(100), Hundredths(6_200), NOW)
+}
+
+#[test]
+fn new_records_are_due_at_once() {
+    let record = record();
+    assert!(record.is_due(NOW));
+    assert!(!record.is_due(NOW - 1));
+    assert_eq!(record.attempts, 0);
+}
+
+#[test]
+fn retries_back_off_from_a_minute_to_a_day() {
+    assert_eq!(retry_delay_secs(1), 60);
+    assert_eq!(retry_delay_secs(2), 120);
+    assert_eq!(retry_delay_secs(5), 960);
+    assert_eq!(retry_delay_secs(12), 24 * 60 * 60);
+    assert_eq!(retry_delay_secs(u32::MAX), 24 * 60 * 60);
+}
+
+#[test]
+fn a_failure_schedules_the_next_try() {
+    let mut record = record();
+    assert!(!record.record_failure("503 from webhook", NOW));
+
+    assert_eq!(record.attempts, 1);
+    assert_eq!(record.last_error.as_deref(), Some("503 from webhook"));
+    assert!(!record.is_due(NOW + 59));
+    assert!(record.is_due(NOW + 60));
+}
+
+#[test]
+fn long_errors_are_truncated() {
+    let mut record = record();
+    record.record_failure(&"x".repeat(10_000), NOW);
+    assert_eq!(record.last_error.unwrap().len(), 256);
+}
+
+#[test]
+fn the_last_retry_dead_letters_the_record() {
+    let mut record = record();
+    for attempt in 1..MAX_DELIVERY_ATTEMPTS {
+        assert!(!record.record_failure("timeout", NOW), "attempt {attempt}");
+    }
+    assert!(record.record_failure("timeout", NOW));
+
+    assert!(record.dead_lettered);
+    assert!(!record.is_due(i64::MAX));
+}
@@ -0,0 +1,54 @@
+// Policies by owner: paging an owner's list with a cursor.
+
+use std::collections::BTreeMap;
+
+use rialo_sdk::prelude::Pubkey;
+use rialo_weather_insurance::millimeters::Hundredths;
+use rialo_weather_insurance::money::Ralo;
+use rialo_weather_insurance::owners::{build_index, owner_page, OWNER_PAGE_SIZE};
+use rialo_weather_insurance::policy::{Policy, PolicyId, PolicyStatus};
+
+fn book(owners: &[(PolicyId, Pubkey)]) -> BTreeMap<PolicyId, Policy> {
+    owners
+        .iter()
+        .map(|&(id, owner)| (id, Policy::new(0, 0, owner, "nairobi".into(), Hundredths::from_f64(20.0), Ralo::whole(100), Ralo::whole(10))))
+        .collect()
+}
+
+#[test]
+fn the_index_files_each_policy_under_its_owner_in_id_order() {
+    let (alice, bob) = (Pubkey::new_from_array([1; 32]), Pubkey::new_from_array([2; 32]));
+    let index = build_index(&book(&[(0, alice), (1, bob), (2, alice), (5, alice)]));
+
+    assert_eq!(index[&alice], vec![0, 2, 5]);
+    assert_eq!(index[&bob], vec![1]);
+}
+
+#[test]
+fn pages_follow_the_cursor_to_the_end() {
+    let owner = Pubkey::new_from_array([1; 32]);
+    let ids: Vec<PolicyId> = (0..OWNER_PAGE_SIZE as u64 + 3).map(|i| i * 2).collect();
+    let policies = book(&ids.iter().map(|&id| (id, owner)).collect::<Vec<_>>());
+
+    let first = owner_page(owner, &ids, &policies, None);
+    assert_eq!(first.total, ids.len() as u32);
+    assert_eq!(first.policies.len(), OWNER_PAGE_SIZE);
+    assert_eq!(first.next, Some(ids[OWNER_PAGE_SIZE - 1]));
+
+    let last = owner_page(owner, &ids, &policies, first.next);
+    assert_eq!(last.policies.iter().map(|p| p.policy_id).collect::<Vec<_>>(), ids[OWNER_PAGE_SIZE..].to_vec());
+    assert_eq!(last.next, None);
+}
+
+#[test]
+fn summaries_carry_status_and_money() {
+    let owner = Pubkey::new_from_array([1; 32]);
+    let policies = book(&[(3, owner)]);
+
+    let page = owner_page(owner, &[3], &policies, None);
+    let summary = &page.policies[0];
+    assert_eq!(summary.policy_id, 3);
+    assert_eq!(summary.status, PolicyStatus::PendingPayment);
+    assert_eq!((summary.payout, summary.premium, summary.paid_out), (Ralo::whole(100), Ralo::whole(10), Ralo::ZERO));
+    assert_eq!(summary.coverage_end, None);
+}
@@ -0,0 +1,31 @@
+// Risk pools: shares issued and redeemed at the pool's current value.
+
+use rialo_weather_insurance::money::Ralo;
+use rialo_weather_insurance::pools::RiskPool;
+
+#[test]
+fn joiners_buy_in_at_the_current_value_per_share() {
+    let mut pool = RiskPool::default();
+
+    // The first member sets one share per base unit
+    let first = pool.shares_for(Ralo::ZERO, Ralo::whole(100));
+    assert_eq!(first, Ralo::whole(100).base_units());
+    pool.members.insert(1, first);
+    pool.total_shares = first;
+
+    // Premiums take the pool to 150; a 150 deposit buys as many shares as the first member holds
+    let second = pool.shares_for(Ralo::whole(150), Ralo::whole(150));
+    assert_eq!(second, first);
+}
+
+#[test]
+fn shares_carry_their_slice_of_premiums_and_payouts() {
+    let mut pool = RiskPool::default();
+    pool.members.insert(1, 300);
+    pool.members.insert(2, 100);
+    pool.total_shares = 400;
+
+    // A payout takes the pool from 400 down to 200: each member absorbs its share of the loss
+    assert_eq!(pool.value_of(Ralo::whole(200), 300), Ralo::whole(150));
+    assert_eq!(pool.value_of(Ralo::whole(200), 100), Ralo::whole(50));
+}
@@ -0,0 +1,54 @@
+// Incident postmortems: which settlement receipts a postmortem is linked from.
+
+use rialo_weather_insurance::millimeters::Hundredths;
+use rialo_weather_insurance::money::Ralo;
+use rialo_weather_insurance::postmortems::{touches, Postmortem};
+use rialo_weather_insurance::receipts::{observation_hash, SettlementReceipt};
+
+const OWM: &str = "https://api.openweathermap.org";
+
+fn receipt(observed_at: i64) -> SettlementReceipt {
+    SettlementReceipt {
+        policy_id:        7,
+        round_id:         Some(41),
+        observation_hash: observation_hash(7, Hundredths(3_250), observed_at),
+        value:            Hundredths(3_250),
+        old_reading:      None,
+        observed_at,
+        amount:           Ralo::whole(100),
+        tx_time:          observed_at + 60,
+        postmortems:      Vec::new(),
+        review:           None,
+    }
+}
+
+fn postmortem(source: Option<&str>) -> Postmortem {
+    Postmortem {
+        source:            source.map(str::to_string),
+        window_start:      3_600,
+        window_end:        7_200,
+        affected_policies: 12,
+        report_hash:       [9; 32],
+        remediation:       "remediation policy RP-4".into(),
+        recorded_at:       86_400,
+        receipts_linked:   0,
+    }
+}
+
+#[test]
+fn receipts_on_readings_inside_the_window_are_touched() {
+    let postmortem = postmortem(None);
+    assert!(touches(&postmortem, &receipt(3_600), Some(OWM)));
+    assert!(touches(&postmortem, &receipt(7_199), None));
+    assert!(!touches(&postmortem, &receipt(7_200), Some(OWM)));
+    assert!(!touches(&postmortem, &receipt(3_599), Some(OWM)));
+}
+
+#[test]
+fn a_provider_postmortem_only_touches_that_providers_readings() {
+    let postmortem = postmortem(Some(OWM));
+    assert!(touches(&postmortem, &receipt(5_000), Some(OWM)));
+    assert!(!touches(&postmortem, &receipt(5_000), Some("https://api.weatherapi.com")));
+    // A reading with no logged check can't be placed with the provider
+    assert!(!touches(&postmortem, &receipt(5_000), None));
+}
@@ -0,0 +1,16 @@
+// Product documents: what a template's published wording has to carry.
+
+use rialo_weather_insurance::product_docs::ProductDoc;
+
+fn doc(uri: &str) -> ProductDoc {
+    ProductDoc { label: "Policy wording".into(), uri: uri.into(), hash: [7; 32] }
+}
+
+#[test]
+fn published_documents_need_a_fetchable_uri_and_a_hash() {
+    assert!(doc("https://example.com/wording-v3.pdf").validate().is_ok());
+    assert!(doc("ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi").validate().is_ok());
+    assert!(doc("http://example.com/wording.pdf").validate().is_err());
+    assert!(ProductDoc { hash: [0; 32], ..doc("https://example.com/wording.pdf") }.validate().is_err());
+    assert!(ProductDoc { label: " ".into(), ..doc("https://example.com/wording.pdf") }.validate().is_err());
+}
@@ -0,0 +1,165 @@
+// Dispatch through ProviderKind and the WeatherAPI.com response shapes.
+
+use rialo_weather_insurance::geo::GeoPoint;
+use rialo_weather_insurance::millimeters::{Hundredths, Millimeters};
+use rialo_weather_insurance::normalization::{Location, Metric, Station};
+use rialo_weather_insurance::oracle;
+use rialo_weather_insurance::providers::{ProviderKind, ReadingTime, WeatherProvider};
+
+const HOUR: i64 = 60 * 60;
+
+#[test]
+fn openweathermap_goes_through_the_oracle() {
+    let kind = ProviderKind::OpenWeatherMap;
+    assert_eq!(
+        kind.build_request("https://api.test", "k", &Location::City("nairobi".into()), ReadingTime::Current),
+        oracle::current_weather_url("https://api.test", &Location::City("nairobi".into()), "k"),
+    );
+
+    let body = br#"{ "rain": { "1h": 4.2 }, "main": { "temp": 21.0, "humidity": 80 } }"#;
+    assert_eq!(kind.parse_observation(ReadingTime::Current, body).unwrap().rainfall_mm, Millimeters(420));
+}
+
+#[test]
+fn pinned_locations_are_queried_exactly() {
+    let point = Location::Point(GeoPoint::new(39_801_700, -89_643_700).unwrap());   // Springfield, Illinois
+    let city_id = Location::CityId(4_250_542);
+
+    assert_eq!(
+        ProviderKind::OpenWeatherMap.build_request("https://api.test", "k", &point, ReadingTime::Current),
+        "https://api.test/data/2.5/weather?lat=39.8017&lon=-89.6437&appid=k&units=metric&lang=en",
+    );
+    assert_eq!(
+        ProviderKind::OpenWeatherMap.build_request("https://api.test", "k", &city_id, ReadingTime::Hour(HOUR)),
+        "https://api.test/data/2.5/history/city?id=4250542&type=hour&start=3600&cnt=1&appid=k&units=metric&lang=en",
+    );
+    assert_eq!(
+        ProviderKind::WeatherApi.build_request("https://api.test", "k", &point, ReadingTime::Current),
+        "https://api.test/v1/current.json?key=k&q=39.8017,-89.6437&lang=en",
+    );
+    assert_eq!(city_id.key(), "id:4250542");
+    assert_eq!(Location::City("  New   York ".into()).canonical(), Location::City("new york".into()));
+}
+
+#[test]
+fn openweathermap_reads_the_other_perils_from_the_same_response() {
+    let body = br#"{ "snow": { "1h": 3.5 }, "main": { "temp": -2.0, "humidity": 90 }, "wind": { "speed": 20.0 } }"#;
+    let observation = ProviderKind::OpenWeatherMap.parse_observation(ReadingTime::Current, body).unwrap();
+
+    assert_eq!(observation.metric(Metric::Rainfall), Some(Hundredths::ZERO));
+    #[cfg(feature = "cold-chain")]
+    assert_eq!(observation.metric(Metric::Temperature), Some(Hundredths(-200)));
+    #[cfg(feature = "wind")]
+    assert_eq!(observation.metric(Metric::WindSpeed), Some(Hundredths(7_200)));
+    #[cfg(feature = "snow")]
+    assert_eq!(observation.metric(Metric::Snowfall), Some(Hundredths(350)));
+}
+
+#[test]
+fn observations_keep_the_station_the_provider_reported() {
+    let body = br#"{ "id": 184745, "base": "stations", "coord": { "lat": -1.2833, "lon": 36.8167 }, "rain": { "1h": 1.0 } }"#;
+    let observation = ProviderKind::OpenWeatherMap.parse_observation(ReadingTime::Current, body).unwrap();
+    assert_eq!(observation.station, Station {
+        station_id:  Some("184745".into()),
+        coord:       Some(GeoPoint { lat_e6: -1_283_300, lon_e6: 36_816_700 }),
+        data_source: Some("stations".into()),
+    });
+
+    // WeatherAPI.com only reports the point it resolved the query to
+    let body = br#"{ "location": { "name": "Nairobi", "lat": -1.28, "lon": 36.82 },
+        "current": { "precip_mm": 0.0 } }"#;
+    let observation = ProviderKind::WeatherApi.parse_observation(ReadingTime::Current, body).unwrap();
+    assert_eq!(observation.station.coord, Some(GeoPoint { lat_e6: -1_280_000, lon_e6: 36_820_000 }));
+    assert_eq!(observation.station.station_id, None);
+
+    let body = br#"{ "rain": { "1h": 1.0 } }"#;
+    assert_eq!(ProviderKind::OpenWeatherMap.parse_observation(ReadingTime::Current, body).unwrap().station, Station::default());
+}
+
+#[test]
+fn weatherapi_reads_current_conditions() {
+    let body = br#"{ "location": { "name": "Nairobi" },
+        "current": { "temp_c": 19.0, "humidity": 88, "wind_kph": 11.2, "precip_mm": 6.1 } }"#;
+    let observation = ProviderKind::WeatherApi.parse_observation(ReadingTime::Current, body).unwrap();
+
+    assert_eq!(observation.rainfall_mm, Millimeters(610));
+    assert_eq!(observation.temperature_c, Some(19.0));
+    assert_eq!(observation.wind_speed_kmh, Some(11.2));
+}
+
+#[test]
+fn weatherapi_history_picks_the_requested_hour() {
+    let start = 1_718_000_000 - 1_718_000_000 % HOUR;
+    let body = format!(
+        r#"{{ "forecast": {{ "forecastday": [ {{ "hour": [
+            {{ "time_epoch": {}, "precip_mm": 0.0, "temp_c": 18.0 }},
+            {{ "time_epoch": {}, "precip_mm": 12.4, "temp_c": 17.5 }}
+        ] }} ] }} }}"#,
+        start,
+        start + HOUR,
+    );
+
+    let kind = ProviderKind::WeatherApi;
+    let at = start + HOUR + 1_200;
+    assert_eq!(kind.parse_observation(ReadingTime::Hour(at), body.as_bytes()).unwrap().rainfall_mm, Millimeters(1_240));
+    assert!(kind.parse_observation(ReadingTime::Hour(start + 5 * HOUR), body.as_bytes()).is_err());
+}
+
+#[test]
+fn providers_declare_their_perils() {
+    assert!(ProviderKind::OpenWeatherMap.supports(Metric::Rainfall));
+    assert!(ProviderKind::WeatherApi.supports(Metric::Rainfall));
+    #[cfg(feature = "air-quality")]
+    assert!(!ProviderKind::WeatherApi.supports(Metric::AirQuality));
+    #[cfg(feature = "snow")]
+    assert!(!ProviderKind::WeatherApi.supports(Metric::Snowfall));
+}
+
+#[test]
+fn a_response_that_ignored_the_metric_pin_is_refused() {
+    // 1h of rain and 293 K: the account answered in its Kelvin default
+    let kelvin = br#"{ "rain": { "1h": 12.0 }, "main": { "temp": 293.15 } }"#;
+    assert!(ProviderKind::OpenWeatherMap.parse_observation(ReadingTime::Current, kelvin).is_err());
+
+    let history = br#"{ "list": [ { "main": { "temp": 95.0 } } ] }"#;
+    assert!(ProviderKind::OpenWeatherMap.parse_observation(ReadingTime::Hour(HOUR), history).is_err());
+
+    let celsius = br#"{ "rain": { "1h": 12.0 }, "main": { "temp": 48.5 } }"#;
+    assert!(ProviderKind::OpenWeatherMap.parse_observation(ReadingTime::Current, celsius).is_ok());
+}
+
+#[test]
+fn weatherbit_queries_a_single_station() {
+    let station = Location::Station("KJFK".into());
+    let kind = ProviderKind::Weatherbit;
+    assert!(kind.queries_stations());
+    assert!(!ProviderKind::OpenWeatherMap.queries_stations());
+    assert_eq!(station.key(), "station:KJFK");
+
+    assert_eq!(
+        kind.build_request("https://api.test", "k", &station, ReadingTime::Current),
+        "https://api.test/v2.0/current?station=KJFK&key=k&units=M&lang=en",
+    );
+    // 2024-06-10T06:13:20Z asks for the hour from 06:00
+    assert_eq!(
+        kind.build_request("https://api.test", "k", &station, ReadingTime::Hour(1_718_000_000)),
+        "https://api.test/v2.0/history/hourly?station=KJFK&start_date=2024-06-10:06&end_date=2024-06-10:07&tz=utc&key=k&units=M&lang=en",
+    );
+}
+
+#[test]
+fn weatherbit_reads_the_station_it_answered_for() {
+    let body = br#"{ "data": [ { "station": "KJFK", "lat": 40.64, "lon": -73.78,
+        "precip": 3.5, "temp": 21.0, "rh": 90, "wind_spd": 5.0 } ], "count": 1 }"#;
+    let observation = ProviderKind::Weatherbit.parse_observation(ReadingTime::Current, body).unwrap();
+    assert_eq!(observation.rainfall_mm, Millimeters(350));
+    assert_eq!(observation.wind_speed_kmh, Some(18.0));
+    assert_eq!(observation.station.station_id, Some("KJFK".into()));
+    assert_eq!(observation.station.coord, Some(GeoPoint { lat_e6: 40_640_000, lon_e6: -73_780_000 }));
+
+    let start = 1_718_000_000 - 1_718_000_000 % HOUR;
+    let body = format!(r#"{{ "data": [ {{ "ts": {start}, "precip": null, "temp": 18.0 }} ] }}"#);
+    let observation = ProviderKind::Weatherbit.parse_observation(ReadingTime::Hour(start + 60), body.as_bytes()).unwrap();
+    assert_eq!(observation.rainfall_mm, Millimeters::ZERO);
+    assert!(ProviderKind::Weatherbit.parse_observation(ReadingTime::Hour(start + HOUR), body.as_bytes()).is_err());
+}
@@ -0,0 +1,47 @@
+// Typed quote errors, and the threshold factor on the base rate.
+
+use rialo_sdk::prelude::RialoError;
+use rialo_weather_insurance::money::Ralo;
+use rialo_weather_insurance::policy::PayoutSpec;
+use rialo_weather_insurance::quote::{threshold_factor_bps, QuoteError};
+
+#[test]
+fn premium_multiples_fail_with_typed_errors() {
+    let below_one = PayoutSpec::PremiumMultiple { premium: Ralo::whole(10), multiple_pct: 99 };
+    assert_eq!(below_one.resolve(500), Err(QuoteError::MultipleBelowOne));
+
+    // 10 RALO at 20× buys 200 RALO of cover, which costs 20 RALO at 10%
+    let underpriced = PayoutSpec::PremiumMultiple { premium: Ralo::whole(10), multiple_pct: 2_000 };
+    assert_eq!(underpriced.resolve(1_000), Err(QuoteError::PremiumBelowRate));
+
+    let overflow = PayoutSpec::PremiumMultiple { premium: Ralo(u64::MAX), multiple_pct: 200 };
+    assert_eq!(overflow.resolve(0), Err(QuoteError::MultipleOverflow));
+}
+
+#[test]
+fn absolute_payouts_price_at_the_rate() {
+    assert_eq!(PayoutSpec::Absolute(Ralo::whole(100)).resolve(500), Ok((Ralo::whole(100), Ralo::whole(5))));
+}
+
+#[test]
+fn floor_errors_carry_their_limit_into_the_contract_error() {
+    let error = QuoteError::PremiumBelowFloor { min: Ralo::whole(1) };
+    assert_eq!(error.message(), "Premium is below the minimum of 1 RALO.");
+
+    let codes = [QuoteError::PayoutBelowFloor { min: Ralo::ZERO }.code(), error.code()];
+    assert_ne!(codes[0], codes[1]);
+
+    let contract_error: RialoError = error.clone().into();
+    assert_eq!(contract_error.code, error.code());
+}
+
+#[test]
+fn rate_scales_inversely_with_the_threshold() {
+    assert_eq!(threshold_factor_bps(None, 80.0), 10_000);
+    assert_eq!(threshold_factor_bps(Some(20.0), 20.0), 10_000);
+    assert_eq!(threshold_factor_bps(Some(20.0), 40.0), 5_000);
+    assert_eq!(threshold_factor_bps(Some(20.0), 10.0), 20_000);
+    // Clamped to 0.25×–4×
+    assert_eq!(threshold_factor_bps(Some(20.0), 400.0), 2_500);
+    assert_eq!(threshold_factor_bps(Some(20.0), 1.0), 40_000);
+}
@@ -0,0 +1,43 @@
+// Hourly check limits: per-caller and global counts, and when they reset.
+
+use rialo_sdk::prelude::{Pubkey, RialoError};
+use rialo_weather_insurance::rate_limits::{CheckMeter, RateLimitExceeded, RateLimitScope, RateLimits, RATE_LIMIT_EXCEEDED_CODE};
+
+const HOUR: i64 = 60 * 60;
+const NOW: i64 = 1_700_000_000 - 1_700_000_000 % HOUR;
+
+#[test]
+fn each_caller_gets_its_own_allowance() {
+    let (alice, bob) = (Pubkey::new_from_array([1; 32]), Pubkey::new_from_array([2; 32]));
+    let limits = RateLimits { per_caller: Some(2), global: None };
+    let mut meter = CheckMeter::default();
+
+    assert_eq!(meter.admit(&limits, &alice, NOW), Ok(()));
+    assert_eq!(meter.admit(&limits, &alice, NOW + 60), Ok(()));
+    assert_eq!(
+        meter.admit(&limits, &alice, NOW + 120),
+        Err(RateLimitExceeded { scope: RateLimitScope::Caller, limit: 2, reset_at: NOW + HOUR }),
+    );
+    assert_eq!(meter.admit(&limits, &bob, NOW + 120), Ok(()));
+
+    // A refused check isn't counted, and the next hour starts afresh
+    assert_eq!(meter.total, 3);
+    assert_eq!(meter.admit(&limits, &alice, NOW + HOUR), Ok(()));
+    assert_eq!(meter.total, 1);
+}
+
+#[test]
+fn the_global_limit_covers_every_caller() {
+    let limits = RateLimits { per_caller: None, global: Some(2) };
+    let mut meter = CheckMeter::default();
+    for n in 0..2 {
+        assert_eq!(meter.admit(&limits, &Pubkey::new_from_array([n; 32]), NOW), Ok(()));
+    }
+
+    let error = meter.admit(&limits, &Pubkey::new_from_array([9; 32]), NOW + 1).unwrap_err();
+    assert_eq!(error.scope, RateLimitScope::Global);
+    assert_eq!(RialoError::from(error), RialoError::custom(RATE_LIMIT_EXCEEDED_CODE, error.message()));
+
+    // No limits set: nothing is refused
+    assert_eq!(meter.admit(&RateLimits::default(), &Pubkey::new_from_array([9; 32]), NOW + 2), Ok(()));
+}
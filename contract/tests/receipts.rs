@@ -0,0 +1,37 @@
+// Receipt commitments: anyone holding the reading can recompute a receipt's observation hash.
+
+use std::collections::BTreeSet;
+
+use rialo_sdk::crypto::sha256;
+use rialo_weather_insurance::millimeters::Hundredths;
+use rialo_weather_insurance::receipts::{observation_hash, SettlementKey};
+
+#[test]
+fn observation_hash_commits_to_policy_reading_and_time() {
+    let mut preimage = Vec::new();
+    preimage.extend_from_slice(&7u64.to_le_bytes());
+    preimage.extend_from_slice(&1_250i64.to_le_bytes());
+    preimage.extend_from_slice(&1_700_000_000i64.to_le_bytes());
+
+    assert_eq!(observation_hash(7, Hundredths(1_250), 1_700_000_000), sha256(&preimage));
+    // Provider readings that round to the same hundredth commit alike
+    assert_eq!(observation_hash(7, Hundredths::from_f64(12.5 + 1e-9), 1_700_000_000), sha256(&preimage));
+}
+
+#[test]
+fn observation_hash_changes_with_any_input() {
+    let base = observation_hash(7, Hundredths(1_250), 1_700_000_000);
+    assert_ne!(base, observation_hash(8, Hundredths(1_250), 1_700_000_000));
+    assert_ne!(base, observation_hash(7, Hundredths(1_260), 1_700_000_000));
+    assert_ne!(base, observation_hash(7, Hundredths(1_250), 1_700_003_600));
+}
+
+#[test]
+fn settlement_keys_tell_rounds_apart() {
+    let mut settled = BTreeSet::new();
+    assert!(settled.insert(SettlementKey { policy_id: 7, round_id: Some(41) }));
+    // The same round retried is the same settlement
+    assert!(!settled.insert(SettlementKey { policy_id: 7, round_id: Some(41) }));
+    assert!(settled.insert(SettlementKey { policy_id: 7, round_id: None }));
+    assert!(settled.insert(SettlementKey { policy_id: 8, round_id: Some(41) }));
+}
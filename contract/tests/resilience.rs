@@ -0,0 +1,49 @@
+// Classifying provider failures, retry timeouts and fallback endpoints.
+
+use rialo_weather_insurance::config::NetworkMode;
+use rialo_weather_insurance::money::Ralo;
+use rialo_weather_insurance::providers::ProviderKind;
+use rialo_weather_insurance::resilience::{timeout_ms, FetchError, FIRST_TIMEOUT_MS};
+use rialo_weather_insurance::underwriter::ProviderConfig;
+
+fn provider(fallback_url: Option<&str>) -> ProviderConfig {
+    ProviderConfig {
+        kind:             ProviderKind::OpenWeatherMap,
+        base_url:         "https://api.openweathermap.org".into(),
+        sandbox_base_url: "https://sandbox.test".into(),
+        fallback_url:     fallback_url.map(String::from),
+        api_key_secret:   String::new(),
+        cost_per_call:    Ralo(1_000),
+    }
+}
+
+#[test]
+fn statuses_are_classified_and_only_server_trouble_is_retried() {
+    assert_eq!(FetchError::from_status(200), None);
+    assert_eq!(FetchError::from_status(429), Some(FetchError::RateLimited));
+    assert_eq!(FetchError::from_status(503), Some(FetchError::BadStatus(503)));
+
+    assert!(FetchError::Timeout.is_transient());
+    assert!(FetchError::BadStatus(500).is_transient());
+    assert!(!FetchError::BadStatus(404).is_transient());
+    assert!(!FetchError::RateLimited.is_transient());
+    assert!(!FetchError::ParseError.is_transient());
+
+    assert_eq!(FetchError::RateLimited.status(), Some(429));
+    assert_eq!(FetchError::Timeout.status(), None);
+}
+
+#[test]
+fn each_retry_waits_twice_as_long() {
+    assert_eq!(timeout_ms(0), FIRST_TIMEOUT_MS);
+    assert_eq!(timeout_ms(2), 4 * FIRST_TIMEOUT_MS);
+}
+
+#[test]
+fn the_mirror_is_tried_after_the_primary_on_mainnet_only() {
+    let provider = provider(Some("https://mirror.test"));
+    assert_eq!(provider.endpoints_for(NetworkMode::MainNet), vec!["https://api.openweathermap.org", "https://mirror.test"]);
+    assert_eq!(provider.endpoints_for(NetworkMode::DevNet), vec!["https://sandbox.test"]);
+
+    assert_eq!(self::provider(None).endpoints_for(NetworkMode::MainNet), vec!["https://api.openweathermap.org"]);
+}
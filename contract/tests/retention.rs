@@ -0,0 +1,50 @@
+// Observation retention: archived check records verify against their hash.
+
+use rialo_sdk::crypto::sha256;
+use rialo_weather_insurance::evaluation::Evaluation;
+use rialo_weather_insurance::millimeters::Hundredths;
+use rialo_weather_insurance::money::Ralo;
+use rialo_weather_insurance::normalization::Station;
+use rialo_weather_insurance::observations::CheckRecord;
+use rialo_weather_insurance::policy::PolicyStatus;
+use rialo_weather_insurance::retention::{check_hash, is_closed, observation_bytes};
+
+fn check(reading: f64) -> CheckRecord {
+    CheckRecord {
+        policy_id:      7,
+        source:         "https://api.openweathermap.org".into(),
+        location:       "nairobi".into(),
+        station:        Station::default(),
+        response:       None,
+        reading:        Hundredths::from_f64(reading),
+        old_reading:    None,
+        observed_at:    3_600,
+        decision:       Evaluation::Triggered { paid: Ralo::whole(100) },
+        audit_selected: false,
+        audit:          None,
+    }
+}
+
+#[test]
+fn the_archived_payload_is_what_the_hash_was_taken_of() {
+    let record = check(64.0);
+    let payload = observation_bytes(&record);
+    assert_eq!(sha256(&payload), check_hash(&record));
+
+    // The payload reads back as the record it was archived from
+    let restored: CheckRecord = serde_json::from_slice(&payload).unwrap();
+    assert_eq!(check_hash(&restored), check_hash(&record));
+
+    // A doctored reading doesn't verify
+    assert_ne!(sha256(&observation_bytes(&check(12.0))), check_hash(&record));
+}
+
+#[test]
+fn only_policies_nothing_more_settles_on_are_closed() {
+    assert!(is_closed(PolicyStatus::PaidOut));
+    assert!(is_closed(PolicyStatus::Expired));
+    assert!(is_closed(PolicyStatus::Withdrawn));
+    assert!(!is_closed(PolicyStatus::Active));
+    assert!(!is_closed(PolicyStatus::PayoutDeferred));
+    assert!(!is_closed(PolicyStatus::PendingAttestation));
+}
@@ -0,0 +1,43 @@
+// Parsing the USGS instantaneous-values feed the river-level product reads.
+
+use rialo_weather_insurance::river::{gauge_height_url, parse_gauge_height_mm};
+
+fn feed(unit: &str, readings: &[&str]) -> String {
+    let values: Vec<String> = readings
+        .iter()
+        .map(|v| format!(r#"{{ "value": "{v}", "qualifiers": ["P"], "dateTime": "2024-09-27T12:00:00.000-04:00" }}"#))
+        .collect();
+    format!(
+        r#"{{ "value": {{ "timeSeries": [ {{
+            "sourceInfo": {{ "siteName": "FRENCH BROAD RIVER AT ASHEVILLE, NC" }},
+            "variable": {{ "unit": {{ "unitCode": "{unit}" }}, "noDataValue": -999999.0 }},
+            "values": [ {{ "value": [ {} ] }} ]
+        }} ] }} }}"#,
+        values.join(", ")
+    )
+}
+
+#[test]
+fn converts_latest_height_to_mm() {
+    let body = feed("ft", &["3.10", "24.67"]);
+    let level = parse_gauge_height_mm(body.as_bytes()).unwrap();
+    assert!((level - 7_519.416).abs() < 1e-6);
+
+    let body = feed("m", &["2.5"]);
+    assert!((parse_gauge_height_mm(body.as_bytes()).unwrap() - 2_500.0).abs() < 1e-9);
+}
+
+#[test]
+fn rejects_missing_readings() {
+    assert!(parse_gauge_height_mm(feed("ft", &["-999999"]).as_bytes()).is_err());
+    assert!(parse_gauge_height_mm(feed("ft", &[]).as_bytes()).is_err());
+    assert!(parse_gauge_height_mm(br#"{ "value": { "timeSeries": [] } }"#).is_err());
+}
+
+#[test]
+fn builds_gauge_height_query() {
+    assert_eq!(
+        gauge_height_url("https://waterservices.usgs.gov", "03451500"),
+        "https://waterservices.usgs.gov/nwis/iv/?format=json&sites=03451500&parameterCd=00065"
+    );
+}
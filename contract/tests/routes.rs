@@ -0,0 +1,50 @@
+// Route cover: which stop's reading decides a check, what a route costs, and the stops it may list.
+
+use rialo_weather_insurance::millimeters::Hundredths;
+use rialo_weather_insurance::money::Ralo;
+use rialo_weather_insurance::normalization::{Comparison, Location};
+use rialo_weather_insurance::routes::{check_stops, decisive_stop, route_premium, Aggregation, MAX_ROUTE_STOPS};
+
+fn city(name: &str) -> Location {
+    Location::City(name.into())
+}
+
+#[test]
+fn worst_of_settles_on_the_wettest_stop_and_best_of_on_the_driest() {
+    let readings = [Hundredths(1_200), Hundredths(3_100), Hundredths(450)];
+
+    assert_eq!(decisive_stop(Aggregation::WorstOf, Comparison::AtOrAbove, &readings), Some(1));
+    assert_eq!(decisive_stop(Aggregation::BestOf, Comparison::AtOrAbove, &readings), Some(2));
+    assert_eq!(decisive_stop(Aggregation::WorstOf, Comparison::AtOrAbove, &[]), None);
+}
+
+#[test]
+fn drought_routes_read_the_other_way_round() {
+    let readings = [Hundredths(1_200), Hundredths(3_100), Hundredths(450)];
+
+    assert_eq!(decisive_stop(Aggregation::WorstOf, Comparison::AtOrBelow, &readings), Some(2));
+    assert_eq!(decisive_stop(Aggregation::BestOf, Comparison::AtOrBelow, &readings), Some(1));
+}
+
+#[test]
+fn worst_of_costs_the_sum_up_to_the_payout_and_best_of_the_cheapest_stop() {
+    let premiums = [Ralo::whole(10), Ralo::whole(6), Ralo::whole(8)];
+
+    assert_eq!(route_premium(Aggregation::WorstOf, &premiums, Ralo::whole(100)), Ralo::whole(24));
+    assert_eq!(route_premium(Aggregation::WorstOf, &premiums, Ralo::whole(20)), Ralo::whole(20));
+    assert_eq!(route_premium(Aggregation::BestOf, &premiums, Ralo::whole(100)), Ralo::whole(6));
+}
+
+#[test]
+fn a_route_lists_distinct_stops_up_to_the_limit() {
+    let own = city("nairobi");
+
+    assert!(check_stops(&own, &[city("mombasa"), city("nakuru")]).is_ok());
+    assert!(check_stops(&own, &[]).is_err());
+    assert!(check_stops(&own, &[city("mombasa"), city("nairobi")]).is_err());
+    assert!(check_stops(&own, &[city("mombasa"), city("mombasa")]).is_err());
+
+    let too_many: Vec<Location> = (0..MAX_ROUTE_STOPS).map(|i| city(&format!("stop-{i}"))).collect();
+    assert!(check_stops(&own, &too_many).is_err());
+    assert!(check_stops(&own, &too_many[1..]).is_ok());
+}
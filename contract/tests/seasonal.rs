@@ -0,0 +1,35 @@
+// Calendar months and coverage-weighted seasonal factors.
+
+use rialo_weather_insurance::seasonal::{coverage_factor_bps, month_of, MonthlyFactors};
+
+const DAY: i64 = 24 * 60 * 60;
+
+// 2024-01-01T00:00:00Z and 2024-03-01T00:00:00Z
+const JAN_1_2024: i64 = 1_704_067_200;
+const MAR_1_2024: i64 = 1_709_251_200;
+
+// Dry Jan–Feb, long rains Mar–May, neutral the rest of the year
+const NAIROBI: MonthlyFactors = [5_000, 5_000, 20_000, 20_000, 20_000, 10_000, 10_000, 10_000, 10_000, 10_000, 10_000, 10_000];
+
+#[test]
+fn months_from_unix_time() {
+    assert_eq!(month_of(0), 0);                   // 1970-01-01
+    assert_eq!(month_of(JAN_1_2024), 0);
+    assert_eq!(month_of(MAR_1_2024 - 1), 1);      // 29 Feb 2024, leap year
+    assert_eq!(month_of(MAR_1_2024), 2);
+    assert_eq!(month_of(1_735_689_599), 11);      // 2024-12-31T23:59:59Z
+    assert_eq!(month_of(-1), 11);                 // 1969-12-31
+}
+
+#[test]
+fn cover_inside_one_month_takes_that_month() {
+    assert_eq!(coverage_factor_bps(&NAIROBI, JAN_1_2024, 10 * DAY), 5_000);
+    assert_eq!(coverage_factor_bps(&NAIROBI, MAR_1_2024, 30 * DAY), 20_000);
+}
+
+#[test]
+fn dry_season_quote_for_wet_season_cover_prices_the_wet_days() {
+    // Ten dry days in late February, then twenty wet days of March
+    let start = MAR_1_2024 - 10 * DAY;
+    assert_eq!(coverage_factor_bps(&NAIROBI, start, 30 * DAY), (10 * 5_000 + 20 * 20_000) / 30);
+}
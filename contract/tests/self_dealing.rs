@@ -0,0 +1,19 @@
+// Self-dealing control: which control keys a beneficiary can hold.
+
+use rialo_sdk::prelude::*;
+use rialo_weather_insurance::self_dealing::{conflict, ConflictedKey};
+
+fn key(byte: u8) -> Pubkey {
+    Pubkey::new_from_array([byte; 32])
+}
+
+#[test]
+fn beneficiaries_holding_a_control_key_conflict() {
+    let (admin, authority, co_signer) = (key(1), key(2), key(3));
+    let co_signers = [co_signer];
+
+    assert_eq!(conflict(&admin, &authority, &co_signers, &authority), Some(ConflictedKey::UnderwriterAuthority));
+    assert_eq!(conflict(&admin, &authority, &co_signers, &co_signer), Some(ConflictedKey::CoSigner));
+    assert_eq!(conflict(&admin, &authority, &co_signers, &admin), Some(ConflictedKey::Admin));
+    assert_eq!(conflict(&admin, &authority, &co_signers, &key(9)), None);
+}
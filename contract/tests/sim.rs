@@ -0,0 +1,54 @@
+// Simulated lifecycles: what a scenario pays over a weather series, day by day.
+
+use rialo_weather_insurance::curves::{PayoutCurve, PayoutTier};
+use rialo_weather_insurance::millimeters::Hundredths;
+use rialo_weather_insurance::money::Ralo;
+use rialo_weather_insurance::policy::PolicyStatus;
+use rialo_weather_insurance::sim::{run, Scenario, Series};
+
+fn scenario() -> Scenario {
+    Scenario::new(50.0, Ralo::whole(100), Ralo::whole(5))
+}
+
+#[test]
+fn dry_month_expires_without_paying() {
+    let timeline = run(&scenario(), &Series::Constant(10.0));
+    assert_eq!(timeline.days.len(), 30);
+    assert_eq!(timeline.total_paid, Ralo::ZERO);
+    assert_eq!(timeline.final_status, PolicyStatus::Expired);
+    assert_eq!(timeline.loss_ratio_bps(), 0);
+}
+
+#[test]
+fn flat_cover_pays_in_full_on_the_first_wet_day() {
+    let timeline = run(&scenario(), &Series::Spike { base: 5.0, peak: 60.0, day: 12 });
+    assert_eq!(timeline.days.len(), 13);
+    assert_eq!(timeline.total_paid, Ralo::whole(100));
+    assert_eq!(timeline.final_status, PolicyStatus::PaidOut);
+    assert_eq!(timeline.loss_ratio_bps(), 200_000);
+}
+
+#[test]
+fn graded_cover_tops_up_as_the_rain_worsens() {
+    let mut scenario = scenario();
+    scenario.curve = PayoutCurve::Tiers(vec![
+        PayoutTier { at: Hundredths(5_000), share_bps: 2_500 },
+        PayoutTier { at: Hundredths(8_000), share_bps: 10_000 },
+    ]);
+    scenario.copay_bps = 1_000;
+
+    let timeline = run(&scenario, &Series::Readings(vec![10.0, 55.0, 40.0, 90.0]));
+    let paid: Vec<Ralo> = timeline.triggers().map(|d| d.paid).collect();
+    assert_eq!(paid, vec![Ralo::whole(100).bps(2_250), Ralo::whole(100).bps(6_750)]);
+    assert_eq!(timeline.total_paid, Ralo::whole(90));
+    assert_eq!(timeline.final_status, PolicyStatus::PaidOut);
+    assert!(timeline.report().contains("PaidOut"));
+}
+
+#[test]
+fn noise_is_repeatable_by_seed() {
+    let series = Series::Noise { mean: 30.0, amplitude: 20.0, seed: 7 };
+    let readings: Vec<f64> = (0..30).map(|day| series.reading(day, 30)).collect();
+    assert!(readings.iter().all(|r| (10.0..50.0).contains(r)));
+    assert_eq!(run(&scenario(), &series), run(&scenario(), &series));
+}
@@ -0,0 +1,40 @@
+// Index smoothing: the rolling statistics, the warm-up and how late or
+// repeated readings move the window.
+
+use rialo_weather_insurance::smoothing::{SmoothedIndex, Smoothing};
+
+#[test]
+fn mean_and_median_read_a_full_window() {
+    let gusts = [10.0, 40.0, 13.0];
+    assert_eq!(Smoothing::RollingMean { samples: 3 }.apply(&gusts), 21.0);
+    assert_eq!(Smoothing::RollingMedian { samples: 3 }.apply(&gusts), 13.0);
+    assert_eq!(Smoothing::RollingMedian { samples: 4 }.apply(&[4.0, 1.0, 9.0, 3.0]), 3.5);
+}
+
+#[test]
+fn nothing_settles_until_the_window_fills_then_it_rolls() {
+    let mut index = SmoothedIndex::new(Smoothing::RollingMean { samples: 3 });
+    assert_eq!(index.record(60.0, 100), None);
+    assert_eq!(index.record(0.0, 200), None);
+    assert_eq!(index.record(30.0, 300), Some(30.0));
+
+    // The lone spike drops out of the window
+    assert_eq!(index.record(0.0, 400), Some(10.0));
+    assert_eq!(index.readings.len(), 3);
+    assert_eq!(index.readings.first(), Some(&(200, 0.0)));
+}
+
+#[test]
+fn late_readings_fill_in_and_stale_or_repeated_ones_add_nothing() {
+    let mut index = SmoothedIndex::new(Smoothing::RollingMedian { samples: 3 });
+    index.record(5.0, 100);
+    index.record(7.0, 300);
+
+    // A historical check fills in behind the newest reading
+    assert_eq!(index.record(50.0, 200), Some(7.0));
+    assert_eq!(index.readings, vec![(100, 5.0), (200, 50.0), (300, 7.0)]);
+
+    assert_eq!(index.record(90.0, 300), None);
+    assert_eq!(index.record(90.0, 50), None);
+    assert_eq!(index.value(), Some(7.0));
+}
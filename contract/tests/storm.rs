@@ -0,0 +1,39 @@
+// Parsing the NHC CurrentStorms.json feed the storm-track product reads.
+
+use rialo_weather_insurance::storm::{parse_active_storms, saffir_simpson};
+
+const FEED: &str = r#"{
+  "activeStorms": [
+    { "id": "al092024", "binNumber": "AT4", "name": "Helene", "classification": "HU",
+      "intensity": "120", "pressure": "938", "latitude": "27.5N", "longitude": "84.4W",
+      "latitudeNumeric": 27.5, "longitudeNumeric": -84.4 },
+    { "id": "al102024", "binNumber": "AT5", "name": "Isaac", "classification": "TS",
+      "intensity": "50", "pressure": "995", "latitude": "36.1N", "longitude": "47.0W",
+      "latitudeNumeric": 36.1, "longitudeNumeric": -47.0 }
+  ]
+}"#;
+
+#[test]
+fn reads_position_and_category_of_each_storm() {
+    let fixes = parse_active_storms(FEED.as_bytes()).unwrap();
+
+    assert_eq!(fixes.len(), 2);
+    assert_eq!(fixes[0].name, "Helene");
+    assert_eq!(fixes[0].category, 4);
+    assert_eq!((fixes[0].position.lat_e6, fixes[0].position.lon_e6), (27_500_000, -84_400_000));
+    assert_eq!(fixes[1].category, 0);   // tropical storm
+}
+
+#[test]
+fn quiet_basin_has_no_storms() {
+    assert!(parse_active_storms(br#"{ "activeStorms": [] }"#).unwrap().is_empty());
+}
+
+#[test]
+fn saffir_simpson_boundaries() {
+    assert_eq!(saffir_simpson(63), 0);
+    assert_eq!(saffir_simpson(64), 1);
+    assert_eq!(saffir_simpson(96), 3);
+    assert_eq!(saffir_simpson(136), 4);
+    assert_eq!(saffir_simpson(137), 5);
+}
@@ -0,0 +1,66 @@
+// Stress scenarios: which policies a catastrophe hits and what each one costs.
+
+use rialo_sdk::prelude::Pubkey;
+use rialo_weather_insurance::curves::{GradedCover, PayoutCurve, PayoutTier};
+use rialo_weather_insurance::geo::GeoPoint;
+use rialo_weather_insurance::millimeters::Hundredths;
+use rialo_weather_insurance::money::Ralo;
+use rialo_weather_insurance::normalization::Metric;
+use rialo_weather_insurance::policy::{Policy, PolicyStatus};
+use rialo_weather_insurance::stress::StressScenario;
+
+fn policy() -> Policy {
+    let mut policy = Policy::new(0, 0, Pubkey::default(), "Nairobi".into(), Hundredths::from_f64(50.0), Ralo::whole(100), Ralo::whole(5));
+    policy.record_premium(Ralo::whole(5), 0);
+    policy
+}
+
+fn flood(regions: &[&str]) -> StressScenario {
+    StressScenario {
+        name:          "100 mm over East Africa".into(),
+        peril:         Metric::Rainfall,
+        reading:       100.0,
+        regions:       regions.iter().map(|r| r.to_string()).collect(),
+        hit_unlocated: false,
+    }
+}
+
+#[test]
+fn cover_in_the_region_loses_what_it_still_owes() {
+    let mut policy = policy();
+    policy.insured_point = Some(GeoPoint::new(-1_286_389, 36_817_223).unwrap());   // Nairobi, geohash kzf0…
+    policy.copay_bps = 1_000;
+
+    assert_eq!(flood(&["kz"]).loss(&policy), Ralo::whole(90));
+    assert_eq!(flood(&["u0"]).loss(&policy), Ralo::ZERO);
+
+    policy.status = PolicyStatus::PaidOut;
+    assert_eq!(flood(&["kz"]).loss(&policy), Ralo::ZERO);
+}
+
+#[test]
+fn unlocated_cover_is_hit_only_when_asked() {
+    let policy = policy();
+    let mut scenario = flood(&["kz"]);
+    assert!(!scenario.hits(&policy));
+
+    scenario.hit_unlocated = true;
+    assert!(scenario.hits(&policy));
+    assert!(flood(&[]).hits(&policy));
+}
+
+#[test]
+fn graded_cover_loses_its_curve_share_and_dry_scenarios_nothing() {
+    let mut policy = policy();
+    policy.graded = Some(GradedCover::new(PayoutCurve::Tiers(vec![
+        PayoutTier { at: Hundredths(5_000),  share_bps: 2_500 },
+        PayoutTier { at: Hundredths(15_000), share_bps: 10_000 },
+    ])));
+    let scenario = flood(&[]);
+    assert_eq!(scenario.share_bps(&policy), 2_500);
+    assert_eq!(scenario.loss(&policy), Ralo::whole(25));
+
+    let drizzle = StressScenario { reading: 20.0, ..scenario };
+    assert_eq!(drizzle.share_bps(&policy), 0);
+    assert_eq!(drizzle.loss(&policy), Ralo::ZERO);
+}
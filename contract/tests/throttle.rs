@@ -0,0 +1,48 @@
+// Check cooldown: when a policy's next live check is allowed, and the error that says so.
+
+use rialo_sdk::prelude::{Pubkey, RialoError};
+use rialo_weather_insurance::millimeters::Hundredths;
+use rialo_weather_insurance::money::Ralo;
+use rialo_weather_insurance::policy::Policy;
+use rialo_weather_insurance::throttle::{CheckThrottled, CHECK_THROTTLED_CODE, DEFAULT_MIN_CHECK_SECS};
+
+const NOW: i64 = 1_700_000_000;
+
+fn policy() -> Policy {
+    Policy::new(0, 0, Pubkey::default(), "nairobi".into(), Hundredths::from_f64(20.0), Ralo::whole(100), Ralo::whole(5))
+}
+
+#[test]
+fn a_policy_never_checked_can_be_checked_now() {
+    let policy = policy();
+    assert_eq!(policy.min_check_secs, DEFAULT_MIN_CHECK_SECS);
+    assert_eq!(policy.next_check_at(NOW), None);
+    assert_eq!(policy.check_throttle(NOW), Ok(()));
+}
+
+#[test]
+fn checks_wait_out_the_interval() {
+    let mut policy = policy();
+    policy.min_check_secs = 600;
+    policy.last_checked = Some(NOW);
+
+    assert_eq!(policy.check_throttle(NOW + 599), Err(CheckThrottled { next_check_at: NOW + 600 }));
+    assert_eq!(policy.check_throttle(NOW + 600), Ok(()));
+}
+
+#[test]
+fn a_zero_interval_never_throttles() {
+    let mut policy = policy();
+    policy.min_check_secs = 0;
+    policy.last_checked = Some(NOW);
+    assert_eq!(policy.check_throttle(NOW), Ok(()));
+}
+
+#[test]
+fn throttled_checks_tell_keepers_when_to_come_back() {
+    let throttled = CheckThrottled { next_check_at: NOW + 600 };
+    assert!(throttled.message().contains(&(NOW + 600).to_string()));
+
+    let error: RialoError = throttled.into();
+    assert_eq!(error.code, CHECK_THROTTLED_CODE);
+}
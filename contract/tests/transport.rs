@@ -0,0 +1,134 @@
+// Provider calls through the mock transport: a fixture scenario answers
+// in-process, and the contract's own retry, parse and trigger logic decides.
+
+use rialo_sdk::crypto::sha256;
+use rialo_sdk::prelude::Pubkey;
+use rialo_weather_fixture::{block_on, Scenario};
+use rialo_weather_insurance::millimeters::Hundredths;
+use rialo_weather_insurance::money::Ralo;
+use rialo_weather_insurance::normalization::{Comparison, Location};
+use rialo_weather_insurance::oracle::CallBudget;
+use rialo_weather_insurance::policy::{Policy, PolicyStatus};
+use rialo_weather_insurance::providers::{ProviderKind, ReadingTime, WeatherProvider};
+use rialo_weather_insurance::resilience::{fetch_with_retry, FetchError, FetchOutcome, MAX_ATTEMPTS};
+use rialo_weather_insurance::transport::mock;
+
+const CITY: &str = "Nairobi";
+const PRIMARY: &str = "https://primary.test";
+const MIRROR: &str = "https://mirror.test";
+
+fn url(base: &str) -> String {
+    ProviderKind::OpenWeatherMap.build_request(base, "test-key", &Location::City(CITY.into()), ReadingTime::Current)
+}
+
+fn active_policy(threshold_mm: f64) -> Policy {
+    let mut policy = Policy::new(0, 0, Pubkey::default(), CITY.to_string(), Hundredths::from_f64(threshold_mm), Ralo::whole(100), Ralo::whole(10));
+    policy.coverage_secs = 30 * 24 * 60 * 60;
+    assert!(policy.record_premium(policy.premium_amount, 0));
+    policy
+}
+
+// Serve `scenario` as it stands on `day`, from the primary only
+fn serve(scenario: Scenario, day: usize) {
+    mock::install(move |url| {
+        url.starts_with(PRIMARY).then(|| {
+            let (status, body) = scenario.respond(day, url);
+            (status, body.into_bytes())
+        })
+    });
+}
+
+fn fetch(bases: &[&str]) -> FetchOutcome {
+    let urls: Vec<String> = bases.iter().map(|base| url(base)).collect();
+    block_on(fetch_with_retry(&urls, &[], &mut CallBudget::per_instruction(), 0))
+}
+
+// One live check as the contract runs it: fetch, parse, apply
+fn check(policy: &mut Policy) -> Result<bool, FetchError> {
+    let response = fetch(&[PRIMARY]).result?;
+    let observation = ProviderKind::OpenWeatherMap
+        .parse_observation(ReadingTime::Current, response.body())
+        .map_err(|_| FetchError::ParseError)?;
+    Ok(policy.apply_reading(observation.rainfall_mm.into(), 0))
+}
+
+#[test]
+fn heavy_rain_triggers_the_payout() {
+    let mut policy = active_policy(20.0);
+    serve(Scenario::named("storm-day-5").unwrap(), 5);
+
+    assert_eq!(check(&mut policy), Ok(true));
+    assert_eq!(policy.status, PolicyStatus::PaidOut);
+    assert_eq!(mock::requests(), vec![url(PRIMARY)]);
+}
+
+#[test]
+fn rain_below_the_threshold_does_not_trigger() {
+    let mut policy = active_policy(5.0);
+    serve(Scenario::named("drizzle").unwrap(), 2);
+
+    assert_eq!(check(&mut policy), Ok(false));
+    assert_eq!(policy.status, PolicyStatus::Active);
+}
+
+#[test]
+fn a_missing_rain_block_reads_as_dry() {
+    let mut policy = active_policy(0.1);
+    serve(Scenario::named("dry-week").unwrap(), 3);
+
+    assert_eq!(check(&mut policy), Ok(false));
+
+    // ...which is exactly what drought cover pays on
+    let mut drought = active_policy(0.0);
+    drought.comparison = Comparison::AtOrBelow;
+    assert_eq!(check(&mut drought), Ok(true));
+}
+
+#[test]
+fn malformed_json_settles_nothing() {
+    let mut policy = active_policy(20.0);
+    serve(Scenario::named("garbled").unwrap(), 2);
+
+    assert_eq!(check(&mut policy), Err(FetchError::ParseError));
+    assert_eq!(policy.status, PolicyStatus::Active);
+    assert_eq!(policy.paid_out, Ralo::ZERO);
+}
+
+#[test]
+fn the_digest_matches_the_body_the_reading_came_from() {
+    serve(Scenario::named("storm-day-5").unwrap(), 5);
+    let (_, body) = Scenario::named("storm-day-5").unwrap().respond(5, &url(PRIMARY));
+
+    let response = fetch(&[PRIMARY]).result.unwrap();
+    let digest = response.digest();
+
+    assert_eq!(digest.sha256, sha256(body.as_bytes()));
+    assert_eq!(digest.len as usize, body.len());
+    assert_eq!(digest.status, 200);
+}
+
+#[test]
+fn an_outage_is_retried_then_fails_over_to_the_mirror() {
+    let scenario = Scenario::named("outage").unwrap();
+    mock::install(move |url| {
+        let day = if url.starts_with(PRIMARY) { 3 } else { 4 };
+        let (status, body) = scenario.respond(day, url);
+        Some((status, body.into_bytes()))
+    });
+
+    let outcome = fetch(&[PRIMARY, MIRROR]);
+
+    assert_eq!(outcome.attempts, MAX_ATTEMPTS + 1);
+    assert_eq!(outcome.status(), Some(200));
+    assert_eq!(mock::requests().last(), Some(&url(MIRROR)));
+}
+
+#[test]
+fn an_unanswered_call_times_out() {
+    serve(Scenario::named("dry-week").unwrap(), 1);
+
+    let outcome = fetch(&[MIRROR]);
+
+    assert_eq!(outcome.result, Err(FetchError::Timeout));
+    assert_eq!(outcome.attempts, MAX_ATTEMPTS);
+}
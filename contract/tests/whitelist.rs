@@ -0,0 +1,23 @@
+// Whitelist enforcement: which roles are checked, and for whom.
+
+use std::collections::BTreeSet;
+
+use rialo_weather_insurance::whitelist::{is_allowed, Role};
+
+#[test]
+fn unenforced_roles_are_open_to_anyone() {
+    let enforced = BTreeSet::from([Role::Underwriter]);
+    assert!(is_allowed(&enforced, None, Role::Policyholder));
+    assert!(is_allowed(&BTreeSet::new(), None, Role::Arbiter));
+    assert!(!is_allowed(&enforced, None, Role::Underwriter));
+}
+
+#[test]
+fn an_enforced_role_needs_that_approval() {
+    let enforced = BTreeSet::from([Role::Policyholder, Role::Underwriter]);
+    let approved = BTreeSet::from([Role::Policyholder]);
+
+    assert!(is_allowed(&enforced, Some(&approved), Role::Policyholder));
+    // Approval for one role doesn't carry over to another
+    assert!(!is_allowed(&enforced, Some(&approved), Role::Underwriter));
+}
@@ -0,0 +1,36 @@
+// Standalone fixture service for DevNet runs.
+//
+//   weather-fixture <scenario> [port]
+//
+// Point an underwriter's sandbox base URL at the printed address and
+// move the scenario clock with GET /_fixture/day/<n> or /_fixture/advance.
+
+use std::process::exit;
+use std::thread;
+
+use rialo_weather_fixture::{FixtureServer, Scenario};
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let name = args.next().unwrap_or_else(|| "storm-day-5".to_string());
+    let port = args.next().unwrap_or_else(|| "8080".to_string());
+
+    let Some(scenario) = Scenario::named(&name) else {
+        eprintln!("unknown scenario `{}` (try dry-week, storm-day-5, drizzle, outage, garbled)", name);
+        exit(2);
+    };
+
+    let server = match FixtureServer::bind(&format!("0.0.0.0:{}", port), scenario) {
+        Ok(server) => server,
+        Err(e) => {
+            eprintln!("failed to bind port {}: {}", port, e);
+            exit(1);
+        }
+    };
+
+    println!("serving `{}` at {} (day {})", name, server.base_url(), server.day());
+
+    loop {
+        thread::park();
+    }
+}
@@ -0,0 +1,236 @@
+// ============================================================
+//  Deterministic weather fixture service
+//
+//  Serves scripted weather scenarios ("dry week then storm on
+//  day 5") over plain HTTP in the same JSON shape OpenWeatherMap
+//  uses for /data/2.5/weather, so the contract's parsing and
+//  trigger logic can be exercised end-to-end without the live API.
+//
+//  The scenario clock only moves when told to:
+//    • in-process  → FixtureServer::set_day / advance
+//    • over HTTP   → GET /_fixture/day/<n>, GET /_fixture/advance
+//
+//  Scenario::respond answers a URL without the server, for a
+//  contract built with `mock-http` (transport.rs) to call in-process.
+// ============================================================
+
+use std::future::Future;
+use std::io::{self, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::pin::pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Waker};
+use std::thread;
+
+// ── Scenario scripting ───────────────────────────────────────
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DayWeather {
+    Dry,          // response carries no `rain` block at all
+    Rain(f64),    // `rain.1h` in mm
+    Outage,       // provider answers 503
+    Malformed,    // provider answers 200 with a body cut off mid-JSON
+}
+
+#[derive(Clone, Debug)]
+pub struct Scenario {
+    pub name: String,
+    pub days: Vec<DayWeather>,   // day 1 is days[0]; days past the end are dry
+}
+
+impl Scenario {
+    pub fn new(name: &str) -> Self {
+        Scenario { name: name.to_string(), days: Vec::new() }
+    }
+
+    pub fn dry(mut self, days: usize) -> Self {
+        self.days.extend(std::iter::repeat_n(DayWeather::Dry, days));
+        self
+    }
+
+    pub fn rain(mut self, mm: f64) -> Self {
+        self.days.push(DayWeather::Rain(mm));
+        self
+    }
+
+    pub fn outage(mut self) -> Self {
+        self.days.push(DayWeather::Outage);
+        self
+    }
+
+    pub fn malformed(mut self) -> Self {
+        self.days.push(DayWeather::Malformed);
+        self
+    }
+
+    // Weather on a 1-based scenario day
+    pub fn day(&self, day: usize) -> DayWeather {
+        day.checked_sub(1)
+            .and_then(|i| self.days.get(i))
+            .copied()
+            .unwrap_or(DayWeather::Dry)
+    }
+
+    // Status and body the server would send for `url` on a scenario day
+    pub fn respond(&self, day: usize, url: &str) -> (u16, String) {
+        let target = match url.split_once("://") {
+            Some((_, rest)) => &rest[rest.find('/').unwrap_or(rest.len())..],
+            None            => url,
+        };
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+        if path == "/data/2.5/weather" {
+            weather_body(query_param(query, "q").unwrap_or("unknown"), self.day(day))
+        } else {
+            not_found()
+        }
+    }
+
+    // Built-in scenarios the standalone server can be started with
+    pub fn named(name: &str) -> Option<Scenario> {
+        match name {
+            "dry-week"    => Some(Scenario::new(name).dry(7)),
+            "storm-day-5" => Some(Scenario::new(name).dry(4).rain(42.0).dry(2)),
+            "drizzle"     => Some(Scenario::new(name).rain(0.4).rain(1.2).rain(0.8).dry(4)),
+            "outage"      => Some(Scenario::new(name).dry(2).outage().rain(35.0).dry(3)),
+            "garbled"     => Some(Scenario::new(name).dry(1).malformed().rain(30.0).dry(4)),
+            _ => None,
+        }
+    }
+}
+
+// ── HTTP server ──────────────────────────────────────────────
+pub struct FixtureServer {
+    addr: SocketAddr,
+    day:  Arc<AtomicUsize>,
+}
+
+impl FixtureServer {
+    // Bind on an ephemeral localhost port and serve in the background
+    pub fn start(scenario: Scenario) -> io::Result<Self> {
+        Self::bind("127.0.0.1:0", scenario)
+    }
+
+    pub fn bind(addr: &str, scenario: Scenario) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        let addr = listener.local_addr()?;
+        let day = Arc::new(AtomicUsize::new(1));
+
+        let clock = Arc::clone(&day);
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let _ = handle(stream, &scenario, &clock);
+            }
+        });
+
+        Ok(FixtureServer { addr, day })
+    }
+
+    // Base URL to configure as an underwriter's sandbox endpoint
+    pub fn base_url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    pub fn day(&self) -> usize {
+        self.day.load(Ordering::SeqCst)
+    }
+
+    pub fn set_day(&self, day: usize) {
+        self.day.store(day, Ordering::SeqCst);
+    }
+
+    pub fn advance(&self) {
+        self.day.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+fn handle(mut stream: TcpStream, scenario: &Scenario, day: &AtomicUsize) -> io::Result<()> {
+    // Read up to the end of the request headers; bodies are ignored
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 1024];
+    while !buf.windows(4).any(|w| w == b"\r\n\r\n") && buf.len() < 16 * 1024 {
+        let n = stream.read(&mut chunk)?;
+        if n == 0 {
+            break;
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    }
+    let request = String::from_utf8_lossy(&buf);
+    let target = request.split_whitespace().nth(1).unwrap_or("/");
+    let path = target.split_once('?').map_or(target, |(path, _)| path);
+
+    let (status, body) = if path == "/_fixture/advance" {
+        let next = day.fetch_add(1, Ordering::SeqCst) + 1;
+        (200, format!("{{\"day\":{}}}", next))
+    } else if let Some(n) = path.strip_prefix("/_fixture/day/").and_then(|n| n.parse().ok()) {
+        day.store(n, Ordering::SeqCst);
+        (200, format!("{{\"day\":{}}}", n))
+    } else {
+        scenario.respond(day.load(Ordering::SeqCst), target)
+    };
+
+    let reason = match status { 200 => "OK", 404 => "Not Found", _ => "Service Unavailable" };
+    let response = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status, reason, body.len(), body,
+    );
+    stream.write_all(response.as_bytes())
+}
+
+fn weather_body(city: &str, weather: DayWeather) -> (u16, String) {
+    match weather {
+        DayWeather::Dry       => (200, format!("{{\"name\":\"{}\"}}", city)),
+        DayWeather::Rain(mm)  => (200, format!("{{\"name\":\"{}\",\"rain\":{{\"1h\":{}}}}}", city, mm)),
+        DayWeather::Outage    => (503, "{\"cod\":503,\"message\":\"service unavailable\"}".to_string()),
+        DayWeather::Malformed => (200, format!("{{\"name\":\"{}\",\"rain\":{{\"1h\":", city)),
+    }
+}
+
+fn not_found() -> (u16, String) {
+    (404, "{\"cod\":\"404\",\"message\":\"not found\"}".to_string())
+}
+
+fn query_param<'a>(query: &'a str, key: &str) -> Option<&'a str> {
+    query.split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(k, _)| *k == key)
+        .map(|(_, v)| v)
+}
+
+// ── Minimal blocking client for tests ────────────────────────
+// Returns the status code and body of a GET against `url`
+pub fn http_get(url: &str) -> io::Result<(u16, Vec<u8>)> {
+    let rest = url.strip_prefix("http://")
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "only http:// is supported"))?;
+    let (host, path) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+    let path = if path.is_empty() { "/" } else { path };
+
+    let mut stream = TcpStream::connect(host)?;
+    let request = format!("GET {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n\r\n", path, host);
+    stream.write_all(request.as_bytes())?;
+
+    let mut raw = Vec::new();
+    stream.read_to_end(&mut raw)?;
+
+    let split = raw.windows(4).position(|w| w == b"\r\n\r\n")
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "truncated response"))?;
+    let status = String::from_utf8_lossy(&raw[..split])
+        .split_whitespace()
+        .nth(1)
+        .and_then(|s| s.parse().ok())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "bad status line"))?;
+
+    Ok((status, raw[split + 4..].to_vec()))
+}
+
+// Run a future to completion on this thread. Enough for the contract's
+// calls against a mock transport, which never wait on anything.
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let mut cx = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+        thread::yield_now();
+    }
+}